@@ -0,0 +1,64 @@
+// src/graph/d2/animation.rs
+// 动画 (D2Plotter::animate)：每帧按归一化时间 t ∈ [0, 1) 重建整个对象列表，到时长后从头循环
+// 适合相平面轨迹、傅里叶级数逼近、椭圆变圆等演示
+
+use std::time::Instant;
+
+use super::common::GeoObj;
+
+pub struct Animation {
+    duration: f64,
+    update: Box<dyn Fn(f64) -> Vec<GeoObj>>,
+    // 第一帧时开始计时 (窗口创建前不计)
+    start: Option<Instant>,
+}
+
+impl Animation {
+    pub fn new(duration_secs: f64, update: impl Fn(f64) -> Vec<GeoObj> + 'static) -> Self {
+        Self { duration: duration_secs, update: Box::new(update), start: None }
+    }
+
+    // now 时刻的对象列表
+    pub fn frame(&mut self, now: Instant) -> Vec<GeoObj> {
+        let start = *self.start.get_or_insert(now);
+        (self.update)(phase(now.duration_since(start).as_secs_f64(), self.duration))
+    }
+}
+
+// 已过时间 -> 循环的归一化时间；时长非法 (非正或非有限) 时停在 t = 0
+pub fn phase(elapsed: f64, duration: f64) -> f64 {
+    if duration <= 0.0 || !duration.is_finite() {
+        return 0.0;
+    }
+    (elapsed / duration).fract()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_phase_wraps() {
+        assert_eq!(phase(0.0, 2.0), 0.0);
+        assert_eq!(phase(0.5, 2.0), 0.25);
+        assert_eq!(phase(2.0, 2.0), 0.0);
+        assert_eq!(phase(5.0, 2.0), 0.5);
+        assert_eq!(phase(1.0, 0.0), 0.0);
+        assert_eq!(phase(1.0, f64::NAN), 0.0);
+    }
+
+    #[test]
+    fn test_frame_passes_normalized_time() {
+        // 每帧的对象数编码了 t，便于检查传入的时间
+        let mut anim = Animation::new(4.0, |t| {
+            (0..(t * 8.0).round() as usize).map(|_| GeoObj::new_explicit(|x| x, [1.0; 4], 1.0)).collect()
+        });
+        let t0 = Instant::now();
+        assert!(anim.frame(t0).is_empty());
+        assert_eq!(anim.frame(t0 + Duration::from_secs(1)).len(), 2);
+        assert_eq!(anim.frame(t0 + Duration::from_secs(3)).len(), 6);
+        // 超过时长后循环
+        assert_eq!(anim.frame(t0 + Duration::from_secs(5)).len(), 2);
+    }
+}
@@ -0,0 +1,286 @@
+// src/d2/arena.rs
+// 单个大 GPU 缓冲上的区间分配 (各图层的顶点数据共用一个 Vertex Buffer)
+#![allow(dead_code)]
+
+use std::ops::Range;
+
+// 向上取整到 align 的倍数 (align > 0)
+pub fn align_up(x: u64, align: u64) -> u64 {
+    x.div_ceil(align) * align
+}
+
+// ====================== CPU 端分配器 ======================
+
+// 首次适配 (first-fit) 区间分配器，只做簿记，不持有 GPU 资源
+// 所有区间边界都是 alignment 的倍数；释放时与相邻空闲区合并
+pub struct ArenaAllocator {
+    capacity: u64,
+    alignment: u64,
+    free: Vec<Range<u64>>, // 按起点升序，两两不相邻 (已合并)
+    live: Vec<Range<u64>>, // 已分配区间
+}
+
+impl ArenaAllocator {
+    pub fn new(capacity: u64, alignment: u64) -> Self {
+        assert!(alignment > 0, "alignment must be positive");
+        let capacity = align_up(capacity, alignment);
+        let mut free = Vec::new();
+        if capacity > 0 {
+            free.push(0..capacity);
+        }
+        Self { capacity, alignment, free, live: Vec::new() }
+    }
+
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    pub fn alignment(&self) -> u64 {
+        self.alignment
+    }
+
+    // 已分配字节数 (含对齐填充)
+    pub fn used(&self) -> u64 {
+        self.live.iter().map(|r| r.end - r.start).sum()
+    }
+
+    pub fn free_ranges(&self) -> &[Range<u64>] {
+        &self.free
+    }
+
+    // 分配 size 字节 (向上对齐)，空间不足返回 None
+    pub fn alloc(&mut self, size: u64) -> Option<Range<u64>> {
+        let size = align_up(size.max(1), self.alignment);
+        let i = self.free.iter().position(|r| r.end - r.start >= size)?;
+        let start = self.free[i].start;
+        if self.free[i].end - start == size {
+            self.free.remove(i);
+        } else {
+            self.free[i].start += size;
+        }
+        let range = start..start + size;
+        self.live.push(range.clone());
+        self.debug_assert_disjoint();
+        Some(range)
+    }
+
+    // 释放之前 alloc 得到的区间，并与前后空闲区合并
+    pub fn free(&mut self, range: Range<u64>) {
+        let Some(k) = self.live.iter().position(|r| *r == range) else {
+            debug_assert!(false, "freeing unknown range {:?}", range);
+            return;
+        };
+        self.live.swap_remove(k);
+
+        let i = self.free.partition_point(|r| r.start < range.start);
+        let merge_prev = i > 0 && self.free[i - 1].end == range.start;
+        let merge_next = i < self.free.len() && self.free[i].start == range.end;
+        match (merge_prev, merge_next) {
+            (true, true) => {
+                self.free[i - 1].end = self.free[i].end;
+                self.free.remove(i);
+            }
+            (true, false) => self.free[i - 1].end = range.end,
+            (false, true) => self.free[i].start = range.start,
+            (false, false) => self.free.insert(i, range),
+        }
+    }
+
+    // 扩容到 new_capacity (已分配区间的位置不变)，新增部分并入末尾空闲区
+    pub fn grow(&mut self, new_capacity: u64) {
+        let new_capacity = align_up(new_capacity, self.alignment);
+        assert!(new_capacity >= self.capacity, "arena cannot shrink");
+        if new_capacity == self.capacity {
+            return;
+        }
+        match self.free.last_mut() {
+            Some(last) if last.end == self.capacity => last.end = new_capacity,
+            _ => self.free.push(self.capacity..new_capacity),
+        }
+        self.capacity = new_capacity;
+    }
+
+    // 分配，不足时容量按倍增扩大直到放得下；返回 (区间, 是否发生了扩容)
+    pub fn alloc_or_grow(&mut self, size: u64) -> (Range<u64>, bool) {
+        let old_capacity = self.capacity;
+        loop {
+            if let Some(range) = self.alloc(size) {
+                return (range, self.capacity != old_capacity);
+            }
+            self.grow((self.capacity * 2).max(self.alignment));
+        }
+    }
+
+    // 调试检查：任意两个已分配区间不重叠，且都在容量之内
+    fn debug_assert_disjoint(&self) {
+        if cfg!(debug_assertions) {
+            let mut live = self.live.clone();
+            live.sort_by_key(|r| r.start);
+            for w in live.windows(2) {
+                assert!(w[0].end <= w[1].start, "overlapping live ranges {:?} and {:?}", w[0], w[1]);
+            }
+            if let Some(last) = live.last() {
+                assert!(last.end <= self.capacity);
+            }
+        }
+    }
+}
+
+// ====================== GPU 顶点缓冲 ======================
+
+// 一个大 Vertex Buffer + 分配器；扩容时新建缓冲并把旧内容整体拷贝过去，已有区间偏移不变
+pub struct VertexArena {
+    pub buffer: wgpu::Buffer,
+    alloc: ArenaAllocator,
+}
+
+impl VertexArena {
+    const USAGE: wgpu::BufferUsages = wgpu::BufferUsages::VERTEX
+        .union(wgpu::BufferUsages::COPY_DST)
+        .union(wgpu::BufferUsages::COPY_SRC);
+
+    pub fn new(device: &wgpu::Device, capacity: u64, alignment: u64) -> Self {
+        // 写入与顶点偏移都要求 4 字节对齐
+        let alloc = ArenaAllocator::new(capacity, align_up(alignment, wgpu::COPY_BUFFER_ALIGNMENT));
+        let buffer = Self::create_buffer(device, alloc.capacity());
+        Self { buffer, alloc }
+    }
+
+    fn create_buffer(device: &wgpu::Device, size: u64) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Layer Vertex Arena"),
+            size,
+            usage: Self::USAGE,
+            mapped_at_creation: false,
+        })
+    }
+
+    pub fn alloc(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, size: u64) -> Range<u64> {
+        let old_capacity = self.alloc.capacity();
+        let (range, grown) = self.alloc.alloc_or_grow(size);
+        if grown {
+            // 之前排队的 write_buffer 会在这次 submit 的拷贝之前执行，旧数据完整搬迁
+            let buffer = Self::create_buffer(device, self.alloc.capacity());
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("Arena Grow") });
+            encoder.copy_buffer_to_buffer(&self.buffer, 0, &buffer, 0, old_capacity);
+            queue.submit(std::iter::once(encoder.finish()));
+            self.buffer = buffer;
+        }
+        range
+    }
+
+    pub fn free(&mut self, range: Range<u64>) {
+        self.alloc.free(range);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 空闲区列表转成 (start, end) 便于比较
+    fn spans(a: &ArenaAllocator) -> Vec<(u64, u64)> {
+        a.free_ranges().iter().map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn test_alloc_and_alignment() {
+        let mut a = ArenaAllocator::new(1000, 8);
+        assert_eq!(a.capacity(), 1000);
+        let r1 = a.alloc(10).unwrap();
+        let r2 = a.alloc(8).unwrap();
+        let r3 = a.alloc(1).unwrap();
+        assert_eq!(r1, 0..16);
+        assert_eq!(r2, 16..24);
+        assert_eq!(r3, 24..32);
+        assert_eq!(a.used(), 32);
+        assert!(a.alloc(2000).is_none());
+
+        // 容量本身也对齐
+        assert_eq!(ArenaAllocator::new(250, 256).capacity(), 256);
+        assert_eq!(align_up(0, 256), 0);
+        assert_eq!(align_up(257, 256), 512);
+    }
+
+    #[test]
+    fn test_free_and_coalesce() {
+        let mut a = ArenaAllocator::new(64, 8);
+        let r: Vec<_> = (0..4).map(|_| a.alloc(16).unwrap()).collect();
+        assert!(spans(&a).is_empty());
+
+        // 不相邻的两个空洞
+        a.free(r[0].clone());
+        a.free(r[2].clone());
+        assert_eq!(spans(&a), vec![(0, 16), (32, 48)]);
+        assert!(a.alloc(32).is_none());
+
+        // 释放中间块：三段合并为一段
+        a.free(r[1].clone());
+        assert_eq!(spans(&a), vec![(0, 48)]);
+        assert_eq!(a.alloc(32).unwrap(), 0..32);
+
+        a.free(0..32);
+        a.free(r[3].clone());
+        assert_eq!(spans(&a), vec![(0, 64)]);
+        assert_eq!(a.used(), 0);
+    }
+
+    #[test]
+    fn test_first_fit_reuses_hole() {
+        let mut a = ArenaAllocator::new(96, 8);
+        let r1 = a.alloc(32).unwrap();
+        let _r2 = a.alloc(32).unwrap();
+        a.free(r1);
+        assert_eq!(a.alloc(24).unwrap(), 0..24);
+        assert_eq!(a.alloc(8).unwrap(), 24..32);
+        assert_eq!(a.alloc(16).unwrap(), 64..80);
+    }
+
+    #[test]
+    fn test_growth() {
+        let mut a = ArenaAllocator::new(64, 8);
+        let r1 = a.alloc(48).unwrap();
+        let (r2, grown) = a.alloc_or_grow(100);
+        assert!(grown);
+        // 倍增：64 -> 128 不够 (末尾空闲 16 + 64 = 80)，-> 256
+        assert_eq!(a.capacity(), 256);
+        // 原有区间不动，新区间紧接在后 (末尾空闲区与扩出部分合并)
+        assert_eq!(r1, 0..48);
+        assert_eq!(r2, 48..152);
+        assert_eq!(spans(&a), vec![(152, 256)]);
+
+        let (_, grown) = a.alloc_or_grow(8);
+        assert!(!grown);
+
+        // 零容量起步也能扩
+        let mut z = ArenaAllocator::new(0, 8);
+        let (r, grown) = z.alloc_or_grow(20);
+        assert!(grown);
+        assert_eq!(r, 0..24);
+    }
+
+    #[test]
+    fn test_no_overlap_under_churn() {
+        let mut a = ArenaAllocator::new(256, 8);
+        let mut live: Vec<Range<u64>> = Vec::new();
+        for step in 0..200u64 {
+            let size = (step * 37) % 90 + 1;
+            if step % 3 == 2 && !live.is_empty() {
+                let r = live.remove((step as usize * 7) % live.len());
+                a.free(r);
+            }
+            let (r, _) = a.alloc_or_grow(size);
+            assert_eq!(r.start % 8, 0);
+            live.push(r);
+        }
+        live.sort_by_key(|r| r.start);
+        for w in live.windows(2) {
+            assert!(w[0].end <= w[1].start);
+        }
+        // 全部释放后合并回一整段
+        for r in live {
+            a.free(r);
+        }
+        assert_eq!(spans(&a), vec![(0, a.capacity())]);
+    }
+}
@@ -1,6 +1,20 @@
 // src/common.rs
 use bytemuck::{Pod, Zeroable};
 
+use super::gpu_field::GpuField;
+use super::gradient::Coloring;
+use super::heatmap::{ColorMap, HeatmapStyle};
+use super::scatter::{MarkerShape, MarkerStyle};
+use super::series::ChannelId;
+use super::watch::RpnSource;
+use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
+use crate::math_forest::geometry::d2::spline::b_spline::BSpline;
+use crate::math_forest::geometry::d2::spline::bezier::CubicBezier;
+use crate::math_forest::geometry::d2::conic::conic::Conic;
+use crate::math_forest::geometry::d2::conic::wipkyy::Wipkyy;
+use crate::math_forest::geometry::d2::linear::line::Line;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+
 // 统一使用这个顶点结构
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
@@ -8,22 +22,122 @@ pub struct Vertex {
     pub position: [f32; 2],
 }
 
+// 带颜色的顶点 (逐点着色的隐函数)：位置 + 颜色交错存放，一个实例缓冲
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+pub struct ColoredVertex {
+    pub position: [f32; 2],
+    pub color: [f32; 4],
+}
+
+// 当前视口信息 (由 update_sim 每次重算时传入)
+#[allow(dead_code)]
+#[derive(Copy, Clone, Debug)]
+pub struct ViewInfo {
+    pub x_range: (f64, f64),
+    pub y_range: (f64, f64),
+    pub zoom: f64,
+    pub pixels: (u32, u32), // 屏幕宽高 (物理像素)
+    pub scale_factor: f64,  // 窗口缩放比 (HiDPI 下物理像素 / 逻辑点)
+}
+
+impl ViewInfo {
+    // 一个像素对应的世界长度 (视口高度 = 4 / zoom)，可作为细节层级的参考
+    pub fn pixel_size(&self) -> f64 {
+        4.0 / (self.zoom * self.pixels.1.max(1) as f64)
+    }
+
+    // 线宽按逻辑点给出 (GeoObj::width)，挤出时换算为物理像素
+    pub fn line_width_px(&self, logical: f32) -> f32 {
+        logical * self.scale_factor as f32
+    }
+
+    // 视口四角经逆变换后的 x 范围 (变换后的显函数按此取参数范围)；不可逆时取原 x 范围
+    pub fn x_range_under(&self, transform: &Matrix3x3) -> (f64, f64) {
+        let Some(inv) = transform.inverse() else { return self.x_range };
+        let (x0, x1) = self.x_range;
+        let (y0, y1) = self.y_range;
+        let xs = [(x0, y0), (x1, y0), (x0, y1), (x1, y1)]
+            .map(|(x, y)| inv.transform_point2(Vec2::new(x, y)).x);
+        (
+            xs.iter().copied().fold(f64::INFINITY, f64::min),
+            xs.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        )
+    }
+}
+
+type CurveFn = Box<dyn Fn(f64) -> (f64, f64) + Sync + Send>;
+type ViewRangeFn = Box<dyn Fn(&ViewInfo) -> (f64, f64) + Sync + Send>;
+
 // 几何类型
 pub enum GeoType {
     // 隐函数 f(x, y) = 0
     Implicit(Box<dyn Fn(f64, f64) -> f64 + Sync + Send>),
+    // 逐点着色的隐函数：第二个闭包把求解得到的点 (世界坐标) 映射为颜色
+    ImplicitColored(
+        Box<dyn Fn(f64, f64) -> f64 + Sync + Send>,
+        Box<dyn Fn(f64, f64) -> [f32; 4] + Sync + Send>,
+    ),
+    // 同一函数的多条等值线 f(x, y) = level (各层共用一次网格求值)
+    LevelSet(Box<dyn Fn(f64, f64) -> f64 + Sync + Send>, Vec<f64>),
+    // 标量场热力图：整个 f(x, y) 按色表着色，画在网格之下 (见 heatmap.rs)
+    Heatmap(Box<dyn Fn(f64, f64) -> f64 + Sync + Send>, ColorMap),
+    // 带细节提示的隐函数 f(x, y, detail_hint) = 0，detail_hint 为当前一个像素的世界长度
+    ImplicitDetailed(Box<dyn Fn(f64, f64, f64) -> f64 + Sync + Send>),
     // 参数方程：存储函数、t范围
     Parametric(Box<dyn Fn(f64) -> (f64, f64) + Sync + Send>, (f64, f64)),
-    // 显函数 y = f(x)
-    Explicit(Box<dyn Fn(f64) -> f64 + Sync + Send>),
+    // 参数方程：t 范围随视口变化
+    ParametricDynamic(CurveFn, ViewRangeFn),
+    // 显函数 y = f(x)：存储函数、定义域 (闭区间，端点精确采样)
+    Explicit(Box<dyn Fn(f64) -> f64 + Sync + Send>, (f64, f64)),
+    // 时间序列通道 (D2Plotter::create_channel)：采样由其他线程追加，增量绘制
+    Series(ChannelId),
+    // 散点：静态的世界坐标，每个点画成固定像素大小的标记 (见 scatter.rs)
+    Points(Vec<Vec2>),
     // 几何对象
     Geometry,
+    // 退化区域 (空集 / 全平面)：没有曲线可画，不进入任何求解器
+    Wipkyy(Wipkyy),
+}
+
+// 对象的解析形式：求交点时优先用闭式解，而不是在闭包上数值求根
+#[derive(Clone, Copy, Debug)]
+pub enum Analytic {
+    Conic(Conic),
+    Line(Line),
+}
+
+impl Analytic {
+    // 施加仿射变换 (与 GeoObj::transform 一致：先求原曲线，再整体摆放)
+    pub fn transformed(&self, m: &Matrix3x3) -> Self {
+        match self {
+            Analytic::Conic(c) => Analytic::Conic(c.transform(m)),
+            Analytic::Line(l) => Analytic::Line(Line::new(m.transform_point2(l.p), m.transform_vector2(l.v))),
+        }
+    }
 }
 
 pub struct GeoObj {
     pub geo_type: GeoType,
-    pub color: [f32; 4],
+    // 解析形式 (圆锥曲线 / 直线构造器会填写)，闭包对象为 None
+    pub analytic: Option<Analytic>,
+    // 着色：单色，或按参数 / 速度渐变 (只对参数方程通道生效，其余对象用 base_color)
+    pub coloring: Coloring,
     pub width: f32,
+    // 仿射变换 (默认单位阵)：先求解原曲线，再把结果摆到变换后的位置
+    pub transform: Matrix3x3,
+    // 参数方程断裂检测阈值 (视口高度的倍数)，None 时取 parametric::JUMP_THRESHOLD_FACTOR
+    pub jump_threshold: Option<f32>,
+    // 散点的形状与逐点颜色 (只对 Points 生效)
+    pub marker: MarkerStyle,
+    // 隐函数的 GPU 求值程序 (由 RPN 翻译而来)；None 时走 CPU 求解器
+    pub gpu_field: Option<GpuField>,
+    // 热力图的归一化区间与求值分辨率 (只对 Heatmap 生效)
+    pub heatmap: HeatmapStyle,
+    // 对象的 RPN 来源 (表达式编译而来的对象)；会话保存只记录有来源的对象，闭包对象无法序列化
+    pub source: Option<RpnSource>,
+    // 需要重新求解 (视图未变时只重算 dirty 的对象)
+    pub(crate) dirty: bool,
 }
 
 impl GeoObj {
@@ -32,8 +146,94 @@ impl GeoObj {
     where F: Fn(f64, f64) -> f64 + Sync + Send + 'static{
         Self {
             geo_type: GeoType::Implicit(Box::new(f)),
-            color,
-            width
+            coloring: Coloring::Flat(color),
+            width,
+            analytic: None,
+            transform: Matrix3x3::IDENTITY,
+            jump_threshold: None,
+            marker: MarkerStyle::default(),
+            gpu_field: None,
+            heatmap: HeatmapStyle::default(),
+            source: None,
+            dirty: true,
+        }
+    }
+
+    // 多条等值线作为一个对象：如 x² + y² 在 levels = [1, 4, 9] 处的三个同心圆
+    pub fn new_level_curves<F>(f: F, levels: &[f64], color: [f32; 4], width: f32) -> Self
+    where F: Fn(f64, f64) -> f64 + Sync + Send + 'static {
+        Self {
+            geo_type: GeoType::LevelSet(Box::new(f), levels.to_vec()),
+            coloring: Coloring::Flat(color),
+            width,
+            analytic: None,
+            transform: Matrix3x3::IDENTITY,
+            jump_threshold: None,
+            marker: MarkerStyle::default(),
+            gpu_field: None,
+            heatmap: HeatmapStyle::default(),
+            source: None,
+            dirty: true,
+        }
+    }
+
+    // 热力图：f 在整个视口上的取值经 colormap 着色 (默认按可见范围的最小 / 最大值归一化)
+    // 等值线另加一个隐函数对象叠在上面，例如 x² + y² - 1 的热力图配上它的零等值线
+    pub fn new_heatmap<F>(f: F, colormap: ColorMap) -> Self
+    where F: Fn(f64, f64) -> f64 + Sync + Send + 'static {
+        Self {
+            geo_type: GeoType::Heatmap(Box::new(f), colormap),
+            coloring: Coloring::Flat([1.0; 4]),
+            width: 0.0,
+            analytic: None,
+            transform: Matrix3x3::IDENTITY,
+            jump_threshold: None,
+            marker: MarkerStyle::default(),
+            gpu_field: None,
+            heatmap: HeatmapStyle::default(),
+            source: None,
+            dirty: true,
+        }
+    }
+
+    // 逐点着色的隐函数：color_fn 在隐函数求解器给出的每个点上求值 (世界坐标，已施加 transform)
+    // 例如按到原点的距离或逃逸时间给 Mandelbrot 集边界上色
+    pub fn new_implicit_colored<F, C>(f: F, color_fn: C, width: f32) -> Self
+    where
+        F: Fn(f64, f64) -> f64 + Sync + Send + 'static,
+        C: Fn(f64, f64) -> [f32; 4] + Sync + Send + 'static,
+    {
+        Self {
+            geo_type: GeoType::ImplicitColored(Box::new(f), Box::new(color_fn)),
+            coloring: Coloring::Flat([1.0; 4]),
+            width,
+            analytic: None,
+            transform: Matrix3x3::IDENTITY,
+            jump_threshold: None,
+            marker: MarkerStyle::default(),
+            gpu_field: None,
+            heatmap: HeatmapStyle::default(),
+            source: None,
+            dirty: true,
+        }
+    }
+
+    // 视口相关的隐函数：第三个参数为 detail_hint (一个像素的世界长度)
+    // 用于分形等需要随缩放调整细节的函数
+    pub fn new_implicit_detailed<F>(f: F, color: [f32; 4], width: f32) -> Self
+    where F: Fn(f64, f64, f64) -> f64 + Sync + Send + 'static{
+        Self {
+            geo_type: GeoType::ImplicitDetailed(Box::new(f)),
+            coloring: Coloring::Flat(color),
+            width,
+            analytic: None,
+            transform: Matrix3x3::IDENTITY,
+            jump_threshold: None,
+            marker: MarkerStyle::default(),
+            gpu_field: None,
+            heatmap: HeatmapStyle::default(),
+            source: None,
+            dirty: true,
         }
     }
 
@@ -42,19 +242,216 @@ impl GeoObj {
     where F: Fn(f64) -> (f64, f64) + Sync + Send + 'static{
         Self {
             geo_type: GeoType::Parametric(Box::new(f), t_range),
+            coloring: Coloring::Flat(color),
+            width,
+            analytic: None,
+            transform: Matrix3x3::IDENTITY,
+            jump_threshold: None,
+            marker: MarkerStyle::default(),
+            gpu_field: None,
+            heatmap: HeatmapStyle::default(),
+            source: None,
+            dirty: true,
+        }
+    }
+
+    // 视口相关的参数方程：t 范围由 t_range(view) 每次重算时给出
+    // 例如 (t, 1/t) 可用 |v| v.x_range 让曲线始终铺满可见区域
+    pub fn new_parametric_dynamic<F, R>(f: F, t_range: R, color: [f32; 4], width: f32) -> Self
+    where
+        F: Fn(f64) -> (f64, f64) + Sync + Send + 'static,
+        R: Fn(&ViewInfo) -> (f64, f64) + Sync + Send + 'static,
+    {
+        Self {
+            geo_type: GeoType::ParametricDynamic(Box::new(f), Box::new(t_range)),
+            coloring: Coloring::Flat(color),
+            width,
+            analytic: None,
+            transform: Matrix3x3::IDENTITY,
+            jump_threshold: None,
+            marker: MarkerStyle::default(),
+            gpu_field: None,
+            heatmap: HeatmapStyle::default(),
+            source: None,
+            dirty: true,
+        }
+    }
+
+    // 三次贝塞尔曲线：走参数方程通道，t ∈ [0, 1]
+    pub fn new_bezier(curve: CubicBezier, color: [f32; 4], width: f32) -> Self {
+        Self::new_parametric(
+            move |t| { let p = curve.point_at(t); (p.x, p.y) },
+            (0.0, 1.0),
+            color,
+            width,
+        )
+    }
+
+    // B 样条曲线：走参数方程通道，t 取样条定义域
+    pub fn new_b_spline(curve: BSpline, color: [f32; 4], width: f32) -> Self {
+        let t_range = curve.domain();
+        Self::new_parametric(
+            move |t| { let p = curve.point_at(t); (p.x, p.y) },
+            t_range,
             color,
-            width
+            width,
+        )
+    }
+
+    // 一般圆锥曲线：虚圆锥曲线等退化情形直接成为 Wipkyy，不走隐函数求解
+    pub fn new_conic(conic: Conic, color: [f32; 4], width: f32) -> Self {
+        if let Some(w) = conic.to_wipkyy() {
+            return Self {
+                geo_type: GeoType::Wipkyy(w),
+                coloring: Coloring::Flat(color),
+                width,
+                analytic: None,
+                transform: Matrix3x3::IDENTITY,
+                jump_threshold: None,
+                marker: MarkerStyle::default(),
+                gpu_field: None,
+                heatmap: HeatmapStyle::default(),
+                source: None,
+                dirty: true,
+            };
+        }
+        // 归一化系数求值，使隐函数求解的阈值与方程整体缩放无关
+        Self {
+            analytic: Some(Analytic::Conic(conic)),
+            ..Self::new_implicit(move |x, y| conic.eval_normalized(Vec2::new(x, y)), color, width)
+        }
+    }
+
+    // 直线：参数范围取视口四角在直线上的投影范围，始终贯穿可见区域
+    pub fn new_line(line: Line, color: [f32; 4], width: f32) -> Self {
+        let t_range = move |view: &ViewInfo| {
+            let (x0, x1) = view.x_range;
+            let (y0, y1) = view.y_range;
+            [(x0, y0), (x1, y0), (x0, y1), (x1, y1)]
+                .map(|(x, y)| line.t_of_project(Vec2::new(x, y)))
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &t| (lo.min(t), hi.max(t)))
+        };
+        Self {
+            analytic: Some(Analytic::Line(line)),
+            ..Self::new_parametric_dynamic(move |t| { let p = line.index_point(t); (p.x, p.y) }, t_range, color, width)
         }
     }
 
     // 显函数构造器
     pub fn new_explicit<F>(f: F, color: [f32; 4], width: f32) -> Self
     where F: Fn(f64) -> f64 + Sync + Send + 'static
+    {
+        Self::new_explicit_on(f, (f64::NEG_INFINITY, f64::INFINITY), color, width)
+    }
+
+    // 限定定义域的显函数构造器 (分段函数、sqrt / ln 等自然定义域)
+    // domain 端点会被精确采样；若 f 在端点处为 NaN (开区间)，求解器会二分逼近边界
+    pub fn new_explicit_on<F>(f: F, domain: (f64, f64), color: [f32; 4], width: f32) -> Self
+    where F: Fn(f64) -> f64 + Sync + Send + 'static
     {
         Self {
-            geo_type: GeoType::Explicit(Box::new(f)),
-            color,
-            width
+            geo_type: GeoType::Explicit(Box::new(f), domain),
+            coloring: Coloring::Flat(color),
+            width,
+            analytic: None,
+            transform: Matrix3x3::IDENTITY,
+            jump_threshold: None,
+            marker: MarkerStyle::default(),
+            gpu_field: None,
+            heatmap: HeatmapStyle::default(),
+            source: None,
+            dirty: true,
+        }
+    }
+
+    // 时间序列：把通道 ch 的采样 (t, v) 依次连成折线，只支持单色
+    pub fn new_series(ch: ChannelId, color: [f32; 4], width: f32) -> Self {
+        Self {
+            geo_type: GeoType::Series(ch),
+            coloring: Coloring::Flat(color),
+            width,
+            analytic: None,
+            transform: Matrix3x3::IDENTITY,
+            jump_threshold: None,
+            marker: MarkerStyle::default(),
+            gpu_field: None,
+            heatmap: HeatmapStyle::default(),
+            source: None,
+            dirty: true,
         }
     }
-}
\ No newline at end of file
+
+    // 散点：size_px 为标记直径 (逻辑点，与线宽一样按窗口缩放比换算)，不随缩放变化
+    pub fn new_points(points: Vec<Vec2>, color: [f32; 4], size_px: f32) -> Self {
+        Self {
+            geo_type: GeoType::Points(points),
+            coloring: Coloring::Flat(color),
+            width: size_px,
+            analytic: None,
+            transform: Matrix3x3::IDENTITY,
+            jump_threshold: None,
+            marker: MarkerStyle::default(),
+            gpu_field: None,
+            heatmap: HeatmapStyle::default(),
+            source: None,
+            dirty: true,
+        }
+    }
+
+    // 设置着色方式 (构造时链式调用)，例如 .with_coloring(Coloring::ByParam(Gradient::rainbow()))
+    pub fn with_coloring(mut self, coloring: Coloring) -> Self {
+        self.coloring = coloring;
+        self
+    }
+
+    // 设置仿射变换 (构造时链式调用)，例如 GeoObj::new_implicit(..).with_transform(Matrix3x3::from_rotation(0.5))
+    pub fn with_transform(mut self, transform: Matrix3x3) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    // 设置参数方程的断裂检测阈值 (视口高度的倍数)：调小可切开更小的跳跃
+    pub fn with_jump_threshold(mut self, factor: f32) -> Self {
+        self.jump_threshold = Some(factor);
+        self
+    }
+
+    // 设置散点的标记形状
+    pub fn with_marker_shape(mut self, shape: MarkerShape) -> Self {
+        self.marker.shape = shape;
+        self
+    }
+
+    // 逐点颜色 (与点按下标对应)，覆盖对象颜色
+    pub fn with_point_colors(mut self, colors: Vec<[f32; 4]>) -> Self {
+        self.marker.colors = Some(colors);
+        self
+    }
+
+    // 附加 GPU 求值程序 (只对 Implicit 生效)：按屏幕像素求值，线宽恒定
+    pub fn with_gpu_field(mut self, field: GpuField) -> Self {
+        self.gpu_field = Some(field);
+        self
+    }
+
+    // 热力图的固定归一化区间 (多帧 / 多个热力图之间颜色可比)
+    pub fn with_heatmap_range(mut self, lo: f64, hi: f64) -> Self {
+        self.heatmap.range = Some((lo, hi));
+        self
+    }
+
+    // 热力图的求值分辨率 (相对屏幕，每个方向；默认 heatmap::DEFAULT_RESOLUTION)
+    // 纹理每边不超过设备上限，再高的分辨率也只到上限为止
+    pub fn with_heatmap_resolution(mut self, resolution: f32) -> Self {
+        assert!(resolution.is_finite() && resolution >= 0.0, "heatmap resolution must be finite and non-negative, got {}", resolution);
+        self.heatmap.resolution = resolution;
+        self
+    }
+
+    // 记录对象的 RPN 来源 (会话保存时据此重建对象)
+    pub fn with_source(mut self, source: RpnSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
@@ -0,0 +1,120 @@
+// src/graph/d2/conformal.rs
+// 复变函数的共形网格：源矩形中的直角网格线 x = 常数 / y = 常数 经 w = f(z) 映射后的像
+// 每条网格线是一个参数方程对象，断开 (极点、分支割线) 交给参数方程求解器的 NaN / 断点检测
+// f 共形的地方，两族像曲线仍然正交
+
+use std::sync::Arc;
+
+use super::common::GeoObj;
+use crate::math_forest::algebra::complex::complex::Complex;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+
+pub type ComplexFn = fn(Complex) -> Complex;
+
+// 常用的复变函数 (演示用)
+pub const PRESETS: [(&str, ComplexFn); 4] = [
+    ("z^2", |z| z * z),
+    ("1/z", Complex::reciprocal),
+    ("exp(z)", Complex::exp),
+    ("ln(z)", Complex::ln),
+];
+
+// [min, max] 内间距为 spacing 的网格坐标 (对齐到 spacing 的整数倍，与背景网格一致)
+pub fn grid_coords(min: f64, max: f64, spacing: f64) -> Vec<f64> {
+    if spacing.is_nan() || spacing <= 0.0 || !min.is_finite() || !max.is_finite() || max < min {
+        return Vec::new();
+    }
+    // 浮点误差不应丢掉恰好落在边界上的线
+    let eps = spacing * 1e-9;
+    let first = ((min - eps) / spacing).ceil() as i64;
+    let last = ((max + eps) / spacing).floor() as i64;
+    (first..=last).map(|k| k as f64 * spacing).collect()
+}
+
+// 复数值 -> 平面点；非有限值 (极点) 统一为 NaN，由求解器断开折线
+fn to_point(w: Complex) -> (f64, f64) {
+    if w.re.is_finite() && w.im.is_finite() { (w.re, w.im) } else { (f64::NAN, f64::NAN) }
+}
+
+impl GeoObj {
+    // source_rect: 源矩形的 (左下角, 右上角)；colors: [竖线 x = 常数 的颜色, 横线 y = 常数 的颜色]
+    // 每条网格线展开为一个参数方程对象，参数为线上的另一个坐标
+    pub fn new_conformal_grid<F>(f: F, source_rect: (Vec2, Vec2), spacing: f64, colors: [[f32; 4]; 2], width: f32) -> Vec<GeoObj>
+    where
+        F: Fn(Complex) -> Complex + Sync + Send + 'static,
+    {
+        let f = Arc::new(f);
+        let (min, max) = source_rect;
+        let mut objects = Vec::new();
+        for x in grid_coords(min.x, max.x, spacing) {
+            let f = f.clone();
+            objects.push(GeoObj::new_parametric(move |t| to_point(f(Complex::new(x, t))), (min.y, max.y), colors[0], width));
+        }
+        for y in grid_coords(min.y, max.y, spacing) {
+            let f = f.clone();
+            objects.push(GeoObj::new_parametric(move |t| to_point(f(Complex::new(t, y))), (min.x, max.x), colors[1], width));
+        }
+        objects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::d2::common::GeoType;
+    use crate::graph::d2::parametric::{ParametricSolver, JUMP_THRESHOLD_FACTOR};
+
+    const RECT: (Vec2, Vec2) = (Vec2 { x: -1.0, y: -2.0 }, Vec2 { x: 2.0, y: 2.0 });
+    const COLORS: [[f32; 4]; 2] = [[1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0]];
+
+    type Curve<'a> = &'a (dyn Fn(f64) -> (f64, f64) + Sync + Send);
+
+    fn curve(obj: &GeoObj) -> (Curve<'_>, (f64, f64)) {
+        let GeoType::Parametric(f, range) = &obj.geo_type else { panic!("expected a parametric grid line") };
+        (f.as_ref(), *range)
+    }
+
+    #[test]
+    fn test_grid_coords() {
+        assert_eq!(grid_coords(-1.0, 2.0, 1.0), vec![-1.0, 0.0, 1.0, 2.0]);
+        assert_eq!(grid_coords(-0.3, 0.9, 0.5), vec![0.0, 0.5]);
+        assert!(grid_coords(0.0, 1.0, 0.0).is_empty());
+        assert!(grid_coords(1.0, 0.0, 0.5).is_empty());
+    }
+
+    #[test]
+    fn test_z_squared_maps_gridline_to_parabola() {
+        // x = 1 (第 3 条竖线)：w = (1 + it)² = 1 - t² + 2it，像满足 u = 1 - v² / 4
+        let objects = GeoObj::new_conformal_grid(|z| z * z, RECT, 1.0, COLORS, 2.0);
+        assert_eq!(objects.len(), 4 + 5);
+        let (f, range) = curve(&objects[2]);
+        assert_eq!(range, (-2.0, 2.0));
+        for i in 0..=40 {
+            let t = -2.0 + i as f64 * 0.1;
+            let (u, v) = f(t);
+            assert!((u - (1.0 - v * v / 4.0)).abs() < 1e-12, "t = {}: ({}, {})", t, u, v);
+        }
+        assert_eq!(objects[2].coloring.base_color(), COLORS[0]);
+
+        // y = 1 (横线)：w = (t + i)² = t² - 1 + 2it，像满足 u = v² / 4 - 1
+        let (f, _) = curve(&objects[4 + 3]);
+        for t in [-1.0, -0.25, 0.5, 2.0] {
+            let (u, v) = f(t);
+            assert!((u - (v * v / 4.0 - 1.0)).abs() < 1e-12);
+        }
+        assert_eq!(objects[7].coloring.base_color(), COLORS[1]);
+    }
+
+    #[test]
+    fn test_pole_breaks_gridline() {
+        // 1/z 的竖线 x = 0 穿过极点 z = 0：像为虚轴，在极点处断成两段
+        let objects = GeoObj::new_conformal_grid(Complex::reciprocal, RECT, 1.0, COLORS, 2.0);
+        let (f, range) = curve(&objects[1]);
+        assert!(f(0.0).0.is_nan());
+        let lines = ParametricSolver::new().polylines(&f, range, 1.0, JUMP_THRESHOLD_FACTOR);
+        assert_eq!(lines.len(), 2);
+        for p in lines.iter().flatten() {
+            assert!(p.0.abs() < 1e-12);
+        }
+    }
+}
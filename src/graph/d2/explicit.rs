@@ -2,21 +2,35 @@
 use rayon::prelude::*;
 use crate::graph::d2::common::Vertex;
 
-// 采样密度
+// 采样密度：每个物理像素的采样数 (screen_w 为物理像素，HiDPI 下采样随之加密)
 const SAMPLING_DENSITY: f64 = 1.0;
 // 渐近线检测阈值：如果相邻两点 Y 差值超过“屏幕高度”的多少倍，则断开
 // 10.0 是一个经验值，既能过滤掉 tan(x)，又不会误伤只是比较陡峭的函数
 const ASYMPTOTE_THRESHOLD_FACTOR: f64 = 10.0;
 
+// 定义域边界二分细化的精度：像素宽度的几分之一
+const BOUNDARY_REFINE_FRACTION: f64 = 0.125;
+// 二分最大迭代次数 (2^-48 已远小于 f64 可分辨的像素宽度)
+const BOUNDARY_REFINE_MAX_ITER: usize = 48;
+
+// 自适应采样：屏幕斜率 (每像素宽度上升的像素数) 超过此值的段视为陡峭段，需要加密
+const STEEP_SLOPE: f64 = 2.0;
+// 陡峭段的最大局部加密倍数 (相对 SAMPLING_DENSITY)
+const MAX_LOCAL_DENSITY: usize = 4;
+
 pub struct ExplicitSolver {}
 
 impl ExplicitSolver {
     pub fn new() -> Self { Self {} }
 
+    /// domain: 函数定义域 (闭区间)，采样范围为视口与定义域的交集
+    /// width_px: 线宽 (物理像素，调用方已按窗口缩放比从逻辑点换算)
+    #[allow(clippy::too_many_arguments)]
     pub fn solve<F>(
         &self,
         f: &F,
         x_range: (f64, f64),
+        domain: (f64, f64),
         width_px: f32,
         zoom: f32,
         screen_w: u32,
@@ -25,27 +39,116 @@ impl ExplicitSolver {
     where
         F: Fn(f64) -> f64 + Sync + Send,
     {
-        let (x_min, x_max) = x_range;
-        let x_len = x_max - x_min;
+        let lines = self.polylines(f, x_range, domain, zoom, screen_w);
+        extrude(&lines, width_px, zoom, screen_h)
+    }
+
+    /// 自适应版本的 solve：平坦处按 1× 密度采样，陡峭段 (接近竖直) 局部加密到至多 4×
+    /// 让 tan(x)、x^(1/3) 在渐近线 / 拐点附近更平滑，平坦区域的采样点与 solve 完全相同
+    #[allow(clippy::too_many_arguments)]
+    pub fn solve_adaptive<F>(
+        &self,
+        f: &F,
+        x_range: (f64, f64),
+        domain: (f64, f64),
+        width_px: f32,
+        zoom: f32,
+        screen_w: u32,
+        screen_h: f32
+    ) -> Vec<Vertex>
+    where
+        F: Fn(f64) -> f64 + Sync + Send,
+    {
+        let lines = self.polylines_adaptive(f, x_range, domain, zoom, screen_w);
+        extrude(&lines, width_px, zoom, screen_h)
+    }
+
+    /// 挤出前的采样折线 (世界坐标)：在无效值与渐近线处断开，每条至少两个点
+    /// solve 的网格由它挤出；矢量导出直接使用
+    pub fn polylines<F>(
+        &self,
+        f: &F,
+        x_range: (f64, f64),
+        domain: (f64, f64),
+        zoom: f32,
+        screen_w: u32,
+    ) -> Vec<Vec<(f64, f64)>>
+    where
+        F: Fn(f64) -> f64 + Sync + Send,
+    {
+        self.sample_polylines(f, x_range, domain, zoom, screen_w, false)
+    }
+
+    /// 自适应采样的折线 (solve_adaptive 由它挤出)
+    pub fn polylines_adaptive<F>(
+        &self,
+        f: &F,
+        x_range: (f64, f64),
+        domain: (f64, f64),
+        zoom: f32,
+        screen_w: u32,
+    ) -> Vec<Vec<(f64, f64)>>
+    where
+        F: Fn(f64) -> f64 + Sync + Send,
+    {
+        self.sample_polylines(f, x_range, domain, zoom, screen_w, true)
+    }
+
+    fn sample_polylines<F>(
+        &self,
+        f: &F,
+        x_range: (f64, f64),
+        domain: (f64, f64),
+        zoom: f32,
+        screen_w: u32,
+        adaptive: bool,
+    ) -> Vec<Vec<(f64, f64)>>
+    where
+        F: Fn(f64) -> f64 + Sync + Send,
+    {
+        let (view_min, view_max) = x_range;
+        let view_len = view_max - view_min;
         // 增加对 screen_w 的检查，防止除以0 panic
-        if x_len <= 0.0 || screen_w == 0 { return Vec::new(); }
+        if view_len <= 0.0 || screen_w == 0 { return Vec::new(); }
 
-        let total_samples = (screen_w as f64 * SAMPLING_DENSITY).ceil() as usize;
+        // 视口与定义域求交
+        let x_min = view_min.max(domain.0);
+        let x_max = view_max.min(domain.1);
+        let x_len = x_max - x_min;
+        if x_len.is_nan() || x_len <= 0.0 { return Vec::new(); }
+
+        // 采样数按交集占屏幕的比例分配，保持像素级密度
+        let total_samples = (screen_w as f64 * SAMPLING_DENSITY * x_len / view_len).ceil() as usize;
         let total_samples = total_samples.max(100);
 
         let step_x = x_len / total_samples as f64;
 
         // 1. 并行计算路径点
-        let path: Vec<(f64, f64)> = (0..=total_samples).into_par_iter().map(|i| {
-            let x = x_min + i as f64 * step_x;
+        // 最后一个点直接取 x_max，保证端点被精确采样而非落在最近的网格点上
+        let samples: Vec<(f64, f64)> = (0..=total_samples).into_par_iter().map(|i| {
+            let x = if i == total_samples { x_max } else { x_min + i as f64 * step_x };
             let y = f(x);
             (x, y)
         }).collect();
 
-        // 2. 准备网格生成参数
-        // 屏幕上的 1 像素对应多少世界单位
-        let pixel_size_world = (2.0 / zoom) / screen_h;
-        let half_width_world = (width_px * 0.5) * pixel_size_world;
+        // 1.5 定义域边界细化
+        // 相邻两点一个有效、一个无效 (NaN / Inf) 时，二分逼近真正的边界，
+        // 避免 ln(x) 看起来在 x≈0.003 处就提前终止
+        let pixel_world_x = view_len / screen_w as f64;
+        let tolerance = pixel_world_x * BOUNDARY_REFINE_FRACTION;
+        let mut path: Vec<(f64, f64)> = Vec::with_capacity(samples.len() + 8);
+        for i in 0..samples.len() {
+            if i > 0 {
+                let p0 = samples[i - 1];
+                let p1 = samples[i];
+                match (p0.1.is_finite(), p1.1.is_finite()) {
+                    (true, false) => path.push(refine_boundary(f, p0.0, p1.0, tolerance)),
+                    (false, true) => path.push(refine_boundary(f, p1.0, p0.0, tolerance)),
+                    _ => {}
+                }
+            }
+            path.push(samples[i]);
+        }
 
         // 计算“视口在世界坐标系下的高度”
         // 我们的视口 Y 范围通常是 center.y +/- (1.0 / zoom)
@@ -55,32 +158,51 @@ impl ExplicitSolver {
         // 计算断点阈值：如果 dy > 10 * 屏幕高度，就认为是渐近线
         let jump_threshold = view_height_world * ASYMPTOTE_THRESHOLD_FACTOR;
 
-        let mut vertices = Vec::with_capacity(total_samples * 6);
-
-        // 3. 生成网格 (含断点检测)
-        for i in 0..path.len().saturating_sub(1) {
-            let p0 = path[i];
-            let p1 = path[i+1];
+        // 1.6 陡峭段加密 (仅自适应)：新插入的点与原采样一起参与下面的断点检测
+        if adaptive {
+            path = refine_steep(f, &path, pixel_world_x, jump_threshold);
+        }
 
+        // 2. 切分折线 (含断点检测)
+        let mut lines = Vec::new();
+        let mut current: Vec<(f64, f64)> = Vec::new();
+        for p in path {
             // A. 基础有效性检测 (NaN / Inf)
-            if !p0.1.is_finite() || !p1.1.is_finite() { continue; }
-
             // B. ★★★ 渐近线/断点检测 ★★★
-            let dy = (p1.1 - p0.1).abs();
             // 如果仅一步之遥(1像素宽)，Y值却跨越了数倍于屏幕的高度，这绝对是断点
-            if dy > jump_threshold {
-                continue;
+            let breaks = !p.1.is_finite()
+                || current.last().is_some_and(|q: &(f64, f64)| (p.1 - q.1).abs() > jump_threshold);
+            if breaks && current.len() > 1 {
+                lines.push(std::mem::take(&mut current));
+            } else if breaks {
+                current.clear();
             }
+            if p.1.is_finite() { current.push(p); }
+        }
+        if current.len() > 1 { lines.push(current); }
+        lines
+    }
+}
 
-            // C. 正常的网格挤出逻辑
+// 把挤出前的折线逐段挤出成三角形 (每段两个三角形)
+fn extrude(lines: &[Vec<(f64, f64)>], width_px: f32, zoom: f32, screen_h: f32) -> Vec<Vertex> {
+    // 屏幕上的 1 像素对应多少世界单位
+    let pixel_size_world = (2.0 / zoom) / screen_h;
+    let half_width_world = (width_px * 0.5) * pixel_size_world;
+
+    let mut vertices = Vec::with_capacity(lines.iter().map(|l| l.len() * 6).sum());
+
+    // 每条折线逐段挤出
+    for line in lines {
+        for w in line.windows(2) {
+            let (p0, p1) = (w[0], w[1]);
             let dx = p1.0 - p0.0;
-            // 注意：这里 dy 已经算过了，如果不取绝对值的话: let dy_signed = p1.1 - p0.1;
-            let dy_signed = p1.1 - p0.1;
+            let dy = p1.1 - p0.1;
 
-            let len = (dx*dx + dy_signed*dy_signed).sqrt();
+            let len = (dx*dx + dy*dy).sqrt();
             if len < 1e-9 { continue; }
 
-            let nx = -dy_signed / len;
+            let nx = -dy / len;
             let ny = dx / len;
 
             // 挤出顶点 (World Space)
@@ -92,7 +214,169 @@ impl ExplicitSolver {
             vertices.push(p0_l); vertices.push(p1_l); vertices.push(p0_r);
             vertices.push(p0_r); vertices.push(p1_l); vertices.push(p1_r);
         }
+    }
+
+    vertices
+}
+
+// 在陡峭段中插入等距的采样点：段的屏幕斜率每超过 STEEP_SLOPE 一倍，局部密度加一倍，至多 MAX_LOCAL_DENSITY 倍
+// 端点无效或跨度已超过断点阈值 (渐近线两侧) 的段不加密，留给断点检测
+fn refine_steep<F>(f: &F, path: &[(f64, f64)], pixel_world: f64, jump_threshold: f64) -> Vec<(f64, f64)>
+where
+    F: Fn(f64) -> f64 + Sync + Send,
+{
+    let Some(&first) = path.first() else { return Vec::new() };
+    let mut refined = Vec::with_capacity(path.len());
+    refined.push(first);
+    for w in path.windows(2) {
+        let (p0, p1) = (w[0], w[1]);
+        let dx = p1.0 - p0.0;
+        let dy = (p1.1 - p0.1).abs();
+        if p0.1.is_finite() && p1.1.is_finite() && dx > 0.0 && dy <= jump_threshold {
+            // 斜率按屏幕像素计 (像素为正方形，横纵同一个世界尺寸)
+            let slope = dy / dx;
+            let pixels = dx / pixel_world;
+            // 不足 1/4 像素宽的段 (如定义域边界细化出的点) 不再细分
+            if slope > STEEP_SLOPE && pixels > 0.25 {
+                let n = ((slope / STEEP_SLOPE).ceil() as usize).clamp(1, MAX_LOCAL_DENSITY);
+                let extra: Vec<(f64, f64)> = (1..n).into_par_iter().map(|k| {
+                    let x = p0.0 + dx * k as f64 / n as f64;
+                    (x, f(x))
+                }).collect();
+                refined.extend(extra);
+            }
+        }
+        refined.push(p1);
+    }
+    refined
+}
+
+/// 在 [x_in, x_out] 之间二分查找定义域边界
+/// x_in 处函数值有效，x_out 处无效；返回最靠近边界的有效点
+fn refine_boundary<F>(f: &F, x_in: f64, x_out: f64, tolerance: f64) -> (f64, f64)
+where
+    F: Fn(f64) -> f64,
+{
+    let mut inside = (x_in, f(x_in));
+    let mut outside = x_out;
+
+    for _ in 0..BOUNDARY_REFINE_MAX_ITER {
+        if (outside - inside.0).abs() <= tolerance { break; }
+        let mid = 0.5 * (inside.0 + outside);
+        let y = f(mid);
+        if y.is_finite() {
+            inside = (mid, y);
+        } else {
+            outside = mid;
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN_W: u32 = 800;
+    const SCREEN_H: f32 = 600.0;
+    const ZOOM: f32 = 1.0;
+    const X_RANGE: (f64, f64) = (-2.0, 2.0);
+    const FULL: (f64, f64) = (f64::NEG_INFINITY, f64::INFINITY);
+
+    fn pixel_world_x() -> f64 {
+        (X_RANGE.1 - X_RANGE.0) / SCREEN_W as f64
+    }
+
+    fn leftmost_x(vertices: &[Vertex]) -> f64 {
+        vertices.iter().map(|v| v.position[0] as f64).fold(f64::INFINITY, f64::min)
+    }
+
+    fn rightmost_x(vertices: &[Vertex]) -> f64 {
+        vertices.iter().map(|v| v.position[0] as f64).fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    #[test]
+    fn test_sqrt_starts_at_zero() {
+        let solver = ExplicitSolver::new();
+        let vertices = solver.solve(&|x: f64| x.sqrt(), X_RANGE, FULL, 1.0, ZOOM, SCREEN_W, SCREEN_H);
+        assert!(!vertices.is_empty());
+        let left = leftmost_x(&vertices);
+        assert!(left.abs() < pixel_world_x(), "sqrt 左端点 {} 偏离 0 超过一个像素", left);
+    }
+
+    #[test]
+    fn test_ln_starts_at_zero() {
+        let solver = ExplicitSolver::new();
+        let vertices = solver.solve(&|x: f64| x.ln(), X_RANGE, FULL, 1.0, ZOOM, SCREEN_W, SCREEN_H);
+        assert!(!vertices.is_empty());
+        let left = leftmost_x(&vertices);
+        assert!(left.abs() < pixel_world_x(), "ln 左端点 {} 偏离 0 超过一个像素", left);
+    }
+
+    #[test]
+    fn test_domain_endpoints_are_exact() {
+        let solver = ExplicitSolver::new();
+        // 端点 0.3337 不落在像素网格上
+        let domain = (-0.5, 0.3337);
+        let vertices = solver.solve(&|_x: f64| 0.5, X_RANGE, domain, 1.0, ZOOM, SCREEN_W, SCREEN_H);
+        assert!((leftmost_x(&vertices) - domain.0).abs() < 1e-5);
+        assert!((rightmost_x(&vertices) - domain.1).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_domain_outside_view_is_empty() {
+        let solver = ExplicitSolver::new();
+        let vertices = solver.solve(&|x: f64| x, X_RANGE, (5.0, 6.0), 1.0, ZOOM, SCREEN_W, SCREEN_H);
+        assert!(vertices.is_empty());
+    }
+
+    fn point_count(lines: &[Vec<(f64, f64)>]) -> usize {
+        lines.iter().map(|l| l.len()).sum()
+    }
+
+    #[test]
+    fn test_adaptive_keeps_flat_regions() {
+        // 平缓的函数没有陡峭段，自适应采样与均匀采样完全相同
+        let solver = ExplicitSolver::new();
+        let f = |x: f64| 0.5 * x.sin();
+        let uniform = solver.polylines(&f, X_RANGE, FULL, ZOOM, SCREEN_W);
+        let adaptive = solver.polylines_adaptive(&f, X_RANGE, FULL, ZOOM, SCREEN_W);
+        assert_eq!(uniform, adaptive);
+        assert_eq!(
+            solver.solve(&f, X_RANGE, FULL, 1.0, ZOOM, SCREEN_W, SCREEN_H).len(),
+            solver.solve_adaptive(&f, X_RANGE, FULL, 1.0, ZOOM, SCREEN_W, SCREEN_H).len(),
+        );
+    }
+
+    #[test]
+    fn test_adaptive_refines_steep_segments() {
+        let solver = ExplicitSolver::new();
+        for f in [|x: f64| x.tan(), |x: f64| x.cbrt()] {
+            let uniform = solver.polylines(&f, X_RANGE, FULL, ZOOM, SCREEN_W);
+            let adaptive = solver.polylines_adaptive(&f, X_RANGE, FULL, ZOOM, SCREEN_W);
+            // 加密只发生在局部：总点数增加，但不超过 4 倍
+            assert!(point_count(&adaptive) > point_count(&uniform));
+            assert!(point_count(&adaptive) <= point_count(&uniform) * MAX_LOCAL_DENSITY);
+            // 断点检测照常：折线条数不变，且每条仍按 x 递增
+            assert_eq!(adaptive.len(), uniform.len());
+            for line in &adaptive {
+                assert!(line.windows(2).all(|w| w[0].0 < w[1].0));
+            }
+        }
+
+        // x^(1/3) 在原点附近：最大步长缩小到均匀采样的 1/4
+        let f = |x: f64| x.cbrt();
+        let near_zero = |lines: &[Vec<(f64, f64)>]| lines[0].windows(2)
+            .filter(|w| w[0].0.abs() < 1e-3 || w[1].0.abs() < 1e-3)
+            .map(|w| w[1].0 - w[0].0)
+            .fold(0.0f64, f64::max);
+        let uniform = solver.polylines(&f, X_RANGE, FULL, ZOOM, SCREEN_W);
+        let adaptive = solver.polylines_adaptive(&f, X_RANGE, FULL, ZOOM, SCREEN_W);
+        assert!(near_zero(&adaptive) <= near_zero(&uniform) / MAX_LOCAL_DENSITY as f64 + 1e-12);
 
-        vertices
+        // 挤出的网格也随之加密
+        let uniform = solver.solve(&f, X_RANGE, FULL, 1.0, ZOOM, SCREEN_W, SCREEN_H);
+        let adaptive = solver.solve_adaptive(&f, X_RANGE, FULL, 1.0, ZOOM, SCREEN_W, SCREEN_H);
+        assert!(adaptive.len() > uniform.len());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,49 @@
+// src/graph/d2/field.wgsl
+// 隐函数的 GPU 通道 (绘制)：gpu_field.rs 的计算着色器把 f(x, y) 按屏幕像素写进纹理，
+// 这里用中心差分估计梯度，按 |f| / |∇f| (零等值线的像素距离) 画固定像素宽度的线
+
+struct Style {
+    color: vec4<f32>,
+    width: f32,
+    shape: u32,
+};
+
+// group 0 为全局 Uniform (此处不用)，与其它流水线共用绑定
+@group(1) @binding(0) var<uniform> style: Style;
+@group(2) @binding(0) var field: texture_2d<f32>;
+
+@vertex
+fn vs_field(@builtin(vertex_index) idx: u32) -> @builtin(position) vec4<f32> {
+    // 全屏三角形 (同 vs_grid)
+    let x = f32(i32(idx << 1u) & 2) * 2.0 - 1.0;
+    let y = f32(i32(idx) & 2) * 2.0 - 1.0;
+    return vec4<f32>(x, y, 0.0, 1.0);
+}
+
+fn sample(p: vec2<i32>) -> f32 {
+    let size = vec2<i32>(textureDimensions(field));
+    return textureLoad(field, clamp(p, vec2<i32>(0), size - 1), 0).r;
+}
+
+// NaN / Inf (定义域外) 的像素不画
+fn is_finite(v: f32) -> bool {
+    return v == v && abs(v) < 3.0e38;
+}
+
+@fragment
+fn fs_field(@builtin(position) frag: vec4<f32>) -> @location(0) vec4<f32> {
+    let p = vec2<i32>(frag.xy);
+    let v = sample(p);
+    let dx = (sample(p + vec2<i32>(1, 0)) - sample(p - vec2<i32>(1, 0))) * 0.5;
+    let dy = (sample(p + vec2<i32>(0, 1)) - sample(p - vec2<i32>(0, 1))) * 0.5;
+    if (!is_finite(v) || !is_finite(dx) || !is_finite(dy)) { discard; }
+
+    let grad = length(vec2<f32>(dx, dy));
+    if (grad <= 0.0) { discard; }
+    let d = abs(v) / grad;
+
+    let half_width = style.width * 0.5;
+    let alpha = 1.0 - smoothstep(half_width - 0.5, half_width + 0.5, d);
+    if (alpha <= 0.0) { discard; }
+    return vec4<f32>(style.color.rgb, style.color.a * alpha);
+}
@@ -0,0 +1,439 @@
+// src/graph/d2/gpu_field.rs
+// 隐函数的 GPU 通道：表达式以编译好的 RPN 给出时 (如 watch 的表达式文件)，
+// 把 RPN 翻译成 WGSL 表达式，在计算着色器中按屏幕分辨率逐像素求值写入 R32Float 纹理，
+// 再由 field.wgsl 的片元着色器按 |f| / |∇f| 画出零等值线 (线宽为固定像素)
+// 任意 Rust 闭包无法翻译，仍走 CPU 求解器；GPU 通道的精度为 f32
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
+use crate::pakoo::math_data::MathData;
+use crate::pakoo::op::Op;
+use crate::pakoo::rpn::RPN;
+
+// 求值纹理格式：单通道 f32
+pub const FIELD_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Float;
+const WORKGROUP_SIZE: u32 = 8;
+
+// 求值函数的参数名 (LoadGlobal(0) = x, LoadGlobal(1) = y，与 watch 的约定一致)
+const FIELD_VARS: [&str; 2] = ["x", "y"];
+
+// ====================== 代码生成 ======================
+
+// 翻译失败的原因，at 为出错指令的下标
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodegenError {
+    Unsupported { at: usize, op: String },     // 跳转、向量、比较等 GPU 通道不支持的指令
+    BadLiteral { at: usize },                  // 非数值常量，或超出 f32 范围 (含 NaN / Inf)
+    UnknownVariable { at: usize, index: usize },
+    BadIdentifier(String),                     // 变量名不是合法的 WGSL 标识符
+    Malformed,                                 // 栈形状不对 (操作数不足或结束时不是恰好一个值)
+    Invalid(String),                           // 生成的着色器未通过 naga 校验
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodegenError::Unsupported { at, op } => write!(f, "op {} ({}) has no GPU translation", at, op),
+            CodegenError::BadLiteral { at } => write!(f, "constant at op {} is not a finite f32", at),
+            CodegenError::UnknownVariable { at, index } => write!(f, "global {} at op {} is not a field variable", index, at),
+            CodegenError::BadIdentifier(name) => write!(f, "'{}' is not a valid WGSL identifier", name),
+            CodegenError::Malformed => write!(f, "malformed RPN program"),
+            CodegenError::Invalid(e) => write!(f, "generated shader is invalid: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CodegenError {}
+
+// 只接受 ASCII 字母 / 数字 / 下划线，且不以数字或双下划线开头 (WGSL 保留)
+fn is_wgsl_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with("__")
+        && name != "_"
+}
+
+// f32 字面量：Debug 格式总带小数点或指数，加 f 后缀；负数加括号，避免与前面的减号连成 --
+fn wgsl_literal(v: f64, at: usize) -> Result<String, CodegenError> {
+    let v32 = v as f32;
+    if !v32.is_finite() {
+        return Err(CodegenError::BadLiteral { at });
+    }
+    Ok(if v32 < 0.0 { format!("({:?}f)", v32) } else { format!("{:?}f", v32) })
+}
+
+// RPN -> 完全加括号的 WGSL 表达式；LoadGlobal(i) 对应 vars[i]
+// 支持 Push (数值) / LoadGlobal / 四则运算 / Sin / Cos / Tan，其余指令报 Unsupported
+pub(crate) fn rpn_to_wgsl(ops: &[Op], vars: &[&str]) -> Result<String, CodegenError> {
+    if let Some(bad) = vars.iter().find(|v| !is_wgsl_identifier(v)) {
+        return Err(CodegenError::BadIdentifier(bad.to_string()));
+    }
+    let mut stack: Vec<String> = Vec::new();
+    for (at, op) in ops.iter().enumerate() {
+        let expr = match op {
+            Op::Push(MathData::Num(v)) => wgsl_literal(*v, at)?,
+            Op::Push(_) => return Err(CodegenError::BadLiteral { at }),
+            Op::LoadGlobal(index) => match vars.get(*index) {
+                Some(name) => name.to_string(),
+                None => return Err(CodegenError::UnknownVariable { at, index: *index }),
+            },
+            Op::Add | Op::Sub | Op::Mul | Op::Div => {
+                let rhs = stack.pop().ok_or(CodegenError::Malformed)?;
+                let lhs = stack.pop().ok_or(CodegenError::Malformed)?;
+                let symbol = match op { Op::Add => "+", Op::Sub => "-", Op::Mul => "*", _ => "/" };
+                format!("({} {} {})", lhs, symbol, rhs)
+            }
+            Op::Sin | Op::Cos | Op::Tan => {
+                let arg = stack.pop().ok_or(CodegenError::Malformed)?;
+                let name = match op { Op::Sin => "sin", Op::Cos => "cos", _ => "tan" };
+                format!("{}({})", name, arg)
+            }
+            other => return Err(CodegenError::Unsupported { at, op: format!("{:?}", other) }),
+        };
+        stack.push(expr);
+    }
+    match (stack.pop(), stack.is_empty()) {
+        (Some(expr), true) => Ok(expr),
+        _ => Err(CodegenError::Malformed),
+    }
+}
+
+// 完整的计算着色器：view 与 D2 的 ViewUniforms 布局相同，params 为对象逆变换的前两行
+pub fn field_shader_source(expr: &str) -> String {
+    format!(r#"// 由 gpu_field.rs 生成
+struct ViewUniforms {{
+    center: vec2<f32>,
+    zoom: f32,
+    aspect: f32,
+    resolution: vec2<f32>,
+    _pad: vec2<f32>,
+}};
+
+struct FieldParams {{
+    inv_row0: vec4<f32>,
+    inv_row1: vec4<f32>,
+}};
+
+@group(0) @binding(0) var<uniform> view: ViewUniforms;
+@group(0) @binding(1) var<uniform> params: FieldParams;
+@group(0) @binding(2) var field: texture_storage_2d<r32float, write>;
+
+fn f({x}: f32, {y}: f32) -> f32 {{
+    return {expr};
+}}
+
+@compute @workgroup_size({wg}, {wg})
+fn cs_field(@builtin(global_invocation_id) id: vec3<u32>) {{
+    let size = textureDimensions(field);
+    if (id.x >= size.x || id.y >= size.y) {{ return; }}
+    // 像素中心的世界坐标 (与 vs_grid 相同的映射，纹理 y 轴向下)
+    let ndc = vec2<f32>(
+        (f32(id.x) + 0.5) / f32(size.x) * 2.0 - 1.0,
+        1.0 - (f32(id.y) + 0.5) / f32(size.y) * 2.0,
+    );
+    let range_y = 2.0 / view.zoom;
+    let world = vec3<f32>(view.center + ndc * vec2<f32>(range_y * view.aspect, range_y), 1.0);
+    let value = f(dot(params.inv_row0.xyz, world), dot(params.inv_row1.xyz, world));
+    textureStore(field, vec2<i32>(id.xy), vec4<f32>(value, 0.0, 0.0, 0.0));
+}}
+"#, x = FIELD_VARS[0], y = FIELD_VARS[1], expr = expr, wg = WORKGROUP_SIZE)
+}
+
+// 用 naga 解析并校验生成的着色器，保证创建流水线时不会因代码生成的问题失败
+fn validate_shader(source: &str) -> Result<(), CodegenError> {
+    use wgpu::naga;
+    let module = naga::front::wgsl::parse_str(source).map_err(|e| CodegenError::Invalid(e.to_string()))?;
+    naga::valid::Validator::new(naga::valid::ValidationFlags::all(), naga::valid::Capabilities::empty())
+        .validate(&module)
+        .map_err(|e| CodegenError::Invalid(e.to_string()))?;
+    Ok(())
+}
+
+// 可在 GPU 上求值的隐函数 f(x, y)：生成的着色器源码 + 流水线缓存的键
+#[derive(Clone, Debug)]
+pub struct GpuField {
+    source: String,
+    key: u64,
+}
+
+impl GpuField {
+    // 翻译并校验；键为 RPN 的哈希 (按生成的表达式计算，等价的程序共用一条流水线)
+    pub(crate) fn from_rpn(rpn: &RPN) -> Result<Self, CodegenError> {
+        let expr = rpn_to_wgsl(rpn.ops(), &FIELD_VARS)?;
+        let source = field_shader_source(&expr);
+        validate_shader(&source)?;
+        let mut hasher = DefaultHasher::new();
+        expr.hash(&mut hasher);
+        Ok(Self { source, key: hasher.finish() })
+    }
+}
+
+// ====================== GPU 资源 ======================
+
+// 对应着色器中的 FieldParams：世界坐标 -> 对象局部坐标 (逆变换的前两行)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+pub struct FieldParams {
+    inv_row0: [f32; 4],
+    inv_row1: [f32; 4],
+}
+
+impl FieldParams {
+    // 不可逆的变换没有可画的点：整张纹理为 NaN
+    pub fn new(transform: &Matrix3x3) -> Self {
+        let Some(inv) = transform.inverse() else {
+            return Self { inv_row0: [f32::NAN; 4], inv_row1: [f32::NAN; 4] };
+        };
+        let m = inv.m.map(|x| x as f32);
+        Self { inv_row0: [m[0], m[1], m[2], 0.0], inv_row1: [m[3], m[4], m[5], 0.0] }
+    }
+}
+
+// 一个图层的求值纹理 (屏幕分辨率)，以及计算 / 绘制两侧的绑定
+pub struct FieldTarget {
+    pub size: (u32, u32),
+    #[allow(dead_code)] // 绘制只经由绑定组访问；保留句柄供读回
+    pub texture: wgpu::Texture,
+    params: wgpu::Buffer,
+    compute_bind_group: wgpu::BindGroup,
+    // 绘制时片元着色器读取纹理 (FieldEvaluator::texture_layout)
+    pub texture_bind_group: wgpu::BindGroup,
+}
+
+// 计算流水线的布局与按键缓存的流水线 (表达式不变时不重建)
+pub struct FieldEvaluator {
+    compute_layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+    pub texture_layout: wgpu::BindGroupLayout,
+    pipelines: HashMap<u64, wgpu::ComputePipeline>,
+}
+
+impl FieldEvaluator {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let uniform = |binding| wgpu::BindGroupLayoutEntry {
+            binding, visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Uniform, has_dynamic_offset: false, min_binding_size: None },
+            count: None,
+        };
+        let compute_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Field Compute Layout"),
+            entries: &[
+                uniform(0),
+                uniform(1),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2, visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture { access: wgpu::StorageTextureAccess::WriteOnly, format: FIELD_FORMAT, view_dimension: wgpu::TextureViewDimension::D2 },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Field Compute Pipeline Layout"), bind_group_layouts: &[&compute_layout], immediate_size: 0,
+        });
+        // R32Float 不可过滤，片元着色器用 textureLoad 逐像素读取
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Field Texture Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0, visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture { sample_type: wgpu::TextureSampleType::Float { filterable: false }, view_dimension: wgpu::TextureViewDimension::D2, multisampled: false },
+                count: None,
+            }],
+        });
+        Self { compute_layout, pipeline_layout, texture_layout, pipelines: HashMap::new() }
+    }
+
+    // view_buffer 为 D2 的全局 Uniform (ViewUniforms)，每帧在提交前写入
+    pub fn create_target(&self, device: &wgpu::Device, view_buffer: &wgpu::Buffer, width: u32, height: u32) -> FieldTarget {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Field Texture"),
+            size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FIELD_FORMAT,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let params = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Field Params"), size: size_of::<FieldParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST, mapped_at_creation: false,
+        });
+        let compute_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Field Compute BG"),
+            layout: &self.compute_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: view_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: params.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::TextureView(&view) },
+            ],
+        });
+        let texture_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Field Texture BG"),
+            layout: &self.texture_layout,
+            entries: &[wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&view) }],
+        });
+        FieldTarget { size: (width, height), texture, params, compute_bind_group, texture_bind_group }
+    }
+
+    pub fn write_params(&self, queue: &wgpu::Queue, target: &FieldTarget, transform: &Matrix3x3) {
+        queue.write_buffer(&target.params, 0, bytemuck::bytes_of(&FieldParams::new(transform)));
+    }
+
+    // 取 (必要时创建) field 对应的计算流水线
+    fn pipeline(&mut self, device: &wgpu::Device, field: &GpuField) -> &wgpu::ComputePipeline {
+        self.pipelines.entry(field.key).or_insert_with(|| {
+            let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Field Shader"),
+                source: wgpu::ShaderSource::Wgsl(field.source.as_str().into()),
+            });
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("Field Pipeline"),
+                layout: Some(&self.pipeline_layout),
+                module: &module,
+                entry_point: Some("cs_field"),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        })
+    }
+
+    // 在 encoder 中记录一次整张纹理的求值
+    pub fn dispatch(&mut self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder, field: &GpuField, target: &FieldTarget) {
+        let pipeline = self.pipeline(device, field).clone();
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: Some("Field Pass"), timestamp_writes: None });
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &target.compute_bind_group, &[]);
+        pass.dispatch_workgroups(target.size.0.div_ceil(WORKGROUP_SIZE), target.size.1.div_ceil(WORKGROUP_SIZE), 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::d2::watch::compile_line;
+
+    fn compile(text: &str) -> RPN {
+        compile_line(text).unwrap().1
+    }
+
+    // sin(x² + y²) - cos(xy) (编译器不生成三角函数指令，手写 RPN)
+    fn wave_ops() -> Vec<Op> {
+        vec![
+            Op::LoadGlobal(0), Op::LoadGlobal(0), Op::Mul,
+            Op::LoadGlobal(1), Op::LoadGlobal(1), Op::Mul,
+            Op::Add, Op::Sin,
+            Op::LoadGlobal(0), Op::LoadGlobal(1), Op::Mul, Op::Cos,
+            Op::Sub,
+        ]
+    }
+
+    #[test]
+    fn test_codegen_known_sequence() {
+        assert_eq!(rpn_to_wgsl(&wave_ops(), &FIELD_VARS).unwrap(), "(sin(((x * x) + (y * y))) - cos((x * y)))");
+
+        // 常量：f32 字面量，负数加括号
+        let ops = vec![Op::LoadGlobal(0), Op::Push(MathData::Num(-2.5)), Op::Sub, Op::Push(MathData::Num(4.0)), Op::Div, Op::Tan];
+        assert_eq!(rpn_to_wgsl(&ops, &FIELD_VARS).unwrap(), "tan(((x - (-2.5f)) / 4.0f))");
+        assert_eq!(rpn_to_wgsl(&[Op::Push(MathData::Num(1e-10))], &FIELD_VARS).unwrap(), "1e-10f");
+    }
+
+    #[test]
+    fn test_codegen_rejects() {
+        let x = Op::LoadGlobal(0);
+        assert!(matches!(rpn_to_wgsl(&[x.clone(), x.clone(), Op::GreaterThan], &FIELD_VARS), Err(CodegenError::Unsupported { at: 2, .. })));
+        assert_eq!(rpn_to_wgsl(&[Op::Push(MathData::Num(1e300))], &FIELD_VARS), Err(CodegenError::BadLiteral { at: 0 }));
+        assert_eq!(rpn_to_wgsl(&[Op::Push(MathData::Bool(true))], &FIELD_VARS), Err(CodegenError::BadLiteral { at: 0 }));
+        assert_eq!(rpn_to_wgsl(&[Op::LoadGlobal(2)], &FIELD_VARS), Err(CodegenError::UnknownVariable { at: 0, index: 2 }));
+        assert_eq!(rpn_to_wgsl(&[x.clone(), Op::Add], &FIELD_VARS), Err(CodegenError::Malformed));
+        assert_eq!(rpn_to_wgsl(&[x.clone(), x.clone()], &FIELD_VARS), Err(CodegenError::Malformed));
+        // 变量名会被原样写进着色器，必须是合法标识符
+        for bad in ["1x", "x y", "__x", "_", "x;", ""] {
+            assert_eq!(rpn_to_wgsl(std::slice::from_ref(&x), &[bad]), Err(CodegenError::BadIdentifier(bad.to_string())));
+        }
+
+        // 编译器输出：三目运算含跳转，留给 CPU
+        assert!(GpuField::from_rpn(&compile("x > 0 ? x : 0 - x")).is_err());
+        let field = GpuField::from_rpn(&compile("x * x + y * y - 4")).unwrap();
+        assert_eq!(field.key, GpuField::from_rpn(&compile("x * x + y * y - 4")).unwrap().key);
+        assert_ne!(field.key, GpuField::from_rpn(&compile("x * x + y * y - 1")).unwrap().key);
+
+        // watch 的隐函数：能翻译的附带 GPU 程序，其余走 CPU
+        let (exprs, _) = crate::graph::d2::watch::compile_source("x * x + y * y - 4\nx > y ? x : y\n");
+        let objects: Vec<_> = exprs.iter().map(|e| e.to_geo_obj([1.0; 4])).collect();
+        assert!(objects[0].gpu_field.is_some());
+        assert!(objects[1].gpu_field.is_none());
+    }
+
+    // 在 size × size 的纹理上求值并读回 (没有可用的 GPU 时返回 None)
+    fn evaluate_on_gpu(field: &GpuField, size: u32, view: [f32; 8]) -> Option<Vec<f32>> {
+        let instance = wgpu::Instance::default();
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default())).ok()?;
+        let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).ok()?;
+
+        let view_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None, size: 32, usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST, mapped_at_creation: false,
+        });
+        queue.write_buffer(&view_buffer, 0, bytemuck::cast_slice(&view));
+        let mut evaluator = FieldEvaluator::new(&device);
+        let target = evaluator.create_target(&device, &view_buffer, size, size);
+        evaluator.write_params(&queue, &target, &Matrix3x3::IDENTITY);
+
+        let bytes_per_row = size * 4; // size 取 64 的倍数，满足 256 字节对齐
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None, size: (bytes_per_row * size) as u64, usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST, mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        evaluator.dispatch(&device, &mut encoder, field, &target);
+        encoder.copy_texture_to_buffer(
+            target.texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback,
+                layout: wgpu::TexelCopyBufferLayout { offset: 0, bytes_per_row: Some(bytes_per_row), rows_per_image: None },
+            },
+            wgpu::Extent3d { width: size, height: size, depth_or_array_layers: 1 },
+        );
+        queue.submit(std::iter::once(encoder.finish()));
+
+        readback.slice(..).map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::PollType::wait_indefinitely()).ok()?;
+        let data = bytemuck::cast_slice(&readback.slice(..).get_mapped_range()).to_vec();
+        Some(data)
+    }
+
+    #[test]
+    fn test_gpu_matches_cpu() {
+        // 视口：中心 (0.25, -0.5)，zoom 1 (纵向 ±2)，正方形
+        let size = 64;
+        let (cx, cy) = (0.25f32, -0.5f32);
+        let view = [cx, cy, 1.0, 1.0, size as f32, size as f32, 0.0, 0.0];
+        let tan_ops = vec![Op::LoadGlobal(0), Op::LoadGlobal(1), Op::LoadGlobal(1), Op::Mul, Op::Push(MathData::Num(1.0)), Op::Add, Op::Div, Op::LoadGlobal(0), Op::Push(MathData::Num(0.5)), Op::Mul, Op::Tan, Op::Sub];
+        let programs = [
+            ("x * x + y * y - 4", compile("x * x + y * y - 4")),
+            ("x * y / (x - 3) - 1", compile("x * y / (x - 3) - 1")),
+            ("sin(x² + y²) - cos(xy)", RPN::new(wave_ops())),
+            ("x / (y² + 1) - tan(x / 2)", RPN::new(tan_ops)),
+        ];
+        for (text, rpn) in programs {
+            let field = GpuField::from_rpn(&rpn).unwrap();
+            let Some(values) = evaluate_on_gpu(&field, size, view) else {
+                eprintln!("no GPU adapter available, skipping GPU field comparison");
+                return;
+            };
+            for (i, value) in values.iter().enumerate() {
+                let (px, py) = ((i as u32 % size) as f32, (i as u32 / size) as f32);
+                let x = cx + ((px + 0.5) / size as f32 * 2.0 - 1.0) * 2.0;
+                let y = cy + (1.0 - (py + 0.5) / size as f32 * 2.0) * 2.0;
+                let MathData::Num(expected) = rpn.eval(&[MathData::Num(x as f64), MathData::Num(y as f64)], &[]) else { panic!() };
+                assert!((*value as f64 - expected).abs() <= 1e-4 * (1.0 + expected.abs()), "{}: ({}, {}) gpu {} cpu {}", text, x, y, value, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+// src/d2/gradient.rs
+// 曲线着色：单色，或按标量 (参数 t / 速度 |P'(t)|) 经过渐变色表映射
+#![allow(dead_code)]
+
+use bytemuck::{Pod, Zeroable};
+
+use super::colors;
+
+// 渐变色标上限 (与 shader.wgsl 中 Gradient 的数组长度一致)
+pub const MAX_STOPS: usize = 8;
+
+// 渐变：色标 (位置 ∈ [0, 1], 颜色)，位置升序
+// range 为标量的映射区间；None 时取本次求解得到的标量最小/最大值
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    pub stops: Vec<(f32, [f32; 4])>,
+    pub range: Option<(f64, f64)>,
+}
+
+impl Gradient {
+    pub fn new(mut stops: Vec<(f32, [f32; 4])>) -> Self {
+        assert!(!stops.is_empty(), "gradient needs at least one stop");
+        assert!(stops.len() <= MAX_STOPS, "gradient supports at most {} stops", MAX_STOPS);
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops, range: None }
+    }
+
+    // 两色线性渐变
+    pub fn two(from: [f32; 4], to: [f32; 4]) -> Self {
+        Self::new(vec![(0.0, from), (1.0, to)])
+    }
+
+    // 蓝 -> 青 -> 绿 -> 黄 -> 红
+    pub fn rainbow() -> Self {
+        Self::new(vec![
+            (0.0, colors::BLUE),
+            (0.25, colors::CYAN),
+            (0.5, colors::GREEN),
+            (0.75, colors::YELLOW),
+            (1.0, colors::RED),
+        ])
+    }
+
+    // 固定映射区间 (构造时链式调用)，例如 Gradient::rainbow().with_range(0.0, 2.0)
+    pub fn with_range(mut self, lo: f64, hi: f64) -> Self {
+        self.range = Some((lo, hi));
+        self
+    }
+
+    // 实际使用的映射区间：固定区间，或 values 中有限值的最小/最大值；退化时扩成长度 1
+    pub fn resolve_range(&self, values: &[f32]) -> (f64, f64) {
+        let (lo, hi) = self.range.unwrap_or_else(|| {
+            values.iter().filter(|v| v.is_finite()).fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                (lo.min(v as f64), hi.max(v as f64))
+            })
+        });
+        if !(lo.is_finite() && hi.is_finite()) { return (0.0, 1.0); }
+        if hi > lo { (lo, hi) } else { (lo, lo + 1.0) }
+    }
+
+    // 在归一化位置 s ∈ [0, 1] 取色 (与 fs_mesh_gradient 的逻辑一致)
+    pub fn sample(&self, s: f32) -> [f32; 4] {
+        let s = s.clamp(0.0, 1.0);
+        let mut color = self.stops[0].1;
+        for w in self.stops.windows(2) {
+            let ((p0, c0), (p1, c1)) = (w[0], w[1]);
+            if s >= p0 {
+                let k = ((s - p0) / (p1 - p0).max(1e-6)).clamp(0.0, 1.0);
+                color = std::array::from_fn(|i| c0[i] + (c1[i] - c0[i]) * k);
+            }
+        }
+        color
+    }
+}
+
+// 着色方式
+#[derive(Clone, Debug, PartialEq)]
+pub enum Coloring {
+    Flat([f32; 4]),
+    // 按参数 t 着色 (显示曲线的走向)
+    ByParam(Gradient),
+    // 按速度 |P'(t)| 着色 (显示参数化的快慢)
+    BySpeed(Gradient),
+    // 按曲率 κ 着色 (显示弯曲程度)
+    ByCurvature(Gradient),
+}
+
+impl Coloring {
+    // 单色部分使用的颜色：渐变取第一个色标 (无法逐顶点着色的对象以此兜底)
+    pub fn base_color(&self) -> [f32; 4] {
+        match self {
+            Coloring::Flat(color) => *color,
+            Coloring::ByParam(g) | Coloring::BySpeed(g) | Coloring::ByCurvature(g) => g.stops[0].1,
+        }
+    }
+
+    pub fn gradient(&self) -> Option<&Gradient> {
+        match self {
+            Coloring::Flat(_) => None,
+            Coloring::ByParam(g) | Coloring::BySpeed(g) | Coloring::ByCurvature(g) => Some(g),
+        }
+    }
+}
+
+// ====================== GPU Uniform ======================
+
+// 对应 shader.wgsl 中的 Gradient (uniform 数组元素按 16 字节对齐，位置每 4 个打包成一个 vec4)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct GradientUniform {
+    colors: [[f32; 4]; MAX_STOPS],
+    positions: [[f32; 4]; MAX_STOPS / 4],
+    count: u32,
+    lo: f32,
+    hi: f32,
+    _pad: f32,
+}
+
+impl GradientUniform {
+    pub fn new(gradient: &Gradient, (lo, hi): (f64, f64)) -> Self {
+        let mut u = Self::zeroed();
+        for (i, &(pos, color)) in gradient.stops.iter().enumerate() {
+            u.colors[i] = color;
+            u.positions[i / 4][i % 4] = pos;
+        }
+        u.count = gradient.stops.len() as u32;
+        u.lo = lo as f32;
+        u.hi = hi as f32;
+        u
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: [f32; 4], b: [f32; 4]) -> bool {
+        a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-6)
+    }
+
+    #[test]
+    fn test_sample_and_range() {
+        let g = Gradient::two([0.0, 0.0, 0.0, 1.0], [1.0, 0.5, 0.0, 1.0]);
+        assert_eq!(g.sample(0.0), [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(g.sample(0.5), [0.5, 0.25, 0.0, 1.0]);
+        assert_eq!(g.sample(2.0), [1.0, 0.5, 0.0, 1.0]);
+
+        // 中间色标
+        let r = Gradient::rainbow();
+        assert!(close(r.sample(0.25), colors::CYAN));
+        assert!(close(r.sample(1.0), colors::RED));
+
+        assert_eq!(g.resolve_range(&[3.0, f32::NAN, -1.0]), (-1.0, 3.0));
+        assert_eq!(g.resolve_range(&[2.0, 2.0]), (2.0, 3.0));
+        assert_eq!(g.resolve_range(&[]), (0.0, 1.0));
+        assert_eq!(g.clone().with_range(5.0, 6.0).resolve_range(&[0.0, 100.0]), (5.0, 6.0));
+    }
+
+    #[test]
+    fn test_uniform_layout() {
+        // 与 WGSL 布局一致：8 个 vec4 颜色 + 2 个 vec4 位置 + count/lo/hi/pad
+        assert_eq!(size_of::<GradientUniform>(), 176);
+
+        let u = GradientUniform::new(&Gradient::rainbow(), (0.0, 2.0));
+        assert_eq!(u.count, 5);
+        assert_eq!(u.positions[0], [0.0, 0.25, 0.5, 0.75]);
+        assert_eq!(u.positions[1][0], 1.0);
+        assert_eq!(u.colors[4], colors::RED);
+        assert_eq!((u.lo, u.hi), (0.0, 2.0));
+    }
+}
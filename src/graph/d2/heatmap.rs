@@ -0,0 +1,434 @@
+// src/graph/d2/heatmap.rs
+// 标量场热力图 (GeoType::Heatmap)：在 CPU 上按屏幕分辨率的一部分对 f(x, y) 求值，
+// 结果上传为 R32Float 纹理，由 heatmap.wgsl 的全屏三角形按 [lo, hi] 归一化后查 256×1 的色表
+// 求值范围比视口大一圈：平移 / 缩放不超过阈值时只有全局 Uniform 变化，不重新求值
+#![allow(dead_code)]
+
+use bytemuck::{Pod, Zeroable};
+use rayon::prelude::*;
+use wgpu::util::DeviceExt;
+
+use super::common::ViewInfo;
+use super::gpu_field::FIELD_FORMAT;
+use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+
+// 色表长度 (与 heatmap.wgsl 中的 255.0 对应)
+pub const LUT_SIZE: usize = 256;
+// 求值纹理相对屏幕的默认分辨率 (每个方向)
+pub const DEFAULT_RESOLUTION: f32 = 0.5;
+// 求值范围在视口四周各多出的比例 (按视口宽高)
+const MARGIN: f64 = 0.25;
+// 缩放变化超过该倍数时重新求值 (放大后纹素变粗，缩小后外圈不够)
+const ZOOM_TOLERANCE: f64 = 1.25;
+
+// viridis 在 0, 0.1, ..., 1 处的取值 (近似 matplotlib，亮度单调递增)
+// 色表数值碰巧接近 FRAC_1_PI 等常量，不是常量的近似
+#[allow(clippy::approx_constant)]
+const VIRIDIS: [[f32; 3]; 11] = [
+    [0.267, 0.005, 0.329],
+    [0.283, 0.141, 0.458],
+    [0.254, 0.265, 0.530],
+    [0.207, 0.372, 0.553],
+    [0.164, 0.471, 0.558],
+    [0.128, 0.567, 0.551],
+    [0.135, 0.659, 0.518],
+    [0.267, 0.749, 0.441],
+    [0.478, 0.821, 0.318],
+    [0.741, 0.873, 0.150],
+    [0.993, 0.906, 0.144],
+];
+const BLUE_WHITE_RED: [[f32; 3]; 3] = [[0.230, 0.299, 0.754], [1.0, 1.0, 1.0], [0.706, 0.016, 0.150]];
+const GRAYSCALE: [[f32; 3]; 2] = [[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]];
+
+// 色表
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorMap {
+    // 深紫 -> 蓝 -> 青绿 -> 黄
+    Viridis,
+    // 蓝 -> 白 -> 红：归一化区间关于 center 对称，center 恰好是白色 (适合看符号)
+    Diverging { center: f64 },
+    Grayscale,
+}
+
+impl ColorMap {
+    // 等距控制点，其间线性插值
+    fn anchors(self) -> &'static [[f32; 3]] {
+        match self {
+            ColorMap::Viridis => &VIRIDIS,
+            ColorMap::Diverging { .. } => &BLUE_WHITE_RED,
+            ColorMap::Grayscale => &GRAYSCALE,
+        }
+    }
+
+    // 色表第 i 项 (RGBA8)
+    fn entry(self, i: usize) -> [u8; 4] {
+        let anchors = self.anchors();
+        let t = i as f32 / (LUT_SIZE - 1) as f32 * (anchors.len() - 1) as f32;
+        let k = (t.floor() as usize).min(anchors.len() - 2);
+        let (c0, c1, w) = (anchors[k], anchors[k + 1], t - k as f32);
+        let rgb: [u8; 3] = std::array::from_fn(|j| ((c0[j] + (c1[j] - c0[j]) * w) * 255.0).round() as u8);
+        [rgb[0], rgb[1], rgb[2], 255]
+    }
+
+    // 烘焙成 256×1 的纹理数据
+    pub fn bake(self) -> [[u8; 4]; LUT_SIZE] {
+        std::array::from_fn(|i| self.entry(i))
+    }
+
+    // 归一化值 s ∈ [0, 1] 的颜色 (与 fs_heatmap 的查表一致)
+    pub fn sample(self, s: f32) -> [f32; 4] {
+        self.entry(lut_index(s)).map(|c| c as f32 / 255.0)
+    }
+}
+
+// 归一化值 -> 色表下标：四舍五入到最近的一项，区间外截断，NaN 取 0
+pub fn lut_index(s: f32) -> usize {
+    let s = if s.is_nan() { 0.0 } else { s.clamp(0.0, 1.0) };
+    (s * (LUT_SIZE - 1) as f32).round() as usize
+}
+
+// 归一化区间：固定区间，或 values 中有限值的最小 / 最大值；退化时扩成长度 1
+// 发散色表再扩成关于 center 对称，正负两侧按同一比例着色
+pub fn resolve_range(colormap: ColorMap, values: &[f32], fixed: Option<(f64, f64)>) -> (f64, f64) {
+    let (lo, hi) = fixed.unwrap_or_else(|| {
+        values.iter().filter(|v| v.is_finite()).fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v as f64), hi.max(v as f64))
+        })
+    });
+    let (lo, hi) = if !(lo.is_finite() && hi.is_finite()) {
+        (0.0, 1.0)
+    } else if hi > lo {
+        (lo, hi)
+    } else {
+        (lo, lo + 1.0)
+    };
+    match colormap {
+        ColorMap::Diverging { center } => {
+            let half = (lo - center).abs().max((hi - center).abs());
+            let half = if half > 0.0 && half.is_finite() { half } else { 1.0 };
+            (center - half, center + half)
+        }
+        _ => (lo, hi),
+    }
+}
+
+// v 在 [lo, hi] 中的位置，截断到 [0, 1] (与 fs_heatmap 一致)
+pub fn normalize(v: f64, (lo, hi): (f64, f64)) -> f32 {
+    ((v - lo) / (hi - lo)).clamp(0.0, 1.0) as f32
+}
+
+// 热力图的外观：归一化区间 (None 时取求值结果的最小 / 最大值) 与求值分辨率 (相对屏幕，每个方向)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HeatmapStyle {
+    pub range: Option<(f64, f64)>,
+    pub resolution: f32,
+}
+
+impl Default for HeatmapStyle {
+    fn default() -> Self {
+        Self { range: None, resolution: DEFAULT_RESOLUTION }
+    }
+}
+
+// ====================== 求值 ======================
+
+// 一次求值覆盖的世界矩形，以及求值时的缩放
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Coverage {
+    pub x_range: (f64, f64),
+    pub y_range: (f64, f64),
+    pub zoom: f64,
+}
+
+impl Coverage {
+    // 视口四周各扩出 MARGIN
+    pub fn around(view: &ViewInfo) -> Self {
+        let expand = |(lo, hi): (f64, f64)| {
+            let pad = (hi - lo) * MARGIN;
+            (lo - pad, hi + pad)
+        };
+        Self { x_range: expand(view.x_range), y_range: expand(view.y_range), zoom: view.zoom }
+    }
+
+    // 视口仍在覆盖范围内，且缩放变化不超过 ZOOM_TOLERANCE 时不必重新求值
+    pub fn covers(&self, view: &ViewInfo) -> bool {
+        let ratio = view.zoom / self.zoom;
+        (1.0 / ZOOM_TOLERANCE..=ZOOM_TOLERANCE).contains(&ratio)
+            && view.x_range.0 >= self.x_range.0 && view.x_range.1 <= self.x_range.1
+            && view.y_range.0 >= self.y_range.0 && view.y_range.1 <= self.y_range.1
+    }
+}
+
+// 纹理尺寸：视口像素 × resolution × (1 + 2·MARGIN)，每边在 [1, max_side] 内
+// max_side 取设备的 max_texture_dimension_2d (高分屏上分辨率调高时会超出)
+pub fn texture_size(pixels: (u32, u32), resolution: f32, max_side: u32) -> (u32, u32) {
+    let scale = resolution.max(0.0) as f64 * (1.0 + 2.0 * MARGIN);
+    let side = |p: u32| ((p as f64 * scale).round() as u32).clamp(1, max_side.max(1));
+    (side(pixels.0), side(pixels.1))
+}
+
+// 在覆盖范围的纹素中心上求值，行 0 为上边缘 (与 gpu_field 的纹理方向一致)
+// f 在对象局部坐标下，transform 不可逆时整张为 NaN
+pub fn evaluate<F>(f: &F, transform: &Matrix3x3, coverage: &Coverage, (width, height): (u32, u32)) -> Vec<f32>
+where F: Fn(f64, f64) -> f64 + Sync + ?Sized {
+    let mut values = vec![f32::NAN; (width * height) as usize];
+    let Some(inv) = transform.inverse() else { return values };
+    let identity = *transform == Matrix3x3::IDENTITY;
+    let (x0, x1) = coverage.x_range;
+    let (y0, y1) = coverage.y_range;
+    let (dx, dy) = ((x1 - x0) / width as f64, (y1 - y0) / height as f64);
+    values.par_chunks_mut(width as usize).enumerate().for_each(|(row, out)| {
+        let y = y1 - (row as f64 + 0.5) * dy;
+        for (col, v) in out.iter_mut().enumerate() {
+            let x = x0 + (col as f64 + 0.5) * dx;
+            let q = if identity { Vec2::new(x, y) } else { inv.transform_point2(Vec2::new(x, y)) };
+            *v = f(q.x, q.y) as f32;
+        }
+    });
+    values
+}
+
+// ====================== GPU 资源 ======================
+
+// 对应 heatmap.wgsl 中的 HeatmapParams：纹理覆盖的世界矩形 + 归一化区间
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+pub struct HeatmapParams {
+    rect_min: [f32; 2],
+    rect_max: [f32; 2],
+    lo: f32,
+    hi: f32,
+    _pad: [f32; 2],
+}
+
+impl HeatmapParams {
+    pub fn new(coverage: &Coverage, (lo, hi): (f64, f64)) -> Self {
+        Self {
+            rect_min: [coverage.x_range.0 as f32, coverage.y_range.0 as f32],
+            rect_max: [coverage.x_range.1 as f32, coverage.y_range.1 as f32],
+            lo: lo as f32,
+            hi: hi as f32,
+            _pad: [0.0; 2],
+        }
+    }
+}
+
+// 一个热力图图层的求值纹理、色表与绑定；coverage 为最近一次上传的求值范围
+pub struct HeatmapTarget {
+    pub size: (u32, u32),
+    pub colormap: ColorMap,
+    pub coverage: Option<Coverage>,
+    texture: wgpu::Texture,
+    params: wgpu::Buffer,
+    pub bind_group: wgpu::BindGroup,
+}
+
+impl HeatmapTarget {
+    // 需要重新求值：尚未上传、尺寸或色表变了，或视口超出了覆盖范围
+    pub fn is_stale(&self, view: &ViewInfo, size: (u32, u32), colormap: ColorMap) -> bool {
+        self.size != size || self.colormap != colormap || self.coverage.is_none_or(|c| !c.covers(view))
+    }
+}
+
+// 绘制流水线的绑定布局 (group 1：参数 + 求值纹理 + 色表)
+pub struct HeatmapRenderer {
+    pub layout: wgpu::BindGroupLayout,
+}
+
+impl HeatmapRenderer {
+    pub fn new(device: &wgpu::Device) -> Self {
+        // R32Float 不可过滤，片元着色器用 textureLoad 自行插值；色表同样按下标读取
+        let texture = |binding, filterable| wgpu::BindGroupLayoutEntry {
+            binding, visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture { sample_type: wgpu::TextureSampleType::Float { filterable }, view_dimension: wgpu::TextureViewDimension::D2, multisampled: false },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Heatmap Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0, visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Uniform, has_dynamic_offset: false, min_binding_size: None },
+                    count: None,
+                },
+                texture(1, false),
+                texture(2, true),
+            ],
+        });
+        Self { layout }
+    }
+
+    pub fn create_target(&self, device: &wgpu::Device, queue: &wgpu::Queue, colormap: ColorMap, (width, height): (u32, u32)) -> HeatmapTarget {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Heatmap Texture"),
+            size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FIELD_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let lut = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("Heatmap LUT"),
+                size: wgpu::Extent3d { width: LUT_SIZE as u32, height: 1, depth_or_array_layers: 1 },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            bytemuck::cast_slice(&colormap.bake()),
+        );
+        let params = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Heatmap Params"), size: size_of::<HeatmapParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST, mapped_at_creation: false,
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let lut_view = lut.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Heatmap BG"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: params.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(&view) },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::TextureView(&lut_view) },
+            ],
+        });
+        HeatmapTarget { size: (width, height), colormap, coverage: None, texture, params, bind_group }
+    }
+
+    // 上传一次求值结果 (values 按行排列，长度为 size.0 × size.1)
+    pub fn upload(&self, queue: &wgpu::Queue, target: &mut HeatmapTarget, values: &[f32], coverage: Coverage, range: (f64, f64)) {
+        let (width, height) = target.size;
+        queue.write_texture(
+            target.texture.as_image_copy(),
+            bytemuck::cast_slice(values),
+            wgpu::TexelCopyBufferLayout { offset: 0, bytes_per_row: Some(width * 4), rows_per_image: Some(height) },
+            wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+        );
+        queue.write_buffer(&target.params, 0, bytemuck::bytes_of(&HeatmapParams::new(&coverage, range)));
+        target.coverage = Some(coverage);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(center: (f64, f64), zoom: f64) -> ViewInfo {
+        let range = 2.0 / zoom;
+        ViewInfo {
+            x_range: (center.0 - range, center.0 + range), y_range: (center.1 - range, center.1 + range),
+            zoom, pixels: (400, 400), scale_factor: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_lut_lookup() {
+        assert_eq!(lut_index(0.0), 0);
+        assert_eq!(lut_index(1.0), 255);
+        assert_eq!(lut_index(0.5), 128);
+        assert_eq!(lut_index(-3.0), 0);
+        assert_eq!(lut_index(7.0), 255);
+        assert_eq!(lut_index(f32::NAN), 0);
+
+        // 两端与控制点一致 (8 位量化)
+        let close = |a: [f32; 4], b: [f32; 3]| a.iter().zip(b).all(|(x, y)| (x - y).abs() <= 0.5 / 255.0 + 1e-6);
+        assert!(close(ColorMap::Viridis.sample(0.0), VIRIDIS[0]));
+        assert!(close(ColorMap::Viridis.sample(1.0), VIRIDIS[10]));
+        assert_eq!(ColorMap::Grayscale.sample(0.0), [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(ColorMap::Grayscale.sample(1.0), [1.0; 4]);
+        assert_eq!(ColorMap::Grayscale.sample(0.25)[0], 64.0 / 255.0);
+        // 发散色表的中间项接近白色
+        assert!(ColorMap::Diverging { center: 0.0 }.sample(0.5).iter().all(|&c| c > 0.99));
+
+        // 烘焙结果与逐项查表相同；viridis 亮度单调递增
+        let lut = ColorMap::Viridis.bake();
+        assert_eq!(lut[200].map(|c| c as f32 / 255.0), ColorMap::Viridis.sample(200.0 / 255.0));
+        let luma = |c: [u8; 4]| 0.2126 * c[0] as f32 + 0.7152 * c[1] as f32 + 0.0722 * c[2] as f32;
+        assert!(lut.windows(2).all(|w| luma(w[1]) >= luma(w[0]) - 0.5));
+    }
+
+    #[test]
+    fn test_normalization() {
+        let values = [3.0, f32::NAN, -1.0, f32::INFINITY, 1.0];
+        assert_eq!(resolve_range(ColorMap::Viridis, &values, None), (-1.0, 3.0));
+        assert_eq!(resolve_range(ColorMap::Viridis, &[2.0, 2.0], None), (2.0, 3.0));
+        assert_eq!(resolve_range(ColorMap::Grayscale, &[f32::NAN], None), (0.0, 1.0));
+        assert_eq!(resolve_range(ColorMap::Viridis, &values, Some((0.0, 10.0))), (0.0, 10.0));
+
+        // 发散：关于 center 对称，center 映射到 0.5
+        let range = resolve_range(ColorMap::Diverging { center: 0.0 }, &values, None);
+        assert_eq!(range, (-3.0, 3.0));
+        assert_eq!(normalize(0.0, range), 0.5);
+        let range = resolve_range(ColorMap::Diverging { center: 2.0 }, &values, None);
+        assert_eq!(range, (-1.0, 5.0));
+        assert_eq!(resolve_range(ColorMap::Diverging { center: 1.0 }, &[1.0], None), (0.0, 2.0));
+
+        assert_eq!(normalize(-1.0, (-1.0, 3.0)), 0.0);
+        assert_eq!(normalize(2.0, (-1.0, 3.0)), 0.75);
+        assert_eq!(normalize(9.0, (-1.0, 3.0)), 1.0);
+    }
+
+    #[test]
+    fn test_coverage() {
+        let v = view((0.0, 0.0), 1.0);
+        let c = Coverage::around(&v);
+        assert_eq!(c.x_range, (-3.0, 3.0));
+        assert!(c.covers(&v));
+        // 平移不超过外圈、缩放不超过阈值时复用
+        assert!(c.covers(&view((0.9, -0.9), 1.0)));
+        assert!(c.covers(&view((0.0, 0.0), 1.2)));
+        assert!(!c.covers(&view((1.1, 0.0), 1.0)));
+        assert!(!c.covers(&view((0.0, 0.0), 1.3)));
+        assert!(!c.covers(&view((0.0, 0.0), 0.75)));
+
+        assert_eq!(texture_size((800, 600), 0.5, 8192), (600, 450));
+        assert_eq!(texture_size((800, 600), 0.0, 8192), (1, 1));
+        // 6K 屏 (6016 × 3384) 分辨率 1.0 时宽 9024 超出上限，只截断超出的一边
+        assert_eq!(texture_size((6016, 3384), 1.0, 8192), (8192, 5076));
+        assert_eq!(texture_size((3840, 2160), 2.0, 8192), (8192, 6480));
+        assert_eq!(texture_size((800, 600), f32::INFINITY, 8192), (8192, 8192));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let c = Coverage { x_range: (0.0, 4.0), y_range: (0.0, 2.0), zoom: 1.0 };
+        // 纹素中心：x = 0.5, 1.5, 2.5, 3.5；行 0 在上 (y = 1.5)
+        let values = evaluate(&|x: f64, y: f64| 10.0 * x + y, &Matrix3x3::IDENTITY, &c, (4, 2));
+        assert_eq!(values, vec![6.5, 16.5, 26.5, 36.5, 5.5, 15.5, 25.5, 35.5]);
+
+        // 平移后的对象在局部坐标求值
+        let moved = evaluate(&|x: f64, _| x, &Matrix3x3::from_translation(1.0, 0.0), &c, (4, 2));
+        assert_eq!(&moved[..4], &[-0.5, 0.5, 1.5, 2.5]);
+        let singular = Matrix3x3::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+        assert!(evaluate(&|x: f64, _| x, &singular, &c, (4, 2)).iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    #[should_panic(expected = "heatmap resolution must be finite and non-negative")]
+    fn test_reject_bad_resolution() {
+        let _ = super::super::common::GeoObj::new_heatmap(|x, y| x + y, ColorMap::Viridis).with_heatmap_resolution(f32::NAN);
+    }
+
+    #[test]
+    fn test_params_and_shader() {
+        // 与 WGSL 布局一致：两个 vec2 + lo/hi + 填充，共 32 字节
+        assert_eq!(size_of::<HeatmapParams>(), 32);
+        let p = HeatmapParams::new(&Coverage { x_range: (-1.0, 2.0), y_range: (-3.0, 4.0), zoom: 1.0 }, (0.5, 1.5));
+        let floats: &[f32] = bytemuck::cast_slice(bytemuck::bytes_of(&p));
+        assert_eq!(floats, &[-1.0, -3.0, 2.0, 4.0, 0.5, 1.5, 0.0, 0.0]);
+
+        use wgpu::naga;
+        let module = naga::front::wgsl::parse_str(include_str!("heatmap.wgsl")).unwrap();
+        naga::valid::Validator::new(naga::valid::ValidationFlags::all(), naga::valid::Capabilities::empty())
+            .validate(&module)
+            .unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+// src/graph/d2/heatmap.wgsl
+// 标量场热力图：heatmap.rs 在 CPU 上求值并上传纹理 (覆盖 params 给出的世界矩形)，
+// 这里把屏幕像素映射回纹理坐标，双线性插值后按 [lo, hi] 归一化，查 256×1 的色表
+
+struct ViewUniforms {
+    center: vec2<f32>,
+    zoom: f32,
+    aspect: f32,
+    resolution: vec2<f32>,
+    _pad: vec2<f32>,
+};
+
+struct HeatmapParams {
+    rect_min: vec2<f32>,
+    rect_max: vec2<f32>,
+    lo: f32,
+    hi: f32,
+    _pad: vec2<f32>,
+};
+
+@group(0) @binding(0) var<uniform> view: ViewUniforms;
+@group(1) @binding(0) var<uniform> params: HeatmapParams;
+@group(1) @binding(1) var field: texture_2d<f32>;
+@group(1) @binding(2) var lut: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world: vec2<f32>,
+};
+
+@vertex
+fn vs_heatmap(@builtin(vertex_index) idx: u32) -> VertexOutput {
+    // 全屏三角形，世界坐标的算法同 vs_grid
+    var out: VertexOutput;
+    let x = f32(i32(idx << 1u) & 2) * 2.0 - 1.0;
+    let y = f32(i32(idx) & 2) * 2.0 - 1.0;
+    out.clip_position = vec4<f32>(x, y, 0.0, 1.0);
+    let range_y = 2.0 / view.zoom;
+    out.world = view.center + vec2<f32>(x * range_y * view.aspect, y * range_y);
+    return out;
+}
+
+fn texel(p: vec2<i32>) -> f32 {
+    let size = vec2<i32>(textureDimensions(field));
+    return textureLoad(field, clamp(p, vec2<i32>(0), size - 1), 0).r;
+}
+
+// NaN / Inf (定义域外) 的像素不画
+fn is_finite(v: f32) -> bool {
+    return v == v && abs(v) < 3.0e38;
+}
+
+@fragment
+fn fs_heatmap(in: VertexOutput) -> @location(0) vec4<f32> {
+    let uv = (in.world - params.rect_min) / (params.rect_max - params.rect_min);
+    if (any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0))) { discard; }
+
+    // 纹理行 0 在上边缘，纹素中心在 i + 0.5
+    let p = vec2<f32>(uv.x, 1.0 - uv.y) * vec2<f32>(textureDimensions(field)) - 0.5;
+    let i = vec2<i32>(floor(p));
+    let t = p - floor(p);
+    let top = mix(texel(i), texel(i + vec2<i32>(1, 0)), t.x);
+    let bottom = mix(texel(i + vec2<i32>(0, 1)), texel(i + vec2<i32>(1, 1)), t.x);
+    let v = mix(top, bottom, t.y);
+    if (!is_finite(v)) { discard; }
+
+    // 与 heatmap::normalize / lut_index 一致
+    let s = clamp((v - params.lo) / (params.hi - params.lo), 0.0, 1.0);
+    let index = i32(round(s * 255.0));
+    return vec4<f32>(textureLoad(lut, vec2<i32>(index, 0), 0).rgb, 1.0);
+}
@@ -1,6 +1,6 @@
 // src/implicit.rs
 use rayon::prelude::*;
-use crate::graph::d2::common::Vertex; // 导入公共顶点结构
+use crate::graph::d2::common::{ColoredVertex, Vertex}; // 导入公共顶点结构
 
 pub struct ImplicitSolver {}
 
@@ -41,9 +41,118 @@ impl ImplicitSolver {
         }).collect()
     }
 
+    // 同一函数的多条等值线 f(x, y) = level：网格上每个点只求值一次，各层共用
+    // 网格与交点插值同 solve，单层时与 solve(|x, y| f(x, y) - level) 的结果只差舍入
+    pub fn solve_levels<F>(&self, f: &F, levels: &[f64], x_range: (f64, f64), y_range: (f64, f64), screen_w: u32, screen_h: u32) -> Vec<Vertex>
+    where
+        F: Fn(f64, f64) -> f64 + Sync,
+    {
+        if levels.is_empty() { return Vec::new(); }
+        let limit = 700;
+        let grid_w = (screen_w as usize / 2).clamp(100, limit);
+        let grid_h = (screen_h as usize / 2).clamp(100, limit);
+
+        let x_step = (x_range.1 - x_range.0) / grid_w as f64;
+        let y_step = (y_range.1 - y_range.0) / grid_h as f64;
+
+        // 第 i 列为 x = x_min + i * x_step 上的 grid_h + 1 个值，相邻两列构成一列格子
+        let columns: Vec<Vec<f64>> = (0..=grid_w).into_par_iter().map(|i| {
+            let x = x_range.0 + i as f64 * x_step;
+            (0..=grid_h).map(|j| f(x, y_range.0 + j as f64 * y_step)).collect()
+        }).collect();
+
+        columns.par_windows(2).enumerate().flat_map_iter(|(i, pair)| {
+            let (left, right) = (&pair[0], &pair[1]);
+            let x = x_range.0 + i as f64 * x_step;
+            let mut local_pts = Vec::new();
+            for j in 0..grid_h {
+                let y = y_range.0 + j as f64 * y_step;
+                for &level in levels {
+                    let v00 = left[j] - level;
+                    let v10 = right[j] - level;
+                    let v01 = left[j + 1] - level;
+                    if v00 * v10 <= 0.0 {
+                        let t = self.linear_interp(v00, v10);
+                        local_pts.push(Vertex { position: [(x + t * x_step) as f32, y as f32] });
+                    }
+                    if v00 * v01 <= 0.0 {
+                        let t = self.linear_interp(v00, v01);
+                        local_pts.push(Vertex { position: [x as f32, (y + t * y_step) as f32] });
+                    }
+                }
+            }
+            local_pts
+        }).collect()
+    }
+
+    // 同 solve，并在每个求得的点上求颜色 (如梯度大小、等值线编号、按距离淡出)
+    pub fn solve_colored<F, C>(&self, f: &F, color_fn: &C, x_range: (f64, f64), y_range: (f64, f64), screen_w: u32, screen_h: u32) -> Vec<ColoredVertex>
+    where
+        F: Fn(f64, f64) -> f64 + Sync,
+        C: Fn(f64, f64) -> [f32; 4] + Sync + ?Sized,
+    {
+        self.solve(f, x_range, y_range, screen_w, screen_h).into_par_iter()
+            .map(|v| ColoredVertex { position: v.position, color: color_fn(v.position[0] as f64, v.position[1] as f64) })
+            .collect()
+    }
+
     fn linear_interp(&self, v0: f64, v1: f64) -> f64 {
         let diff = v1 - v0;
         if diff.abs() < 1e-15 { return 0.5; }
         (-v0 / diff).clamp(0.0, 1.0)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_colored() {
+        let solver = ImplicitSolver::new();
+        let circle = |x: f64, y: f64| x * x + y * y - 1.0;
+        let range = (-2.0, 2.0);
+        let plain = solver.solve(&circle, range, range, 400, 400);
+        // 颜色按点的位置求值，点与 solve 一一对应
+        let colored = solver.solve_colored(&circle, &|x: f64, y: f64| [x as f32, y as f32, 0.0, 1.0], range, range, 400, 400);
+        assert_eq!(colored.len(), plain.len());
+        for (c, v) in colored.iter().zip(&plain) {
+            assert_eq!(c.position, v.position);
+            assert_eq!(c.color, [v.position[0], v.position[1], 0.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn test_solve_levels() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let solver = ImplicitSolver::new();
+        let calls = AtomicUsize::new(0);
+        let r2 = |x: f64, y: f64| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            x * x + y * y
+        };
+        let range = (-4.0, 4.0);
+
+        // 单层：与平移后的 solve 逐点一致 (只差舍入)
+        let single = solver.solve_levels(&r2, &[1.0], range, range, 400, 400);
+        let shifted = solver.solve(&|x: f64, y: f64| x * x + y * y - 1.0, range, range, 400, 400);
+        assert_eq!(single.len(), shifted.len());
+        for (a, b) in single.iter().zip(&shifted) {
+            assert!((a.position[0] - b.position[0]).abs() < 1e-6 && (a.position[1] - b.position[1]).abs() < 1e-6);
+        }
+
+        // 三层共用一次网格求值：每个网格点一次，与层数无关
+        calls.store(0, Ordering::Relaxed);
+        let levels = [1.0, 4.0, 9.0];
+        let all = solver.solve_levels(&r2, &levels, range, range, 400, 400);
+        assert_eq!(calls.load(Ordering::Relaxed), 201 * 201);
+        let per_level: Vec<usize> = levels.iter().map(|&l| solver.solve_levels(&r2, &[l], range, range, 400, 400).len()).collect();
+        assert_eq!(all.len(), per_level.iter().sum::<usize>());
+        // 每个点落在某一个同心圆上
+        for v in &all {
+            let r = (v.position[0] as f64).hypot(v.position[1] as f64);
+            assert!([1.0, 2.0, 3.0].iter().any(|&k| (r - k).abs() < 0.05), "r = {}", r);
+        }
+        assert!(solver.solve_levels(&r2, &[], range, range, 400, 400).is_empty());
+    }
+}
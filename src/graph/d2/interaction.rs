@@ -0,0 +1,262 @@
+// src/d2/interaction.rs
+// 2D 视图的鼠标 / 触控板交互：缩放数学、双击检测、双指缩放、像素滚动平滑
+//
+// 手动测试：
+// 1. 鼠标滚轮：每格缩放 LINE_ZOOM_RATIO，光标下的点保持不动
+// 2. 触控板双指滚动 (PixelDelta)：缩放平滑连续，快慢与系统无关
+// 3. 触控板捏合 (PinchGesture) / 触摸屏双指：围绕手势中心缩放
+// 4. 键盘 + / - 围绕窗口中心缩放，0 复位
+// 5. 400ms 内、5px 范围内双击左键：视图复位到初始中心与缩放
+#![allow(dead_code)]
+
+use std::time::{Duration, Instant};
+use winit::event::TouchPhase;
+
+// 滚轮每格 (LineDelta) 的缩放倍率
+pub const LINE_ZOOM_RATIO: f64 = 1.1;
+// 像素滚动：每像素的对数缩放量 (zoom *= exp(rate * px))
+pub const PIXEL_ZOOM_RATE: f64 = 0.0025;
+// 像素滚动平滑的时间常数 (秒)
+pub const SCROLL_SMOOTH_TAU: f64 = 0.06;
+
+pub const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+pub const DOUBLE_CLICK_DIST: f64 = 5.0;
+
+// ====================== 缩放 ======================
+
+// 围绕屏幕像素 anchor 缩放：anchor 下的世界坐标保持不变
+// center/zoom 与 ViewState 一致 (可见高度 = 4 / zoom)；size 为窗口像素尺寸
+// 返回新的 (center, zoom)；factor 非法 (非正或非有限) 时原样返回
+pub fn zoom_about(center: (f64, f64), zoom: f64, factor: f64, anchor: (f64, f64), size: (f64, f64)) -> ((f64, f64), f64) {
+    if !(factor.is_finite() && factor > 0.0) || size.0 <= 0.0 || size.1 <= 0.0 {
+        return (center, zoom);
+    }
+    let aspect = size.0 / size.1;
+    let rel_x = anchor.0 / size.0 - 0.5;
+    let rel_y = 0.5 - anchor.1 / size.1;
+
+    let world_h = 4.0 / zoom;
+    let world_x = center.0 + rel_x * world_h * aspect;
+    let world_y = center.1 + rel_y * world_h;
+
+    let new_zoom = zoom * factor;
+    let new_h = 4.0 / new_zoom;
+    ((world_x - rel_x * new_h * aspect, world_y - rel_y * new_h), new_zoom)
+}
+
+// ====================== 双击 ======================
+
+#[derive(Default)]
+pub struct ClickTracker {
+    last: Option<(Instant, (f64, f64))>,
+}
+
+impl ClickTracker {
+    // 记录一次按下；与上一次按下构成双击时返回 true (并清空，第三次按下不会再触发)
+    pub fn press(&mut self, now: Instant, pos: (f64, f64)) -> bool {
+        if let Some((t, p)) = self.last.take() {
+            let close = (pos.0 - p.0).hypot(pos.1 - p.1) <= DOUBLE_CLICK_DIST;
+            if close && now.saturating_duration_since(t) <= DOUBLE_CLICK_TIME {
+                return true;
+            }
+        }
+        self.last = Some((now, pos));
+        false
+    }
+}
+
+// ====================== 触摸 ======================
+
+// 若干点的质心
+pub fn centroid(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    if points.is_empty() {
+        return None;
+    }
+    let n = points.len() as f64;
+    let (sx, sy) = points.iter().fold((0.0, 0.0), |(sx, sy), p| (sx + p.0, sy + p.1));
+    Some((sx / n, sy / n))
+}
+
+// 各点到质心的平均距离 (捏合手势的 "张开程度")
+pub fn spread(points: &[(f64, f64)]) -> Option<f64> {
+    let c = centroid(points)?;
+    let n = points.len() as f64;
+    Some(points.iter().map(|p| (p.0 - c.0).hypot(p.1 - c.1)).sum::<f64>() / n)
+}
+
+// 多点触摸跟踪：两指及以上移动时给出 (质心, 缩放倍率)
+#[derive(Default)]
+pub struct TouchTracker {
+    points: Vec<(u64, (f64, f64))>,
+}
+
+impl TouchTracker {
+    pub fn update(&mut self, id: u64, phase: TouchPhase, pos: (f64, f64)) -> Option<((f64, f64), f64)> {
+        let before = self.pinch_spread();
+        match phase {
+            TouchPhase::Started => {
+                self.points.retain(|(i, _)| *i != id);
+                self.points.push((id, pos));
+            }
+            TouchPhase::Moved => {
+                if let Some(p) = self.points.iter_mut().find(|(i, _)| *i == id) {
+                    p.1 = pos;
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => self.points.retain(|(i, _)| *i != id),
+        }
+        if phase != TouchPhase::Moved {
+            return None; // 手指增减时质心跳变，不产生缩放
+        }
+        let (before, after) = (before?, self.pinch_spread()?);
+        let positions: Vec<(f64, f64)> = self.points.iter().map(|(_, p)| *p).collect();
+        if before > 0.0 { Some((centroid(&positions)?, after / before)) } else { None }
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.points.len()
+    }
+
+    fn pinch_spread(&self) -> Option<f64> {
+        if self.points.len() < 2 {
+            return None;
+        }
+        let positions: Vec<(f64, f64)> = self.points.iter().map(|(_, p)| *p).collect();
+        spread(&positions)
+    }
+}
+
+// ====================== 像素滚动平滑 ======================
+
+// PixelDelta 先累积为待应用的对数缩放量，每帧按 dt 指数衰减地释放
+// 释放量之和恰等于累积量，所以总缩放只取决于滚动距离，与帧率无关
+#[derive(Default)]
+pub struct ScrollSmoother {
+    pending: f64,
+    anchor: (f64, f64),
+}
+
+impl ScrollSmoother {
+    pub fn push_pixels(&mut self, dy: f64, anchor: (f64, f64)) {
+        self.pending += dy * PIXEL_ZOOM_RATE;
+        self.anchor = anchor;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.pending != 0.0
+    }
+
+    // 推进 dt 秒，返回本帧的 (缩放倍率, 锚点)
+    pub fn step(&mut self, dt: f64) -> Option<(f64, (f64, f64))> {
+        if !self.is_active() {
+            return None;
+        }
+        let take = if self.pending.abs() < 1e-4 {
+            self.pending // 余量很小时一次释放完
+        } else {
+            self.pending * (1.0 - (-dt.clamp(0.0, 0.1) / SCROLL_SMOOTH_TAU).exp())
+        };
+        self.pending -= take;
+        Some((take.exp(), self.anchor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_about_keeps_anchor_fixed() {
+        let size = (800.0, 600.0);
+        let to_world = |c: (f64, f64), z: f64, p: (f64, f64)| {
+            let h = 4.0 / z;
+            (c.0 + (p.0 / size.0 - 0.5) * h * size.0 / size.1, c.1 + (0.5 - p.1 / size.1) * h)
+        };
+        let (center, zoom) = ((1.0, -2.0), 0.7);
+        let anchor = (123.0, 456.0);
+        let before = to_world(center, zoom, anchor);
+        let (c2, z2) = zoom_about(center, zoom, 1.5, anchor, size);
+        let after = to_world(c2, z2, anchor);
+        assert!((z2 - 1.05).abs() < 1e-12);
+        assert!((before.0 - after.0).abs() < 1e-12 && (before.1 - after.1).abs() < 1e-12);
+
+        // 围绕窗口中心缩放：中心不动
+        let (c3, _) = zoom_about(center, zoom, 2.0, (400.0, 300.0), size);
+        assert!((c3.0 - 1.0).abs() < 1e-12 && (c3.1 + 2.0).abs() < 1e-12);
+
+        // 非法倍率被忽略
+        assert_eq!(zoom_about(center, zoom, 0.0, anchor, size), (center, zoom));
+        assert_eq!(zoom_about(center, zoom, f64::NAN, anchor, size), (center, zoom));
+    }
+
+    #[test]
+    fn test_double_click_timing() {
+        let t0 = Instant::now();
+        let ms = |m| t0 + Duration::from_millis(m);
+
+        let mut c = ClickTracker::default();
+        assert!(!c.press(t0, (10.0, 10.0)));
+        assert!(c.press(ms(300), (13.0, 14.0)));
+        // 第三次按下不再触发
+        assert!(!c.press(ms(350), (13.0, 14.0)));
+
+        // 太慢
+        let mut c = ClickTracker::default();
+        assert!(!c.press(t0, (0.0, 0.0)));
+        assert!(!c.press(ms(401), (0.0, 0.0)));
+        // 慢的那次成为新的第一次
+        assert!(c.press(ms(700), (0.0, 0.0)));
+
+        // 太远
+        let mut c = ClickTracker::default();
+        assert!(!c.press(t0, (0.0, 0.0)));
+        assert!(!c.press(ms(100), (4.0, 3.1)));
+    }
+
+    #[test]
+    fn test_centroid_and_spread() {
+        assert_eq!(centroid(&[]), None);
+        assert_eq!(centroid(&[(0.0, 0.0), (4.0, 2.0)]), Some((2.0, 1.0)));
+        assert_eq!(spread(&[(0.0, 0.0), (6.0, 8.0)]), Some(5.0));
+    }
+
+    #[test]
+    fn test_touch_pinch() {
+        let mut t = TouchTracker::default();
+        assert_eq!(t.update(1, TouchPhase::Started, (100.0, 100.0)), None);
+        // 单指移动：无缩放
+        assert_eq!(t.update(1, TouchPhase::Moved, (110.0, 100.0)), None);
+        assert_eq!(t.update(2, TouchPhase::Started, (210.0, 100.0)), None);
+        assert_eq!(t.active_count(), 2);
+
+        // 两指距离 100 -> 200，质心 (160, 100) -> (210, 100)
+        let (c, f) = t.update(2, TouchPhase::Moved, (310.0, 100.0)).unwrap();
+        assert!((f - 2.0).abs() < 1e-12);
+        assert_eq!(c, (210.0, 100.0));
+
+        t.update(2, TouchPhase::Ended, (310.0, 100.0));
+        assert_eq!(t.active_count(), 1);
+        assert_eq!(t.update(1, TouchPhase::Moved, (0.0, 0.0)), None);
+    }
+
+    #[test]
+    fn test_scroll_smoothing_conserves_total() {
+        let mut s = ScrollSmoother::default();
+        s.push_pixels(120.0, (5.0, 6.0));
+        s.push_pixels(80.0, (7.0, 8.0));
+
+        let mut total = 1.0;
+        let mut frames = 0;
+        while let Some((f, anchor)) = s.step(1.0 / 60.0) {
+            assert!(f >= 1.0);
+            assert_eq!(anchor, (7.0, 8.0));
+            total *= f;
+            frames += 1;
+            assert!(frames < 1000);
+        }
+        // 多帧平滑释放，总量等于一次性缩放
+        assert!(frames > 3);
+        assert!((total.ln() - 200.0 * PIXEL_ZOOM_RATE).abs() < 1e-12);
+        assert!(!s.is_active());
+    }
+}
@@ -0,0 +1,432 @@
+// src/graph/d2/intersection.rs
+// 交点标注：两个对象的交点画成固定像素大小的标记点，并附坐标文字
+//   解析通道：两者都有解析形式 (圆锥曲线 / 直线) 时用闭式解 (line520、Conic::intersect_conic)
+//   数值通道：闭包对象先在采样折线上找符号变化 / 折线相交，再在原闭包上二分细化
+#![allow(dead_code)]
+
+use bytemuck::{Pod, Zeroable};
+
+use super::common::{Analytic, GeoObj, GeoType, ViewInfo};
+use super::text::Annotation;
+use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
+use crate::math_forest::geometry::d2::intersection::line520;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+
+// 标记与坐标文字的颜色 (与曲线颜色无关，一眼能认出是交点)
+pub const MARKER_COLOR: [f32; 4] = [1.0, 0.85, 0.2, 1.0];
+pub const LABEL_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.9];
+
+// 数值通道：沿参数曲线的采样数 (即折线段数)
+const CURVE_SAMPLES: usize = 1024;
+// 两个隐函数求交时，在视口上铺的网格 (每个方向的格数)
+const FIELD_GRID: usize = 256;
+// 二分 / 牛顿细化的最大迭代次数
+const REFINE_ITER: usize = 64;
+
+// 交点标记的实例数据 (世界坐标，半径在着色器中按像素固定)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct MarkerInstance {
+    pub position: [f32; 2],
+    pub color: [f32; 4],
+}
+
+// 一对需要标注交点的对象：任一对象或视图变化后重算 points
+pub struct IntersectionPair {
+    pub a: usize,
+    pub b: usize,
+    pub points: Vec<Vec2>,
+    pub(crate) dirty: bool,
+}
+
+impl IntersectionPair {
+    pub fn new(a: usize, b: usize) -> Self {
+        Self { a, b, points: Vec::new(), dirty: true }
+    }
+
+    pub fn involves(&self, id: usize) -> bool {
+        self.a == id || self.b == id
+    }
+
+    pub fn markers(&self) -> impl Iterator<Item = MarkerInstance> + '_ {
+        self.points.iter().map(|p| MarkerInstance { position: p.to_f32_array(), color: MARKER_COLOR })
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = Annotation> + '_ {
+        self.points.iter().map(|p| Annotation {
+            pos: (p.x, p.y),
+            text: format!("({:.3}, {:.3})", p.x, p.y),
+            color: LABEL_COLOR,
+        })
+    }
+}
+
+// ====================== 入口 ======================
+
+// 两个对象在当前视口下的交点 (数值通道只在可见区域内搜索)
+// 相距不到半个像素的交点合并为一个
+pub fn intersect_objects(a: &GeoObj, b: &GeoObj, view: &ViewInfo) -> Vec<Vec2> {
+    let points = match (analytic_of(a), analytic_of(b)) {
+        (Some(pa), Some(pb)) => intersect_analytic(&pa, &pb),
+        _ => intersect_numeric(a, b, view),
+    };
+    dedup(points, view.pixel_size() * 0.5)
+}
+
+// 解析形式 (已施加对象的仿射变换)
+fn analytic_of(obj: &GeoObj) -> Option<Analytic> {
+    let analytic = obj.analytic?;
+    Some(if obj.transform == Matrix3x3::IDENTITY { analytic } else { analytic.transformed(&obj.transform) })
+}
+
+fn dedup(points: Vec<Vec2>, tol: f64) -> Vec<Vec2> {
+    let mut out: Vec<Vec2> = Vec::with_capacity(points.len());
+    for p in points {
+        if !(p.x.is_finite() && p.y.is_finite()) { continue; }
+        if out.iter().all(|q| (p - *q).len() > tol) {
+            out.push(p);
+        }
+    }
+    out
+}
+
+// ====================== 解析通道 ======================
+
+// 无实交点 / 重合时结果为空 (求交函数以 NaN 表示，统一在 dedup 中滤掉)
+pub fn intersect_analytic(a: &Analytic, b: &Analytic) -> Vec<Vec2> {
+    match (a, b) {
+        (Analytic::Conic(ca), Analytic::Conic(cb)) => {
+            let q = ca.intersect_conic(cb);
+            [q.n1, q.n2, q.n3, q.n4].iter()
+                .filter(|z| !z.is_nan())
+                .map(|z| Vec2::new(z.re, z.im))
+                .collect()
+        }
+        (Analytic::Conic(c), Analytic::Line(l)) | (Analytic::Line(l), Analytic::Conic(c)) => {
+            let dp = line520::x_conic_line(c, l);
+            vec![dp.p1, dp.p2]
+        }
+        (Analytic::Line(la), Analytic::Line(lb)) => {
+            if la.is_parallel(lb) { return Vec::new(); }
+            vec![line520::x_line_line(la, lb)]
+        }
+    }
+}
+
+// ====================== 数值通道 ======================
+
+// 对象的两种数值视角：标量场 F(x, y) (零点集即曲线) 与参数曲线 c(t)
+// 显函数两者兼有；隐函数只有场，参数方程只有曲线
+type Field<'a> = Box<dyn Fn(f64, f64) -> f64 + 'a>;
+type Curve<'a> = Box<dyn Fn(f64) -> Vec2 + 'a>;
+
+fn field_of<'a>(obj: &'a GeoObj, view: &ViewInfo) -> Option<Field<'a>> {
+    let inv = obj.transform.inverse()?;
+    let local = move |x: f64, y: f64| inv.transform_point2(Vec2::new(x, y));
+    match &obj.geo_type {
+        GeoType::Implicit(f) | GeoType::ImplicitColored(f, _) => Some(Box::new(move |x, y| { let q = local(x, y); f(q.x, q.y) })),
+        // 各层之积 Π (f - level) 的零点集即所有等值线的并，穿过每条线时变号
+        GeoType::LevelSet(f, levels) => Some(Box::new(move |x, y| {
+            let q = local(x, y);
+            let v = f(q.x, q.y);
+            levels.iter().map(|l| v - l).product()
+        })),
+        GeoType::ImplicitDetailed(f) => {
+            let detail = view.pixel_size() * inv.det().abs().sqrt();
+            Some(Box::new(move |x, y| { let q = local(x, y); f(q.x, q.y, detail) }))
+        }
+        // y - f(x)：定义域外为 NaN，不会被当成符号变化
+        GeoType::Explicit(f, domain) => Some(Box::new(move |x, y| {
+            let q = local(x, y);
+            if q.x < domain.0 || q.x > domain.1 { f64::NAN } else { q.y - f(q.x) }
+        })),
+        _ => None,
+    }
+}
+
+fn curve_of<'a>(obj: &'a GeoObj, view: &ViewInfo) -> Option<(Curve<'a>, (f64, f64))> {
+    let m = obj.transform;
+    let apply = move |(x, y): (f64, f64)| m.transform_point2(Vec2::new(x, y));
+    match &obj.geo_type {
+        GeoType::Parametric(f, t_range) => Some((Box::new(move |t| apply(f(t))), *t_range)),
+        GeoType::ParametricDynamic(f, t_range_fn) => Some((Box::new(move |t| apply(f(t))), t_range_fn(view))),
+        GeoType::Explicit(f, domain) => {
+            let (t0, t1) = view.x_range_under(&m);
+            Some((Box::new(move |t| apply((t, f(t)))), (t0.max(domain.0), t1.min(domain.1))))
+        }
+        _ => None,
+    }
+}
+
+pub fn intersect_numeric(a: &GeoObj, b: &GeoObj, view: &ViewInfo) -> Vec<Vec2> {
+    // 优先“曲线 vs 场”：一维符号变化 + 二分，最可靠
+    if let (Some((c, range)), Some(f)) = (curve_of(a, view), field_of(b, view)) {
+        return curve_field(&c, range, &f);
+    }
+    if let (Some(f), Some((c, range))) = (field_of(a, view), curve_of(b, view)) {
+        return curve_field(&c, range, &f);
+    }
+    if let (Some((ca, ra)), Some((cb, rb))) = (curve_of(a, view), curve_of(b, view)) {
+        return curve_curve(&ca, ra, &cb, rb);
+    }
+    if let (Some(fa), Some(fb)) = (field_of(a, view), field_of(b, view)) {
+        return field_field(&fa, &fb, view);
+    }
+    Vec::new()
+}
+
+// t 轴上的均匀采样
+fn sample_params((t0, t1): (f64, f64)) -> impl Iterator<Item = f64> {
+    (0..=CURVE_SAMPLES).map(move |i| t0 + (t1 - t0) * i as f64 / CURVE_SAMPLES as f64)
+}
+
+// 曲线与场：d(t) = F(c(t)) 的符号变化即穿越零点集
+// 二分后 |d| 反而比两端大的是极点 (如 tan 的跳变) 而非交点，丢弃
+fn curve_field(c: &Curve, range: (f64, f64), f: &Field) -> Vec<Vec2> {
+    if !(range.0.is_finite() && range.1.is_finite()) || range.0 >= range.1 { return Vec::new(); }
+    let d = |t: f64| { let p = c(t); f(p.x, p.y) };
+
+    let samples: Vec<(f64, f64)> = sample_params(range).map(|t| (t, d(t))).collect();
+    let mut points = Vec::new();
+    for w in samples.windows(2) {
+        let ((t0, d0), (t1, d1)) = (w[0], w[1]);
+        if !(d0.is_finite() && d1.is_finite()) { continue; }
+        if d0 == 0.0 {
+            points.push(c(t0));
+            continue;
+        }
+        if d0 * d1 >= 0.0 { continue; }
+        let t = bisect(&d, t0, t1, d0);
+        if d(t).abs() <= d0.abs().max(d1.abs()) {
+            points.push(c(t));
+        }
+    }
+    // 最后一个采样点恰为零点
+    if let Some(&(t, dt)) = samples.last() && dt == 0.0 {
+        points.push(c(t));
+    }
+    points
+}
+
+// 在 [lo, hi] 上二分 g 的变号点 (g_lo 为 g(lo)，与 g(hi) 异号)
+fn bisect(g: &dyn Fn(f64) -> f64, mut lo: f64, mut hi: f64, mut g_lo: f64) -> f64 {
+    for _ in 0..REFINE_ITER {
+        let mid = 0.5 * (lo + hi);
+        if mid <= lo || mid >= hi { break; }
+        let g_mid = g(mid);
+        if g_mid == 0.0 { return mid; }
+        if (g_mid < 0.0) == (g_lo < 0.0) {
+            lo = mid;
+            g_lo = g_mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+// 两条参数曲线：采样折线两两求交，再对相交的一对线段同时二分参数区间
+fn curve_curve(ca: &Curve, ra: (f64, f64), cb: &Curve, rb: (f64, f64)) -> Vec<Vec2> {
+    let valid = |r: (f64, f64)| r.0.is_finite() && r.1.is_finite() && r.0 < r.1;
+    if !valid(ra) || !valid(rb) { return Vec::new(); }
+
+    let pa: Vec<(f64, Vec2)> = sample_params(ra).map(|t| (t, ca(t))).collect();
+    let pb: Vec<(f64, Vec2)> = sample_params(rb).map(|t| (t, cb(t))).collect();
+
+    let mut points = Vec::new();
+    for wa in pa.windows(2) {
+        let (a0, a1) = (wa[0].1, wa[1].1);
+        for wb in pb.windows(2) {
+            let (b0, b1) = (wb[0].1, wb[1].1);
+            if segment_cross(a0, a1, b0, b1).is_none() { continue; }
+            points.push(refine_curve_curve(ca, (wa[0].0, wa[1].0), cb, (wb[0].0, wb[1].0)));
+        }
+    }
+    points
+}
+
+// 线段 p0p1 与 q0q1 的交点 (含端点)；平行、不相交或含非有限点时为 None
+fn segment_cross(p0: Vec2, p1: Vec2, q0: Vec2, q1: Vec2) -> Option<Vec2> {
+    // 包围盒快速排除
+    if p0.x.max(p1.x) < q0.x.min(q1.x) || q0.x.max(q1.x) < p0.x.min(p1.x)
+        || p0.y.max(p1.y) < q0.y.min(q1.y) || q0.y.max(q1.y) < p0.y.min(p1.y) {
+        return None;
+    }
+    let (r, s) = (p1 - p0, q1 - q0);
+    let denom = r.cross(s);
+    if !denom.is_finite() || denom == 0.0 { return None; }
+    let d = q0 - p0;
+    let u = d.cross(s) / denom;
+    let v = d.cross(r) / denom;
+    let eps = 1e-9;
+    ((-eps..=1.0 + eps).contains(&u) && (-eps..=1.0 + eps).contains(&v)).then(|| p0 + r * u)
+}
+
+// 每轮把两段参数区间各二分一次，保留弦仍相交的那一对子区间
+// 弦与曲线的偏差随区间长度平方缩小，最后一对弦的交点即为交点
+fn refine_curve_curve(ca: &Curve, (mut a0, mut a1): (f64, f64), cb: &Curve, (mut b0, mut b1): (f64, f64)) -> Vec2 {
+    let (mut pa0, mut pa1, mut pb0, mut pb1) = (ca(a0), ca(a1), cb(b0), cb(b1));
+    for _ in 0..REFINE_ITER {
+        let (am, bm) = (0.5 * (a0 + a1), 0.5 * (b0 + b1));
+        if am <= a0 || am >= a1 || bm <= b0 || bm >= b1 { break; }
+        let (pam, pbm) = (ca(am), cb(bm));
+        let candidates = [
+            ((a0, am, pa0, pam), (b0, bm, pb0, pbm)),
+            ((a0, am, pa0, pam), (bm, b1, pbm, pb1)),
+            ((am, a1, pam, pa1), (b0, bm, pb0, pbm)),
+            ((am, a1, pam, pa1), (bm, b1, pbm, pb1)),
+        ];
+        let Some(&(sa, sb)) = candidates.iter().find(|(sa, sb)| segment_cross(sa.2, sa.3, sb.2, sb.3).is_some()) else { break };
+        (a0, a1, pa0, pa1) = sa;
+        (b0, b1, pb0, pb1) = sb;
+    }
+    segment_cross(pa0, pa1, pb0, pb1).unwrap_or((pa0 + pa1) * 0.5)
+}
+
+// 两个标量场：在视口网格上找两者同时变号、且线性插值线段相交的格子作为初值，
+// 再对方程组 F = G = 0 做牛顿迭代 (差分雅可比)；收敛点须留在初值附近
+fn field_field(fa: &Field, fb: &Field, view: &ViewInfo) -> Vec<Vec2> {
+    let n = FIELD_GRID;
+    let (x0, x1) = view.x_range;
+    let (y0, y1) = view.y_range;
+    let (hx, hy) = ((x1 - x0) / n as f64, (y1 - y0) / n as f64);
+    let node = |i: usize, j: usize| Vec2::new(x0 + hx * i as f64, y0 + hy * j as f64);
+
+    let grid = |f: &Field| -> Vec<f64> {
+        (0..=n).flat_map(|j| (0..=n).map(move |i| (i, j))).map(|(i, j)| { let p = node(i, j); f(p.x, p.y) }).collect()
+    };
+    let (ga, gb) = (grid(fa), grid(fb));
+
+    let mut points = Vec::new();
+    for j in 0..n {
+        for i in 0..n {
+            let (Some(sa), Some(sb)) = (cell_segment(&ga, i, j, n, &node), cell_segment(&gb, i, j, n, &node)) else { continue };
+            let Some(seed) = segment_cross(sa.0, sa.1, sb.0, sb.1) else { continue };
+            if let Some(p) = newton2(fa, fb, seed, hx.max(hy)) {
+                points.push(p);
+            }
+        }
+    }
+    points
+}
+
+// 一个格子内零点集的线性近似 (marching squares 取前两个边交点)
+fn cell_segment(g: &[f64], i: usize, j: usize, n: usize, node: &dyn Fn(usize, usize) -> Vec2) -> Option<(Vec2, Vec2)> {
+    let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+    let value = |(ci, cj): (usize, usize)| g[cj * (n + 1) + ci];
+    let mut hits = [Vec2::NAN; 2];
+    let mut count = 0;
+    for k in 0..4 {
+        let (c0, c1) = (corners[k], corners[(k + 1) % 4]);
+        let (v0, v1) = (value(c0), value(c1));
+        if !(v0.is_finite() && v1.is_finite()) || (v0 < 0.0) == (v1 < 0.0) { continue; }
+        let s = v0 / (v0 - v1);
+        if count < 2 {
+            hits[count] = node(c0.0, c0.1) + (node(c1.0, c1.1) - node(c0.0, c0.1)) * s;
+        }
+        count += 1;
+    }
+    (count >= 2).then_some((hits[0], hits[1]))
+}
+
+fn newton2(fa: &Field, fb: &Field, seed: Vec2, cell: f64) -> Option<Vec2> {
+    let h = cell * 1e-4;
+    let mut p = seed;
+    for _ in 0..REFINE_ITER {
+        let (a, b) = (fa(p.x, p.y), fb(p.x, p.y));
+        if a == 0.0 && b == 0.0 { break; }
+        let (ax, ay) = ((fa(p.x + h, p.y) - a) / h, (fa(p.x, p.y + h) - a) / h);
+        let (bx, by) = ((fb(p.x + h, p.y) - b) / h, (fb(p.x, p.y + h) - b) / h);
+        let det = ax * by - ay * bx;
+        if !det.is_finite() || det == 0.0 { return None; }
+        let step = Vec2::new((a * by - b * ay) / det, (b * ax - a * bx) / det);
+        p -= step;
+        if step.len() <= 1e-14 * (1.0 + p.len()) { break; }
+    }
+    ((p - seed).len() <= 2.0 * cell && p.x.is_finite() && p.y.is_finite()).then_some(p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_forest::geometry::d2::conic::conic::Conic;
+    use crate::math_forest::geometry::d2::linear::line::Line;
+
+    fn view() -> ViewInfo {
+        ViewInfo { x_range: (-4.0, 4.0), y_range: (-4.0, 4.0), zoom: 0.5, pixels: (400, 400), scale_factor: 1.0 }
+    }
+
+    fn sorted_x(mut points: Vec<Vec2>) -> Vec<Vec2> {
+        points.sort_by(|p, q| p.x.total_cmp(&q.x));
+        points
+    }
+
+    #[test]
+    fn test_numeric_parabola_and_line() {
+        // y = x^2 与 y = 1：两个闭包对象，走数值通道
+        let a = GeoObj::new_explicit(|x| x * x, [1.0; 4], 2.0);
+        let b = GeoObj::new_explicit(|_| 1.0, [1.0; 4], 2.0);
+        let pts = sorted_x(intersect_objects(&a, &b, &view()));
+        assert_eq!(pts.len(), 2, "{:?}", pts);
+        assert!((pts[0].x + 1.0).abs() < 1e-6 && (pts[0].y - 1.0).abs() < 1e-6);
+        assert!((pts[1].x - 1.0).abs() < 1e-6 && (pts[1].y - 1.0).abs() < 1e-6);
+
+        // 参数曲线 vs 参数曲线 (折线相交 + 参数二分)
+        let a = GeoObj::new_parametric(|t| (t, t * t), (-3.0, 3.0), [1.0; 4], 2.0);
+        let b = GeoObj::new_parametric(|t| (t, 1.0), (-3.0, 3.0), [1.0; 4], 2.0);
+        let pts = sorted_x(intersect_objects(&a, &b, &view()));
+        assert_eq!(pts.len(), 2, "{:?}", pts);
+        assert!((pts[0].x + 1.0).abs() < 1e-6 && (pts[1].x - 1.0).abs() < 1e-6);
+
+        // 两个隐函数：单位圆与 x = y
+        let a = GeoObj::new_implicit(|x, y| x * x + y * y - 1.0, [1.0; 4], 2.0);
+        let b = GeoObj::new_implicit(|x, y| x - y, [1.0; 4], 2.0);
+        let pts = sorted_x(intersect_objects(&a, &b, &view()));
+        assert_eq!(pts.len(), 2, "{:?}", pts);
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        assert!((pts[0].x + h).abs() < 1e-6 && (pts[1].y - h).abs() < 1e-6);
+
+        // 等值线组与直线：与 y = 0 交于每个同心圆的两侧
+        let a = GeoObj::new_level_curves(|x, y| x * x + y * y, &[1.0, 4.0], [1.0; 4], 2.0);
+        let b = GeoObj::new_explicit(|_| 0.0, [1.0; 4], 2.0);
+        let pts = sorted_x(intersect_objects(&a, &b, &view()));
+        assert_eq!(pts.len(), 4, "{:?}", pts);
+        for (p, x) in pts.iter().zip([-2.0, -1.0, 1.0, 2.0]) {
+            assert!((p.x - x).abs() < 1e-6 && p.y.abs() < 1e-6, "{:?}", p);
+        }
+    }
+
+    #[test]
+    fn test_numeric_rejects_poles() {
+        // y = tan x 在极点处变号，但与 y = 100 的交点只在每个分支上各有一个
+        let a = GeoObj::new_explicit(f64::tan, [1.0; 4], 2.0);
+        let b = GeoObj::new_explicit(|_| 100.0, [1.0; 4], 2.0);
+        let pts = intersect_objects(&a, &b, &view());
+        assert!(!pts.is_empty());
+        assert!(pts.iter().all(|p| (p.x.tan() - 100.0).abs() < 1e-6), "{:?}", pts);
+    }
+
+    #[test]
+    fn test_analytic_conic_and_line() {
+        // 单位圆与 y = 0.5，圆经平移 (1, 0)
+        let circle = GeoObj::new_conic(Conic::new(1.0, 0.0, 1.0, 0.0, 0.0, -1.0), [1.0; 4], 2.0)
+            .with_transform(Matrix3x3::from_translation(1.0, 0.0));
+        let line = GeoObj::new_line(Line::new(Vec2::new(0.0, 0.5), Vec2::new(1.0, 0.0)), [1.0; 4], 2.0);
+        let pts = sorted_x(intersect_objects(&circle, &line, &view()));
+        let dx = 0.75f64.sqrt();
+        assert_eq!(pts.len(), 2);
+        assert!((pts[0] - Vec2::new(1.0 - dx, 0.5)).len() < 1e-9);
+        assert!((pts[1] - Vec2::new(1.0 + dx, 0.5)).len() < 1e-9);
+
+        // 两圆 (圆锥曲线求交) 与两直线
+        let other = GeoObj::new_conic(Conic::new(1.0, 0.0, 1.0, 0.0, 0.0, -1.0), [1.0; 4], 2.0);
+        let pts = sorted_x(intersect_objects(&circle, &other, &view()));
+        assert_eq!(pts.len(), 2);
+        assert!(pts.iter().all(|p| (p.x - 0.5).abs() < 1e-9 && (p.y.abs() - dx).abs() < 1e-9));
+
+        let diagonal = GeoObj::new_line(Line::new(Vec2::ZERO, Vec2::new(1.0, 1.0)), [1.0; 4], 2.0);
+        let pts = intersect_objects(&line, &diagonal, &view());
+        assert_eq!(pts.len(), 1);
+        assert!((pts[0] - Vec2::new(0.5, 0.5)).len() < 1e-12);
+    }
+}
@@ -1,23 +1,47 @@
 // src/giac
 #[allow(dead_code)]
 
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::mpsc::Receiver;
 use std::time::Instant;
 use winit::application::ApplicationHandler;
 use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
-use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::keyboard::Key;
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
 use winit::window::{Window, WindowId};
 use wgpu::util::DeviceExt;
 use bytemuck::{Pod, Zeroable};
 
-use super::common::{Vertex, GeoObj, GeoType};
+use super::common::{ColoredVertex, Vertex, GeoObj, GeoType, ViewInfo};
 use super::implicit::ImplicitSolver;
-use super::parametric::ParametricSolver;
+use super::parametric::{CurveScalar, ParametricSolver, JUMP_THRESHOLD_FACTOR};
 use super::explicit::ExplicitSolver;
+use super::text::{self, Annotation, TextVertex};
+use super::arena::{align_up, VertexArena};
+use super::gradient::{Coloring, GradientUniform};
+use super::watch;
+use super::svg;
+use super::series::{self, ChannelId, SeriesHandle, SeriesTessellation};
+use super::intersection::{self, IntersectionPair, MarkerInstance};
+use super::scatter::{self, PointInstance, POINT_ATTRIBUTES};
+use super::animation::Animation;
+use super::gpu_field::{FieldEvaluator, FieldTarget};
+use super::heatmap::{self, Coverage, HeatmapRenderer, HeatmapTarget};
+use super::interaction::{self, ClickTracker, ScrollSmoother, TouchTracker, LINE_ZOOM_RATIO};
+use crate::graph::session::{ObjectDesc, Session};
+use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
 
 // 4x MSAA
 const SAMPLE_COUNT: u32 = 4; // 4倍采样，效果通常足够好
 
+// 按 S 键导出 SVG 的文件名 (工作目录下)
+const SVG_EXPORT_PATH: &str = "forest.svg";
+
+// 图层顶点共享缓冲的初始容量 (字节)，不够时倍增
+const VERTEX_ARENA_INITIAL: u64 = 64 * 1024;
+
 // 全局 Uniform (注意对齐)
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
@@ -34,7 +58,8 @@ struct ViewUniforms {
 struct StyleUniform {
     color: [f32; 4],
     width: f32,
-    _padding: [f32; 3],
+    shape: u32, // 散点的标记形状 (MarkerShape::code)
+    _padding: [f32; 2],
 }
 
 struct ViewState {
@@ -44,13 +69,45 @@ struct ViewState {
     is_dragging: bool,
     last_mouse_pos: Option<(f64, f64)>,
     dirty: bool,
+    scale_factor: f64, // 窗口缩放比：线宽与点大小按逻辑点换算为物理像素
+    follow: bool,      // 有时间序列时让最新采样保持在右边缘 (手动拖动后关闭，复位视图后恢复)
+}
+
+// 视图的持久部分 (会话保存 / 恢复)：中心与缩放，不含拖拽等瞬时状态
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewStateSnapshot {
+    pub center_x: f64,
+    pub center_y: f64,
+    pub zoom: f64,
 }
 
+impl ViewState {
+    // 给定像素尺寸的视口：纵向范围 center_y ± 2 / zoom，横向按宽高比展开
+    fn view_info(&self, width: u32, height: u32) -> ViewInfo {
+        let aspect = width as f32 / height as f32;
+        let range_y = 2.0 / self.zoom;
+        let range_x = range_y * aspect as f64;
+        let x_range = (self.center_x - range_x, self.center_x + range_x);
+        let y_range = (self.center_y - range_y, self.center_y + range_y);
+        ViewInfo { x_range, y_range, zoom: self.zoom, pixels: (width, height), scale_factor: self.scale_factor }
+    }
+}
+
+// 图层：顶点在共享缓冲中的区间 + 样式在样式缓冲中的动态偏移
+// 渐变着色的图层另有一段逐顶点标量 (同一个共享缓冲) 和渐变 Uniform 的动态偏移
 struct RenderLayer {
-    vertex_buffer: wgpu::Buffer,
+    vertex_range: Option<std::ops::Range<u64>>,
     vertex_count: u32,
-    style_buffer: wgpu::Buffer,
-    style_bind_group: wgpu::BindGroup,
+    style_offset: u32,
+    scalar_range: Option<std::ops::Range<u64>>,
+    gradient_offset: u32,
+    // 时间序列图层：vertex_range 为固定大小的段槽区，按簿记增量写入
+    series: Option<SeriesTessellation>,
+    // GPU 求值的隐函数：屏幕分辨率的求值纹理，field_pending 时在本帧绘制前重新求值
+    field: Option<FieldTarget>,
+    field_pending: bool,
+    // 热力图：CPU 求值结果的纹理，视口超出覆盖范围时才重新求值
+    heatmap: Option<HeatmapTarget>,
 }
 
 struct WindowState {
@@ -64,27 +121,126 @@ struct WindowState {
     msaa_texture: wgpu::Texture,
 
     grid_pipeline: wgpu::RenderPipeline,
+    grid_overlay_pipeline: wgpu::RenderPipeline, // 有热力图时的网格 (背景透明，叠在热力图上)
     point_pipeline: wgpu::RenderPipeline, // 隐函数
+    colored_point_pipeline: wgpu::RenderPipeline, // 隐函数 (逐点颜色)
     mesh_pipeline: wgpu::RenderPipeline,  // 参数方程 (实心网格)
+    gradient_pipeline: wgpu::RenderPipeline, // 参数方程 (按逐顶点标量渐变着色)
+    text_pipeline: wgpu::RenderPipeline,  // 文字标注 (位图字体)
+    marker_pipeline: wgpu::RenderPipeline, // 交点标记 (固定像素大小)
+    scatter_pipeline: wgpu::RenderPipeline, // 散点 (固定像素大小，逐点颜色与形状)
+    field_pipeline: wgpu::RenderPipeline, // GPU 求值的隐函数 (全屏，按求值纹理画零等值线)
+    field_evaluator: FieldEvaluator,
+    heatmap_pipeline: wgpu::RenderPipeline, // 标量场热力图 (全屏，按求值纹理查色表)
+    heatmap_renderer: HeatmapRenderer,
+
+    // 文字：字体图集 + 顶点缓冲 (每帧重建)
+    text_bind_group: wgpu::BindGroup,
+    text_vertex_buffer: wgpu::Buffer,
+    text_vertex_count: u32,
+
+    // 交点标记：实例缓冲 (每帧重建)
+    marker_buffer: wgpu::Buffer,
+    marker_count: u32,
 
     globals_buffer: wgpu::Buffer,
     globals_bind_group: wgpu::BindGroup,
     style_bind_group_layout: wgpu::BindGroupLayout,
+    // 所有图层共用：一个顶点缓冲 (区间分配) + 一个样式 Uniform 缓冲 (动态偏移)
+    vertex_arena: VertexArena,
+    style_buffer: wgpu::Buffer,
+    style_bind_group: wgpu::BindGroup,
+    style_stride: u64, // 按 min_uniform_buffer_offset_alignment 对齐
+    // 渐变色表：同样每个图层一段，动态偏移
+    gradient_bind_group_layout: wgpu::BindGroupLayout,
+    gradient_buffer: wgpu::Buffer,
+    gradient_bind_group: wgpu::BindGroup,
+    gradient_stride: u64,
     layers: Vec<RenderLayer>,
 }
 
+impl WindowState {
+    // 释放所有图层的顶点区间；下一帧按对象列表重建图层并重写样式
+    fn clear_layers(&mut self) {
+        for layer in self.layers.drain(..) {
+            if let Some(range) = layer.vertex_range { self.vertex_arena.free(range); }
+            if let Some(range) = layer.scalar_range { self.vertex_arena.free(range); }
+        }
+    }
+}
+
 pub struct D2Plotter {
     instance: wgpu::Instance,
     state: Option<WindowState>,
     view: ViewState,
     objects: Vec<GeoObj>,
+    annotations: Vec<Annotation>,
+    // 需要标注交点的对象对 (show_intersections 开启)
+    intersections: Vec<IntersectionPair>,
 
     implicit_solver: ImplicitSolver,
     parametric_solver: ParametricSolver,
     explicit_solver: ExplicitSolver,
     last_frame_time: Option<Instant>,
+
+    // 交互状态：双击检测、触摸捏合、像素滚动平滑
+    clicks: ClickTracker,
+    touches: TouchTracker,
+    scroll: ScrollSmoother,
+
+    // 外部提供的新对象列表 (如表达式文件热重载)，空闲时轮询
+    object_source: Option<Receiver<Vec<GeoObj>>>,
+
+    // 时间序列通道 (下标即 ChannelId)，以及上次检查时各通道累计采样数之和
+    channels: Vec<SeriesHandle>,
+    series_seen: u64,
+
+    // 动画：每帧重建对象列表 (animate)
+    animation: Option<Animation>,
+
+    // 会话文件：关闭窗口时把视图与可序列化的对象写入 (--session)
+    session_path: Option<PathBuf>,
+}
+
+
+fn create_uniform_buffer(device: &wgpu::Device, label: &str, size: u64) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label), size, usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST, mapped_at_creation: false,
+    })
 }
 
+// 绑定大小只覆盖一个元素 (StyleUniform / GradientUniform)，各图层通过动态偏移选择自己的那一段
+fn create_dynamic_bind_group<T>(device: &wgpu::Device, label: &str, layout: &wgpu::BindGroupLayout, buffer: &wgpu::Buffer) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some(label),
+        layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                buffer, offset: 0, size: wgpu::BufferSize::new(size_of::<T>() as u64),
+            }),
+        }],
+    })
+}
+
+fn create_dynamic_uniform_layout<T>(device: &wgpu::Device, label: &str, visibility: wgpu::ShaderStages) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some(label),
+        entries: &[wgpu::BindGroupLayoutEntry { binding: 0, visibility, ty: wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Uniform, has_dynamic_offset: true, min_binding_size: wgpu::BufferSize::new(size_of::<T>() as u64) }, count: None }],
+    })
+}
+
+// 把 bytes 写进共享缓冲中的 slot：旧区间放得下就原地覆盖，否则归还 (空闲区自动合并) 后重新分配
+fn upload_to_arena(arena: &mut VertexArena, device: &wgpu::Device, queue: &wgpu::Queue, slot: &mut Option<std::ops::Range<u64>>, bytes: &[u8]) {
+    let required_size = bytes.len() as u64;
+    if let Some(range) = slot.take_if(|r| r.end - r.start < required_size || required_size == 0) {
+        arena.free(range);
+    }
+    if required_size > 0 {
+        let range = slot.get_or_insert_with(|| arena.alloc(device, queue, required_size));
+        queue.write_buffer(&arena.buffer, range.start, bytes);
+    }
+}
 
 fn create_msaa_texture(
     device: &wgpu::Device,
@@ -108,6 +264,115 @@ fn create_msaa_texture(
 }
 
 
+// 求解单个对象，返回世界坐标顶点，以及渐变着色时的逐顶点标量 (单色时为空)
+// 仿射变换在线宽挤出之前施加，保证线宽仍是屏幕像素常数：
+// 参数方程 / 显函数变换采样点后再挤出，隐函数在逆变换后的位置求值
+fn solve_geo_obj(
+    obj: &GeoObj,
+    view: &ViewInfo,
+    implicit_solver: &ImplicitSolver,
+    parametric_solver: &ParametricSolver,
+    explicit_solver: &ExplicitSolver,
+) -> (Vec<Vertex>, Vec<f32>) {
+    let (width, height) = view.pixels;
+    let aspect = width as f32 / height as f32;
+    let zoom = view.zoom as f32;
+    // 求解器按物理像素挤出线宽
+    let line_width = view.line_width_px(obj.width);
+    let jump_factor = obj.jump_threshold.unwrap_or(JUMP_THRESHOLD_FACTOR);
+    let m = obj.transform;
+    let identity = m == Matrix3x3::IDENTITY;
+    let apply = |(x, y): (f64, f64)| { let q = m.transform_point2(Vec2::new(x, y)); (q.x, q.y) };
+
+    // 参数方程通道：渐变着色时同时求标量
+    let scalar = match obj.coloring {
+        Coloring::Flat(_) => None,
+        Coloring::ByParam(_) => Some(CurveScalar::Param),
+        Coloring::BySpeed(_) => Some(CurveScalar::Speed),
+        Coloring::ByCurvature(_) => Some(CurveScalar::Curvature),
+    };
+    let parametric = |g: &(dyn Fn(f64) -> (f64, f64) + Sync + Send), t_range: (f64, f64)| match scalar {
+        None => (parametric_solver.solve(&g, t_range, line_width, zoom, aspect, height as f32, jump_factor), Vec::new()),
+        Some(kind) => parametric_solver.solve_with_scalar(&g, t_range, kind, line_width, zoom, height as f32, jump_factor),
+    };
+
+    match &obj.geo_type {
+        GeoType::Implicit(func) | GeoType::ImplicitColored(func, _) => {
+            if identity {
+                return (implicit_solver.solve(func, view.x_range, view.y_range, width, height), Vec::new());
+            }
+            let Some(inv) = m.inverse() else { return (Vec::new(), Vec::new()) };
+            let f = |x: f64, y: f64| { let q = inv.transform_point2(Vec2::new(x, y)); func(q.x, q.y) };
+            (implicit_solver.solve(&f, view.x_range, view.y_range, width, height), Vec::new())
+        },
+        GeoType::LevelSet(func, levels) => {
+            if identity {
+                return (implicit_solver.solve_levels(func, levels, view.x_range, view.y_range, width, height), Vec::new());
+            }
+            let Some(inv) = m.inverse() else { return (Vec::new(), Vec::new()) };
+            let f = |x: f64, y: f64| { let q = inv.transform_point2(Vec2::new(x, y)); func(q.x, q.y) };
+            (implicit_solver.solve_levels(&f, levels, view.x_range, view.y_range, width, height), Vec::new())
+        },
+        GeoType::ImplicitDetailed(func) => {
+            if identity {
+                let detail = view.pixel_size();
+                let f = |x: f64, y: f64| func(x, y, detail);
+                return (implicit_solver.solve(&f, view.x_range, view.y_range, width, height), Vec::new());
+            }
+            let Some(inv) = m.inverse() else { return (Vec::new(), Vec::new()) };
+            // 一个像素在原坐标系中的长度按面积缩放比估计
+            let detail = view.pixel_size() * inv.det().abs().sqrt();
+            let f = |x: f64, y: f64| { let q = inv.transform_point2(Vec2::new(x, y)); func(q.x, q.y, detail) };
+            (implicit_solver.solve(&f, view.x_range, view.y_range, width, height), Vec::new())
+        },
+        GeoType::Parametric(func, t_range) => {
+            // ★ 核心修改：传入屏幕信息以计算线宽，返回三角形网格顶点
+            if identity {
+                parametric(func.as_ref(), *t_range)
+            } else {
+                parametric(&|t: f64| apply(func(t)), *t_range)
+            }
+        },
+        GeoType::ParametricDynamic(func, t_range_fn) => {
+            let t_range = t_range_fn(view);
+            if identity {
+                parametric(func.as_ref(), t_range)
+            } else {
+                parametric(&|t: f64| apply(func(t)), t_range)
+            }
+        },
+        // ★ 新增：显函数处理
+        GeoType::Explicit(func, domain) => {
+            if identity {
+                // 显函数只需要 x_range、定义域，以及屏幕信息 (陡峭处自适应加密)
+                return (explicit_solver.solve_adaptive(
+                    func, view.x_range, *domain, line_width, zoom, width, height as f32
+                ), Vec::new());
+            }
+            // 变换后不再是 y = f(x)：按参数方程 t -> M·(t, f(t)) 处理
+            // t 范围取视口四角逆变换后的 x 范围与定义域之交
+            let (t0, t1) = view.x_range_under(&m);
+            let (t0, t1) = (t0.max(domain.0), t1.min(domain.1));
+            parametric(&|t: f64| apply((t, func(t))), (t0, t1))
+        },
+        // 时间序列由 update_series 增量挤出
+        // 散点不经过求解器，由 update_sim 直接上传实例数据
+        GeoType::Series(_) | GeoType::Points(_) | GeoType::Heatmap(_, _) | GeoType::Geometry | GeoType::Wipkyy(_) => (Vec::new(), Vec::new())
+    }
+}
+
+// 逐点着色的隐函数：求解方式同 solve_geo_obj (变换时在逆变换后的位置求值)，颜色在世界坐标上求
+fn solve_colored_implicit(obj: &GeoObj, view: &ViewInfo, implicit_solver: &ImplicitSolver) -> Vec<ColoredVertex> {
+    let GeoType::ImplicitColored(func, color_fn) = &obj.geo_type else { return Vec::new() };
+    let (width, height) = view.pixels;
+    if obj.transform == Matrix3x3::IDENTITY {
+        return implicit_solver.solve_colored(func, color_fn.as_ref(), view.x_range, view.y_range, width, height);
+    }
+    let Some(inv) = obj.transform.inverse() else { return Vec::new() };
+    let f = |x: f64, y: f64| { let q = inv.transform_point2(Vec2::new(x, y)); func(q.x, q.y) };
+    implicit_solver.solve_colored(&f, color_fn.as_ref(), view.x_range, view.y_range, width, height)
+}
+
 impl D2Plotter {
     pub(crate) fn new() -> Self {
         Self {
@@ -116,113 +381,409 @@ impl D2Plotter {
             view: ViewState {
                 center_x: 0.0, center_y: 0.0, zoom: 1.0,
                 is_dragging: false, last_mouse_pos: None, dirty: true,
+                scale_factor: 1.0, follow: true,
             },
             objects: Vec::new(),
+            annotations: Vec::new(),
+            intersections: Vec::new(),
             implicit_solver: ImplicitSolver::new(),
             parametric_solver: ParametricSolver::new(),
             explicit_solver: ExplicitSolver::new(),
             last_frame_time: None,
+            clicks: ClickTracker::default(),
+            touches: TouchTracker::default(),
+            scroll: ScrollSmoother::default(),
+            object_source: None,
+            channels: Vec::new(),
+            series_seen: 0,
+            animation: None,
+            session_path: None,
         }
     }
 
-    pub fn add_object(&mut self, obj: GeoObj) {
+    // 返回对象 id (即添加顺序)，供 set_transform 等使用
+    pub fn add_object(&mut self, obj: GeoObj) -> usize {
         self.objects.push(obj);
+        self.objects.len() - 1
+    }
+
+    // 实时修改某个对象的仿射变换：只标记该对象需要重算
+    pub fn set_transform(&mut self, id: usize, transform: Matrix3x3) {
+        let Some(obj) = self.objects.get_mut(id) else { return };
+        obj.transform = transform;
+        obj.dirty = true;
+        if let Some(s) = &self.state {
+            s.window.request_redraw();
+        }
+    }
+
+    // 替换散点对象的点集 (动态数据)：只重新上传该图层，不是散点的 id 忽略
+    pub fn update_points(&mut self, id: usize, points: Vec<Vec2>) {
+        let Some(obj) = self.objects.get_mut(id) else { return };
+        let GeoType::Points(current) = &mut obj.geo_type else { return };
+        *current = points;
+        obj.dirty = true;
+        if let Some(s) = &self.state {
+            s.window.request_redraw();
+        }
+    }
+
+    // 整体替换对象列表：图层在下一帧按新列表重建 (对象 id 随之失效，交点标注一并清除)
+    pub fn replace_objects(&mut self, objects: Vec<GeoObj>) {
+        self.objects = objects;
+        self.intersections.clear();
+        self.view.dirty = true;
+        if let Some(s) = &mut self.state {
+            s.clear_layers();
+            s.window.request_redraw();
+        }
+    }
+
+    pub fn object_count(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn view_state(&self) -> ViewStateSnapshot {
+        ViewStateSnapshot { center_x: self.view.center_x, center_y: self.view.center_y, zoom: self.view.zoom }
+    }
+
+    // 跳到快照的视图 (关闭跟随，与手动拖动一致)
+    pub fn restore_view(&mut self, snapshot: ViewStateSnapshot) {
+        self.view.center_x = snapshot.center_x;
+        self.view.center_y = snapshot.center_y;
+        self.view.zoom = snapshot.zoom;
+        self.view.follow = false;
+        self.scroll = ScrollSmoother::default();
+        self.view.dirty = true;
+        if let Some(s) = &self.state { s.window.request_redraw(); }
+    }
+
+    // 当前视图 + 可序列化的对象；闭包对象跳过并在控制台提示
+    pub fn session(&self) -> Session {
+        let objects: Vec<ObjectDesc> = self.objects.iter().filter_map(ObjectDesc::from_geo_obj).collect();
+        let skipped = self.objects.len() - objects.len();
+        if skipped > 0 {
+            eprintln!("[session] skipped {} closure-backed object(s) that cannot be saved", skipped);
+        }
+        Session { view: Some(self.view_state()), camera: None, objects }
+    }
+
+    // 恢复会话的视图，并追加其中的对象
+    pub fn restore_session(&mut self, session: &Session) {
+        if let Some(view) = session.view {
+            self.restore_view(view);
+        }
+        for desc in &session.objects {
+            self.add_object(desc.to_geo_obj());
+        }
+    }
+
+    // 关闭窗口时把会话写入 path (文件中的 3D 相机等其它部分保留)
+    pub fn set_session_path(&mut self, path: PathBuf) {
+        self.session_path = Some(path);
+    }
+
+    // 窗口运行期间从 rx 接收新的对象列表 (只取最新的一份) 并替换
+    pub fn set_object_source(&mut self, rx: Receiver<Vec<GeoObj>>) {
+        self.object_source = Some(rx);
+    }
+
+    // 动画：每次重绘按 t = 已过时间 / duration_secs (循环，t ∈ [0, 1)) 调用 update，
+    // 用返回的对象列表替换当前对象，并继续请求下一帧
+    pub fn animate(&mut self, duration_secs: f64, update: impl Fn(f64) -> Vec<GeoObj> + 'static) {
+        self.animation = Some(Animation::new(duration_secs, update));
+        if let Some(s) = &self.state {
+            s.window.request_redraw();
+        }
+    }
+
+    // 新建时间序列通道，最多保留 capacity 个采样；用 GeoObj::new_series(ch, ..) 显示
+    pub fn create_channel(&mut self, capacity: usize) -> ChannelId {
+        self.channels.push(SeriesHandle::new(capacity));
+        ChannelId(self.channels.len() - 1)
+    }
+
+    // 追加采样 (t, v)；窗口运行后 (run_app 占用了 plotter) 改用 channel_handle 从其他线程写入
+    pub fn push_sample(&self, ch: ChannelId, t: f64, v: f64) {
+        if let Some(handle) = self.channels.get(ch.0) {
+            handle.push(t, v);
+        }
+    }
+
+    // 通道的共享句柄，可 clone 后交给模拟线程
+    pub fn channel_handle(&self, ch: ChannelId) -> Option<SeriesHandle> {
+        self.channels.get(ch.0).cloned()
+    }
+
+    // 文字标注：锚点为世界坐标，字号固定为屏幕像素，不随缩放变化
+    pub fn add_annotation(&mut self, pos: Vec2, text: String, color: [f32; 4]) {
+        self.annotations.push(Annotation { pos: (pos.x, pos.y), text, color });
+    }
+
+    // 标注两个对象的交点 (标记点 + 坐标)，任一对象或视图变化后自动重算
+    // 两者都是圆锥曲线 / 直线时用解析解，否则在可见区域内数值求解
+    pub fn show_intersections(&mut self, id_a: usize, id_b: usize) {
+        // 对象与自身没有孤立的交点
+        if id_a == id_b { return; }
+        if self.intersections.iter().any(|p| p.involves(id_a) && p.involves(id_b)) { return; }
+        self.intersections.push(IntersectionPair::new(id_a, id_b));
+        if let Some(s) = &self.state {
+            s.window.request_redraw();
+        }
+    }
+
+    // 当前视图 (全部对象、网格与标注) 导出为 SVG，画布 width × height
+    // 视口中心与缩放同窗口，横向范围按画布宽高比展开；线宽按逻辑点，与画布单位一致
+    pub fn export_svg(&self, path: impl AsRef<std::path::Path>, width: u32, height: u32) -> std::io::Result<()> {
+        std::fs::write(path, self.to_svg(width, height))
+    }
+
+    pub fn to_svg(&self, width: u32, height: u32) -> String {
+        let view = ViewInfo { scale_factor: 1.0, ..self.view.view_info(width.max(1), height.max(1)) };
+        svg::render(
+            &self.objects, &self.annotations, &view,
+            &self.implicit_solver, &self.parametric_solver, &self.explicit_solver,
+        )
+    }
+
+    // 围绕屏幕像素 anchor 缩放 (滚轮、捏合、键盘共用)
+    fn zoom_view(&mut self, factor: f64, anchor: (f64, f64)) {
+        let Some(s) = &self.state else { return };
+        let size = s.window.inner_size();
+        let ((cx, cy), zoom) = interaction::zoom_about(
+            (self.view.center_x, self.view.center_y), self.view.zoom, factor, anchor,
+            (size.width as f64, size.height as f64),
+        );
+        self.view.center_x = cx;
+        self.view.center_y = cy;
+        self.view.zoom = zoom;
+        self.view.dirty = true;
+        s.window.request_redraw();
+    }
+
+    // 复位到初始视图
+    fn reset_view(&mut self) {
+        self.view.center_x = 0.0;
+        self.view.center_y = 0.0;
+        self.view.zoom = 1.0;
+        self.view.follow = true;
+        self.scroll = ScrollSmoother::default();
+        self.view.dirty = true;
+        if let Some(s) = &self.state { s.window.request_redraw(); }
+    }
+
+    // 光标位置，未知时取窗口中心
+    fn cursor_or_center(&self) -> (f64, f64) {
+        let Some(s) = &self.state else { return (0.0, 0.0) };
+        let size = s.window.inner_size();
+        self.view.last_mouse_pos.unwrap_or((size.width as f64 / 2.0, size.height as f64 / 2.0))
+    }
+
+    // 推进像素滚动的平滑缩放；未释放完时继续请求重绘
+    fn tick_scroll(&mut self) {
+        let now = Instant::now();
+        let dt = self.last_frame_time.map_or(1.0 / 60.0, |t| now.duration_since(t).as_secs_f64());
+        if let Some((factor, anchor)) = self.scroll.step(dt) {
+            self.zoom_view(factor, anchor);
+        }
+        self.last_frame_time = if self.scroll.is_active() { Some(now) } else { None };
     }
 
     fn update_sim(&mut self) {
         let s = match self.state.as_mut() { Some(s) => s, None => return };
 
-        let width = s.config.width;
-        let height = s.config.height;
-        let aspect = width as f32 / height as f32;
-
-        let range_y = 2.0 / self.view.zoom;
-        let range_x = range_y * aspect as f64;
-        let x_range = (self.view.center_x - range_x, self.view.center_x + range_x);
-        let y_range = (self.view.center_y - range_y, self.view.center_y + range_y);
+        let view_info = self.view.view_info(s.config.width, s.config.height);
 
         // 同步 Layer
-        if s.layers.len() != self.objects.len() {
-            s.layers.clear();
-            for obj in &self.objects {
-                let style_data = StyleUniform { color: obj.color, width: obj.width, _padding: [0.0;3] };
-                let buffer = s.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                    label: Some("Style Buffer"),
-                    contents: bytemuck::cast_slice(&[style_data]),
-                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-                });
-                let bg = s.device.create_bind_group(&wgpu::BindGroupDescriptor {
-                    label: Some("Style BindGroup"),
-                    layout: &s.style_bind_group_layout,
-                    entries: &[wgpu::BindGroupEntry { binding: 0, resource: buffer.as_entire_binding() }],
-                });
-                let vb = s.device.create_buffer(&wgpu::BufferDescriptor {
-                    label: Some("Empty VB"), size: 1024, usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST, mapped_at_creation: false
-                });
+        let mut force = self.view.dirty;
+        let rebuilt = s.layers.len() != self.objects.len();
+        if rebuilt {
+            force = true;
+            s.clear_layers();
 
+            // 样式：所有对象打包进一个 Uniform 缓冲，一次写入
+            let required = s.style_stride * self.objects.len().max(1) as u64;
+            if s.style_buffer.size() < required {
+                s.style_buffer = create_uniform_buffer(&s.device, "Style Buffer", required);
+                s.style_bind_group = create_dynamic_bind_group::<StyleUniform>(&s.device, "Style BindGroup", &s.style_bind_group_layout, &s.style_buffer);
+            }
+            // 渐变色表与样式一一对应，内容在求解后写入 (自动区间依赖求解结果)
+            let gradient_required = s.gradient_stride * self.objects.len().max(1) as u64;
+            if s.gradient_buffer.size() < gradient_required {
+                s.gradient_buffer = create_uniform_buffer(&s.device, "Gradient Buffer", gradient_required);
+                s.gradient_bind_group = create_dynamic_bind_group::<GradientUniform>(&s.device, "Gradient BindGroup", &s.gradient_bind_group_layout, &s.gradient_buffer);
+            }
+            let mut style_bytes = vec![0u8; required as usize];
+            for (i, obj) in self.objects.iter().enumerate() {
+                let style_data = StyleUniform {
+                    color: obj.coloring.base_color(), width: view_info.line_width_px(obj.width),
+                    shape: obj.marker.shape.code(), _padding: [0.0; 2],
+                };
+                let offset = i as u64 * s.style_stride;
+                let start = offset as usize;
+                style_bytes[start..start + size_of::<StyleUniform>()].copy_from_slice(bytemuck::bytes_of(&style_data));
                 s.layers.push(RenderLayer {
-                    vertex_buffer: vb,
-                    vertex_count: 0,
-                    style_buffer: buffer,
-                    style_bind_group: bg,
+                    vertex_range: None, vertex_count: 0, style_offset: offset as u32,
+                    scalar_range: None, gradient_offset: (i as u64 * s.gradient_stride) as u32,
+                    series: None, field: None, field_pending: false, heatmap: None,
                 });
             }
+            s.queue.write_buffer(&s.style_buffer, 0, &style_bytes);
         }
 
-        // 计算
-        for (i, obj) in self.objects.iter().enumerate() {
+        // 计算：视图变化时全部重算，否则只重算 dirty 的对象 (如刚修改了 transform)
+        // 散点与视图无关，只在自身 dirty 或图层重建时上传
+        for (i, obj) in self.objects.iter_mut().enumerate() {
+            let stale = if matches!(obj.geo_type, GeoType::Points(_)) { rebuilt } else { force };
+            if !stale && !obj.dirty { continue; }
+            let obj_dirty = obj.dirty;
+            obj.dirty = false;
+            if matches!(obj.geo_type, GeoType::Series(_)) { continue; }
+            for pair in self.intersections.iter_mut().filter(|p| p.involves(i)) {
+                pair.dirty = true;
+            }
             let layer = &mut s.layers[i];
 
-            let vertices = match &obj.geo_type {
-                GeoType::Implicit(func) => {
-                    self.implicit_solver.solve(func, x_range, y_range, width, height)
-                },
-                GeoType::Parametric(func, t_range) => {
-                    // ★ 核心修改：传入屏幕信息以计算线宽，返回三角形网格顶点
-                    self.parametric_solver.solve(
-                        func,
-                        *t_range,
-                        obj.width,
-                        self.view.zoom as f32,
-                        aspect,
-                        height as f32
-                    )
-                },
-
-                // ★ 新增：显函数处理
-                GeoType::Explicit(func) => {
-                    // 显函数只需要 x_range，以及屏幕信息
-                    self.explicit_solver.solve(
-                        func, x_range, obj.width,
-                        self.view.zoom as f32, width, height as f32
-                    )
-                },
+            // 空区域 (如虚圆锥曲线) 没有可画的点，不进入求解器
+            if matches!(&obj.geo_type, GeoType::Wipkyy(w) if w.is_empty()) {
+                if let Some(range) = layer.vertex_range.take() { s.vertex_arena.free(range); }
+                if let Some(range) = layer.scalar_range.take() { s.vertex_arena.free(range); }
+                layer.vertex_count = 0;
+                continue;
+            }
 
-                GeoType::Geometry => Vec::new()
-            };
+            // 可在 GPU 上求值的隐函数：只更新求值纹理的尺寸与变换，求值在 redraw 中派发
+            if let (GeoType::Implicit(_), Some(_)) = (&obj.geo_type, &obj.gpu_field) {
+                let (width, height) = (s.config.width, s.config.height);
+                if layer.field.as_ref().is_none_or(|f| f.size != (width, height)) {
+                    layer.field = Some(s.field_evaluator.create_target(&s.device, &s.globals_buffer, width, height));
+                }
+                if let Some(target) = &layer.field {
+                    s.field_evaluator.write_params(&s.queue, target, &obj.transform);
+                }
+                if let Some(range) = layer.vertex_range.take() { s.vertex_arena.free(range); }
+                layer.vertex_count = 0;
+                layer.field_pending = true;
+                continue;
+            }
+            layer.field = None;
 
-            if vertices.len() > 0 {
-                let required_size = (vertices.len() * size_of::<Vertex>()) as u64;
-                if layer.vertex_buffer.size() < required_size {
-                    layer.vertex_buffer = s.device.create_buffer(&wgpu::BufferDescriptor {
-                        label: Some("Resize VB"),
-                        size: required_size * 2,
-                        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                        mapped_at_creation: false,
-                    });
+            // 热力图：视口仍在上次的求值范围内时不重算 (对象自身变化时总是重算)
+            if let GeoType::Heatmap(func, colormap) = &obj.geo_type {
+                let max_side = s.device.limits().max_texture_dimension_2d;
+                let size = heatmap::texture_size(view_info.pixels, obj.heatmap.resolution, max_side);
+                if obj_dirty || layer.heatmap.as_ref().is_none_or(|t| t.is_stale(&view_info, size, *colormap)) {
+                    let coverage = Coverage::around(&view_info);
+                    let values = heatmap::evaluate(func.as_ref(), &obj.transform, &coverage, size);
+                    let range = heatmap::resolve_range(*colormap, &values, obj.heatmap.range);
+                    if layer.heatmap.as_ref().is_none_or(|t| t.size != size || t.colormap != *colormap) {
+                        layer.heatmap = Some(s.heatmap_renderer.create_target(&s.device, &s.queue, *colormap, size));
+                    }
+                    if let Some(target) = &mut layer.heatmap {
+                        s.heatmap_renderer.upload(&s.queue, target, &values, coverage, range);
+                    }
                 }
-                s.queue.write_buffer(&layer.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
-                layer.vertex_count = vertices.len() as u32;
-            } else {
+                if let Some(range) = layer.vertex_range.take() { s.vertex_arena.free(range); }
                 layer.vertex_count = 0;
+                continue;
+            }
+            layer.heatmap = None;
+
+            if let GeoType::Points(points) = &obj.geo_type {
+                let instances = scatter::build_instances(points, &obj.marker, obj.coloring.base_color(), &obj.transform);
+                upload_to_arena(&mut s.vertex_arena, &s.device, &s.queue, &mut layer.vertex_range, bytemuck::cast_slice(&instances));
+                layer.vertex_count = instances.len() as u32;
+                continue;
+            }
+
+            if matches!(obj.geo_type, GeoType::ImplicitColored(_, _)) {
+                let vertices = solve_colored_implicit(obj, &view_info, &self.implicit_solver);
+                upload_to_arena(&mut s.vertex_arena, &s.device, &s.queue, &mut layer.vertex_range, bytemuck::cast_slice(&vertices));
+                layer.vertex_count = vertices.len() as u32;
+                continue;
+            }
+
+            let (vertices, scalars) = solve_geo_obj(
+                obj, &view_info,
+                &self.implicit_solver, &self.parametric_solver, &self.explicit_solver,
+            );
+
+            upload_to_arena(&mut s.vertex_arena, &s.device, &s.queue, &mut layer.vertex_range, bytemuck::cast_slice(&vertices));
+            upload_to_arena(&mut s.vertex_arena, &s.device, &s.queue, &mut layer.scalar_range, bytemuck::cast_slice(&scalars));
+            if let (Some(gradient), false) = (obj.coloring.gradient(), scalars.is_empty()) {
+                let uniform = GradientUniform::new(gradient, gradient.resolve_range(&scalars));
+                s.queue.write_buffer(&s.gradient_buffer, layer.gradient_offset as u64, bytemuck::bytes_of(&uniform));
             }
+            layer.vertex_count = vertices.len() as u32;
+        }
+
+        // 交点：数值通道只搜索可见区域，视图变化时也要重算
+        for pair in self.intersections.iter_mut().filter(|p| force || p.dirty) {
+            pair.dirty = false;
+            pair.points = match (self.objects.get(pair.a), self.objects.get(pair.b)) {
+                (Some(a), Some(b)) => intersection::intersect_objects(a, b, &view_info),
+                _ => Vec::new(),
+            };
         }
 
         self.view.dirty = false;
     }
 
+    // 跟随模式：把视口中心移到让最新采样 (所有显示中的通道取最大 t) 落在右边缘
+    fn follow_series(&mut self) {
+        let Some(s) = &self.state else { return };
+        if !self.view.follow { return; }
+        let newest = self.objects.iter()
+            .filter_map(|o| match o.geo_type { GeoType::Series(ch) => self.channels.get(ch.0), _ => None })
+            .filter_map(|h| h.lock().last())
+            .map(|p| p.x)
+            .filter(|t| t.is_finite())
+            .fold(None, |acc: Option<f64>, t| Some(acc.map_or(t, |a| a.max(t))));
+        let Some(newest) = newest else { return };
+        let range_x = self.view.view_info(s.config.width, s.config.height).x_range;
+        let center_x = series::follow_center_x(newest, (range_x.1 - range_x.0) * 0.5);
+        if center_x != self.view.center_x {
+            self.view.center_x = center_x;
+            self.view.dirty = true;
+        }
+    }
+
+    // 时间序列图层：首次分配段槽区，之后只挤出新增的段并写到对应偏移
+    fn update_series(&mut self) {
+        let Some(s) = self.state.as_mut() else { return };
+        if s.layers.len() != self.objects.len() { return; }
+        let view_info = self.view.view_info(s.config.width, s.config.height);
+        // 与参数方程挤出的线宽换算一致
+        let world_per_px = (2.0 / self.view.zoom) / s.config.height as f64;
+        for (obj, layer) in self.objects.iter().zip(s.layers.iter_mut()) {
+            let GeoType::Series(ch) = obj.geo_type else { continue };
+            let Some(handle) = self.channels.get(ch.0) else { continue };
+            let ring = handle.lock();
+            let tess = layer.series.get_or_insert_with(|| SeriesTessellation::new(ring.capacity()));
+            let bytes_per_vertex = size_of::<Vertex>() as u64;
+            let range = layer.vertex_range
+                .get_or_insert_with(|| s.vertex_arena.alloc(&s.device, &s.queue, tess.vertex_count() as u64 * bytes_per_vertex))
+                .clone();
+            layer.vertex_count = tess.vertex_count() as u32;
+            let half_width = view_info.line_width_px(obj.width) as f64 * 0.5 * world_per_px;
+            for (slot, vertices) in tess.update(&ring, &obj.transform, half_width) {
+                let offset = range.start + (slot * series::VERTICES_PER_SEGMENT) as u64 * bytes_per_vertex;
+                s.queue.write_buffer(&s.vertex_arena.buffer, offset, bytemuck::cast_slice(&vertices));
+            }
+        }
+    }
+
     fn redraw(&mut self) {
-        if self.view.dirty { self.update_sim(); }
+        self.tick_scroll();
+        if let Some(animation) = &mut self.animation {
+            let objects = animation.frame(Instant::now());
+            self.replace_objects(objects);
+        }
+        self.follow_series();
+        if self.view.dirty || self.objects.iter().any(|o| o.dirty) || self.intersections.iter().any(|p| p.dirty) {
+            self.update_sim();
+        }
+        self.update_series();
         let s = match self.state.as_mut() { Some(s) => s, None => return };
 
         let width = s.config.width as f32;
@@ -237,10 +798,52 @@ impl D2Plotter {
         };
         s.queue.write_buffer(&s.globals_buffer, 0, bytemuck::cast_slice(&[globals]));
 
+        // 文字顶点随视图变化，每帧在屏幕空间重建 (交点坐标也作为标注)
+        let center = (self.view.center_x, self.view.center_y);
+        let mut text_vertices = text::build_text_vertices(&self.annotations, center, self.view.zoom, width, height);
+        let labels: Vec<Annotation> = self.intersections.iter().flat_map(|p| p.labels()).collect();
+        text_vertices.extend(text::build_text_vertices(&labels, center, self.view.zoom, width, height));
+        if !text_vertices.is_empty() {
+            let required_size = (text_vertices.len() * size_of::<TextVertex>()) as u64;
+            if s.text_vertex_buffer.size() < required_size {
+                s.text_vertex_buffer = s.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Resize Text VB"),
+                    size: required_size * 2,
+                    usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+            }
+            s.queue.write_buffer(&s.text_vertex_buffer, 0, bytemuck::cast_slice(&text_vertices));
+        }
+        s.text_vertex_count = text_vertices.len() as u32;
+
+        let markers: Vec<MarkerInstance> = self.intersections.iter().flat_map(|p| p.markers()).collect();
+        if !markers.is_empty() {
+            let required_size = (markers.len() * size_of::<MarkerInstance>()) as u64;
+            if s.marker_buffer.size() < required_size {
+                s.marker_buffer = s.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Resize Marker VB"),
+                    size: required_size * 2,
+                    usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+            }
+            s.queue.write_buffer(&s.marker_buffer, 0, bytemuck::cast_slice(&markers));
+        }
+        s.marker_count = markers.len() as u32;
+
         let frame = s.surface.get_current_texture().expect("Failed to acquire frame");
         let view = frame.texture.create_view(&wgpu::TextureViewDescriptor::default());
         let mut encoder = s.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
 
+        // GPU 求值的隐函数：在绘制之前重新求值 (全局 Uniform 已在上面写入)
+        for (obj, layer) in self.objects.iter().zip(s.layers.iter_mut()) {
+            if let (true, Some(field), Some(target)) = (layer.field_pending, &obj.gpu_field, &layer.field) {
+                s.field_evaluator.dispatch(&s.device, &mut encoder, field, target);
+                layer.field_pending = false;
+            }
+        }
+
         // 获取 MSAA 的 View
         let msaa_view = s.msaa_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
@@ -265,44 +868,120 @@ impl D2Plotter {
                 ..Default::default()
             });
 
-            // Pass 1: Grid
-            rp.set_pipeline(&s.grid_pipeline);
+            // Pass 1: 热力图 (按对象顺序叠放) + Grid
+            // 有热力图时网格改用透明背景叠在上面，坐标轴与曲线仍然可见
             rp.set_bind_group(0, &s.globals_bind_group, &[]);
+            let mut has_heatmap = false;
+            for target in s.layers.iter().filter_map(|l| l.heatmap.as_ref()) {
+                rp.set_pipeline(&s.heatmap_pipeline);
+                rp.set_bind_group(1, &target.bind_group, &[]);
+                rp.draw(0..3, 0..1);
+                has_heatmap = true;
+            }
+            rp.set_pipeline(if has_heatmap { &s.grid_overlay_pipeline } else { &s.grid_pipeline });
             rp.draw(0..3, 0..1);
 
             // Pass 2: Graph Objects
             for (i, obj) in self.objects.iter().enumerate() {
                 let layer = &s.layers[i];
-                if layer.vertex_count > 0 {
-                    rp.set_bind_group(1, &layer.style_bind_group, &[]);
+                // GPU 求值的隐函数：全屏三角形，片元着色器读取求值纹理
+                if let Some(target) = &layer.field {
+                    rp.set_pipeline(&s.field_pipeline);
+                    rp.set_bind_group(1, &s.style_bind_group, &[layer.style_offset]);
+                    rp.set_bind_group(2, &target.texture_bind_group, &[]);
+                    rp.draw(0..3, 0..1);
+                    continue;
+                }
+                // 无顶点的图层没有区间
+                if let Some(range) = &layer.vertex_range {
+                    rp.set_bind_group(1, &s.style_bind_group, &[layer.style_offset]);
+                    let stride = match obj.geo_type {
+                        GeoType::Points(_) => size_of::<PointInstance>(),
+                        GeoType::ImplicitColored(_, _) => size_of::<ColoredVertex>(),
+                        _ => size_of::<Vertex>(),
+                    };
+                    let bytes = layer.vertex_count as u64 * stride as u64;
+                    let slice = s.vertex_arena.buffer.slice(range.start..range.start + bytes);
 
                     match obj.geo_type {
-                        GeoType::Implicit(_) => {
+                        GeoType::Implicit(_) | GeoType::ImplicitDetailed(_) | GeoType::LevelSet(_, _) => {
                             // 隐函数：使用 Point Pipeline (Instancing)
                             rp.set_pipeline(&s.point_pipeline);
                             // Slot 0 is Instance Data
-                            rp.set_vertex_buffer(0, layer.vertex_buffer.slice(0..(layer.vertex_count as u64 * 8)));
+                            rp.set_vertex_buffer(0, slice);
                             rp.draw(0..4, 0..layer.vertex_count);
                         },
-                        // ★ 参数方程和显函数都使用 Mesh Pipeline (实心三角形)
-                        GeoType::Parametric(_, _) | GeoType::Explicit(_) => {
+                        // 逐点着色：实例数据为 ColoredVertex (位置 + 颜色)
+                        GeoType::ImplicitColored(_, _) => {
+                            rp.set_pipeline(&s.colored_point_pipeline);
+                            rp.set_vertex_buffer(0, slice);
+                            rp.draw(0..4, 0..layer.vertex_count);
+                        },
+                        // 时间序列：段槽区中有效的部分 (回绕时两段)
+                        GeoType::Series(_) => {
+                            let Some(tess) = &layer.series else { continue };
                             rp.set_pipeline(&s.mesh_pipeline);
-                            rp.set_vertex_buffer(0, layer.vertex_buffer.slice(0..(layer.vertex_count as u64 * 8)));
+                            rp.set_vertex_buffer(0, slice);
+                            for r in tess.draw_ranges() {
+                                rp.draw(r, 0..1);
+                            }
+                        },
+                        // ★ 参数方程和显函数都使用 Mesh Pipeline (实心三角形)
+                        GeoType::Parametric(_, _) | GeoType::ParametricDynamic(_, _) | GeoType::Explicit(_, _) => {
+                            if let Some(scalar_range) = &layer.scalar_range {
+                                // 渐变：第二个顶点缓冲为逐顶点标量
+                                rp.set_pipeline(&s.gradient_pipeline);
+                                rp.set_bind_group(2, &s.gradient_bind_group, &[layer.gradient_offset]);
+                                let scalar_bytes = layer.vertex_count as u64 * size_of::<f32>() as u64;
+                                rp.set_vertex_buffer(1, s.vertex_arena.buffer.slice(scalar_range.start..scalar_range.start + scalar_bytes));
+                            } else {
+                                rp.set_pipeline(&s.mesh_pipeline);
+                            }
+                            rp.set_vertex_buffer(0, slice);
                             rp.draw(0..layer.vertex_count, 0..1);
                         },
+                        // 散点：每个实例一个四边形
+                        GeoType::Points(_) => {
+                            rp.set_pipeline(&s.scatter_pipeline);
+                            rp.set_vertex_buffer(0, slice);
+                            rp.draw(0..4, 0..layer.vertex_count);
+                        },
                         _ => {}
                     }
                 }
             }
+
+            // Pass 3: 交点标记 (曲线之上、文字之下)
+            if s.marker_count > 0 {
+                rp.set_pipeline(&s.marker_pipeline);
+                rp.set_bind_group(0, &s.globals_bind_group, &[]);
+                let bytes = s.marker_count as u64 * size_of::<MarkerInstance>() as u64;
+                rp.set_vertex_buffer(0, s.marker_buffer.slice(0..bytes));
+                rp.draw(0..4, 0..s.marker_count);
+            }
+
+            // Pass 4: Annotations (画在所有曲线之上)
+            if s.text_vertex_count > 0 {
+                rp.set_pipeline(&s.text_pipeline);
+                rp.set_bind_group(0, &s.text_bind_group, &[]);
+                let bytes = s.text_vertex_count as u64 * size_of::<TextVertex>() as u64;
+                rp.set_vertex_buffer(0, s.text_vertex_buffer.slice(0..bytes));
+                rp.draw(0..s.text_vertex_count, 0..1);
+            }
         }
         s.queue.submit(std::iter::once(encoder.finish()));
         frame.present();
+        if self.animation.is_some() {
+            s.window.request_redraw();
+        }
     }
 }
 
 impl ApplicationHandler for D2Plotter {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         let window = Arc::new(event_loop.create_window(Window::default_attributes().with_title("GraphMF - 12.27 - Duo")).unwrap());
+        self.view.scale_factor = window.scale_factor();
+        self.view.dirty = true;
         let s = pollster::block_on(async {
             let surface = self.instance.create_surface(window.clone()).unwrap();
             let adapter = self.instance.request_adapter(&wgpu::RequestAdapterOptions::default()).await.unwrap();
@@ -322,10 +1001,18 @@ impl ApplicationHandler for D2Plotter {
                 label: Some("Globals Layout"),
                 entries: &[wgpu::BindGroupLayoutEntry { binding: 0, visibility: wgpu::ShaderStages::VERTEX_FRAGMENT, ty: wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Uniform, has_dynamic_offset: false, min_binding_size: None }, count: None }],
             });
-            let style_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("Style Layout"),
-                entries: &[wgpu::BindGroupLayoutEntry { binding: 0, visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT, ty: wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Uniform, has_dynamic_offset: false, min_binding_size: None }, count: None }],
-            });
+            let style_layout = create_dynamic_uniform_layout::<StyleUniform>(&device, "Style Layout", wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT);
+            let gradient_layout = create_dynamic_uniform_layout::<GradientUniform>(&device, "Gradient Layout", wgpu::ShaderStages::FRAGMENT);
+
+            // 共享的样式缓冲、渐变缓冲与顶点缓冲
+            let uniform_alignment = device.limits().min_uniform_buffer_offset_alignment as u64;
+            let style_stride = align_up(size_of::<StyleUniform>() as u64, uniform_alignment);
+            let style_buffer = create_uniform_buffer(&device, "Style Buffer", style_stride);
+            let style_bind_group = create_dynamic_bind_group::<StyleUniform>(&device, "Style BindGroup", &style_layout, &style_buffer);
+            let gradient_stride = align_up(size_of::<GradientUniform>() as u64, uniform_alignment);
+            let gradient_buffer = create_uniform_buffer(&device, "Gradient Buffer", gradient_stride);
+            let gradient_bind_group = create_dynamic_bind_group::<GradientUniform>(&device, "Gradient BindGroup", &gradient_layout, &gradient_buffer);
+            let vertex_arena = VertexArena::new(&device, VERTEX_ARENA_INITIAL, size_of::<Vertex>() as u64);
 
             // Globals
             let globals_buffer = device.create_buffer(&wgpu::BufferDescriptor {
@@ -352,6 +1039,24 @@ impl ApplicationHandler for D2Plotter {
                 }, cache: None, multiview_mask: None,
             });
 
+            // 1b. Grid Overlay Pipeline (热力图之上：只画坐标轴，背景透明)
+            let grid_overlay_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Grid Overlay Pipeline"),
+                layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor { bind_group_layouts: &[&globals_layout], ..Default::default() })),
+                vertex: wgpu::VertexState { module: &shader, entry_point: Some("vs_grid"), buffers: &[], compilation_options: Default::default() },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader, entry_point: Some("fs_grid_overlay"),
+                    targets: &[Some(wgpu::ColorTargetState { format: config.format, blend: Some(wgpu::BlendState::ALPHA_BLENDING), write_mask: wgpu::ColorWrites::ALL })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None, multisample: wgpu::MultisampleState {
+                    count: SAMPLE_COUNT,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                }, cache: None, multiview_mask: None,
+            });
+
             // 2. Point Pipeline (Implicit: Instancing)
             let point_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
                 label: Some("Point Pipeline"),
@@ -378,6 +1083,32 @@ impl ApplicationHandler for D2Plotter {
                 }, cache: None, multiview_mask: None,
             });
 
+            // 2b. Colored Point Pipeline (逐点着色的隐函数: ColoredVertex 实例)
+            let colored_point_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Colored Point Pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader, entry_point: Some("vs_point_colored"),
+                    buffers: &[wgpu::VertexBufferLayout {
+                        array_stride: size_of::<ColoredVertex>() as u64,
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4]
+                    }],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader, entry_point: Some("fs_point_colored"),
+                    targets: &[Some(wgpu::ColorTargetState { format: config.format, blend: Some(wgpu::BlendState::ALPHA_BLENDING), write_mask: wgpu::ColorWrites::ALL })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState { topology: wgpu::PrimitiveTopology::TriangleStrip, ..Default::default() },
+                depth_stencil: None, multisample: wgpu::MultisampleState {
+                    count: SAMPLE_COUNT,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                }, cache: None, multiview_mask: None,
+            });
+
             // 3. Mesh Pipeline (Parametric: Solid Triangles)
             let mesh_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
                 label: Some("Mesh Pipeline"),
@@ -405,48 +1136,339 @@ impl ApplicationHandler for D2Plotter {
                 }, cache: None, multiview_mask: None,
             });
 
+            // 3b. Gradient Mesh Pipeline (Parametric: 逐顶点标量 -> 渐变色表)
+            let gradient_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Gradient Mesh Pipeline"),
+                layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: None, bind_group_layouts: &[&globals_layout, &style_layout, &gradient_layout], immediate_size: 0,
+                })),
+                vertex: wgpu::VertexState {
+                    module: &shader, entry_point: Some("vs_mesh_gradient"),
+                    buffers: &[
+                        wgpu::VertexBufferLayout {
+                            array_stride: 8,
+                            step_mode: wgpu::VertexStepMode::Vertex,
+                            attributes: &wgpu::vertex_attr_array![0 => Float32x2]
+                        },
+                        // 标量单独一个缓冲，单色图层不需要为它付出带宽
+                        wgpu::VertexBufferLayout {
+                            array_stride: 4,
+                            step_mode: wgpu::VertexStepMode::Vertex,
+                            attributes: &wgpu::vertex_attr_array![1 => Float32]
+                        },
+                    ],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader, entry_point: Some("fs_mesh_gradient"),
+                    targets: &[Some(wgpu::ColorTargetState { format: config.format, blend: Some(wgpu::BlendState::ALPHA_BLENDING), write_mask: wgpu::ColorWrites::ALL })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState { topology: wgpu::PrimitiveTopology::TriangleList, ..Default::default() },
+                depth_stencil: None, multisample: wgpu::MultisampleState {
+                    count: SAMPLE_COUNT,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                }, cache: None, multiview_mask: None,
+            });
+
+            // 3c. Marker Pipeline (交点标记: Instancing，只用全局 Uniform)
+            let marker_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Marker Pipeline"),
+                layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor { bind_group_layouts: &[&globals_layout], ..Default::default() })),
+                vertex: wgpu::VertexState {
+                    module: &shader, entry_point: Some("vs_marker"),
+                    buffers: &[wgpu::VertexBufferLayout {
+                        array_stride: size_of::<MarkerInstance>() as u64,
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4]
+                    }],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader, entry_point: Some("fs_marker"),
+                    targets: &[Some(wgpu::ColorTargetState { format: config.format, blend: Some(wgpu::BlendState::ALPHA_BLENDING), write_mask: wgpu::ColorWrites::ALL })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState { topology: wgpu::PrimitiveTopology::TriangleStrip, ..Default::default() },
+                depth_stencil: None, multisample: wgpu::MultisampleState {
+                    count: SAMPLE_COUNT,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                }, cache: None, multiview_mask: None,
+            });
+            // 3d. Scatter Pipeline (散点: Instancing，样式 Uniform 给出大小与形状)
+            let scatter_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Scatter Pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader, entry_point: Some("vs_scatter"),
+                    buffers: &[wgpu::VertexBufferLayout {
+                        array_stride: size_of::<PointInstance>() as u64,
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &POINT_ATTRIBUTES,
+                    }],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader, entry_point: Some("fs_scatter"),
+                    targets: &[Some(wgpu::ColorTargetState { format: config.format, blend: Some(wgpu::BlendState::ALPHA_BLENDING), write_mask: wgpu::ColorWrites::ALL })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState { topology: wgpu::PrimitiveTopology::TriangleStrip, ..Default::default() },
+                depth_stencil: None, multisample: wgpu::MultisampleState {
+                    count: SAMPLE_COUNT,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                }, cache: None, multiview_mask: None,
+            });
+            // 3e. Field Pipeline (GPU 求值的隐函数：计算着色器写纹理，这里按纹理画线)
+            let field_evaluator = FieldEvaluator::new(&device);
+            let field_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Field Draw Shader"),
+                source: wgpu::ShaderSource::Wgsl(include_str!("field.wgsl").into()),
+            });
+            let field_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Field Pipeline"),
+                layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    bind_group_layouts: &[&globals_layout, &style_layout, &field_evaluator.texture_layout], ..Default::default()
+                })),
+                vertex: wgpu::VertexState {
+                    module: &field_shader, entry_point: Some("vs_field"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &field_shader, entry_point: Some("fs_field"),
+                    targets: &[Some(wgpu::ColorTargetState { format: config.format, blend: Some(wgpu::BlendState::ALPHA_BLENDING), write_mask: wgpu::ColorWrites::ALL })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None, multisample: wgpu::MultisampleState {
+                    count: SAMPLE_COUNT,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                }, cache: None, multiview_mask: None,
+            });
+            // 3f. Heatmap Pipeline (标量场：CPU 求值纹理 + 色表，画在网格之下)
+            let heatmap_renderer = HeatmapRenderer::new(&device);
+            let heatmap_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Heatmap Shader"),
+                source: wgpu::ShaderSource::Wgsl(include_str!("heatmap.wgsl").into()),
+            });
+            let heatmap_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Heatmap Pipeline"),
+                layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    bind_group_layouts: &[&globals_layout, &heatmap_renderer.layout], ..Default::default()
+                })),
+                vertex: wgpu::VertexState {
+                    module: &heatmap_shader, entry_point: Some("vs_heatmap"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &heatmap_shader, entry_point: Some("fs_heatmap"),
+                    targets: &[Some(config.format.into())],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None, multisample: wgpu::MultisampleState {
+                    count: SAMPLE_COUNT,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                }, cache: None, multiview_mask: None,
+            });
+            let marker_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Marker VB"), size: 1024, usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST, mapped_at_creation: false
+            });
+
+            // 4. Text Pipeline (Annotations: 字体图集贴图)
+            let text_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Text Shader"),
+                source: wgpu::ShaderSource::Wgsl(include_str!("text.wgsl").into()),
+            });
+            let text_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Text Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0, visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture { sample_type: wgpu::TextureSampleType::Float { filterable: true }, view_dimension: wgpu::TextureViewDimension::D2, multisampled: false },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1, visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            });
+            let atlas_texture = device.create_texture_with_data(
+                &queue,
+                &wgpu::TextureDescriptor {
+                    label: Some("Font Atlas"),
+                    size: wgpu::Extent3d { width: text::ATLAS_SIZE, height: text::ATLAS_SIZE, depth_or_array_layers: 1 },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: wgpu::TextureFormat::R8Unorm,
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                },
+                wgpu::util::TextureDataOrder::LayerMajor,
+                &text::build_atlas(),
+            );
+            let atlas_view = atlas_texture.create_view(&wgpu::TextureViewDescriptor::default());
+            // 像素字体用最近邻采样，保持边缘锐利
+            let atlas_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("Font Sampler"),
+                ..Default::default()
+            });
+            let text_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Text BG"),
+                layout: &text_layout,
+                entries: &[
+                    wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&atlas_view) },
+                    wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&atlas_sampler) },
+                ],
+            });
+            let text_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Text Pipeline"),
+                layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor { bind_group_layouts: &[&text_layout], ..Default::default() })),
+                vertex: wgpu::VertexState {
+                    module: &text_shader, entry_point: Some("vs_text"),
+                    buffers: &[wgpu::VertexBufferLayout {
+                        array_stride: size_of::<TextVertex>() as u64,
+                        step_mode: wgpu::VertexStepMode::Vertex,
+                        attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Float32x4]
+                    }],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &text_shader, entry_point: Some("fs_text"),
+                    targets: &[Some(wgpu::ColorTargetState { format: config.format, blend: Some(wgpu::BlendState::ALPHA_BLENDING), write_mask: wgpu::ColorWrites::ALL })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState { topology: wgpu::PrimitiveTopology::TriangleList, ..Default::default() },
+                depth_stencil: None, multisample: wgpu::MultisampleState {
+                    count: SAMPLE_COUNT,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                }, cache: None, multiview_mask: None,
+            });
+            let text_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Text VB"), size: 1024, usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST, mapped_at_creation: false
+            });
+
             let msaa_texture = create_msaa_texture(&device, &config, SAMPLE_COUNT);
 
             WindowState {
                 window, surface, device, queue, config,
                 msaa_texture,
-                grid_pipeline, point_pipeline, mesh_pipeline,
+                grid_pipeline, grid_overlay_pipeline, point_pipeline, colored_point_pipeline, mesh_pipeline, gradient_pipeline, text_pipeline, marker_pipeline, scatter_pipeline,
+                field_pipeline, field_evaluator,
+                heatmap_pipeline, heatmap_renderer,
+                text_bind_group, text_vertex_buffer, text_vertex_count: 0,
+                marker_buffer, marker_count: 0,
                 globals_buffer, globals_bind_group,
-                style_bind_group_layout: style_layout, layers: Vec::new(),
+                style_bind_group_layout: style_layout,
+                vertex_arena, style_buffer, style_bind_group, style_stride,
+                gradient_bind_group_layout: gradient_layout,
+                gradient_buffer, gradient_bind_group, gradient_stride,
+                layers: Vec::new(),
             }
         });
         self.state = Some(s);
     }
 
     // WindowEvent 保持不变 (缩放/拖拽逻辑)
+    // 有对象来源或时间序列时按固定间隔醒来检查；都没有时保持默认的事件驱动
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let mut interval = None;
+        if let Some(rx) = &self.object_source {
+            if let Some(objects) = rx.try_iter().last() {
+                self.replace_objects(objects);
+            }
+            interval = Some(watch::POLL_INTERVAL);
+        }
+        if !self.channels.is_empty() {
+            // 有新采样才重绘
+            let pushed = self.channels.iter().map(|h| h.lock().pushed()).sum();
+            if pushed != self.series_seen {
+                self.series_seen = pushed;
+                if let Some(s) = &self.state { s.window.request_redraw(); }
+            }
+            interval = Some(interval.map_or(series::POLL_INTERVAL, |i: std::time::Duration| i.min(series::POLL_INTERVAL)));
+        }
+        if let Some(interval) = interval {
+            event_loop.set_control_flow(ControlFlow::WaitUntil(Instant::now() + interval));
+        }
+    }
+
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
         match event {
-            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::CloseRequested => {
+                if let Some(path) = &self.session_path {
+                    let Session { view, objects, .. } = self.session();
+                    let result = Session::update_file(path, |s| {
+                        s.view = view;
+                        s.objects = objects;
+                    });
+                    if let Err(e) = result {
+                        eprintln!("[session] {}: {}, not saved", path.display(), e);
+                    }
+                }
+                event_loop.exit();
+            }
             WindowEvent::MouseWheel { delta, .. } => {
-                let s = self.state.as_ref().unwrap();
+                let anchor = self.cursor_or_center();
+                match delta {
+                    // 滚轮：每格固定倍率，立即生效
+                    MouseScrollDelta::LineDelta(_, y) => self.zoom_view(LINE_ZOOM_RATIO.powf(y as f64), anchor),
+                    // 触控板：累积后按帧平滑释放
+                    MouseScrollDelta::PixelDelta(pos) => {
+                        self.scroll.push_pixels(pos.y, anchor);
+                        if let Some(s) = &self.state { s.window.request_redraw(); }
+                    }
+                }
+            }
+            // 触控板捏合：光标位置即手势中心
+            WindowEvent::PinchGesture { delta, .. } => {
+                let anchor = self.cursor_or_center();
+                self.zoom_view(1.0 + delta, anchor);
+            }
+            WindowEvent::DoubleTapGesture { .. } => self.reset_view(),
+            // 触摸屏：两指及以上围绕质心缩放
+            WindowEvent::Touch(touch) => {
+                let pos = (touch.location.x, touch.location.y);
+                if let Some((centroid, factor)) = self.touches.update(touch.id, touch.phase, pos) {
+                    self.zoom_view(factor, centroid);
+                }
+            }
+            WindowEvent::KeyboardInput { event, .. } if event.state == ElementState::Pressed => {
+                let Some(s) = &self.state else { return };
                 let size = s.window.inner_size();
-                let mouse_pos = self.view.last_mouse_pos.unwrap_or((size.width as f64 / 2.0, size.height as f64 / 2.0));
-                let aspect = size.width as f64 / size.height as f64;
-                let full_world_h = 2.0 * 2.0 / self.view.zoom;
-                let full_world_w = full_world_h * aspect;
-                let mouse_rel_x = (mouse_pos.0 / size.width as f64) - 0.5;
-                let mouse_rel_y = 0.5 - (mouse_pos.1 / size.height as f64);
-                let mouse_world_x = self.view.center_x + mouse_rel_x * full_world_w;
-                let mouse_world_y = self.view.center_y + mouse_rel_y * full_world_h;
-
-                let y = match delta { MouseScrollDelta::LineDelta(_, y) => y as f64, MouseScrollDelta::PixelDelta(pos) => pos.y / 60.0 };
-                self.view.zoom *= 1.1f64.powf(y);
-
-                let new_full_world_h = 4.0 / self.view.zoom;
-                let new_full_world_w = new_full_world_h * aspect;
-                self.view.center_x = mouse_world_x - mouse_rel_x * new_full_world_w;
-                self.view.center_y = mouse_world_y - mouse_rel_y * new_full_world_h;
-
-                self.view.dirty = true;
-                s.window.request_redraw();
+                let center = (size.width as f64 / 2.0, size.height as f64 / 2.0);
+                match event.logical_key.as_ref() {
+                    Key::Character("+") | Key::Character("=") => self.zoom_view(LINE_ZOOM_RATIO, center),
+                    Key::Character("-") => self.zoom_view(1.0 / LINE_ZOOM_RATIO, center),
+                    Key::Character("0") => self.reset_view(),
+                    // 按窗口的逻辑尺寸导出
+                    Key::Character("s") => {
+                        let logical = size.to_logical::<f64>(self.view.scale_factor);
+                        match self.export_svg(SVG_EXPORT_PATH, logical.width.round() as u32, logical.height.round() as u32) {
+                            Ok(()) => println!("[svg] saved {}", SVG_EXPORT_PATH),
+                            Err(e) => eprintln!("[svg] cannot write {}: {}", SVG_EXPORT_PATH, e),
+                        }
+                    }
+                    _ => {}
+                }
             }
             WindowEvent::MouseInput { state, button: MouseButton::Left, .. } => {
                 self.view.is_dragging = state == ElementState::Pressed;
+                if state == ElementState::Pressed {
+                    let pos = self.cursor_or_center();
+                    if self.clicks.press(Instant::now(), pos) { self.reset_view(); }
+                }
             }
             WindowEvent::CursorMoved { position, .. } => {
                 if self.view.is_dragging {
@@ -459,6 +1481,7 @@ impl ApplicationHandler for D2Plotter {
                         let dy = (position.y - last.1) / size.height as f64 * world_h;
                         self.view.center_x -= dx;
                         self.view.center_y += dy;
+                        self.view.follow = false;
 
                         self.view.dirty = true;
                         s.window.request_redraw();
@@ -478,9 +1501,245 @@ impl ApplicationHandler for D2Plotter {
                     s.window.request_redraw();
                 }
             }
+            // 移到不同缩放比的显示器：样式 (点大小) 与线宽都要按新比例重建
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.view.scale_factor = scale_factor;
+                self.view.dirty = true;
+                if let Some(s) = self.state.as_mut() {
+                    s.clear_layers();
+                    s.window.request_redraw();
+                }
+            }
             WindowEvent::RedrawRequested => { self.redraw(); }
             _ => (),
         }
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_forest::geometry::d2::spline::b_spline::BSpline;
+
+    fn view() -> ViewInfo {
+        ViewInfo { x_range: (-4.0, 4.0), y_range: (-4.0, 4.0), zoom: 0.5, pixels: (400, 400), scale_factor: 1.0 }
+    }
+
+    fn solve(obj: &GeoObj) -> Vec<Vertex> {
+        solve_geo_obj(obj, &view(), &ImplicitSolver::new(), &ParametricSolver::new(), &ExplicitSolver::new()).0
+    }
+
+    #[test]
+    fn test_implicit_translated() {
+        let obj = GeoObj::new_implicit(|x, y| x * x + y * y - 1.0, [1.0; 4], 2.0)
+            .with_transform(Matrix3x3::from_translation(2.0, -1.0));
+        let vs = solve(&obj);
+        assert!(vs.len() > 50);
+
+        let n = vs.len() as f64;
+        let cx = vs.iter().map(|v| v.position[0] as f64).sum::<f64>() / n;
+        let cy = vs.iter().map(|v| v.position[1] as f64).sum::<f64>() / n;
+        assert!((cx - 2.0).abs() < 0.05 && (cy + 1.0).abs() < 0.05, "center = ({}, {})", cx, cy);
+        for v in &vs {
+            let r = (v.position[0] as f64 - 2.0).hypot(v.position[1] as f64 + 1.0);
+            assert!((r - 1.0).abs() < 0.05, "r = {}", r);
+        }
+    }
+
+    #[test]
+    fn test_implicit_detailed_hint() {
+        // detail_hint 为一个像素的世界长度：视口高 8、400 像素时为 0.02，圆半径 100 * 0.02 = 2
+        let obj = GeoObj::new_implicit_detailed(|x, y, hint| x * x + y * y - (100.0 * hint).powi(2), [1.0; 4], 2.0);
+        let vs = solve(&obj);
+        assert!(vs.len() > 50);
+        for v in &vs {
+            let r = (v.position[0] as f64).hypot(v.position[1] as f64);
+            assert!((r - 2.0).abs() < 0.05, "r = {}", r);
+        }
+    }
+
+    #[test]
+    fn test_jump_threshold_override() {
+        // 阶梯 (t, 0.5 floor(t))：zoom = 0.5 时默认阈值为 0.4 世界单位，三处台阶都切断
+        let stairs = |t: f64| (t, 0.5 * t.floor());
+        let strict = solve(&GeoObj::new_parametric(stairs, (0.0, 3.5), [1.0; 4], 2.0));
+        // 阈值放宽到 0.2 (0.8 世界单位)：台阶之间连线，每处断裂少的是一段线段的 6 个顶点
+        let loose = solve(&GeoObj::new_parametric(stairs, (0.0, 3.5), [1.0; 4], 2.0).with_jump_threshold(0.2));
+        assert_eq!(loose.len() - strict.len(), 3 * 6);
+    }
+
+    #[test]
+    fn test_b_spline_solved_as_parametric() {
+        // 控制点共线的三次样条仍在这条直线上，且 clamped 样条经过首末控制点
+        let control = (0..6).map(|i| Vec2::new(i as f64 * 0.5 - 1.5, i as f64 * 0.5 - 1.5)).collect();
+        let vs = solve(&GeoObj::new_b_spline(BSpline::new_clamped(control, 3), [1.0; 4], 2.0));
+        assert!(vs.len() > 50);
+        for v in &vs {
+            assert!((v.position[0] - v.position[1]).abs() < 0.05, "{:?}", v.position);
+        }
+        let near = |x: f32| vs.iter().any(|v| (v.position[0] - x).abs() < 0.05);
+        assert!(near(-1.5) && near(1.0));
+    }
+
+    #[test]
+    fn test_explicit_rotated() {
+        // y = x^2 (x ∈ [-1, 1]) 旋转 90° 后为 x = -y^2 (y ∈ [-1, 1])
+        let obj = GeoObj::new_explicit_on(|x| x * x, (-1.0, 1.0), [1.0; 4], 2.0)
+            .with_transform(Matrix3x3::from_rotation(std::f64::consts::FRAC_PI_2));
+        let vs = solve(&obj);
+        assert!(!vs.is_empty());
+
+        // 线宽 2px：挤出半宽 = 1 像素 = 0.01 世界单位
+        let (mut y_min, mut y_max) = (f64::INFINITY, f64::NEG_INFINITY);
+        for v in &vs {
+            let (x, y) = (v.position[0] as f64, v.position[1] as f64);
+            assert!((x + y * y).abs() < 0.05, "({}, {})", x, y);
+            y_min = y_min.min(y);
+            y_max = y_max.max(y);
+        }
+        assert!((y_min + 1.0).abs() < 0.05 && (y_max - 1.0).abs() < 0.05);
+
+        // 单位变换仍走显函数求解器，点集为 y = x^2
+        let plain = solve(&GeoObj::new_explicit_on(|x| x * x, (-1.0, 1.0), [1.0; 4], 2.0));
+        assert!(plain.iter().all(|v| (v.position[1] - v.position[0] * v.position[0]).abs() < 0.05));
+    }
+
+    #[test]
+    fn test_imaginary_conic_is_empty() {
+        use crate::math_forest::geometry::d2::conic::conic::{Conic, ConicType};
+
+        // x^2 + y^2 + 1 = 0：分类为虚空 -> Wipkyy::empty -> 绘图对象不产生顶点
+        let conic = Conic::new(1.0, 0.0, 1.0, 0.0, 0.0, 1.0);
+        assert_eq!(conic.get_conic_type(), ConicType::Imaginary);
+        let obj = GeoObj::new_conic(conic, [1.0; 4], 2.0);
+        match &obj.geo_type {
+            GeoType::Wipkyy(w) => assert!(w.is_empty()),
+            _ => panic!("imaginary conic should become a Wipkyy object"),
+        }
+        assert!(solve(&obj).is_empty());
+
+        // 实圆照常走隐函数求解
+        let circle = GeoObj::new_conic(Conic::new(1.0, 0.0, 1.0, 0.0, 0.0, -1.0), [1.0; 4], 2.0);
+        assert!(matches!(circle.geo_type, GeoType::Implicit(_)));
+        assert!(!solve(&circle).is_empty());
+    }
+
+    #[test]
+    fn test_gradient_coloring_emits_scalars() {
+        use super::super::gradient::Gradient;
+
+        let lissajous = |t: f64| ((3.0 * t).sin(), (2.0 * t).sin());
+        let tau = std::f64::consts::TAU;
+        let flat = GeoObj::new_parametric(lissajous, (0.0, tau), [1.0; 4], 3.0);
+        let (flat_vs, flat_scalars) = solve_geo_obj(&flat, &view(), &ImplicitSolver::new(), &ParametricSolver::new(), &ExplicitSolver::new());
+        assert!(flat_scalars.is_empty());
+
+        let by_t = GeoObj::new_parametric(lissajous, (0.0, tau), [1.0; 4], 3.0)
+            .with_coloring(Coloring::ByParam(Gradient::rainbow()));
+        let (vs, scalars) = solve_geo_obj(&by_t, &view(), &ImplicitSolver::new(), &ParametricSolver::new(), &ExplicitSolver::new());
+        assert_eq!(vs.len(), scalars.len());
+        assert_eq!(vs.len(), flat_vs.len());
+        assert!(scalars.iter().all(|t| (0.0..=tau as f32 + 1e-5).contains(t)));
+
+        // 隐函数不走参数方程通道：退回单色
+        let implicit = GeoObj::new_implicit(|x, y| x * x + y * y - 1.0, [1.0; 4], 2.0)
+            .with_coloring(Coloring::BySpeed(Gradient::rainbow()));
+        let (vs, scalars) = solve_geo_obj(&implicit, &view(), &ImplicitSolver::new(), &ParametricSolver::new(), &ExplicitSolver::new());
+        assert!(!vs.is_empty() && scalars.is_empty());
+    }
+
+    #[test]
+    fn test_line_width_scales_with_scale_factor() {
+        // y = 0，线宽 2 逻辑点：挤出半宽 = 1 点 × 缩放比 × 每像素世界长度 (0.01)
+        for scale_factor in [1.0, 1.5, 2.0] {
+            let view = ViewInfo { scale_factor, ..view() };
+            assert!((view.line_width_px(2.0) - 2.0 * scale_factor as f32).abs() < 1e-6);
+
+            let obj = GeoObj::new_explicit_on(|_x| 0.0, (-1.0, 1.0), [1.0; 4], 2.0);
+            let (vs, _) = solve_geo_obj(&obj, &view, &ImplicitSolver::new(), &ParametricSolver::new(), &ExplicitSolver::new());
+            let half = vs.iter().map(|v| v.position[1].abs() as f64).fold(0.0, f64::max);
+            assert!((half - 0.01 * scale_factor).abs() < 1e-6, "scale {}: half width {}", scale_factor, half);
+        }
+    }
+
+    #[test]
+    fn test_intersections_reset_on_replace() {
+        let mut plotter = D2Plotter::new();
+        let a = plotter.add_object(GeoObj::new_explicit(|x| x, [1.0; 4], 2.0));
+        let b = plotter.add_object(GeoObj::new_explicit(|x| -x, [1.0; 4], 2.0));
+        plotter.show_intersections(a, a);
+        assert!(plotter.intersections.is_empty());
+        plotter.show_intersections(a, b);
+        plotter.show_intersections(b, a);
+        assert_eq!(plotter.intersections.len(), 1);
+
+        // 替换后旧的 id 指向新对象，已有的标注对不再有效
+        plotter.replace_objects(vec![GeoObj::new_explicit(|x| x * x, [1.0; 4], 2.0)]);
+        assert!(plotter.intersections.is_empty());
+    }
+
+    #[test]
+    fn test_update_points() {
+        let mut plotter = D2Plotter::new();
+        let line = plotter.add_object(GeoObj::new_explicit(|x| x, [1.0; 4], 2.0));
+        let id = plotter.add_object(GeoObj::new_points(vec![Vec2::new(0.0, 0.0)], [1.0; 4], 6.0));
+        for obj in &mut plotter.objects { obj.dirty = false; }
+
+        // 替换点集并标记该对象需要重新上传
+        plotter.update_points(id, vec![Vec2::new(1.0, 2.0), Vec2::new(3.0, 4.0)]);
+        let obj = &plotter.objects[id];
+        assert!(obj.dirty);
+        match &obj.geo_type {
+            GeoType::Points(ps) => assert_eq!(ps.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>(), vec![(1.0, 2.0), (3.0, 4.0)]),
+            _ => panic!("update_points should keep the object a point set"),
+        }
+        // 散点不经过求解器，实例数据由 scatter::build_instances 给出
+        assert!(solve(obj).is_empty());
+
+        // 不是散点的对象与越界 id 都忽略
+        plotter.update_points(line, vec![Vec2::new(0.0, 0.0)]);
+        plotter.update_points(99, Vec::new());
+        assert!(!plotter.objects[line].dirty);
+        assert!(matches!(plotter.objects[line].geo_type, GeoType::Explicit(_, _)));
+    }
+
+    #[test]
+    fn test_implicit_colored_points() {
+        // 与单色隐函数求解结果相同，颜色与点一一对应
+        let circle = |x: f64, y: f64| x * x + y * y - 1.0;
+        let plain = solve(&GeoObj::new_implicit(circle, [1.0; 4], 2.0).with_transform(Matrix3x3::from_translation(3.0, 0.0)));
+        let colored = GeoObj::new_implicit_colored(circle, |x, _y| if x >= 0.0 { [1.0, 0.0, 0.0, 1.0] } else { [0.0, 0.0, 1.0, 1.0] }, 2.0)
+            .with_transform(Matrix3x3::from_translation(3.0, 0.0));
+        let vs = solve_colored_implicit(&colored, &view(), &ImplicitSolver::new());
+        assert_eq!(vs.len(), plain.len());
+        assert!(vs.iter().zip(&plain).all(|(c, v)| c.position == v.position));
+
+        // 颜色按世界坐标 (已平移) 求值：整个圆都在 x > 0 一侧
+        assert!(vs.iter().all(|v| v.color == [1.0, 0.0, 0.0, 1.0]));
+
+        let centered = GeoObj::new_implicit_colored(circle, |x, _y| [x as f32, 0.0, 0.0, 1.0], 2.0);
+        for v in solve_colored_implicit(&centered, &view(), &ImplicitSolver::new()) {
+            assert_eq!(v.color[0], v.position[0]);
+        }
+        // 单色对象走普通通道
+        assert!(solve_colored_implicit(&GeoObj::new_implicit(circle, [1.0; 4], 2.0), &view(), &ImplicitSolver::new()).is_empty());
+        assert_eq!(size_of::<ColoredVertex>(), 24);
+    }
+
+    #[test]
+    fn test_level_curves_translated() {
+        // 三个同心圆作为一个对象，随变换整体平移
+        let obj = GeoObj::new_level_curves(|x, y| x * x + y * y, &[0.25, 1.0, 2.25], [1.0; 4], 2.0)
+            .with_transform(Matrix3x3::from_translation(1.0, 0.5));
+        let vs = solve(&obj);
+        let mut counts = [0; 3];
+        for v in &vs {
+            let r = (v.position[0] as f64 - 1.0).hypot(v.position[1] as f64 - 0.5);
+            let k = [0.5, 1.0, 1.5].iter().position(|&k| (r - k).abs() < 0.05).expect("point off every level");
+            counts[k] += 1;
+        }
+        // 周长越大交点越多
+        assert!(counts[0] > 20 && counts[0] < counts[1] && counts[1] < counts[2], "{:?}", counts);
+    }
+}
@@ -13,3 +13,29 @@ pub mod explicit;
 
 
 
+
+pub mod text;
+
+pub mod arena;
+
+pub mod interaction;
+
+pub mod gradient;
+
+pub mod watch;
+
+pub mod intersection;
+
+pub mod svg;
+
+pub mod series;
+
+pub mod scatter;
+
+pub mod animation;
+
+pub mod gpu_field;
+
+pub mod conformal;
+
+pub mod heatmap;
@@ -3,15 +3,27 @@ use crate::graph::d2::common::Vertex;
 
 const SAMPLES_PER_UNIT_T: f64 = 20.0;
 
-// ★ 新增：断裂阈值系数
-// 如果两点之间的屏幕距离超过了屏幕高度的 2 倍，就认为是断点/渐近线，不连线。
-const JUMP_THRESHOLD_FACTOR: f32 = 2.0;
+// 断裂检测阈值系数 (相对视口高度)：相邻采样点距离超过它时二分细化，判断是陡峭还是真断点
+// GeoObj::jump_threshold 可逐对象覆盖
+pub const JUMP_THRESHOLD_FACTOR: f32 = 0.1;
+// 二分细化的最大深度：每层只跟踪较长的一半，超过深度仍未缩短到阈值内即视为断点
+const JUMP_REFINE_MAX_DEPTH: usize = 24;
+
+// 逐顶点标量 (用于渐变着色)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveScalar {
+    Param, // 参数 t
+    Speed, // |P'(t)|，中心差分
+    Curvature, // |P' × P''| / |P'|³，中心差分
+}
 
 pub struct ParametricSolver {}
 
 impl ParametricSolver {
     pub fn new() -> Self { Self {} }
 
+    /// jump_factor: 断裂检测阈值 (视口高度的倍数)，通常取 JUMP_THRESHOLD_FACTOR
+    #[allow(clippy::too_many_arguments)]
     pub fn solve<F>(
         &self,
         f: &F,
@@ -19,36 +31,141 @@ impl ParametricSolver {
         width_px: f32,
         zoom: f32,
         aspect: f32,
-        screen_h: f32
+        screen_h: f32,
+        jump_factor: f32
     ) -> Vec<Vertex>
+    where
+        F: Fn(f64) -> (f64, f64) + Sync + Send,
+    {
+        let Some((path, step_t)) = Self::sample_path(f, t_range) else { return Vec::new() };
+        let broken = Self::find_jumps(f, &path, t_range.0, step_t, jump_factor, zoom);
+        Self::extrude(&path, &broken, None, width_px, zoom, screen_h).0
+    }
+
+    // 同 solve，另外为每个输出顶点给出标量 (与顶点一一对应，作为第二个顶点缓冲)
+    #[allow(clippy::too_many_arguments)]
+    pub fn solve_with_scalar<F>(
+        &self,
+        f: &F,
+        t_range: (f64, f64),
+        scalar: CurveScalar,
+        width_px: f32,
+        zoom: f32,
+        screen_h: f32,
+        jump_factor: f32
+    ) -> (Vec<Vertex>, Vec<f32>)
+    where
+        F: Fn(f64) -> (f64, f64) + Sync + Send,
+    {
+        let Some((path, step_t)) = Self::sample_path(f, t_range) else { return (Vec::new(), Vec::new()) };
+        let broken = Self::find_jumps(f, &path, t_range.0, step_t, jump_factor, zoom);
+        let values = Self::sample_scalar(f, t_range.0, step_t, path.len(), scalar);
+        Self::extrude(&path, &broken, Some(&values), width_px, zoom, screen_h)
+    }
+
+    // 挤出前的采样折线 (世界坐标)：在无效值与断点处断开，每条至少两个点
+    // 断点判定与 solve 相同，矢量导出直接使用
+    pub fn polylines<F>(&self, f: &F, t_range: (f64, f64), zoom: f32, jump_factor: f32) -> Vec<Vec<(f64, f64)>>
+    where
+        F: Fn(f64) -> (f64, f64) + Sync + Send,
+    {
+        let Some((path, step_t)) = Self::sample_path(f, t_range) else { return Vec::new() };
+        let broken = Self::find_jumps(f, &path, t_range.0, step_t, jump_factor, zoom);
+        let finite = |p: &(f64, f64)| p.0.is_finite() && p.1.is_finite();
+
+        let mut lines = Vec::new();
+        let mut current = Vec::new();
+        for (i, p) in path.iter().enumerate() {
+            if !finite(p) || (i > 0 && broken[i - 1]) {
+                if current.len() > 1 { lines.push(std::mem::take(&mut current)); } else { current.clear(); }
+            }
+            if finite(p) { current.push(*p); }
+        }
+        if current.len() > 1 { lines.push(current); }
+        lines
+    }
+
+    // 采样点 t_i = t_min + i * step 处的标量
+    pub fn sample_scalar<F>(f: &F, t_min: f64, step_t: f64, count: usize, scalar: CurveScalar) -> Vec<f64>
+    where
+        F: Fn(f64) -> (f64, f64) + Sync + Send,
+    {
+        // 差分步长取采样间距的 1%，远小于曲线的可见细节
+        let h = step_t * 0.01;
+        (0..count).into_par_iter().map(|i| {
+            let t = t_min + i as f64 * step_t;
+            match scalar {
+                CurveScalar::Param => t,
+                CurveScalar::Speed => {
+                    let (a, b) = (f(t + h), f(t - h));
+                    (a.0 - b.0).hypot(a.1 - b.1) / (2.0 * h)
+                }
+                CurveScalar::Curvature => {
+                    // 二阶差分对舍入误差更敏感，步长放大到采样间距的 10%
+                    let h = h * 10.0;
+                    let (a, p, b) = (f(t + h), f(t), f(t - h));
+                    let (dx, dy) = ((a.0 - b.0) / (2.0 * h), (a.1 - b.1) / (2.0 * h));
+                    let (ddx, ddy) = ((a.0 - 2.0 * p.0 + b.0) / (h * h), (a.1 - 2.0 * p.1 + b.1) / (h * h));
+                    let speed = dx.hypot(dy);
+                    if speed < 1e-12 { f64::NAN } else { (dx * ddy - dy * ddx).abs() / speed.powi(3) }
+                }
+            }
+        }).collect()
+    }
+
+    // 1. 计算所有点 (包含屏幕外的)，返回 (采样点, t 步长)
+    fn sample_path<F>(f: &F, t_range: (f64, f64)) -> Option<(Vec<(f64, f64)>, f64)>
     where
         F: Fn(f64) -> (f64, f64) + Sync + Send,
     {
         let (t_min, t_max) = t_range;
         let t_len = t_max - t_min;
-        if t_len <= 0.0 { return Vec::new(); }
+        if t_len <= 0.0 { return None; }
 
         let total_samples = (t_len * SAMPLES_PER_UNIT_T).floor() as usize;
         let total_samples = total_samples.max(200);
         let step_t = t_len / total_samples as f64;
 
-        // 1. 计算所有点 (包含屏幕外的)
-        let path: Vec<(f64, f64)> = (0..=total_samples).into_par_iter().map(|i| {
+        let path = (0..=total_samples).into_par_iter().map(|i| {
             let t = t_min + i as f64 * step_t;
             f(t)
         }).collect();
+        Some((path, step_t))
+    }
+
+    // 2. 断裂检测：broken[i] 表示线段 path[i] -> path[i+1] 跨越了断点，不连线
+    // 距离超过阈值 (视口高度 × jump_factor) 的线段在 t 上二分：
+    // 陡峭但连续时较长的一半会逐层缩短到阈值内；真断点 (渐近线、跳跃) 则始终跨越原距离
+    fn find_jumps<F>(f: &F, path: &[(f64, f64)], t_min: f64, step_t: f64, jump_factor: f32, zoom: f32) -> Vec<bool>
+    where
+        F: Fn(f64) -> (f64, f64) + Sync + Send,
+    {
+        let max_jump = (2.0 / zoom as f64) * jump_factor as f64;
+        let max_jump_sq = max_jump * max_jump;
+        path.par_windows(2).enumerate().map(|(i, w)| {
+            let t0 = t_min + i as f64 * step_t;
+            is_jump(f, (t0, w[0]), (t0 + step_t, w[1]), max_jump_sq)
+        }).collect()
+    }
 
-        // 2. 准备网格参数
+    // 把折线挤出为三角形网格；values 存在时同步输出每个顶点的标量
+    // 每段独立成四边形，断点处直接跳过，断开后的两段不会在端点处拼接
+    fn extrude(
+        path: &[(f64, f64)],
+        broken: &[bool],
+        values: Option<&[f64]>,
+        width_px: f32,
+        zoom: f32,
+        screen_h: f32
+    ) -> (Vec<Vertex>, Vec<f32>) {
+        // 3. 准备网格参数
         let pixel_size_world = (2.0 / zoom) / screen_h;
         let half_width_world = (width_px * 0.5) * pixel_size_world;
 
-        // ★ 计算世界坐标系下的最大允许跳跃距离
-        // 视口高度 = 2.0 / zoom
-        let max_jump_dist_sq = ((2.0 / zoom) * JUMP_THRESHOLD_FACTOR).powi(2);
+        let mut vertices = Vec::with_capacity(path.len() * 6);
+        let mut scalars = Vec::with_capacity(if values.is_some() { path.len() * 6 } else { 0 });
 
-        let mut vertices = Vec::with_capacity(total_samples * 6);
-
-        // 3. 生成网格 (含熔断检测)
+        // 4. 生成网格 (含熔断检测)
         for i in 0..path.len().saturating_sub(1) {
             let p0 = path[i];
             let p1 = path[i+1];
@@ -67,9 +184,8 @@ impl ParametricSolver {
             // 如果两点重合，跳过
             if dist_sq < 1e-12 { continue; }
 
-            // ★ C. 渐近线熔断检测 (Asymptote Culling)
-            // 如果一步跨越了半个银河系，那肯定是渐近线，切断它！
-            if dist_sq > max_jump_dist_sq {
+            // C. 断点熔断 (渐近线 / 跳跃)：切断，两侧各自成段
+            if broken[i] {
                 continue;
             }
 
@@ -92,8 +208,152 @@ impl ParametricSolver {
             // 生成两个三角形 (Quad)
             vertices.push(p0_l); vertices.push(p1_l); vertices.push(p0_r);
             vertices.push(p0_r); vertices.push(p1_l); vertices.push(p1_r);
+            if let Some(values) = values {
+                let (s0, s1) = (values[i] as f32, values[i + 1] as f32);
+                scalars.extend_from_slice(&[s0, s1, s0, s0, s1, s1]);
+            }
+        }
+
+        (vertices, scalars)
+    }
+}
+
+fn dist_sq(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)
+}
+
+// (t0, p0) -> (t1, p1) 之间是否有断点：每次二分后只跟踪较长的一半
+// 连续曲线上弦长随区间减半而缩短，断点所在的一半则保持跨越原距离
+fn is_jump<F>(f: &F, a: (f64, (f64, f64)), b: (f64, (f64, f64)), max_jump_sq: f64) -> bool
+where
+    F: Fn(f64) -> (f64, f64),
+{
+    let finite = |p: (f64, f64)| p.0.is_finite() && p.1.is_finite();
+    // 端点无效的线段挤出时本就会跳过
+    if !finite(a.1) || !finite(b.1) { return false; }
+
+    let (mut a, mut b) = (a, b);
+    for _ in 0..JUMP_REFINE_MAX_DEPTH {
+        if dist_sq(a.1, b.1) <= max_jump_sq { return false; }
+        let tm = 0.5 * (a.0 + b.0);
+        let pm = f(tm);
+        if !finite(pm) { return true; }
+        if dist_sq(a.1, pm) >= dist_sq(pm, b.1) { b = (tm, pm); } else { a = (tm, pm); }
+    }
+    dist_sq(a.1, b.1) > max_jump_sq
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_by_param_is_monotonic() {
+        let f = |t: f64| ((3.0 * t).sin(), (2.0 * t).sin());
+        let values = ParametricSolver::sample_scalar(&f, -1.0, 0.01, 300, CurveScalar::Param);
+        assert_eq!(values[0], -1.0);
+        assert!(values.windows(2).all(|w| w[1] > w[0]));
+
+        // 每个输出顶点都有标量，且每个线段内的标量不减
+        let solver = ParametricSolver::new();
+        let (vertices, scalars) = solver.solve_with_scalar(&f, (0.0, 6.0), CurveScalar::Param, 4.0, 0.5, 400.0, JUMP_THRESHOLD_FACTOR);
+        assert!(!vertices.is_empty());
+        assert_eq!(vertices.len(), scalars.len());
+        for quad in scalars.chunks(6) {
+            assert!(quad[0] <= quad[1]);
+        }
+        assert!(scalars.first().unwrap() < scalars.last().unwrap());
+
+        // 单色路径的顶点不变
+        assert_eq!(
+            bytemuck::cast_slice::<Vertex, f32>(&solver.solve(&f, (0.0, 6.0), 4.0, 0.5, 1.0, 400.0, JUMP_THRESHOLD_FACTOR)),
+            bytemuck::cast_slice::<Vertex, f32>(&vertices),
+        );
+    }
+
+    #[test]
+    fn test_scalar_speed_on_circle() {
+        // 半径 2、角速度 3 的圆：|P'| = 6 处处相同
+        let f = |t: f64| (2.0 * (3.0 * t).cos(), 2.0 * (3.0 * t).sin());
+        let values = ParametricSolver::sample_scalar(&f, 0.0, 0.05, 100, CurveScalar::Speed);
+        for v in values {
+            assert!((v - 6.0).abs() < 1e-4, "speed = {}", v);
+        }
+
+        // 直线 (t, t)：速度 √2
+        let line = |t: f64| (t, t);
+        let (_, scalars) = ParametricSolver::new().solve_with_scalar(&line, (0.0, 1.0), CurveScalar::Speed, 2.0, 1.0, 400.0, JUMP_THRESHOLD_FACTOR);
+        assert!(scalars.iter().all(|v| (*v - std::f32::consts::SQRT_2).abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_scalar_curvature() {
+        // 半径 2 的圆：κ = 0.5
+        let circle = |t: f64| (2.0 * t.cos(), 2.0 * t.sin());
+        let values = ParametricSolver::sample_scalar(&circle, 0.0, 0.05, 100, CurveScalar::Curvature);
+        assert!(values.iter().all(|k| (k - 0.5).abs() < 1e-4));
+
+        // 抛物线 (t, t²)：顶点处 κ = 2
+        let parabola = |t: f64| (t, t * t);
+        let values = ParametricSolver::sample_scalar(&parabola, -1.0, 0.05, 41, CurveScalar::Curvature);
+        assert!((values[20] - 2.0).abs() < 1e-4);
+        assert!(values[0] < values[10] && values[10] < values[20]);
+    }
+
+    // 跨过 x = 0 的三角形 (两侧都超出线宽)
+    fn crosses_zero(vertices: &[Vertex], tol: f32) -> usize {
+        vertices.chunks(3).filter(|tri| {
+            let lo = tri.iter().map(|v| v.position[0]).fold(f32::INFINITY, f32::min);
+            let hi = tri.iter().map(|v| v.position[0]).fold(f32::NEG_INFINITY, f32::max);
+            lo < -tol && hi > tol
+        }).count()
+    }
+
+    #[test]
+    fn test_hyperbola_branches_not_joined() {
+        // (t, 1/t)：两支之间不应有穿过原点的连线，缩得再远也一样
+        let solver = ParametricSolver::new();
+        let f = |t: f64| (t, 1.0 / t);
+        for (t_range, zoom) in [((-10.0, 10.0), 1.0), ((-10.0, 10.0), 0.05), ((-10.01, 10.0), 0.02)] {
+            let vertices = solver.solve(&f, t_range, 2.0, zoom, 1.0, 400.0, JUMP_THRESHOLD_FACTOR);
+            assert!(!vertices.is_empty());
+            let line_width = 2.0 * (2.0 / zoom) / 400.0;
+            assert_eq!(crosses_zero(&vertices, line_width), 0, "zoom = {}", zoom);
         }
+    }
+
+    #[test]
+    fn test_steep_curve_stays_connected() {
+        // sin(t)/t 与陡峭的 (t, 50t³)：都连续，所有采样段都连上
+        let solver = ParametricSolver::new();
+        let sinc = |t: f64| (t, t.sin() / t);
+        let cubic = |t: f64| (t, 50.0 * t.powi(3));
+        for f in [&sinc as &(dyn Fn(f64) -> (f64, f64) + Sync + Send), &cubic] {
+            let (path, step_t) = ParametricSolver::sample_path(&f, (-10.0, 10.0)).unwrap();
+            let broken = ParametricSolver::find_jumps(&f, &path, -10.0, step_t, JUMP_THRESHOLD_FACTOR, 1.0);
+            assert!(broken.iter().all(|b| !b));
+            assert!(!solver.solve(&f, (-10.0, 10.0), 2.0, 1.0, 1.0, 400.0, JUMP_THRESHOLD_FACTOR).is_empty());
+        }
+
+        // 跳跃间断 (阶跃函数) 被切断
+        let step = |t: f64| (t, if t < 0.013 { 0.0 } else { 1.0 });
+        let (path, step_t) = ParametricSolver::sample_path(&step, (-1.0, 1.0)).unwrap();
+        let broken = ParametricSolver::find_jumps(&step, &path, -1.0, step_t, JUMP_THRESHOLD_FACTOR, 1.0);
+        assert_eq!(broken.iter().filter(|b| **b).count(), 1);
+    }
+
+    #[test]
+    fn test_polylines_split_at_breaks() {
+        let solver = ParametricSolver::new();
+        // (t, 1/t)：两支各成一条折线，0 附近的点都不连线
+        let lines = solver.polylines(&|t: f64| (t, 1.0 / t), (-10.0, 10.0), 1.0, JUMP_THRESHOLD_FACTOR);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].iter().all(|p| p.0 < 0.0) && lines[1].iter().all(|p| p.0 > 0.0));
 
-        vertices
+        // 连续曲线是一整条，端点即参数范围端点
+        let lines = solver.polylines(&|t: f64| (t.cos(), t.sin()), (0.0, 3.0), 1.0, JUMP_THRESHOLD_FACTOR);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0][0], (1.0, 0.0));
+        assert_eq!(*lines[0].last().unwrap(), (3.0f64.cos(), 3.0f64.sin()));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,99 @@
+// src/graph/d2/scatter.rs
+// 散点 (GeoType::Points)：每个点一个实例化四边形，大小为固定屏幕像素
+// 点是静态的世界坐标，平移 / 缩放只改全局 Uniform，不需要重新上传
+// 形状编号写在样式 Uniform 里，由片元着色器按 SDF 选择
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+
+// 标记形状
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MarkerShape {
+    #[default]
+    Circle,
+    Square,
+    Cross,
+}
+
+impl MarkerShape {
+    // 对应 shader.wgsl 中 fs_scatter 的分支编号
+    pub fn code(self) -> u32 {
+        match self {
+            MarkerShape::Circle => 0,
+            MarkerShape::Square => 1,
+            MarkerShape::Cross => 2,
+        }
+    }
+}
+
+// 散点的外观：形状 + 可选的逐点颜色 (缺省或下标越界的点用对象的 base_color)
+#[derive(Clone, Debug, Default)]
+pub struct MarkerStyle {
+    pub shape: MarkerShape,
+    pub colors: Option<Vec<[f32; 4]>>,
+}
+
+// 每个点的实例数据 (世界坐标 + 颜色)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+pub struct PointInstance {
+    pub position: [f32; 2],
+    pub color: [f32; 4],
+}
+
+// 与 PointInstance 的字段一一对应
+pub const POINT_ATTRIBUTES: [wgpu::VertexAttribute; 2] = wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4];
+
+// 点集 -> 实例数据：先施加仿射变换；非有限的点 (如求根失败的 NaN) 跳过
+pub fn build_instances(points: &[Vec2], style: &MarkerStyle, base_color: [f32; 4], transform: &Matrix3x3) -> Vec<PointInstance> {
+    let identity = *transform == Matrix3x3::IDENTITY;
+    points.iter().enumerate()
+        .filter(|(_, p)| p.x.is_finite() && p.y.is_finite())
+        .map(|(i, p)| {
+            let q = if identity { *p } else { transform.transform_point2(*p) };
+            let color = style.colors.as_ref().and_then(|c| c.get(i)).copied().unwrap_or(base_color);
+            PointInstance { position: q.to_f32_array(), color }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instance_layout() {
+        // 24 字节：位置 8 + 颜色 16，属性偏移与着色器的 location 对应
+        assert_eq!(size_of::<PointInstance>(), 24);
+        assert_eq!(POINT_ATTRIBUTES[0].offset, std::mem::offset_of!(PointInstance, position) as u64);
+        assert_eq!(POINT_ATTRIBUTES[1].offset, std::mem::offset_of!(PointInstance, color) as u64);
+        assert_eq!(POINT_ATTRIBUTES[1].shader_location, 1);
+
+        let instance = PointInstance { position: [1.0, 2.0], color: [0.1, 0.2, 0.3, 0.4] };
+        let floats: &[f32] = bytemuck::cast_slice(bytemuck::bytes_of(&instance));
+        assert_eq!(floats, &[1.0, 2.0, 0.1, 0.2, 0.3, 0.4]);
+    }
+
+    #[test]
+    fn test_build_instances() {
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let base = [1.0; 4];
+        let points = [Vec2::new(1.0, 0.0), Vec2::new(f64::NAN, 0.0), Vec2::new(0.0, 2.0)];
+
+        // 逐点颜色只覆盖前两个点；NaN 点被跳过，但颜色仍按原下标对应
+        let style = MarkerStyle { shape: MarkerShape::Square, colors: Some(vec![red, red]) };
+        let instances = build_instances(&points, &style, base, &Matrix3x3::IDENTITY);
+        assert_eq!(instances, vec![
+            PointInstance { position: [1.0, 0.0], color: red },
+            PointInstance { position: [0.0, 2.0], color: base },
+        ]);
+
+        // 仿射变换作用在点上
+        let moved = build_instances(&points, &MarkerStyle::default(), base, &Matrix3x3::from_translation(1.0, 1.0));
+        assert_eq!(moved[0].position, [2.0, 1.0]);
+        assert_eq!(moved[1].position, [1.0, 3.0]);
+        assert_eq!(MarkerShape::Cross.code(), 2);
+    }
+}
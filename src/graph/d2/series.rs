@@ -0,0 +1,314 @@
+// src/d2/series.rs
+// 时间序列通道：其他线程不断追加 (t, v) 采样，窗口把它画成滚动曲线 (示波器)
+//   数据：定长环形缓冲，满了覆盖最旧的采样；每个采样有单调递增的序号
+//   绘制：第 k 段 (采样 k - 1 -> k) 的四边形固定放在段槽 k % capacity，每帧只挤出新增的段
+//   跟随：最新采样保持在视口右边缘；用户手动拖动后暂停，复位视图后恢复
+#![allow(dead_code)]
+
+use std::ops::Range;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use super::common::Vertex;
+use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+
+// 有通道时窗口检查新采样的间隔 (约 60 帧)
+pub const POLL_INTERVAL: Duration = Duration::from_millis(16);
+// 跟随模式下最新采样距右边缘的留白 (视口宽度的比例)
+pub const FOLLOW_MARGIN: f64 = 0.05;
+// 每段一个四边形 = 两个三角形
+pub const VERTICES_PER_SEGMENT: usize = 6;
+
+// ====================== 环形缓冲 ======================
+
+pub struct RingBuffer<T> {
+    data: Vec<T>,
+    capacity: usize,
+    pushed: u64, // 累计写入个数 = 下一个采样的序号
+}
+
+impl<T: Copy> RingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "ring buffer capacity must be positive");
+        Self { data: Vec::with_capacity(capacity), capacity, pushed: 0 }
+    }
+
+    pub fn push(&mut self, value: T) {
+        let slot = (self.pushed % self.capacity as u64) as usize;
+        if slot < self.data.len() {
+            self.data[slot] = value;
+        } else {
+            self.data.push(value);
+        }
+        self.pushed += 1;
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    // 累计写入个数 (含已被覆盖的)
+    pub fn pushed(&self) -> u64 {
+        self.pushed
+    }
+
+    // 仍保留的最旧采样的序号
+    pub fn first_seq(&self) -> u64 {
+        self.pushed - self.data.len() as u64
+    }
+
+    // 按序号取采样，已被覆盖或尚未写入时为 None
+    pub fn get(&self, seq: u64) -> Option<T> {
+        if seq < self.first_seq() || seq >= self.pushed {
+            return None;
+        }
+        Some(self.data[(seq % self.capacity as u64) as usize])
+    }
+
+    pub fn last(&self) -> Option<T> {
+        self.pushed.checked_sub(1).and_then(|seq| self.get(seq))
+    }
+
+    // 从旧到新
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        (self.first_seq()..self.pushed).map(|seq| self.data[(seq % self.capacity as u64) as usize])
+    }
+}
+
+// ====================== 通道 ======================
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ChannelId(pub(crate) usize);
+
+// 通道的共享句柄：可以 clone 后交给模拟线程，窗口运行期间照常写入
+#[derive(Clone)]
+pub struct SeriesHandle {
+    samples: Arc<Mutex<RingBuffer<Vec2>>>,
+}
+
+impl SeriesHandle {
+    pub fn new(capacity: usize) -> Self {
+        Self { samples: Arc::new(Mutex::new(RingBuffer::new(capacity))) }
+    }
+
+    pub fn push(&self, t: f64, v: f64) {
+        self.lock().push(Vec2::new(t, v));
+    }
+
+    // 写入线程 panic 不影响读取：缓冲里的采样仍然有效
+    pub fn lock(&self) -> MutexGuard<'_, RingBuffer<Vec2>> {
+        self.samples.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ====================== 增量挤出 ======================
+
+// 一个通道在顶点缓冲中的簿记：capacity 个段槽，每槽 VERTICES_PER_SEGMENT 个顶点
+// 段 k 只在采样 k - 1 与 k 都还保留时有效，有效段的序号为 [start, end)
+pub struct SeriesTessellation {
+    capacity: usize,
+    start: u64,
+    end: u64,
+    half_width: f64, // 挤出时的世界半宽：缩放后与之不同，需要整体重建
+}
+
+impl SeriesTessellation {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, start: 0, end: 0, half_width: f64::NAN }
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.capacity * VERTICES_PER_SEGMENT
+    }
+
+    // 挤出新增的段，返回要写入的 (起始段槽, 顶点)；段槽回绕时拆成两次写入
+    // half_width 变化 (缩放) 时重建全部保留的段
+    pub fn update(&mut self, ring: &RingBuffer<Vec2>, transform: &Matrix3x3, half_width: f64) -> Vec<(usize, Vec<Vertex>)> {
+        let start = ring.first_seq() + 1;
+        let end = ring.pushed().max(start);
+        let from = if half_width == self.half_width { self.end.clamp(start, end) } else { start };
+        self.start = start;
+        self.end = end;
+        self.half_width = half_width;
+
+        let apply = |p: Vec2| transform.transform_point2(p);
+        slot_runs(from..end, self.capacity)
+            .into_iter()
+            .map(|(slot, seqs)| {
+                let vertices = seqs
+                    .flat_map(|k| {
+                        // 两端都在缓冲里 (k ∈ [start, end))
+                        let p0 = apply(ring.get(k - 1).unwrap());
+                        let p1 = apply(ring.get(k).unwrap());
+                        segment_quad(p0, p1, half_width)
+                    })
+                    .collect();
+                (slot, vertices)
+            })
+            .collect()
+    }
+
+    // 需要绘制的顶点区间 (至多两段)
+    pub fn draw_ranges(&self) -> Vec<Range<u32>> {
+        let n = VERTICES_PER_SEGMENT as u32;
+        slot_runs(self.start..self.end, self.capacity)
+            .into_iter()
+            .map(|(slot, seqs)| slot as u32 * n..(slot + seqs.count()) as u32 * n)
+            .collect()
+    }
+}
+
+// 把连续的段序号按段槽切成不回绕的若干段：(起始段槽, 序号区间)
+fn slot_runs(seqs: Range<u64>, capacity: usize) -> Vec<(usize, Range<u64>)> {
+    let cap = capacity as u64;
+    // 超过一圈的部分会被后面的覆盖，只保留最后 capacity 个
+    let mut k = seqs.start.max(seqs.end.saturating_sub(cap));
+    let mut runs = Vec::new();
+    while k < seqs.end {
+        let slot = k % cap;
+        let run_end = seqs.end.min(k + (cap - slot));
+        runs.push((slot as usize, k..run_end));
+        k = run_end;
+    }
+    runs
+}
+
+// 线段 p0 -> p1 挤出为两个三角形；非有限或重合的端点给出零面积三角形 (占住段槽但不可见)
+fn segment_quad(p0: Vec2, p1: Vec2, half_width: f64) -> [Vertex; VERTICES_PER_SEGMENT] {
+    let d = p1 - p0;
+    let len = d.len();
+    let finite = p0.x.is_finite() && p0.y.is_finite() && p1.x.is_finite() && p1.y.is_finite();
+    if !finite || len < 1e-12 {
+        return [Vertex { position: [0.0; 2] }; VERTICES_PER_SEGMENT];
+    }
+    let offset = d.roll90() * (half_width / len);
+    let v = |p: Vec2| Vertex { position: p.to_f32_array() };
+    let (p0_l, p0_r, p1_l, p1_r) = (v(p0 + offset), v(p0 - offset), v(p1 + offset), v(p1 - offset));
+    [p0_l, p1_l, p0_r, p0_r, p1_l, p1_r]
+}
+
+// ====================== 自动跟随 ======================
+
+// 让最新采样 newest_t 落在右边缘内侧 FOLLOW_MARGIN 处的视口中心 x；range_x 为视口半宽
+pub fn follow_center_x(newest_t: f64, range_x: f64) -> f64 {
+    newest_t - range_x + 2.0 * range_x * FOLLOW_MARGIN
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring_with(capacity: usize, n: u64) -> RingBuffer<Vec2> {
+        let mut ring = RingBuffer::new(capacity);
+        for i in 0..n {
+            ring.push(Vec2::new(i as f64, (i * i) as f64));
+        }
+        ring
+    }
+
+    #[test]
+    fn test_ring_buffer() {
+        let mut ring = RingBuffer::new(3);
+        assert!(ring.is_empty() && ring.last().is_none());
+        ring.push(1);
+        ring.push(2);
+        assert_eq!(ring.iter().collect::<Vec<_>>(), vec![1, 2]);
+        ring.push(3);
+        ring.push(4);
+        ring.push(5);
+        // 满了覆盖最旧的
+        assert_eq!(ring.len(), 3);
+        assert_eq!(ring.pushed(), 5);
+        assert_eq!(ring.first_seq(), 2);
+        assert_eq!(ring.iter().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(ring.get(1), None);
+        assert_eq!(ring.get(2), Some(3));
+        assert_eq!(ring.get(5), None);
+        assert_eq!(ring.last(), Some(5));
+
+        // 多线程写入
+        let handle = SeriesHandle::new(100);
+        let producer = handle.clone();
+        std::thread::spawn(move || (0..10).for_each(|i| producer.push(i as f64, 0.0))).join().unwrap();
+        assert_eq!(handle.lock().len(), 10);
+    }
+
+    #[test]
+    fn test_slot_runs() {
+        assert_eq!(slot_runs(1..4, 8), vec![(1, 1..4)]);
+        // 回绕：序号 6..11 落在段槽 6, 7 | 0, 1, 2
+        assert_eq!(slot_runs(6..11, 8), vec![(6, 6..8), (0, 8..11)]);
+        // 超过一圈只保留最后 capacity 个
+        assert_eq!(slot_runs(0..20, 8), vec![(4, 12..16), (0, 16..20)]);
+        assert!(slot_runs(5..5, 8).is_empty());
+    }
+
+    #[test]
+    fn test_incremental_tessellation() {
+        let id = Matrix3x3::IDENTITY;
+        let mut tess = SeriesTessellation::new(4);
+        let mut ring = ring_with(4, 3);
+
+        // 3 个采样 -> 段 1, 2
+        let writes = tess.update(&ring, &id, 0.1);
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].0, 1);
+        assert_eq!(writes[0].1.len(), 2 * VERTICES_PER_SEGMENT);
+        assert_eq!(tess.draw_ranges(), vec![6..18]);
+
+        // 没有新采样：不写入
+        assert!(tess.update(&ring, &id, 0.1).is_empty());
+
+        // 再来 3 个：只挤出段 3, 4, 5 (段槽 3 | 0, 1)，最旧的段 1、2 被覆盖
+        for i in 3..6 {
+            ring.push(Vec2::new(i as f64, 0.0));
+        }
+        let writes = tess.update(&ring, &id, 0.1);
+        let slots: Vec<(usize, usize)> = writes.iter().map(|(s, v)| (*s, v.len() / VERTICES_PER_SEGMENT)).collect();
+        assert_eq!(slots, vec![(3, 1), (0, 2)]);
+        // 保留采样 2..6，有效段 3, 4, 5
+        assert_eq!(tess.draw_ranges(), vec![18..24, 0..12]);
+
+        // 段槽 0 的四边形连接采样 3 -> 4
+        let quad = &writes[1].1[..VERTICES_PER_SEGMENT];
+        let xs: Vec<f32> = quad.iter().map(|v| v.position[0]).collect();
+        assert!(xs.iter().all(|&x| (3.0..=4.0).contains(&x)));
+
+        // 缩放后线宽变化：全部重建
+        let writes = tess.update(&ring, &id, 0.2);
+        let total: usize = writes.iter().map(|(_, v)| v.len()).sum();
+        assert_eq!(total, 3 * VERTICES_PER_SEGMENT);
+
+        // 单个采样没有段可画
+        let mut single = SeriesTessellation::new(4);
+        single.update(&ring_with(4, 1), &id, 0.1);
+        assert!(single.draw_ranges().is_empty());
+    }
+
+    #[test]
+    fn test_segment_quad_width() {
+        let quad = segment_quad(Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), 0.5);
+        let ys: Vec<f32> = quad.iter().map(|v| v.position[1]).collect();
+        assert!(ys.iter().all(|y| (y.abs() - 0.5).abs() < 1e-6));
+        // NaN 采样：零面积
+        let quad = segment_quad(Vec2::new(0.0, 0.0), Vec2::new(f64::NAN, 0.0), 0.5);
+        assert!(quad.iter().all(|v| v.position == [0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_follow_center() {
+        // 视口半宽 5：右边缘 = center + 5，最新采样在右边缘内侧 0.05 × 10
+        let cx = follow_center_x(100.0, 5.0);
+        assert!((cx + 5.0 - (100.0 + 0.5)).abs() < 1e-12);
+        assert!(cx - 5.0 < 100.0);
+    }
+}
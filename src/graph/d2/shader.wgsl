@@ -12,6 +12,7 @@ struct ViewUniforms {
 struct Style {
     color: vec4<f32>,
     width: f32,
+    shape: u32, // 散点标记形状：0 圆，1 方块，2 叉号
 };
 
 @group(0) @binding(0) var<uniform> view: ViewUniforms;
@@ -51,6 +52,21 @@ fn fs_grid(in: VertexOutput) -> @location(0) vec4<f32> {
     return mix(vec4<f32>(0.05, 0.05, 0.05, 1.0), vec4<f32>(0.5, 0.5, 0.5, 1.0), grid);
 }
 
+// 有热力图时网格叠在它上面：背景透明，alpha 取网格强度
+// 除坐标轴外再画整数网格线 (较淡)，线距小于 8 像素时淡出
+@fragment
+fn fs_grid_overlay(in: VertexOutput) -> @location(0) vec4<f32> {
+    let coord = in.uv;
+    let px = fwidth(coord);
+    let axis = smoothstep(2.0 * px, vec2<f32>(0.0), abs(coord));
+    let f = fract(coord);
+    let dist = min(f, vec2<f32>(1.0) - f);
+    let fade = 1.0 - smoothstep(vec2<f32>(1.0 / 16.0), vec2<f32>(1.0 / 8.0), px);
+    let line = smoothstep(px, vec2<f32>(0.0), dist) * fade * 0.35;
+    let grid = max(max(axis.x, axis.y), max(line.x, line.y));
+    return vec4<f32>(0.5, 0.5, 0.5, grid);
+}
+
 // ==========================================
 // 2. Implicit Shader (Points) - 隐函数点
 // ==========================================
@@ -89,6 +105,45 @@ fn fs_point(in: VertexOutput) -> @location(0) vec4<f32> {
     return vec4<f32>(style.color.rgb, style.color.a * alpha);
 }
 
+// 逐点着色的隐函数：四边形同 vs_point，颜色来自第二个实例缓冲
+struct ColoredPointOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_point_colored(
+    @builtin(vertex_index) idx: u32,
+    @location(0) center_pos: vec2<f32>,
+    @location(1) color: vec4<f32>
+) -> ColoredPointOutput {
+    var out: ColoredPointOutput;
+
+    let u = f32(i32(idx) & 1) * 2.0 - 1.0;
+    let v = f32(i32(idx >> 1u) & 1) * 2.0 - 1.0;
+    out.uv = vec2<f32>(u, v);
+    out.color = color;
+
+    let range_y = 2.0 / view.zoom;
+    let range_x = range_y * view.aspect;
+    let ndc = vec2<f32>((center_pos.x - view.center.x) / range_x, (center_pos.y - view.center.y) / range_y);
+
+    let pixel_scale = vec2<f32>(2.0 / view.resolution.x, 2.0 / view.resolution.y);
+    let offset = vec2<f32>(u, v) * (style.width * 0.5) * pixel_scale;
+
+    out.clip_position = vec4<f32>(ndc + offset, 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_point_colored(in: ColoredPointOutput) -> @location(0) vec4<f32> {
+    let d = dot(in.uv, in.uv);
+    let alpha = 1.0 - smoothstep(0.8, 1.0, d);
+    if (alpha <= 0.0) { discard; }
+    return vec4<f32>(in.color.rgb, in.color.a * alpha);
+}
+
 // ==========================================
 // 3. Parametric Shader (Solid Mesh) - 参数方程线
 //    CPU 已经把顶点算好了，这里只负责简单的世界转屏幕
@@ -107,4 +162,164 @@ fn vs_mesh(@location(0) pos: vec2<f32>) -> @builtin(position) vec4<f32> {
 @fragment
 fn fs_mesh() -> @location(0) vec4<f32> {
     return style.color;
-}
\ No newline at end of file
+}
+// ==========================================
+// 4. Gradient Mesh Shader - 按逐顶点标量渐变着色的参数方程线
+//    标量 (参数 t / 速度) 由 CPU 求出，按 [lo, hi] 归一化后查色表
+// ==========================================
+struct Gradient {
+    colors: array<vec4<f32>, 8>,
+    positions: array<vec4<f32>, 2>, // 色标位置，每 4 个打包
+    count: u32,
+    lo: f32,
+    hi: f32,
+    _pad: f32,
+};
+
+@group(2) @binding(0) var<uniform> gradient: Gradient;
+
+struct GradientOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) value: f32,
+};
+
+@vertex
+fn vs_mesh_gradient(@location(0) pos: vec2<f32>, @location(1) value: f32) -> GradientOutput {
+    let range_y = 2.0 / view.zoom;
+    let range_x = range_y * view.aspect;
+
+    var out: GradientOutput;
+    out.clip_position = vec4<f32>((pos.x - view.center.x) / range_x, (pos.y - view.center.y) / range_y, 0.0, 1.0);
+    out.value = value;
+    return out;
+}
+
+fn gradient_position(i: u32) -> f32 {
+    return gradient.positions[i / 4u][i % 4u];
+}
+
+@fragment
+fn fs_mesh_gradient(in: GradientOutput) -> @location(0) vec4<f32> {
+    let s = clamp((in.value - gradient.lo) / (gradient.hi - gradient.lo), 0.0, 1.0);
+    var color = gradient.colors[0];
+    for (var i = 1u; i < gradient.count; i++) {
+        let p0 = gradient_position(i - 1u);
+        let p1 = gradient_position(i);
+        if (s >= p0) {
+            let k = clamp((s - p0) / max(p1 - p0, 1e-6), 0.0, 1.0);
+            color = mix(gradient.colors[i - 1u], gradient.colors[i], k);
+        }
+    }
+    return color;
+}
+
+// ==========================================
+// 5. Marker Shader - 交点标记
+//    与隐函数点同为实例化四边形，但半径固定为屏幕像素，带深色描边以区别于曲线
+// ==========================================
+const MARKER_RADIUS_PX: f32 = 6.0;
+
+struct MarkerOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_marker(
+    @builtin(vertex_index) idx: u32,
+    @location(0) center_pos: vec2<f32>,
+    @location(1) color: vec4<f32>
+) -> MarkerOutput {
+    var out: MarkerOutput;
+
+    let u = f32(i32(idx) & 1) * 2.0 - 1.0;
+    let v = f32(i32(idx >> 1u) & 1) * 2.0 - 1.0;
+    out.uv = vec2<f32>(u, v);
+    out.color = color;
+
+    let range_y = 2.0 / view.zoom;
+    let range_x = range_y * view.aspect;
+    let ndc = vec2<f32>((center_pos.x - view.center.x) / range_x, (center_pos.y - view.center.y) / range_y);
+
+    let pixel_scale = vec2<f32>(2.0 / view.resolution.x, 2.0 / view.resolution.y);
+    let offset = vec2<f32>(u, v) * MARKER_RADIUS_PX * pixel_scale;
+
+    out.clip_position = vec4<f32>(ndc + offset, 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_marker(in: MarkerOutput) -> @location(0) vec4<f32> {
+    let d = length(in.uv);
+    let aa = fwidth(d);
+    let alpha = 1.0 - smoothstep(1.0 - aa, 1.0, d);
+    if (alpha <= 0.0) { discard; }
+    // 外圈描边 (约 1.5 像素)，内部为标记颜色
+    let ring = smoothstep(0.7 - aa, 0.7, d);
+    let rgb = mix(in.color.rgb, vec3<f32>(0.05, 0.05, 0.05), ring);
+    return vec4<f32>(rgb, in.color.a * alpha);
+}
+
+// ==========================================
+// 6. Scatter Shader - 散点
+//    实例化四边形，直径 style.width (物理像素)，不随缩放变化
+//    形状由有符号距离场给出 (uv 单位：半径 = 1，内部为负)
+// ==========================================
+struct ScatterOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_scatter(
+    @builtin(vertex_index) idx: u32,
+    @location(0) center_pos: vec2<f32>,
+    @location(1) color: vec4<f32>
+) -> ScatterOutput {
+    var out: ScatterOutput;
+
+    let u = f32(i32(idx) & 1) * 2.0 - 1.0;
+    let v = f32(i32(idx >> 1u) & 1) * 2.0 - 1.0;
+    out.uv = vec2<f32>(u, v);
+    out.color = color;
+
+    let range_y = 2.0 / view.zoom;
+    let range_x = range_y * view.aspect;
+    let ndc = vec2<f32>((center_pos.x - view.center.x) / range_x, (center_pos.y - view.center.y) / range_y);
+
+    let pixel_scale = vec2<f32>(2.0 / view.resolution.x, 2.0 / view.resolution.y);
+    let offset = vec2<f32>(u, v) * style.width * 0.5 * pixel_scale;
+
+    out.clip_position = vec4<f32>(ndc + offset, 0.0, 1.0);
+    return out;
+}
+
+fn marker_sdf(uv: vec2<f32>, shape: u32) -> f32 {
+    switch shape {
+        case 1u: {
+            let a = abs(uv);
+            return max(a.x, a.y) - 0.8;
+        }
+        case 2u: {
+            // 两条对角线方向的细条 (半宽 0.2)
+            let r = abs(vec2<f32>(uv.x + uv.y, uv.x - uv.y)) * 0.70710678;
+            let bar1 = max(r.x - 0.2, r.y - 1.0);
+            let bar2 = max(r.y - 0.2, r.x - 1.0);
+            return min(bar1, bar2);
+        }
+        default: {
+            return length(uv) - 1.0;
+        }
+    }
+}
+
+@fragment
+fn fs_scatter(in: ScatterOutput) -> @location(0) vec4<f32> {
+    let d = marker_sdf(in.uv, style.shape);
+    let aa = fwidth(d);
+    let alpha = 1.0 - smoothstep(-aa, 0.0, d);
+    if (alpha <= 0.0) { discard; }
+    return vec4<f32>(in.color.rgb, in.color.a * alpha);
+}
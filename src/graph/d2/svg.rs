@@ -0,0 +1,400 @@
+// src/d2/svg.rs
+// 矢量导出：把当前视图中的对象按 CPU 端几何重放为 SVG (论文插图等)
+//   显函数 / 参数方程：挤出前的采样折线 -> <path>，线宽与颜色即 SVG 描边
+//   隐函数：暂无等值线折线，退化为零点处的小圆点
+//   网格与坐标轴：细灰线，刻度步长取 1 / 2 / 5 × 10^k，刻度值为 <text>
+// 画布坐标：视口 x_range × y_range 映射到 [0, width] × [0, height]，y 轴向下
+#![allow(dead_code)]
+
+use std::fmt::Write;
+
+use super::common::{GeoObj, GeoType, ViewInfo};
+use super::explicit::ExplicitSolver;
+use super::implicit::ImplicitSolver;
+use super::parametric::{ParametricSolver, JUMP_THRESHOLD_FACTOR};
+use super::text::Annotation;
+use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+
+// 沿较短边大约画多少条网格线
+const GRID_TARGET_LINES: f64 = 8.0;
+const GRID_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 0.3];
+const GRID_WIDTH: f64 = 0.5;
+// 坐标轴与屏幕上的 fs_grid 同色
+const AXIS_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 1.0];
+const AXIS_WIDTH: f64 = 1.0;
+const LABEL_SIZE: f64 = 10.0;
+
+// 极简 SVG 写出器：只输出本模块用到的几种元素
+pub struct SvgWriter {
+    out: String,
+}
+
+impl SvgWriter {
+    // 画布 width × height (用户单位)，viewBox 与之相同
+    pub fn new(width: u32, height: u32) -> Self {
+        let mut out = String::new();
+        let _ = writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        let _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width, h = height,
+        );
+        // 曲线裁剪到画布内 (参数方程的采样点可能远在视口之外)
+        let _ = writeln!(out, r#"<defs><clipPath id="view"><rect x="0" y="0" width="{}" height="{}"/></clipPath></defs>"#, width, height);
+        Self { out }
+    }
+
+    pub fn begin_group(&mut self, clipped: bool) {
+        self.out.push_str(if clipped { "<g clip-path=\"url(#view)\">\n" } else { "<g>\n" });
+    }
+
+    pub fn end_group(&mut self) {
+        self.out.push_str("</g>\n");
+    }
+
+    // 折线 (少于两个点时不输出)
+    pub fn path(&mut self, points: &[(f64, f64)], color: [f32; 4], width: f64) {
+        if points.len() < 2 { return; }
+        let mut d = String::with_capacity(points.len() * 16);
+        for (i, (x, y)) in points.iter().enumerate() {
+            let _ = write!(d, "{}{:.2} {:.2}", if i == 0 { "M" } else { " L" }, x, y);
+        }
+        let _ = writeln!(
+            self.out,
+            r#"<path d="{}" fill="none" stroke="{}" stroke-opacity="{}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+            d, rgb(color), color[3], width,
+        );
+    }
+
+    pub fn line(&mut self, a: (f64, f64), b: (f64, f64), color: [f32; 4], width: f64) {
+        let _ = writeln!(
+            self.out,
+            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="{}" stroke-opacity="{}" stroke-width="{}"/>"#,
+            a.0, a.1, b.0, b.1, rgb(color), color[3], width,
+        );
+    }
+
+    pub fn circle(&mut self, c: (f64, f64), r: f64, color: [f32; 4]) {
+        let _ = writeln!(
+            self.out,
+            r#"<circle cx="{:.2}" cy="{:.2}" r="{}" fill="{}" fill-opacity="{}"/>"#,
+            c.0, c.1, r, rgb(color), color[3],
+        );
+    }
+
+    // anchor: "start" / "middle" / "end"
+    pub fn text(&mut self, p: (f64, f64), text: &str, color: [f32; 4], size: f64, anchor: &str) {
+        let _ = writeln!(
+            self.out,
+            r#"<text x="{:.2}" y="{:.2}" font-family="monospace" font-size="{}" text-anchor="{}" fill="{}" fill-opacity="{}">{}</text>"#,
+            p.0, p.1, size, anchor, rgb(color), color[3], escape(text),
+        );
+    }
+
+    pub fn finish(mut self) -> String {
+        self.out.push_str("</svg>\n");
+        self.out
+    }
+}
+
+fn rgb(c: [f32; 4]) -> String {
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", channel(c[0]), channel(c[1]), channel(c[2]))
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+// 世界坐标 -> 画布坐标
+pub fn to_canvas(view: &ViewInfo, (x, y): (f64, f64)) -> (f64, f64) {
+    let (x0, x1) = view.x_range;
+    let (y0, y1) = view.y_range;
+    let (w, h) = (view.pixels.0 as f64, view.pixels.1 as f64);
+    ((x - x0) / (x1 - x0) * w, (y1 - y) / (y1 - y0) * h)
+}
+
+// 整齐的刻度步长：1 / 2 / 5 × 10^k 中使 span 内约有 target 格的那个
+pub fn nice_step(span: f64, target: f64) -> f64 {
+    let raw = span / target;
+    let mag = 10f64.powf(raw.log10().floor());
+    let norm = raw / mag;
+    let nice = if norm < 1.5 { 1.0 } else if norm < 3.5 { 2.0 } else if norm < 7.5 { 5.0 } else { 10.0 };
+    nice * mag
+}
+
+// 刻度值文本：小数位数由步长决定 (0.25 的步长不会出现，1 / 2 / 5 只需 -log10 位)
+fn format_tick(v: f64, step: f64) -> String {
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    let s = format!("{:.*}", decimals, v);
+    // 避免 "-0"
+    if s.trim_start_matches('-').chars().all(|c| c == '0' || c == '.') { "0".to_string() } else { s }
+}
+
+// [lo, hi] 内所有 step 的整数倍
+fn ticks(lo: f64, hi: f64, step: f64) -> impl Iterator<Item = f64> {
+    let first = (lo / step).ceil() as i64;
+    let last = (hi / step).floor() as i64;
+    (first..=last).map(move |k| k as f64 * step)
+}
+
+// 网格线、坐标轴与刻度值
+fn write_grid(svg: &mut SvgWriter, view: &ViewInfo) {
+    let (x0, x1) = view.x_range;
+    let (y0, y1) = view.y_range;
+    let (w, h) = (view.pixels.0 as f64, view.pixels.1 as f64);
+    let step = nice_step((x1 - x0).min(y1 - y0), GRID_TARGET_LINES);
+
+    svg.begin_group(false);
+    for x in ticks(x0, x1, step) {
+        let (cx, _) = to_canvas(view, (x, 0.0));
+        svg.line((cx, 0.0), (cx, h), GRID_COLOR, GRID_WIDTH);
+    }
+    for y in ticks(y0, y1, step) {
+        let (_, cy) = to_canvas(view, (0.0, y));
+        svg.line((0.0, cy), (w, cy), GRID_COLOR, GRID_WIDTH);
+    }
+
+    // 坐标轴 (可见时)；刻度值贴着坐标轴，轴在视口外时贴边
+    let (ox, oy) = to_canvas(view, (0.0, 0.0));
+    if (0.0..=w).contains(&ox) { svg.line((ox, 0.0), (ox, h), AXIS_COLOR, AXIS_WIDTH); }
+    if (0.0..=h).contains(&oy) { svg.line((0.0, oy), (w, oy), AXIS_COLOR, AXIS_WIDTH); }
+    let label_y = (oy + LABEL_SIZE + 2.0).clamp(LABEL_SIZE + 2.0, h - 2.0);
+    let label_x = (ox - 3.0).clamp(LABEL_SIZE * 2.0, w - 3.0);
+    for x in ticks(x0, x1, step).filter(|x| x.abs() > step * 0.5) {
+        let (cx, _) = to_canvas(view, (x, 0.0));
+        svg.text((cx, label_y), &format_tick(x, step), AXIS_COLOR, LABEL_SIZE, "middle");
+    }
+    for y in ticks(y0, y1, step).filter(|y| y.abs() > step * 0.5) {
+        let (_, cy) = to_canvas(view, (0.0, y));
+        svg.text((label_x, cy + LABEL_SIZE * 0.35), &format_tick(y, step), AXIS_COLOR, LABEL_SIZE, "end");
+    }
+    svg.end_group();
+}
+
+// 一个对象的矢量几何 (世界坐标)
+enum Shape {
+    Lines(Vec<Vec<(f64, f64)>>),
+    Points(Vec<(f64, f64)>),
+}
+
+// 与 main::solve_geo_obj 的分派一致，只是不做线宽挤出
+fn object_shape(
+    obj: &GeoObj,
+    view: &ViewInfo,
+    implicit_solver: &ImplicitSolver,
+    parametric_solver: &ParametricSolver,
+    explicit_solver: &ExplicitSolver,
+) -> Shape {
+    let (width, height) = view.pixels;
+    let zoom = view.zoom as f32;
+    let jump_factor = obj.jump_threshold.unwrap_or(JUMP_THRESHOLD_FACTOR);
+    let m = obj.transform;
+    let identity = m == Matrix3x3::IDENTITY;
+    let apply = |(x, y): (f64, f64)| { let q = m.transform_point2(Vec2::new(x, y)); (q.x, q.y) };
+    let points = |vs: Vec<super::common::Vertex>| {
+        Shape::Points(vs.iter().map(|v| (v.position[0] as f64, v.position[1] as f64)).collect())
+    };
+    let parametric = |g: &(dyn Fn(f64) -> (f64, f64) + Sync + Send), t_range: (f64, f64)| {
+        Shape::Lines(parametric_solver.polylines(&g, t_range, zoom, jump_factor))
+    };
+
+    match &obj.geo_type {
+        GeoType::Implicit(func) | GeoType::ImplicitColored(func, _) => {
+            if identity {
+                return points(implicit_solver.solve(func, view.x_range, view.y_range, width, height));
+            }
+            let Some(inv) = m.inverse() else { return Shape::Points(Vec::new()) };
+            let f = |x: f64, y: f64| { let q = inv.transform_point2(Vec2::new(x, y)); func(q.x, q.y) };
+            points(implicit_solver.solve(&f, view.x_range, view.y_range, width, height))
+        }
+        GeoType::LevelSet(func, levels) => {
+            let Some(inv) = m.inverse() else { return Shape::Points(Vec::new()) };
+            let f = |x: f64, y: f64| { let q = inv.transform_point2(Vec2::new(x, y)); func(q.x, q.y) };
+            points(implicit_solver.solve_levels(&f, levels, view.x_range, view.y_range, width, height))
+        }
+        GeoType::ImplicitDetailed(func) => {
+            let Some(inv) = m.inverse() else { return Shape::Points(Vec::new()) };
+            let detail = view.pixel_size() * inv.det().abs().sqrt();
+            let f = |x: f64, y: f64| { let q = inv.transform_point2(Vec2::new(x, y)); func(q.x, q.y, detail) };
+            points(implicit_solver.solve(&f, view.x_range, view.y_range, width, height))
+        }
+        GeoType::Parametric(func, t_range) => {
+            if identity { parametric(func.as_ref(), *t_range) } else { parametric(&|t: f64| apply(func(t)), *t_range) }
+        }
+        GeoType::ParametricDynamic(func, t_range_fn) => {
+            let t_range = t_range_fn(view);
+            if identity { parametric(func.as_ref(), t_range) } else { parametric(&|t: f64| apply(func(t)), t_range) }
+        }
+        GeoType::Explicit(func, domain) => {
+            if identity {
+                return Shape::Lines(explicit_solver.polylines_adaptive(func, view.x_range, *domain, zoom, width));
+            }
+            let (t0, t1) = view.x_range_under(&m);
+            let (t0, t1) = (t0.max(domain.0), t1.min(domain.1));
+            parametric(&|t: f64| apply((t, func(t))), (t0, t1))
+        }
+        // 散点导出为圆点 (直径同屏幕上的标记)，形状与逐点颜色不导出
+        GeoType::Points(ps) => Shape::Points(
+            ps.iter().filter(|p| p.x.is_finite() && p.y.is_finite()).map(|p| apply((p.x, p.y))).collect()
+        ),
+        // 时间序列的采样在窗口的通道里，导出时不含；热力图是栅格，不导出
+        GeoType::Series(_) | GeoType::Heatmap(_, _) | GeoType::Geometry | GeoType::Wipkyy(_) => Shape::Lines(Vec::new()),
+    }
+}
+
+// 整个视图的 SVG 文本：网格在下，对象按添加顺序叠放，标注在最上
+// view.pixels 即画布尺寸；线宽按逻辑点 (GeoObj::width) 直接作为 SVG 描边宽度
+// 渐变着色的对象以 base_color 单色输出
+pub fn render(
+    objects: &[GeoObj],
+    annotations: &[Annotation],
+    view: &ViewInfo,
+    implicit_solver: &ImplicitSolver,
+    parametric_solver: &ParametricSolver,
+    explicit_solver: &ExplicitSolver,
+) -> String {
+    let mut svg = SvgWriter::new(view.pixels.0, view.pixels.1);
+    write_grid(&mut svg, view);
+
+    svg.begin_group(true);
+    for obj in objects {
+        let color = obj.coloring.base_color();
+        match object_shape(obj, view, implicit_solver, parametric_solver, explicit_solver) {
+            Shape::Lines(lines) => {
+                for line in lines {
+                    let points: Vec<(f64, f64)> = line.into_iter().map(|p| to_canvas(view, p)).collect();
+                    svg.path(&points, color, obj.width as f64);
+                }
+            }
+            Shape::Points(points) => {
+                for p in points {
+                    // 逐点着色的隐函数按点取色
+                    let color = match &obj.geo_type {
+                        GeoType::ImplicitColored(_, color_fn) => color_fn(p.0, p.1),
+                        _ => color,
+                    };
+                    svg.circle(to_canvas(view, p), obj.width as f64 * 0.5, color);
+                }
+            }
+        }
+    }
+    svg.end_group();
+
+    svg.begin_group(false);
+    for a in annotations {
+        let (x, y) = to_canvas(view, a.pos);
+        svg.text((x + 4.0, y - 4.0), &a.text, a.color, LABEL_SIZE * 1.2, "start");
+    }
+    svg.end_group();
+    svg.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    // (元素名, 属性) 列表
+    fn parse(svg: &str) -> Vec<(String, Vec<(String, String)>)> {
+        let mut reader = Reader::from_str(svg);
+        let mut elements = Vec::new();
+        loop {
+            match reader.read_event().expect("SVG 不是合法的 XML") {
+                Event::Start(e) | Event::Empty(e) => {
+                    let name = String::from_utf8(e.name().as_ref().to_vec()).unwrap();
+                    let attrs = e.attributes().map(|a| {
+                        let a = a.unwrap();
+                        (String::from_utf8(a.key.as_ref().to_vec()).unwrap(), a.unescape_value().unwrap().into_owned())
+                    }).collect();
+                    elements.push((name, attrs));
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        elements
+    }
+
+    fn attr<'a>(element: &'a (String, Vec<(String, String)>), key: &str) -> &'a str {
+        element.1.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str()).unwrap()
+    }
+
+    fn view() -> ViewInfo {
+        ViewInfo { x_range: (-4.0, 4.0), y_range: (-2.0, 2.0), zoom: 1.0, pixels: (800, 400), scale_factor: 1.0 }
+    }
+
+    fn render_objects(objects: &[GeoObj], annotations: &[Annotation]) -> String {
+        render(objects, annotations, &view(), &ImplicitSolver::new(), &ParametricSolver::new(), &ExplicitSolver::new())
+    }
+
+    #[test]
+    fn test_svg_structure() {
+        let objects = vec![
+            GeoObj::new_explicit(|x| x * x - 1.0, [1.0, 0.0, 0.0, 1.0], 2.0),
+            // tan 在视口内有两条渐近线，断成三段
+            GeoObj::new_explicit(|x| x.tan(), [0.0, 0.0, 1.0, 1.0], 3.0),
+            GeoObj::new_parametric(|t| (t.cos(), t.sin()), (0.0, std::f64::consts::TAU), [0.0, 1.0, 0.0, 1.0], 1.5),
+            GeoObj::new_implicit(|x, y| x * x / 9.0 + y * y - 1.0, [1.0; 4], 2.0),
+        ];
+        let annotations = vec![Annotation { pos: (1.0, 1.0), text: "a < b".to_string(), color: [1.0; 4] }];
+        let svg = render_objects(&objects, &annotations);
+        let elements = parse(&svg);
+
+        let root = &elements[0];
+        assert_eq!(root.0, "svg");
+        assert_eq!(attr(root, "viewBox"), "0 0 800 400");
+        assert_eq!((attr(root, "width"), attr(root, "height")), ("800", "400"));
+
+        let paths: Vec<_> = elements.iter().filter(|e| e.0 == "path").collect();
+        assert_eq!(paths.len(), 1 + 3 + 1);
+        assert_eq!(paths.iter().filter(|p| attr(p, "stroke") == "#0000ff").count(), 3);
+        assert!(paths.iter().all(|p| attr(p, "fill") == "none"));
+        assert_eq!(attr(paths[0], "stroke-width"), "2");
+
+        // 隐函数退化为圆点
+        assert!(elements.iter().filter(|e| e.0 == "circle").count() > 50);
+        // 刻度值与标注 (特殊字符已转义，解析后还原)
+        assert!(svg.contains(">a &lt; b</text>"));
+        assert!(elements.iter().filter(|e| e.0 == "text").count() > 5);
+    }
+
+    #[test]
+    fn test_horizontal_line_maps_to_expected_y() {
+        // y = 1 在 y_range = (-2, 2)、高 400 的画布上位于 y = 100
+        let svg = render_objects(&[GeoObj::new_explicit(|_| 1.0, [1.0; 4], 2.0)], &[]);
+        let elements = parse(&svg);
+        let path = elements.iter().find(|e| e.0 == "path").unwrap();
+        let d = attr(path, "d");
+        let coords: Vec<f64> = d.split(|c: char| c == 'M' || c == 'L' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse().unwrap())
+            .collect();
+        assert!(coords.len() >= 4);
+        let (xs, ys): (Vec<f64>, Vec<f64>) = coords.chunks(2).map(|c| (c[0], c[1])).unzip();
+        assert!(ys.iter().all(|y| (y - 100.0).abs() < 1e-9));
+        // 横向铺满画布
+        assert_eq!(xs[0], 0.0);
+        assert_eq!(*xs.last().unwrap(), 800.0);
+    }
+
+    #[test]
+    fn test_nice_ticks() {
+        assert_eq!(nice_step(4.0, 8.0), 0.5);
+        assert_eq!(nice_step(10.0, 8.0), 1.0);
+        assert_eq!(nice_step(20.0, 8.0), 2.0);
+        assert_eq!(nice_step(300.0, 8.0), 50.0);
+        assert_eq!(format_tick(1.5, 0.5), "1.5");
+        assert_eq!(format_tick(-0.0, 0.5), "0");
+        assert_eq!(format_tick(20.0, 10.0), "20");
+
+        // 网格线落在步长的整数倍上：y_range (-2, 2) 步长 0.5，共 9 条横线
+        let svg = render_objects(&[], &[]);
+        let horizontal = parse(&svg).into_iter()
+            .filter(|e| e.0 == "line" && attr(e, "y1") == attr(e, "y2"))
+            .count();
+        assert_eq!(horizontal, 9 + 1); // 网格 + x 轴
+    }
+}
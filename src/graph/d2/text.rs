@@ -0,0 +1,297 @@
+// src/graph/d2/text.rs
+// 位图字体文字标注：8x8 点阵字体 -> 128x128 纹理图集 -> 每个字符一个贴图四边形
+#![allow(dead_code)]
+
+use bytemuck::{Pod, Zeroable};
+
+// 图集排布：16 x 16 个字符，每个 8x8 像素
+pub const GLYPH_SIZE: u32 = 8;
+pub const ATLAS_COLS: u32 = 16;
+pub const ATLAS_SIZE: u32 = GLYPH_SIZE * ATLAS_COLS; // 128
+
+// 屏幕上每个字符像素放大倍数 (8px -> 16px)
+pub const TEXT_SCALE: f32 = 2.0;
+// 文字左下角相对锚点的像素偏移，避免盖住被标注的点
+pub const ANCHOR_OFFSET: [f32; 2] = [4.0, 4.0];
+
+// 文字顶点 (NDC 坐标，直接交给 GPU)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct TextVertex {
+    pub position: [f32; 2],
+    pub uv: [f32; 2],
+    pub color: [f32; 4],
+}
+
+// 一条文字标注：世界坐标锚点 + 文本 + 颜色
+pub struct Annotation {
+    pub pos: (f64, f64),
+    pub text: String,
+    pub color: [f32; 4],
+}
+
+// ====================== 字体数据 ======================
+
+// 8x8 点阵字体 (public domain font8x8_basic)
+// 每个字符 8 行，每行 1 字节，最低位是最左边的像素
+// 只收录可打印 ASCII (0x20..=0x7E)，其余码位留空
+const GLYPHS_ASCII: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x18, 0x3C, 0x3C, 0x18, 0x18, 0x00, 0x18, 0x00], // !
+    [0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // "
+    [0x36, 0x36, 0x7F, 0x36, 0x7F, 0x36, 0x36, 0x00], // #
+    [0x0C, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x0C, 0x00], // $
+    [0x00, 0x63, 0x33, 0x18, 0x0C, 0x66, 0x63, 0x00], // %
+    [0x1C, 0x36, 0x1C, 0x6E, 0x3B, 0x33, 0x6E, 0x00], // &
+    [0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00], // '
+    [0x18, 0x0C, 0x06, 0x06, 0x06, 0x0C, 0x18, 0x00], // (
+    [0x06, 0x0C, 0x18, 0x18, 0x18, 0x0C, 0x06, 0x00], // )
+    [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00], // *
+    [0x00, 0x0C, 0x0C, 0x3F, 0x0C, 0x0C, 0x00, 0x00], // +
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ,
+    [0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00], // -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00], // .
+    [0x60, 0x30, 0x18, 0x0C, 0x06, 0x03, 0x01, 0x00], // /
+    [0x3E, 0x63, 0x73, 0x7B, 0x6F, 0x67, 0x3E, 0x00], // 0
+    [0x0C, 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x3F, 0x00], // 1
+    [0x1E, 0x33, 0x30, 0x1C, 0x06, 0x33, 0x3F, 0x00], // 2
+    [0x1E, 0x33, 0x30, 0x1C, 0x30, 0x33, 0x1E, 0x00], // 3
+    [0x38, 0x3C, 0x36, 0x33, 0x7F, 0x30, 0x78, 0x00], // 4
+    [0x3F, 0x03, 0x1F, 0x30, 0x30, 0x33, 0x1E, 0x00], // 5
+    [0x1C, 0x06, 0x03, 0x1F, 0x33, 0x33, 0x1E, 0x00], // 6
+    [0x3F, 0x33, 0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x00], // 7
+    [0x1E, 0x33, 0x33, 0x1E, 0x33, 0x33, 0x1E, 0x00], // 8
+    [0x1E, 0x33, 0x33, 0x3E, 0x30, 0x18, 0x0E, 0x00], // 9
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x00], // :
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ;
+    [0x18, 0x0C, 0x06, 0x03, 0x06, 0x0C, 0x18, 0x00], // <
+    [0x00, 0x00, 0x3F, 0x00, 0x00, 0x3F, 0x00, 0x00], // =
+    [0x06, 0x0C, 0x18, 0x30, 0x18, 0x0C, 0x06, 0x00], // >
+    [0x1E, 0x33, 0x30, 0x18, 0x0C, 0x00, 0x0C, 0x00], // ?
+    [0x3E, 0x63, 0x7B, 0x7B, 0x7B, 0x03, 0x1E, 0x00], // @
+    [0x0C, 0x1E, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x00], // A
+    [0x3F, 0x66, 0x66, 0x3E, 0x66, 0x66, 0x3F, 0x00], // B
+    [0x3C, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3C, 0x00], // C
+    [0x1F, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1F, 0x00], // D
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x46, 0x7F, 0x00], // E
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x06, 0x0F, 0x00], // F
+    [0x3C, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7C, 0x00], // G
+    [0x33, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x33, 0x00], // H
+    [0x1E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // I
+    [0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, 0x00], // J
+    [0x67, 0x66, 0x36, 0x1E, 0x36, 0x66, 0x67, 0x00], // K
+    [0x0F, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7F, 0x00], // L
+    [0x63, 0x77, 0x7F, 0x7F, 0x6B, 0x63, 0x63, 0x00], // M
+    [0x63, 0x67, 0x6F, 0x7B, 0x73, 0x63, 0x63, 0x00], // N
+    [0x1C, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1C, 0x00], // O
+    [0x3F, 0x66, 0x66, 0x3E, 0x06, 0x06, 0x0F, 0x00], // P
+    [0x1E, 0x33, 0x33, 0x33, 0x3B, 0x1E, 0x38, 0x00], // Q
+    [0x3F, 0x66, 0x66, 0x3E, 0x36, 0x66, 0x67, 0x00], // R
+    [0x1E, 0x33, 0x07, 0x0E, 0x38, 0x33, 0x1E, 0x00], // S
+    [0x3F, 0x2D, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // T
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3F, 0x00], // U
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // V
+    [0x63, 0x63, 0x63, 0x6B, 0x7F, 0x77, 0x63, 0x00], // W
+    [0x63, 0x63, 0x36, 0x1C, 0x1C, 0x36, 0x63, 0x00], // X
+    [0x33, 0x33, 0x33, 0x1E, 0x0C, 0x0C, 0x1E, 0x00], // Y
+    [0x7F, 0x63, 0x31, 0x18, 0x4C, 0x66, 0x7F, 0x00], // Z
+    [0x1E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1E, 0x00], // [
+    [0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x40, 0x00], // \
+    [0x1E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1E, 0x00], // ]
+    [0x08, 0x1C, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00], // ^
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF], // _
+    [0x0C, 0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00], // `
+    [0x00, 0x00, 0x1E, 0x30, 0x3E, 0x33, 0x6E, 0x00], // a
+    [0x07, 0x06, 0x06, 0x3E, 0x66, 0x66, 0x3B, 0x00], // b
+    [0x00, 0x00, 0x1E, 0x33, 0x03, 0x33, 0x1E, 0x00], // c
+    [0x38, 0x30, 0x30, 0x3E, 0x33, 0x33, 0x6E, 0x00], // d
+    [0x00, 0x00, 0x1E, 0x33, 0x3F, 0x03, 0x1E, 0x00], // e
+    [0x1C, 0x36, 0x06, 0x0F, 0x06, 0x06, 0x0F, 0x00], // f
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x1F], // g
+    [0x07, 0x06, 0x36, 0x6E, 0x66, 0x66, 0x67, 0x00], // h
+    [0x0C, 0x00, 0x0E, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // i
+    [0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E], // j
+    [0x07, 0x06, 0x66, 0x36, 0x1E, 0x36, 0x67, 0x00], // k
+    [0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // l
+    [0x00, 0x00, 0x33, 0x7F, 0x7F, 0x6B, 0x63, 0x00], // m
+    [0x00, 0x00, 0x1F, 0x33, 0x33, 0x33, 0x33, 0x00], // n
+    [0x00, 0x00, 0x1E, 0x33, 0x33, 0x33, 0x1E, 0x00], // o
+    [0x00, 0x00, 0x3B, 0x66, 0x66, 0x3E, 0x06, 0x0F], // p
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x78], // q
+    [0x00, 0x00, 0x3B, 0x6E, 0x66, 0x06, 0x0F, 0x00], // r
+    [0x00, 0x00, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x00], // s
+    [0x08, 0x0C, 0x3E, 0x0C, 0x0C, 0x2C, 0x18, 0x00], // t
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6E, 0x00], // u
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // v
+    [0x00, 0x00, 0x63, 0x6B, 0x7F, 0x7F, 0x36, 0x00], // w
+    [0x00, 0x00, 0x63, 0x36, 0x1C, 0x36, 0x63, 0x00], // x
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x3E, 0x30, 0x1F], // y
+    [0x00, 0x00, 0x3F, 0x19, 0x0C, 0x26, 0x3F, 0x00], // z
+    [0x38, 0x0C, 0x0C, 0x07, 0x0C, 0x0C, 0x38, 0x00], // {
+    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00], // |
+    [0x07, 0x0C, 0x0C, 0x38, 0x0C, 0x0C, 0x07, 0x00], // }
+    [0x6E, 0x3B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ~
+];
+
+// 完整 256 码位字体表 (2KB)，编译期展开
+pub const FONT_8X8: [u8; 256 * 8] = build_font();
+
+const fn build_font() -> [u8; 256 * 8] {
+    let mut font = [0u8; 256 * 8];
+    let mut c = 0;
+    while c < GLYPHS_ASCII.len() {
+        let mut row = 0;
+        while row < 8 {
+            font[(0x20 + c) * 8 + row] = GLYPHS_ASCII[c][row];
+            row += 1;
+        }
+        c += 1;
+    }
+    font
+}
+
+// 把字体表展开成 R8 灰度图集 (ATLAS_SIZE x ATLAS_SIZE)
+pub fn build_atlas() -> Vec<u8> {
+    let size = ATLAS_SIZE as usize;
+    let mut pixels = vec![0u8; size * size];
+    for code in 0..256usize {
+        let cell_x = (code % ATLAS_COLS as usize) * GLYPH_SIZE as usize;
+        let cell_y = (code / ATLAS_COLS as usize) * GLYPH_SIZE as usize;
+        for row in 0..8 {
+            let bits = FONT_8X8[code * 8 + row];
+            for col in 0..8 {
+                if bits & (1 << col) != 0 {
+                    pixels[(cell_y + row) * size + cell_x + col] = 255;
+                }
+            }
+        }
+    }
+    pixels
+}
+
+// ====================== 网格生成 ======================
+
+// 字符在图集中的 uv 矩形 (u0, v0, u1, v1)
+// 非 Latin-1 字符统一显示为 '?'
+fn glyph_uv(ch: char) -> [f32; 4] {
+    let code = if (ch as u32) < 256 { ch as u32 } else { '?' as u32 };
+    let step = 1.0 / ATLAS_COLS as f32;
+    let u0 = (code % ATLAS_COLS) as f32 * step;
+    let v0 = (code / ATLAS_COLS) as f32 * step;
+    [u0, v0, u0 + step, v0 + step]
+}
+
+// 把标注列表转为屏幕空间四边形 (TriangleList，每字符 6 个顶点)
+// center / zoom 与 ViewUniforms 一致：可见区域高度为 4 / zoom
+pub fn build_text_vertices(
+    annotations: &[Annotation],
+    center: (f64, f64),
+    zoom: f64,
+    screen_w: f32,
+    screen_h: f32,
+) -> Vec<TextVertex> {
+    let mut vertices = Vec::new();
+    if screen_w <= 0.0 || screen_h <= 0.0 { return vertices; }
+
+    let range_y = 2.0 / zoom;
+    let range_x = range_y * (screen_w / screen_h) as f64;
+    let glyph_px = GLYPH_SIZE as f32 * TEXT_SCALE;
+
+    // 像素 -> NDC (像素 y 轴向下)
+    let to_ndc = |px: f32, py: f32| [px / screen_w * 2.0 - 1.0, 1.0 - py / screen_h * 2.0];
+
+    for a in annotations {
+        let ndc_x = ((a.pos.0 - center.0) / range_x) as f32;
+        let ndc_y = ((a.pos.1 - center.1) / range_y) as f32;
+        if !ndc_x.is_finite() || !ndc_y.is_finite() { continue; }
+
+        // 锚点像素坐标
+        let anchor_x = (ndc_x + 1.0) * 0.5 * screen_w + ANCHOR_OFFSET[0];
+        let anchor_y = (1.0 - ndc_y) * 0.5 * screen_h - ANCHOR_OFFSET[1];
+
+        let line_count = a.text.lines().count().max(1);
+        // 多行文本整体向上排，最后一行底边贴着锚点
+        let mut top = anchor_y - glyph_px * line_count as f32;
+
+        for line in a.text.lines() {
+            let mut left = anchor_x;
+            for ch in line.chars() {
+                if ch != ' ' {
+                    let [u0, v0, u1, v1] = glyph_uv(ch);
+                    let p0 = to_ndc(left, top);
+                    let p1 = to_ndc(left + glyph_px, top + glyph_px);
+                    let v = |position: [f32; 2], uv: [f32; 2]| TextVertex { position, uv, color: a.color };
+
+                    vertices.push(v([p0[0], p0[1]], [u0, v0]));
+                    vertices.push(v([p0[0], p1[1]], [u0, v1]));
+                    vertices.push(v([p1[0], p0[1]], [u1, v0]));
+                    vertices.push(v([p1[0], p0[1]], [u1, v0]));
+                    vertices.push(v([p0[0], p1[1]], [u0, v1]));
+                    vertices.push(v([p1[0], p1[1]], [u1, v1]));
+                }
+                left += glyph_px;
+            }
+            top += glyph_px;
+        }
+    }
+    vertices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(x: f64, y: f64, text: &str) -> Annotation {
+        Annotation { pos: (x, y), text: text.to_string(), color: [1.0; 4] }
+    }
+
+    #[test]
+    fn test_font_layout() {
+        // 控制字符与扩展区为空白
+        assert!(FONT_8X8[..0x20 * 8].iter().all(|&b| b == 0));
+        assert!(FONT_8X8[0x7F * 8..].iter().all(|&b| b == 0));
+        // 'A' 的第一行
+        assert_eq!(FONT_8X8['A' as usize * 8], 0x0C);
+        assert_eq!(FONT_8X8['~' as usize * 8], 0x6E);
+    }
+
+    #[test]
+    fn test_atlas_pixels() {
+        let atlas = build_atlas();
+        assert_eq!(atlas.len(), (ATLAS_SIZE * ATLAS_SIZE) as usize);
+        // '_' 最后一行是满的
+        let code = '_' as usize;
+        let cell_x = (code % 16) * 8;
+        let cell_y = (code / 16) * 8;
+        let row = &atlas[(cell_y + 7) * 128 + cell_x..(cell_y + 7) * 128 + cell_x + 8];
+        assert!(row.iter().all(|&p| p == 255));
+    }
+
+    #[test]
+    fn test_quads_per_char() {
+        let v = build_text_vertices(&[label(0.0, 0.0, "x = 1")], (0.0, 0.0), 1.0, 800.0, 600.0);
+        // 空格不生成四边形
+        assert_eq!(v.len(), 3 * 6);
+    }
+
+    #[test]
+    fn test_anchor_position() {
+        let (w, h) = (800.0, 600.0);
+        let v = build_text_vertices(&[label(0.0, 0.0, "A")], (0.0, 0.0), 1.0, w, h);
+        // 原点在屏幕中心；第一个顶点是字符左上角
+        let glyph_px = GLYPH_SIZE as f32 * TEXT_SCALE;
+        let expect_x = ANCHOR_OFFSET[0] / w * 2.0;
+        let expect_y = (ANCHOR_OFFSET[1] + glyph_px) / h * 2.0;
+        assert!((v[0].position[0] - expect_x).abs() < 1e-6);
+        assert!((v[0].position[1] - expect_y).abs() < 1e-6);
+        // 字符宽度固定为 glyph_px 像素，与缩放无关
+        let v2 = build_text_vertices(&[label(0.0, 0.0, "A")], (0.0, 0.0), 10.0, w, h);
+        let width = v2[2].position[0] - v2[0].position[0];
+        assert!((width - glyph_px / w * 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_non_latin_falls_back() {
+        assert_eq!(glyph_uv('数'), glyph_uv('?'));
+    }
+}
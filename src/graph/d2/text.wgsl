@@ -0,0 +1,31 @@
+// src/graph/d2/text.wgsl
+// 位图字体文字：顶点已在 CPU 端换算到 NDC，这里只负责采样图集
+
+@group(0) @binding(0) var font_atlas: texture_2d<f32>;
+@group(0) @binding(1) var font_sampler: sampler;
+
+struct TextOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_text(
+    @location(0) pos: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) color: vec4<f32>,
+) -> TextOutput {
+    var out: TextOutput;
+    out.clip_position = vec4<f32>(pos, 0.0, 1.0);
+    out.uv = uv;
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs_text(in: TextOutput) -> @location(0) vec4<f32> {
+    let coverage = textureSample(font_atlas, font_sampler, in.uv).r;
+    if (coverage <= 0.0) { discard; }
+    return vec4<f32>(in.color.rgb, in.color.a * coverage);
+}
@@ -0,0 +1,257 @@
+// src/d2/watch.rs
+// 热重载：监视一个表达式文件 (每行一个 pakoo 表达式)，文件变化时重新编译并替换绘图对象
+//   含 y 的行按隐函数 f(x, y) = 0 绘制，否则按显函数 y = f(x) 绘制
+//   空行与 # 开头的行忽略；编译失败的行只在控制台报告，其余行照常绘制
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use winit::event_loop::EventLoop;
+
+use crate::pakoo::compiler::Compiler;
+use crate::pakoo::math_data::MathData;
+use crate::pakoo::rpn::RPN;
+use crate::pakoo::symbol_table::SymbolTable;
+use crate::graph::session::Session;
+
+use super::colors;
+use super::common::GeoObj;
+use super::gpu_field::GpuField;
+use super::main::D2Plotter;
+
+// 轮询文件修改时间的间隔 (绘图窗口以同样的间隔检查新的对象列表)
+pub const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// x, y 预先登记为全局 0, 1，求值时作为 env_data 传入
+const X_ID: usize = 0;
+const Y_ID: usize = 1;
+
+const LINE_WIDTH: f32 = 3.0;
+const PALETTE: [[f32; 4]; 6] = [colors::RED, colors::BLUE, colors::GREEN, colors::ORANGE, colors::PURPLE, colors::CYAN];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExprKind {
+    Explicit, // y = f(x)
+    Implicit, // f(x, y) = 0
+}
+
+// 编译好的一行
+pub struct CompiledExpr {
+    pub line: usize, // 行号 (从 1 开始)
+    pub kind: ExprKind,
+    rpn: Arc<RPN>,
+}
+
+impl CompiledExpr {
+    // 非数值结果 (Bool 等) 与求值错误 (如除以 0) 视为无定义
+    pub fn eval(&self, x: f64, y: f64) -> f64 {
+        match self.rpn.try_eval(&[MathData::Num(x), MathData::Num(y)], &[]) {
+            Ok(MathData::Num(v)) => v,
+            _ => f64::NAN,
+        }
+    }
+
+    pub fn to_geo_obj(&self, color: [f32; 4]) -> GeoObj {
+        geo_obj_from_rpn(self.kind, self.rpn.clone(), color, LINE_WIDTH)
+    }
+}
+
+// 对象的 RPN 来源：表达式种类 + 编译结果 (x, y 为全局 0, 1)
+#[derive(Clone)]
+pub struct RpnSource {
+    pub kind: ExprKind,
+    pub(crate) rpn: Arc<RPN>,
+}
+
+// 由 RPN 构造绘图对象，并记录来源 (会话保存时据此重建)
+pub fn geo_obj_from_rpn(kind: ExprKind, rpn: Arc<RPN>, color: [f32; 4], width: f32) -> GeoObj {
+    let source = RpnSource { kind, rpn: rpn.clone() };
+    // 极点处 (如 1/x 在 x = 0) 求值出错，当作无定义，不能让求解线程 panic
+    let eval = move |x: f64, y: f64| match rpn.try_eval(&[MathData::Num(x), MathData::Num(y)], &[]) {
+        Ok(MathData::Num(v)) => v,
+        _ => f64::NAN,
+    };
+    let obj = match kind {
+        ExprKind::Explicit => GeoObj::new_explicit(move |x| eval(x, 0.0), color, width),
+        // 能翻译成 WGSL 的表达式在 GPU 上逐像素求值，否则 (含跳转等指令) 走 CPU
+        ExprKind::Implicit => {
+            let obj = GeoObj::new_implicit(eval, color, width);
+            match GpuField::from_rpn(&source.rpn) {
+                Ok(field) => obj.with_gpu_field(field),
+                Err(_) => obj,
+            }
+        }
+    };
+    obj.with_source(source)
+}
+
+// ====================== 编译 ======================
+
+// 编译一行表达式；语法错误返回错误信息 (文件写错一行不能让窗口退出)
+pub fn compile_line(text: &str) -> Result<(ExprKind, RPN), String> {
+    let mut table = SymbolTable::new();
+    table.get_or_create_id("x");
+    table.get_or_create_id("y");
+
+    let result = Compiler::new(text, &mut table).try_compile()?;
+
+    // 只认识 x, y：其他全局在求值时没有对应的数据
+    if let Some(&id) = result.dependencies.iter().find(|&&id| id != X_ID && id != Y_ID) {
+        return Err(format!("unknown identifier '{}'", table.name_of(id).unwrap_or("?")));
+    }
+    // 编译器只检查栈形状，下标范围按 x, y 两个全局再查一遍
+    let rpn = RPN::new_checked(result.ops, 0, 2).map_err(|e| e.to_string())?;
+
+    let kind = if result.dependencies.contains(&Y_ID) { ExprKind::Implicit } else { ExprKind::Explicit };
+    Ok((kind, rpn))
+}
+
+// 逐行编译整个文件：返回成功的表达式和带行号的错误信息
+pub fn compile_source(source: &str) -> (Vec<CompiledExpr>, Vec<String>) {
+    let mut exprs = Vec::new();
+    let mut errors = Vec::new();
+    for (i, text) in source.lines().enumerate() {
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') { continue; }
+        match compile_line(text) {
+            Ok((kind, rpn)) => exprs.push(CompiledExpr { line: i + 1, kind, rpn: Arc::new(rpn) }),
+            Err(e) => errors.push(format!("line {}: {} ({})", i + 1, e, text)),
+        }
+    }
+    (exprs, errors)
+}
+
+// ====================== 文件监视 ======================
+
+// 读取并编译文件，错误打印到控制台；文件读不了时返回 None
+pub fn load(path: &Path) -> Option<Vec<GeoObj>> {
+    let source = fs::read_to_string(path)
+        .map_err(|e| eprintln!("[watch] cannot read {}: {}", path.display(), e))
+        .ok()?;
+    let (exprs, errors) = compile_source(&source);
+    for e in &errors {
+        eprintln!("[watch] {}", e);
+    }
+    println!("[watch] loaded {} expression(s), {} error(s)", exprs.len(), errors.len());
+    Some(exprs.iter().enumerate().map(|(i, e)| e.to_geo_obj(PALETTE[i % PALETTE.len()])).collect())
+}
+
+// 修改时间 + 长度 (修改时间精度较粗的文件系统上，长度变化也能被发现)
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+// 后台线程轮询文件，变化时重新编译并把新的对象列表发给绘图窗口
+// 接收端关闭 (窗口退出) 后线程结束
+pub fn spawn_watcher(path: PathBuf) -> Receiver<Vec<GeoObj>> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut last = stamp(&path);
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let now = stamp(&path);
+            if now == last { continue; }
+            last = now;
+            let Some(objects) = load(&path) else { continue };
+            if tx.send(objects).is_err() { break; }
+        }
+    });
+    rx
+}
+
+// --watch <path> 入口；带 --session 时恢复上次的视图 (对象仍以表达式文件为准)，关闭窗口时保存
+pub fn run(path: &Path, session: Option<&Path>) {
+    let event_loop = EventLoop::new().unwrap();
+    let mut plotter = D2Plotter::new();
+    for obj in load(path).unwrap_or_default() {
+        plotter.add_object(obj);
+    }
+    if let Some(session) = session {
+        if let Some(view) = Session::load_or_default(session).view {
+            plotter.restore_view(view);
+        }
+        plotter.set_session_path(session.to_path_buf());
+    }
+    plotter.set_object_source(spawn_watcher(path.to_path_buf()));
+    event_loop.run_app(&mut plotter).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::common::GeoType;
+
+    #[test]
+    fn test_classify_and_compile() {
+        let source = "\
+# 注释与空行忽略
+
+x * x
+x * x + y * y - 4
+x > 0 ? x : 0 - x
+x +
+z * x
+(x + 1
+";
+        let (exprs, errors) = compile_source(source);
+        assert_eq!(exprs.iter().map(|e| (e.line, e.kind)).collect::<Vec<_>>(), vec![
+            (3, ExprKind::Explicit),
+            (4, ExprKind::Implicit),
+            (5, ExprKind::Explicit),
+        ]);
+        assert_eq!(exprs[0].eval(3.0, 0.0), 9.0);
+        assert_eq!(exprs[1].eval(1.0, 1.0), -2.0);
+        assert_eq!(exprs[2].eval(-2.5, 0.0), 2.5);
+
+        // 每个错误都带行号
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("line 6: Malformed expression: missing operand"));
+        assert!(errors[1].starts_with("line 7: unknown identifier 'z'"));
+        assert!(errors[2].starts_with("line 8:"));
+    }
+
+    #[test]
+    fn test_malformed_line_is_error() {
+        // 词法错误与语法错误都作为 Err 返回，不 panic (release 构建 panic 即退出)
+        assert_eq!(compile_line("x $ 1").err().as_deref(), Some("非法字符: $"));
+        assert_eq!(compile_line("y = x").err().as_deref(), Some("非法字符: =，相等比较请用 =="));
+        assert_eq!(compile_line("1.2.3 * x").err().as_deref(), Some("非法数字: 1.2.3"));
+        assert_eq!(compile_line("|x + 1").err().as_deref(), Some("Mismatched '|'"));
+        assert_eq!(compile_line("x ? 1").err().as_deref(), Some("'?' without matching ':'"));
+        assert!(compile_line("sin(x)) * 2").is_err());
+    }
+
+    #[test]
+    fn test_pole_is_nan() {
+        let (kind, rpn) = compile_line("1 / x").unwrap();
+        let expr = CompiledExpr { line: 1, kind, rpn: Arc::new(rpn) };
+        assert!(expr.eval(0.0, 0.0).is_nan());
+        assert_eq!(expr.eval(2.0, 0.0), 0.5);
+
+        // 绘图对象的求值闭包同样不 panic (求解线程会采样到极点)
+        let obj = expr.to_geo_obj([1.0; 4]);
+        let GeoType::Explicit(f, _) = &obj.geo_type else { panic!("expected an explicit object") };
+        assert!(f(0.0).is_nan());
+        assert_eq!(f(-4.0), -0.25);
+    }
+
+    #[test]
+    fn test_watcher_reloads_on_change() {
+        let path = std::env::temp_dir().join(format!("forest_watch_{}.txt", std::process::id()));
+        fs::write(&path, "x\n").unwrap();
+        let rx = spawn_watcher(path.clone());
+
+        thread::sleep(POLL_INTERVAL * 2);
+        fs::write(&path, "x * x\nx * x + y * y - 1\n").unwrap();
+        let objects = rx.recv_timeout(Duration::from_secs(5)).expect("no reload");
+        assert_eq!(objects.len(), 2);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -2,15 +2,47 @@
 
 use super::super::super::math_forest::geometry::d3::linear::vec3::Vec3;
 use super::super::super::math_forest::algebra::linear::matrix4x4::Matrix4x4;
+use super::super::gpu_interop::mat4_to_cols_f32;
+
+use std::f64::consts::{PI, TAU};
 
 use winit::event::{MouseButton, MouseScrollDelta};
 use glam::Mat4; // 仅保留 Mat4 用于最终输出给 GPU
 
+// 俯仰角限制 (避免万向节锁)
+const PITCH_LIMIT: f64 = 1.55;
+// 相机到注视点的距离范围 (远平面 1000)
+const RADIUS_MIN: f64 = 0.1;
+const RADIUS_MAX: f64 = 1000.0;
+// 纵向视场角 (度)
+const FOV_Y_DEGREES: f64 = 45.0;
+
+// 相机动画：起止状态 + 进度
+struct CameraAnimation {
+    from: (Vec3, f64, f64, f64), // (target, yaw, pitch, radius)
+    to: (Vec3, f64, f64, f64),   // yaw 已按最短路径展开，可能超出 [0, 2PI)
+    final_yaw: f64,              // 调用方给定的 yaw，结束时精确落在这里
+    elapsed: f64,
+    duration: f64,
+}
+
+// 相机的持久状态 (会话保存 / 恢复)，不含动画与转台
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraSnapshot {
+    pub target: Vec3,
+    pub yaw: f64,
+    pub pitch: f64,
+    pub radius: f64,
+}
+
 pub struct Camera {
     pub target: Vec3, // [替换] DVec3 -> Vec3
     pub yaw: f64,
     pub pitch: f64,
     pub radius: f64,
+
+    animation: Option<CameraAnimation>,
+    turntable_speed: Option<f64>, // 转台模式：每秒 yaw 增量 (弧度)
 }
 
 impl Camera {
@@ -20,35 +52,126 @@ impl Camera {
             yaw: 45.0f64.to_radians(),
             pitch: 30.0f64.to_radians(),
             radius: 10.0,
+            animation: None,
+            turntable_speed: None,
+        }
+    }
+
+    pub fn snapshot(&self) -> CameraSnapshot {
+        CameraSnapshot { target: self.target, yaw: self.yaw, pitch: self.pitch, radius: self.radius }
+    }
+
+    /// 立即跳到快照的视角 (打断动画)；pitch 与 radius 按交互时的范围限制
+    pub fn restore(&mut self, s: CameraSnapshot) {
+        self.animation = None;
+        self.target = s.target;
+        self.yaw = s.yaw;
+        self.pitch = s.pitch.clamp(-PITCH_LIMIT, PITCH_LIMIT);
+        self.radius = s.radius.clamp(RADIUS_MIN, RADIUS_MAX);
+    }
+
+    /// 注视点移到 target，朝向与距离不变 (打断动画)
+    pub fn look_at(&mut self, target: Vec3) {
+        self.animation = None;
+        self.target = target;
+    }
+
+    /// 设置到注视点的距离，限制在 [RADIUS_MIN, RADIUS_MAX] (打断动画)
+    pub fn set_radius(&mut self, r: f64) {
+        self.animation = None;
+        self.radius = r.clamp(RADIUS_MIN, RADIUS_MAX);
+    }
+
+    /// 注视包围盒 (min, max) 的中心，距离取外接球 (半径为半对角线) 恰好装进纵向视场：r / sin(fov / 2)
+    /// 朝向不变；窗口比正方形窄时左右可能略有裁切；空盒 (min > max) 时不动
+    pub fn frame_aabb(&mut self, (min, max): (Vec3, Vec3)) {
+        if min.x > max.x || min.y > max.y || min.z > max.z {
+            return;
+        }
+        let half_fov = 0.5 * FOV_Y_DEGREES.to_radians();
+        self.look_at((min + max) * 0.5);
+        self.set_radius((max - min).len() * 0.5 / half_fov.sin());
+    }
+
+    // ====================== 动画 ======================
+
+    /// 平滑飞向目标视角，duration 秒内用 smoothstep 缓动
+    /// yaw 沿最短路径插值，pitch 会被限制在 ±PITCH_LIMIT
+    pub fn animate_to(&mut self, target: Vec3, yaw: f64, pitch: f64, radius: f64, duration: f64) {
+        let pitch = pitch.clamp(-PITCH_LIMIT, PITCH_LIMIT);
+        let unwrapped_yaw = self.yaw + wrap_angle(yaw - self.yaw);
+        self.animation = Some(CameraAnimation {
+            from: (self.target, self.yaw, self.pitch, self.radius),
+            to: (target, unwrapped_yaw, pitch, radius),
+            final_yaw: yaw,
+            elapsed: 0.0,
+            duration,
+        });
+        if duration <= 0.0 {
+            self.tick(0.0);
+        }
+    }
+
+    /// 转台模式：持续旋转 yaw；speed 为 0 时关闭
+    pub fn turntable(&mut self, speed_rad_per_sec: f64) {
+        self.turntable_speed = if speed_rad_per_sec == 0.0 { None } else { Some(speed_rad_per_sec) };
+    }
+
+    pub fn is_turntable(&self) -> bool {
+        self.turntable_speed.is_some()
+    }
+
+    /// 推进 dt 秒，返回是否仍需继续重绘 (动画未结束或转台开启)
+    pub fn tick(&mut self, dt: f64) -> bool {
+        if let Some(anim) = self.animation.as_mut() {
+            anim.elapsed += dt;
+            if anim.elapsed >= anim.duration {
+                // 精确到达
+                let (target, _, pitch, radius) = anim.to;
+                let yaw = anim.final_yaw;
+                self.animation = None;
+                self.target = target;
+                self.yaw = yaw;
+                self.pitch = pitch;
+                self.radius = radius;
+            } else {
+                let x = anim.elapsed / anim.duration;
+                let s = x * x * (3.0 - 2.0 * x); // smoothstep
+                let (t0, y0, p0, r0) = anim.from;
+                let (t1, y1, p1, r1) = anim.to;
+                self.target = t0 + (t1 - t0) * s;
+                self.yaw = y0 + (y1 - y0) * s;
+                self.pitch = p0 + (p1 - p0) * s;
+                self.radius = r0 + (r1 - r0) * s;
+            }
+        } else if let Some(speed) = self.turntable_speed {
+            self.yaw = (self.yaw + speed * dt).rem_euclid(TAU);
         }
+
+        self.animation.is_some() || self.turntable_speed.is_some()
     }
 
     // 生成视图投影矩阵
     // 输出给 Shader 的 Uniform 必须是 f32
     pub fn build_view_projection_matrix(&self, aspect: f32) -> Mat4 {
+        let view_proj = self.view_projection(aspect);
+
+        // MathForest(f64, 行优先) -> glam(f32, 列优先)，约定见 gpu_interop
+        Mat4::from_cols_array(&mat4_to_cols_f32(view_proj))
+    }
+
+    // 视图投影矩阵 (MathForest f64, 行优先)，CPU 端计算 (如视锥剔除) 使用
+    pub fn view_projection(&self, aspect: f32) -> Matrix4x4 {
         let eye = self.get_eye_position();
 
         // [替换] 使用 MathForest::Matrix4x4 进行高精度矩阵计算
         // 注意：Vec3::K 代表 Z 轴 (0,0,1)
         let view = Matrix4x4::look_at_rh(eye, self.target, Vec3::K);
 
-        // [替换] 使用 perspective_rh_gl (对应 OpenGL [-1, 1] 深度)
-        let proj = Matrix4x4::perspective_rh_gl(45.0f64.to_radians(), aspect as f64, 0.1, 1000.0);
-
-        let view_proj = proj * view;
-
-        // ★ [核心适配] 将 MathForest(f64, Row-Major) 转换为 glam(f32, Col-Major)
-        // 我们的 m 数组是 [Row0, Row1, Row2, Row3]
-        // glam 期望的是 [Col0, Col1, Col2, Col3]
-        // 因此需要先转置 (Transpose)，把列变成行存储的形式，再转 f32
-        let t = view_proj.transpose();
+        // WGPU 的裁剪空间深度为 [0, 1]
+        let proj = Matrix4x4::perspective_rh_vk(FOV_Y_DEGREES.to_radians(), aspect as f64, 0.1, 1000.0);
 
-        Mat4::from_cols_array(&[
-            t.m[0] as f32, t.m[1] as f32, t.m[2] as f32, t.m[3] as f32,
-            t.m[4] as f32, t.m[5] as f32, t.m[6] as f32, t.m[7] as f32,
-            t.m[8] as f32, t.m[9] as f32, t.m[10] as f32, t.m[11] as f32,
-            t.m[12] as f32, t.m[13] as f32, t.m[14] as f32, t.m[15] as f32,
-        ])
+        proj * view
     }
 
     pub fn get_eye_position(&self) -> Vec3 {
@@ -65,12 +188,14 @@ impl Camera {
     pub fn process_mouse_drag(&mut self, dx: f64, dy: f64, button: MouseButton) {
         match button {
             MouseButton::Left => {
+                self.animation = None; // 手动操作打断动画
                 let sensitivity = 0.005;
                 self.yaw -= dx * sensitivity;
                 self.pitch += dy * sensitivity;
-                self.pitch = self.pitch.clamp(-1.55, 1.55);
+                self.pitch = self.pitch.clamp(-PITCH_LIMIT, PITCH_LIMIT);
             }
             MouseButton::Middle => {
+                self.animation = None;
                 let sensitivity = self.radius * 0.0015;
 
                 let eye = self.get_eye_position();
@@ -94,7 +219,215 @@ impl Camera {
             MouseScrollDelta::LineDelta(_, y) => *y as f64 * 1.0,
             MouseScrollDelta::PixelDelta(pos) => pos.y * 0.01,
         };
+        self.animation = None;
         self.radius -= zoom_amount;
-        self.radius = self.radius.clamp(0.1, 1000.0);
+        self.radius = self.radius.clamp(RADIUS_MIN, RADIUS_MAX);
+    }
+}
+// ====================== 视锥 ======================
+
+// 视锥的 6 个平面 (a, b, c, d)：a*x + b*y + c*z + d >= 0 为内侧
+// 由 clip = M * p 的行组合得到 (Gribb-Hartmann)：左右 w±x，下上 w±y，近 z、远 w-z (深度范围 [0, 1])
+pub struct Frustum {
+    planes: [[f64; 4]; 6],
+}
+
+impl Frustum {
+    // m: 投影 * 视图 (* 模型)，行优先
+    pub fn from_matrix(m: &Matrix4x4) -> Self {
+        let row = |i: usize| [m.m[i * 4], m.m[i * 4 + 1], m.m[i * 4 + 2], m.m[i * 4 + 3]];
+        let (r0, r1, r2, r3) = (row(0), row(1), row(2), row(3));
+        let add = |a: [f64; 4], b: [f64; 4]| [a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]];
+        let sub = |a: [f64; 4], b: [f64; 4]| [a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]];
+        Self {
+            planes: [add(r3, r0), sub(r3, r0), add(r3, r1), sub(r3, r1), r2, sub(r3, r2)],
+        }
+    }
+
+    // AABB 是否完全位于某个平面外侧 (保守：不在外侧的都算可见)
+    // 对每个平面取法向最靠内的顶点 (p-vertex)，它在外侧则整个盒子在外侧
+    pub fn is_aabb_outside(&self, min: Vec3, max: Vec3) -> bool {
+        if min.x > max.x || min.y > max.y || min.z > max.z {
+            return true; // 空盒
+        }
+        self.planes.iter().any(|&[a, b, c, d]| {
+            let px = if a >= 0.0 { max.x } else { min.x };
+            let py = if b >= 0.0 { max.y } else { min.y };
+            let pz = if c >= 0.0 { max.z } else { min.z };
+            a * px + b * py + c * pz + d < 0.0
+        })
+    }
+}
+
+// 角度差归一化到 (-PI, PI]，用于最短路径插值
+fn wrap_angle(a: f64) -> f64 {
+    let r = (a + PI).rem_euclid(TAU) - PI;
+    if r == -PI { PI } else { r }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f64 = 1.0 / 60.0;
+
+    #[test]
+    fn test_monotonic_easing_and_arrival() {
+        let mut cam = Camera::new();
+        let target = Vec3::new(1.0, -2.0, 3.0);
+        cam.animate_to(target, 1.0, 0.2, 4.0, 1.0);
+
+        let mut last_radius = cam.radius;
+        let mut last_step = 0.0;
+        let mut steps = 0;
+        while cam.tick(DT) {
+            // radius 从 10 单调减小到 4
+            assert!(cam.radius <= last_radius);
+            let step = last_radius - cam.radius;
+            // 前半程加速
+            if steps < 25 { assert!(step >= last_step); }
+            last_step = step;
+            last_radius = cam.radius;
+            steps += 1;
+            assert!(steps < 1000);
+        }
+        assert_eq!(cam.target, target);
+        assert_eq!(cam.yaw, 1.0);
+        assert_eq!(cam.pitch, 0.2);
+        assert_eq!(cam.radius, 4.0);
+    }
+
+    #[test]
+    fn test_yaw_shortest_path() {
+        let mut cam = Camera::new();
+        cam.yaw = 350.0f64.to_radians();
+        cam.animate_to(cam.target, 10.0f64.to_radians(), cam.pitch, cam.radius, 1.0);
+
+        // 途中 yaw 只能增加 (350 -> 360 -> 370)，不能倒转 340 度
+        let mut last = cam.yaw;
+        while cam.tick(DT) {
+            assert!(cam.yaw >= last);
+            assert!(cam.yaw <= 370.0f64.to_radians() + 1e-12);
+            last = cam.yaw;
+        }
+        assert_eq!(cam.yaw, 10.0f64.to_radians());
+    }
+
+    #[test]
+    fn test_pitch_clamped() {
+        let mut cam = Camera::new();
+        cam.animate_to(Vec3::ZERO, 0.0, 3.0, 10.0, 0.5);
+        while cam.tick(DT) {
+            assert!(cam.pitch <= PITCH_LIMIT);
+        }
+        assert_eq!(cam.pitch, PITCH_LIMIT);
+    }
+
+    #[test]
+    fn test_turntable() {
+        let mut cam = Camera::new();
+        assert!(!cam.tick(DT));
+        cam.turntable(0.5);
+        let yaw0 = cam.yaw;
+        assert!(cam.tick(1.0));
+        assert!((cam.yaw - (yaw0 + 0.5)).abs() < 1e-12);
+        cam.turntable(0.0);
+        assert!(!cam.tick(DT));
+    }
+
+    #[test]
+    fn test_zero_duration_jumps() {
+        let mut cam = Camera::new();
+        cam.animate_to(Vec3::ZERO, 2.0, 0.1, 7.0, 0.0);
+        assert_eq!(cam.radius, 7.0);
+        assert!(!cam.tick(DT));
+    }
+
+    #[test]
+    fn test_frustum_cull() {
+        let cam = Camera::new();
+        let frustum = Frustum::from_matrix(&cam.view_projection(1.0));
+        let unit = Vec3::new(0.5, 0.5, 0.5);
+
+        // 相机注视点处的盒子可见
+        assert!(!frustum.is_aabb_outside(cam.target - unit, cam.target + unit));
+
+        // 相机背后的盒子被剔除
+        let eye = cam.get_eye_position();
+        let behind = eye + (eye - cam.target).unit() * 5.0;
+        assert!(frustum.is_aabb_outside(behind - unit, behind + unit));
+
+        // 远平面之外
+        let far = cam.target + (cam.target - eye).unit() * 2000.0;
+        assert!(frustum.is_aabb_outside(far - unit, far + unit));
+
+        // 跨越视锥边界的大盒子不剔除
+        let big = Vec3::new(500.0, 500.0, 500.0);
+        assert!(!frustum.is_aabb_outside(behind - big, behind + big));
+
+        // 空盒
+        assert!(frustum.is_aabb_outside(unit, -unit));
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut cam = Camera::new();
+        cam.animate_to(Vec3::new(1.0, 2.0, 3.0), 0.5, 0.4, 7.0, 1.0);
+        let saved = CameraSnapshot { target: Vec3::new(-1.0, 0.0, 2.0), yaw: 2.0, pitch: -0.3, radius: 25.0 };
+        cam.restore(saved);
+        assert_eq!(cam.snapshot(), saved);
+        // 恢复打断动画
+        assert!(!cam.tick(DT));
+        assert_eq!(cam.snapshot(), saved);
+
+        // 越界的 pitch / radius 被限制
+        cam.restore(CameraSnapshot { pitch: 3.0, radius: 0.0, ..saved });
+        assert_eq!((cam.pitch, cam.radius), (PITCH_LIMIT, 0.1));
+    }
+
+    #[test]
+    fn test_look_at_and_radius() {
+        let mut cam = Camera::new();
+        cam.animate_to(Vec3::new(1.0, 1.0, 1.0), 0.5, 0.4, 7.0, 1.0);
+        cam.look_at(Vec3::new(2.0, -1.0, 0.5));
+        // 打断动画，朝向保持
+        assert!(!cam.tick(DT));
+        assert_eq!(cam.target, Vec3::new(2.0, -1.0, 0.5));
+        assert!(((cam.get_eye_position() - cam.target).len() - cam.radius).abs() < 1e-12);
+
+        cam.set_radius(25.0);
+        assert_eq!(cam.radius, 25.0);
+        cam.set_radius(-1.0);
+        assert_eq!(cam.radius, RADIUS_MIN);
+        cam.set_radius(1e9);
+        assert_eq!(cam.radius, RADIUS_MAX);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_frame_aabb() {
+        let mut cam = Camera::new();
+        let (yaw, pitch) = (cam.yaw, cam.pitch);
+        let (min, max) = (Vec3::new(-1.0, 2.0, 0.0), Vec3::new(3.0, 4.0, 4.0));
+        cam.frame_aabb((min, max));
+        assert_eq!(cam.target, Vec3::new(1.0, 3.0, 2.0));
+        assert_eq!((cam.yaw, cam.pitch), (yaw, pitch));
+        // 半对角线 3，sin(22.5°) ≈ 0.383
+        assert!((cam.radius - 3.0 / 22.5f64.to_radians().sin()).abs() < 1e-12);
+
+        // 外接球恰好与纵向视场相切：八个角都在视锥内
+        let frustum = Frustum::from_matrix(&cam.view_projection(1.0));
+        for i in 0..8 {
+            let corner = Vec3::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            );
+            assert!(!frustum.is_aabb_outside(corner, corner));
+        }
+
+        // 空盒不动
+        let before = cam.snapshot();
+        cam.frame_aabb((max, min));
+        assert_eq!(cam.snapshot(), before);
+    }
+}
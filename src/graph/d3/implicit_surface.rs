@@ -1,12 +1,18 @@
 // src/d3/implicit_surface.rs
 #![allow(dead_code)]
 
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use rayon::prelude::*;
 use super::mesh::{MeshData, Vertex3D}; // 使用相对路径导入 mesh
 use super::implicit_data::{EDGE_TABLE, TRI_TABLE}; // 导入查找表
 
 // ★ 引入 MathForest
+use crate::math_forest::numerics::diff::gradient_f64;
 use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+use crate::pakoo::math_data::MathData;
+use crate::pakoo::rpn::RPN;
 
 pub struct ImplicitSurfaceSolver;
 
@@ -23,8 +29,77 @@ impl ImplicitSurfaceSolver {
     ) -> MeshData
     where
         F: Fn(f64, f64, f64) -> f64 + Sync + Send,
+    {
+        let never = AtomicBool::new(false);
+        Self::solve_with_progress(func, x_range, y_range, z_range, resolution, &never, &|_| {})
+            .expect("solve is never cancelled")
+    }
+
+    /// 同 solve，但可以在后台线程中运行：
+    /// 每处理完一层 z 切片 (采样 resolution + 1 层 + march resolution 层) 调用一次 progress(已完成比例)，
+    /// 调用是串行的，比例严格递增；
+    /// 每层开始前检查 cancel，被置位后剩余切片直接跳过，返回 None
+    pub fn solve_with_progress<F>(
+        func: &F,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        z_range: (f64, f64),
+        resolution: u32,
+        cancel: &AtomicBool,
+        progress: &(dyn Fn(f64) + Sync),
+    ) -> Option<MeshData>
+    where
+        F: Fn(f64, f64, f64) -> f64 + Sync + Send,
+    {
+        let normal = |p: Vec3| calc_gradient_normal(func, p);
+        Self::solve_impl(func, &normal, x_range, y_range, z_range, resolution, cancel, progress)
+    }
+
+    /// 标量场来自 RPN 表达式 (三个参数依次为 x, y, z) 时：
+    /// 场值用 eval，法线用 eval_dual 精确求梯度，不做有限差分
+    pub(crate) fn solve_rpn(
+        rpn: &RPN,
+        env_data: &[MathData],
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        z_range: (f64, f64),
+        resolution: u32,
+    ) -> MeshData {
+        let func = |x: f64, y: f64, z: f64| match rpn.eval(env_data, &[MathData::Num(x), MathData::Num(y), MathData::Num(z)]) {
+            MathData::Num(v) => v,
+            _ => f64::NAN,
+        };
+        let normal = |p: Vec3| rpn.gradient3(env_data, p).unit();
+        let never = AtomicBool::new(false);
+        Self::solve_impl(&func, &normal, x_range, y_range, z_range, resolution, &never, &|_| {})
+            .expect("solve is never cancelled")
+    }
+
+    // 均匀网格 Marching Cubes 的实现，normal(p) 给出曲面上 p 处的单位法线
+    #[allow(clippy::too_many_arguments)]
+    fn solve_impl<F, N>(
+        func: &F,
+        normal: &N,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        z_range: (f64, f64),
+        resolution: u32,
+        cancel: &AtomicBool,
+        progress: &(dyn Fn(f64) + Sync),
+    ) -> Option<MeshData>
+    where
+        F: Fn(f64, f64, f64) -> f64 + Sync + Send,
+        N: Fn(Vec3) -> Vec3 + Sync,
     {
         let res_p1 = (resolution + 1) as usize;
+        let total_slabs = res_p1 + resolution as usize;
+        let done = Mutex::new(0usize);
+        let finish_slab = || {
+            let mut done = done.lock().unwrap();
+            *done += 1;
+            progress(*done as f64 / total_slabs as f64);
+        };
+        let cancelled = || cancel.load(Ordering::Relaxed);
         let total_points = res_p1 * res_p1 * res_p1;
 
         let step_x = (x_range.1 - x_range.0) / resolution as f64;
@@ -37,6 +112,7 @@ impl ImplicitSurfaceSolver {
 
         // Rayon 并行填充
         values.par_chunks_mut(res_p1 * res_p1).enumerate().for_each(|(k, plane)| {
+            if cancelled() { return; }
             let z = z_range.0 + k as f64 * step_z;
             for j in 0..res_p1 {
                 let y = y_range.0 + j as f64 * step_y;
@@ -46,132 +122,267 @@ impl ImplicitSurfaceSolver {
                     plane[idx] = func(x, y, z);
                 }
             }
+            finish_slab();
         });
+        if cancelled() { return None; }
 
         // 2. 并行 Marching Cubes
         // 我们将 Z 轴切片进行并行处理，每个线程计算一层的三角形
-        let geometry_parts: Vec<(Vec<Vertex3D>, Vec<u32>)> = (0..resolution).into_par_iter().map(|k| {
+        let geometry_parts: Vec<(Vec<Vertex3D>, Vec<u32>)> = (0..resolution as usize).into_par_iter().map(|k| {
             let mut local_vertices = Vec::new();
             let mut local_indices = Vec::new();
-            let mut index_counter = 0;
-
-            for j in 0..resolution {
-                for i in 0..resolution {
-                    // 计算 8 个角点的索引
-                    // 坐标顺序参考 Paul Bourke (标准右手系顺序匹配 TRI_TABLE)
-                    // 0:(x,y,z), 1:(x+1,y,z), 2:(x+1,y,z+1), 3:(x,y,z+1) ...
-
-                    let idx0 = k as usize * res_p1 * res_p1 + j as usize * res_p1 + i as usize;
+            if cancelled() { return (local_vertices, local_indices); }
 
-                    // 8个顶点的偏移 (x, y, z)
-                    let corner_offsets = [
-                        (0, 0, 0), (1, 0, 0), (1, 0, 1), (0, 0, 1),
-                        (0, 1, 0), (1, 1, 0), (1, 1, 1), (0, 1, 1)
-                    ];
+            for j in 0..resolution as usize {
+                for i in 0..resolution as usize {
+                    let idx0 = k * res_p1 * res_p1 + j * res_p1 + i;
 
-                    let mut cube_index = 0;
                     let mut corner_vals = [0.0; 8];
                     let mut corner_pos = [Vec3::ZERO; 8];
 
                     for n in 0..8 {
-                        let (di, dj, dk) = corner_offsets[n];
+                        let (di, dj, dk) = CORNER_OFFSETS[n];
                         // 计算全局索引 (注意步长)
-                        let global_idx = idx0 + dk * res_p1 * res_p1 + dj * res_p1 + di;
-
-                        let val = values[global_idx];
-                        corner_vals[n] = val;
+                        corner_vals[n] = values[idx0 + dk * res_p1 * res_p1 + dj * res_p1 + di];
 
                         // 世界坐标
-                        let wx = x_range.0 + (i as usize + di) as f64 * step_x;
-                        let wy = y_range.0 + (j as usize + dj) as f64 * step_y;
-                        let wz = z_range.0 + (k as usize + dk) as f64 * step_z;
+                        let wx = x_range.0 + (i + di) as f64 * step_x;
+                        let wy = y_range.0 + (j + dj) as f64 * step_y;
+                        let wz = z_range.0 + (k + dk) as f64 * step_z;
                         corner_pos[n] = Vec3::new(wx, wy, wz);
+                    }
+
+                    march_cube(normal, &corner_pos, &corner_vals, &mut local_vertices, &mut local_indices);
+                }
+            }
+            finish_slab();
+            (local_vertices, local_indices)
+        }).collect();
+        if cancelled() { return None; }
+
+        // 3. 合并所有线程的网格
+        Some(merge_parts(geometry_parts))
+    }
+
+    /// 自适应 Marching Cubes：结果与 solve 相同分辨率，但只细分靠近曲面的区域
+    /// 第一遍：在 resolution / 4 的粗网格上采样，变号的粗格子 (及其 26 邻域，
+    ///         以免曲面只从格子侧面穿出时漏掉) 记入位掩码
+    /// 第二遍：只对掩码中的粗格子按 resolution 细分并 march
+    /// 注意：完全落在单个粗格子内部、不引起角点变号的小闭曲面会被忽略
+    pub fn solve_adaptive<F>(
+        func: &F,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        z_range: (f64, f64),
+        resolution: u32,
+    ) -> MeshData
+    where
+        F: Fn(f64, f64, f64) -> f64 + Sync + Send,
+    {
+        const FACTOR: usize = 4;
+        let res = resolution as usize;
+        // 粗格子 c 覆盖细格子 [4c, 4c + 4) (最后一层可能越过 resolution，越界部分不 march)
+        let coarse = res.div_ceil(FACTOR).max(1);
+        let c_p1 = coarse + 1;
+
+        let step_x = (x_range.1 - x_range.0) / resolution as f64;
+        let step_y = (y_range.1 - y_range.0) / resolution as f64;
+        let step_z = (z_range.1 - z_range.0) / resolution as f64;
+        let point = |i: usize, j: usize, k: usize| {
+            Vec3::new(
+                x_range.0 + i as f64 * step_x,
+                y_range.0 + j as f64 * step_y,
+                z_range.0 + k as f64 * step_z,
+            )
+        };
 
-                        if val < 0.0 { // 假设 Isovalue = 0.0
-                            cube_index |= 1 << n;
+        // 1. 粗网格标量场 (粗网格点与细网格点重合)
+        let mut coarse_values = vec![0.0; c_p1 * c_p1 * c_p1];
+        coarse_values.par_chunks_mut(c_p1 * c_p1).enumerate().for_each(|(k, plane)| {
+            for j in 0..c_p1 {
+                for i in 0..c_p1 {
+                    let p = point(i * FACTOR, j * FACTOR, k * FACTOR);
+                    plane[j * c_p1 + i] = func(p.x, p.y, p.z);
+                }
+            }
+        });
+
+        // 2. 变号的粗格子 -> 位掩码 (并膨胀一圈)
+        let cell = |i: usize, j: usize, k: usize| (k * coarse + j) * coarse + i;
+        let mut straddle = CellMask::new(coarse * coarse * coarse);
+        for k in 0..coarse {
+            for j in 0..coarse {
+                for i in 0..coarse {
+                    let mut inside = 0;
+                    for &(di, dj, dk) in &CORNER_OFFSETS {
+                        if coarse_values[((k + dk) * c_p1 + j + dj) * c_p1 + i + di] < 0.0 { inside += 1; }
+                    }
+                    if inside != 0 && inside != 8 { straddle.set(cell(i, j, k)); }
+                }
+            }
+        }
+        let mut active = CellMask::new(coarse * coarse * coarse);
+        let near = |c: usize, d: isize| c.checked_add_signed(d).filter(|&n| n < coarse);
+        for k in 0..coarse {
+            for j in 0..coarse {
+                for i in 0..coarse {
+                    if !straddle.get(cell(i, j, k)) { continue; }
+                    for dk in -1..=1 {
+                        for dj in -1..=1 {
+                            for di in -1..=1 {
+                                if let (Some(ni), Some(nj), Some(nk)) = (near(i, di), near(j, dj), near(k, dk)) {
+                                    active.set(cell(ni, nj, nk));
+                                }
+                            }
                         }
                     }
+                }
+            }
+        }
+
+        // 3. 只细分激活的粗格子：每个粗格子独立采样 (FACTOR + 1)^3 个细网格点
+        let normal = |p: Vec3| calc_gradient_normal(func, p);
+        let geometry_parts: Vec<(Vec<Vertex3D>, Vec<u32>)> = active.iter_set().collect::<Vec<_>>().into_par_iter().map(|c| {
+            let (ci, cj, ck) = (c % coarse, (c / coarse) % coarse, c / (coarse * coarse));
+            let (i0, j0, k0) = (ci * FACTOR, cj * FACTOR, ck * FACTOR);
+            let n = FACTOR + 1;
+            let mut values = [0.0; (FACTOR + 1) * (FACTOR + 1) * (FACTOR + 1)];
+            for k in 0..n {
+                for j in 0..n {
+                    for i in 0..n {
+                        let p = point(i0 + i, j0 + j, k0 + k);
+                        values[(k * n + j) * n + i] = func(p.x, p.y, p.z);
+                    }
+                }
+            }
 
-                    // 查表：如果完全在内部或外部，跳过
-                    let edges = EDGE_TABLE[cube_index];
-                    if edges == 0 { continue; }
-
-                    // 插值计算 12 条边上的点
-                    let mut vert_list = [Vec3::ZERO; 12];
-
-                    if (edges & 1) != 0 { vert_list[0] = vertex_interp(corner_pos[0], corner_vals[0], corner_pos[1], corner_vals[1]); }
-                    if (edges & 2) != 0 { vert_list[1] = vertex_interp(corner_pos[1], corner_vals[1], corner_pos[2], corner_vals[2]); }
-                    if (edges & 4) != 0 { vert_list[2] = vertex_interp(corner_pos[2], corner_vals[2], corner_pos[3], corner_vals[3]); }
-                    if (edges & 8) != 0 { vert_list[3] = vertex_interp(corner_pos[3], corner_vals[3], corner_pos[0], corner_vals[0]); }
-                    if (edges & 16) != 0 { vert_list[4] = vertex_interp(corner_pos[4], corner_vals[4], corner_pos[5], corner_vals[5]); }
-                    if (edges & 32) != 0 { vert_list[5] = vertex_interp(corner_pos[5], corner_vals[5], corner_pos[6], corner_vals[6]); }
-                    if (edges & 64) != 0 { vert_list[6] = vertex_interp(corner_pos[6], corner_vals[6], corner_pos[7], corner_vals[7]); }
-                    if (edges & 128) != 0 { vert_list[7] = vertex_interp(corner_pos[7], corner_vals[7], corner_pos[4], corner_vals[4]); }
-                    if (edges & 256) != 0 { vert_list[8] = vertex_interp(corner_pos[0], corner_vals[0], corner_pos[4], corner_vals[4]); }
-                    if (edges & 512) != 0 { vert_list[9] = vertex_interp(corner_pos[1], corner_vals[1], corner_pos[5], corner_vals[5]); }
-                    if (edges & 1024) != 0 { vert_list[10] = vertex_interp(corner_pos[2], corner_vals[2], corner_pos[6], corner_vals[6]); }
-                    if (edges & 2048) != 0 { vert_list[11] = vertex_interp(corner_pos[3], corner_vals[3], corner_pos[7], corner_vals[7]); }
-
-                    // 生成三角形
-                    for t in (0..16).step_by(3) {
-                        let v_idx1 = TRI_TABLE[cube_index][t];
-                        if v_idx1 == -1 { break; }
-                        let v_idx2 = TRI_TABLE[cube_index][t+1];
-                        let v_idx3 = TRI_TABLE[cube_index][t+2];
-
-                        let p1 = vert_list[v_idx1 as usize];
-                        let p2 = vert_list[v_idx2 as usize];
-                        let p3 = vert_list[v_idx3 as usize];
-
-                        // 计算法线：对该点位置再次求导 (Gradient)
-                        let n1 = calc_gradient_normal(func, p1);
-                        let n2 = calc_gradient_normal(func, p2);
-                        let n3 = calc_gradient_normal(func, p3);
-
-                        // Push 顶点 (MathForest f64 -> GPU f32)
-                        local_vertices.push(Vertex3D {
-                            position: [p1.x as f32, p1.y as f32, p1.z as f32],
-                            normal:   [n1.x as f32, n1.y as f32, n1.z as f32]
-                        });
-                        local_vertices.push(Vertex3D {
-                            position: [p2.x as f32, p2.y as f32, p2.z as f32],
-                            normal:   [n2.x as f32, n2.y as f32, n2.z as f32]
-                        });
-                        local_vertices.push(Vertex3D {
-                            position: [p3.x as f32, p3.y as f32, p3.z as f32],
-                            normal:   [n3.x as f32, n3.y as f32, n3.z as f32]
-                        });
-
-                        local_indices.push(index_counter);
-                        local_indices.push(index_counter + 1);
-                        local_indices.push(index_counter + 2);
-                        index_counter += 3;
+            let mut local_vertices = Vec::new();
+            let mut local_indices = Vec::new();
+            for k in 0..FACTOR.min(res.saturating_sub(k0)) {
+                for j in 0..FACTOR.min(res.saturating_sub(j0)) {
+                    for i in 0..FACTOR.min(res.saturating_sub(i0)) {
+                        let mut corner_vals = [0.0; 8];
+                        let mut corner_pos = [Vec3::ZERO; 8];
+                        for m in 0..8 {
+                            let (di, dj, dk) = CORNER_OFFSETS[m];
+                            corner_vals[m] = values[((k + dk) * n + j + dj) * n + i + di];
+                            corner_pos[m] = point(i0 + i + di, j0 + j + dj, k0 + k + dk);
+                        }
+                        march_cube(&normal, &corner_pos, &corner_vals, &mut local_vertices, &mut local_indices);
                     }
                 }
             }
             (local_vertices, local_indices)
         }).collect();
 
-        // 3. 合并所有线程的网格
-        let mut final_vertices = Vec::new();
-        let mut final_indices = Vec::new();
-        let mut base_index = 0;
-
-        for (mut verts, mut idxs) in geometry_parts {
-            // 修正 indices 的偏移量
-            for i in &mut idxs {
-                *i += base_index;
-            }
-            base_index += verts.len() as u32;
+        merge_parts(geometry_parts)
+    }
+}
+
+// 8 个角点的偏移 (x, y, z)
+// 坐标顺序参考 Paul Bourke (标准右手系顺序匹配 TRI_TABLE)
+// 0:(x,y,z), 1:(x+1,y,z), 2:(x+1,y,z+1), 3:(x,y,z+1) ...
+const CORNER_OFFSETS: [(usize, usize, usize); 8] = [
+    (0, 0, 0), (1, 0, 0), (1, 0, 1), (0, 0, 1),
+    (0, 1, 0), (1, 1, 0), (1, 1, 1), (0, 1, 1)
+];
+
+// 12 条边的端点 (角点编号)
+const EDGE_CORNERS: [(usize, usize); 12] = [
+    (0, 1), (1, 2), (2, 3), (3, 0),
+    (4, 5), (5, 6), (6, 7), (7, 4),
+    (0, 4), (1, 5), (2, 6), (3, 7),
+];
+
+// 激活格子的位掩码
+struct CellMask {
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl CellMask {
+    fn new(len: usize) -> Self {
+        Self { bits: vec![0; len.div_ceil(64)], len }
+    }
+
+    fn set(&mut self, i: usize) {
+        self.bits[i / 64] |= 1 << (i % 64);
+    }
+
+    fn get(&self, i: usize) -> bool {
+        self.bits[i / 64] & (1 << (i % 64)) != 0
+    }
 
-            final_vertices.append(&mut verts);
-            final_indices.append(&mut idxs);
+    fn iter_set(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|&i| self.get(i))
+    }
+}
+
+// 单个立方体：查表生成三角形，追加到 vertices / indices
+fn march_cube<N>(
+    normal: &N,
+    corner_pos: &[Vec3; 8],
+    corner_vals: &[f64; 8],
+    vertices: &mut Vec<Vertex3D>,
+    indices: &mut Vec<u32>,
+)
+where N: Fn(Vec3) -> Vec3
+{
+    let mut cube_index = 0;
+    for (n, &val) in corner_vals.iter().enumerate() {
+        if val < 0.0 { // 假设 Isovalue = 0.0
+            cube_index |= 1 << n;
         }
+    }
 
-        MeshData { vertices: final_vertices, indices: final_indices }
+    // 查表：如果完全在内部或外部，跳过
+    let edges = EDGE_TABLE[cube_index];
+    if edges == 0 { return; }
+
+    // 插值计算 12 条边上的点
+    let mut vert_list = [Vec3::ZERO; 12];
+    for (e, &(a, b)) in EDGE_CORNERS.iter().enumerate() {
+        if (edges & (1 << e)) != 0 {
+            vert_list[e] = vertex_interp(corner_pos[a], corner_vals[a], corner_pos[b], corner_vals[b]);
+        }
+    }
+
+    // 生成三角形
+    for t in (0..16).step_by(3) {
+        if TRI_TABLE[cube_index][t] == -1 { break; }
+        for v in 0..3 {
+            let p = vert_list[TRI_TABLE[cube_index][t + v] as usize];
+            // 计算法线：该点的梯度方向
+            let n = normal(p);
+            // Push 顶点 (MathForest f64 -> GPU f32)
+            indices.push(vertices.len() as u32);
+            vertices.push(Vertex3D {
+                position: p.to_f32_array(),
+                normal:   n.to_f32_array()
+            });
+        }
     }
 }
 
+// 合并各线程的局部网格，修正 indices 的偏移量
+fn merge_parts(parts: Vec<(Vec<Vertex3D>, Vec<u32>)>) -> MeshData {
+    let mut final_vertices = Vec::new();
+    let mut final_indices = Vec::new();
+    let mut base_index = 0;
+
+    for (mut verts, mut idxs) in parts {
+        for i in &mut idxs {
+            *i += base_index;
+        }
+        base_index += verts.len() as u32;
+
+        final_vertices.append(&mut verts);
+        final_indices.append(&mut idxs);
+    }
+
+    MeshData { vertices: final_vertices, indices: final_indices }
+}
+
 // 辅助：线性插值找零点
 #[inline]
 fn vertex_interp(p1: Vec3, v1: f64, p2: Vec3, v2: f64) -> Vec3 {
@@ -187,11 +398,90 @@ fn vertex_interp(p1: Vec3, v1: f64, p2: Vec3, v2: f64) -> Vec3 {
 fn calc_gradient_normal<F>(func: &F, p: Vec3) -> Vec3
 where F: Fn(f64, f64, f64) -> f64
 {
-    let eps = 1e-6;
-    let dx = func(p.x + eps, p.y, p.z) - func(p.x - eps, p.y, p.z);
-    let dy = func(p.x, p.y + eps, p.z) - func(p.x, p.y - eps, p.z);
-    let dz = func(p.x, p.y, p.z + eps) - func(p.x, p.y, p.z - eps);
-
     // unit() 已经处理了零向量情况
-    Vec3::new(dx, dy, dz).unit()
-}
\ No newline at end of file
+    gradient_f64(|q| func(q.x, q.y, q.z), p, 1e-6).unit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn total_area(m: &MeshData) -> f64 {
+        m.indices.chunks(3).map(|t| {
+            let p = |i: u32| {
+                let v = m.vertices[i as usize].position;
+                Vec3::new(v[0] as f64, v[1] as f64, v[2] as f64)
+            };
+            let (a, b, c) = (p(t[0]), p(t[1]), p(t[2]));
+            (b - a).cross(c - a).len() * 0.5
+        }).sum()
+    }
+
+    #[test]
+    fn test_adaptive_matches_uniform() {
+        let calls = AtomicUsize::new(0);
+        let sphere = |x: f64, y: f64, z: f64| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            x * x + y * y + z * z - 1.0
+        };
+        let range = (-3.0, 3.0);
+
+        let uniform = ImplicitSurfaceSolver::solve(&sphere, range, range, range, 48);
+        let uniform_calls = calls.swap(0, Ordering::Relaxed);
+        let adaptive = ImplicitSurfaceSolver::solve_adaptive(&sphere, range, range, range, 48);
+        let adaptive_calls = calls.load(Ordering::Relaxed);
+
+        // 细网格点的取值相同，三角形完全一致 (只是顺序不同)
+        assert_eq!(uniform.indices.len(), adaptive.indices.len());
+        let (a1, a2) = (total_area(&uniform), total_area(&adaptive));
+        assert!((a1 - a2).abs() < 1e-9 * a1);
+        assert!((a1 - 4.0 * std::f64::consts::PI).abs() < 0.1);
+
+        // 函数求值 (不含法线的梯度求值) 明显更少
+        let normal_calls = adaptive.vertices.len() * 6;
+        assert!(adaptive_calls - normal_calls < (uniform_calls - normal_calls) / 2);
+    }
+
+    #[test]
+    fn test_adaptive_non_divisible_resolution() {
+        // 37 不是 4 的倍数：最后一层粗格子只 march 范围内的细格子
+        let f = |x: f64, y: f64, z: f64| x * x + y * y + z * z - 0.81;
+        let range = (-1.0, 1.0);
+        let uniform = ImplicitSurfaceSolver::solve(&f, range, range, range, 37);
+        let adaptive = ImplicitSurfaceSolver::solve_adaptive(&f, range, range, range, 37);
+        assert_eq!(uniform.indices.len(), adaptive.indices.len());
+        for v in &adaptive.vertices {
+            assert!(v.position.iter().all(|c| c.abs() <= 1.0 + 1e-6));
+        }
+    }
+
+    #[test]
+    fn test_rpn_normals_from_dual() {
+        use crate::pakoo::op::Op;
+
+        // x * x + y * y + z * z - 1
+        let sq = |i| [Op::LoadPara(i), Op::LoadPara(i), Op::Mul];
+        let mut ops = Vec::new();
+        ops.extend(sq(0));
+        ops.extend(sq(1));
+        ops.push(Op::Add);
+        ops.extend(sq(2));
+        ops.push(Op::Add);
+        ops.extend([Op::Push(MathData::Num(1.0)), Op::Sub]);
+        let rpn = RPN::new(ops);
+
+        let range = (-1.5, 1.5);
+        let mesh = ImplicitSurfaceSolver::solve_rpn(&rpn, &[], range, range, range, 20);
+        let direct = ImplicitSurfaceSolver::solve(&|x: f64, y: f64, z: f64| x * x + y * y + z * z - 1.0, range, range, range, 20);
+        assert_eq!(mesh.indices, direct.indices);
+        assert!(!mesh.vertices.is_empty());
+
+        // 球面法线即位置方向
+        for v in &mesh.vertices {
+            let p = Vec3::new(v.position[0] as f64, v.position[1] as f64, v.position[2] as f64);
+            let n = Vec3::new(v.normal[0] as f64, v.normal[1] as f64, v.normal[2] as f64);
+            assert!(n.dis(p.unit()) < 1e-6);
+        }
+    }
+}
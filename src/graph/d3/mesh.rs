@@ -1,7 +1,13 @@
 // src/d3/mesh.rs
+use std::collections::HashMap;
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use bytemuck::{Pod, Zeroable};
+use rayon::prelude::*;
 
 // ★ 引入 MathForest Vec3 (f64)
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
 use crate::math_forest::geometry::d3::linear::vec3::Vec3;
 
 // GPU 顶点结构体 (保持 f32，WGPU 标准管线)
@@ -17,8 +23,73 @@ pub struct MeshData {
     pub indices: Vec<u32>,
 }
 
+// 参数曲面的拓扑选项
+// wrap_u / wrap_v：周期方向 (如环面的 u、v) 首尾共用同一列/行顶点，接缝处法线连续
+// collapse_degenerate_rows：整行退化为一点 (如球面两极) 时合并为单个顶点，法线取相邻行平均
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SurfaceTopology {
+    pub wrap_u: bool,
+    pub wrap_v: bool,
+    pub collapse_degenerate_rows: bool,
+}
+
+// 判定整行退化时的距离容差 (相对坐标量级)
+const DEGENERATE_EPS: f64 = 1e-9;
+
+// 参数曲面的默认突跃阈值：相邻网格点距离超过它时不连接三角形
+pub const DEFAULT_JUMP_THRESHOLD: f32 = 10.0;
+
+// 第 i 个采样点沿某一方向做差分的两个邻居 (共 n 个采样)
+// 内部取两侧；边界取自身与内侧一个；周期方向 (首尾重合) 跨过接缝取另一端的内侧点
+fn diff_neighbors(i: usize, n: usize, wrap: bool) -> (usize, usize) {
+    if n < 2 {
+        return (i, i);
+    }
+    if wrap && n > 2 && (i == 0 || i == n - 1) {
+        return (n - 2, 1);
+    }
+    (i.saturating_sub(1), (i + 1).min(n - 1))
+}
+
+// 具名几何体构造器的参数错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeshError {
+    InvalidSize(&'static str),    // 半径、高度等不是正的有限数
+    TooFewSegments(&'static str), // 分段数不足以构成封闭曲面
+    SelfIntersecting,             // 环面 r_minor >= r_major
+}
+
+impl std::fmt::Display for MeshError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MeshError::InvalidSize(name) => write!(f, "{} must be positive and finite", name),
+            MeshError::TooFewSegments(name) => write!(f, "too few segments for {}", name),
+            MeshError::SelfIntersecting => write!(f, "torus r_minor must be smaller than r_major"),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+fn check_size(value: f64, name: &'static str) -> Result<(), MeshError> {
+    if value.is_finite() && value > 0.0 { Ok(()) } else { Err(MeshError::InvalidSize(name)) }
+}
+
+fn check_segments(segments: u32, min: u32, name: &'static str) -> Result<(), MeshError> {
+    if segments >= min { Ok(()) } else { Err(MeshError::TooFewSegments(name)) }
+}
+
+// 以 axis 为第三轴的右手正交标架 (e1, e2, â)；axis 为 +z 时即 (x, y, z)
+fn axis_frame(axis: Vec3) -> (Vec3, Vec3, Vec3) {
+    let a = axis.unit();
+    let helper = if a.x.abs() < 0.9 { Vec3::I } else { Vec3::J };
+    let e1 = helper.project_onto_plane(a).unit();
+    (e1, a.cross(e1), a)
+}
+
 impl MeshData {
     // ★ 泛型 F 现在返回 MathForest::Vec3 (f64)
+    // 兼容接口：不做接缝焊接与极点合并
     pub fn new_parametric_surface<F>(
         func: F,
         u_range: (f64, f64),
@@ -27,7 +98,50 @@ impl MeshData {
         v_segments: u32,
     ) -> Self
     where
-        F: Fn(f64, f64) -> Vec3,
+        F: Fn(f64, f64) -> Vec3 + Sync,
+    {
+        Self::new_parametric_surface_with(
+            func, u_range, v_range, u_segments, v_segments, SurfaceTopology::default(),
+        )
+    }
+
+    // 带拓扑选项的参数曲面
+    // 例：环面用 wrap_u + wrap_v，顶点数恰为 u_segments * v_segments；
+    //     球面 (u 为经度) 用 wrap_u + collapse_degenerate_rows，两极各一个顶点
+    pub fn new_parametric_surface_with<F>(
+        func: F,
+        u_range: (f64, f64),
+        v_range: (f64, f64),
+        u_segments: u32,
+        v_segments: u32,
+        topology: SurfaceTopology,
+    ) -> Self
+    where
+        F: Fn(f64, f64) -> Vec3 + Sync,
+    {
+        let never = AtomicBool::new(false);
+        Self::new_parametric_surface_cancellable(
+            func, u_range, v_range, u_segments, v_segments, topology, DEFAULT_JUMP_THRESHOLD, &never,
+        )
+        .expect("surface generation is never cancelled")
+    }
+
+    // 同 new_parametric_surface_with，可在后台线程中运行：
+    // 采样与法线按 u 行并行 (rayon)，每行开始前检查 cancel，被置位后返回 None
+    // jump_threshold：三角形边长超过它 (如渐近线两侧) 时不连接
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_parametric_surface_cancellable<F>(
+        func: F,
+        u_range: (f64, f64),
+        v_range: (f64, f64),
+        u_segments: u32,
+        v_segments: u32,
+        topology: SurfaceTopology,
+        jump_threshold: f32,
+        cancel: &AtomicBool,
+    ) -> Option<Self>
+    where
+        F: Fn(f64, f64) -> Vec3 + Sync,
     {
         let mut vertices = Vec::new();
         let mut indices = Vec::new();
@@ -35,18 +149,105 @@ impl MeshData {
         let (v_min, v_max) = v_range;
         let u_step = (u_max - u_min) / u_segments as f64;
         let v_step = (v_max - v_min) / v_segments as f64;
+        let nu = u_segments as usize + 1;
+        let nv = v_segments as usize + 1;
+        let cancelled = || cancel.load(Ordering::Relaxed);
+
+        // --- 1. 采样位置 (每个采样点只求值一次) ---
+        let pos: Vec<Vec<Vec3>> = (0..nu)
+            .into_par_iter()
+            .map(|i| {
+                if cancelled() { return Vec::new(); }
+                (0..nv).map(|j| func(u_min + i as f64 * u_step, v_min + j as f64 * v_step)).collect()
+            })
+            .collect();
+        if cancelled() {
+            return None;
+        }
+        let finite = |p: Vec3| p.x.is_finite() && p.y.is_finite() && p.z.is_finite();
 
-        // --- 1. 生成顶点 (含有效性检查) ---
-        for i in 0..=u_segments {
-            for j in 0..=v_segments {
-                let u = u_min + i as f64 * u_step;
-                let v = v_min + j as f64 * v_step;
+        // 退化行检测：v = v_j 的整行 (所有 u) 或 u = u_i 的整列 (所有 v) 重合为一点
+        let same = |a: Vec3, b: Vec3| finite(a) && finite(b) && a.dis(b) <= DEGENERATE_EPS * (1.0 + a.len());
+        let collapse = topology.collapse_degenerate_rows;
+        let row_collapsed: Vec<bool> = (0..nv)
+            .map(|j| collapse && (1..nu).all(|i| same(pos[0][j], pos[i][j])))
+            .collect();
+        let col_collapsed: Vec<bool> = (0..nu)
+            .map(|i| collapse && (1..nv).all(|j| same(pos[i][0], pos[i][j])))
+            .collect();
+
+        // --- 2. 法线：对采样网格差分 (内部中心差分，边界单侧差分，周期方向跨过接缝) ---
+        // 差分的一侧无效 (NaN) 时退回另一侧；两侧都无效时给一个默认向上的法线
+        let tangent = |a: (usize, usize), b: (usize, usize), step: f64| -> Option<Vec3> {
+            let (pa, pb) = (pos[a.0][a.1], pos[b.0][b.1]);
+            (a != b && finite(pa) && finite(pb)).then(|| (pb - pa) * (1.0 / step))
+        };
+        let grid_normal = |i: usize, j: usize| -> Vec3 {
+            let (ul, uh) = diff_neighbors(i, nu, topology.wrap_u);
+            let (vl, vh) = diff_neighbors(j, nv, topology.wrap_v);
+            let du = tangent((ul, j), (uh, j), u_step)
+                .or_else(|| tangent((i, j), (uh, j), u_step))
+                .or_else(|| tangent((ul, j), (i, j), u_step));
+            let dv = tangent((i, vl), (i, vh), v_step)
+                .or_else(|| tangent((i, j), (i, vh), v_step))
+                .or_else(|| tangent((i, vl), (i, j), v_step));
+            match (du, dv) {
+                (Some(du), Some(dv)) => du.cross(dv).unit(),
+                _ => Vec3::new(0.0, 0.0, 1.0),
+            }
+        };
+        let normals: Vec<Vec<Vec3>> = (0..nu)
+            .into_par_iter()
+            .map(|i| {
+                if cancelled() { return Vec::new(); }
+                (0..nv).map(|j| if finite(pos[i][j]) { grid_normal(i, j) } else { Vec3::ZERO }).collect()
+            })
+            .collect();
+        if cancelled() {
+            return None;
+        }
+        // 合并点的法线：相邻 (靠内侧) 一行采样法线的平均
+        let averaged_normal = |samples: Vec<(usize, usize)>| -> Vec3 {
+            let mut sum = Vec3::ZERO;
+            for (i, j) in samples {
+                if finite(pos[i][j]) {
+                    let n = normals[i][j];
+                    if finite(n) { sum += n; }
+                }
+            }
+            sum.unit()
+        };
+
+        // --- 3. 生成顶点 (含有效性检查) ---
+        // idx[i][j]：采样点 (i, j) 对应的顶点下标
+        let mut idx = vec![vec![0u32; nv]; nu];
+        for i in 0..nu {
+            for j in 0..nv {
+                // 周期方向：末列/末行与首列/首行共用顶点
+                if topology.wrap_u && i == nu - 1 && nu > 1 {
+                    idx[i][j] = idx[0][j];
+                    continue;
+                }
+                if topology.wrap_v && j == nv - 1 && nv > 1 {
+                    idx[i][j] = idx[i][0];
+                    continue;
+                }
+                // 退化行：只在首次出现时生成顶点
+                if row_collapsed[j] && i > 0 {
+                    idx[i][j] = idx[0][j];
+                    continue;
+                }
+                if col_collapsed[i] && j > 0 {
+                    idx[i][j] = idx[i][0];
+                    continue;
+                }
 
-                let pos = func(u, v);
+                let p = pos[i][j];
+                idx[i][j] = vertices.len() as u32;
 
                 // ★ [新增]：断点/无效值检测
                 // 如果坐标是 NaN 或无穷大，写入一个 NaN 标记，后续生成三角形时会跳过
-                if !pos.x.is_finite() || !pos.y.is_finite() || !pos.z.is_finite() {
+                if !finite(p) {
                     vertices.push(Vertex3D {
                         position: [f32::NAN; 3],
                         normal: [0.0; 3],
@@ -54,86 +255,78 @@ impl MeshData {
                     continue;
                 }
 
-                // --- ★ 之前修复的法线逻辑 (带安全检查) ★ ---
-                let eps = 1e-9;
-                let pos_u = func(u + eps, v);
-                let pos_v = func(u, v + eps);
-
-                // 安全检查：如果邻居也是无效值，无法计算法线，给一个默认向上的法线
-                let normal = if pos_u.x.is_finite() && pos_v.x.is_finite() {
-                    let du = (pos_u - pos) * (1.0 / eps);
-                    let dv = (pos_v - pos) * (1.0 / eps);
-                    du.cross(dv).unit()
+                let normal = if row_collapsed[j] && nv > 1 {
+                    let jj = if j + 1 < nv { j + 1 } else { j - 1 };
+                    averaged_normal((0..nu).map(|ii| (ii, jj)).collect())
+                } else if col_collapsed[i] && nu > 1 {
+                    let ii = if i + 1 < nu { i + 1 } else { i - 1 };
+                    averaged_normal((0..nv).map(|jj| (ii, jj)).collect())
                 } else {
-                    Vec3::new(0.0, 0.0, 1.0)
+                    normals[i][j]
                 };
-                // ----------------------------------------
 
                 vertices.push(Vertex3D {
-                    position: [pos.x as f32, pos.y as f32, pos.z as f32],
-                    normal: [normal.x as f32, normal.y as f32, normal.z as f32],
+                    position: p.to_f32_array(),
+                    normal: normal.to_f32_array(),
                 });
             }
         }
 
-        // --- 2. 生成索引 (含突跃屏蔽) ---
+        // --- 4. 生成索引 (含突跃屏蔽) ---
 
-        // 定义最大允许边长的平方 (阈值)。
-        // 如果两个网格点距离超过这个值（例如渐近线），则不连接三角形。
-        // 这里设为 10.0 的平方，你可以根据场景缩放调整，或者作为参数传入。
-        const JUMP_THRESHOLD_SQ: f32 = 10.0 * 10.0;
+        // 最大允许边长的平方：两个网格点距离超过 jump_threshold (例如渐近线) 时不连接三角形
+        let jump_threshold_sq = jump_threshold * jump_threshold;
 
-        for i in 0..u_segments {
-            for j in 0..v_segments {
-                let row1 = i * (v_segments + 1);
-                let row2 = (i + 1) * (v_segments + 1);
+        // 辅助闭包：检查三角形是否有效
+        let is_valid_tri = |i1: u32, i2: u32, i3: u32| -> bool {
+            // 0. 合并顶点后的退化三角形 (极点处)
+            if i1 == i2 || i2 == i3 || i3 == i1 {
+                return false;
+            }
+            let p1 = vertices[i1 as usize].position;
+            let p2 = vertices[i2 as usize].position;
+            let p3 = vertices[i3 as usize].position;
 
+            // 1. 检查是否有 NaN (之前的断点)
+            if p1[0].is_nan() || p2[0].is_nan() || p3[0].is_nan() {
+                return false;
+            }
+
+            // 2. 检查边长是否过大 (突跃屏蔽)
+            let d12 =
+                (p1[0] - p2[0]).powi(2) + (p1[1] - p2[1]).powi(2) + (p1[2] - p2[2]).powi(2);
+            let d23 =
+                (p2[0] - p3[0]).powi(2) + (p2[1] - p3[1]).powi(2) + (p2[2] - p3[2]).powi(2);
+            let d31 =
+                (p3[0] - p1[0]).powi(2) + (p3[1] - p1[1]).powi(2) + (p3[2] - p1[2]).powi(2);
+
+            if d12 > jump_threshold_sq || d23 > jump_threshold_sq || d31 > jump_threshold_sq
+            {
+                return false;
+            }
+            true
+        };
+
+        for i in 0..nu - 1 {
+            for j in 0..nv - 1 {
                 // 四个角点索引
-                let idx_a = row1 + j;
-                let idx_b = row1 + j + 1;
-                let idx_c = row2 + j + 1;
-                let idx_d = row2 + j;
-
-                // 获取位置
-                let pa = vertices[idx_a as usize].position;
-                let pb = vertices[idx_b as usize].position;
-                let pc = vertices[idx_c as usize].position;
-                let pd = vertices[idx_d as usize].position;
-
-                // 辅助闭包：检查三角形是否有效
-                let is_valid_tri = |p1: [f32; 3], p2: [f32; 3], p3: [f32; 3]| -> bool {
-                    // 1. 检查是否有 NaN (之前的断点)
-                    if p1[0].is_nan() || p2[0].is_nan() || p3[0].is_nan() {
-                        return false;
-                    }
-
-                    // 2. 检查边长是否过大 (突跃屏蔽)
-                    let d12 =
-                        (p1[0] - p2[0]).powi(2) + (p1[1] - p2[1]).powi(2) + (p1[2] - p2[2]).powi(2);
-                    let d23 =
-                        (p2[0] - p3[0]).powi(2) + (p2[1] - p3[1]).powi(2) + (p2[2] - p3[2]).powi(2);
-                    let d31 =
-                        (p3[0] - p1[0]).powi(2) + (p3[1] - p1[1]).powi(2) + (p3[2] - p1[2]).powi(2);
-
-                    if d12 > JUMP_THRESHOLD_SQ || d23 > JUMP_THRESHOLD_SQ || d31 > JUMP_THRESHOLD_SQ
-                    {
-                        return false;
-                    }
-                    true
-                };
+                let idx_a = idx[i][j];
+                let idx_b = idx[i][j + 1];
+                let idx_c = idx[i + 1][j + 1];
+                let idx_d = idx[i + 1][j];
 
                 // Triangle 1: a-d-b
-                if is_valid_tri(pa, pd, pb) {
+                if is_valid_tri(idx_a, idx_d, idx_b) {
                     indices.extend_from_slice(&[idx_a, idx_d, idx_b]);
                 }
 
                 // Triangle 2: b-d-c
-                if is_valid_tri(pb, pd, pc) {
+                if is_valid_tri(idx_b, idx_d, idx_c) {
                     indices.extend_from_slice(&[idx_b, idx_d, idx_c]);
                 }
             }
         }
-        Self { vertices, indices }
+        Some(Self { vertices, indices })
     }
 
     // 坐标轴 (简单物体直接构造 f32 数据)
@@ -191,4 +384,702 @@ impl MeshData {
         let indices = vec![0, 1, 2, 0, 2, 3];
         Self { vertices, indices }
     }
+
+    // 正二十面体细分球 (icosphere)：三角形大小均匀，没有经纬球的极点聚集
+    // 每次细分把一个三角形按边中点分成 4 个，新顶点投影回球面
+    // 面数 20 * 4^n，顶点数 10 * 4^n + 2；subdivisions = 0 即正二十面体
+    pub fn new_icosphere(radius: f64, subdivisions: u32) -> Self {
+        let t = (1.0 + 5.0f64.sqrt()) / 2.0;
+        let mut positions: Vec<Vec3> = [
+            (-1.0, t, 0.0), (1.0, t, 0.0), (-1.0, -t, 0.0), (1.0, -t, 0.0),
+            (0.0, -1.0, t), (0.0, 1.0, t), (0.0, -1.0, -t), (0.0, 1.0, -t),
+            (t, 0.0, -1.0), (t, 0.0, 1.0), (-t, 0.0, -1.0), (-t, 0.0, 1.0),
+        ]
+        .iter()
+        .map(|&(x, y, z)| Vec3::new(x, y, z).unit())
+        .collect();
+        // 逆时针 (从外侧看)，法线朝外
+        let mut faces: Vec<[u32; 3]> = vec![
+            [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+            [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+            [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+            [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+        ];
+
+        for _ in 0..subdivisions {
+            // 边 (小下标, 大下标) -> 中点顶点，相邻三角形共用
+            let mut midpoints: HashMap<(u32, u32), u32> = HashMap::new();
+            let mut midpoint = |a: u32, b: u32| -> u32 {
+                *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                    positions.push(((positions[a as usize] + positions[b as usize]) * 0.5).unit());
+                    positions.len() as u32 - 1
+                })
+            };
+            faces = faces
+                .iter()
+                .flat_map(|&[a, b, c]| {
+                    let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+                    [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+                })
+                .collect();
+        }
+
+        // 单位球上的位置即法线
+        let vertices = positions
+            .iter()
+            .map(|n| {
+                let p = *n * radius;
+                Vertex3D {
+                    position: p.to_f32_array(),
+                    normal: n.to_f32_array(),
+                }
+            })
+            .collect();
+        let indices = faces.into_iter().flatten().collect();
+        Self { vertices, indices }
+    }
+
+    // ====================== 具名几何体 ======================
+    // 都由参数曲面生成，法线朝外；周期方向焊接、极点合并
+
+    // 旋转曲面：profile(t) = (ρ, h)，ρ 为到轴的距离，h 为沿 axis 的坐标 (轴过原点)
+    // 轮廓在 (ρ, h) 平面内逆时针走 (如外侧从下往上) 时法线朝外
+    // 首尾重合的闭合轮廓 (如环面的截面圆) 自动焊接，落在轴上的端点合并为一个顶点
+    // u_seg 为绕轴分段，v_seg 为沿轮廓分段
+    pub fn new_surface_of_revolution<F>(
+        profile: F,
+        axis: Vec3,
+        range: (f64, f64),
+        u_seg: u32,
+        v_seg: u32,
+    ) -> Result<Self, MeshError>
+    where
+        F: Fn(f64) -> Vec2 + Sync,
+    {
+        check_size(axis.len(), "axis")?;
+        check_segments(u_seg, 3, "u_seg")?;
+        check_segments(v_seg, 1, "v_seg")?;
+        let (e1, e2, a) = axis_frame(axis);
+        let (p0, p1) = (profile(range.0), profile(range.1));
+        let closed = p0.dis(p1) <= DEGENERATE_EPS * (1.0 + p0.len());
+        let topology = SurfaceTopology { wrap_u: true, wrap_v: closed, collapse_degenerate_rows: true };
+        Ok(Self::new_parametric_surface_with(
+            move |u, t| {
+                let p = profile(t);
+                (e1 * u.cos() + e2 * u.sin()) * p.x + a * p.y
+            },
+            (0.0, TAU), range, u_seg, v_seg, topology,
+        ))
+    }
+
+    // 经纬球：segments 为经线分段，纬线分段取其一半 (至少 2)
+    pub fn new_sphere(center: Vec3, radius: f64, segments: u32) -> Result<Self, MeshError> {
+        check_size(radius, "radius")?;
+        check_segments(segments, 3, "segments")?;
+        let mesh = Self::new_surface_of_revolution(
+            move |t| Vec2::new(radius * t.cos(), radius * t.sin()),
+            Vec3::K, (-FRAC_PI_2, FRAC_PI_2), segments, (segments / 2).max(2),
+        )?;
+        Ok(mesh.translated(center))
+    }
+
+    // 环面 (轴为 z 轴)：r_major 为中心圆半径，r_minor 为管半径 (须小于 r_major)
+    // seg_u 绕 z 轴分段，seg_v 绕管分段
+    pub fn new_torus(center: Vec3, r_major: f64, r_minor: f64, seg_u: u32, seg_v: u32) -> Result<Self, MeshError> {
+        check_size(r_major, "r_major")?;
+        check_size(r_minor, "r_minor")?;
+        if r_minor >= r_major { return Err(MeshError::SelfIntersecting); }
+        check_segments(seg_u, 3, "seg_u")?;
+        check_segments(seg_v, 3, "seg_v")?;
+        let mesh = Self::new_surface_of_revolution(
+            move |t| Vec2::new(r_major + r_minor * t.cos(), r_minor * t.sin()),
+            Vec3::K, (0.0, TAU), seg_u, seg_v,
+        )?;
+        Ok(mesh.translated(center))
+    }
+
+    // 圆柱 (含上下底面)：底面圆心为 base，沿 axis 方向高 height
+    pub fn new_cylinder(base: Vec3, axis: Vec3, radius: f64, height: f64, segments: u32) -> Result<Self, MeshError> {
+        check_size(radius, "radius")?;
+        check_size(height, "height")?;
+        // 侧面从下往上，底面由内向外，顶面由外向内：三段轮廓都逆时针
+        let side = Self::new_surface_of_revolution(move |t| Vec2::new(radius, t), axis, (0.0, height), segments, 1)?;
+        let bottom = Self::new_surface_of_revolution(|t| Vec2::new(t, 0.0), axis, (0.0, radius), segments, 1)?;
+        let top = Self::new_surface_of_revolution(move |t| Vec2::new(radius - t, height), axis, (0.0, radius), segments, 1)?;
+        Ok(Self::merge(vec![side, bottom, top]).translated(base))
+    }
+
+    // 圆锥 (含底面)：底面圆心为 base，顶点在 base + axis.unit() * height
+    pub fn new_cone(base: Vec3, axis: Vec3, radius: f64, height: f64, segments: u32) -> Result<Self, MeshError> {
+        check_size(radius, "radius")?;
+        check_size(height, "height")?;
+        let side = Self::new_surface_of_revolution(
+            move |t| Vec2::new(radius * (1.0 - t), height * t), axis, (0.0, 1.0), segments, 1,
+        )?;
+        let bottom = Self::new_surface_of_revolution(|t| Vec2::new(t, 0.0), axis, (0.0, radius), segments, 1)?;
+        Ok(Self::merge(vec![side, bottom]).translated(base))
+    }
+
+    // 长方体 (实心，6 个面各自独立顶点以保持棱角处法线不平均)
+    pub fn new_box(min: Vec3, max: Vec3) -> Result<Self, MeshError> {
+        check_size(max.x - min.x, "box width (max.x - min.x)")?;
+        check_size(max.y - min.y, "box depth (max.y - min.y)")?;
+        check_size(max.z - min.z, "box height (max.z - min.z)")?;
+        let face = |f: Box<dyn Fn(f64, f64) -> Vec3 + Sync>| Self::new_parametric_surface(f, (0.0, 1.0), (0.0, 1.0), 1, 1);
+        let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+        // 每个面的 (u, v) 次序使 ∂u × ∂v 朝外
+        Ok(Self::merge(vec![
+            face(Box::new(move |u, v| Vec3::new(max.x, lerp(min.y, max.y, u), lerp(min.z, max.z, v)))),
+            face(Box::new(move |u, v| Vec3::new(min.x, lerp(min.y, max.y, v), lerp(min.z, max.z, u)))),
+            face(Box::new(move |u, v| Vec3::new(lerp(min.x, max.x, v), max.y, lerp(min.z, max.z, u)))),
+            face(Box::new(move |u, v| Vec3::new(lerp(min.x, max.x, u), min.y, lerp(min.z, max.z, v)))),
+            face(Box::new(move |u, v| Vec3::new(lerp(min.x, max.x, u), lerp(min.y, max.y, v), max.z))),
+            face(Box::new(move |u, v| Vec3::new(lerp(min.x, max.x, v), lerp(min.y, max.y, u), min.z))),
+        ]))
+    }
+
+    // 平移所有顶点 (法线不变)
+    pub fn translated(mut self, offset: Vec3) -> Self {
+        let d = offset.to_f32_array();
+        for v in &mut self.vertices {
+            for (p, d) in v.position.iter_mut().zip(d) { *p += d; }
+        }
+        self
+    }
+
+    // 拼接若干网格 (顶点不焊接)
+    fn merge(parts: Vec<MeshData>) -> Self {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for part in parts {
+            let base = vertices.len() as u32;
+            indices.extend(part.indices.iter().map(|i| i + base));
+            vertices.extend(part.vertices);
+        }
+        Self { vertices, indices }
+    }
+
+    // 长方体线框 (12 条棱，LineList)，用作占位或包围盒显示
+    pub fn new_box_wireframe(min: Vec3, max: Vec3) -> Self {
+        let n = [0.0, 0.0, 0.0];
+        let vertices = (0..8)
+            .map(|i| {
+                let pick = |bit: usize, lo: f64, hi: f64| if i & bit == 0 { lo as f32 } else { hi as f32 };
+                Vertex3D { position: [pick(1, min.x, max.x), pick(2, min.y, max.y), pick(4, min.z, max.z)], normal: n }
+            })
+            .collect();
+        // 顶点编号的三个二进制位对应 x / y / z，棱连接只差一位的两个顶点
+        let indices = vec![
+            0, 1, 2, 3, 4, 5, 6, 7, // x 方向
+            0, 2, 1, 3, 4, 6, 5, 7, // y 方向
+            0, 4, 1, 5, 2, 6, 3, 7, // z 方向
+        ];
+        Self { vertices, indices }
+    }
+
+    // 轴对齐包围盒 (模型空间)，返回 (min, max)
+    // 跳过非有限顶点 (参数曲面的无效采样)；无有效顶点时 min > max (空盒)
+    pub fn compute_aabb(&self) -> (Vec3, Vec3) {
+        let mut min = Vec3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+        let mut max = Vec3::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for v in &self.vertices {
+            let [x, y, z] = v.position.map(|c| c as f64);
+            if !(x.is_finite() && y.is_finite() && z.is_finite()) {
+                continue;
+            }
+            min = Vec3::new(min.x.min(x), min.y.min(y), min.z.min(z));
+            max = Vec3::new(max.x.max(x), max.y.max(y), max.z.max(z));
+        }
+        (min, max)
+    }
+
+    // ---------- 叠加线：等参线与等高线 (LineList) ----------
+
+    // 参数曲面的等参线：u_lines 条 iso-u 线 (u 为常数，沿 v 采样 samples 段) 与 v_lines 条 iso-v 线
+    // 周期方向 (topology.wrap_u / wrap_v) 的线均匀分布且不重复首尾，否则包含两端边界
+    // 与 new_parametric_surface 相同：非有限采样点处、相邻点距离超过 jump_threshold 处断开
+    // 顶点法线为曲面法线 (中心差分)，供 offset_along_normals 使用
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_iso_curves<F>(
+        func: F,
+        u_range: (f64, f64),
+        v_range: (f64, f64),
+        u_lines: u32,
+        v_lines: u32,
+        samples: u32,
+        topology: SurfaceTopology,
+        jump_threshold: f32,
+    ) -> Self
+    where
+        F: Fn(f64, f64) -> Vec3 + Sync,
+    {
+        let lines = iso_polylines(&func, u_range, v_range, u_lines, v_lines, samples, topology, jump_threshold);
+        // 差分步长：参数范围的 1e-6
+        let (hu, hv) = ((u_range.1 - u_range.0) * 1e-6, (v_range.1 - v_range.0) * 1e-6);
+        let normal = |u: f64, v: f64| -> Vec3 {
+            let du = func(u + hu, v) - func(u - hu, v);
+            let dv = func(u, v + hv) - func(u, v - hv);
+            let n = du.cross(dv);
+            if n.len() > 0.0 && n.len().is_finite() { n.unit() } else { Vec3::ZERO }
+        };
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for line in &lines {
+            let base = vertices.len() as u32;
+            vertices.extend(line.iter().map(|&(u, v, p)| Vertex3D {
+                position: p.to_f32_array(),
+                normal: normal(u, v).to_f32_array(),
+            }));
+            for k in 1..line.len() as u32 {
+                indices.extend_from_slice(&[base + k - 1, base + k]);
+            }
+        }
+        Self { vertices, indices }
+    }
+
+    // 任意三角网格上 z = level 的等高线 (marching triangles)
+    // 每个三角形在与平面相交的两条边上线性插值出交点，连成一段；法线同样插值
+    // 顶点恰好在平面上时按 z >= level 归入上方，共享边上的交点不会重复产生；含非有限顶点的三角形跳过
+    pub fn extract_z_contours(&self, levels: &[f64]) -> MeshData {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let corner = |i: u32| {
+            let v = &self.vertices[i as usize];
+            let [px, py, pz] = v.position.map(|c| c as f64);
+            let [nx, ny, nz] = v.normal.map(|c| c as f64);
+            (Vec3::new(px, py, pz), Vec3::new(nx, ny, nz))
+        };
+        for tri in self.indices.chunks_exact(3) {
+            let corners = [corner(tri[0]), corner(tri[1]), corner(tri[2])];
+            if corners.iter().any(|(p, _)| !(p.x.is_finite() && p.y.is_finite() && p.z.is_finite())) {
+                continue;
+            }
+            for &level in levels {
+                let above = corners.map(|(p, _)| p.z >= level);
+                let crossings = (0..3)
+                    .filter(|&k| above[k] != above[(k + 1) % 3])
+                    .map(|k| {
+                        let ((pa, na), (pb, nb)) = (corners[k], corners[(k + 1) % 3]);
+                        let t = (level - pa.z) / (pb.z - pa.z);
+                        let n = na + (nb - na) * t;
+                        let n = if n.len() > 0.0 { n.unit() } else { n };
+                        Vertex3D {
+                            // z 直接取 level，不受插值舍入影响
+                            position: [(pa.x + (pb.x - pa.x) * t) as f32, (pa.y + (pb.y - pa.y) * t) as f32, level as f32],
+                            normal: n.to_f32_array(),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                // 穿过平面的三角形恰有两条边变号
+                if let [a, b] = crossings[..] {
+                    let base = vertices.len() as u32;
+                    vertices.extend_from_slice(&[a, b]);
+                    indices.extend_from_slice(&[base, base + 1]);
+                }
+            }
+        }
+        MeshData { vertices, indices }
+    }
+
+    // 顶点沿各自法线外移 distance (法线为零的顶点不动)
+    // 叠加在曲面上的线条外移一点，避免与曲面深度冲突 (z-fighting)
+    pub fn offset_along_normals(mut self, distance: f32) -> Self {
+        for v in &mut self.vertices {
+            for (p, n) in v.position.iter_mut().zip(v.normal) { *p += n * distance; }
+        }
+        self
+    }
+}
+
+// 等参线采样 (世界坐标 f64)：每个点带参数 (u, v)，每条至少两个点
+// 参数见 MeshData::new_iso_curves
+#[allow(clippy::too_many_arguments)]
+pub fn iso_polylines<F>(
+    func: &F,
+    u_range: (f64, f64),
+    v_range: (f64, f64),
+    u_lines: u32,
+    v_lines: u32,
+    samples: u32,
+    topology: SurfaceTopology,
+    jump_threshold: f32,
+) -> Vec<Vec<(f64, f64, Vec3)>>
+where
+    F: Fn(f64, f64) -> Vec3 + Sync,
+{
+    // 第 i 条线的参数：周期方向不取末端 (与首端重合)
+    let line_param = |range: (f64, f64), count: u32, wrap: bool, i: u32| {
+        let parts = if wrap || count < 2 { count } else { count - 1 };
+        range.0 + (range.1 - range.0) * i as f64 / parts.max(1) as f64
+    };
+    let sample_param = |range: (f64, f64), k: u32| range.0 + (range.1 - range.0) * k as f64 / samples as f64;
+
+    let iso_u = (0..u_lines).into_par_iter().map(|i| {
+        let u = line_param(u_range, u_lines, topology.wrap_u, i);
+        (0..=samples).map(|k| {
+            let v = sample_param(v_range, k);
+            (u, v, func(u, v))
+        }).collect::<Vec<_>>()
+    });
+    let iso_v = (0..v_lines).into_par_iter().map(|j| {
+        let v = line_param(v_range, v_lines, topology.wrap_v, j);
+        (0..=samples).map(|k| {
+            let u = sample_param(u_range, k);
+            (u, v, func(u, v))
+        }).collect::<Vec<_>>()
+    });
+    let paths: Vec<Vec<(f64, f64, Vec3)>> = iso_u.chain(iso_v).collect();
+    paths.into_iter().flat_map(|path| split_path(path, jump_threshold as f64)).collect()
+}
+
+// 在非有限点与突跃处断开 (同 new_parametric_surface 的三角形屏蔽)，丢弃不足两个点的片段
+fn split_path(path: Vec<(f64, f64, Vec3)>, jump_threshold: f64) -> Vec<Vec<(f64, f64, Vec3)>> {
+    let finite = |p: Vec3| p.x.is_finite() && p.y.is_finite() && p.z.is_finite();
+    let mut lines = Vec::new();
+    let mut current: Vec<(f64, f64, Vec3)> = Vec::new();
+    for point in path {
+        let jump = current.last().is_some_and(|last| last.2.dis(point.2) > jump_threshold);
+        if !finite(point.2) || jump {
+            if current.len() > 1 { lines.push(std::mem::take(&mut current)); } else { current.clear(); }
+        }
+        if finite(point.2) { current.push(point); }
+    }
+    if current.len() > 1 { lines.push(current); }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn torus(u: f64, v: f64) -> Vec3 {
+        let (r_major, r_minor) = (3.0, 1.2);
+        Vec3::new(
+            (r_major + r_minor * v.cos()) * u.cos(),
+            (r_major + r_minor * v.cos()) * u.sin(),
+            r_minor * v.sin(),
+        )
+    }
+
+    fn sphere(u: f64, v: f64) -> Vec3 {
+        Vec3::new(v.sin() * u.cos(), v.sin() * u.sin(), v.cos())
+    }
+
+    #[test]
+    fn test_default_is_unwelded() {
+        let m = MeshData::new_parametric_surface(torus, (0.0, TAU), (0.0, TAU), 12, 8);
+        assert_eq!(m.vertices.len(), 13 * 9);
+        assert_eq!(m.indices.len(), 12 * 8 * 6);
+    }
+
+    #[test]
+    fn test_wrapped_torus() {
+        let (nu, nv) = (24u32, 12u32);
+        let topology = SurfaceTopology { wrap_u: true, wrap_v: true, ..Default::default() };
+        let m = MeshData::new_parametric_surface_with(torus, (0.0, TAU), (0.0, TAU), nu, nv, topology);
+
+        assert_eq!(m.vertices.len(), (nu * nv) as usize);
+        assert_eq!(m.indices.len(), (nu * nv * 6) as usize);
+        assert!(m.indices.iter().all(|&i| (i as usize) < m.vertices.len()));
+
+        // 接缝已焊接：不存在位置重合的两个顶点，接缝两侧的三角形共享同一顶点 (法线完全一致)
+        for (a, va) in m.vertices.iter().enumerate() {
+            for vb in &m.vertices[a + 1..] {
+                let d: f32 = (0..3).map(|k| (va.position[k] - vb.position[k]).abs()).sum();
+                assert!(d > 1e-4);
+            }
+        }
+        // u = 2π 一侧的最后一列四边形引用的正是 u = 0 的顶点：每个顶点恰被 6 个三角形使用
+        let mut uses = vec![0; m.vertices.len()];
+        for &i in &m.indices { uses[i as usize] += 1; }
+        assert!(uses.iter().all(|&c| c == 6));
+    }
+
+    #[test]
+    fn test_collapsed_poles() {
+        let (nu, nv) = (16u32, 10u32);
+        let topology = SurfaceTopology { wrap_u: true, wrap_v: false, collapse_degenerate_rows: true };
+        let m = MeshData::new_parametric_surface_with(sphere, (0.0, TAU), (0.0, PI), nu, nv, topology);
+
+        // 中间 nv - 1 行各 nu 个顶点，两极各 1 个
+        assert_eq!(m.vertices.len(), (nu * (nv - 1) + 2) as usize);
+        assert!(m.indices.iter().all(|&i| (i as usize) < m.vertices.len()));
+        // 极点三角形不退化：每个三角形三个下标互不相同
+        for t in m.indices.chunks(3) {
+            assert!(t[0] != t[1] && t[1] != t[2] && t[0] != t[2]);
+        }
+        // 极点法线为相邻行平均，沿 ±z (法线朝向由参数化决定，只比较轴向)
+        let poles: Vec<&Vertex3D> = m.vertices.iter().filter(|v| v.position[2].abs() > 0.999).collect();
+        assert_eq!(poles.len(), 2);
+        for p in poles {
+            assert!(p.normal[2].abs() > 0.999, "{:?}", p.normal);
+        }
+    }
+
+    #[test]
+    fn test_parallel_matches_serial() {
+        // 带断点 (u = v 附近无定义) 与突跃 (1 / (u - 0.5)) 的确定性曲面
+        let f = |u: f64, v: f64| {
+            if (u - v).abs() < 0.01 { return Vec3::new(f64::NAN, 0.0, 0.0); }
+            let mut z = (u * 7.0).sin() * (v * 5.0).cos() + 1.0 / (u - 0.5013);
+            for k in 1..40 { z += ((u + v) * k as f64).sin() / (k * k) as f64; }
+            Vec3::new(u, v, z)
+        };
+        let build = || MeshData::new_parametric_surface(f, (0.0, 1.0), (0.0, 1.0), 400, 400);
+        let serial = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap().install(build);
+        let parallel = build();
+
+        let bits = |m: &MeshData| -> Vec<u32> {
+            m.vertices.iter().flat_map(|v| v.position.iter().chain(&v.normal).map(|c| c.to_bits())).collect()
+        };
+        assert_eq!(bits(&serial), bits(&parallel));
+        assert_eq!(serial.indices, parallel.indices);
+        assert!(parallel.vertices.iter().any(|v| v.position[0].is_nan()));
+    }
+
+    #[test]
+    fn test_grid_normals_and_jump_threshold() {
+        // 球面法线由网格差分得到，仍沿径向 (含经度接缝两侧)
+        let topology = SurfaceTopology { wrap_u: true, wrap_v: false, collapse_degenerate_rows: true };
+        let m = MeshData::new_parametric_surface_with(sphere, (0.0, TAU), (0.0, PI), 64, 32, topology);
+        for v in &m.vertices {
+            let dot: f32 = (0..3).map(|k| v.position[k] * v.normal[k]).sum();
+            assert!(dot.abs() > 0.998, "{:?}", v);
+        }
+
+        // z = 0.3 / (u - 0.5)：渐近线附近 (u = 0.5 的采样点两侧) 两列三角形被默认阈值屏蔽，阈值为无穷时全部连接
+        let f = |u: f64, v: f64| Vec3::new(u, v, 0.3 / (u - 0.5013));
+        let never = AtomicBool::new(false);
+        let build = |threshold: f32| {
+            MeshData::new_parametric_surface_cancellable(f, (0.0, 1.0), (0.0, 1.0), 20, 4, SurfaceTopology::default(), threshold, &never)
+                .unwrap()
+        };
+        assert_eq!(build(DEFAULT_JUMP_THRESHOLD).indices.len(), 18 * 4 * 6);
+        assert_eq!(build(f32::INFINITY).indices.len(), 20 * 4 * 6);
+        assert_eq!(MeshData::new_parametric_surface(f, (0.0, 1.0), (0.0, 1.0), 20, 4).indices, build(DEFAULT_JUMP_THRESHOLD).indices);
+
+        // 已取消：不求值，直接返回 None
+        let cancel = AtomicBool::new(true);
+        let m = MeshData::new_parametric_surface_cancellable(
+            |_, _| -> Vec3 { panic!("cancelled job must not sample") },
+            (0.0, 1.0), (0.0, 1.0), 8, 8, SurfaceTopology::default(), DEFAULT_JUMP_THRESHOLD, &cancel,
+        );
+        assert!(m.is_none());
+    }
+
+    #[test]
+    fn test_z_contours_of_ramp() {
+        // 斜面 z = 0.6x + 0.8y：等高线是法向 (0.6, 0.8) 的直线，长度为直线在单位正方形内的弦长
+        let ramp = |u: f64, v: f64| Vec3::new(u, v, 0.6 * u + 0.8 * v);
+        let m = MeshData::new_parametric_surface(ramp, (0.0, 1.0), (0.0, 1.0), 20, 20);
+        for (level, chord) in [(0.25, 0.5208333333333334), (0.75, 1.25), (1.15, 0.5208333333333336)] {
+            let c = m.extract_z_contours(&[level]);
+            assert_eq!(c.indices.len(), c.vertices.len());
+            let mut length = 0.0;
+            for seg in c.indices.chunks(2) {
+                let [a, b] = [seg[0], seg[1]].map(|i| c.vertices[i as usize].position.map(|x| x as f64));
+                for p in [a, b] {
+                    assert_eq!(p[2], level as f32 as f64);
+                    assert!((0.6 * p[0] + 0.8 * p[1] - level).abs() < 1e-6, "{:?}", p);
+                }
+                // 线段方向垂直于 (0.6, 0.8)
+                let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+                assert!((0.6 * dx + 0.8 * dy).abs() < 1e-6);
+                length += dx.hypot(dy);
+            }
+            assert!((length - chord).abs() < 1e-5, "level {}: {}", level, length);
+        }
+        // 多个层级一次提取；范围外的层级为空
+        let all = m.extract_z_contours(&[0.25, 0.75, 1.15]);
+        let single: usize = [0.25, 0.75, 1.15].iter().map(|&l| m.extract_z_contours(&[l]).indices.len()).sum();
+        assert_eq!(all.indices.len(), single);
+        assert!(m.extract_z_contours(&[-0.1, 1.5]).indices.is_empty());
+    }
+
+    #[test]
+    fn test_iso_curves() {
+        let topology = SurfaceTopology { wrap_u: true, wrap_v: true, ..Default::default() };
+        let lines = iso_polylines(&torus, (0.0, TAU), (0.0, TAU), 12, 8, 64, topology, DEFAULT_JUMP_THRESHOLD);
+        assert_eq!(lines.len(), 12 + 8);
+        // 周期方向均匀分布，不重复 0 与 2π
+        assert_eq!(lines[11][0].0, TAU * 11.0 / 12.0);
+        // 端点 (及所有采样点) 在环面上：(√(x² + y²) - R)² + z² = r²
+        let on_torus = |p: Vec3| ((p.x.hypot(p.y) - 3.0).powi(2) + p.z * p.z - 1.2 * 1.2).abs();
+        for line in &lines {
+            assert_eq!(line.len(), 65);
+            for &(u, v, p) in [line[0], line[64]].iter() {
+                assert!(on_torus(p) < 1e-9);
+                assert_eq!(p, torus(u, v));
+            }
+        }
+
+        // LineList：每条线 64 段；法线为曲面法线 (沿管截面的径向)，外移后仍在偏移曲面上
+        let m = MeshData::new_iso_curves(torus, (0.0, TAU), (0.0, TAU), 12, 8, 64, topology, DEFAULT_JUMP_THRESHOLD);
+        assert_eq!(m.vertices.len(), 20 * 65);
+        assert_eq!(m.indices.len(), 20 * 64 * 2);
+        let shifted = MeshData { vertices: m.vertices.clone(), indices: Vec::new() }.offset_along_normals(0.05);
+        for (v, s) in m.vertices.iter().zip(&shifted.vertices) {
+            let [x, y, z] = v.position.map(|c| c as f64);
+            let radial = Vec3::new(x - 3.0 * x / x.hypot(y), y - 3.0 * y / x.hypot(y), z).unit();
+            let n = Vec3::new(v.normal[0] as f64, v.normal[1] as f64, v.normal[2] as f64);
+            assert!((n.dot(radial).abs() - 1.0).abs() < 1e-4, "{:?}", v);
+            let [sx, sy, sz] = s.position.map(|c| c as f64);
+            assert!(((sx.hypot(sy) - 3.0).hypot(sz) - 1.2).abs() > 0.049);
+        }
+
+        // 非周期：含两端边界；在突跃 (u = 0.5) 与无效值 (v > 0.9) 处断开
+        let f = |u: f64, v: f64| if v > 0.9 { Vec3::new(f64::NAN, 0.0, 0.0) } else { Vec3::new(u, v, 1.0 / (u - 0.5)) };
+        let lines = iso_polylines(&f, (0.0, 1.0), (0.0, 1.0), 2, 3, 40, SurfaceTopology::default(), DEFAULT_JUMP_THRESHOLD);
+        let iso_u = lines.iter().filter(|l| l[0].0 == l[1].0).collect::<Vec<_>>();
+        assert_eq!(iso_u.iter().map(|l| l[0].0).collect::<Vec<_>>(), [0.0, 1.0]);
+        assert!(iso_u.iter().all(|l| l.last().unwrap().1 <= 0.9));
+        // v = 0, 0.5 两条 iso-v 线各断成两段，v = 1 整条无效
+        assert_eq!(lines.len(), 2 + 2 * 2);
+    }
+
+    #[test]
+    fn test_compute_aabb() {
+        let m = MeshData::new_parametric_surface(torus, (0.0, TAU), (0.0, TAU), 48, 24);
+        let (min, max) = m.compute_aabb();
+        assert!((max.x - 4.2).abs() < 1e-3 && (min.x + 4.2).abs() < 1e-3);
+        assert!((max.z - 1.2).abs() < 1e-2 && (min.z + 1.2).abs() < 1e-2);
+
+        // 非有限顶点不参与
+        let mut m = MeshData::new_plane(2.0);
+        m.vertices.push(Vertex3D { position: [f32::NAN, 0.0, 0.0], normal: [0.0; 3] });
+        let (min, max) = m.compute_aabb();
+        assert_eq!((min.x, min.y, min.z), (-1.0, -1.0, 0.0));
+        assert_eq!((max.x, max.y, max.z), (1.0, 1.0, 0.0));
+
+        // 空网格：min > max
+        let empty = MeshData { vertices: vec![], indices: vec![] };
+        let (min, max) = empty.compute_aabb();
+        assert!(min.x > max.x);
+    }
+
+    #[test]
+    fn test_icosphere() {
+        for n in 0..4 {
+            let m = MeshData::new_icosphere(2.0, n);
+            assert_eq!(m.vertices.len(), 10 * 4usize.pow(n) + 2);
+            assert_eq!(m.indices.len(), 3 * 20 * 4usize.pow(n));
+            assert!(m.indices.iter().all(|&i| (i as usize) < m.vertices.len()));
+        }
+
+        let m = MeshData::new_icosphere(2.0, 3);
+        let pos = |i: u32| {
+            let [x, y, z] = m.vertices[i as usize].position.map(|c| c as f64);
+            Vec3::new(x, y, z)
+        };
+        // 顶点都在球面上，法线沿径向
+        for v in &m.vertices {
+            let [x, y, z] = v.position.map(|c| c as f64);
+            assert!((Vec3::new(x, y, z).len() - 2.0).abs() < 1e-5);
+            let [nx, ny, nz] = v.normal.map(|c| c as f64);
+            assert!((Vec3::new(nx, ny, nz) * 2.0 - Vec3::new(x, y, z)).len() < 1e-5);
+        }
+        // 所有三角形朝外
+        for t in m.indices.chunks(3) {
+            let (a, b, c) = (pos(t[0]), pos(t[1]), pos(t[2]));
+            assert!((b - a).cross(c - a).dot(a + b + c) > 0.0);
+        }
+    }
+
+    // 所有三角形按顶点次序的面法线都背离 center
+    fn assert_outward(m: &MeshData, center: Vec3) {
+        let pos = |i: u32| {
+            let [x, y, z] = m.vertices[i as usize].position.map(|c| c as f64);
+            Vec3::new(x, y, z)
+        };
+        for t in m.indices.chunks(3) {
+            let (a, b, c) = (pos(t[0]), pos(t[1]), pos(t[2]));
+            let n = (b - a).cross(c - a);
+            assert!(n.dot((a + b + c) * (1.0 / 3.0) - center) > 0.0, "inward triangle {:?}", t);
+        }
+    }
+
+    #[test]
+    fn test_named_solids() {
+        let center = Vec3::new(1.0, -2.0, 0.5);
+        let torus = MeshData::new_torus(center, 3.0, 1.2, 24, 12).unwrap();
+        assert_eq!(torus.vertices.len(), 24 * 12);
+        let (min, max) = torus.compute_aabb();
+        assert!((max.x - 5.2).abs() < 1e-3 && (min.z + 0.7).abs() < 1e-3);
+        // 环面的法线朝向管的中心圆外侧
+        let ring = |i: u32| {
+            let p = torus.vertices[i as usize].position;
+            let (x, y) = (p[0] as f64 - center.x, p[1] as f64 - center.y);
+            Vec3::new(x, y, 0.0).unit() * 3.0 + center
+        };
+        for t in torus.indices.chunks(3) {
+            let p = torus.vertices[t[0] as usize].position.map(|c| c as f64);
+            let n = torus.vertices[t[0] as usize].normal.map(|c| c as f64);
+            let d = Vec3::new(p[0], p[1], p[2]) - ring(t[0]);
+            assert!(d.dot(Vec3::new(n[0], n[1], n[2])) > 0.0);
+        }
+
+        // 球面顶点都在半径 r 处
+        let sphere = MeshData::new_sphere(center, 2.0, 16).unwrap();
+        assert_eq!(sphere.vertices.len(), 16 * 7 + 2);
+        for v in &sphere.vertices {
+            let [x, y, z] = v.position.map(|c| c as f64);
+            assert!((Vec3::new(x, y, z).dis(center) - 2.0).abs() < 1e-5);
+        }
+        assert_outward(&sphere, center);
+
+        let axis = Vec3::new(1.0, 1.0, 1.0);
+        let cylinder = MeshData::new_cylinder(center, axis, 1.0, 4.0, 16).unwrap();
+        assert_outward(&cylinder, center + axis.unit() * 2.0);
+        for v in &cylinder.vertices {
+            let [x, y, z] = v.position.map(|c| c as f64);
+            let h = (Vec3::new(x, y, z) - center).dot(axis.unit());
+            assert!((-1e-5..4.0 + 1e-5).contains(&h));
+        }
+
+        let cone = MeshData::new_cone(center, -Vec3::I, 1.0, 2.0, 16).unwrap();
+        assert_outward(&cone, center - Vec3::I * 0.5);
+        let (min, max) = cone.compute_aabb();
+        assert!((min.x - (center.x - 2.0)).abs() < 1e-5 && (max.x - center.x).abs() < 1e-5);
+
+        let cube = MeshData::new_box(Vec3::new(-1.0, -2.0, -3.0), Vec3::new(1.0, 2.0, 3.0)).unwrap();
+        assert_eq!((cube.vertices.len(), cube.indices.len()), (24, 36));
+        assert_outward(&cube, Vec3::ZERO);
+
+        let moved = MeshData::new_sphere(Vec3::ZERO, 1.0, 8).unwrap().translated(Vec3::new(5.0, 0.0, 0.0));
+        assert!((moved.compute_aabb().0.x - 4.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_surface_of_revolution() {
+        // 竖直线段绕轴旋转得到圆柱侧面
+        let (nu, nv) = (16u32, 4u32);
+        let axis = Vec3::new(0.0, 2.0, 1.0);
+        let m = MeshData::new_surface_of_revolution(|t| Vec2::new(1.5, t), axis, (0.0, 2.0), nu, nv).unwrap();
+        assert_eq!(m.indices.len(), (nu * nv * 6) as usize);
+        assert_eq!(m.vertices.len(), (nu * (nv + 1)) as usize);
+        for v in &m.vertices {
+            let [x, y, z] = v.position.map(|c| c as f64);
+            assert!((Vec3::new(x, y, z).project_onto_plane(axis).len() - 1.5).abs() < 1e-5);
+        }
+        assert_outward(&m, axis.unit());
+
+        // 闭合轮廓 (圆) 首尾焊接
+        let ring = MeshData::new_surface_of_revolution(
+            |t| Vec2::new(2.0 + 0.5 * t.cos(), 0.5 * t.sin()), Vec3::K, (0.0, TAU), nu, nv,
+        ).unwrap();
+        assert_eq!(ring.vertices.len(), (nu * nv) as usize);
+    }
+
+    #[test]
+    fn test_named_solid_validation() {
+        assert_eq!(MeshData::new_torus(Vec3::ZERO, 1.0, 1.0, 24, 12).err(), Some(MeshError::SelfIntersecting));
+        assert_eq!(MeshData::new_torus(Vec3::ZERO, 1.0, 0.5, 2, 12).err(), Some(MeshError::TooFewSegments("seg_u")));
+        assert_eq!(MeshData::new_sphere(Vec3::ZERO, -1.0, 16).err(), Some(MeshError::InvalidSize("radius")));
+        assert!(MeshData::new_sphere(Vec3::ZERO, f64::NAN, 16).is_err());
+        assert!(MeshData::new_cylinder(Vec3::ZERO, Vec3::K, 1.0, 0.0, 16).is_err());
+        assert_eq!(MeshData::new_cone(Vec3::ZERO, Vec3::ZERO, 1.0, 1.0, 16).err(), Some(MeshError::InvalidSize("axis")));
+        assert!(MeshData::new_cone(Vec3::ZERO, Vec3::K, 1.0, 1.0, 2).is_err());
+        assert!(MeshData::new_box(Vec3::ZERO, Vec3::new(1.0, -1.0, 1.0)).is_err());
+    }
 }
@@ -0,0 +1,265 @@
+// src/d3/mesh_job.rs
+// 后台网格生成：求解在独立线程中进行，窗口照常响应
+//   MeshJob 描述要生成的网格 (隐曲面 / 参数曲面)
+//   PendingJob 持有线程、事件通道与取消标志；窗口在 about_to_wait 中 poll，完成后上传 GPU
+//   PendingJob 被丢弃 (绘图器关闭) 时置取消标志并等待线程结束，求解器每层切片检查一次标志
+#![allow(dead_code)]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread::{self, JoinHandle};
+
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+
+use super::implicit_surface::ImplicitSurfaceSolver;
+use super::mesh::{MeshData, SurfaceTopology, DEFAULT_JUMP_THRESHOLD};
+
+type ScalarField = Box<dyn Fn(f64, f64, f64) -> f64 + Sync + Send>;
+type SurfaceFn = Box<dyn Fn(f64, f64) -> Vec3 + Sync + Send>;
+
+// 参数曲面估计占位包围盒时的采样数 (每个方向)
+const BOUNDS_SAMPLES: usize = 8;
+
+pub enum MeshJobKind {
+    Implicit {
+        func: ScalarField,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        z_range: (f64, f64),
+        resolution: u32,
+    },
+    Parametric {
+        func: SurfaceFn,
+        u_range: (f64, f64),
+        v_range: (f64, f64),
+        u_segments: u32,
+        v_segments: u32,
+        topology: SurfaceTopology,
+    },
+}
+
+// 一个待生成的网格及其显示颜色
+pub struct MeshJob {
+    pub kind: MeshJobKind,
+    pub color: [f32; 4],
+}
+
+impl MeshJob {
+    pub fn implicit<F>(func: F, x_range: (f64, f64), y_range: (f64, f64), z_range: (f64, f64), resolution: u32, color: [f32; 4]) -> Self
+    where F: Fn(f64, f64, f64) -> f64 + Sync + Send + 'static {
+        Self { kind: MeshJobKind::Implicit { func: Box::new(func), x_range, y_range, z_range, resolution }, color }
+    }
+
+    pub fn parametric<F>(
+        func: F, u_range: (f64, f64), v_range: (f64, f64), u_segments: u32, v_segments: u32,
+        topology: SurfaceTopology, color: [f32; 4],
+    ) -> Self
+    where F: Fn(f64, f64) -> Vec3 + Sync + Send + 'static {
+        Self { kind: MeshJobKind::Parametric { func: Box::new(func), u_range, v_range, u_segments, v_segments, topology }, color }
+    }
+
+    // 占位线框的范围：隐曲面取采样范围，参数曲面取粗采样点的包围盒
+    pub fn bounds(&self) -> (Vec3, Vec3) {
+        match &self.kind {
+            MeshJobKind::Implicit { x_range, y_range, z_range, .. } => (
+                Vec3::new(x_range.0, y_range.0, z_range.0),
+                Vec3::new(x_range.1, y_range.1, z_range.1),
+            ),
+            MeshJobKind::Parametric { func, u_range, v_range, .. } => {
+                let mut min = Vec3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+                let mut max = -min;
+                let lerp = |r: (f64, f64), i: usize| r.0 + (r.1 - r.0) * i as f64 / BOUNDS_SAMPLES as f64;
+                for i in 0..=BOUNDS_SAMPLES {
+                    for j in 0..=BOUNDS_SAMPLES {
+                        let p = func(lerp(*u_range, i), lerp(*v_range, j));
+                        if !(p.x.is_finite() && p.y.is_finite() && p.z.is_finite()) { continue; }
+                        min = Vec3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+                        max = Vec3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+                    }
+                }
+                (min, max)
+            }
+        }
+    }
+
+    // 在当前线程执行，进度与结果写入 events；被取消时返回 false 且不发送 Done
+    pub fn execute(&self, cancel: &AtomicBool, events: &Sender<JobEvent>) -> bool {
+        let report = |f: f64| { let _ = events.send(JobEvent::Progress(f)); };
+        let mesh = match &self.kind {
+            MeshJobKind::Implicit { func, x_range, y_range, z_range, resolution } => {
+                ImplicitSurfaceSolver::solve_with_progress(func, *x_range, *y_range, *z_range, *resolution, cancel, &report)
+            }
+            // 参数曲面按行并行采样，很快，不细分进度 (取消仍按行检查)
+            MeshJobKind::Parametric { func, u_range, v_range, u_segments, v_segments, topology } => {
+                let mesh = MeshData::new_parametric_surface_cancellable(
+                    func, *u_range, *v_range, *u_segments, *v_segments, *topology, DEFAULT_JUMP_THRESHOLD, cancel,
+                );
+                mesh.inspect(|_| report(1.0))
+            }
+        };
+        match mesh {
+            Some(mesh) => { let _ = events.send(JobEvent::Done(mesh)); true }
+            None => false,
+        }
+    }
+}
+
+// 后台线程发往窗口的事件
+pub enum JobEvent {
+    Progress(f64), // 已完成比例 (0 ~ 1)
+    Done(MeshData),
+}
+
+pub enum JobStatus {
+    Running(f64),
+    Done(MeshData),
+    Failed, // 被取消，或线程 panic 后通道断开
+}
+
+// 进行中的任务 (状态机：Running -> Done / Failed)
+pub struct PendingJob {
+    pub id: u64,
+    pub color: [f32; 4],
+    pub bounds: (Vec3, Vec3),
+    progress: f64,
+    events: Receiver<JobEvent>,
+    cancel: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl PendingJob {
+    // 在新线程中执行 (线程内部的求解仍由 rayon 并行)
+    pub fn spawn(id: u64, job: MeshJob) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut pending = Self::new(id, &job, rx, cancel.clone());
+        pending.handle = Some(thread::spawn(move || { job.execute(&cancel, &tx); }));
+        pending
+    }
+
+    // 在当前线程执行完毕，事件留在通道中等待 poll (测试与无窗口场景)
+    pub fn run_sync(id: u64, job: MeshJob) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        job.execute(&cancel, &tx);
+        Self::new(id, &job, rx, cancel)
+    }
+
+    fn new(id: u64, job: &MeshJob, events: Receiver<JobEvent>, cancel: Arc<AtomicBool>) -> Self {
+        Self { id, color: job.color, bounds: job.bounds(), progress: 0.0, events, cancel, handle: None }
+    }
+
+    pub fn progress(&self) -> f64 {
+        self.progress
+    }
+
+    // 取走已到达的事件；完成后再 poll 返回 Failed (结果只交付一次)
+    pub fn poll(&mut self) -> JobStatus {
+        loop {
+            match self.events.try_recv() {
+                Ok(JobEvent::Progress(f)) => self.progress = self.progress.max(f),
+                Ok(JobEvent::Done(mesh)) => {
+                    self.progress = 1.0;
+                    return JobStatus::Done(mesh);
+                }
+                Err(TryRecvError::Empty) => return JobStatus::Running(self.progress),
+                Err(TryRecvError::Disconnected) => return JobStatus::Failed,
+            }
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for PendingJob {
+    fn drop(&mut self) {
+        self.cancel();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn sphere(x: f64, y: f64, z: f64) -> f64 {
+        x * x + y * y + z * z - 1.0
+    }
+
+    #[test]
+    fn test_progress_monotonic_and_result_matches() {
+        let range = (-1.5, 1.5);
+        let job = MeshJob::implicit(sphere, range, range, range, 24, [1.0; 4]);
+        let (tx, rx) = mpsc::channel();
+        assert!(job.execute(&AtomicBool::new(false), &tx));
+        drop(tx);
+
+        let mut last = 0.0;
+        let mut steps = 0;
+        let mut mesh = None;
+        for event in rx {
+            match event {
+                JobEvent::Progress(f) => {
+                    assert!(f > last && f <= 1.0);
+                    last = f;
+                    steps += 1;
+                }
+                JobEvent::Done(m) => mesh = Some(m),
+            }
+        }
+        assert_eq!(steps, 25 + 24); // 每层切片一次
+        assert_eq!(last, 1.0);
+
+        let direct = ImplicitSurfaceSolver::solve(&sphere, range, range, range, 24);
+        let mesh = mesh.unwrap();
+        assert_eq!(mesh.indices, direct.indices);
+        assert!(mesh.vertices.iter().zip(&direct.vertices).all(|(a, b)| a.position == b.position));
+    }
+
+    #[test]
+    fn test_state_machine() {
+        let range = (-1.5, 1.5);
+        let mut job = PendingJob::run_sync(7, MeshJob::implicit(sphere, range, range, range, 16, [1.0; 4]));
+        assert_eq!(job.bounds, (Vec3::new(-1.5, -1.5, -1.5), Vec3::new(1.5, 1.5, 1.5)));
+        assert!(matches!(job.poll(), JobStatus::Done(m) if !m.indices.is_empty()));
+        assert_eq!(job.progress(), 1.0);
+        assert!(matches!(job.poll(), JobStatus::Failed));
+
+        // 参数曲面：占位框来自粗采样
+        let mut job = PendingJob::run_sync(8, MeshJob::parametric(
+            |u, v| Vec3::new(u, v, u * v), (0.0, 2.0), (-1.0, 1.0), 10, 10, SurfaceTopology::default(), [1.0; 4],
+        ));
+        assert_eq!(job.bounds, (Vec3::new(0.0, -1.0, -2.0), Vec3::new(2.0, 1.0, 2.0)));
+        assert!(matches!(job.poll(), JobStatus::Done(m) if m.vertices.len() == 121));
+    }
+
+    #[test]
+    fn test_cancel_on_drop() {
+        // 很慢的场：不取消的话要算很久
+        let slow = |x: f64, y: f64, z: f64| {
+            std::thread::sleep(Duration::from_micros(10));
+            sphere(x, y, z)
+        };
+        let range = (-1.5, 1.5);
+        let mut job = PendingJob::spawn(1, MeshJob::implicit(slow, range, range, range, 200, [1.0; 4]));
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(matches!(job.poll(), JobStatus::Running(_)));
+
+        let start = Instant::now();
+        drop(job);
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        // 预先取消：不会发送 Done
+        let job = MeshJob::implicit(sphere, range, range, range, 8, [1.0; 4]);
+        let (tx, rx) = mpsc::channel();
+        assert!(!job.execute(&AtomicBool::new(true), &tx));
+        drop(tx);
+        assert!(rx.iter().all(|e| matches!(e, JobEvent::Progress(_))));
+    }
+}
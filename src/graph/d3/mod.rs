@@ -4,17 +4,23 @@ mod mesh;
 pub mod parametric_curve;
 pub mod implicit_surface;
 mod implicit_data; // 假设查找表在这里
+pub mod sdf;
+pub mod mesh_job;
 
 // 导出求解器
 pub use parametric_curve::ParametricCurveSolver;
 pub use implicit_surface::ImplicitSurfaceSolver;
+pub use camera::CameraSnapshot;
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::mem::size_of;
+use std::time::{Duration, Instant};
 use winit::{
     application::ApplicationHandler,
     event::{ElementState, MouseButton, WindowEvent, DeviceEvent},
-    event_loop::ActiveEventLoop,
+    event_loop::{ActiveEventLoop, ControlFlow},
+    keyboard::Key,
     window::{Window, WindowId},
 };
 use wgpu::util::DeviceExt;
@@ -22,14 +28,17 @@ use bytemuck::{Pod, Zeroable};
 
 // --- ★ 引入 MathForest ---
 use crate::math_forest::algebra::linear::matrix4x4::Matrix4x4;
+use super::gpu_interop::mat4_to_cols_f32;
+use super::session::Session;
 use crate::math_forest::geometry::d3::linear::vec3::Vec3;
 
 // 保留 glam::Mat4 仅用于与 Camera 的返回值对接 (Camera 内部已处理好 ViewProj 的 f32 转换)
 use glam::Mat4;
 
-use self::camera::Camera;
+use self::camera::{Camera, Frustum};
+use self::mesh_job::{JobStatus, MeshJob, PendingJob};
 // 导出 MeshData 和 Vertex3D 以便外部使用
-pub use self::mesh::{MeshData, Vertex3D};
+pub use self::mesh::{DEFAULT_JUMP_THRESHOLD, MeshData, SurfaceTopology, Vertex3D};
 
 // --- GPU 数据结构 ---
 
@@ -46,6 +55,49 @@ struct Uniforms {
     _pad3: [f32; 4],        // 16 bytes -> Total 192 bytes (padding for alignment)
 }
 
+// 实例数据 (每个实例一份)：模型矩阵按列拆成 4 个 Float32x4，外加实例颜色
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+struct InstanceRaw {
+    model: [f32; 16], // 列优先，与 Uniforms::model 相同
+    color: [f32; 4],
+}
+
+// location 0/1 为顶点的 position/normal
+const INSTANCE_ATTRIBUTES: [wgpu::VertexAttribute; 5] =
+    wgpu::vertex_attr_array![2 => Float32x4, 3 => Float32x4, 4 => Float32x4, 5 => Float32x4, 6 => Float32x4];
+
+// 实例化对象的 GPU 端数据
+struct InstanceBuffer {
+    buffer: wgpu::Buffer,
+    count: u32,
+    capacity: u32,
+    // 每个实例的颜色；None 时统一使用对象颜色
+    colors: Option<Vec<[f32; 4]>>,
+    // 单个网格的模型空间包围盒 (对象包围盒为所有实例的并)
+    mesh_aabb: (Vec3, Vec3),
+}
+
+// 模型矩阵动画：起止变换 + 进度 (插值见 Matrix4x4::lerp)
+struct TransformAnimation {
+    from: Matrix4x4,
+    to: Matrix4x4,
+    elapsed: f64,
+    duration: f64,
+}
+
+impl TransformAnimation {
+    // 推进 dt 秒，返回当前变换与是否结束 (smoothstep 缓动，结束时精确落在 to)
+    fn advance(&mut self, dt: f64) -> (Matrix4x4, bool) {
+        self.elapsed += dt;
+        if self.elapsed >= self.duration {
+            return (self.to, true);
+        }
+        let x = self.elapsed / self.duration;
+        (self.from.lerp(self.to, x * x * (3.0 - 2.0 * x)), false)
+    }
+}
+
 // 渲染对象 (GPU端 + 逻辑状态)
 struct RenderObject {
     vertex_buffer: wgpu::Buffer,
@@ -59,6 +111,14 @@ struct RenderObject {
     // ★ 使用 MathForest 的矩阵 (f64, Row-Major)
     model_matrix: Matrix4x4,
     topology: wgpu::PrimitiveTopology,
+    // 模型空间包围盒 (min, max)，用于视锥剔除
+    aabb: (Vec3, Vec3),
+    // D3Plotter::add_object 分配的 id (坐标轴、占位线框等内部对象为 None)
+    id: Option<usize>,
+    // 实例化绘制：同一网格按各实例的变换画多份
+    instances: Option<InstanceBuffer>,
+    // 进行中的模型矩阵动画
+    animation: Option<TransformAnimation>,
 }
 
 // ==========================================
@@ -70,6 +130,10 @@ pub struct GeoObjD3 {
     pub topology: wgpu::PrimitiveTopology,
     pub use_lighting: bool,
     pub is_transparent: bool,
+    // 实例变换：Some 时按实例化路径绘制 (一份顶点缓冲 + 每实例一个模型矩阵)
+    pub instances: Option<Vec<Matrix4x4>>,
+    // 每个实例的颜色 (可选，缺省用 color)
+    pub instance_colors: Option<Vec<[f32; 4]>>,
 }
 
 impl GeoObjD3 {
@@ -81,6 +145,8 @@ impl GeoObjD3 {
             topology: wgpu::PrimitiveTopology::TriangleList,
             use_lighting: true,
             is_transparent: false,
+            instances: None,
+            instance_colors: None,
         }
     }
 
@@ -92,8 +158,29 @@ impl GeoObjD3 {
             topology: wgpu::PrimitiveTopology::LineList,
             use_lighting: false, // 线条通常不需要光照
             is_transparent: false,
+            instances: None,
+            instance_colors: None,
+        }
+    }
+
+    // 叠加在曲面上的线条 (等参线、等高线)：沿顶点法线外移 offset，避免与曲面深度冲突
+    pub fn new_overlay(mesh: MeshData, color: [f32; 4], offset: f32) -> Self {
+        Self::new_wireframe(mesh.offset_along_normals(offset), color)
+    }
+
+    // 辅助构造函数：同一网格按 transforms 画多份 (如大量散点小球)
+    pub fn new_instanced(mesh: MeshData, color: [f32; 4], transforms: Vec<Matrix4x4>) -> Self {
+        Self {
+            instances: Some(transforms),
+            ..Self::new_surface(mesh, color)
         }
     }
+
+    // 每个实例单独着色 (按下标对应；不足的部分用 color)
+    pub fn with_instance_colors(mut self, colors: Vec<[f32; 4]>) -> Self {
+        self.instance_colors = Some(colors);
+        self
+    }
 }
 
 // ==========================================
@@ -110,6 +197,10 @@ pub struct State {
     mesh_pipeline: wgpu::RenderPipeline,
     line_pipeline: wgpu::RenderPipeline,
     transparent_pipeline: wgpu::RenderPipeline,
+    // 实例化版本 (顶点布局多一个逐实例缓冲)
+    instanced_mesh_pipeline: wgpu::RenderPipeline,
+    instanced_line_pipeline: wgpu::RenderPipeline,
+    instanced_transparent_pipeline: wgpu::RenderPipeline,
 
     bind_group_layout: wgpu::BindGroupLayout,
     depth_texture: wgpu::Texture,
@@ -117,14 +208,18 @@ pub struct State {
 
     camera: Camera,
     mouse_pressed: Option<MouseButton>,
+    scale_factor: f64, // 窗口缩放比：拖拽位移按逻辑点计，HiDPI 下灵敏度不变
+    last_frame_time: Option<Instant>, // 相机动画计时，空闲时为 None
 
     objects: Vec<RenderObject>, // 不透明对象
     transparent_objects: Vec<RenderObject>, // 半透明对象 (最后绘制)
+    placeholders: Vec<(u64, RenderObject)>, // 后台生成中的网格：以包围盒线框占位 (任务 id, 线框)
 }
 
 impl State {
     async fn new(window: Arc<Window>) -> Self {
         let size = window.inner_size();
+        let scale_factor = window.scale_factor();
         let instance = wgpu::Instance::default();
         let surface = instance.create_surface(window.clone()).unwrap();
         let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions::default()).await.unwrap();
@@ -156,11 +251,15 @@ impl State {
         });
 
         // 1. Mesh Pipeline (实体，开启深度写入)
-        let mesh_pipeline = create_pipeline(&device, &pipeline_layout, &shader, config.format, wgpu::PrimitiveTopology::TriangleList, true, false);
+        let mesh_pipeline = create_pipeline(&device, &pipeline_layout, &shader, config.format, wgpu::PrimitiveTopology::TriangleList, true, false, false);
         // 2. Line Pipeline (线框)
-        let line_pipeline = create_pipeline(&device, &pipeline_layout, &shader, config.format, wgpu::PrimitiveTopology::LineList, true, false);
+        let line_pipeline = create_pipeline(&device, &pipeline_layout, &shader, config.format, wgpu::PrimitiveTopology::LineList, true, false, false);
         // 3. Transparent Pipeline (开启混合，不写入深度但进行测试)
-        let transparent_pipeline = create_pipeline(&device, &pipeline_layout, &shader, config.format, wgpu::PrimitiveTopology::TriangleList, false, true);
+        let transparent_pipeline = create_pipeline(&device, &pipeline_layout, &shader, config.format, wgpu::PrimitiveTopology::TriangleList, false, true, false);
+        // 4. 以上三种的实例化版本
+        let instanced_mesh_pipeline = create_pipeline(&device, &pipeline_layout, &shader, config.format, wgpu::PrimitiveTopology::TriangleList, true, false, true);
+        let instanced_line_pipeline = create_pipeline(&device, &pipeline_layout, &shader, config.format, wgpu::PrimitiveTopology::LineList, true, false, true);
+        let instanced_transparent_pipeline = create_pipeline(&device, &pipeline_layout, &shader, config.format, wgpu::PrimitiveTopology::TriangleList, false, true, true);
 
         // 深度纹理
         let (depth_texture, depth_view) = create_depth_texture(&device, &config);
@@ -168,12 +267,16 @@ impl State {
         let mut state = Self {
             window, surface, device, queue, config,
             mesh_pipeline, line_pipeline, transparent_pipeline,
+            instanced_mesh_pipeline, instanced_line_pipeline, instanced_transparent_pipeline,
             bind_group_layout,
             depth_texture, depth_view,
             camera: Camera::new(),
             mouse_pressed: None,
+            scale_factor,
+            last_frame_time: None,
             objects: Vec::new(),
             transparent_objects: Vec::new(),
+            placeholders: Vec::new(),
         };
 
         // --- ★ 初始化默认场景 (坐标轴和网格) ---
@@ -202,6 +305,81 @@ impl State {
 
     // 添加对象的方法 (内部使用)
     fn add_mesh(&mut self, mesh: MeshData, color: [f32; 4], use_lighting: bool, topology: wgpu::PrimitiveTopology, is_transparent: bool) {
+        let obj = self.create_render_object(mesh, color, use_lighting, topology);
+        self.push_object(obj, is_transparent);
+    }
+
+    fn push_object(&mut self, obj: RenderObject, is_transparent: bool) {
+        if is_transparent {
+            self.transparent_objects.push(obj);
+        } else {
+            self.objects.push(obj);
+        }
+    }
+
+    // 上传 D3Plotter 暂存的对象 (实例化对象额外创建实例缓冲)
+    fn add_geo_object(&mut self, id: usize, obj: GeoObjD3) {
+        let mut render_obj = self.create_render_object(obj.mesh, obj.color, obj.use_lighting, obj.topology);
+        render_obj.id = Some(id);
+        if let Some(transforms) = obj.instances {
+            let raw = pack_instances(&transforms, obj.instance_colors.as_deref(), obj.color);
+            let capacity = raw.len().max(1) as u32;
+            let buffer = create_instance_buffer(&self.device, capacity);
+            self.queue.write_buffer(&buffer, 0, bytemuck::cast_slice(&raw));
+            let mesh_aabb = render_obj.aabb;
+            render_obj.aabb = instances_aabb(mesh_aabb, &transforms);
+            render_obj.instances = Some(InstanceBuffer {
+                buffer, count: raw.len() as u32, capacity, colors: obj.instance_colors, mesh_aabb,
+            });
+        }
+        self.push_object(render_obj, obj.is_transparent);
+    }
+
+    // 重写实例缓冲 (容量不足时重建)，并更新用于剔除的包围盒；id 不存在或不是实例化对象时返回 false
+    fn set_instance_transforms(&mut self, id: usize, transforms: &[Matrix4x4]) -> bool {
+        let Some(obj) = self.objects.iter_mut().chain(self.transparent_objects.iter_mut()).find(|o| o.id == Some(id)) else {
+            return false;
+        };
+        let Some(inst) = obj.instances.as_mut() else { return false };
+
+        let raw = pack_instances(transforms, inst.colors.as_deref(), obj.color);
+        if raw.len() as u32 > inst.capacity {
+            inst.capacity = raw.len() as u32;
+            inst.buffer = create_instance_buffer(&self.device, inst.capacity);
+        }
+        self.queue.write_buffer(&inst.buffer, 0, bytemuck::cast_slice(&raw));
+        inst.count = raw.len() as u32;
+        obj.aabb = instances_aabb(inst.mesh_aabb, transforms);
+        true
+    }
+
+    // 在 duration 秒内把模型矩阵从当前值过渡到 target (打断进行中的动画)；id 不存在时返回 false
+    fn animate_model_matrix(&mut self, id: usize, target: Matrix4x4, duration: f64) -> bool {
+        let Some(obj) = self.objects.iter_mut().chain(self.transparent_objects.iter_mut()).find(|o| o.id == Some(id)) else {
+            return false;
+        };
+        if duration <= 0.0 {
+            obj.model_matrix = target;
+            obj.animation = None;
+        } else {
+            obj.animation = Some(TransformAnimation { from: obj.model_matrix, to: target, elapsed: 0.0, duration });
+        }
+        true
+    }
+
+    // 后台任务的占位线框 (半透明的任务颜色)
+    fn add_placeholder(&mut self, job: &PendingJob) {
+        let [r, g, b, _] = job.color;
+        let mesh = MeshData::new_box_wireframe(job.bounds.0, job.bounds.1);
+        let obj = self.create_render_object(mesh, [r, g, b, 0.5], false, wgpu::PrimitiveTopology::LineList);
+        self.placeholders.push((job.id, obj));
+    }
+
+    fn remove_placeholder(&mut self, id: u64) {
+        self.placeholders.retain(|(job_id, _)| *job_id != id);
+    }
+
+    fn create_render_object(&self, mesh: MeshData, color: [f32; 4], use_lighting: bool, topology: wgpu::PrimitiveTopology) -> RenderObject {
         let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("VB"), contents: bytemuck::cast_slice(&mesh.vertices), usage: wgpu::BufferUsages::VERTEX,
         });
@@ -214,7 +392,7 @@ impl State {
 
         let uniforms = Uniforms {
             view_proj: Mat4::IDENTITY.to_cols_array(), // 占位，update时更新
-            model: mat4_to_raw_f32(model_matrix),      // ★ 转换
+            model: mat4_to_cols_f32(model_matrix),      // ★ 转换
             camera_pos: [0.0; 3],
             _pad: 0.0,
             base_color: color,
@@ -231,15 +409,13 @@ impl State {
             label: Some("BG"), layout: &self.bind_group_layout, entries: &[wgpu::BindGroupEntry { binding: 0, resource: uniform_buffer.as_entire_binding() }],
         });
 
-        let obj = RenderObject {
+        RenderObject {
             vertex_buffer, index_buffer, num_indices: mesh.indices.len() as u32,
-            uniform_buffer, bind_group, color, use_lighting, model_matrix, topology
-        };
-
-        if is_transparent {
-            self.transparent_objects.push(obj);
-        } else {
-            self.objects.push(obj);
+            uniform_buffer, bind_group, color, use_lighting, model_matrix, topology,
+            aabb: mesh.compute_aabb(),
+            id: None,
+            instances: None,
+            animation: None,
         }
     }
 
@@ -253,22 +429,43 @@ impl State {
         }
     }
 
+    // 推进相机与对象的动画；动画进行中持续请求重绘
+    fn tick_animations(&mut self) {
+        let now = Instant::now();
+        let dt = self.last_frame_time.map_or(0.0, |t| now.duration_since(t).as_secs_f64());
+        let mut animating = self.camera.tick(dt);
+        for obj in self.objects.iter_mut().chain(self.transparent_objects.iter_mut()) {
+            let Some(anim) = obj.animation.as_mut() else { continue };
+            let (matrix, done) = anim.advance(dt);
+            obj.model_matrix = matrix;
+            if done {
+                obj.animation = None;
+            } else {
+                animating = true;
+            }
+        }
+        if animating {
+            self.last_frame_time = Some(now);
+            self.window.request_redraw();
+        } else {
+            self.last_frame_time = None;
+        }
+    }
+
     fn update(&mut self) {
         let aspect = self.config.width as f32 / self.config.height as f32;
 
         // Camera 返回的是 glam::Mat4 (已经针对 GPU 做过转置处理)，直接转数组
         let vp = self.camera.build_view_projection_matrix(aspect).to_cols_array();
 
-        // MathForest::Vec3 -> [f32; 3]
-        let cam_pos_f64 = self.camera.get_eye_position();
-        let cam_pos = [cam_pos_f64.x as f32, cam_pos_f64.y as f32, cam_pos_f64.z as f32];
+        let cam_pos = self.camera.get_eye_position().to_f32_array();
 
         // 更新所有对象 Uniform
         let update_obj = |obj: &RenderObject| {
             let u = Uniforms {
                 view_proj: vp,
                 // ★ MathForest Matrix4x4 (Row-Major) -> GPU (Col-Major f32)
-                model: mat4_to_raw_f32(obj.model_matrix),
+                model: mat4_to_cols_f32(obj.model_matrix),
                 camera_pos: cam_pos,
                 _pad: 0.0,
                 base_color: obj.color,
@@ -281,9 +478,17 @@ impl State {
 
         for obj in &self.objects { update_obj(obj); }
         for obj in &self.transparent_objects { update_obj(obj); }
+        for (_, obj) in &self.placeholders { update_obj(obj); }
     }
 
     fn render(&mut self) {
+        let aspect = self.config.width as f32 / self.config.height as f32;
+        let view_proj = self.camera.view_projection(aspect);
+        // 包围盒完全在视锥外的对象不提交绘制 (AABB 在模型空间，平面取自 VP * Model)
+        let visible = |obj: &RenderObject| {
+            !Frustum::from_matrix(&(view_proj * obj.model_matrix)).is_aabb_outside(obj.aabb.0, obj.aabb.1)
+        };
+
         let output = match self.surface.get_current_texture() { Ok(tex) => tex, Err(_) => return };
         let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
         let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
@@ -305,21 +510,27 @@ impl State {
             });
 
             // 1. 绘制不透明物体
-            for obj in &self.objects {
-                self.draw_obj(&mut rp, obj, &self.mesh_pipeline, &self.line_pipeline);
+            for obj in self.objects.iter().chain(self.placeholders.iter().map(|(_, o)| o)).filter(|o| visible(o)) {
+                self.draw_obj(&mut rp, obj, false);
             }
 
             // 2. 绘制半透明物体
-            rp.set_pipeline(&self.transparent_pipeline);
-            for obj in &self.transparent_objects {
-                self.draw_obj(&mut rp, obj, &self.transparent_pipeline, &self.line_pipeline); // 半透明通常是 Mesh
+            for obj in self.transparent_objects.iter().filter(|o| visible(o)) {
+                self.draw_obj(&mut rp, obj, true); // 半透明通常是 Mesh
             }
         }
         self.queue.submit(std::iter::once(encoder.finish()));
         output.present();
     }
 
-    fn draw_obj<'a>(&'a self, rp: &mut wgpu::RenderPass<'a>, obj: &'a RenderObject, mesh_p: &'a wgpu::RenderPipeline, line_p: &'a wgpu::RenderPipeline) {
+    fn draw_obj<'a>(&'a self, rp: &mut wgpu::RenderPass<'a>, obj: &'a RenderObject, transparent: bool) {
+        let instanced = obj.instances.is_some();
+        let (mesh_p, line_p) = match (instanced, transparent) {
+            (false, false) => (&self.mesh_pipeline, &self.line_pipeline),
+            (false, true) => (&self.transparent_pipeline, &self.line_pipeline),
+            (true, false) => (&self.instanced_mesh_pipeline, &self.instanced_line_pipeline),
+            (true, true) => (&self.instanced_transparent_pipeline, &self.instanced_line_pipeline),
+        };
         match obj.topology {
             wgpu::PrimitiveTopology::TriangleList => rp.set_pipeline(mesh_p),
             wgpu::PrimitiveTopology::LineList => rp.set_pipeline(line_p),
@@ -328,7 +539,14 @@ impl State {
         rp.set_bind_group(0, &obj.bind_group, &[]);
         rp.set_vertex_buffer(0, obj.vertex_buffer.slice(..));
         rp.set_index_buffer(obj.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-        rp.draw_indexed(0..obj.num_indices, 0, 0..1);
+        match &obj.instances {
+            Some(inst) if inst.count > 0 => {
+                rp.set_vertex_buffer(1, inst.buffer.slice(..));
+                rp.draw_indexed(0..obj.num_indices, 0, 0..inst.count);
+            }
+            Some(_) => {}
+            None => rp.draw_indexed(0..obj.num_indices, 0, 0..1),
+        }
     }
 }
 
@@ -336,38 +554,64 @@ impl State {
 // ★ 辅助函数
 // ==========================================
 
-// 将 MathForest::Matrix4x4 (f64, Row-Major) 转换为 WGPU 所需的 (f32, Col-Major)
-fn mat4_to_raw_f32(m: Matrix4x4) -> [f32; 16] {
-    // 关键：WGPU/OpenGL 期望列优先矩阵。
-    // MathForest 是行优先的。
-    // 行优先数据的 [Row0, Row1...] 如果直接当作列优先数据读取，相当于转置。
-    // 但是 GPU 上的矩阵乘法通常是 Mat * Vec (标准数学写法)。
-    // 如果我们把 CPU 的 Row0 传给 GPU 的 Col0，那么 GPU 上的矩阵就是 CPU 矩阵的转置。
-    // 为了让 GPU 矩阵 == CPU 矩阵，我们需要在 CPU 端先转置一次（变成列优先存储），
-    // 然后传给 GPU。
-
-    let t = m.transpose(); // 转置以获得列优先的数据布局
-    [
-        t.m[0] as f32, t.m[1] as f32, t.m[2] as f32, t.m[3] as f32,
-        t.m[4] as f32, t.m[5] as f32, t.m[6] as f32, t.m[7] as f32,
-        t.m[8] as f32, t.m[9] as f32, t.m[10] as f32, t.m[11] as f32,
-        t.m[12] as f32, t.m[13] as f32, t.m[14] as f32, t.m[15] as f32,
-    ]
+fn create_instance_buffer(device: &wgpu::Device, capacity: u32) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Instances"),
+        size: capacity as u64 * size_of::<InstanceRaw>() as u64,
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
 }
 
+// 实例数据打包：transforms[i] 与 colors[i] 一一对应，颜色缺失时用 base_color
+fn pack_instances(transforms: &[Matrix4x4], colors: Option<&[[f32; 4]]>, base_color: [f32; 4]) -> Vec<InstanceRaw> {
+    transforms.iter().enumerate().map(|(i, m)| InstanceRaw {
+        model: mat4_to_cols_f32(*m),
+        color: colors.and_then(|c| c.get(i)).copied().unwrap_or(base_color),
+    }).collect()
+}
+
+// 所有实例包围盒的并 (各实例变换网格包围盒的 8 个角点)
+fn instances_aabb(mesh_aabb: (Vec3, Vec3), transforms: &[Matrix4x4]) -> (Vec3, Vec3) {
+    let (lo, hi) = mesh_aabb;
+    let mut min = Vec3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+    let mut max = Vec3::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for m in transforms {
+        for i in 0..8 {
+            let corner = Vec3::new(
+                if i & 1 == 0 { lo.x } else { hi.x },
+                if i & 2 == 0 { lo.y } else { hi.y },
+                if i & 4 == 0 { lo.z } else { hi.z },
+            );
+            let p = m.transform_point3(corner);
+            min = Vec3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+            max = Vec3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+        }
+    }
+    (min, max)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn create_pipeline(
     device: &wgpu::Device, layout: &wgpu::PipelineLayout, shader: &wgpu::ShaderModule,
-    fmt: wgpu::TextureFormat, topology: wgpu::PrimitiveTopology, depth_write: bool, blend: bool
+    fmt: wgpu::TextureFormat, topology: wgpu::PrimitiveTopology, depth_write: bool, blend: bool, instanced: bool
 ) -> wgpu::RenderPipeline {
+    let vertex_layout = wgpu::VertexBufferLayout {
+        array_stride: size_of::<Vertex3D>() as wgpu::BufferAddress,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3],
+    };
+    let instance_layout = wgpu::VertexBufferLayout {
+        array_stride: size_of::<InstanceRaw>() as wgpu::BufferAddress,
+        step_mode: wgpu::VertexStepMode::Instance,
+        attributes: &INSTANCE_ATTRIBUTES,
+    };
+    let buffers = [vertex_layout, instance_layout];
     device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
         label: None, layout: Some(layout),
         vertex: wgpu::VertexState {
-            module: shader, entry_point: Some("vs_main"),
-            buffers: &[wgpu::VertexBufferLayout {
-                array_stride: size_of::<Vertex3D>() as wgpu::BufferAddress,
-                step_mode: wgpu::VertexStepMode::Vertex,
-                attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3],
-            }],
+            module: shader, entry_point: Some(if instanced { "vs_instanced" } else { "vs_main" }),
+            buffers: if instanced { &buffers } else { &buffers[..1] },
             compilation_options: Default::default(),
         },
         fragment: Some(wgpu::FragmentState {
@@ -403,12 +647,29 @@ fn create_depth_texture(device: &wgpu::Device, config: &wgpu::SurfaceConfigurati
     (tex, view)
 }
 
+// 转台模式默认转速 (弧度/秒)
+const TURNTABLE_SPEED: f64 = 0.5;
+// 有后台任务时检查进度的间隔
+const JOB_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const WINDOW_TITLE: &str = "MathForest - 3D";
+
 // ==========================================
 // ★ 3. AppD3 主入口
 // ==========================================
 pub struct D3Plotter {
     pub state: Option<State>,
-    pub pending_objects: Vec<GeoObjD3>,
+    // 窗口打开前添加的对象 (id, 对象)
+    pub pending_objects: Vec<(usize, GeoObjD3)>,
+    // 窗口打开前请求的模型矩阵动画 (id, 目标, 时长)，窗口打开时开始播放
+    pending_animations: Vec<(usize, Matrix4x4, f64)>,
+    // 窗口打开前设置的视角 (restore_view)
+    pending_camera: Option<CameraSnapshot>,
+    // 会话文件：关闭窗口时把相机写入 (--session)
+    session_path: Option<PathBuf>,
+    next_object_id: usize,
+    // 后台生成中的网格 (丢弃时取消并等待线程结束)
+    jobs: Vec<PendingJob>,
+    next_job_id: u64,
 }
 
 impl D3Plotter {
@@ -416,40 +677,214 @@ impl D3Plotter {
         Self {
             state: None,
             pending_objects: Vec::new(),
+            pending_animations: Vec::new(),
+            pending_camera: None,
+            session_path: None,
+            next_object_id: 0,
+            jobs: Vec::new(),
+            next_job_id: 0,
         }
     }
 
-    // ★ 对外接口：添加 3D 对象
-    pub fn add_object(&mut self, obj: GeoObjD3) {
-        self.pending_objects.push(obj);
+    // ★ 对外接口：添加 3D 对象，返回对象 id
+    pub fn add_object(&mut self, obj: GeoObjD3) -> usize {
+        let id = self.next_object_id;
+        self.next_object_id += 1;
+        self.pending_objects.push((id, obj));
+        id
+    }
+
+    // 整体替换实例化对象的实例变换 (实例颜色按下标保留)
+    pub fn set_instance_transforms(&mut self, id: usize, transforms: &[Matrix4x4]) {
+        if let Some(state) = self.state.as_mut() {
+            if state.set_instance_transforms(id, transforms) {
+                state.window.request_redraw();
+            }
+        } else if let Some((_, obj)) = self.pending_objects.iter_mut().find(|(i, _)| *i == id)
+            && obj.instances.is_some()
+        {
+            obj.instances = Some(transforms.to_vec());
+        }
+    }
+
+    // 把对象的模型矩阵平滑过渡到 target (刚体变换之间按旋转 slerp + 平移插值)
+    pub fn animate_model_matrix(&mut self, id: usize, target: Matrix4x4, duration: f64) {
+        if let Some(state) = self.state.as_mut() {
+            if state.animate_model_matrix(id, target, duration) {
+                state.window.request_redraw();
+            }
+        } else {
+            self.pending_animations.push((id, target, duration));
+        }
+    }
+
+    pub fn view_state(&self) -> CameraSnapshot {
+        match &self.state {
+            Some(state) => state.camera.snapshot(),
+            None => self.pending_camera.unwrap_or_else(|| Camera::new().snapshot()),
+        }
+    }
+
+    // 跳到快照的视角；窗口未打开时在打开后生效
+    pub fn restore_view(&mut self, snapshot: CameraSnapshot) {
+        if let Some(state) = self.state.as_mut() {
+            state.camera.restore(snapshot);
+            state.window.request_redraw();
+        } else {
+            self.pending_camera = Some(snapshot);
+        }
+    }
+
+    // 相机的编程接口 (look_at / set_camera_radius / frame_aabb)：窗口未打开时改写待恢复的视角
+    fn update_camera(&mut self, f: impl FnOnce(&mut Camera)) {
+        if let Some(state) = self.state.as_mut() {
+            f(&mut state.camera);
+            state.window.request_redraw();
+        } else {
+            let mut camera = Camera::new();
+            camera.restore(self.view_state());
+            f(&mut camera);
+            self.pending_camera = Some(camera.snapshot());
+        }
+    }
+
+    // 注视点移到 target，朝向与距离不变
+    pub fn look_at(&mut self, target: Vec3) {
+        self.update_camera(|c| c.look_at(target));
+    }
+
+    pub fn set_camera_radius(&mut self, r: f64) {
+        self.update_camera(|c| c.set_radius(r));
+    }
+
+    // 让包围盒 (min, max) 整个落在视野内，如 frame_aabb(mesh.compute_aabb())
+    pub fn frame_aabb(&mut self, aabb: (Vec3, Vec3)) {
+        self.update_camera(|c| c.frame_aabb(aabb));
+    }
+
+    // 关闭窗口时把相机写入 path (文件中的 2D 视图与对象保留)
+    pub fn set_session_path(&mut self, path: PathBuf) {
+        self.session_path = Some(path);
+    }
+
+    // 在后台线程生成网格，立即返回；窗口打开前即开始计算，完成前以包围盒线框占位
+    pub fn add_object_async(&mut self, spec: MeshJob) {
+        let job = PendingJob::spawn(self.next_job_id, spec);
+        self.next_job_id += 1;
+        if let Some(state) = self.state.as_mut() {
+            state.add_placeholder(&job);
+        }
+        self.jobs.push(job);
+    }
+
+    // 收取已完成的任务并上传；返回是否有变化 (需要重绘)
+    fn poll_jobs(&mut self) -> bool {
+        let Some(state) = self.state.as_mut() else { return false };
+        let mut changed = false;
+        self.jobs.retain_mut(|job| {
+            let before = job.progress();
+            match job.poll() {
+                JobStatus::Running(progress) => {
+                    changed |= progress != before;
+                    true
+                }
+                JobStatus::Done(mesh) => {
+                    state.remove_placeholder(job.id);
+                    state.add_mesh(mesh, job.color, true, wgpu::PrimitiveTopology::TriangleList, false);
+                    changed = true;
+                    false
+                }
+                JobStatus::Failed => {
+                    eprintln!("[d3] mesh job {} failed", job.id);
+                    state.remove_placeholder(job.id);
+                    changed = true;
+                    false
+                }
+            }
+        });
+
+        if changed {
+            // 标题栏显示总体进度
+            let title = if self.jobs.is_empty() {
+                WINDOW_TITLE.to_string()
+            } else {
+                let mean = self.jobs.iter().map(|j| j.progress()).sum::<f64>() / self.jobs.len() as f64;
+                format!("{} (meshing {} object(s), {:.0}%)", WINDOW_TITLE, self.jobs.len(), mean * 100.0)
+            };
+            state.window.set_title(&title);
+        }
+        changed
     }
 }
 
 impl ApplicationHandler for D3Plotter {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        let window = Arc::new(event_loop.create_window(Window::default_attributes().with_title("MathForest - 3D")).unwrap());
+        let window = Arc::new(event_loop.create_window(Window::default_attributes().with_title(WINDOW_TITLE)).unwrap());
         let mut state = pollster::block_on(State::new(window));
 
         // --- ★ 将暂存的对象上传到 GPU ---
-        for obj in self.pending_objects.drain(..) {
-            state.add_mesh(
-                obj.mesh,
-                obj.color,
-                obj.use_lighting,
-                obj.topology,
-                obj.is_transparent
-            );
+        for (id, obj) in self.pending_objects.drain(..) {
+            state.add_geo_object(id, obj);
+        }
+        if let Some(camera) = self.pending_camera.take() {
+            state.camera.restore(camera);
+        }
+        for (id, target, duration) in self.pending_animations.drain(..) {
+            state.animate_model_matrix(id, target, duration);
+        }
+        for job in &self.jobs {
+            state.add_placeholder(job);
         }
 
         self.state = Some(state);
     }
 
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        if self.poll_jobs() && let Some(state) = &self.state {
+            state.window.request_redraw();
+        }
+        if self.jobs.is_empty() {
+            event_loop.set_control_flow(ControlFlow::Wait);
+        } else {
+            event_loop.set_control_flow(ControlFlow::WaitUntil(Instant::now() + JOB_POLL_INTERVAL));
+        }
+    }
+
+    // 窗口关闭：取消未完成的任务并等待线程退出
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        self.jobs.clear();
+    }
+
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
         if let Some(state) = self.state.as_mut() {
             match event {
-                WindowEvent::CloseRequested => event_loop.exit(),
+                WindowEvent::CloseRequested => {
+                    if let Some(path) = &self.session_path {
+                        let camera = self.view_state();
+                        if let Err(e) = Session::update_file(path, |s| s.camera = Some(camera)) {
+                            eprintln!("[session] {}: {}, not saved", path.display(), e);
+                        }
+                    }
+                    event_loop.exit();
+                }
                 WindowEvent::Resized(size) => state.resize(size),
-                WindowEvent::RedrawRequested => { state.update(); state.render(); }
+                WindowEvent::ScaleFactorChanged { scale_factor, .. } => state.scale_factor = scale_factor,
+                WindowEvent::RedrawRequested => { state.tick_animations(); state.update(); state.render(); }
+                // T: 转台开关  R: 平滑回到默认视角
+                WindowEvent::KeyboardInput { event, .. } if event.state == ElementState::Pressed && !event.repeat => {
+                    match event.logical_key.as_ref() {
+                        Key::Character("t") | Key::Character("T") => {
+                            let speed = if state.camera.is_turntable() { 0.0 } else { TURNTABLE_SPEED };
+                            state.camera.turntable(speed);
+                        }
+                        Key::Character("r") | Key::Character("R") => {
+                            let home = Camera::new();
+                            state.camera.animate_to(home.target, home.yaw, home.pitch, home.radius, 1.0);
+                        }
+                        _ => return,
+                    }
+                    state.window.request_redraw();
+                }
                 WindowEvent::MouseInput { state: mstate, button, .. } => {
                     state.mouse_pressed = if mstate == ElementState::Pressed { Some(button) } else { None };
                 }
@@ -466,10 +901,112 @@ impl ApplicationHandler for D3Plotter {
         if let Some(state) = self.state.as_mut() {
             if let DeviceEvent::MouseMotion { delta } = event {
                 if let Some(btn) = state.mouse_pressed {
-                    state.camera.process_mouse_drag(delta.0, delta.1, btn);
+                    let k = state.scale_factor;
+                    state.camera.process_mouse_drag(delta.0 / k, delta.1 / k, btn);
                     state.window.request_redraw();
                 }
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 按 WGSL 约定读取列优先数组 (m[col * 4 + row])，计算 M * v
+    fn mul_col_major(m: &[f32; 16], v: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        for (row, o) in out.iter_mut().enumerate() {
+            *o = (0..4).map(|col| m[col * 4 + row] * v[col]).sum();
+        }
+        out
+    }
+
+    #[test]
+    fn test_instance_packing_matches_uniform_path() {
+        let m = Matrix4x4::from_scale_rotation_translation(
+            Vec3::new(0.5, 2.0, 1.0), Vec3::new(1.0, 1.0, 0.0), 0.7, Vec3::new(3.0, -1.0, 2.0),
+        );
+        let color = [0.1, 0.2, 0.3, 1.0];
+
+        // 单对象路径：模型矩阵放在 Uniforms 中
+        let single = Uniforms {
+            view_proj: Mat4::IDENTITY.to_cols_array(),
+            model: mat4_to_cols_f32(m),
+            camera_pos: [0.0; 3],
+            _pad: 0.0,
+            base_color: color,
+            use_lighting: 1.0,
+            _pad2: [0.0; 3],
+            _pad3: [0.0; 4],
+        };
+        // 实例路径：Uniforms::model 为单位阵，变换放在实例缓冲
+        let uniform_model = mat4_to_cols_f32(Matrix4x4::IDENTITY);
+        let inst = pack_instances(&[m], None, color);
+        assert_eq!(inst[0].model, single.model);
+        assert_eq!(inst[0].color, single.base_color);
+        // 平移在第 4 列 (location 5)
+        assert_eq!(&inst[0].model[12..15], &[3.0, -1.0, 2.0]);
+
+        // 着色器中 u.model * instance_model * p 与单对象路径 u.model * p 一致，也与 CPU 端一致
+        let p = Vec3::new(0.3, -1.2, 2.5);
+        let v = [p.x as f32, p.y as f32, p.z as f32, 1.0];
+        let a = mul_col_major(&single.model, v);
+        let b = mul_col_major(&uniform_model, mul_col_major(&inst[0].model, v));
+        let e = m.transform_point3(p);
+        for (k, ek) in [e.x, e.y, e.z, 1.0].into_iter().enumerate() {
+            assert!((a[k] - b[k]).abs() < 1e-6);
+            assert!((a[k] as f64 - ek).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_instance_layout_and_colors() {
+        // 4 列矩阵 + 颜色，各 16 字节
+        assert_eq!(size_of::<InstanceRaw>(), 80);
+        let offsets: Vec<u64> = INSTANCE_ATTRIBUTES.iter().map(|a| a.offset).collect();
+        assert_eq!(offsets, [0, 16, 32, 48, 64]);
+        let locations: Vec<u32> = INSTANCE_ATTRIBUTES.iter().map(|a| a.shader_location).collect();
+        assert_eq!(locations, [2, 3, 4, 5, 6]);
+        assert!(INSTANCE_ATTRIBUTES.iter().all(|a| a.format == wgpu::VertexFormat::Float32x4));
+
+        // 颜色按下标对应，缺失的用对象颜色
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let base = [0.5, 0.5, 0.5, 1.0];
+        let ts = [Matrix4x4::IDENTITY, Matrix4x4::from_translation(Vec3::new(1.0, 0.0, 0.0))];
+        let raw = pack_instances(&ts, Some(&[red]), base);
+        assert_eq!((raw[0].color, raw[1].color), (red, base));
+    }
+
+    #[test]
+    fn test_instances_aabb_union() {
+        let unit = (Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+        let ts = [
+            Matrix4x4::from_translation(Vec3::new(5.0, 0.0, 0.0)),
+            Matrix4x4::from_translation(Vec3::new(0.0, -3.0, 0.0)) * Matrix4x4::from_scale(Vec3::new(2.0, 2.0, 2.0)),
+        ];
+        let (min, max) = instances_aabb(unit, &ts);
+        assert!(min.dis(Vec3::new(-2.0, -5.0, -2.0)) < 1e-12);
+        assert!(max.dis(Vec3::new(6.0, 1.0, 2.0)) < 1e-12);
+        // 没有实例：空包围盒
+        assert!(instances_aabb(unit, &[]).0.x.is_infinite());
+    }
+
+    #[test]
+    fn test_transform_animation() {
+        let to = Matrix4x4::from_scale_rotation_translation(Vec3::ONE, Vec3::K, 2.0, Vec3::new(3.0, 0.0, 0.0));
+        let mut anim = TransformAnimation { from: Matrix4x4::IDENTITY, to, elapsed: 0.0, duration: 2.0 };
+
+        // 中途：smoothstep(0.5) = 0.5，刚体变换保持正交
+        let (m, done) = anim.advance(1.0);
+        assert!(!done);
+        let (r, t) = (m.transform_vector3(Vec3::I), m.transform_point3(Vec3::ZERO));
+        assert!((r - Vec3::new(1.0f64.cos(), 1.0f64.sin(), 0.0)).len() < 1e-12);
+        assert!((t - Vec3::new(1.5, 0.0, 0.0)).len() < 1e-12);
+
+        // 结束：精确落在目标
+        let (m, done) = anim.advance(1.5);
+        assert!(done);
+        assert_eq!(m, to);
+    }
+}
@@ -3,9 +3,25 @@
 
 // ★ 引入 MathForest Vec3
 use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
 // 引用同模块下的 mesh
+use crate::math_forest::geometry::d3::curve::curve_eval::FrenetData;
 use super::mesh::{MeshData, Vertex3D};
 
+// 管子截面的局部坐标轴
+struct Frame {
+    pos: Vec3,
+    normal: Vec3,   // 管子截面的局部 X 轴
+    binormal: Vec3, // 管子截面的局部 Y 轴
+}
+
+/// 截面标架的取法 (见 solve_with_frames)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TubeFrame {
+    Frenet,
+    ParallelTransport,
+}
+
 pub struct ParametricCurveSolver;
 
 impl ParametricCurveSolver {
@@ -24,20 +40,10 @@ impl ParametricCurveSolver {
     where
         F: Fn(f64) -> Vec3, // 返回 MathForest Vec3
     {
-        let mut vertices = Vec::new();
-        let mut indices = Vec::new();
-
         let (t_min, t_max) = t_range;
         let t_step = (t_max - t_min) / path_segments as f64;
 
         // 1. 计算路径骨架点 (P) 和 标架 (Frenet Frame 或类似)
-        struct Frame {
-            pos: Vec3,
-            // tangent: Vec3, // 暂时没用到，如果是平行输运需要用到
-            normal: Vec3,   // 管子截面的局部 X 轴
-            binormal: Vec3, // 管子截面的局部 Y 轴
-        }
-
         let mut frames = Vec::with_capacity((path_segments + 1) as usize);
 
         for i in 0..=path_segments {
@@ -69,10 +75,72 @@ impl ParametricCurveSolver {
             frames.push(Frame { pos, normal, binormal });
         }
 
-        // 2. 生成管壁顶点
-        for i in 0..=path_segments {
-            let frame = &frames[i as usize];
+        Self::build_tube(&frames, radius, tube_segments)
+    }
+
+    /// (p, q) 环面结的管状体：绕环面中心轴 p 圈、绕管 q 圈
+    /// x = (R + r·cos(qt))·cos(pt), y = (R + r·cos(qt))·sin(pt), z = r·sin(qt), t ∈ [0, 2π]
+    /// crossings: 即 p；gcd(p, q) = 1 时是真正的结 (如 (2, 3) 三叶结)，否则曲线会重复走 gcd 遍
+    /// major_radius / minor_radius: 环面的 R / r，radius: 管子半径
+    pub fn solve_knot(
+        crossings: u32,
+        q: u32,
+        major_radius: f64,
+        minor_radius: f64,
+        radius: f64,
+        tube_segments: u32,
+        path_segments: u32,
+    ) -> MeshData {
+        assert!(crossings > 0 && q > 0, "torus knot: p and q must be positive");
+        let (p, q) = (crossings as f64, q as f64);
+        Self::solve(
+            |t| {
+                let ring = major_radius + minor_radius * (q * t).cos();
+                Vec3::new(ring * (p * t).cos(), ring * (p * t).sin(), minor_radius * (q * t).sin())
+            },
+            (0.0, std::f64::consts::TAU),
+            radius,
+            tube_segments,
+            path_segments,
+        )
+    }
+
+    /// 用预先算好的 Frenet 数据 (见 curve_eval::sample_range) 生成管状体
+    /// frame 选择截面的朝向：
+    ///   Frenet: 直接用 N / B，截面随曲线扭转 (可视化挠率)
+    ///   ParallelTransport: 以首点的 N 为起点沿切线平行输运，截面不自转，拐点处也不翻转
+    pub fn solve_with_frames(frames: &[FrenetData], radius: f64, tube_segments: u32, frame: TubeFrame) -> MeshData {
+        let rings: Vec<Frame> = match frame {
+            TubeFrame::Frenet => frames.iter()
+                .map(|d| Frame { pos: d.pos, normal: d.normal, binormal: d.binormal })
+                .collect(),
+            TubeFrame::ParallelTransport => {
+                let mut rings: Vec<Frame> = Vec::with_capacity(frames.len());
+                for d in frames {
+                    // 上一个标架对当前切线做 Gram-Schmidt：法向投影到当前法平面，
+                    // 同时把沿路径累积的浮点误差重新正交化
+                    let transported = rings.last()
+                        .and_then(|prev| Matrix3x3::from_cols(d.tangent, prev.normal, prev.binormal).gram_schmidt());
+                    let (normal, binormal) = match transported {
+                        Some(m) => (m.col(1), m.col(2)),
+                        None => (d.normal, d.tangent.cross(d.normal)),
+                    };
+                    rings.push(Frame { pos: d.pos, normal, binormal });
+                }
+                rings
+            }
+        };
+        Self::build_tube(&rings, radius, tube_segments)
+    }
+
+    // 沿各截面标架生成管壁网格 (相邻圆环两两相连)
+    fn build_tube(frames: &[Frame], radius: f64, tube_segments: u32) -> MeshData {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let path_segments = frames.len().saturating_sub(1) as u32;
 
+        // 2. 生成管壁顶点
+        for frame in frames {
             for j in 0..=tube_segments {
                 // j 和 tube_segments 重合时闭合圆环 (0 和 2PI)
                 let theta = (j as f64 / tube_segments as f64) * std::f64::consts::TAU;
@@ -88,8 +156,8 @@ impl ParametricCurveSolver {
 
                 // [类型转换] f64 -> f32 存入 VertexBuffer
                 vertices.push(Vertex3D {
-                    position: [position.x as f32, position.y as f32, position.z as f32],
-                    normal:   [normal.x as f32, normal.y as f32, normal.z as f32],
+                    position: position.to_f32_array(),
+                    normal:   normal.to_f32_array(),
                 });
             }
         }
@@ -116,4 +184,57 @@ impl ParametricCurveSolver {
 
         MeshData { vertices, indices }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_forest::geometry::d3::curve::curve_eval;
+
+    #[test]
+    fn test_solve_with_frames() {
+        let helix = |t: f64| Vec3::new(t.cos(), t.sin(), 0.5 * t);
+        let frames = curve_eval::sample_range(&helix, (0.0, 4.0), 20);
+
+        for frame in [TubeFrame::Frenet, TubeFrame::ParallelTransport] {
+            let mesh = ParametricCurveSolver::solve_with_frames(&frames, 0.2, 8, frame);
+            assert_eq!(mesh.vertices.len(), 21 * 9);
+            assert_eq!(mesh.indices.len(), 20 * 8 * 6);
+
+            // 每个圆环上的点到骨架点距离为半径，且位于法平面内
+            for (i, d) in frames.iter().enumerate() {
+                for v in &mesh.vertices[i * 9..(i + 1) * 9] {
+                    let p = Vec3::new(v.position[0] as f64, v.position[1] as f64, v.position[2] as f64);
+                    assert!(((p - d.pos).len() - 0.2).abs() < 1e-5);
+                    assert!((p - d.pos).dot(d.tangent).abs() < 1e-5);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_knot() {
+        let mesh = ParametricCurveSolver::solve_knot(2, 3, 2.0, 1.0, 0.1, 6, 120);
+        assert_eq!(mesh.vertices.len(), 121 * 7);
+        assert_eq!(mesh.indices.len(), 120 * 6 * 6);
+
+        // 管壁上的点离环面中心圆 (半径 R) 的距离都在 r ± 管半径以内
+        for v in &mesh.vertices {
+            let (x, y, z) = (v.position[0] as f64, v.position[1] as f64, v.position[2] as f64);
+            let d = ((x.hypot(y) - 2.0).powi(2) + z * z).sqrt();
+            assert!((d - 1.0).abs() <= 0.1 + 1e-5);
+        }
+
+        // 首尾圆环重合 (t = 0 与 t = 2π 是同一点)
+        let (first, last) = (&mesh.vertices[0], &mesh.vertices[120 * 7]);
+        for k in 0..3 {
+            assert!((first.position[k] - last.position[k]).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "torus knot: p and q must be positive")]
+    fn test_solve_knot_zero() {
+        ParametricCurveSolver::solve_knot(0, 3, 2.0, 1.0, 0.1, 6, 120);
+    }
+}
@@ -0,0 +1,361 @@
+// src/d3/sdf.rs
+// 网格 -> 有符号距离场 (SDF)：可重新提取等值面 (换分辨率)、偏移 (膨胀 / 腐蚀)、与其他隐函数做布尔运算
+//   距离：点到三角形的最近距离，三角形按均匀网格分桶加速
+//   符号：沿 +x 方向的射线奇偶性 (每行采样点共用一条射线)，内部为负
+//   限制：要求网格封闭 (允许接缝处顶点不焊接)；开放网格上穿过破洞的行符号会错乱
+#![allow(dead_code)]
+
+use rayon::prelude::*;
+
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+
+use super::implicit_surface::ImplicitSurfaceSolver;
+use super::mesh::MeshData;
+
+// 规则采样的有符号距离场：(resolution + 1)³ 个采样点，x 变化最快 (与 ImplicitSurfaceSolver 一致)
+#[derive(Clone, Debug)]
+pub struct SdfGrid {
+    pub min: Vec3,
+    pub max: Vec3,
+    pub resolution: u32,
+    pub values: Vec<f64>,
+}
+
+impl SdfGrid {
+    // 采样间距
+    pub fn step(&self) -> Vec3 {
+        (self.max - self.min) / self.resolution as f64
+    }
+
+    // 采样点 (i, j, k) 的值
+    pub fn sample(&self, i: usize, j: usize, k: usize) -> f64 {
+        let n = self.resolution as usize + 1;
+        self.values[(k * n + j) * n + i]
+    }
+
+    // 三线性插值；范围外的点先夹到边界上，再加上到边界的距离 (距离的上界)
+    pub fn value_at(&self, p: Vec3) -> f64 {
+        let q = Vec3::new(
+            p.x.clamp(self.min.x, self.max.x),
+            p.y.clamp(self.min.y, self.max.y),
+            p.z.clamp(self.min.z, self.max.z),
+        );
+        let step = self.step();
+        let last = self.resolution as usize - 1;
+        // 格子下标与格内偏移 (0 ~ 1)
+        let cell = |v: f64, lo: f64, h: f64| {
+            let t = (v - lo) / h;
+            let i = (t.floor().max(0.0) as usize).min(last);
+            (i, t - i as f64)
+        };
+        let (i, fx) = cell(q.x, self.min.x, step.x);
+        let (j, fy) = cell(q.y, self.min.y, step.y);
+        let (k, fz) = cell(q.z, self.min.z, step.z);
+
+        let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+        let plane = |k: usize| {
+            let y0 = lerp(self.sample(i, j, k), self.sample(i + 1, j, k), fx);
+            let y1 = lerp(self.sample(i, j + 1, k), self.sample(i + 1, j + 1, k), fx);
+            lerp(y0, y1, fy)
+        };
+        lerp(plane(k), plane(k + 1), fz) + (p - q).len()
+    }
+
+    // 偏移：d > 0 向外膨胀，d < 0 向内腐蚀
+    pub fn offset(&self, d: f64) -> SdfGrid {
+        SdfGrid { values: self.values.iter().map(|v| v - d).collect(), ..self.clone() }
+    }
+
+    // 重新提取零等值面 (resolution 可与采样分辨率不同)
+    pub fn to_mesh(&self, resolution: u32) -> MeshData {
+        ImplicitSurfaceSolver::solve(
+            &|x, y, z| self.value_at(Vec3::new(x, y, z)),
+            (self.min.x, self.max.x),
+            (self.min.y, self.max.y),
+            (self.min.z, self.max.z),
+            resolution,
+        )
+    }
+}
+
+impl MeshData {
+    // 在 bounds = (min, max) 内按 resolution 采样有符号距离场 (见文件头的说明)
+    pub fn to_sdf(&self, bounds: (Vec3, Vec3), resolution: u32) -> SdfGrid {
+        assert!(resolution > 0, "sdf resolution must be positive");
+        let (min, max) = bounds;
+        let n = resolution as usize + 1;
+        let step = (max - min) / resolution as f64;
+        let triangles = self.triangles();
+        let buckets = TriangleBuckets::new(&triangles, bounds);
+
+        // 每行 (固定 j, k) 一条射线，记录与网格的交点 x 坐标
+        let crossings = row_crossings(&triangles, min, step, n);
+
+        let mut values = vec![0.0; n * n * n];
+        values.par_chunks_mut(n * n).enumerate().for_each(|(k, plane)| {
+            let z = min.z + k as f64 * step.z;
+            for j in 0..n {
+                let y = min.y + j as f64 * step.y;
+                let row = &crossings[k * n + j];
+                for i in 0..n {
+                    let p = Vec3::new(min.x + i as f64 * step.x, y, z);
+                    let dist = buckets.nearest(&triangles, p);
+                    // 右侧交点个数为奇数 => 在内部
+                    let inside = row.iter().filter(|&&x| x > p.x).count() % 2 == 1;
+                    plane[j * n + i] = if inside { -dist } else { dist };
+                }
+            }
+        });
+
+        SdfGrid { min, max, resolution, values }
+    }
+
+    // 三角形列表 (f64)，跳过含非有限顶点的三角形
+    fn triangles(&self) -> Vec<[Vec3; 3]> {
+        let pos = |i: u32| {
+            let [x, y, z] = self.vertices[i as usize].position.map(|c| c as f64);
+            Vec3::new(x, y, z)
+        };
+        self.indices.chunks_exact(3)
+            .map(|t| [pos(t[0]), pos(t[1]), pos(t[2])])
+            .filter(|t| t.iter().all(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite()))
+            .collect()
+    }
+}
+
+// ====================== 符号：射线奇偶性 ======================
+
+// 射线起点微小偏移，避免恰好穿过三角形的边或顶点 (如球面极点) 时重复或漏计
+const RAY_JITTER: (f64, f64) = (1.234e-7, 2.718e-7);
+
+// 每行 (j, k) 的射线 y = y_j, z = z_k 与各三角形的交点 x 坐标，按 k * n + j 存放
+fn row_crossings(triangles: &[[Vec3; 3]], min: Vec3, step: Vec3, n: usize) -> Vec<Vec<f64>> {
+    let mut rows = vec![Vec::new(); n * n];
+    let (jy, jz) = (RAY_JITTER.0 * step.y, RAY_JITTER.1 * step.z);
+    let to_index = |v: f64, lo: f64, h: f64| (v - lo) / h;
+    for [a, b, c] in triangles {
+        // 三角形在 yz 平面上的包围盒覆盖的行 (向外放宽一行，是否相交交给 ray_x_crossing 判断)
+        let (y0, y1) = (a.y.min(b.y).min(c.y), a.y.max(b.y).max(c.y));
+        let (z0, z1) = (a.z.min(b.z).min(c.z), a.z.max(b.z).max(c.z));
+        let rows_of = |lo: f64, hi: f64, origin: f64, h: f64| {
+            let first = to_index(lo, origin, h).floor().max(0.0);
+            let last = to_index(hi, origin, h).ceil().min((n - 1) as f64);
+            (first <= last).then_some((first as usize, last as usize))
+        };
+        let (Some((j0, j1)), Some((k0, k1))) = (rows_of(y0, y1, min.y, step.y), rows_of(z0, z1, min.z, step.z)) else { continue };
+        for k in k0..=k1 {
+            for j in j0..=j1 {
+                let (y, z) = (min.y + j as f64 * step.y + jy, min.z + k as f64 * step.z + jz);
+                if let Some(x) = ray_x_crossing(a, b, c, y, z) {
+                    rows[k * n + j].push(x);
+                }
+            }
+        }
+    }
+    rows
+}
+
+// 平行于 x 轴的直线 (y, z) 与三角形的交点 x；在 yz 投影中用重心坐标判断
+fn ray_x_crossing(a: &Vec3, b: &Vec3, c: &Vec3, y: f64, z: f64) -> Option<f64> {
+    let det = (b.y - a.y) * (c.z - a.z) - (c.y - a.y) * (b.z - a.z);
+    if det.abs() < 1e-300 { return None; } // 投影退化 (三角形平行于 x 轴)
+    let (dy, dz) = (y - a.y, z - a.z);
+    let u = (dy * (c.z - a.z) - (c.y - a.y) * dz) / det;
+    let v = ((b.y - a.y) * dz - dy * (b.z - a.z)) / det;
+    if u < 0.0 || v < 0.0 || u + v > 1.0 { return None; }
+    Some(a.x + (b.x - a.x) * u + (c.x - a.x) * v)
+}
+
+// ====================== 距离：均匀网格分桶 ======================
+
+struct TriangleBuckets {
+    origin: Vec3,
+    cell: f64,
+    dims: [usize; 3],
+    cells: Vec<Vec<u32>>, // 每个桶里与之 (包围盒) 相交的三角形
+}
+
+impl TriangleBuckets {
+    // 覆盖采样范围与网格包围盒的并集，保证查询点都落在桶网格内
+    fn new(triangles: &[[Vec3; 3]], bounds: (Vec3, Vec3)) -> Self {
+        let (mut lo, mut hi) = bounds;
+        for t in triangles {
+            for p in t {
+                lo = Vec3::new(lo.x.min(p.x), lo.y.min(p.y), lo.z.min(p.z));
+                hi = Vec3::new(hi.x.max(p.x), hi.y.max(p.y), hi.z.max(p.z));
+            }
+        }
+        let extent = hi - lo;
+        let longest = extent.x.max(extent.y).max(extent.z).max(1e-12);
+        // 每轴桶数约为三角形数的立方根 (平均每桶 ~1 个三角形)
+        let per_axis = ((triangles.len() as f64).cbrt().ceil() as usize).clamp(1, 64);
+        let cell = longest / per_axis as f64;
+        let dim = |e: f64| ((e / cell).ceil() as usize).max(1);
+        let dims = [dim(extent.x), dim(extent.y), dim(extent.z)];
+
+        let mut buckets = Self { origin: lo, cell, dims, cells: vec![Vec::new(); dims[0] * dims[1] * dims[2]] };
+        for (id, [a, b, c]) in triangles.iter().enumerate() {
+            let t_lo = buckets.cell_of(Vec3::new(a.x.min(b.x).min(c.x), a.y.min(b.y).min(c.y), a.z.min(b.z).min(c.z)));
+            let t_hi = buckets.cell_of(Vec3::new(a.x.max(b.x).max(c.x), a.y.max(b.y).max(c.y), a.z.max(b.z).max(c.z)));
+            for z in t_lo[2]..=t_hi[2] {
+                for y in t_lo[1]..=t_hi[1] {
+                    for x in t_lo[0]..=t_hi[0] {
+                        let idx = buckets.index([x, y, z]);
+                        buckets.cells[idx].push(id as u32);
+                    }
+                }
+            }
+        }
+        buckets
+    }
+
+    fn cell_of(&self, p: Vec3) -> [usize; 3] {
+        let c = |v: f64, lo: f64, dim: usize| (((v - lo) / self.cell).floor().max(0.0) as usize).min(dim - 1);
+        [c(p.x, self.origin.x, self.dims[0]), c(p.y, self.origin.y, self.dims[1]), c(p.z, self.origin.z, self.dims[2])]
+    }
+
+    fn index(&self, [x, y, z]: [usize; 3]) -> usize {
+        (z * self.dims[1] + y) * self.dims[0] + x
+    }
+
+    // 到最近三角形的距离：以 p 所在的桶为中心逐圈向外搜索，
+    // 当前最近距离不超过 p 到已搜索区域边界的距离时停止
+    fn nearest(&self, triangles: &[[Vec3; 3]], p: Vec3) -> f64 {
+        if triangles.is_empty() { return f64::INFINITY; }
+        let center = self.cell_of(p);
+        let max_ring = self.dims.iter().max().copied().unwrap_or(1);
+        let mut best = f64::INFINITY;
+        for r in 0..=max_ring {
+            let range = |axis: usize| center[axis].saturating_sub(r)..=(center[axis] + r).min(self.dims[axis] - 1);
+            for z in range(2) {
+                for y in range(1) {
+                    for x in range(0) {
+                        // 只看第 r 圈 (内圈已搜索过)
+                        let ring = x.abs_diff(center[0]).max(y.abs_diff(center[1])).max(z.abs_diff(center[2]));
+                        if ring != r { continue; }
+                        for &id in &self.cells[self.index([x, y, z])] {
+                            let [a, b, c] = triangles[id as usize];
+                            best = best.min(closest_point_on_triangle(p, a, b, c).dis_pow2(p));
+                        }
+                    }
+                }
+            }
+            // 已搜索区域 [center - r, center + r] 的边界到 p 的最短距离
+            let reach = (0..3).map(|axis| {
+                let (v, lo) = match axis { 0 => (p.x, self.origin.x), 1 => (p.y, self.origin.y), _ => (p.z, self.origin.z) };
+                let below = v - (lo + (center[axis] as f64 - r as f64) * self.cell);
+                let above = lo + (center[axis] + r + 1) as f64 * self.cell - v;
+                below.min(above)
+            }).fold(f64::INFINITY, f64::min);
+            if best <= reach * reach { break; }
+        }
+        best.sqrt()
+    }
+}
+
+// 点到三角形的最近点 (Ericson, Real-Time Collision Detection 5.1.5)
+// 按 Voronoi 区域依次判断：三个顶点、三条边、内部
+fn closest_point_on_triangle(p: Vec3, a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 { return a; }
+
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 { return b; }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 { return c; }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    // 内部：退化三角形 (面积为零) 时分母为零，退回顶点 a
+    let denom = va + vb + vc;
+    if denom.abs() < 1e-300 { return a; }
+    a + ab * (vb / denom) + ac * (vc / denom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::mesh::SurfaceTopology;
+    use std::f64::consts::{PI, TAU};
+
+    fn unit_sphere_mesh() -> MeshData {
+        let topology = SurfaceTopology { wrap_u: true, wrap_v: false, collapse_degenerate_rows: true };
+        MeshData::new_parametric_surface_with(
+            |u, v| Vec3::new(v.sin() * u.cos(), v.sin() * u.sin(), v.cos()),
+            (0.0, TAU), (0.0, PI), 64, 32, topology,
+        )
+    }
+
+    #[test]
+    fn test_closest_point_on_triangle() {
+        let (a, b, c) = (Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        // 内部 / 边 / 顶点区域
+        assert!((closest_point_on_triangle(Vec3::new(0.2, 0.2, 1.0), a, b, c) - Vec3::new(0.2, 0.2, 0.0)).len() < 1e-12);
+        assert_eq!(closest_point_on_triangle(Vec3::new(0.5, -1.0, 0.0), a, b, c), Vec3::new(0.5, 0.0, 0.0));
+        assert_eq!(closest_point_on_triangle(Vec3::new(2.0, -1.0, 0.0), a, b, c), b);
+        let q = closest_point_on_triangle(Vec3::new(1.0, 1.0, 0.0), a, b, c);
+        assert!((q - Vec3::new(0.5, 0.5, 0.0)).len() < 1e-12);
+    }
+
+    #[test]
+    fn test_sphere_sdf_matches_analytic() {
+        let mesh = unit_sphere_mesh();
+        let bounds = (Vec3::new(-1.5, -1.5, -1.5), Vec3::new(1.5, 1.5, 1.5));
+        let sdf = mesh.to_sdf(bounds, 24);
+
+        // 采样点上：距离误差只来自网格的弦高 (1 - cos(π/64) ≈ 0.0012)，符号全部正确
+        let step = sdf.step();
+        for k in 0..=24 {
+            for j in 0..=24 {
+                for i in 0..=24 {
+                    let p = sdf.min + Vec3::new(i as f64 * step.x, j as f64 * step.y, k as f64 * step.z);
+                    let expect = p.len() - 1.0;
+                    assert!((sdf.sample(i, j, k) - expect).abs() < 3e-3, "{} -> {} vs {}", p, sdf.sample(i, j, k), expect);
+                }
+            }
+        }
+
+        // 格点之间：三线性插值误差 (远离球心，距离函数光滑)
+        for p in [Vec3::new(0.33, -0.71, 0.2), Vec3::new(1.1, 0.4, -0.9), Vec3::new(-0.05, 0.6, 0.55)] {
+            assert!((sdf.value_at(p) - (p.len() - 1.0)).abs() < 0.02);
+        }
+        // 范围外：加上到边界的距离
+        let far = Vec3::new(3.0, 0.0, 0.0);
+        assert!((sdf.value_at(far) - 2.0).abs() < 3e-3);
+    }
+
+    #[test]
+    fn test_offset_surface() {
+        let mesh = unit_sphere_mesh();
+        let bounds = (Vec3::new(-1.6, -1.6, -1.6), Vec3::new(1.6, 1.6, 1.6));
+        let dilated = mesh.to_sdf(bounds, 24).offset(0.2).to_mesh(32);
+
+        assert!(!dilated.vertices.is_empty());
+        for v in &dilated.vertices {
+            let r = Vec3::new(v.position[0] as f64, v.position[1] as f64, v.position[2] as f64).len();
+            assert!((r - 1.2).abs() < 0.02, "r = {}", r);
+        }
+    }
+}
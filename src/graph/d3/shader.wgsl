@@ -17,10 +17,20 @@ struct VertexInput {
     @location(1) normal: vec3<f32>,
 };
 
+// 实例化绘制：模型矩阵按列传入 (列优先，与 u.model 相同)
+struct InstanceInput {
+    @location(2) model_0: vec4<f32>,
+    @location(3) model_1: vec4<f32>,
+    @location(4) model_2: vec4<f32>,
+    @location(5) model_3: vec4<f32>,
+    @location(6) color: vec4<f32>,
+};
+
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) world_pos: vec3<f32>,
     @location(1) world_normal: vec3<f32>,
+    @location(2) color: vec4<f32>,
 };
 
 @vertex
@@ -30,6 +40,20 @@ fn vs_main(in: VertexInput) -> VertexOutput {
     out.world_pos = world_pos.xyz;
     out.clip_position = u.view_proj * world_pos;
     out.world_normal = in.normal;
+    out.color = u.base_color;
+    return out;
+}
+
+@vertex
+fn vs_instanced(in: VertexInput, inst: InstanceInput) -> VertexOutput {
+    var out: VertexOutput;
+    let instance_model = mat4x4<f32>(inst.model_0, inst.model_1, inst.model_2, inst.model_3);
+    let world_pos = u.model * instance_model * vec4<f32>(in.position, 1.0);
+    out.world_pos = world_pos.xyz;
+    out.clip_position = u.view_proj * world_pos;
+    // 法线只随实例旋转 (非均匀缩放下近似)
+    out.world_normal = (instance_model * vec4<f32>(in.normal, 0.0)).xyz;
+    out.color = inst.color;
     return out;
 }
 
@@ -37,11 +61,11 @@ fn vs_main(in: VertexInput) -> VertexOutput {
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // 1. 如果不使用光照 (如坐标轴)，直接返回颜色
     if (u.use_lighting < 0.5) {
-        return u.base_color;
+        return in.color;
     }
 
     // 2. 光照计算 (曲面)
-    let object_color = u.base_color.rgb;
+    let object_color = in.color.rgb;
     let light_pos = vec3<f32>(10.0, 10.0, 20.0);
     let light_color = vec3<f32>(1.0, 1.0, 1.0);
 
@@ -65,5 +89,5 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
     let final_color = (ambient + diffuse + specular) * object_color;
 
-    return vec4<f32>(final_color, u.base_color.a);
+    return vec4<f32>(final_color, in.color.a);
 }
\ No newline at end of file
@@ -0,0 +1,54 @@
+// src/graph/gpu_interop.rs
+// CPU (MathForest, f64, 行优先) -> GPU (wgpu / WGSL, f32, 列优先) 的数据转换
+// 所有上传到着色器的矩阵都经过这里，转置约定只写一处，并由测试锁定
+
+use crate::math_forest::algebra::linear::matrix4x4::Matrix4x4;
+
+/// 4x4 矩阵 -> 列优先 f32 数组 (WGSL mat4x4 / glam::Mat4::from_cols_array 的布局)
+/// 行优先存储的 Mᵀ 恰好是 M 的列优先存储，因此转置后逐元素转 f32
+/// 着色器中 M * v 与 CPU 端 Matrix4x4 * v 结果一致
+pub fn mat4_to_cols_f32(m: Matrix4x4) -> [f32; 16] {
+    m.transpose().m.map(|x| x as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+
+    // 模拟 WGSL：列优先数组 c[col * 4 + row]，在 f32 下计算 M * v
+    fn gpu_mul(c: &[f32; 16], v: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0f32; 4];
+        for (row, o) in out.iter_mut().enumerate() {
+            *o = (0..4).map(|col| c[col * 4 + row] * v[col]).sum();
+        }
+        out
+    }
+
+    #[test]
+    fn test_mat4_convention_matches_cpu() {
+        let m = Matrix4x4::from_translation(Vec3::new(3.0, -2.0, 5.0))
+            * Matrix4x4::from_axis_angle(Vec3::new(1.0, 2.0, -1.0).unit(), 0.8);
+        let cols = mat4_to_cols_f32(m);
+
+        // 平移位于最后一列
+        assert_eq!(&cols[12..15], &[3.0, -2.0, 5.0]);
+        assert_eq!(cols[15], 1.0);
+
+        // 同一点：CPU (f64) 与模拟 GPU (f32 列优先) 结果一致
+        let p = Vec3::new(0.7, -1.3, 2.2);
+        let cpu = m.transform_point3(p);
+        let [x, y, z] = p.to_f32_array();
+        let gpu = gpu_mul(&cols, [x, y, z, 1.0]);
+        let cpu = cpu.to_f32_array();
+        for k in 0..3 {
+            assert!((gpu[k] - cpu[k]).abs() < 1e-5, "{:?} vs {:?}", gpu, cpu);
+        }
+        assert!((gpu[3] - 1.0).abs() < 1e-6);
+
+        // 与 glam 的列优先约定一致 (相机把 VP 矩阵交给 glam)
+        let g = glam::Mat4::from_cols_array(&cols);
+        let gp = g.transform_point3(glam::Vec3::new(x, y, z));
+        assert!((gp - glam::Vec3::from(cpu)).length() < 1e-5);
+    }
+}
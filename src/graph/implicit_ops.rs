@@ -0,0 +1,279 @@
+// src/graph/implicit_ops.rs
+// 隐函数的组合子：布尔运算、光滑融合与坐标变换
+// 约定 f < 0 为内部、f > 0 为外部；结果仍是闭包，可直接交给 ImplicitSurfaceSolver::solve
+// d2 子模块是平面版本 (GeoObj::new_implicit)
+#![allow(dead_code)]
+
+use crate::math_forest::algebra::linear::matrix4x4::Matrix4x4;
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+
+// ====================== 标量 ======================
+
+// 多项式光滑最小值 (k 为融合半径，k <= 0 退化为 min)
+// 与 min 的差不超过 k / 4，只在 |a - b| < k 的范围内生效
+pub fn smooth_min(a: f64, b: f64, k: f64) -> f64 {
+    if k <= 0.0 {
+        return a.min(b);
+    }
+    let h = (0.5 + 0.5 * (b - a) / k).clamp(0.0, 1.0);
+    b + (a - b) * h - k * h * (1.0 - h)
+}
+
+pub fn smooth_max(a: f64, b: f64, k: f64) -> f64 {
+    -smooth_min(-a, -b, k)
+}
+
+// ====================== 布尔运算 ======================
+
+// 并：min(f, g)
+pub fn union<F, G>(f: F, g: G) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send
+where F: Fn(f64, f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64, f64) -> f64 + Sync + Send {
+    move |x, y, z| f(x, y, z).min(g(x, y, z))
+}
+
+// 交：max(f, g)
+pub fn intersection<F, G>(f: F, g: G) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send
+where F: Fn(f64, f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64, f64) -> f64 + Sync + Send {
+    move |x, y, z| f(x, y, z).max(g(x, y, z))
+}
+
+// 差 (f 挖去 g)：max(f, -g)
+pub fn difference<F, G>(f: F, g: G) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send
+where F: Fn(f64, f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64, f64) -> f64 + Sync + Send {
+    move |x, y, z| f(x, y, z).max(-g(x, y, z))
+}
+
+// 光滑并：接缝处以半径 k 圆角过渡 (融合区域会略微膨胀)
+pub fn smooth_union<F, G>(f: F, g: G, k: f64) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send
+where F: Fn(f64, f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64, f64) -> f64 + Sync + Send {
+    move |x, y, z| smooth_min(f(x, y, z), g(x, y, z), k)
+}
+
+pub fn smooth_intersection<F, G>(f: F, g: G, k: f64) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send
+where F: Fn(f64, f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64, f64) -> f64 + Sync + Send {
+    move |x, y, z| smooth_max(f(x, y, z), g(x, y, z), k)
+}
+
+pub fn smooth_difference<F, G>(f: F, g: G, k: f64) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send
+where F: Fn(f64, f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64, f64) -> f64 + Sync + Send {
+    move |x, y, z| smooth_max(f(x, y, z), -g(x, y, z), k)
+}
+
+// ====================== 类型擦除 ======================
+// 上面的组合子返回各不相同的 impl 类型，形体个数在运行时才确定 (如从列表折叠) 时无法放进同一个容器
+// 装箱后类型统一，且 Box 本身实现了 Fn + Sync + Send，可以继续交给任意组合子与 ImplicitSurfaceSolver::solve
+
+pub type BoxedImplicit = Box<dyn Fn(f64, f64, f64) -> f64 + Sync + Send>;
+
+pub fn boxed<F>(f: F) -> BoxedImplicit
+where F: Fn(f64, f64, f64) -> f64 + Sync + Send + 'static {
+    Box::new(f)
+}
+
+// 任意多个形体的并；空列表处处为 +∞ (空集)
+pub fn union_all(parts: Vec<BoxedImplicit>) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send {
+    move |x, y, z| parts.iter().fold(f64::INFINITY, |acc, f| acc.min(f(x, y, z)))
+}
+
+// 任意多个形体依次光滑并 (左折叠)；空列表处处为 +∞
+pub fn smooth_union_all(parts: Vec<BoxedImplicit>, k: f64) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send {
+    move |x, y, z| {
+        let mut values = parts.iter().map(|f| f(x, y, z));
+        let first = values.next().unwrap_or(f64::INFINITY);
+        values.fold(first, |acc, v| smooth_min(acc, v, k))
+    }
+}
+
+// ====================== 变换 ======================
+// 变换形体 = 对采样点做逆变换后再求值
+
+// 平移 offset
+pub fn translate<F>(f: F, offset: Vec3) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send
+where F: Fn(f64, f64, f64) -> f64 + Sync + Send {
+    move |x, y, z| f(x - offset.x, y - offset.y, z - offset.z)
+}
+
+// 绕过原点的轴 axis 旋转 angle (右手)
+pub fn rotate<F>(f: F, axis: Vec3, angle: f64) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send
+where F: Fn(f64, f64, f64) -> f64 + Sync + Send {
+    let inverse = Matrix4x4::from_axis_angle(axis, -angle);
+    move |x, y, z| {
+        let p = inverse.transform_point3(Vec3::new(x, y, z));
+        f(p.x, p.y, p.z)
+    }
+}
+
+// 以原点为中心均匀缩放 s (s > 0)
+// 结果乘以 s：若 f 是距离场，缩放后仍是距离场；非均匀缩放无法保持距离，这里不提供
+// f 不是距离场时零等值面照样正确，只是数值大小没有几何意义
+pub fn scale<F>(f: F, s: f64) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send
+where F: Fn(f64, f64, f64) -> f64 + Sync + Send {
+    assert!(s > 0.0, "scale factor must be positive");
+    move |x, y, z| f(x / s, y / s, z / s) * s
+}
+
+// ====================== 平面 ======================
+
+pub mod d2 {
+    use super::{smooth_max, smooth_min};
+    use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
+    use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+
+    pub fn union<F, G>(f: F, g: G) -> impl Fn(f64, f64) -> f64 + Sync + Send
+    where F: Fn(f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64) -> f64 + Sync + Send {
+        move |x, y| f(x, y).min(g(x, y))
+    }
+
+    pub fn intersection<F, G>(f: F, g: G) -> impl Fn(f64, f64) -> f64 + Sync + Send
+    where F: Fn(f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64) -> f64 + Sync + Send {
+        move |x, y| f(x, y).max(g(x, y))
+    }
+
+    pub fn difference<F, G>(f: F, g: G) -> impl Fn(f64, f64) -> f64 + Sync + Send
+    where F: Fn(f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64) -> f64 + Sync + Send {
+        move |x, y| f(x, y).max(-g(x, y))
+    }
+
+    pub fn smooth_union<F, G>(f: F, g: G, k: f64) -> impl Fn(f64, f64) -> f64 + Sync + Send
+    where F: Fn(f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64) -> f64 + Sync + Send {
+        move |x, y| smooth_min(f(x, y), g(x, y), k)
+    }
+
+    pub fn smooth_intersection<F, G>(f: F, g: G, k: f64) -> impl Fn(f64, f64) -> f64 + Sync + Send
+    where F: Fn(f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64) -> f64 + Sync + Send {
+        move |x, y| smooth_max(f(x, y), g(x, y), k)
+    }
+
+    pub fn smooth_difference<F, G>(f: F, g: G, k: f64) -> impl Fn(f64, f64) -> f64 + Sync + Send
+    where F: Fn(f64, f64) -> f64 + Sync + Send, G: Fn(f64, f64) -> f64 + Sync + Send {
+        move |x, y| smooth_max(f(x, y), -g(x, y), k)
+    }
+
+    pub fn translate<F>(f: F, offset: Vec2) -> impl Fn(f64, f64) -> f64 + Sync + Send
+    where F: Fn(f64, f64) -> f64 + Sync + Send {
+        move |x, y| f(x - offset.x, y - offset.y)
+    }
+
+    // 绕原点逆时针旋转 angle
+    pub fn rotate<F>(f: F, angle: f64) -> impl Fn(f64, f64) -> f64 + Sync + Send
+    where F: Fn(f64, f64) -> f64 + Sync + Send {
+        let inverse = Matrix3x3::from_rotation(-angle);
+        move |x, y| {
+            let p = inverse.transform_point2(Vec2::new(x, y));
+            f(p.x, p.y)
+        }
+    }
+
+    // 同三维版本：均匀缩放，结果乘以 s 以保持距离场
+    pub fn scale<F>(f: F, s: f64) -> impl Fn(f64, f64) -> f64 + Sync + Send
+    where F: Fn(f64, f64) -> f64 + Sync + Send {
+        assert!(s > 0.0, "scale factor must be positive");
+        move |x, y| f(x / s, y / s) * s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn sphere(r: f64) -> impl Fn(f64, f64, f64) -> f64 + Sync + Send {
+        move |x, y, z| (x * x + y * y + z * z).sqrt() - r
+    }
+
+    #[test]
+    fn test_boolean_signs() {
+        // 两个单位球，球心 (0,0,0) 与 (1.5,0,0)
+        let a = sphere(1.0);
+        let b = || translate(sphere(1.0), Vec3::new(1.5, 0.0, 0.0));
+
+        let u = union(sphere(1.0), b());
+        assert!(u(0.0, 0.0, 0.0) < 0.0 && u(1.5, 0.0, 0.0) < 0.0);
+        assert!(u(0.75, 1.0, 0.0) > 0.0);
+        assert!(u(-1.0, 0.0, 0.0).abs() < 1e-12 && u(2.5, 0.0, 0.0).abs() < 1e-12);
+
+        let i = intersection(sphere(1.0), b());
+        assert!(i(0.75, 0.0, 0.0) < 0.0);
+        assert!(i(0.0, 0.0, 0.0) > 0.0 && i(1.5, 0.0, 0.0) > 0.0);
+        assert!(i(1.0, 0.0, 0.0).abs() < 1e-12);
+
+        let d = difference(a, b());
+        assert!(d(-0.5, 0.0, 0.0) < 0.0);
+        assert!(d(0.75, 0.0, 0.0) > 0.0 && d(1.5, 0.0, 0.0) > 0.0);
+        assert!(d(0.5, 0.0, 0.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_smooth_blends() {
+        assert_eq!(smooth_min(1.0, 3.0, 0.5), 1.0); // |a - b| >= k 时就是 min
+        assert!((smooth_min(1.0, 1.0, 0.4) - 0.9).abs() < 1e-12); // 最大偏差 k / 4
+
+        // 光滑并包住普通并，并把颈部填起来
+        let plain = union(sphere(1.0), translate(sphere(1.0), Vec3::new(2.2, 0.0, 0.0)));
+        let smooth = smooth_union(sphere(1.0), translate(sphere(1.0), Vec3::new(2.2, 0.0, 0.0)), 0.5);
+        assert!(plain(1.1, 0.0, 0.0) > 0.0 && smooth(1.1, 0.0, 0.0) < 0.0);
+        assert!(smooth(0.0, 0.0, 0.0) < 0.0 && smooth(5.0, 0.0, 0.0) > 0.0);
+        assert!(smooth(-1.0, 0.0, 0.0).abs() < 1e-12); // 远离接缝处不变
+
+        let si = smooth_intersection(sphere(1.0), translate(sphere(1.0), Vec3::new(1.5, 0.0, 0.0)), 0.3);
+        assert!(si(0.75, 0.0, 0.0) < 0.0 && si(0.0, 0.0, 0.0) > 0.0);
+        let sd = smooth_difference(sphere(1.0), translate(sphere(1.0), Vec3::new(1.5, 0.0, 0.0)), 0.3);
+        assert!(sd(-0.8, 0.0, 0.0) < 0.0 && sd(0.9, 0.0, 0.0) > 0.0);
+    }
+
+    #[test]
+    fn test_transforms() {
+        // 沿 x 轴的长盒子 |x| < 2, |y|, |z| < 0.5
+        let slab = |x: f64, y: f64, z: f64| (x.abs() - 2.0).max(y.abs() - 0.5).max(z.abs() - 0.5);
+        let turned = rotate(slab, Vec3::new(0.0, 0.0, 1.0), FRAC_PI_2); // 转到沿 y 轴
+        assert!(turned(0.0, 1.5, 0.0) < 0.0);
+        assert!(turned(1.5, 0.0, 0.0) > 0.0);
+        assert!(turned(0.0, 2.0, 0.0).abs() < 1e-12);
+
+        let moved = translate(sphere(1.0), Vec3::new(0.0, 0.0, 3.0));
+        assert!(moved(0.0, 0.0, 3.0) < 0.0 && moved(0.0, 0.0, 0.0) > 0.0);
+        assert!(moved(0.0, 0.0, 4.0).abs() < 1e-12);
+
+        // 缩放后仍是距离场
+        let big = scale(sphere(1.0), 2.0);
+        assert!((big(5.0, 0.0, 0.0) - 3.0).abs() < 1e-12);
+        assert!((big(0.0, 0.0, 0.0) + 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_boxed_scene() {
+        // 一排个数在运行时决定的球：装箱后放进同一个 Vec
+        let balls: Vec<BoxedImplicit> = (0..4)
+            .map(|i| boxed(translate(sphere(0.5), Vec3::new(i as f64, 0.0, 0.0))))
+            .collect();
+        let row = union_all(balls);
+        assert!(row(3.0, 0.0, 0.0) < 0.0 && row(0.5, 0.0, 0.0).abs() < 1e-12);
+        assert!(row(1.5, 1.0, 0.0) > 0.0);
+        assert_eq!(union_all(Vec::new())(0.0, 0.0, 0.0), f64::INFINITY);
+
+        // 装箱结果仍可继续组合
+        let cut = difference(boxed(sphere(2.0)), boxed(row));
+        assert!(cut(0.0, 0.0, 0.0) > 0.0 && cut(0.0, 1.0, 0.0) < 0.0);
+
+        let balls: Vec<BoxedImplicit> = vec![boxed(sphere(1.0)), boxed(translate(sphere(1.0), Vec3::new(2.2, 0.0, 0.0)))];
+        let blob = smooth_union_all(balls, 0.5);
+        assert!(blob(1.1, 0.0, 0.0) < 0.0 && blob(-1.0, 0.0, 0.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_d2_ops() {
+        let circle = |x: f64, y: f64| (x * x + y * y).sqrt() - 1.0;
+        let u = d2::union(circle, d2::translate(circle, Vec2::new(1.5, 0.0)));
+        assert!(u(1.5, 0.0) < 0.0 && u(0.75, 1.0) > 0.0);
+        let d = d2::difference(circle, d2::translate(circle, Vec2::new(1.5, 0.0)));
+        assert!(d(-0.5, 0.0) < 0.0 && d(0.75, 0.0) > 0.0);
+        let s = d2::smooth_union(circle, d2::translate(circle, Vec2::new(2.2, 0.0)), 0.5);
+        assert!(s(1.1, 0.0) < 0.0);
+
+        let bar = |x: f64, y: f64| (x.abs() - 2.0).max(y.abs() - 0.5);
+        let turned = d2::rotate(bar, FRAC_PI_2);
+        assert!(turned(0.0, 1.5) < 0.0 && turned(1.5, 0.0) > 0.0);
+        assert!((d2::scale(circle, 3.0)(0.0, 4.0) - 1.0).abs() < 1e-12);
+    }
+}
@@ -1,4 +1,13 @@
 pub mod d3;
 // 绘图器
 pub mod d2;
-mod style;
\ No newline at end of file
+// 隐函数组合子 (平面 / 空间共用)
+pub mod implicit_ops;
+// f64 -> f32 / 行优先 -> 列优先的 GPU 数据转换
+pub mod gpu_interop;
+mod style;
+// 会话保存 / 恢复 (--session)
+pub mod session;
+// 求解器快照测试 (固定测试函数 + 输出摘要)
+#[cfg(test)]
+pub mod testing;
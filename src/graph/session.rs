@@ -0,0 +1,275 @@
+// src/graph/session.rs
+// 会话：视图状态 + 可序列化的对象描述，退出时写入文件，启动时 (--session <path>) 读回
+// 文本格式 (仿 Env::serialize)，首行版本号，之后每项一行：
+//   VIEW2 <center_x> <center_y> <zoom>
+//   VIEW3 <target.x> <target.y> <target.z> <yaw> <pitch> <radius>
+//   OBJ <explicit|implicit> <r> <g> <b> <a> <width> <ops...>   ops 为各条 Op 的 Debug 文本
+// 只有 RPN 来源的对象可以保存，闭包对象跳过
+#![allow(dead_code)]
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use winit::event_loop::EventLoop;
+
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+use crate::pakoo::persist;
+use crate::pakoo::rpn::RPN;
+
+use super::d2::common::GeoObj;
+use super::d2::main::{D2Plotter, ViewStateSnapshot};
+use super::d2::watch::{self, ExprKind};
+use super::d3::CameraSnapshot;
+
+const FORMAT_HEADER: &str = "FOREST-SESSION";
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, PartialEq)]
+pub enum SessionError {
+    Io(String),
+    VersionMismatch,
+    InvalidLine(usize), // 行号 (从 1 开始)
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Io(e) => write!(f, "cannot access session file: {}", e),
+            SessionError::VersionMismatch => write!(f, "session format version mismatch (expected {} {})", FORMAT_HEADER, FORMAT_VERSION),
+            SessionError::InvalidLine(line) => write!(f, "invalid session data at line {}", line),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+// 可保存的对象：表达式 + 外观
+#[derive(Clone)]
+pub struct ObjectDesc {
+    pub kind: ExprKind,
+    pub(crate) rpn: Arc<RPN>,
+    pub color: [f32; 4],
+    pub width: f32,
+}
+
+impl ObjectDesc {
+    // 闭包对象 (没有 RPN 来源) 返回 None
+    pub fn from_geo_obj(obj: &GeoObj) -> Option<Self> {
+        let source = obj.source.as_ref()?;
+        Some(Self { kind: source.kind, rpn: source.rpn.clone(), color: obj.coloring.base_color(), width: obj.width })
+    }
+
+    pub fn to_geo_obj(&self) -> GeoObj {
+        watch::geo_obj_from_rpn(self.kind, self.rpn.clone(), self.color, self.width)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Session {
+    pub view: Option<ViewStateSnapshot>,
+    pub camera: Option<CameraSnapshot>,
+    pub objects: Vec<ObjectDesc>,
+}
+
+impl Session {
+    pub fn serialize(&self) -> String {
+        let mut s = format!("{} {}\n", FORMAT_HEADER, FORMAT_VERSION);
+        if let Some(v) = &self.view {
+            s.push_str(&format!("VIEW2 {} {} {}\n", v.center_x, v.center_y, v.zoom));
+        }
+        if let Some(c) = &self.camera {
+            s.push_str(&format!("VIEW3 {} {} {} {} {} {}\n", c.target.x, c.target.y, c.target.z, c.yaw, c.pitch, c.radius));
+        }
+        for obj in &self.objects {
+            let kind = match obj.kind {
+                ExprKind::Explicit => "explicit",
+                ExprKind::Implicit => "implicit",
+            };
+            let [r, g, b, a] = obj.color;
+            let ops = obj.rpn.ops().iter().map(|op| format!("{:?}", op)).collect::<Vec<_>>().join(" ");
+            s.push_str(&format!("OBJ {} {} {} {} {} {} {}\n", kind, r, g, b, a, obj.width, ops));
+        }
+        s
+    }
+
+    // 从 serialize 的文本恢复 (空行忽略)；任何一行不合法都整体失败
+    pub fn deserialize(s: &str) -> Result<Session, SessionError> {
+        let mut lines = s.lines().map(str::trim).enumerate().filter(|(_, l)| !l.is_empty());
+        let (_, header) = lines.next().ok_or(SessionError::VersionMismatch)?;
+        match header.split_once(' ') {
+            Some((FORMAT_HEADER, version)) if version.trim().parse() == Ok(FORMAT_VERSION) => {}
+            _ => return Err(SessionError::VersionMismatch),
+        }
+
+        let mut session = Session::default();
+        for (i, line) in lines {
+            parse_line(&mut session, line).ok_or(SessionError::InvalidLine(i + 1))?;
+        }
+        Ok(session)
+    }
+
+    pub fn load(path: &Path) -> Result<Session, SessionError> {
+        let text = fs::read_to_string(path).map_err(|e| SessionError::Io(e.to_string()))?;
+        Self::deserialize(&text)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SessionError> {
+        fs::write(path, self.serialize()).map_err(|e| SessionError::Io(e.to_string()))
+    }
+
+    // 启动时使用：文件不存在或损坏时从空会话开始 (只在控制台提示)
+    pub fn load_or_default(path: &Path) -> Session {
+        if !path.exists() {
+            println!("[session] {} not found, starting fresh", path.display());
+            return Session::default();
+        }
+        Self::load(path).unwrap_or_else(|e| {
+            eprintln!("[session] {}: {}, starting fresh", path.display(), e);
+            Session::default()
+        })
+    }
+
+    // 只改写会话的一部分 (2D / 3D 窗口各自保存自己的视图)，其余部分保留文件中已有的内容
+    // 文件不存在时新建；已有文件损坏或版本不符时不写入，以免覆盖掉其余部分
+    pub fn update_file(path: &Path, update: impl FnOnce(&mut Session)) -> Result<(), SessionError> {
+        let mut session = if path.exists() { Self::load(path)? } else { Session::default() };
+        update(&mut session);
+        session.save(path)?;
+        println!("[session] saved to {}", path.display());
+        Ok(())
+    }
+}
+
+// 解析一行 (首行之后)；格式不对时返回 None
+fn parse_line(session: &mut Session, line: &str) -> Option<()> {
+    let mut words = line.split_whitespace();
+    let keyword = words.next()?;
+    // NaN / inf 与格式错误一样拒绝 (否则恢复出的视口或相机不可用)
+    let mut num = || words.next()?.parse::<f64>().ok().filter(|v| v.is_finite());
+    match keyword {
+        "VIEW2" => {
+            let (center_x, center_y, zoom) = (num()?, num()?, num()?);
+            // zoom 必须为正，否则视口范围无意义
+            if zoom <= 0.0 { return None; }
+            session.view = Some(ViewStateSnapshot { center_x, center_y, zoom });
+        }
+        "VIEW3" => {
+            let target = Vec3::new(num()?, num()?, num()?);
+            session.camera = Some(CameraSnapshot { target, yaw: num()?, pitch: num()?, radius: num()? });
+        }
+        "OBJ" => {
+            // 关键字、种类、颜色 4 个分量、线宽各占一个词，其后全部是 ops
+            let mut fields = line.splitn(8, ' ');
+            fields.next();
+            let kind = match fields.next()? {
+                "explicit" => ExprKind::Explicit,
+                "implicit" => ExprKind::Implicit,
+                _ => return None,
+            };
+            let mut float = || fields.next()?.parse::<f32>().ok().filter(|v| v.is_finite());
+            let color = [float()?, float()?, float()?, float()?];
+            let width = float()?;
+            let ops = persist::parse_ops(fields.next().unwrap_or("")).ok()?;
+            // 与 watch::compile_line 相同的检查：无参数，全局只有 x, y
+            let rpn = RPN::new_checked(ops, 0, 2).ok()?;
+            session.objects.push(ObjectDesc { kind, rpn: Arc::new(rpn), color, width });
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+// --session <path> 入口 (不带 --watch)：恢复上次的对象与视图，关闭窗口时保存
+pub fn run(path: &Path) {
+    let event_loop = EventLoop::new().unwrap();
+    let mut plotter = D2Plotter::new();
+    plotter.restore_session(&Session::load_or_default(path));
+    println!("[session] restored {} object(s)", plotter.object_count());
+    plotter.set_session_path(PathBuf::from(path));
+    event_loop.run_app(&mut plotter).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::d2::colors;
+
+    fn curve(text: &str, color: [f32; 4]) -> GeoObj {
+        let (kind, rpn) = watch::compile_line(text).unwrap();
+        watch::geo_obj_from_rpn(kind, Arc::new(rpn), color, 2.5)
+    }
+
+    #[test]
+    fn test_round_trip_plotter() {
+        let mut plotter = D2Plotter::new();
+        plotter.add_object(curve("x * x - 1", colors::RED));
+        plotter.add_object(curve("x * x + y * y - 4", colors::BLUE));
+        // 闭包对象不能保存，跳过
+        plotter.add_object(GeoObj::new_explicit(|x| x.sin(), colors::GREEN, 2.0));
+        let view = ViewStateSnapshot { center_x: 1.25, center_y: -3.5, zoom: 0.1 + 0.2 };
+        plotter.restore_view(view);
+
+        let mut session = plotter.session();
+        assert_eq!(session.objects.len(), 2);
+        session.camera = Some(CameraSnapshot { target: Vec3::new(1.0, 2.0, 3.0), yaw: 0.7, pitch: -0.2, radius: 12.0 });
+        let text = session.serialize();
+        let restored = Session::deserialize(&text).unwrap();
+        assert_eq!(restored.serialize(), text);
+        assert_eq!(restored.camera, session.camera);
+
+        let mut fresh = D2Plotter::new();
+        fresh.restore_session(&restored);
+        assert_eq!(fresh.view_state(), view);
+        assert_eq!(fresh.object_count(), 2);
+
+        // 重建的对象求值与原表达式一致，外观保留
+        let objects = restored.objects.iter().map(ObjectDesc::to_geo_obj).collect::<Vec<_>>();
+        assert_eq!(objects[0].coloring.base_color(), colors::RED);
+        assert_eq!(objects[1].width, 2.5);
+        let (kind, rpn) = (restored.objects[1].kind, &restored.objects[1].rpn);
+        assert_eq!(kind, ExprKind::Implicit);
+        assert!(objects[1].gpu_field.is_some());
+        assert_eq!(rpn.to_infix_string(), watch::compile_line("x * x + y * y - 4").unwrap().1.to_infix_string());
+    }
+
+    #[test]
+    fn test_corrupt_and_missing_files() {
+        assert_eq!(Session::deserialize("").err(), Some(SessionError::VersionMismatch));
+        assert_eq!(Session::deserialize("FOREST-SESSION 2\n").err(), Some(SessionError::VersionMismatch));
+        assert_eq!(Session::deserialize("FOREST-SESSION 1\n\nVIEW2 0 0").err(), Some(SessionError::InvalidLine(3)));
+        assert_eq!(Session::deserialize("FOREST-SESSION 1\nVIEW2 0 0 -1").err(), Some(SessionError::InvalidLine(2)));
+        assert_eq!(Session::deserialize("FOREST-SESSION 1\nVIEW2 NaN 0 1").err(), Some(SessionError::InvalidLine(2)));
+        assert_eq!(Session::deserialize("FOREST-SESSION 1\nVIEW2 0 inf 1").err(), Some(SessionError::InvalidLine(2)));
+        assert_eq!(Session::deserialize("FOREST-SESSION 1\nVIEW3 0 0 0 NaN 0.5 8").err(), Some(SessionError::InvalidLine(2)));
+        assert!(Session::deserialize("FOREST-SESSION 1\nOBJ explicit 1 0 0 1 2 LoadGlobal(0)").is_ok());
+        assert_eq!(Session::deserialize("FOREST-SESSION 1\nOBJ explicit NaN 0 0 1 2 LoadGlobal(0)").err(), Some(SessionError::InvalidLine(2)));
+        assert_eq!(Session::deserialize("FOREST-SESSION 1\nOBJ explicit 1 0 0 1 2 Push(Num(1.0)) Pow").err(), Some(SessionError::InvalidLine(2)));
+        // 缺操作数的 RPN 在读入时拒绝
+        assert_eq!(Session::deserialize("FOREST-SESSION 1\nOBJ explicit 1 0 0 1 2 Add").err(), Some(SessionError::InvalidLine(2)));
+
+        let dir = std::env::temp_dir();
+        let missing = dir.join(format!("forest_session_missing_{}.txt", std::process::id()));
+        let session = Session::load_or_default(&missing);
+        assert!(session.view.is_none() && session.objects.is_empty());
+
+        // 损坏的文件：启动时从空会话开始，但 update_file 拒绝覆盖
+        let path = dir.join(format!("forest_session_{}.txt", std::process::id()));
+        fs::write(&path, "garbage").unwrap();
+        assert!(matches!(Session::load(&path), Err(SessionError::VersionMismatch)));
+        assert!(Session::load_or_default(&path).objects.is_empty());
+        let camera = CameraSnapshot { target: Vec3::ZERO, yaw: 1.0, pitch: 0.5, radius: 8.0 };
+        assert_eq!(Session::update_file(&path, |s| s.camera = Some(camera)), Err(SessionError::VersionMismatch));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "garbage");
+
+        // 文件不存在时新建；之后 update_file 只改写给定部分
+        fs::remove_file(&path).unwrap();
+        Session::update_file(&path, |s| s.camera = Some(camera)).unwrap();
+        let view = ViewStateSnapshot { center_x: 2.0, center_y: 0.0, zoom: 4.0 };
+        Session::update_file(&path, |s| s.view = Some(view)).unwrap();
+        let loaded = Session::load(&path).unwrap();
+        assert_eq!((loaded.view, loaded.camera), (Some(view), Some(camera)));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,285 @@
+// src/graph/testing.rs
+// 求解器快照测试：固定的测试函数 + 把输出归约成稳定摘要 (顶点数、索引数、包围盒、面积/体积、量化位置哈希)
+// 摘要与 snapshots/solvers.snap 中提交的值逐行比较；采样、焊接、并行方式的改动会表现为可审阅的快照 diff
+// 重新生成：FOREST_UPDATE_SNAPSHOTS=1 cargo test graph::testing
+// 位置先量化到 1e-6 网格再排序后哈希，与顶点顺序无关 (rayon 的合并顺序不影响结果)
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+
+use super::d2::common::Vertex;
+use super::d3::MeshData;
+
+const SNAPSHOT_FILE: &str = "src/graph/snapshots/solvers.snap";
+const UPDATE_ENV: &str = "FOREST_UPDATE_SNAPSHOTS";
+// 位置量化网格
+const QUANTUM: f64 = 1e-6;
+
+// ---------- 测试函数 ----------
+
+// 三次函数：两个驻点，一个拐点
+pub fn cubic(x: f64) -> f64 {
+    x * x * x - 2.0 * x + 0.5
+}
+
+// 视口内有渐近线 (±π/2)
+pub fn tan(x: f64) -> f64 {
+    x.tan()
+}
+
+// 单位圆 (参数式)
+pub fn circle_curve(t: f64) -> (f64, f64) {
+    (t.cos(), t.sin())
+}
+
+// 半径 1.5 的圆 (隐式)
+pub fn circle_implicit(x: f64, y: f64) -> f64 {
+    x * x + y * y - 2.25
+}
+
+// 半径 1 的球的距离场
+pub fn sphere_sdf(x: f64, y: f64, z: f64) -> f64 {
+    (x * x + y * y + z * z).sqrt() - 1.0
+}
+
+// 环面 R = 2, r = 0.5 (轴为 z)，u 绕轴，v 绕管
+pub fn torus(u: f64, v: f64) -> Vec3 {
+    let rho = 2.0 + 0.5 * v.cos();
+    Vec3::new(rho * u.cos(), rho * u.sin(), 0.5 * v.sin())
+}
+
+// ---------- 摘要 ----------
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub vertices: usize,
+    pub indices: usize,
+    pub bbox: ([f64; 3], [f64; 3]),
+    // 度量名与值 (长度、面积、体积)，按给定顺序输出
+    pub measures: Vec<(&'static str, f64)>,
+    pub hash: u64,
+}
+
+impl Summary {
+    fn from_points(points: &[[f64; 3]], indices: usize, measures: Vec<(&'static str, f64)>) -> Self {
+        let mut bbox = ([f64::INFINITY; 3], [f64::NEG_INFINITY; 3]);
+        for p in points {
+            bbox.0 = std::array::from_fn(|k| bbox.0[k].min(p[k]));
+            bbox.1 = std::array::from_fn(|k| bbox.1[k].max(p[k]));
+        }
+        if points.is_empty() {
+            bbox = ([0.0; 3], [0.0; 3]);
+        }
+        Self { vertices: points.len(), indices, bbox, measures, hash: position_hash(points) }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let corner = |c: &[f64; 3]| format!("{:.6},{:.6},{:.6}", c[0], c[1], c[2]);
+        write!(f, "v={} i={} bbox=[{}]..[{}]", self.vertices, self.indices, corner(&self.bbox.0), corner(&self.bbox.1))?;
+        for (name, value) in &self.measures {
+            // 7 位有效数字：求和顺序不同带来的末位差异不会进入快照
+            write!(f, " {}={:.6e}", name, value)?;
+        }
+        write!(f, " hash={:016x}", self.hash)
+    }
+}
+
+// 量化到 QUANTUM 网格后排序，再做 FNV-1a；-0.0 与 0.0 量化后相同
+pub fn position_hash(points: &[[f64; 3]]) -> u64 {
+    let mut keys = points.iter()
+        .map(|p| p.map(|c| (c / QUANTUM).round() as i64))
+        .collect::<Vec<_>>();
+    keys.sort_unstable();
+    let mut hash = 0xcbf29ce484222325u64;
+    for byte in keys.iter().flatten().flat_map(|c| c.to_le_bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+// 采样折线 (世界坐标)：索引数记为折线条数，度量为总长度
+pub fn summarize_polylines(lines: &[Vec<(f64, f64)>]) -> Summary {
+    let points = lines.iter().flatten().map(|&(x, y)| [x, y, 0.0]).collect::<Vec<_>>();
+    let length = lines.iter()
+        .flat_map(|l| l.windows(2))
+        .map(|w| (w[1].0 - w[0].0).hypot(w[1].1 - w[0].1))
+        .sum();
+    Summary::from_points(&points, lines.len(), vec![("length", length)])
+}
+
+// 三角形列表 (每 3 个顶点一个三角形，无索引)：度量为面积之和
+pub fn summarize_triangles_2d(vertices: &[Vertex]) -> Summary {
+    let points = vertices.iter().map(|v| [v.position[0] as f64, v.position[1] as f64, 0.0]).collect::<Vec<_>>();
+    let area = points.chunks_exact(3)
+        .map(|t| ((t[1][0] - t[0][0]) * (t[2][1] - t[0][1]) - (t[1][1] - t[0][1]) * (t[2][0] - t[0][0])).abs() * 0.5)
+        .sum();
+    Summary::from_points(&points, 0, vec![("area", area)])
+}
+
+// 离散点集 (隐函数曲线与网格边的交点)
+pub fn summarize_points_2d(vertices: &[Vertex]) -> Summary {
+    let points = vertices.iter().map(|v| [v.position[0] as f64, v.position[1] as f64, 0.0]).collect::<Vec<_>>();
+    Summary::from_points(&points, 0, Vec::new())
+}
+
+// 三角网格：度量为表面积与有向体积 (散度定理，闭合且朝外时为正)
+pub fn summarize_mesh(mesh: &MeshData) -> Summary {
+    let points = mesh.vertices.iter().map(|v| v.position.map(|c| c as f64)).collect::<Vec<_>>();
+    let p = |i: u32| {
+        let [x, y, z] = points[i as usize];
+        Vec3::new(x, y, z)
+    };
+    let (mut area, mut volume) = (0.0, 0.0);
+    for t in mesh.indices.chunks_exact(3) {
+        let (a, b, c) = (p(t[0]), p(t[1]), p(t[2]));
+        area += (b - a).cross(c - a).len() * 0.5;
+        volume += a.dot(b.cross(c)) / 6.0;
+    }
+    Summary::from_points(&points, mesh.indices.len(), vec![("area", area), ("volume", volume)])
+}
+
+// ---------- 快照文件 ----------
+
+// 并行运行的测试共用一个快照文件，读改写需要串行
+static SNAPSHOT_LOCK: Mutex<()> = Mutex::new(());
+
+fn snapshot_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SNAPSHOT_FILE)
+}
+
+// 每行 "<名称> <摘要>"，# 开头为注释
+fn read_snapshots() -> BTreeMap<String, String> {
+    let text = fs::read_to_string(snapshot_path()).unwrap_or_default();
+    text.lines()
+        .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+        .filter_map(|l| l.split_once(' '))
+        .map(|(name, summary)| (name.to_string(), summary.to_string()))
+        .collect()
+}
+
+fn write_snapshots(snapshots: &BTreeMap<String, String>) {
+    let mut text = format!("# 求解器快照，由 {}=1 cargo test graph::testing 生成\n", UPDATE_ENV);
+    for (name, summary) in snapshots {
+        text.push_str(&format!("{} {}\n", name, summary));
+    }
+    fs::write(snapshot_path(), text).expect("cannot write snapshot file");
+}
+
+// 与已提交的快照比较；设置了 FOREST_UPDATE_SNAPSHOTS 时改为写入
+pub fn assert_snapshot(name: &str, summary: &Summary) {
+    assert!(!name.contains(char::is_whitespace), "snapshot name must not contain whitespace");
+    let actual = summary.to_string();
+    let _guard = SNAPSHOT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut snapshots = read_snapshots();
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        if snapshots.get(name) != Some(&actual) {
+            snapshots.insert(name.to_string(), actual);
+            write_snapshots(&snapshots);
+        }
+        return;
+    }
+    match snapshots.get(name) {
+        Some(expected) => assert_eq!(
+            &actual, expected,
+            "snapshot '{}' changed; rerun with {}=1 to accept", name, UPDATE_ENV,
+        ),
+        None => panic!("snapshot '{}' missing; rerun with {}=1 to record it", name, UPDATE_ENV),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::TAU;
+
+    use crate::graph::d2::explicit::ExplicitSolver;
+    use crate::graph::d2::implicit::ImplicitSolver;
+    use crate::graph::d2::parametric::{JUMP_THRESHOLD_FACTOR, ParametricSolver};
+    use crate::graph::d3::implicit_surface::ImplicitSurfaceSolver;
+    use crate::graph::d3::SurfaceTopology;
+
+    const SCREEN_W: u32 = 800;
+    const SCREEN_H: f32 = 600.0;
+    const ZOOM: f32 = 0.25;
+    const X_RANGE: (f64, f64) = (-4.0, 4.0);
+    const FULL: (f64, f64) = (f64::NEG_INFINITY, f64::INFINITY);
+    const BOX: (f64, f64) = (-1.5, 1.5);
+
+    #[test]
+    fn test_hash_is_order_independent() {
+        let points = [[0.1, 0.2, 0.3], [-1.0, 2.0, 0.0], [0.5, 0.5, 0.5]];
+        let mut shuffled = points;
+        shuffled.reverse();
+        assert_eq!(position_hash(&points), position_hash(&shuffled));
+        // 量化网格以下的差异不改变哈希，以上的改变
+        assert_eq!(position_hash(&[[0.0, 0.0, 0.0]]), position_hash(&[[-1e-8, 1e-8, -0.0]]));
+        assert_ne!(position_hash(&points), position_hash(&[[0.1, 0.2, 0.3], [-1.0, 2.0, 0.0], [0.5, 0.5, 0.500002]]));
+    }
+
+    #[test]
+    fn snapshot_explicit_cubic() {
+        let lines = ExplicitSolver::new().polylines(&cubic, X_RANGE, FULL, ZOOM, SCREEN_W);
+        assert_snapshot("explicit_cubic_polylines", &summarize_polylines(&lines));
+    }
+
+    #[test]
+    fn snapshot_explicit_cubic_mesh() {
+        let vertices = ExplicitSolver::new().solve(&cubic, X_RANGE, FULL, 2.0, ZOOM, SCREEN_W, SCREEN_H);
+        assert_snapshot("explicit_cubic_mesh", &summarize_triangles_2d(&vertices));
+    }
+
+    #[test]
+    fn snapshot_explicit_tan() {
+        let solver = ExplicitSolver::new();
+        let lines = solver.polylines(&tan, X_RANGE, FULL, ZOOM, SCREEN_W);
+        // 渐近线 ±π/2 处断开
+        assert_eq!(lines.len(), 3);
+        assert_snapshot("explicit_tan_polylines", &summarize_polylines(&lines));
+        let adaptive = solver.polylines_adaptive(&tan, X_RANGE, FULL, ZOOM, SCREEN_W);
+        assert_snapshot("explicit_tan_adaptive", &summarize_polylines(&adaptive));
+    }
+
+    #[test]
+    fn snapshot_parametric_circle() {
+        let solver = ParametricSolver::new();
+        let lines = solver.polylines(&circle_curve, (0.0, TAU), ZOOM, JUMP_THRESHOLD_FACTOR);
+        assert_snapshot("parametric_circle_polylines", &summarize_polylines(&lines));
+        let vertices = solver.solve(&circle_curve, (0.0, TAU), 2.0, ZOOM, 1.0, SCREEN_H, JUMP_THRESHOLD_FACTOR);
+        assert_snapshot("parametric_circle_mesh", &summarize_triangles_2d(&vertices));
+    }
+
+    #[test]
+    fn snapshot_implicit_circle() {
+        let vertices = ImplicitSolver::new().solve(&circle_implicit, (-2.0, 2.0), (-2.0, 2.0), SCREEN_W, SCREEN_H as u32);
+        assert_snapshot("implicit_circle", &summarize_points_2d(&vertices));
+    }
+
+    #[test]
+    fn snapshot_marching_cubes_sphere() {
+        let mesh = ImplicitSurfaceSolver::solve(&sphere_sdf, BOX, BOX, BOX, 32);
+        assert_snapshot("marching_cubes_sphere", &summarize_mesh(&mesh));
+        let adaptive = ImplicitSurfaceSolver::solve_adaptive(&sphere_sdf, BOX, BOX, BOX, 32);
+        assert_snapshot("marching_cubes_sphere_adaptive", &summarize_mesh(&adaptive));
+    }
+
+    #[test]
+    fn snapshot_parametric_surface_torus() {
+        let topology = SurfaceTopology { wrap_u: true, wrap_v: true, collapse_degenerate_rows: false };
+        let mesh = MeshData::new_parametric_surface_with(torus, (0.0, TAU), (0.0, TAU), 48, 24, topology);
+        assert_snapshot("parametric_surface_torus", &summarize_mesh(&mesh));
+    }
+
+    #[test]
+    fn snapshot_revolution_sphere() {
+        let mesh = MeshData::new_sphere(Vec3::ZERO, 1.0, 32).unwrap();
+        assert_snapshot("revolution_sphere", &summarize_mesh(&mesh));
+    }
+}
@@ -1,4 +1,5 @@
 use std::io::{self, Write};
+use std::path::Path;
 use std::time::Instant;
 use crate::pakoo::env::Env;
 use crate::pakoo::math_data::MathData;
@@ -12,6 +13,35 @@ mod test;
 mod pakoo;
 
 fn main() {
+    // 热重载模式：cargo run -- --watch exprs.txt
+    // 会话：cargo run -- --session forest.session (可与 --watch 同用；加 --d3 时保存 3D 相机)
+    let args: Vec<String> = std::env::args().collect();
+    let session = match args.iter().position(|a| a == "--session") {
+        Some(i) => match args.get(i + 1) {
+            Some(path) => Some(Path::new(path)),
+            None => {
+                println!("用法: --session <path>");
+                return;
+            }
+        },
+        None => None,
+    };
+    if let Some(i) = args.iter().position(|a| a == "--watch") {
+        match args.get(i + 1) {
+            Some(path) => graph::d2::watch::run(Path::new(path), session),
+            None => println!("用法: --watch <path>"),
+        }
+        return;
+    }
+    if let Some(session) = session {
+        if args.iter().any(|a| a == "--d3") {
+            test::g23_test::main_d3(Some(session));
+        } else {
+            graph::session::run(session);
+        }
+        return;
+    }
+
     println!("MathForest - Graph by Duo\n欢迎：663251235\n输入测试模式(d2/d3):\n");
 
     // 打印提示符并立即刷新到屏幕
@@ -28,9 +58,52 @@ fn main() {
             println!("d2 demo running");
             test::g23_test::main_d2();
         }
+        "pencil" => {
+            println!("conic pencil demo running");
+            test::g23_test::main_d2_pencil();
+        }
+        "scatter" => {
+            println!("scatter demo running");
+            test::g23_test::main_d2_scatter();
+        }
+        "animate" => {
+            println!("animated d2 demo running");
+            test::g23_test::main_d2_animated();
+        }
+        "heatmap" => {
+            println!("heatmap demo running");
+            test::g23_test::main_d2_heatmap();
+        }
+        "conformal" => {
+            print!("preset (z^2, 1/z, exp(z), ln(z)): ");
+            io::stdout().flush().unwrap();
+            let mut preset = String::new();
+            io::stdin().read_line(&mut preset).expect("无法读取");
+            println!("conformal grid demo running");
+            test::g23_test::main_d2_conformal(preset.trim());
+        }
+        "sampling" => {
+            test::g23_test::explicit_sampling();
+        }
         "d3" => {
             println!("d3 demo running");
-            test::g23_test::main_d3();
+            test::g23_test::main_d3(None);
+        }
+        "instanced" => {
+            println!("instanced d3 demo running");
+            test::g23_test::main_d3_instanced();
+        }
+        "d3_animate" => {
+            println!("d3 transform animation demo running");
+            test::g23_test::main_d3_transform_animation();
+        }
+        "isolines" => {
+            println!("surface isolines demo running");
+            test::g23_test::main_d3_isolines();
+        }
+        "repl" => {
+            println!("pakoo repl (empty line to quit)");
+            test::g23_test::pakoo_repl();
         }
         "ran_test" => {
             for i in 1..6 {
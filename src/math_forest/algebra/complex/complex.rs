@@ -37,6 +37,18 @@ impl Complex {
         }
     }
 
+    /// cis(θ) = cos θ + i·sin θ，即单位圆上辐角为 θ 的点 (from_polar(1, θ) 省去乘 r)
+    #[inline]
+    pub fn cis(theta: f64) -> Self {
+        let (im, re) = theta.sin_cos();
+        Self { re, im }
+    }
+
+    /// n 次单位根 cis(2πk/n)，k = 0..n (n = 0 时为空)
+    pub fn unit_circle_n(n: usize) -> Vec<Complex> {
+        (0..n).map(|k| Self::cis(std::f64::consts::TAU * k as f64 / n as f64)).collect()
+    }
+
     #[inline]
     pub fn len_sq(&self) -> f64 {
         self.re * self.re + self.im * self.im
@@ -119,6 +131,22 @@ impl Complex {
         Self::new(re_part, im_part)
     }
 
+    /// 两个平方根 [主值, -主值]
+    pub fn principal_sqrt_all(self) -> [Complex; 2] {
+        let r = self.sqrt();
+        [r, -r]
+    }
+
+    /// 三个立方根：主值 (辐角 arg/3) 依次旋转 0、2π/3、4π/3
+    pub fn cbrt_all(self) -> [Complex; 3] {
+        if self.is_zero() {
+            return [Self::ZERO; 3];
+        }
+        let r = Self::from_polar(self.len().cbrt(), self.arg() / 3.0);
+        let w = Self::new(-0.5, 0.75f64.sqrt()); // cis(2π/3)
+        [r, r * w, r * w.conj()]
+    }
+
     pub fn sin(self) -> Self {
         Self::new(
             self.re.sin() * self.im.cosh(),
@@ -133,12 +161,175 @@ impl Complex {
         )
     }
 
+    // tan(z) = -i·tanh(iz)，借用 tanh 的稳定公式，|im| 很大时不会出现 inf/inf
     pub fn tan(self) -> Self {
-        let two_re = 2.0 * self.re;
-        let two_im = 2.0 * self.im;
-        let den = two_re.cos() + two_im.cosh();
-        Self::new(two_re.sin() / den, two_im.sinh() / den)
+        self.mul_i().tanh().div_i()
+    }
+
+    // ====================== 乘 ±i (分量交换，避免 0·inf 产生 NaN) ======================
+
+    #[inline]
+    fn mul_i(self) -> Self {
+        Self::new(-self.im, self.re)
     }
+
+    #[inline]
+    fn div_i(self) -> Self {
+        Self::new(self.im, -self.re)
+    }
+
+    // ====================== 对数 / 指数补充 ======================
+
+    // 以实数 base 为底的对数：ln(z) / ln(base)
+    pub fn log(self, base: f64) -> Self {
+        self.ln() / base.ln()
+    }
+
+    // e^z - 1，|z| 很小时避免相消
+    // Re = expm1(x)·cos(y) - 2sin²(y/2)
+    pub fn expm1(self) -> Self {
+        let (sin, cos) = self.im.sin_cos();
+        let half_sin = (self.im * 0.5).sin();
+        Self::new(
+            self.re.exp_m1() * cos - 2.0 * half_sin * half_sin,
+            self.re.exp() * sin,
+        )
+    }
+
+    // ln(1 + z)，|z| 很小时避免相消；割线为 (-inf, -1]
+    pub fn ln_1p(self) -> Self {
+        if self.len_sq() > 0.25 {
+            return (1.0 + self).ln();
+        }
+        // |1+z|² = 1 + (2x + x² + y²)
+        let t = 2.0 * self.re + self.len_sq();
+        Self::new(0.5 * t.ln_1p(), self.im.atan2(1.0 + self.re))
+    }
+
+    // 整数次幂：反复平方，不走 ln/exp，z = 0 与负实数也精确
+    pub fn powi(self, n: i32) -> Self {
+        let mut base = self;
+        let mut k = n.unsigned_abs();
+        let mut acc = Self::ONE;
+        while k > 0 {
+            if k & 1 == 1 {
+                acc *= base;
+            }
+            base *= base;
+            k >>= 1;
+        }
+        if n < 0 { acc.reciprocal() } else { acc }
+    }
+
+    // ====================== 双曲函数 ======================
+
+    // sinh(x+iy) = sinh(x)cos(y) + i·cosh(x)sin(y)
+    pub fn sinh(self) -> Self {
+        let (sin, cos) = self.im.sin_cos();
+        if self.re.abs() > HYPER_LARGE {
+            let s = self.re.signum();
+            return Self::new(s * scaled_half_exp(self.re.abs(), cos), scaled_half_exp(self.re.abs(), sin));
+        }
+        Self::new(self.re.sinh() * cos, self.re.cosh() * sin)
+    }
+
+    // cosh(x+iy) = cosh(x)cos(y) + i·sinh(x)sin(y)
+    pub fn cosh(self) -> Self {
+        let (sin, cos) = self.im.sin_cos();
+        if self.re.abs() > HYPER_LARGE {
+            let s = self.re.signum();
+            return Self::new(scaled_half_exp(self.re.abs(), cos), s * scaled_half_exp(self.re.abs(), sin));
+        }
+        Self::new(self.re.cosh() * cos, self.re.sinh() * sin)
+    }
+
+    // Kahan 公式：t = tan(y), β = 1 + t², s = sinh(x), ρ = √(1 + s²)
+    // tanh = (βρs + i·t) / (1 + βs²)
+    // |x| 很大时 tanh → ±1，虚部按 4·sin(y)cos(y)·e^(-2|x|) 衰减
+    pub fn tanh(self) -> Self {
+        if self.re.abs() > TANH_LARGE {
+            let (sin, cos) = self.im.sin_cos();
+            return Self::new(
+                1.0f64.copysign(self.re),
+                4.0 * sin * cos * (-2.0 * self.re.abs()).exp(),
+            );
+        }
+        let t = self.im.tan();
+        let beta = 1.0 + t * t;
+        let s = self.re.sinh();
+        let rho = (1.0 + s * s).sqrt();
+        let den = 1.0 + beta * s * s;
+        Self::new(beta * rho * s / den, t / den)
+    }
+
+    // ====================== 反三角 / 反双曲 (主值) ======================
+    // 割线约定与 C99 / Kahan 一致，割线上的取值由虚部 ±0.0 决定 (逆时针连续)：
+    //   asin, acos : 实轴 (-inf, -1] 与 [1, inf)
+    //   atan       : 虚轴 (-i·inf, -i] 与 [i, i·inf)，±i 处为 ±i·inf
+    //   asinh      : 虚轴 (-i·inf, -i] 与 [i, i·inf)
+    //   acosh      : 实轴 (-inf, 1]
+    //   atanh      : 实轴 (-inf, -1] 与 [1, inf)，±1 处为 ±inf
+
+    // asin: Re = atan(x / Re(√(1-z)·√(1+z)))，Im = asinh(Im(conj(√(1-z))·√(1+z)))
+    pub fn asin(self) -> Self {
+        let s1 = (1.0 - self).sqrt();
+        let s2 = (1.0 + self).sqrt();
+        Self::new(
+            self.re.atan2(s1.re * s2.re - s1.im * s2.im),
+            (s1.re * s2.im - s1.im * s2.re).asinh(),
+        )
+    }
+
+    // acos: Re = 2·atan(Re√(1-z) / Re√(1+z))，Im = asinh(Im(conj(√(1+z))·√(1-z)))
+    pub fn acos(self) -> Self {
+        let s1 = (1.0 - self).sqrt();
+        let s2 = (1.0 + self).sqrt();
+        Self::new(
+            2.0 * s1.re.atan2(s2.re),
+            (s2.re * s1.im - s2.im * s1.re).asinh(),
+        )
+    }
+
+    // atan(z) = -i·atanh(iz)
+    pub fn atan(self) -> Self {
+        self.mul_i().atanh().div_i()
+    }
+
+    // asinh(z) = -i·asin(iz)
+    pub fn asinh(self) -> Self {
+        self.mul_i().asin().div_i()
+    }
+
+    // acosh: Re = asinh(Re(conj(√(z-1))·√(z+1)))，Im = 2·atan(Im√(z-1) / Re√(z+1))
+    pub fn acosh(self) -> Self {
+        let s1 = (self - 1.0).sqrt();
+        let s2 = (self + 1.0).sqrt();
+        Self::new(
+            (s1.re * s2.re + s1.im * s2.im).asinh(),
+            2.0 * s1.im.atan2(s2.re),
+        )
+    }
+
+    // atanh(z) = [ln(1+z) - ln(1-z)] / 2
+    pub fn atanh(self) -> Self {
+        (self.ln_1p() - (-self).ln_1p()) * 0.5
+    }
+}
+
+// |x| 超过此值时 sinh/cosh 改用 e^|x| / 2 的拆分乘法，避免 cosh(x) 先溢出
+const HYPER_LARGE: f64 = 20.0;
+// |x| 超过此值时 tanh(x) 在 f64 精度下已等于 ±1
+const TANH_LARGE: f64 = 22.0;
+
+// c · e^x / 2，拆成 (c·e^(x/2)/2)·e^(x/2)，结果可表示时中间量不溢出
+// c = 0 时直接返回 0，避免 0·inf
+#[inline]
+fn scaled_half_exp(x: f64, c: f64) -> f64 {
+    if c == 0.0 {
+        return c;
+    }
+    let half = (x * 0.5).exp();
+    (c * half * 0.5) * half
 }
 
 // ====================== 运算符重载 ======================
@@ -326,3 +517,199 @@ impl fmt::Display for Complex {
         write!(f, "{:.4} {} {:.4}i", self.re, sign, self.im.abs())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    const EPS: f64 = 1e-12;
+
+    fn close(a: Complex, b: Complex) -> bool {
+        (a - b).len() < 1e-10 * (1.0 + b.len())
+    }
+
+    #[test]
+    fn test_inverse_roundtrip() {
+        let samples = [
+            Complex::new(0.3, 0.2),
+            Complex::new(-0.5, 0.4),
+            Complex::new(0.1, -0.7),
+            Complex::new(-0.6, -0.3),
+        ];
+        for z in samples {
+            assert!(close(z.asin().sin(), z), "asin {}", z);
+            assert!(close(z.acos().cos(), z), "acos {}", z);
+            assert!(close(z.atan().tan(), z), "atan {}", z);
+            assert!(close(z.asinh().sinh(), z), "asinh {}", z);
+            assert!(close(z.acosh().cosh(), z), "acosh {}", z);
+            assert!(close(z.atanh().tanh(), z), "atanh {}", z);
+        }
+    }
+
+    #[test]
+    fn test_hyperbolic_identity() {
+        for z in [Complex::new(1.0, 1.0), Complex::new(-2.5, 0.7), Complex::new(0.3, -4.0)] {
+            let c = z.cosh();
+            let s = z.sinh();
+            assert!(close(c * c - s * s, Complex::ONE), "{}", z);
+            assert!(close(z.tanh(), s / c), "{}", z);
+        }
+    }
+
+    #[test]
+    fn test_real_axis_matches_f64() {
+        for x in [-0.9, -0.25, 0.0, 0.5, 0.95] {
+            let z = Complex::from_real(x);
+            assert!((z.asin().re - x.asin()).abs() < EPS);
+            assert!((z.acos().re - x.acos()).abs() < EPS);
+            assert!((z.atan().re - x.atan()).abs() < EPS);
+            assert!((z.atanh().re - x.atanh()).abs() < EPS);
+            assert!(z.asin().im.abs() < EPS);
+        }
+        for x in [-3.0, -0.5, 0.0, 1.5, 10.0] {
+            let z = Complex::from_real(x);
+            assert!((z.sinh().re - x.sinh()).abs() < EPS * (1.0 + x.sinh().abs()));
+            assert!((z.cosh().re - x.cosh()).abs() < EPS * x.cosh());
+            assert!((z.tanh().re - x.tanh()).abs() < EPS);
+            assert!((z.asinh().re - x.asinh()).abs() < EPS);
+        }
+        for x in [1.0, 2.0, 50.0] {
+            let z = Complex::from_real(x);
+            assert!((z.acosh().re - x.acosh()).abs() < EPS);
+            assert!(z.acosh().im.abs() < EPS);
+        }
+    }
+
+    #[test]
+    fn test_known_values() {
+        let one_i = Complex::new(1.0, 1.0);
+        let asinh_1 = 1.0f64.asinh();
+
+        assert!(close(Complex::I.asin(), Complex::new(0.0, asinh_1)));
+        assert!(close(Complex::I.acos(), Complex::new(FRAC_PI_2, -asinh_1)));
+        assert!(close(Complex::I.atanh(), Complex::new(0.0, FRAC_PI_4)));
+        assert!(close((-Complex::I).asinh(), Complex::new(0.0, -FRAC_PI_2)));
+
+        assert!(close(one_i.sinh(), Complex::new(0.634963914784736, 1.298457581415977)));
+        assert!(close(one_i.cosh(), Complex::new(0.833730025131149, 0.988897705762865)));
+        assert!(close(one_i.tanh(), Complex::new(1.083923327338694, 0.271752585319512)));
+        assert!(close(one_i.atan(), Complex::new(1.017221967897851, 0.402359478108525)));
+        assert!(close(one_i.asinh(), Complex::new(1.061275061905036, 0.666239432492515)));
+        assert!(close(one_i.acosh(), Complex::new(1.061275061905036, 0.904556894302381)));
+    }
+
+    #[test]
+    fn test_atan_singularities() {
+        // ±i 是 atan 的奇点：结果为 ±i·inf，但不能出现 NaN
+        let up = Complex::I.atan();
+        let down = (-Complex::I).atan();
+        assert!(!up.is_nan() && !down.is_nan());
+        assert_eq!(up.im, f64::INFINITY);
+        assert_eq!(down.im, f64::NEG_INFINITY);
+
+        let one = Complex::ONE.atanh();
+        assert!(!one.is_nan());
+        assert_eq!(one.re, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_branch_cuts() {
+        // 割线两侧由虚部符号区分
+        let above = Complex::new(2.0, 0.0).asin();
+        let below = Complex::new(2.0, -0.0).asin();
+        let acosh_2 = 2.0f64.acosh();
+        assert!(close(above, Complex::new(FRAC_PI_2, acosh_2)));
+        assert!(close(below, Complex::new(FRAC_PI_2, -acosh_2)));
+
+        let neg = Complex::new(-2.0, 0.0).acosh();
+        assert!(close(neg, Complex::new(acosh_2, std::f64::consts::PI)));
+
+        let t = Complex::new(2.0, 0.0).atanh();
+        assert!(close(t, Complex::new(3.0f64.ln() * 0.5, FRAC_PI_2)));
+    }
+
+    #[test]
+    fn test_large_arguments() {
+        // |im| 很大时 tan 收敛到 ±i，不能是 inf/inf
+        let t = Complex::new(1.0, 1000.0).tan();
+        assert!(close(t, Complex::I));
+        let t = Complex::new(1.0, -1000.0).tan();
+        assert!(close(t, -Complex::I));
+
+        // |re| 接近溢出边界：cosh(x) 本身溢出，但乘上 cos(y) 后仍可表示
+        let x = 710.0;
+        let c = Complex::new(x, 2.0).cosh();
+        assert!(c.re.is_finite() && c.im.is_finite());
+        let expect = (x - std::f64::consts::LN_2).exp() * 2.0f64.cos();
+        assert!((c.re / expect - 1.0).abs() < 1e-12);
+
+        assert!(close(Complex::new(800.0, 0.3).tanh(), Complex::ONE));
+        assert_eq!(Complex::new(2000.0, 0.0).sinh().im, 0.0);
+    }
+
+    #[test]
+    fn test_small_arguments() {
+        let z = Complex::new(1e-10, -2e-10);
+        assert!(close(z.expm1(), z + z * z * 0.5));
+        assert!(close(z.ln_1p(), z - z * z * 0.5));
+        assert!(close(Complex::new(0.5, 1.0).expm1(), Complex::new(0.5, 1.0).exp() - 1.0));
+        assert!(close(Complex::new(-3.0, 0.5).ln_1p(), Complex::new(-2.0, 0.5).ln()));
+    }
+
+    #[test]
+    fn test_powi_and_log() {
+        let z = Complex::new(1.0, 1.0);
+        assert!(close(z.powi(2), Complex::new(0.0, 2.0)));
+        assert!(close(z.powi(8), Complex::from_real(16.0)));
+        assert!(close(z.powi(-2), Complex::new(0.0, -0.5)));
+        assert_eq!(z.powi(0), Complex::ONE);
+        assert_eq!(Complex::ZERO.powi(3), Complex::ZERO);
+        assert!(close(Complex::new(-2.0, 0.0).powi(3), Complex::from_real(-8.0)));
+
+        assert!(close(Complex::from_real(1000.0).log(10.0), Complex::from_real(3.0)));
+        assert!(close(Complex::I.log(std::f64::consts::E), Complex::new(0.0, FRAC_PI_2)));
+    }
+
+    #[test]
+    fn test_cis_and_roots_of_unity() {
+        assert_eq!(Complex::cis(0.0), Complex::ONE);
+        assert!(close(Complex::cis(FRAC_PI_2), Complex::I));
+        assert!(close(Complex::cis(0.7), Complex::from_polar(1.0, 0.7)));
+
+        assert!(Complex::unit_circle_n(0).is_empty());
+        for n in [1, 2, 5, 12] {
+            let roots = Complex::unit_circle_n(n);
+            assert_eq!(roots.len(), n);
+            assert_eq!(roots[0], Complex::ONE);
+            for (k, z) in roots.iter().enumerate() {
+                assert!((z.len() - 1.0).abs() < EPS);
+                assert!(close(z.powi(n as i32), Complex::ONE), "n = {}, k = {}", n, k);
+            }
+            // n > 1 时单位根之和为 0
+            let sum = roots.iter().fold(Complex::ZERO, |acc, z| acc + *z);
+            if n > 1 { assert!(sum.len() < 1e-12, "n = {}: sum = {}", n, sum); }
+        }
+        assert!(close(Complex::unit_circle_n(8)[1], Complex::cis(FRAC_PI_4)));
+    }
+
+    #[test]
+    fn test_all_roots() {
+        for z in [Complex::new(3.0, -4.0), Complex::new(-2.0, 0.0), Complex::new(0.0, 1.0), Complex::from_real(8.0)] {
+            let [s1, s2] = z.principal_sqrt_all();
+            assert_eq!(s1, z.sqrt());
+            assert_eq!(s2, -s1);
+            assert!(close(s2 * s2, z));
+
+            let roots = z.cbrt_all();
+            for (k, w) in roots.iter().enumerate() {
+                assert!(close(w.powi(3), z), "z = {}, k = {}", z, k);
+                // 相邻根辐角差 2π/3
+                let next = roots[(k + 1) % 3];
+                assert!(close(next / *w, Complex::cis(std::f64::consts::TAU / 3.0)));
+            }
+        }
+        assert!(close(Complex::from_real(8.0).cbrt_all()[0], Complex::from_real(2.0)));
+        assert_eq!(Complex::ZERO.cbrt_all(), [Complex::ZERO; 3]);
+    }
+}
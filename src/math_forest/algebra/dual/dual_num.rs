@@ -0,0 +1,290 @@
+// src/math_forest/algebra/dual/dual_num.rs
+// 对偶数 a + bε (ε² = 0)：前向自动微分
+// f(a + ε) = f(a) + f'(a)ε，eps 分量即导数，没有差分的截断误差与步长选择问题
+#![allow(dead_code)]
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Dual {
+    pub re: f64,  // 函数值
+    pub eps: f64, // 导数 (无穷小部分的系数)
+}
+
+impl Dual {
+    pub const ZERO: Dual = Dual::new(0.0, 0.0);
+    pub const ONE: Dual = Dual::new(1.0, 0.0);
+
+    #[inline(always)]
+    pub const fn new(re: f64, eps: f64) -> Self {
+        Self { re, eps }
+    }
+
+    /// 常数：导数为 0
+    #[inline(always)]
+    pub const fn constant(re: f64) -> Self {
+        Self { re, eps: 0.0 }
+    }
+
+    /// 自变量：对自身的导数为 1
+    #[inline(always)]
+    pub const fn variable(re: f64) -> Self {
+        Self { re, eps: 1.0 }
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.re.is_nan() || self.eps.is_nan()
+    }
+
+    // 链式法则：值为 f(a)，导数为 f'(a)·b
+    #[inline(always)]
+    fn chain(self, value: f64, derivative: f64) -> Self {
+        Self::new(value, derivative * self.eps)
+    }
+
+    // ====================== 初等函数 ======================
+
+    pub fn recip(self) -> Self {
+        let r = 1.0 / self.re;
+        self.chain(r, -r * r)
+    }
+
+    pub fn sin(self) -> Self {
+        let (sin, cos) = self.re.sin_cos();
+        self.chain(sin, cos)
+    }
+
+    pub fn cos(self) -> Self {
+        let (sin, cos) = self.re.sin_cos();
+        self.chain(cos, -sin)
+    }
+
+    // (tan x)' = 1 + tan² x
+    pub fn tan(self) -> Self {
+        let t = self.re.tan();
+        self.chain(t, 1.0 + t * t)
+    }
+
+    pub fn exp(self) -> Self {
+        let e = self.re.exp();
+        self.chain(e, e)
+    }
+
+    pub fn ln(self) -> Self {
+        self.chain(self.re.ln(), 1.0 / self.re)
+    }
+
+    pub fn sqrt(self) -> Self {
+        let s = self.re.sqrt();
+        self.chain(s, 0.5 / s)
+    }
+
+    // x^n (n 为常数)：n = 0 时导数恒为 0 (避免 0·inf)
+    pub fn powf(self, n: f64) -> Self {
+        if n == 0.0 {
+            return Self::ONE;
+        }
+        self.chain(self.re.powf(n), n * self.re.powf(n - 1.0))
+    }
+
+    // x^y (两者都可含导数)：d(x^y) = x^y (y' ln x + y x'/x)
+    // 指数为常数时退回 powf，底数可以为负
+    pub fn pow(self, other: Dual) -> Self {
+        if other.eps == 0.0 {
+            return self.powf(other.re);
+        }
+        let value = self.re.powf(other.re);
+        Self::new(value, value * (other.eps * self.re.ln() + other.re * self.eps / self.re))
+    }
+
+    // |x|' = sign(x)，x = 0 处取 0
+    pub fn abs(self) -> Self {
+        let sign = if self.re > 0.0 { 1.0 } else if self.re < 0.0 { -1.0 } else { 0.0 };
+        self.chain(self.re.abs(), sign)
+    }
+}
+
+// ====================== 运算符重载 ======================
+
+impl Add for Dual {
+    type Output = Dual;
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self::Output {
+        Dual::new(self.re + rhs.re, self.eps + rhs.eps)
+    }
+}
+
+impl Add<f64> for Dual {
+    type Output = Dual;
+    #[inline(always)]
+    fn add(self, rhs: f64) -> Self::Output {
+        Dual::new(self.re + rhs, self.eps)
+    }
+}
+
+impl Add<Dual> for f64 {
+    type Output = Dual;
+    #[inline(always)]
+    fn add(self, rhs: Dual) -> Dual {
+        rhs + self
+    }
+}
+
+impl Sub for Dual {
+    type Output = Dual;
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Dual::new(self.re - rhs.re, self.eps - rhs.eps)
+    }
+}
+
+impl Sub<f64> for Dual {
+    type Output = Dual;
+    #[inline(always)]
+    fn sub(self, rhs: f64) -> Self::Output {
+        Dual::new(self.re - rhs, self.eps)
+    }
+}
+
+impl Sub<Dual> for f64 {
+    type Output = Dual;
+    #[inline(always)]
+    fn sub(self, rhs: Dual) -> Dual {
+        Dual::new(self - rhs.re, -rhs.eps)
+    }
+}
+
+// (a + bε)(c + dε) = ac + (ad + bc)ε
+impl Mul for Dual {
+    type Output = Dual;
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self::Output {
+        Dual::new(self.re * rhs.re, self.re * rhs.eps + self.eps * rhs.re)
+    }
+}
+
+impl Mul<f64> for Dual {
+    type Output = Dual;
+    #[inline(always)]
+    fn mul(self, rhs: f64) -> Self::Output {
+        Dual::new(self.re * rhs, self.eps * rhs)
+    }
+}
+
+impl Mul<Dual> for f64 {
+    type Output = Dual;
+    #[inline(always)]
+    fn mul(self, rhs: Dual) -> Dual {
+        rhs * self
+    }
+}
+
+// (a + bε) / (c + dε) = a/c + (bc - ad)/c² ε
+impl Div for Dual {
+    type Output = Dual;
+    #[inline(always)]
+    fn div(self, rhs: Self) -> Self::Output {
+        let inv = 1.0 / rhs.re;
+        Dual::new(self.re * inv, (self.eps * rhs.re - self.re * rhs.eps) * inv * inv)
+    }
+}
+
+impl Div<f64> for Dual {
+    type Output = Dual;
+    #[inline(always)]
+    fn div(self, rhs: f64) -> Self::Output {
+        Dual::new(self.re / rhs, self.eps / rhs)
+    }
+}
+
+impl Div<Dual> for f64 {
+    type Output = Dual;
+    #[inline(always)]
+    fn div(self, rhs: Dual) -> Dual {
+        let inv = 1.0 / rhs.re;
+        Dual::new(self * inv, -self * rhs.eps * inv * inv)
+    }
+}
+
+impl Neg for Dual {
+    type Output = Dual;
+    #[inline(always)]
+    fn neg(self) -> Dual {
+        Dual::new(-self.re, -self.eps)
+    }
+}
+
+impl AddAssign for Dual {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Dual {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Dual {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Dual {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl fmt::Display for Dual {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.eps >= 0.0 {
+            write!(f, "{:.4} + {:.4}ε", self.re, self.eps)
+        } else {
+            write!(f, "{:.4} - {:.4}ε", self.re, -self.eps)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-12 * (1.0 + b.abs())
+    }
+
+    #[test]
+    fn test_elementary_derivatives() {
+        let x = 0.7;
+        let v = Dual::variable(x);
+        let cases: [(Dual, f64, f64); 8] = [
+            (v.sin(), x.sin(), x.cos()),
+            (v.cos(), x.cos(), -x.sin()),
+            (v.tan(), x.tan(), 1.0 / (x.cos() * x.cos())),
+            (v.exp(), x.exp(), x.exp()),
+            (v.ln(), x.ln(), 1.0 / x),
+            (v.sqrt(), x.sqrt(), 0.5 / x.sqrt()),
+            (v.powf(3.5), x.powf(3.5), 3.5 * x.powf(2.5)),
+            (v.pow(v), x.powf(x), x.powf(x) * (x.ln() + 1.0)),
+        ];
+        for (d, value, derivative) in cases {
+            assert!(close(d.re, value) && close(d.eps, derivative), "{} vs ({}, {})", d, value, derivative);
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_rules() {
+        let x = Dual::variable(2.0);
+        // (x² + 1) / (x - 3) 在 x = 2：值 -5，导数 (2x(x-3) - (x²+1)) / (x-3)² = -9
+        let f = (x * x + 1.0) / (x - 3.0);
+        assert!(close(f.re, -5.0) && close(f.eps, -9.0));
+        // 常数不带导数
+        let c = Dual::constant(4.0);
+        assert_eq!((c * c - 1.0 / c).eps, 0.0);
+        assert_eq!(-x, Dual::new(-2.0, -1.0));
+    }
+}
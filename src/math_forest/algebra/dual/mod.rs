@@ -0,0 +1 @@
+pub mod dual_num;
@@ -3,6 +3,9 @@
 // 骈数
 use std::fmt;
 use std::ops::{Add, Div, Mul, Neg, Sub};
+use crate::math_forest::algebra::solver::polynomial::solve_real_quadratic_for_real;
+use crate::math_forest::geometry::d2::fertile::d_point::DPoint;
+use crate::math_forest::geometry::d2::linear::line::Line;
 use crate::math_forest::geometry::d2::linear::vec2::Vec2;
 
 //
@@ -34,6 +37,60 @@ impl DNum {
 
     pub const INF: DNum = DNum { n1: f64::INFINITY, n2: f64::INFINITY };
     pub const NAN: DNum = DNum { n1: f64::NAN, n2: f64::NAN };
+
+    /// 实二次方程 a*x^2 + b*x + c = 0 的两根 (见 solve_real_quadratic_for_real)
+    #[inline]
+    pub fn from_quadratic(a: f64, b: f64, c: f64) -> Self {
+        solve_real_quadratic_for_real(a, b, c)
+    }
+
+    // 按升序排列 (n1 <= n2)；含 NaN 时保持原顺序
+    #[inline]
+    pub fn sorted(self) -> Self {
+        if self.n1 > self.n2 { DNum::new(self.n2, self.n1) } else { self }
+    }
+
+    // 交换两值
+    #[inline]
+    pub fn swap(self) -> Self {
+        DNum::new(self.n2, self.n1)
+    }
+
+    // 中值
+    #[inline]
+    pub fn mid(self) -> f64 {
+        (self.n1 + self.n2) * 0.5
+    }
+
+    // 两值之差的绝对值 (区间长度)
+    #[inline]
+    pub fn span(self) -> f64 {
+        (self.n1 - self.n2).abs()
+    }
+
+    // 两值都是有限数 (非 NaN、非无穷)
+    #[inline]
+    pub fn both_finite(self) -> bool {
+        self.n1.is_finite() && self.n2.is_finite()
+    }
+
+    // 对两值分别作用 f
+    #[inline]
+    pub fn map<F: Fn(f64) -> f64>(self, f: F) -> Self {
+        DNum::new(f(self.n1), f(self.n2))
+    }
+
+    // 与另一个骈数逐分量作用 f
+    #[inline]
+    pub fn apply_pairwise<F: Fn(f64, f64) -> f64>(self, other: DNum, f: F) -> Self {
+        DNum::new(f(self.n1, other.n1), f(self.n2, other.n2))
+    }
+
+    // 以两值为参数索引直线，得到骈点
+    #[inline]
+    pub fn to_dpoint_on(self, line: &Line) -> DPoint {
+        line.index_d_point(self)
+    }
 }
 
 // ====================== 运算符 ======================
@@ -141,6 +198,27 @@ impl Div<DNum> for f64 {
     }
 }
 
+// DNum (+ - * /) DNum：逐分量运算
+macro_rules! impl_dnum_pairwise {
+    ($Trait:ident, $method:ident, $op:tt) => {
+        impl $Trait<DNum> for DNum {
+            type Output = DNum;
+            #[inline]
+            fn $method(self, rhs: DNum) -> DNum {
+                DNum {
+                    n1: self.n1 $op rhs.n1,
+                    n2: self.n2 $op rhs.n2,
+                }
+            }
+        }
+    };
+}
+
+impl_dnum_pairwise!(Add, add, +);
+impl_dnum_pairwise!(Sub, sub, -);
+impl_dnum_pairwise!(Mul, mul, *);
+impl_dnum_pairwise!(Div, div, /);
+
 // ====================== 格式输出 ======================
 
 impl fmt::Display for DNum {
@@ -162,6 +240,42 @@ mod tests {
         println!("a = {:?}\nb = {:?}", a, b);
     }
 
+    #[test]
+    fn test_pairwise_ops() {
+        let a = DNum::new(2.0, 3.0);
+        let b = DNum::new(1.0, 4.0);
+        assert_eq!(a + b, DNum::new(3.0, 7.0));
+        assert_eq!(a - b, DNum::new(1.0, -1.0));
+        assert_eq!(a * b, DNum::new(2.0, 12.0));
+        assert_eq!(a / b, DNum::new(2.0, 0.75));
+        assert_eq!(a.apply_pairwise(b, f64::max), DNum::new(2.0, 4.0));
+        assert_eq!(a.map(|x| x * x), DNum::new(4.0, 9.0));
+
+        // NaN 逐分量传播，不污染另一分量
+        let n = DNum::new(f64::NAN, 1.0);
+        for r in [a + n, a - n, a * n, a / n, n.map(f64::sqrt)] {
+            assert!(r.n1.is_nan() && !r.n2.is_nan());
+        }
+        assert!(!n.both_finite() && a.both_finite());
+        assert!(!(a / DNum::new(0.0, 1.0)).both_finite());
+    }
+
+    #[test]
+    fn test_root_helpers() {
+        let r = DNum::from_quadratic(1.0, -1.0, -6.0); // 根 3, -2
+        assert_eq!(r.sorted(), DNum::new(-2.0, 3.0));
+        assert_eq!(r.sorted().sorted(), r.sorted());
+        assert_eq!(r.swap().sorted(), r.sorted());
+        assert_eq!((r.min(), r.max()), (-2.0, 3.0));
+        assert_eq!(r.mid(), 0.5);
+        assert_eq!(r.span(), 5.0);
+        assert!(DNum::from_quadratic(1.0, 0.0, 1.0).mid().is_nan());
+
+        let l = Line::new(Vec2::new(1.0, 1.0), Vec2::new(0.0, 2.0));
+        let dp = r.to_dpoint_on(&l);
+        assert_eq!(dp, DPoint::new(Vec2::new(1.0, 7.0), Vec2::new(1.0, -3.0)));
+    }
+
     #[test]
     fn test_display() {
         let d = DNum::new(1.5, -2.7);
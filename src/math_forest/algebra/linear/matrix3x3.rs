@@ -3,8 +3,14 @@
 
 use std::fmt;
 use std::ops::{Add, Sub, Mul, Neg, AddAssign, SubAssign, MulAssign};
-use crate::math_forest::algebra::solver::linear::solve_linear_3x3;
+use crate::math_forest::algebra::fertile::t_num::TNum;
+use crate::math_forest::algebra::solver::linear::{solve_linear_3x3, solve_linear_n};
 use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+
+// Jacobi 特征值迭代：非对角元范数的收敛阈值 (相对矩阵范数) 与最大扫描轮数
+const JACOBI_TOL: f64 = 1e-10;
+const JACOBI_MAX_SWEEPS: usize = 100;
 
 /// 3x3 矩阵，按行优先存储 (Row-Major)
 /// [ m00, m01, m02 ]
@@ -36,6 +42,20 @@ impl Matrix3x3 {
         }
     }
 
+    /// 从三个列向量构造
+    pub fn from_cols(c0: Vec3, c1: Vec3, c2: Vec3) -> Self {
+        Self::new(
+            c0.x, c1.x, c2.x,
+            c0.y, c1.y, c2.y,
+            c0.z, c1.z, c2.z
+        )
+    }
+
+    /// 第 i 列
+    pub fn col(&self, i: usize) -> Vec3 {
+        Vec3::new(self.m[i], self.m[3 + i], self.m[6 + i])
+    }
+
     // ====================== 2D 仿射变换构造 (Affine Transforms) ======================
 
     /// 构造 2D 平移矩阵
@@ -112,21 +132,101 @@ impl Matrix3x3 {
         )
     }
 
+    /// 伴随矩阵 (代数余子式的转置)，adj(M) = det(M) * M⁻¹
+    /// 齐次坐标下与逆矩阵等价 (相差一个比例)，且奇异时仍有定义
+    pub fn adjugate(&self) -> Self {
+        let m = self.m;
+        Self::new(
+            m[4] * m[8] - m[5] * m[7], m[2] * m[7] - m[1] * m[8], m[1] * m[5] - m[2] * m[4],
+            m[5] * m[6] - m[3] * m[8], m[0] * m[8] - m[2] * m[6], m[2] * m[3] - m[0] * m[5],
+            m[3] * m[7] - m[4] * m[6], m[1] * m[6] - m[0] * m[7], m[0] * m[4] - m[1] * m[3]
+        )
+    }
+
+    /// Gram-Schmidt 正交化 (修正版，逐列减去已得到的分量)：返回列向量严格单位正交的矩阵
+    /// 第一列只做归一化，方向不变；之后每列去掉前面各列的分量
+    /// 某列 (或其去掉分量后的剩余部分) 接近零 —— 即各列线性相关 —— 时返回 None
+    pub fn gram_schmidt(&self) -> Option<Self> {
+        let mut basis: [Vec3; 3] = [Vec3::ZERO; 3];
+        for i in 0..3 {
+            let original = self.col(i);
+            let mut v = original;
+            for e in &basis[..i] {
+                v = v - *e * e.dot(v);
+            }
+            let len = v.len();
+            if len < 1e-10 * original.len().max(1.0) {
+                return None;
+            }
+            basis[i] = v / len;
+        }
+        Some(Self::from_cols(basis[0], basis[1], basis[2]))
+    }
+
+    /// 列向量是否单位正交 (MᵀM 与单位阵逐元素相差不超过 epsilon)
+    pub fn is_orthonormal(&self, epsilon: f64) -> bool {
+        let gram = self.transpose() * *self;
+        gram.m.iter().zip(Self::IDENTITY.m.iter()).all(|(a, b)| (a - b).abs() <= epsilon)
+    }
+
+    /// 实对称矩阵的特征分解 (Jacobi 迭代：逐个用 Givens 旋转消去非对角元)
+    /// 返回 (升序排列的三个特征值, 以对应单位特征向量为列的正交矩阵)，即 A = V diag(λ) Vᵀ
+    /// 矩阵不对称、含非有限值或 100 轮内未收敛时返回 None
+    pub fn eigenvectors_symmetric(&self) -> Option<(TNum, Self)> {
+        let norm = self.m.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if !norm.is_finite() {
+            return None;
+        }
+        let tol = JACOBI_TOL * norm.max(1.0);
+        let at = |a: &Self, i: usize, j: usize| a.m[i * 3 + j];
+        if (0..3).any(|i| (0..i).any(|j| (at(self, i, j) - at(self, j, i)).abs() > tol)) {
+            return None;
+        }
+
+        let mut a = *self;
+        let mut v = Self::IDENTITY;
+        let off = |a: &Self| (2.0 * (at(a, 0, 1).powi(2) + at(a, 0, 2).powi(2) + at(a, 1, 2).powi(2))).sqrt();
+        let mut sweeps = 0;
+        while off(&a) > tol {
+            if sweeps == JACOBI_MAX_SWEEPS {
+                return None;
+            }
+            sweeps += 1;
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                let apq = at(&a, p, q);
+                if apq == 0.0 {
+                    continue;
+                }
+                // 旋转角使 A' = Jᵀ A J 的 (p, q) 元为 0；t = tan φ 取绝对值较小的根
+                let theta = (at(&a, q, q) - at(&a, p, p)) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                let mut j = Self::IDENTITY;
+                j.m[p * 3 + p] = c;
+                j.m[q * 3 + q] = c;
+                j.m[p * 3 + q] = s;
+                j.m[q * 3 + p] = -s;
+                a = j.transpose() * a * j;
+                v *= j;
+            }
+        }
+
+        let mut order = [0, 1, 2];
+        order.sort_by(|&i, &k| at(&a, i, i).total_cmp(&at(&a, k, k)));
+        let [i0, i1, i2] = order;
+        let values = TNum::new(at(&a, i0, i0), at(&a, i1, i1), at(&a, i2, i2));
+        let vectors = Self::from_cols(v.col(i0).unit(), v.col(i1).unit(), v.col(i2).unit());
+        Some((values, vectors))
+    }
+
     /// 求逆矩阵
     pub fn inverse(&self) -> Option<Self> {
         let det = self.det();
         if det.abs() < 1e-12 {
             return None;
         }
-        let inv_det = 1.0 / det;
-        let m = self.m;
-
-        // 计算代数余子式并转置 (Adjugate Matrix)
-        Some(Self::new(
-            (m[4] * m[8] - m[5] * m[7]) * inv_det, (m[2] * m[7] - m[1] * m[8]) * inv_det, (m[1] * m[5] - m[2] * m[4]) * inv_det,
-            (m[5] * m[6] - m[3] * m[8]) * inv_det, (m[0] * m[8] - m[2] * m[6]) * inv_det, (m[2] * m[3] - m[0] * m[5]) * inv_det,
-            (m[3] * m[7] - m[4] * m[6]) * inv_det, (m[1] * m[6] - m[0] * m[7]) * inv_det, (m[0] * m[4] - m[1] * m[3]) * inv_det
-        ))
+        Some(self.adjugate() * (1.0 / det))
     }
 
     // ====================== 应用于 Vec2 (2D 变换) ======================
@@ -142,6 +242,41 @@ impl Matrix3x3 {
         Vec2::new(x, y)
     }
 
+    /// 投影变换 2D 点：(x', y', w') = M (x, y, 1)，返回 (x'/w', y'/w')
+    /// w' ≈ 0 时点被映射到无穷远，返回 Vec2::INF
+    pub fn project_point2(&self, p: Vec2) -> Vec2 {
+        let x = self.m[0] * p.x + self.m[1] * p.y + self.m[2];
+        let y = self.m[3] * p.x + self.m[4] * p.y + self.m[5];
+        let w = self.m[6] * p.x + self.m[7] * p.y + self.m[8];
+        if w.abs() < 1e-12 {
+            return Vec2::INF;
+        }
+        Vec2::new(x / w, y / w)
+    }
+
+    /// 由四组对应点求单应矩阵 H (H * src[i] ∝ dst[i])，归一化使 m22 = 1
+    /// 展开为 8 元线性方程组：
+    ///   x' (g x + h y + 1) = a x + b y + c
+    ///   y' (g x + h y + 1) = d x + e y + f
+    /// 任意三点共线 (退化) 或 H 的 m22 恰为 0 时返回 None
+    pub fn homography_from_quad(src: [Vec2; 4], dst: [Vec2; 4]) -> Option<Self> {
+        let mut a = [[0.0; 8]; 8];
+        let mut b = [0.0; 8];
+        for i in 0..4 {
+            let (p, q) = (src[i], dst[i]);
+            a[2 * i] = [p.x, p.y, 1.0, 0.0, 0.0, 0.0, -q.x * p.x, -q.x * p.y];
+            a[2 * i + 1] = [0.0, 0.0, 0.0, p.x, p.y, 1.0, -q.y * p.x, -q.y * p.y];
+            b[2 * i] = q.x;
+            b[2 * i + 1] = q.y;
+        }
+        let h = solve_linear_n(a, b)?;
+        Some(Self::new(
+            h[0], h[1], h[2],
+            h[3], h[4], h[5],
+            h[6], h[7], 1.0
+        ))
+    }
+
     /// 变换 2D 向量 (视为 (x, y, 0)) - 忽略平移
     /// 结果: (x', y') = (m00*x + m01*y, m10*x + m11*y)
     pub fn transform_vector2(&self, v: Vec2) -> Vec2 {
@@ -244,4 +379,114 @@ impl fmt::Display for Matrix3x3 {
                self.m[3], self.m[4], self.m[5],
                self.m[6], self.m[7], self.m[8])
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: Vec2, b: Vec2) -> bool {
+        (a - b).len() < 1e-9
+    }
+
+    #[test]
+    fn test_homography_from_quad() {
+        let square = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)];
+        let quad = [Vec2::new(-1.0, -0.5), Vec2::new(3.0, 0.0), Vec2::new(2.0, 2.5), Vec2::new(0.0, 1.0)];
+        let h = Matrix3x3::homography_from_quad(square, quad).unwrap();
+        for i in 0..4 {
+            assert!(approx(h.project_point2(square[i]), quad[i]));
+        }
+
+        // 正方形中心 -> 四边形对角线交点 (射影中心)
+        let diag = |p: Vec2, q: Vec2, r: Vec2, s: Vec2| {
+            let (d1, d2) = (q - p, s - r);
+            let t = (r - p).cross(d2) / d1.cross(d2);
+            p + d1 * t
+        };
+        let center = diag(quad[0], quad[2], quad[1], quad[3]);
+        assert!(approx(h.project_point2(Vec2::new(0.5, 0.5)), center));
+
+        // 仿射情形退化为 transform_point2
+        let affine = Matrix3x3::from_transform(Vec2::new(2.0, -1.0), 0.3, Vec2::new(1.5, 0.5));
+        let mapped = square.map(|p| affine.transform_point2(p));
+        let h = Matrix3x3::homography_from_quad(square, mapped).unwrap();
+        assert!(h.m.iter().zip(affine.m.iter()).all(|(a, b)| (a - b).abs() < 1e-9));
+
+        // 三点共线
+        let line = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(2.0, 2.0), Vec2::new(0.0, 1.0)];
+        assert!(Matrix3x3::homography_from_quad(line, quad).is_none());
+    }
+
+    #[test]
+    fn test_project_point2_at_infinity() {
+        // w = x + 1：x = -1 被送到无穷远
+        let h = Matrix3x3::new(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0);
+        assert_eq!(h.project_point2(Vec2::new(-1.0, 3.0)), Vec2::INF);
+        assert!(approx(h.project_point2(Vec2::new(1.0, 2.0)), Vec2::new(0.5, 1.0)));
+        // 逆矩阵与伴随矩阵只差比例
+        let adj = h.adjugate();
+        let inv = h.inverse().unwrap();
+        assert!(adj.m.iter().zip(inv.m.iter()).all(|(a, b)| (a - b * h.det()).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_gram_schmidt() {
+        // 略有误差的旋转矩阵
+        let (s, c) = 0.4f64.sin_cos();
+        let noisy = Matrix3x3::new(
+            c + 1e-4, -s, 2e-5,
+            s, c - 3e-5, 1e-4,
+            -5e-5, 1e-4, 1.0 + 2e-4,
+        );
+        assert!(!noisy.is_orthonormal(1e-6));
+        let fixed = noisy.gram_schmidt().unwrap();
+        assert!(fixed.is_orthonormal(1e-12));
+        assert!(fixed.det() > 0.0); // 保持手性
+        // 第一列方向不变
+        assert!(fixed.col(0).cross(noisy.col(0)).len() < 1e-12);
+        // 已经单位正交的矩阵不变
+        let rot = Matrix3x3::from_cols(Vec3::new(c, s, 0.0), Vec3::new(-s, c, 0.0), Vec3::K);
+        let same = rot.gram_schmidt().unwrap();
+        assert!(same.m.iter().zip(rot.m.iter()).all(|(a, b)| (a - b).abs() < 1e-15));
+
+        // 退化：零列 / 线性相关
+        assert!(Matrix3x3::from_cols(Vec3::ZERO, Vec3::J, Vec3::K).gram_schmidt().is_none());
+        assert!(Matrix3x3::from_cols(Vec3::I, Vec3::J, Vec3::new(1.0, 1.0, 0.0)).gram_schmidt().is_none());
+    }
+
+    #[test]
+    fn test_eigenvectors_symmetric() {
+        // 块对角：特征值 1, 3, 5
+        let a = Matrix3x3::new(2.0, 1.0, 0.0, 1.0, 2.0, 0.0, 0.0, 0.0, 5.0);
+        let (values, vectors) = a.eigenvectors_symmetric().unwrap();
+        assert!((values.n1 - 1.0).abs() < 1e-10 && (values.n2 - 3.0).abs() < 1e-10 && (values.n3 - 5.0).abs() < 1e-10);
+        assert!(vectors.col(2).cross(Vec3::K).len() < 1e-10);
+
+        // 一般对称矩阵：A v = λ v，V 单位正交，特征值之和 = 迹
+        let a = Matrix3x3::new(4.0, -2.0, 1.5, -2.0, 3.0, 0.7, 1.5, 0.7, -1.0);
+        let (values, vectors) = a.eigenvectors_symmetric().unwrap();
+        assert!(values.n1 <= values.n2 && values.n2 <= values.n3);
+        assert!(vectors.is_orthonormal(1e-12));
+        for (i, lambda) in [values.n1, values.n2, values.n3].into_iter().enumerate() {
+            let v = vectors.col(i);
+            let av = Vec3::new(
+                a.m[0] * v.x + a.m[1] * v.y + a.m[2] * v.z,
+                a.m[3] * v.x + a.m[4] * v.y + a.m[5] * v.z,
+                a.m[6] * v.x + a.m[7] * v.y + a.m[8] * v.z,
+            );
+            assert!(av.dis(v * lambda) < 1e-9);
+        }
+        assert!((values.n1 + values.n2 + values.n3 - a.trace()).abs() < 1e-12);
+
+        // 对角阵只需排序；重根也能得到正交基
+        let (values, _) = Matrix3x3::new(3.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 2.0).eigenvectors_symmetric().unwrap();
+        assert_eq!((values.n1, values.n2, values.n3), (-1.0, 2.0, 3.0));
+        let (values, vectors) = Matrix3x3::IDENTITY.eigenvectors_symmetric().unwrap();
+        assert_eq!(values, TNum::all(1.0));
+        assert!(vectors.is_orthonormal(1e-12));
+
+        // 不对称 / 非有限值
+        assert!(Matrix3x3::new(1.0, 2.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0).eigenvectors_symmetric().is_none());
+        assert!(Matrix3x3::new(f64::NAN, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0).eigenvectors_symmetric().is_none());
+    }
+}
@@ -3,6 +3,7 @@
 
 use std::fmt;
 use std::ops::{Add, Sub, Mul, Neg, AddAssign, SubAssign, MulAssign};
+use crate::math_forest::algebra::linear::quaternion::Quaternion;
 use crate::math_forest::algebra::solver::linear::{det4x4, solve_linear_4x4};
 use crate::math_forest::geometry::d3::linear::vec3::Vec3;
 
@@ -112,7 +113,7 @@ impl Matrix4x4 {
     }
 
     /// 构造复合变换: T * R * S (先缩放，再旋转，再平移)
-    /// 注意：由于暂无 Quat，这里 rotation 使用 Axis-Angle
+    /// rotation 使用 Axis-Angle (四元数见 Quaternion::to_matrix4x4)
     pub fn from_scale_rotation_translation(scale: Vec3, axis: Vec3, angle: f64, translation: Vec3) -> Self {
         let t_mat = Self::from_translation(translation);
         let r_mat = Self::from_axis_angle(axis, angle);
@@ -122,6 +123,17 @@ impl Matrix4x4 {
         t_mat * r_mat * s_mat
     }
 
+    /// 朝向矩阵 (只含旋转)：把物体局部 +X 转到 forward，局部 +Y 尽量贴近 up
+    /// 用于箭头、广告牌等需要"面朝某方向"而非"看向某点"的物体
+    /// 列向量依次为 forward, up', right (right = forward × up)，from_look_dir(I, J) 为单位阵
+    /// forward 与 up 平行时结果为 NaN
+    pub fn from_look_dir(forward: Vec3, up: Vec3) -> Self {
+        let f = forward.unit();
+        let r = f.cross(up).unit(); // right
+        let u = r.cross(f);         // 实际的 up
+        Self::from_cols(f, u, r, Vec3::ZERO)
+    }
+
     // ====================== 投影与相机矩阵 (Graphics) ======================
 
     /// [Graphics] 构造 LookAt 矩阵 (右手坐标系 Right-Handed)
@@ -164,6 +176,20 @@ impl Matrix4x4 {
         )
     }
 
+    /// [Graphics] 透视投影 (右手系, Vulkan / Metal / WGPU 风格 [0, 1] 深度)
+    /// 近平面映射到 0，远平面映射到 1 (配合 Depth32Float + Less 比较)
+    pub fn perspective_rh_vk(fov_y_radians: f64, aspect_ratio: f64, z_near: f64, z_far: f64) -> Self {
+        let inv_len = 1.0 / (z_near - z_far);
+        let f = 1.0 / (0.5 * fov_y_radians).tan();
+
+        Self::new(
+            f / aspect_ratio, 0.0,  0.0, 0.0,
+            0.0,              f,    0.0, 0.0,
+            0.0,              0.0,  z_far * inv_len, (z_near * z_far) * inv_len,
+            0.0,              0.0, -1.0, 0.0
+        )
+    }
+
     /// [Graphics] 正交投影 (右手系, OpenGL 风格)
     pub fn orthographic_rh_gl(left: f64, right: f64, bottom: f64, top: f64, near: f64, far: f64) -> Self {
         let w_inv = 1.0 / (right - left);
@@ -301,6 +327,48 @@ impl Matrix4x4 {
             m[12], m[13], m[14], m[15], e4,
         )
     }
+
+    // ====================== 插值 (动画) ======================
+
+    /// 是否为纯旋转：左上角 3x3 正交且 det ≈ 1，平移为 0，最后一行为 (0, 0, 0, 1)
+    pub fn is_rotation(&self, epsilon: f64) -> bool {
+        let m = self.m;
+        let affine = [m[3], m[7], m[11], m[12], m[13], m[14]].iter().all(|v| v.abs() <= epsilon)
+            && (m[15] - 1.0).abs() <= epsilon;
+        if !affine {
+            return false;
+        }
+        // RᵀR = I (列两两正交且为单位长度)
+        let col = |j: usize| Vec3::new(m[j], m[4 + j], m[8 + j]);
+        let orthonormal = (0..3).all(|i| (i..3).all(|j| {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            (col(i).dot(col(j)) - expected).abs() <= epsilon
+        }));
+        orthonormal && (self.det() - 1.0).abs() <= epsilon
+    }
+
+    /// 变换插值：t = 0 为 self，t = 1 为 other
+    /// 两者都是刚体变换 (旋转 + 平移) 时，旋转部分用四元数 slerp，平移部分线性插值，中间结果仍是刚体变换
+    /// 否则 (含缩放、切变、投影) 退化为逐元素线性插值
+    pub fn lerp(&self, other: Matrix4x4, t: f64) -> Matrix4x4 {
+        const EPSILON: f64 = 1e-9;
+        let (ra, ta) = self.split_translation();
+        let (rb, tb) = other.split_translation();
+        if ra.is_rotation(EPSILON) && rb.is_rotation(EPSILON) {
+            let q = Quaternion::from_rotation_matrix(&ra).slerp(Quaternion::from_rotation_matrix(&rb), t);
+            return Self::from_translation(ta + (tb - ta) * t) * q.to_matrix4x4();
+        }
+        *self + (other - *self) * t
+    }
+
+    // 拆成 (去掉平移的矩阵, 平移)，满足 self = T * R
+    fn split_translation(&self) -> (Matrix4x4, Vec3) {
+        let mut linear = *self;
+        linear.m[3] = 0.0;
+        linear.m[7] = 0.0;
+        linear.m[11] = 0.0;
+        (linear, Vec3::new(self.m[3], self.m[7], self.m[11]))
+    }
 }
 
 // ====================== 运算符重载 ======================
@@ -385,4 +453,85 @@ impl fmt::Display for Matrix4x4 {
                self.m[8], self.m[9], self.m[10], self.m[11],
                self.m[12], self.m[13], self.m[14], self.m[15])
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perspective_depth_ranges() {
+        let (near, far) = (0.1, 1000.0);
+        let vk = Matrix4x4::perspective_rh_vk(1.0, 1.5, near, far);
+        let gl = Matrix4x4::perspective_rh_gl(1.0, 1.5, near, far);
+        // 右手系：相机看向 -z
+        let depth = |m: &Matrix4x4, d: f64| m.project_point3(Vec3::new(0.0, 0.0, -d)).z;
+
+        assert!(depth(&vk, near).abs() < 1e-9);
+        assert!((depth(&vk, far) - 1.0).abs() < 1e-9);
+        assert!((depth(&gl, near) + 1.0).abs() < 1e-9);
+        assert!((depth(&gl, far) - 1.0).abs() < 1e-9);
+
+        // 深度单调递增，x / y 与 OpenGL 版本一致
+        assert!(depth(&vk, 1.0) < depth(&vk, 10.0));
+        let p = Vec3::new(0.3, -0.7, -5.0);
+        let (a, b) = (vk.project_point3(p), gl.project_point3(p));
+        assert!((a.x - b.x).abs() < 1e-12 && (a.y - b.y).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_from_look_dir() {
+        let close = |a: &Matrix4x4, b: &Matrix4x4| a.m.iter().zip(b.m).all(|(x, y)| (x - y).abs() < 1e-12);
+        assert!(close(&Matrix4x4::from_look_dir(Vec3::I, Vec3::J), &Matrix4x4::IDENTITY));
+
+        // 看向 +z：绕 y 轴转 90 度
+        let m = Matrix4x4::from_look_dir(Vec3::K, Vec3::J);
+        assert!(close(&m, &Matrix4x4::from_axis_angle(Vec3::J, -std::f64::consts::FRAC_PI_2)));
+        assert!((m.transform_vector3(Vec3::I) - Vec3::K).len() < 1e-12);
+        assert!((m.det() - 1.0).abs() < 1e-12);
+
+        // up 不必与 forward 垂直，也不必归一化
+        let m = Matrix4x4::from_look_dir(Vec3::new(1.0, 1.0, 0.0), Vec3::new(0.0, 3.0, 0.0));
+        let f = m.transform_vector3(Vec3::I);
+        assert!((f - Vec3::new(1.0, 1.0, 0.0).unit()).len() < 1e-12);
+        assert!(m.transform_vector3(Vec3::J).dot(f).abs() < 1e-12);
+        assert!((m.det() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_is_rotation() {
+        let eps = 1e-9;
+        assert!(Matrix4x4::IDENTITY.is_rotation(eps));
+        assert!(Matrix4x4::from_axis_angle(Vec3::new(1.0, 2.0, 3.0), 2.0).is_rotation(eps));
+        // 镜像 (det = -1)、缩放、平移都不是纯旋转
+        assert!(!Matrix4x4::from_scale(Vec3::new(-1.0, 1.0, 1.0)).is_rotation(eps));
+        assert!(!Matrix4x4::from_scale(Vec3::new(2.0, 2.0, 2.0)).is_rotation(eps));
+        assert!(!Matrix4x4::from_translation(Vec3::I).is_rotation(eps));
+        assert!(!Matrix4x4::perspective_rh_vk(1.0, 1.0, 0.1, 10.0).is_rotation(eps));
+    }
+
+    #[test]
+    fn test_lerp_rigid_and_fallback() {
+        use std::f64::consts::PI;
+        let close = |a: &Matrix4x4, b: &Matrix4x4| a.m.iter().zip(b.m).all(|(x, y)| (x - y).abs() < 1e-12);
+
+        // 纯旋转：中点是半角旋转，仍然正交 (逐元素插值在 180° 时会退化成奇异矩阵)
+        let a = Matrix4x4::IDENTITY;
+        let b = Matrix4x4::from_axis_angle(Vec3::K, PI);
+        let mid = a.lerp(b, 0.5);
+        assert!(close(&mid, &Matrix4x4::from_axis_angle(Vec3::K, PI / 2.0)));
+        assert!(mid.is_rotation(1e-12));
+        assert!(close(&a.lerp(b, 0.0), &a) && close(&a.lerp(b, 1.0), &b));
+
+        // 刚体变换：旋转 slerp，平移线性插值
+        let a = Matrix4x4::from_translation(Vec3::new(0.0, 0.0, 2.0));
+        let b = Matrix4x4::from_scale_rotation_translation(Vec3::ONE, Vec3::J, 1.0, Vec3::new(4.0, 0.0, 2.0));
+        let m = a.lerp(b, 0.25);
+        let expected = Matrix4x4::from_scale_rotation_translation(Vec3::ONE, Vec3::J, 0.25, Vec3::new(1.0, 0.0, 2.0));
+        assert!(close(&m, &expected));
+
+        // 含缩放：逐元素插值
+        let s = Matrix4x4::from_scale(Vec3::new(3.0, 3.0, 3.0));
+        let m = Matrix4x4::IDENTITY.lerp(s, 0.5);
+        assert!(close(&m, &Matrix4x4::from_scale(Vec3::new(2.0, 2.0, 2.0))));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod matrix2x2;
 pub mod matrix3x3;
 pub mod matrix4x4;
+pub mod quaternion;
@@ -0,0 +1,206 @@
+// src/math_forest/algebra/linear/quaternion.rs
+#![allow(dead_code)]
+
+use std::fmt;
+use std::ops::{Mul, Neg};
+use crate::math_forest::algebra::linear::matrix4x4::Matrix4x4;
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+
+/// 四元数 q = w + xi + yj + zk
+/// 单位四元数表示三维旋转 (q 与 -q 表示同一旋转)，约定与 Matrix4x4 相同：右手系，列向量 (M * v)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion {
+    pub w: f64, // 实部
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+// 夹角小于此值时 slerp 退化为归一化线性插值 (避免除以 sin θ ≈ 0)
+const SLERP_LINEAR_THRESHOLD: f64 = 0.9995;
+
+impl Quaternion {
+    pub const IDENTITY: Quaternion = Quaternion::new(1.0, 0.0, 0.0, 0.0);
+
+    #[inline(always)]
+    pub const fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// 绕 axis 旋转 angle 弧度 (与 Matrix4x4::from_axis_angle 一致)
+    pub fn from_axis_angle(axis: Vec3, angle: f64) -> Self {
+        let axis = axis.unit();
+        let (sin, cos) = (angle * 0.5).sin_cos();
+        Self::new(cos, axis.x * sin, axis.y * sin, axis.z * sin)
+    }
+
+    /// 从矩阵左上角 3x3 的旋转部分提取 (Shepperd 方法：按最大的对角组合选分支，保证数值稳定)
+    /// 调用方需保证该部分是旋转矩阵 (见 Matrix4x4::is_rotation)，平移部分被忽略
+    pub fn from_rotation_matrix(m: &Matrix4x4) -> Self {
+        let m = m.m;
+        let (m00, m01, m02) = (m[0], m[1], m[2]);
+        let (m10, m11, m12) = (m[4], m[5], m[6]);
+        let (m20, m21, m22) = (m[8], m[9], m[10]);
+        let trace = m00 + m11 + m22;
+
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0; // s = 4w
+            Self::new(0.25 * s, (m21 - m12) / s, (m02 - m20) / s, (m10 - m01) / s)
+        } else if m00 > m11 && m00 > m22 {
+            let s = (1.0 + m00 - m11 - m22).sqrt() * 2.0; // s = 4x
+            Self::new((m21 - m12) / s, 0.25 * s, (m01 + m10) / s, (m02 + m20) / s)
+        } else if m11 > m22 {
+            let s = (1.0 + m11 - m00 - m22).sqrt() * 2.0; // s = 4y
+            Self::new((m02 - m20) / s, (m01 + m10) / s, 0.25 * s, (m12 + m21) / s)
+        } else {
+            let s = (1.0 + m22 - m00 - m11).sqrt() * 2.0; // s = 4z
+            Self::new((m10 - m01) / s, (m02 + m20) / s, (m12 + m21) / s, 0.25 * s)
+        };
+        q.normalize()
+    }
+
+    /// 转为 4x4 旋转矩阵 (平移为 0)；q 无需预先归一化
+    pub fn to_matrix4x4(self) -> Matrix4x4 {
+        let Quaternion { w, x, y, z } = self.normalize();
+        Matrix4x4::new(
+            1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z),       2.0 * (x * z + w * y),       0.0,
+            2.0 * (x * y + w * z),       1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x),       0.0,
+            2.0 * (x * z - w * y),       2.0 * (y * z + w * x),       1.0 - 2.0 * (x * x + y * y), 0.0,
+            0.0,                         0.0,                         0.0,                         1.0
+        )
+    }
+
+    #[inline]
+    pub fn dot(&self, other: Quaternion) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    #[inline]
+    pub fn len(&self) -> f64 {
+        self.dot(*self).sqrt()
+    }
+
+    /// 归一化；零四元数返回 NaN
+    pub fn normalize(&self) -> Self {
+        let inv = 1.0 / self.len();
+        Self::new(self.w * inv, self.x * inv, self.y * inv, self.z * inv)
+    }
+
+    #[inline]
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// 旋转向量：q v q* (q 需为单位四元数)
+    pub fn rotate(&self, v: Vec3) -> Vec3 {
+        let p = *self * Quaternion::new(0.0, v.x, v.y, v.z) * self.conjugate();
+        Vec3::new(p.x, p.y, p.z)
+    }
+
+    /// 球面线性插值：t = 0 为 self，t = 1 为 other，沿最短弧以匀角速度旋转
+    pub fn slerp(&self, other: Quaternion, t: f64) -> Self {
+        let a = self.normalize();
+        let mut b = other.normalize();
+        let mut cos = a.dot(b);
+        // q 与 -q 是同一旋转：取夹角不超过 90° 的那个，走短弧
+        if cos < 0.0 {
+            b = -b;
+            cos = -cos;
+        }
+
+        let (ka, kb) = if cos > SLERP_LINEAR_THRESHOLD {
+            (1.0 - t, t)
+        } else {
+            let theta = cos.acos();
+            let inv_sin = 1.0 / theta.sin();
+            (((1.0 - t) * theta).sin() * inv_sin, (t * theta).sin() * inv_sin)
+        };
+        Self::new(
+            a.w * ka + b.w * kb,
+            a.x * ka + b.x * kb,
+            a.y * ka + b.y * kb,
+            a.z * ka + b.z * kb,
+        ).normalize()
+    }
+}
+
+// Hamilton 积：(p * q) 表示先做 q 再做 p
+impl Mul for Quaternion {
+    type Output = Self;
+    fn mul(self, q: Self) -> Self {
+        let p = self;
+        Self::new(
+            p.w * q.w - p.x * q.x - p.y * q.y - p.z * q.z,
+            p.w * q.x + p.x * q.w + p.y * q.z - p.z * q.y,
+            p.w * q.y - p.x * q.z + p.y * q.w + p.z * q.x,
+            p.w * q.z + p.x * q.y - p.y * q.x + p.z * q.w,
+        )
+    }
+}
+
+impl Neg for Quaternion {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.w, -self.x, -self.y, -self.z)
+    }
+}
+
+impl fmt::Display for Quaternion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Quat({} + {}i + {}j + {}k)", self.w, self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn close(a: &Matrix4x4, b: &Matrix4x4) -> bool {
+        a.m.iter().zip(b.m).all(|(x, y)| (x - y).abs() < 1e-12)
+    }
+
+    #[test]
+    fn test_matrix_roundtrip() {
+        let axes = [Vec3::I, Vec3::J, Vec3::K, Vec3::new(1.0, -2.0, 0.5)];
+        // 覆盖 Shepperd 方法的四个分支 (迹为正 / 180° 时按最大对角元)
+        for axis in axes {
+            for angle in [0.0, 0.3, FRAC_PI_2, 2.5, PI] {
+                let m = Matrix4x4::from_axis_angle(axis, angle);
+                let q = Quaternion::from_axis_angle(axis, angle);
+                assert!(close(&q.to_matrix4x4(), &m), "axis {:?}, angle {}", axis, angle);
+
+                let back = Quaternion::from_rotation_matrix(&m);
+                assert!(close(&back.to_matrix4x4(), &m));
+                // 同一旋转只差符号
+                assert!((back.dot(q).abs() - 1.0).abs() < 1e-12);
+            }
+        }
+
+        let v = Vec3::new(0.2, 0.7, -1.0);
+        let q = Quaternion::from_axis_angle(Vec3::new(1.0, 1.0, 1.0), 1.0);
+        assert!((q.rotate(v) - q.to_matrix4x4().transform_vector3(v)).len() < 1e-12);
+    }
+
+    #[test]
+    fn test_slerp() {
+        let a = Quaternion::from_axis_angle(Vec3::K, 0.2);
+        let b = Quaternion::from_axis_angle(Vec3::K, 1.4);
+        assert!((a.slerp(b, 0.0).dot(a) - 1.0).abs() < 1e-12);
+        assert!((a.slerp(b, 1.0).dot(b) - 1.0).abs() < 1e-12);
+        // 同轴旋转：角度线性变化
+        let mid = a.slerp(b, 0.25);
+        assert!((mid.dot(Quaternion::from_axis_angle(Vec3::K, 0.5)) - 1.0).abs() < 1e-12);
+
+        // 走短弧：350° 与 10° 之间的中点是 0°，而不是 180°
+        let a = Quaternion::from_axis_angle(Vec3::J, -10f64.to_radians());
+        let b = Quaternion::from_axis_angle(Vec3::J, 10f64.to_radians());
+        let c = Quaternion::from_axis_angle(Vec3::J, 350f64.to_radians());
+        assert!((c.slerp(b, 0.5).dot(Quaternion::IDENTITY).abs() - 1.0).abs() < 1e-12);
+        assert!((a.slerp(b, 0.5).dot(Quaternion::IDENTITY) - 1.0).abs() < 1e-12);
+
+        // 几乎相同的旋转走线性分支，仍是单位四元数
+        let d = Quaternion::from_axis_angle(Vec3::J, 1e-6);
+        assert!((Quaternion::IDENTITY.slerp(d, 0.5).len() - 1.0).abs() < 1e-12);
+    }
+}
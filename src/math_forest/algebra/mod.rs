@@ -9,5 +9,8 @@ pub mod solver;
 
 //
 pub mod complex;
+
+//
+pub mod dual;
 mod function;
 mod range;
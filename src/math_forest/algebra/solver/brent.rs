@@ -0,0 +1,113 @@
+// src/math_forest/algebra/solver/brent.rs
+#![allow(dead_code)]
+
+// Brent 法：在已知变号区间 [a, b] 内求 f 的根
+// 反二次插值 / 割线法加速，步长不合格时退回二分，保证收敛
+const BRENT_MAX_ITER: usize = 100;
+
+/// 在 [a, b] 内求 f(x) = 0 的根，精度 tol
+/// 要求 f(a)、f(b) 异号 (或其一为 0)；不满足或出现非有限值时返回 None
+pub fn find_root_bracketed<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: f64) -> Option<f64> {
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if !fa.is_finite() || !fb.is_finite() {
+        return None;
+    }
+    if fa == 0.0 {
+        return Some(a);
+    }
+    if fb == 0.0 {
+        return Some(b);
+    }
+    if fa.signum() == fb.signum() {
+        return None;
+    }
+
+    // c 与 b 始终夹住根；d 为本次步长，e 为上上次步长
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+    for _ in 0..BRENT_MAX_ITER {
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        // 保证 b 是当前最好的估计
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tol1 = 2.0 * f64::EPSILON * b.abs() + 0.5 * tol;
+        let xm = 0.5 * (c - b);
+        if xm.abs() <= tol1 || fb == 0.0 {
+            return Some(b);
+        }
+
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            // 尝试插值：a == c 时为割线，否则为反二次插值
+            let s = fb / fa;
+            let (mut p, mut q);
+            if a == c {
+                p = 2.0 * xm * s;
+                q = 1.0 - s;
+            } else {
+                let qa = fa / fc;
+                let r = fb / fc;
+                p = s * (2.0 * xm * qa * (qa - r) - (b - a) * (r - 1.0));
+                q = (qa - 1.0) * (r - 1.0) * (s - 1.0);
+            }
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            // 插值点需落在区间内且收缩足够快，否则二分
+            let min1 = 3.0 * xm * q - (tol1 * q).abs();
+            let min2 = (e * q).abs();
+            if 2.0 * p < min1.min(min2) {
+                e = d;
+                d = p / q;
+            } else {
+                d = xm;
+                e = d;
+            }
+        } else {
+            d = xm;
+            e = d;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol1 { d } else { tol1.copysign(xm) };
+        fb = f(b);
+        if !fb.is_finite() {
+            return None;
+        }
+    }
+    Some(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_brent_roots() {
+        // x^3 - 2x - 5 = 0 (Wallis 的经典例子)
+        let r = find_root_bracketed(|x| x * x * x - 2.0 * x - 5.0, 2.0, 3.0, 1e-12).unwrap();
+        assert!((r - 2.0945514815423265).abs() < 1e-12);
+        // 端点恰为根
+        assert_eq!(find_root_bracketed(|x| x - 1.0, 1.0, 2.0, 1e-12), Some(1.0));
+        // cos x = x
+        let r = find_root_bracketed(|x| x.cos() - x, 0.0, 1.0, 1e-12).unwrap();
+        assert!((r.cos() - r).abs() < 1e-12);
+        // 未夹住根
+        assert_eq!(find_root_bracketed(|x| x * x + 1.0, -1.0, 1.0, 1e-12), None);
+    }
+}
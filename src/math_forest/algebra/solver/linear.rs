@@ -114,4 +114,54 @@ pub fn solve_linear_4x4(
     let dw = det4x4(a1, b1, c1, e1, a2, b2, c2, e2, a3, b3, c3, e3, a4, b4, c4, e4);
 
     (dx * inv_det, dy * inv_det, dz * inv_det, dw * inv_det)
-}
\ No newline at end of file
+}
+// 求解 N 元一次线性方程组 Ax = b (列主元高斯消元)
+// 主元相对矩阵最大元过小 (奇异) 时返回 None
+pub fn solve_linear_n<const N: usize>(mut a: [[f64; N]; N], mut b: [f64; N]) -> Option<[f64; N]> {
+    let scale = a.iter().flatten().fold(0.0f64, |m, v| m.max(v.abs()));
+    if scale == 0.0 || !scale.is_finite() { return None; }
+
+    for col in 0..N {
+        // 选主元
+        let pivot = (col..N).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < EPSILON * scale { return None; }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let (top, rest) = a.split_at_mut(col + 1);
+        let pivot_row = &top[col];
+        for (offset, row) in rest.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            if factor == 0.0 { continue; }
+            for (v, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *v -= factor * p;
+            }
+            b[col + 1 + offset] -= factor * b[col];
+        }
+    }
+
+    // 回代
+    let mut x = [0.0; N];
+    for row in (0..N).rev() {
+        let tail: f64 = (row + 1..N).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_linear_n() {
+        // 需要换行的主元 (a[0][0] = 0)
+        let a = [[0.0, 2.0, 1.0], [1.0, -1.0, 0.0], [3.0, 0.0, 2.0]];
+        let x = solve_linear_n(a, [5.0, -1.0, 9.0]).unwrap();
+        let expect = solve_linear_3x3(0.0, 2.0, 1.0, 5.0, 1.0, -1.0, 0.0, -1.0, 3.0, 0.0, 2.0, 9.0);
+        assert!((x[0] - expect.0).abs() < 1e-12 && (x[1] - expect.1).abs() < 1e-12 && (x[2] - expect.2).abs() < 1e-12);
+
+        // 奇异
+        assert!(solve_linear_n([[1.0, 2.0], [2.0, 4.0]], [1.0, 2.0]).is_none());
+    }
+}
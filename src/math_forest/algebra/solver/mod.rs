@@ -2,3 +2,5 @@ pub mod linear;
 pub mod trigonometric;
 pub mod polynomial;
 pub mod nt;
+pub mod brent;
+pub mod predicates;
@@ -29,12 +29,47 @@ pub fn solve_real_quadratic_for_real(a: f64, b: f64, c: f64) -> DNum {
     }
 
     let sqrt_delta = delta.sqrt();
-    let inv_2a = 1.0 / (2.0 * a);
 
-    // 经典公式
-    let n1 = (-b + sqrt_delta) * inv_2a;
-    let n2 = (-b - sqrt_delta) * inv_2a;
-    DNum::new(n1, n2)
+    // 稳定公式：q = -(b + sign(b)·sqrt(delta)) / 2 不做相近数相减
+    // 一个根为 q / a，另一个由韦达定理 x1·x2 = c / a 得 c / q
+    // 经典公式在 |b| >> sqrt(delta) 时，(-b ± sqrt_delta) 中有一支会灾难性相消
+    let q = -0.5 * (b + sqrt_delta.copysign(b));
+    let (n1, n2) = if q == 0.0 {
+        (0.0, 0.0) // b = 0 且 delta = 0 => c = 0，二重根 0
+    } else if b >= 0.0 {
+        // q / a 对应 (-b - sqrt_delta) / 2a
+        (c / q, q / a)
+    } else {
+        // q / a 对应 (-b + sqrt_delta) / 2a
+        (q / a, c / q)
+    };
+
+    // 牛顿法抛光，顺序与经典公式一致: n1 取 +sqrt_delta，n2 取 -sqrt_delta
+    DNum::new(
+        polish_quadratic_root(a, b, c, n1, QUADRATIC_POLISH_ITER),
+        polish_quadratic_root(a, b, c, n2, QUADRATIC_POLISH_ITER),
+    )
+}
+
+// 实二次方程求根后的牛顿迭代次数
+const QUADRATIC_POLISH_ITER: usize = 2;
+
+/// 牛顿法抛光 a*x^2 + b*x + c = 0 的一个近似根
+/// 只接受使残差减小的步，导数接近 0 (二重根附近) 时停止
+pub fn polish_quadratic_root(a: f64, b: f64, c: f64, x: f64, iter: usize) -> f64 {
+    let f = |x: f64| (a * x + b) * x + c;
+    let mut x = x;
+    let mut fx = f(x);
+    for _ in 0..iter {
+        let df = 2.0 * a * x + b;
+        if fx == 0.0 || df.abs() < 1e-300 { break; }
+        let x_new = x - fx / df;
+        let f_new = f(x_new);
+        if f_new.abs() >= fx.abs() { break; }
+        x = x_new;
+        fx = f_new;
+    }
+    x
 }
 
 /// 实系数二次方程 -> 复数解 (DComplex)
@@ -110,20 +145,21 @@ pub fn solve_cubic(a: Complex, b: Complex, c: Complex, d: Complex) -> TComplex {
     // 变换: x = t - p/3
     let p_div_3 = p / 3.0;
     let m = q - p * p_div_3;
-    let n = p_div_3 * (q * 2.0 - p * p_div_3 * 2.0) - r; // 简化后的常数项计算
-    // 原式: (2 p^3)/27 - (p q)/3 + r => 这里的 n 是负的原常数项吗？
-    // 让我们用标准公式: y^3 + py + q = 0 (这里的 p,q 指 depressed 的参数)
-    // depressed_p = (3ac - b^2) / 3a^2 = q - p^2/3. (你的 m) matches.
-    // depressed_q = (2b^3 - 9abc + 27a^2d) / 27a^3.
-    // 你的 n 计算: (2p^3 - 9pq + 27r) / 27. Matches.
+    // n = 2p^3/27 - pq/3 + r = (p/3)(2(p/3)^2 - q) + r
+    let n = p_div_3 * (p_div_3 * p_div_3 * 2.0 - q) + r;
+    // 标准公式: y^3 + py + q = 0 (这里的 p,q 指 depressed 的参数)
+    // depressed_p = (3ac - b^2) / 3a^2 = q - p^2/3. (m)
+    // depressed_q = (2b^3 - 9abc + 27a^2d) / 27a^3. (n)
 
     // 判别式 Delta = (n/2)^2 + (m/3)^3
     let delta = (n / 2.0).powf(2.0) + (m / 3.0).powf(3.0);
     let sqrt_delta = delta.sqrt();
 
     // Cardano 公式
-    // u = (-n/2 + sqrt_delta)^(1/3)
-    let u = (-n / 2.0 + sqrt_delta).powf(1.0 / 3.0);
+    // u = (-n/2 ± sqrt_delta)^(1/3)，取模较大的一支，避免相消得到 u = 0
+    let w1 = -n / 2.0 + sqrt_delta;
+    let w2 = -n / 2.0 - sqrt_delta;
+    let u = w1.max(w2).powf(1.0 / 3.0);
 
     // [重要修正] v 不能直接由 (-n/2 - sqrt_delta) 开根号得到，
     // 因为必须满足 u * v = -m/3 的约束。
@@ -194,7 +230,8 @@ pub fn solve_quartic(a: Complex, b: Complex, c: Complex, d: Complex, e: Complex)
     let cubic_d = -(bb * bb);
 
     let cubic_roots = solve_cubic(cubic_a, cubic_b, cubic_c, cubic_d);
-    let m = cubic_roots.n1; // 取任意一个非零实根通常更好，这里取 n1
+    // 取模最大的根，保证 m != 0 (B != 0 时预解方程必有非零根)
+    let m = cubic_roots.n1.max(cubic_roots.n2).max(cubic_roots.n3);
 
     // 求解两个二次方程
     // y^2 + sqrt(2m) y + (m + A/2 + B/sqrt(8m)) = 0
@@ -226,4 +263,80 @@ pub fn solve_quartic(a: Complex, b: Complex, c: Complex, d: Complex, e: Complex)
         roots2.n1 - p_div_4,
         roots2.n2 - p_div_4,
     )
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quadratic_order_and_values() {
+        // (x - 3)(x + 2) = x^2 - x - 6；n1 对应 +sqrt(delta)
+        let r = solve_real_quadratic_for_real(1.0, -1.0, -6.0);
+        assert_eq!((r.n1, r.n2), (3.0, -2.0));
+        // 负 a 时顺序同样按经典公式
+        let r = solve_real_quadratic_for_real(-1.0, 1.0, 6.0);
+        assert_eq!((r.n1, r.n2), (-2.0, 3.0));
+        // 二重根
+        let r = solve_real_quadratic_for_real(1.0, 0.0, 0.0);
+        assert_eq!((r.n1, r.n2), (0.0, 0.0));
+        let r = solve_real_quadratic_for_real(1.0, -2.0, 1.0);
+        assert_eq!((r.n1, r.n2), (1.0, 1.0));
+        assert!(solve_real_quadratic_for_real(1.0, 0.0, 1.0).n1.is_nan());
+    }
+
+    #[test]
+    fn test_quadratic_ill_conditioned() {
+        // x^2 + 1e8 x + 1 = 0: 根约为 -1e-8 与 -1e8
+        // 经典公式的小根 (-b + sqrt_delta) / 2 会丢失全部有效数字
+        let (a, b, c) = (1.0, 1e8, 1.0);
+        let r = solve_real_quadratic_for_real(a, b, c);
+        let small = r.n1;
+        let big = r.n2;
+        let exact_small = -1e-8; // 精确值 -1e-8 - 1e-24，f64 下即 -1e-8
+        assert!(((small - exact_small) / exact_small).abs() < 1e-15, "{}", small);
+        assert!(((big + 1e8) / 1e8).abs() < 1e-15, "{}", big);
+
+        // 对照：经典公式的相对误差远大于此
+        let naive = (-b + (b * b - 4.0 * a * c).sqrt()) / (2.0 * a);
+        assert!(((naive - exact_small) / exact_small).abs() > 1e-3);
+
+        // b < 0 的对称情形
+        let r = solve_real_quadratic_for_real(1.0, -1e8, 1.0);
+        assert!(((r.n1 - 1e8) / 1e8).abs() < 1e-15);
+        assert!(((r.n2 - 1e-8) / 1e-8).abs() < 1e-15);
+    }
+
+    fn residual_cubic(a: f64, b: f64, c: f64, d: f64, x: Complex) -> f64 {
+        (x * x * x * a + x * x * b + x * c + d).len()
+    }
+
+    #[test]
+    fn test_cubic_roots() {
+        // (x - 1)(x - 2)(x + 3) = x^3 - 7x + 6
+        // x^3 - 1 = 0 (u 分支相消的情形)
+        // 2x^3 - 3x^2 + 4x - 5 = 0
+        for (a, b, c, d) in [(1.0, 0.0, -7.0, 6.0), (1.0, 0.0, 0.0, -1.0), (2.0, -3.0, 4.0, -5.0)] {
+            let r = solve_cubic(
+                Complex::from_real(a), Complex::from_real(b), Complex::from_real(c), Complex::from_real(d),
+            );
+            for x in [r.n1, r.n2, r.n3] {
+                assert!(residual_cubic(a, b, c, d, x) < 1e-9, "{} for {:?}", x, (a, b, c, d));
+            }
+        }
+    }
+
+    #[test]
+    fn test_quartic_roots() {
+        // y^4 - y = 0 -> 0 与三个立方单位根
+        // (x^2 - 1)(x^2 - 4) = x^4 - 5x^2 + 4
+        // x^4 + 2x^3 - 3x + 7
+        for k in [[1.0, 0.0, 0.0, -1.0, 0.0], [1.0, 0.0, -5.0, 0.0, 4.0], [1.0, 2.0, 0.0, -3.0, 7.0]] {
+            let c = |i: usize| Complex::from_real(k[i]);
+            let r = solve_quartic(c(0), c(1), c(2), c(3), c(4));
+            for x in [r.n1, r.n2, r.n3, r.n4] {
+                let v = x * x * x * x * k[0] + x * x * x * k[1] + x * x * k[2] + x * k[3] + k[4];
+                assert!(v.len() < 1e-9, "{} for {:?}", x, k);
+            }
+        }
+    }
+}
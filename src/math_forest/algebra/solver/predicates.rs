@@ -0,0 +1,315 @@
+// src/math_forest/algebra/solver/predicates.rs
+// 鲁棒几何谓词 (Shewchuk 自适应精度)：返回值的符号总是精确的
+//   先按普通 f64 计算并与误差上界比较，能确定符号时直接返回 (绝大多数输入)
+//   否则改用扩展精度的"展开式"精确计算：一个数表示为若干互不重叠的 f64 之和
+// 展开式按绝对值从小到大存放，零分量被消去；基本运算 two_sum / two_product 无舍入误差
+#![allow(dead_code)]
+
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+
+// 半个 ulp (2^-53)
+const EPSILON: f64 = f64::EPSILON * 0.5;
+// Dekker 拆分常数 2^27 + 1
+const SPLITTER: f64 = 134217729.0;
+// 快速估计的相对误差上界 (Shewchuk, "Adaptive Precision Floating-Point Arithmetic", 1997)
+const CCW_ERRBOUND_A: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const ICC_ERRBOUND_A: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+// ====================== 无误差变换 ======================
+
+/// a + b = x + y，x = fl(a + b)，y 为舍入误差 (精确)
+#[inline]
+pub fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+// 要求 |a| >= |b|，比 two_sum 少三次运算
+#[inline]
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+#[inline]
+pub fn two_diff(a: f64, b: f64) -> (f64, f64) {
+    two_sum(a, -b)
+}
+
+// 把 a 拆成高低两半，各自不超过 26 位有效数字 (两半相乘无舍入)
+#[inline]
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let hi = c - (c - a);
+    (hi, a - hi)
+}
+
+/// a * b = x + y，x = fl(a * b)，y 为舍入误差 (精确，Dekker 算法)
+#[inline]
+pub fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    let (a_hi, a_lo) = split(a);
+    let (b_hi, b_lo) = split(b);
+    let err = ((x - a_hi * b_hi) - a_lo * b_hi) - a_hi * b_lo;
+    (x, a_lo * b_lo - err)
+}
+
+// ====================== 展开式运算 ======================
+
+// a - b 的精确值 (两个分量)
+fn diff_expansion(a: f64, b: f64) -> Vec<f64> {
+    let (x, y) = two_diff(a, b);
+    compress([y, x].into_iter())
+}
+
+// 去掉零分量；全为零时保留一个 0
+fn compress(parts: impl Iterator<Item = f64>) -> Vec<f64> {
+    let h: Vec<f64> = parts.filter(|&v| v != 0.0).collect();
+    if h.is_empty() { vec![0.0] } else { h }
+}
+
+// e + f (Shewchuk 的 fast_expansion_sum_zeroelim：按绝对值归并后逐个累加)
+fn expansion_sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let mut merged = Vec::with_capacity(e.len() + f.len());
+    let (mut i, mut j) = (0, 0);
+    while i < e.len() && j < f.len() {
+        if e[i].abs() < f[j].abs() {
+            merged.push(e[i]);
+            i += 1;
+        } else {
+            merged.push(f[j]);
+            j += 1;
+        }
+    }
+    merged.extend_from_slice(&e[i..]);
+    merged.extend_from_slice(&f[j..]);
+
+    let mut h = Vec::with_capacity(merged.len());
+    let mut q = merged[0];
+    for &g in &merged[1..] {
+        let (sum, err) = two_sum(q, g);
+        if err != 0.0 { h.push(err); }
+        q = sum;
+    }
+    if q != 0.0 || h.is_empty() { h.push(q); }
+    h
+}
+
+// e * b (scale_expansion_zeroelim)
+fn scale_expansion(e: &[f64], b: f64) -> Vec<f64> {
+    let mut h = Vec::with_capacity(e.len() * 2);
+    let (mut q, err) = two_product(e[0], b);
+    if err != 0.0 { h.push(err); }
+    for &x in &e[1..] {
+        let (p1, p0) = two_product(x, b);
+        let (sum, err) = two_sum(q, p0);
+        if err != 0.0 { h.push(err); }
+        let (next, err) = fast_two_sum(p1, sum);
+        if err != 0.0 { h.push(err); }
+        q = next;
+    }
+    if q != 0.0 || h.is_empty() { h.push(q); }
+    h
+}
+
+fn expansion_product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(vec![0.0], |acc, &b| expansion_sum(&acc, &scale_expansion(e, b)))
+}
+
+fn expansion_diff(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let neg: Vec<f64> = f.iter().map(|v| -v).collect();
+    expansion_sum(e, &neg)
+}
+
+// 展开式的近似值：从小到大累加，符号与精确值相同
+fn estimate(e: &[f64]) -> f64 {
+    e.iter().sum()
+}
+
+// ====================== 谓词 ======================
+
+/// 有向面积的两倍 (b - a) × (c - a)：> 0 为逆时针，< 0 为顺时针，= 0 共线
+/// 符号精确；数值在快速路径上与直接计算一致
+pub fn orient2d(a: Vec2, b: Vec2, c: Vec2) -> f64 {
+    let det_left = (a.x - c.x) * (b.y - c.y);
+    let det_right = (a.y - c.y) * (b.x - c.x);
+    let det = det_left - det_right;
+
+    // 两项异号时没有相消，结果符号一定正确
+    let det_sum = if det_left > 0.0 {
+        if det_right <= 0.0 { return det; }
+        det_left + det_right
+    } else if det_left < 0.0 {
+        if det_right >= 0.0 { return det; }
+        -det_left - det_right
+    } else {
+        return det;
+    };
+
+    let bound = CCW_ERRBOUND_A * det_sum;
+    if det >= bound || -det >= bound {
+        return det;
+    }
+    orient2d_exact(a, b, c)
+}
+
+fn orient2d_exact(a: Vec2, b: Vec2, c: Vec2) -> f64 {
+    let (acx, acy) = (diff_expansion(a.x, c.x), diff_expansion(a.y, c.y));
+    let (bcx, bcy) = (diff_expansion(b.x, c.x), diff_expansion(b.y, c.y));
+    estimate(&expansion_diff(&expansion_product(&acx, &bcy), &expansion_product(&acy, &bcx)))
+}
+
+/// d 相对于过 a, b, c 的圆：> 0 在圆内，< 0 在圆外，= 0 四点共圆 (a, b, c 需为逆时针，顺时针时符号相反)
+pub fn incircle(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> f64 {
+    let (adx, ady) = (a.x - d.x, a.y - d.y);
+    let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+    let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+
+    let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+    let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+    let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+    let alift = adx * adx + ady * ady;
+    let blift = bdx * bdx + bdy * bdy;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    let bound = ICC_ERRBOUND_A * permanent;
+    if det > bound || -det > bound {
+        return det;
+    }
+    incircle_exact(a, b, c, d)
+}
+
+fn incircle_exact(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> f64 {
+    let (adx, ady) = (diff_expansion(a.x, d.x), diff_expansion(a.y, d.y));
+    let (bdx, bdy) = (diff_expansion(b.x, d.x), diff_expansion(b.y, d.y));
+    let (cdx, cdy) = (diff_expansion(c.x, d.x), diff_expansion(c.y, d.y));
+
+    let cross = |ux: &[f64], uy: &[f64], vx: &[f64], vy: &[f64]| {
+        expansion_diff(&expansion_product(ux, vy), &expansion_product(vx, uy))
+    };
+    let lift = |x: &[f64], y: &[f64]| expansion_sum(&expansion_product(x, x), &expansion_product(y, y));
+
+    let a_term = expansion_product(&lift(&adx, &ady), &cross(&bdx, &bdy, &cdx, &cdy));
+    let b_term = expansion_product(&lift(&bdx, &bdy), &cross(&cdx, &cdy, &adx, &ady));
+    let c_term = expansion_product(&lift(&cdx, &cdy), &cross(&adx, &ady, &bdx, &bdy));
+    estimate(&expansion_sum(&expansion_sum(&a_term, &b_term), &c_term))
+}
+
+/// a, b, c 是否严格逆时针
+pub fn is_ccw(a: Vec2, b: Vec2, c: Vec2) -> bool {
+    orient2d(a, b, c) > 0.0
+}
+
+/// 三点是否精确共线 (不带容差)
+pub fn is_collinear(a: Vec2, b: Vec2, c: Vec2) -> bool {
+    orient2d(a, b, c) == 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn naive_orient(a: Vec2, b: Vec2, c: Vec2) -> f64 {
+        (a.x - c.x) * (b.y - c.y) - (a.y - c.y) * (b.x - c.x)
+    }
+
+    #[test]
+    fn test_error_free_transforms() {
+        let (x, y) = two_sum(1.0, 1e-20);
+        assert_eq!((x, y), (1.0, 1e-20));
+        let a = 1.0 + f64::EPSILON;
+        let (x, y) = two_product(a, a);
+        // (1 + ε)² = 1 + 2ε + ε²，ε² 落在误差项里
+        assert_eq!(x, 1.0 + 2.0 * f64::EPSILON);
+        assert_eq!(y, f64::EPSILON * f64::EPSILON);
+
+        let e = expansion_sum(&[1e-30, 1.0], &[-1.0]);
+        assert_eq!(e, vec![1e-30]);
+        assert_eq!(estimate(&scale_expansion(&[1e-30, 1.0], 3.0)), 3.0 + 3e-30);
+    }
+
+    #[test]
+    fn test_orient2d_last_ulp() {
+        // Kettner 等人的经典反例：p 在 0.5 附近按 ulp 网格移动，q, r 在直线 y = x 上
+        // 精确符号为 sign(j - i) (p 在直线上方时 p, q, r 逆时针)
+        let ulp = 0.5f64 * f64::EPSILON;
+        let (q, r) = (Vec2::new(12.0, 12.0), Vec2::new(24.0, 24.0));
+        let mut naive_wrong = 0;
+        for i in 0..64i32 {
+            for j in 0..64 {
+                let p = Vec2::new(0.5 + i as f64 * ulp, 0.5 + j as f64 * ulp);
+                let expected = (j - i).signum();
+                let o = orient2d(p, q, r);
+                assert_eq!(o.partial_cmp(&0.0).unwrap() as i32, expected, "i = {}, j = {}", i, j);
+                assert_eq!(is_collinear(p, q, r), i == j);
+                assert_eq!(is_ccw(p, q, r), j > i);
+                if (naive_orient(p, q, r).partial_cmp(&0.0).unwrap() as i32) != expected {
+                    naive_wrong += 1;
+                }
+            }
+        }
+        // 直接计算在这组输入上会给出错误的符号
+        assert!(naive_wrong > 0);
+    }
+
+    #[test]
+    fn test_exact_collinear_and_cocircular() {
+        // 直线 y = 0.75x + 0.5 上的二进制有理点 (坐标精确可表示)，平移到远处后仍精确共线
+        let on_line = |t: f64| Vec2::new(t, 0.75 * t + 0.5);
+        for offset in [0.0, 1e6, -3.0e9] {
+            let shift = Vec2::new(offset, offset);
+            let (a, b, c) = (on_line(0.125) + shift, on_line(1024.25) + shift, on_line(-7.5) + shift);
+            assert_eq!(orient2d(a, b, c), 0.0);
+            assert!(is_collinear(c, a, b));
+        }
+
+        // 半径 5 的圆上的整点 (3-4-5)：远离原点时直接计算的平方项丢失低位
+        for offset in [0.0, 1e7, 1e10] {
+            let p = |x: f64, y: f64| Vec2::new(x + offset, y + offset);
+            let (a, b, c) = (p(5.0, 0.0), p(0.0, 5.0), p(-5.0, 0.0));
+            assert_eq!(incircle(a, b, c, p(3.0, 4.0)), 0.0);
+            assert_eq!(incircle(a, b, c, p(-4.0, -3.0)), 0.0);
+            assert!(incircle(a, b, c, p(0.0, 0.0)) > 0.0);
+            assert!(incircle(a, b, c, p(6.0, 0.0)) < 0.0);
+            // 顺时针时符号相反
+            assert!(incircle(c, b, a, p(0.0, 0.0)) < 0.0);
+        }
+
+        // 圆上一点沿径向移动一个 ulp：圆内为正，圆外为负
+        let (a, b, c) = (Vec2::new(5.0, 0.0), Vec2::new(0.0, 5.0), Vec2::new(-5.0, 0.0));
+        let d = Vec2::new(3.0, 4.0);
+        let inside = Vec2::new(3.0, 4.0f64.next_down());
+        let outside = Vec2::new(3.0, 4.0f64.next_up());
+        assert_eq!(incircle(a, b, c, d), 0.0);
+        assert!(incircle(a, b, c, inside) > 0.0);
+        assert!(incircle(a, b, c, outside) < 0.0);
+    }
+
+    #[test]
+    fn test_agrees_with_naive_when_separated() {
+        let mut rng = StdRng::seed_from_u64(1366);
+        let mut p = || Vec2::new(rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0));
+        for _ in 0..2000 {
+            let (a, b, c, d) = (p(), p(), p(), p());
+            let naive = naive_orient(a, b, c);
+            if naive.abs() > 1e-6 {
+                assert_eq!(orient2d(a, b, c), naive);
+            }
+            // 随机输入上精确路径与快速路径一致
+            assert_eq!(orient2d(a, b, c).signum(), orient2d_exact(a, b, c).signum());
+            let fast = incircle(a, b, c, d);
+            if fast.abs() > 1e-6 {
+                assert_eq!(fast.signum(), incircle_exact(a, b, c, d).signum());
+                assert!((fast - incircle_exact(a, b, c, d)).abs() <= 1e-9 * fast.abs().max(1.0));
+            }
+        }
+    }
+}
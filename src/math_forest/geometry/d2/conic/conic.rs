@@ -3,8 +3,12 @@
 
 use std::fmt;
 use crate::math_forest::algebra::solver::linear::{det4x4, solve_linear_2x2};
-use crate::math_forest::algebra::solver::polynomial::solve_real_quadratic_for_real;
+use crate::math_forest::algebra::solver::polynomial::{solve_real_quadratic_for_real, solve_complex_quadratic_for_complex, solve_quartic};
+use crate::math_forest::algebra::complex::complex::Complex;
+use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
+use crate::math_forest::algebra::complex::q_complex::QComplex;
 use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
 use crate::math_forest::geometry::d2::linear::line::Line;
 use crate::math_forest::geometry::d2::conic::h_line::HLine;
 use crate::math_forest::geometry::d2::conic::x_line::XLine;
@@ -12,6 +16,7 @@ use crate::math_forest::geometry::d2::conic::circle::Circle;
 use crate::math_forest::geometry::d2::conic::ellipse::Ellipse;
 use crate::math_forest::geometry::d2::conic::hyperbola::Hyperbola;
 use crate::math_forest::geometry::d2::conic::parabola::Parabola;
+use crate::math_forest::geometry::d2::conic::wipkyy::Wipkyy;
 use crate::math_forest::geometry::d2::fertile::d_point::DPoint;
 
 /// 圆锥曲线类型枚举
@@ -28,7 +33,8 @@ pub enum ConicType {
     Line,               // 单直线 (退化抛物线)
     ParallelLines,      // 平行双直线 (退化抛物线)
     IntersectingLines,  // 交叉双直线 (退化双曲线 - XLine)
-    Imaginary,          // 虚空 (无实数解)
+    Imaginary,          // 虚空 (无实数解)，对应 Wipkyy::empty
+    Everything,         // 方程恒为零 (整个平面)，对应 Wipkyy::everything
 }
 
 /// 通用圆锥曲线方程: Ax^2 + Bxy + Cy^2 + Dx + Ey + F = 0
@@ -91,8 +97,138 @@ impl Conic {
         Self::new(a, b, c, d, e, f)
     }
 
+    /// 焦点-准线构造：|PF| = e · dist(P, L)
+    /// 0 < e < 1 椭圆，e = 1 抛物线，e > 1 双曲线
+    /// 设准线单位法向 n、有向距离 s(P) = n·P + c0，展开 |P - F|^2 - e^2 s(P)^2 = 0
+    pub fn from_focus_directrix(focus: Vec2, directrix: Line, e: f64) -> Self {
+        let n = directrix.v.roll90().unit();
+        let c0 = -n.dot(directrix.p);
+        let e2 = e * e;
+
+        Self::new(
+            1.0 - e2 * n.x * n.x,
+            -2.0 * e2 * n.x * n.y,
+            1.0 - e2 * n.y * n.y,
+            -2.0 * focus.x - 2.0 * e2 * n.x * c0,
+            -2.0 * focus.y - 2.0 * e2 * n.y * c0,
+            focus.pow2() - e2 * c0 * c0,
+        )
+    }
+
+    /// 圆: (x - px)^2 + (y - py)^2 - r^2 = 0
+    pub fn from_circle(c: &Circle) -> Self {
+        Self::new(1.0, 0.0, 1.0, -2.0 * c.p.x, -2.0 * c.p.y, c.p.pow2() - c.r * c.r)
+    }
+
+    /// 椭圆 (见 Ellipse::to_conic)
+    pub fn from_ellipse(el: &Ellipse) -> Self {
+        el.to_conic()
+    }
+
+    /// 双曲线 P(t) = P + tU + V/t
+    /// 由 d = X - P 得 d × V = t (U × V)，U × d = (U × V) / t，相乘消去 t：
+    /// (d × V)(U × d) = (U × V)^2，整体除以 (U × V)^2 归一化
+    pub fn from_hyperbola(h: &Hyperbola) -> Self {
+        let (u, v) = (h.u, h.v);
+        let k2 = u.cross(v).powi(2);
+
+        // 关于 d 的二次型系数
+        let a = -u.y * v.y / k2;
+        let b = (u.x * v.y + u.y * v.x) / k2;
+        let c = -u.x * v.x / k2;
+
+        Self::from_centered_quadratic(a, b, c, 0.0, 0.0, -1.0, h.p)
+    }
+
+    /// 抛物线 P(t) = P + tU + (t^2/4)V，U ⊥ V 且 |U| = |V|
+    /// 由 d·U = t|U|^2、d·V = (t^2/4)|V|^2 消去 t：(d·U)^2 - 4|V|^2 (d·V) = 0，整体除以 |V|^4
+    pub fn from_parabola(pa: &Parabola) -> Self {
+        let u = pa.u();
+        let v = pa.v;
+        let l2 = v.pow2();
+        let q = 1.0 / (l2 * l2);
+        let l = 4.0 / l2;
+
+        Self::from_centered_quadratic(
+            u.x * u.x * q, 2.0 * u.x * u.y * q, u.y * u.y * q,
+            -l * v.x, -l * v.y, 0.0,
+            pa.p,
+        )
+    }
+
+    /// 两直线之积: (n1·(X - p1)) (n2·(X - p2)) = 0，n 为单位法向
+    /// 取单位法向后 eval 等于点到两直线有向距离之积
+    pub fn from_line_pair(l1: &Line, l2: &Line) -> Self {
+        // 单条直线 a x + b y + c = 0
+        let coeffs = |l: &Line| {
+            let n = l.v.roll90().unit();
+            (n.x, n.y, -n.dot(l.p))
+        };
+        let (a1, b1, c1) = coeffs(l1);
+        let (a2, b2, c2) = coeffs(l2);
+
+        Self::new(
+            a1 * a2,
+            a1 * b2 + a2 * b1,
+            b1 * b2,
+            a1 * c2 + a2 * c1,
+            b1 * c2 + b2 * c1,
+            c1 * c2,
+        )
+    }
+
+    /// 过四点的圆锥曲线束中的两个退化成员 (按两种方式把四点配对连线)：
+    /// (p1p2)(p3p4) 与 (p2p3)(p4p1)，已归一化；束中任一成员为 Conic::lerp(a, b, λ)
+    /// 四点中有三点共线时束退化，结果可能相同或为零
+    pub fn pencil_through_four_points(p1: Vec2, p2: Vec2, p3: Vec2, p4: Vec2) -> (Conic, Conic) {
+        let a = Self::from_line_pair(&Line::from_two_points(p1, p2), &Line::from_two_points(p3, p4));
+        let b = Self::from_line_pair(&Line::from_two_points(p2, p3), &Line::from_two_points(p4, p1));
+        (a.normalized(), b.normalized())
+    }
+
+    /// 曲线束 λA + (1 - λ)B：两端先归一化再逐系数插值，结果量级不随 A、B 的比例漂移
+    /// λ = 1 为 A.normalized()，λ = 0 为 B.normalized()
+    pub fn lerp(a: &Conic, b: &Conic, lambda: f64) -> Conic {
+        let (a, b) = (a.normalized(), b.normalized());
+        let mix = |x: f64, y: f64| lambda * x + (1.0 - lambda) * y;
+        Self::new(mix(a.a, b.a), mix(a.b, b.b), mix(a.c, b.c), mix(a.d, b.d), mix(a.e, b.e), mix(a.f, b.f))
+    }
+
+    /// 叉线 (见 XLine::to_conic)
+    pub fn from_x_line(xl: &XLine) -> Self {
+        xl.to_conic()
+    }
+
+    /// 平行双直线 (见 HLine::to_conic)
+    pub fn from_h_line(hl: &HLine) -> Self {
+        hl.to_conic()
+    }
+
+    // 以 p 为原点给出的方程 (d = X - p 的系数) 平移回世界坐标
+    fn from_centered_quadratic(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64, p: Vec2) -> Self {
+        Self::new(
+            a, b, c,
+            d - 2.0 * a * p.x - b * p.y,
+            e - b * p.x - 2.0 * c * p.y,
+            f + a * p.x * p.x + b * p.x * p.y + c * p.y * p.y - d * p.x - e * p.y,
+        )
+    }
+
     // ====================== 属性计算 ======================
 
+    /// 系数向量 (A, B, C, D, E, F) 的模长
+    pub fn coeff_norm(&self) -> f64 {
+        (self.a * self.a + self.b * self.b + self.c * self.c
+            + self.d * self.d + self.e * self.e + self.f * self.f).sqrt()
+    }
+
+    /// 归一化：系数整体除以模长 (方程不变，比例无关的阈值判断以此为准)
+    pub fn normalized(&self) -> Self {
+        let n = self.coeff_norm();
+        if n < 1e-300 { return *self; }
+        Self::new(self.a / n, self.b / n, self.c / n, self.d / n, self.e / n, self.f / n)
+    }
+
     /// 判别式 Delta = B^2 - 4AC
     pub fn discriminant(&self) -> f64 {
         self.b * self.b - 4.0 * self.a * self.c
@@ -111,26 +247,40 @@ impl Conic {
     }
 
     /// 获取圆锥曲线类型
+    /// 在归一化系数上判断，结果与方程整体缩放无关
     pub fn get_conic_type(&self) -> ConicType {
-        let delta = self.discriminant();
-        let det = self.det_3x3_scaled();
-        let is_degenerate = det.abs() < 1.0; // 阈值需要根据系数大小调整，这里简化
+        if self.coeff_norm() == 0.0 { return ConicType::Everything; }
+        let n = self.normalized();
+        let delta = n.discriminant();
+        let det = n.det_3x3_scaled();
+        let is_degenerate = det.abs() < Self::EPSILON;
 
         if is_degenerate {
             if delta < -Self::EPSILON { return ConicType::Point; }
             if delta > Self::EPSILON { return ConicType::IntersectingLines; }
-            // delta == 0
-            // 进一步区分平行线还是单线，需检查子行列式，这里简化
+            // delta == 0：二次项为零时退化为一次方程
+            if n.a.abs() < Self::EPSILON && n.b.abs() < Self::EPSILON && n.c.abs() < Self::EPSILON {
+                // D x + E y + F = 0；D = E = 0 时为 F = 0 (F ≠ 0)，无解
+                if n.d.abs() < Self::EPSILON && n.e.abs() < Self::EPSILON { return ConicType::Imaginary; }
+                return ConicType::Line;
+            }
+            // 用余子式之和 K = (4CF - E^2) + (4AF - D^2) 区分：
+            // K < 0 两条实平行线，K = 0 重合为一条直线，K > 0 两条虚平行线 (如 x^2 + 1 = 0)
+            let k = (4.0 * n.c * n.f - n.e * n.e) + (4.0 * n.a * n.f - n.d * n.d);
+            if k > Self::EPSILON { return ConicType::Imaginary; }
+            if k > -Self::EPSILON { return ConicType::Line; }
             return ConicType::ParallelLines;
         }
 
         if delta < -Self::EPSILON {
-            if (self.a - self.c).abs() < Self::EPSILON && self.b.abs() < Self::EPSILON {
+            // 椭圆型：(A + C) 与 det 同号时没有实点 (虚椭圆，如 x^2 + y^2 + 1 = 0)
+            if (n.a + n.c) * det > 0.0 { return ConicType::Imaginary; }
+            if (n.a - n.c).abs() < Self::EPSILON && n.b.abs() < Self::EPSILON {
                 return ConicType::Circle;
             }
             ConicType::Ellipse
         } else if delta > Self::EPSILON {
-            if (self.a + self.c).abs() < Self::EPSILON {
+            if (n.a + n.c).abs() < Self::EPSILON {
                 return ConicType::RectangularHyperbola;
             }
             return ConicType::Hyperbola;
@@ -244,6 +394,15 @@ impl Conic {
         None // 待实现：标准型转渐近线型
     }
 
+    /// 退化为空集或全平面时转为 Wipkyy，否则为 None
+    pub fn to_wipkyy(&self) -> Option<Wipkyy> {
+        match self.get_conic_type() {
+            ConicType::Imaginary => Some(Wipkyy::empty()),
+            ConicType::Everything => Some(Wipkyy::everything()),
+            _ => None,
+        }
+    }
+
     // ====================== 通用几何计算 ======================
 
     /// 计算点 p 处的值
@@ -252,37 +411,260 @@ impl Conic {
             + self.d * p.x + self.e * p.y + self.f
     }
 
+    /// 归一化后的值：eval / 系数模长，容差判断与方程缩放无关
+    pub fn eval_normalized(&self, p: Vec2) -> f64 {
+        self.eval(p) / self.coeff_norm()
+    }
+
+    /// 梯度 ∇F = (2Ax + By + D, Bx + 2Cy + E)：等值线 F = eval(p) 在 p 处的法向，指向 F 增大的一侧
+    pub fn gradient_at(&self, p: Vec2) -> Vec2 {
+        Vec2::new(
+            2.0 * self.a * p.x + self.b * p.y + self.d,
+            self.b * p.x + 2.0 * self.c * p.y + self.e,
+        )
+    }
+
+    /// 过 p 且垂直于梯度的直线；p 在曲线上时即切线
+    /// 梯度为零 (p 是奇点，如交叉直线的交点或有心曲线的中心) 时返回 None
+    pub fn tangent_line_at(&self, p: Vec2) -> Option<Line> {
+        let g = self.gradient_at(p);
+        if g.len() <= Self::EPSILON * self.coeff_norm() * (1.0 + p.len()) {
+            return None;
+        }
+        Some(Line::new(p, g.roll90()))
+    }
+
     /// 极点-极线 (Pole-Polar) 关系
     /// 给定点 P(x0, y0)，返回极线 L: (2Ax0 + By0 + D)x + (Bx0 + 2Cy0 + E)y + (Dx0 + Ey0 + 2F) = 0
-    /// 注意系数需要除以 2 还原
+    /// 法向 (x, y 系数) 相对常数项可忽略时 (P 为有心曲线的中心)，极线是无穷远直线，返回 Line::AT_INFINITY
+    /// 抛物线的中心在无穷远，有限点的极线总是有限直线
+    /// 三个系数全为零 (P 是退化曲线的奇点，如交叉直线的交点) 时极线无定义，返回 Line::NAN
     pub fn polar_line(&self, p: Vec2) -> Line {
-        // 直线方程 Ax + By + C = 0 -> 法向量 (A, B), 基点 ?
-        // Line 定义是 (Point, Direction)
         let la = 2.0 * self.a * p.x + self.b * p.y + self.d; // x 系数 * 2
         let lb = self.b * p.x + 2.0 * self.c * p.y + self.e; // y 系数 * 2
         let lc = self.d * p.x + self.e * p.y + 2.0 * self.f; // 常数 * 2
 
-        // 实际方程: (la/2) x + (lb/2) y + (lc/2) = 0
-        let normal = Vec2::new(la, lb); // 法向量
-        if normal.len() < Self::EPSILON {
-            // 极点在中心，极线在无穷远
-            return Line::new(Vec2::NAN, Vec2::NAN);
+        let normal = Vec2::new(la, lb);
+        let size = normal.len().max(lc.abs());
+        if size <= Self::EPSILON * self.coeff_norm() * (1.0 + p.len()) {
+            return Line::NAN;
+        }
+        if normal.len() <= Self::EPSILON * size {
+            return Line::AT_INFINITY;
         }
 
-        // 构造直线：找到直线上一点
-        // 设 x=0, y = -lc/lb (如果 lb!=0)
-        let p_on_line = if lb.abs() > Self::EPSILON {
-            Vec2::new(0.0, -lc / lb)
-        } else {
-            Vec2::new(-lc / la, 0.0)
+        // 基点取原点到直线的垂足 -lc·n / |n|²，不单独除以 la 或 lb
+        Line::new(normal * (-lc / normal.pow2()), normal.roll90())
+    }
+
+    /// 直线 l 的极点 (polar_line 的逆)：M P = λ L，即 P = adj(M) L (齐次坐标)
+    /// 无穷远直线的极点是中心；经过有心曲线中心的直线 (以及抛物线中平行于对称轴的直线)
+    /// 的极点在无穷远，返回 Vec2::INF；退化曲线上 adj(M) L = 0 时返回 Vec2::NAN
+    pub fn pole_of_line(&self, l: &Line) -> Vec2 {
+        let pole = Self::dual_point(&self.normalized().to_matrix().adjugate(), Self::line_coords(l));
+        if pole.len() <= Self::EPSILON {
+            return Vec2::NAN;
+        }
+        if pole.z.abs() <= Self::EPSILON * pole.len() {
+            return Vec2::INF;
+        }
+        Vec2::new(pole.x / pole.z, pole.y / pole.z)
+    }
+
+    /// 直线是否与曲线相切：极点在直线上，即 Lᵀ adj(M) L = 0 (对偶圆锥曲线)
+    /// 曲线与直线方程均先归一化，tol 与两者的缩放无关
+    /// 射影意义下双曲线的渐近线也算相切 (切点在无穷远)
+    pub fn is_tangent_line(&self, l: &Line, tol: f64) -> bool {
+        let coords = Self::line_coords(l);
+        let pole = Self::dual_point(&self.normalized().to_matrix().adjugate(), coords);
+        pole.dot(coords).abs() <= tol
+    }
+
+    // 直线 n·X + c = 0 的齐次坐标 (n, c)，n 为单位法向；无穷远直线为 (0, 0, 1)
+    fn line_coords(l: &Line) -> Vec3 {
+        if l.is_at_infinity() {
+            return Vec3::K;
+        }
+        let n = l.v.roll90().unit();
+        Vec3::new(n.x, n.y, -n.dot(l.p))
+    }
+
+    // adj(M) L (adj(M) 对称，按列组合即可)
+    fn dual_point(adj: &Matrix3x3, l: Vec3) -> Vec3 {
+        adj.col(0) * l.x + adj.col(1) * l.y + adj.col(2) * l.z
+    }
+
+    /// 极点 P 对应的切线对: M' = (PᵀMP) M - (MP)(MP)ᵀ (M 为对称矩阵，P 为齐次坐标)
+    /// MP 即极线，(MP)(MP)ᵀ 是二重极线；结果恒为退化圆锥曲线 (det = 0)，且经过 P：
+    ///   P 在曲线外：过 P 的两条实切线 (IntersectingLines)
+    ///   P 在曲线上：该点处的切线 (二重，Line)
+    ///   P 在曲线内：两条共轭虚切线，实部只剩 P 一点 (Point)
+    /// 例如单位圆与原点：极线是无穷远直线，切线对为 x² + y² = 0
+    pub fn polar_conic(&self, p: Vec2) -> Conic {
+        // MP (对应 polar_line 中的 la/2, lb/2, lc/2)
+        let q0 = self.a * p.x + 0.5 * self.b * p.y + 0.5 * self.d;
+        let q1 = 0.5 * self.b * p.x + self.c * p.y + 0.5 * self.e;
+        let q2 = 0.5 * self.d * p.x + 0.5 * self.e * p.y + self.f;
+        let s = self.eval(p); // PᵀMP
+
+        Conic::new(
+            s * self.a - q0 * q0,
+            s * self.b - 2.0 * q0 * q1,
+            s * self.c - q1 * q1,
+            s * self.d - 2.0 * q0 * q2,
+            s * self.e - 2.0 * q1 * q2,
+            s * self.f - q2 * q2,
+        )
+    }
+
+    /// 对称矩阵形式 Xᵀ M X = 0，X = (x, y, 1)
+    /// [ A    B/2  D/2 ]
+    /// [ B/2  C    E/2 ]
+    /// [ D/2  E/2  F   ]
+    pub fn to_matrix(&self) -> Matrix3x3 {
+        Matrix3x3::new(
+            self.a,       0.5 * self.b, 0.5 * self.d,
+            0.5 * self.b, self.c,       0.5 * self.e,
+            0.5 * self.d, 0.5 * self.e, self.f
+        )
+    }
+
+    /// 由矩阵还原 (先对称化，非对称部分对二次型没有贡献)
+    pub fn from_matrix(m: &Matrix3x3) -> Self {
+        let m = m.m;
+        Self::new(m[0], m[1] + m[3], m[4], m[2] + m[6], m[5] + m[7], m[8])
+    }
+
+    /// 射影变换：曲线上的点 X 映射为 H X，新曲线矩阵为 H⁻ᵀ M H⁻¹
+    /// 用伴随矩阵代替逆矩阵 (只差一个比例)；H 奇异时结果退化
+    /// 仿射 H 保持类型；一般的 H 把原曲线与“被送到无穷远的直线”相离 / 相切 / 相交
+    /// 分别变成椭圆 / 抛物线 / 双曲线
+    pub fn transform(&self, h: &Matrix3x3) -> Conic {
+        let inv = h.adjugate();
+        Self::from_matrix(&(inv.transpose() * self.to_matrix() * inv))
+    }
+}
+
+// ====================== 圆锥曲线求交 (Bezout 消元) ======================
+
+impl Conic {
+    /// 与另一条圆锥曲线求交，返回 4 个交点 (Bezout: 至多 4 个)
+    /// 每个交点以 x + iy 的形式存入 Complex；非实交点 (复交点 / 无穷远点) 为 NaN
+    pub fn intersect_conic(&self, other: &Conic) -> QComplex {
+        let pts = self.intersect_conic_complex(other);
+        let to_real = |(x, y): (Complex, Complex)| -> Complex {
+            let tol = 1e-7 * (1.0 + x.len() + y.len());
+            if x.is_nan() || y.is_nan() || x.im.abs() > tol || y.im.abs() > tol {
+                Complex::NAN
+            } else {
+                Complex::new(x.re, y.re)
+            }
         };
+        QComplex::new(to_real(pts[0]), to_real(pts[1]), to_real(pts[2]), to_real(pts[3]))
+    }
+
+    /// 与另一条圆锥曲线求交，返回复射影意义下的 4 组 (x, y) 坐标
+    /// 原理：两式都看成 x 的二次多项式 (系数是 y 的多项式)
+    ///   P(x) = A1 x^2 + (B1 y + D1) x + (C1 y^2 + E1 y + F1)
+    ///   Q(x) = A2 x^2 + (B2 y + D2) x + (C2 y^2 + E2 y + F2)
+    /// 关于 x 的结式 Res(y) = (p2 q0 - q2 p0)^2 - (p2 q1 - q2 p1)(p1 q0 - q1 p0) 是 y 的四次多项式
+    /// 解出 y 后，由 q2 P - p2 Q 消去 x^2 得到 x (线性回代)
+    /// 结式次数不足 4 (如两圆，另两个交点在无穷远) 时，缺失的根为 NaN
+    pub fn intersect_conic_complex(&self, other: &Conic) -> [(Complex, Complex); 4] {
+        // 两者都不含 x^2 时，以 y 为主元更稳：交换 x、y 再求
+        if self.a.abs() < Self::EPSILON && other.a.abs() < Self::EPSILON {
+            let swapped = self.swap_xy().intersect_conic_complex(&other.swap_xy());
+            return swapped.map(|(x, y)| (y, x));
+        }
+
+        // 以 y 的升幂系数表示 p_i(y)、q_i(y)
+        let (p2, p1, p0) = ([self.a], [self.d, self.b], [self.f, self.e, self.c]);
+        let (q2, q1, q0) = ([other.a], [other.d, other.b], [other.f, other.e, other.c]);
+
+        let r0 = poly_sub(&poly_mul(&p2, &q0), &poly_mul(&q2, &p0)); // p2 q0 - q2 p0
+        let r1 = poly_sub(&poly_mul(&p2, &q1), &poly_mul(&q2, &p1)); // p2 q1 - q2 p1
+        let r2 = poly_sub(&poly_mul(&p1, &q0), &poly_mul(&q1, &p0)); // p1 q0 - q1 p0
+        let res = poly_sub(&poly_mul(&r0, &r0), &poly_mul(&r1, &r2));
 
-        // 方向向量是法向量旋转 90 度
-        let dir = normal.roll90();
-        Line::new(p_on_line, dir)
+        // 去掉数值上为零的高次项，按实际次数求根
+        let scale = res.iter().fold(0.0f64, |m, c| m.max(c.abs()));
+        if scale == 0.0 {
+            // 结式恒为零：两曲线有公共分量，交点不唯一
+            return [(Complex::NAN, Complex::NAN); 4];
+        }
+        let mut degree = 4;
+        while degree > 0 && res[degree].abs() <= 1e-12 * scale {
+            degree -= 1;
+        }
+        let c = |k: usize| if k <= degree { Complex::from_real(res[k]) } else { Complex::ZERO };
+        let roots = solve_quartic(c(4), c(3), c(2), c(1), c(0));
+        let ys = [roots.n1, roots.n2, roots.n3, roots.n4];
+
+        let mut out = [(Complex::NAN, Complex::NAN); 4];
+        for (k, &y) in ys.iter().enumerate().take(degree) {
+            out[k] = (self.back_substitute_x(other, y, &out[..k]), y);
+        }
+        out
+    }
+
+    // 已知交点的 y，求 x
+    fn back_substitute_x(&self, other: &Conic, y: Complex, found: &[(Complex, Complex)]) -> Complex {
+        let eval_at = |c: &Conic, x: Complex| {
+            x * x * c.a + x * (y * c.b + c.d) + y * y * c.c + y * c.e + c.f
+        };
+        let p1 = y * self.b + self.d;
+        let p0 = y * y * self.c + y * self.e + self.f;
+        let q1 = y * other.b + other.d;
+        let q0 = y * y * other.c + y * other.e + other.f;
+
+        // q2 P - p2 Q = (q2 p1 - p2 q1) x + (q2 p0 - p2 q0) = 0
+        let lin = p1 * other.a - q1 * self.a;
+        let cst = p0 * other.a - q0 * self.a;
+        let scale = 1.0 + p1.len() * other.a.abs() + q1.len() * self.a.abs();
+        if lin.len() > 1e-9 * scale {
+            return -cst / lin;
+        }
+
+        // 退化 (如两交点 y 相同、关于竖直线对称)：直接解 P(x) = 0，取使 Q 最小的根
+        // 同一个 y 已经用过的根让给另一个
+        let (base, partner) = if self.a.abs() >= other.a.abs() { (self, other) } else { (other, self) };
+        let xs = solve_complex_quadratic_for_complex(
+            Complex::from_real(base.a),
+            y * base.b + base.d,
+            y * y * base.c + y * base.e + base.f,
+        );
+        let used = |x: Complex| found.iter().any(|&(fx, fy)| (fx - x).len() < 1e-9 && (fy - y).len() < 1e-9);
+        let score = |x: Complex| eval_at(partner, x).len() + if used(x) { f64::INFINITY } else { 0.0 };
+        if score(xs.n1) <= score(xs.n2) { xs.n1 } else { xs.n2 }
+    }
+
+    // 交换 x、y 的角色
+    fn swap_xy(&self) -> Conic {
+        Conic::new(self.c, self.b, self.a, self.e, self.d, self.f)
     }
 }
 
+// 升幂系数多项式乘法 (结式中乘积次数不超过 4，更高次项恒为零，直接截断)
+fn poly_mul(a: &[f64], b: &[f64]) -> [f64; 5] {
+    let mut out = [0.0; 5];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            if let Some(o) = out.get_mut(i + j) {
+                *o += x * y;
+            }
+        }
+    }
+    out
+}
+
+fn poly_sub(a: &[f64], b: &[f64]) -> [f64; 5] {
+    let mut out = [0.0; 5];
+    for (k, o) in out.iter_mut().enumerate() {
+        *o = a.get(k).copied().unwrap_or(0.0) - b.get(k).copied().unwrap_or(0.0);
+    }
+    out
+}
+
 impl fmt::Display for Conic {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Conic({:.2}x² + {:.2}xy + {:.2}y² + {:.2}x + {:.2}y + {:.2} = 0)",
@@ -332,4 +714,397 @@ fn det4x4_array(m: &[f64; 16]) -> f64 {
         m[8], m[9], m[10], m[11],
         m[12], m[13], m[14], m[15]
     )
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_forest::geometry::d2::intersection::line520::x_circle_circle;
+
+    fn circle_conic(c: &Circle) -> Conic {
+        Conic::from_circle(c)
+    }
+
+    fn real_points(q: QComplex) -> Vec<Vec2> {
+        [q.n1, q.n2, q.n3, q.n4].iter()
+            .filter(|z| !z.is_nan())
+            .map(|z| Vec2::new(z.re, z.im))
+            .collect()
+    }
+
+    fn assert_same_points(got: &[Vec2], expect: &[Vec2]) {
+        assert_eq!(got.len(), expect.len(), "{:?} vs {:?}", got, expect);
+        for e in expect {
+            assert!(got.iter().any(|g| (*g - *e).len() < 1e-7), "missing {} in {:?}", e, got);
+        }
+    }
+
+    #[test]
+    fn test_two_circles_match_x_circle_circle() {
+        let c1 = Circle::new(Vec2::new(0.0, 0.0), 2.0);
+        let c2 = Circle::new(Vec2::new(2.0, 1.0), 2.0);
+        let expect = x_circle_circle(&c1, &c2);
+        let got = real_points(circle_conic(&c1).intersect_conic(&circle_conic(&c2)));
+        assert_same_points(&got, &[expect.p1, expect.p2]);
+    }
+
+    #[test]
+    fn test_two_circles_symmetric() {
+        // 两交点 y 相同，线性回代退化
+        let c1 = Circle::new(Vec2::new(0.0, 0.0), 2.0);
+        let c2 = Circle::new(Vec2::new(0.0, 2.0), 2.0);
+        let expect = x_circle_circle(&c1, &c2);
+        let got = real_points(circle_conic(&c1).intersect_conic(&circle_conic(&c2)));
+        assert_same_points(&got, &[expect.p1, expect.p2]);
+    }
+
+    #[test]
+    fn test_ellipse_hyperbola_four_points() {
+        // x^2/4 + y^2 = 1 与 x^2 - y^2 = 1 交于 (±x0, ±y0)
+        let e = Conic::new(0.25, 0.0, 1.0, 0.0, 0.0, -1.0);
+        let h = Conic::new(1.0, 0.0, -1.0, 0.0, 0.0, -1.0);
+        let x0 = (8.0f64 / 5.0).sqrt();
+        let y0 = (3.0f64 / 5.0).sqrt();
+        let got = real_points(e.intersect_conic(&h));
+        assert_same_points(&got, &[
+            Vec2::new(x0, y0), Vec2::new(x0, -y0), Vec2::new(-x0, y0), Vec2::new(-x0, -y0),
+        ]);
+    }
+
+    #[test]
+    fn test_parabola_line_pair_no_x_squared() {
+        // y = x^2 与 x = y^2 交于 (0, 0), (1, 1)，另两个交点为复数
+        let p1 = Conic::new(1.0, 0.0, 0.0, 0.0, -1.0, 0.0);
+        let p2 = Conic::new(0.0, 0.0, 1.0, -1.0, 0.0, 0.0);
+        let got = real_points(p1.intersect_conic(&p2));
+        assert_same_points(&got, &[Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0)]);
+        for (x, y) in p1.intersect_conic_complex(&p2) {
+            // 复交点也满足两条方程
+            let r1 = x * x - y;
+            let r2 = y * y - x;
+            assert!(r1.len() < 1e-9 && r2.len() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_from_focus_directrix() {
+        let focus = Vec2::new(1.0, 2.0);
+        let directrix = Line::new(Vec2::new(-3.0, 0.0), Vec2::new(1.0, 1.0));
+
+        for (e, expect) in [
+            (0.5, ConicType::Ellipse),
+            (1.0, ConicType::Parabola),
+            (2.0, ConicType::Hyperbola),
+        ] {
+            let c = Conic::from_focus_directrix(focus, directrix, e);
+            assert_eq!(c.get_conic_type(), expect, "e = {}", e);
+            // 缩放方程不改变分类
+            let scaled = Conic::new(c.a * 1e-4, c.b * 1e-4, c.c * 1e-4, c.d * 1e-4, c.e * 1e-4, c.f * 1e-4);
+            assert_eq!(scaled.get_conic_type(), expect);
+
+            // 过焦点的射线与曲线求交，交点满足 |PF| = e · dist(P, L)
+            let mut count = 0;
+            for k in 0..24 {
+                let w = Vec2::from_angle_length(k as f64 * 0.27, 1.0);
+                // eval(F + r w) = α r^2 + β r + γ
+                let alpha = c.a * w.x * w.x + c.b * w.x * w.y + c.c * w.y * w.y;
+                let beta = 2.0 * c.a * focus.x * w.x + c.b * (focus.x * w.y + focus.y * w.x)
+                    + 2.0 * c.c * focus.y * w.y + c.d * w.x + c.e * w.y;
+                let gamma = c.eval(focus);
+                let roots = solve_real_quadratic_for_real(alpha, beta, gamma);
+                for r in [roots.n1, roots.n2] {
+                    if !r.is_finite() { continue; }
+                    let p = focus + w * r;
+                    assert!((p.dis(focus) - e * directrix.dis_p(p)).abs() < 1e-9, "e = {}, p = {}", e, p);
+                    assert!(c.eval_normalized(p).abs() < 1e-9);
+                    count += 1;
+                }
+            }
+            assert!(count >= 24);
+        }
+    }
+
+    #[test]
+    fn test_from_geometry_objects() {
+        let circle = Circle::new(Vec2::new(-1.0, 2.0), 1.5);
+        let c = Conic::from_circle(&circle);
+        assert_eq!(c.get_conic_type(), ConicType::Circle);
+        for k in 0..16 {
+            assert!(c.eval_normalized(circle.index_point(k as f64 * 0.4)).abs() < 1e-9);
+        }
+
+        let el = Ellipse::new(Vec2::new(1.0, -1.0), Vec2::new(2.0, 0.5), Vec2::new(-0.3, 1.0));
+        let c = Conic::from_ellipse(&el);
+        assert_eq!(c.get_conic_type(), ConicType::Ellipse);
+        for k in 0..16 {
+            assert!(c.eval_normalized(el.index_point(k as f64 * 0.4)).abs() < 1e-9);
+        }
+
+        let h = Hyperbola::new(Vec2::new(0.5, 1.0), Vec2::new(1.0, 0.3), Vec2::new(0.2, 1.5));
+        let c = Conic::from_hyperbola(&h);
+        assert_eq!(c.get_conic_type(), ConicType::Hyperbola);
+        for t in [-4.0, -1.0, -0.2, 0.3, 1.0, 2.5] {
+            assert!(c.eval_normalized(h.index_point(t)).abs() < 1e-9);
+        }
+        // 渐近线垂直时为等轴双曲线
+        let rect = Hyperbola::new(Vec2::ZERO, Vec2::new(1.0, 1.0), Vec2::new(1.0, -1.0));
+        assert_eq!(Conic::from_hyperbola(&rect).get_conic_type(), ConicType::RectangularHyperbola);
+
+        let pa = Parabola::new(Vec2::new(2.0, -1.0), Vec2::new(0.6, 0.8));
+        let c = Conic::from_parabola(&pa);
+        assert_eq!(c.get_conic_type(), ConicType::Parabola);
+        for t in [-3.0, -1.0, 0.0, 0.5, 2.0, 4.0] {
+            assert!(c.eval_normalized(pa.index_point(t)).abs() < 1e-9);
+        }
+        // 抛物线也满足焦点-准线定义 (e = 1)
+        let fd = Conic::from_focus_directrix(pa.focus(), pa.directrix(), 1.0);
+        for t in [-2.0, 1.0, 3.0] {
+            assert!(fd.eval_normalized(pa.index_point(t)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_degenerate_classification() {
+        // 虚椭圆 / 虚圆
+        let empty = Conic::new(1.0, 0.0, 1.0, 0.0, 0.0, 1.0);
+        assert_eq!(empty.get_conic_type(), ConicType::Imaginary);
+        assert!(empty.to_wipkyy().unwrap().is_empty());
+        assert_eq!(Conic::new(-2.0, 0.5, -1.0, 0.0, 0.0, -3.0).get_conic_type(), ConicType::Imaginary);
+        // 实圆不受影响
+        assert_eq!(Conic::new(1.0, 0.0, 1.0, 0.0, 0.0, -1.0).get_conic_type(), ConicType::Circle);
+        assert!(Conic::new(1.0, 0.0, 1.0, 0.0, 0.0, -1.0).to_wipkyy().is_none());
+
+        // 平行线族：实 / 重合 / 虚
+        assert_eq!(Conic::new(1.0, 0.0, 0.0, 0.0, 0.0, -1.0).get_conic_type(), ConicType::ParallelLines);
+        assert_eq!(Conic::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0).get_conic_type(), ConicType::Line);
+        assert_eq!(Conic::new(1.0, 0.0, 0.0, 0.0, 0.0, 1.0).get_conic_type(), ConicType::Imaginary);
+
+        // 一次方程与常数方程
+        assert_eq!(Conic::new(0.0, 0.0, 0.0, 1.0, 2.0, 3.0).get_conic_type(), ConicType::Line);
+        assert_eq!(Conic::new(0.0, 0.0, 0.0, 0.0, 0.0, 5.0).get_conic_type(), ConicType::Imaginary);
+        let all = Conic::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        assert_eq!(all.get_conic_type(), ConicType::Everything);
+        assert!(all.to_wipkyy().unwrap().contains(Vec2::new(3.0, 4.0)));
+    }
+
+    #[test]
+    fn test_eval_normalized_scale_free() {
+        let c = Conic::new(1.0, 0.5, 2.0, -1.0, 3.0, -4.0);
+        let big = Conic::new(1e6, 0.5e6, 2e6, -1e6, 3e6, -4e6);
+        let p = Vec2::new(0.7, -0.2);
+        assert!((c.eval_normalized(p) - big.eval_normalized(p)).abs() < 1e-12);
+        assert!((c.normalized().coeff_norm() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_polar_conic() {
+        let unit = Conic::new(1.0, 0.0, 1.0, 0.0, 0.0, -1.0);
+
+        // 圆心：极线在无穷远，切线对退化为 x² + y² = 0
+        let center = unit.polar_conic(Vec2::ZERO);
+        assert!(unit.polar_line(Vec2::ZERO).is_at_infinity());
+        assert_eq!(center.get_conic_type(), ConicType::Point);
+        assert!(center.normalized().det_3x3_scaled().abs() < 1e-12);
+        assert!(center.eval(Vec2::ZERO).abs() < 1e-12);
+
+        // 圆外一点 (2, 0)：切点 (1/2, ±√3/2)，两条切线上的点都满足方程
+        let p = Vec2::new(2.0, 0.0);
+        let pair = unit.polar_conic(p);
+        assert_eq!(pair.get_conic_type(), ConicType::IntersectingLines);
+        for t in [Vec2::new(0.5, 0.75f64.sqrt()), Vec2::new(0.5, -(0.75f64.sqrt()))] {
+            for k in [-1.0, 0.5, 3.0] {
+                assert!(pair.eval(p + (t - p) * k).abs() < 1e-12);
+            }
+        }
+
+        // 曲线上的点：二重切线
+        assert_eq!(unit.polar_conic(Vec2::new(0.6, 0.8)).get_conic_type(), ConicType::Line);
+
+        // 一般椭圆：极线与曲线的交点 (切点) 在切线对上
+        let e = Ellipse::new(Vec2::new(1.0, -2.0), Vec2::new(3.0, 1.0), Vec2::new(-0.5, 2.0));
+        let conic = e.to_conic();
+        let p = Vec2::new(6.0, 4.0);
+        let pair = conic.polar_conic(p);
+        assert_eq!(pair.get_conic_type(), ConicType::IntersectingLines);
+        let touch = e.intersect_line(conic.polar_line(p));
+        assert!(pair.eval_normalized(touch.p1).abs() < 1e-9);
+        assert!(pair.eval_normalized(touch.p2).abs() < 1e-9);
+        assert!(pair.eval_normalized(p).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_projective_transform() {
+        let circle = Conic::new(1.0, 0.0, 1.0, 0.0, 0.0, -1.0);
+        // w = a x + 1：直线 x = -1/a 被送到无穷远
+        let h = |a: f64| Matrix3x3::new(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, a, 0.0, 1.0);
+
+        assert_eq!(circle.transform(&h(0.5)).get_conic_type(), ConicType::Ellipse); // 相离
+        assert_eq!(circle.transform(&h(1.0)).get_conic_type(), ConicType::Parabola); // 相切
+        assert_eq!(circle.transform(&h(2.0)).get_conic_type(), ConicType::Hyperbola); // 相交
+
+        // 圆上的点映射后落在新曲线上
+        for a in [0.5, 1.0, 2.0] {
+            let image = circle.transform(&h(a));
+            for k in 0..12 {
+                let t = k as f64 * 0.5 + 0.1;
+                let q = h(a).project_point2(Vec2::new(t.cos(), t.sin()));
+                if q.x.is_finite() {
+                    assert!(image.eval_normalized(q).abs() < 1e-9);
+                }
+            }
+        }
+
+        // 仿射变换保持类型 (旋转 + 平移保持圆)
+        let moved = circle.transform(&Matrix3x3::from_transform(Vec2::new(3.0, -2.0), 0.7, Vec2::new(1.0, 1.0)));
+        assert_eq!(moved.get_conic_type(), ConicType::Circle);
+        assert!((moved.center() - Vec2::new(3.0, -2.0)).len() < 1e-9);
+        assert_eq!(circle.transform(&Matrix3x3::from_scaling(2.0, 1.0)).get_conic_type(), ConicType::Ellipse);
+    }
+
+    #[test]
+    fn test_pencil_through_four_points() {
+        let pts = [Vec2::new(-1.0, -0.5), Vec2::new(2.0, -1.0), Vec2::new(1.5, 1.5), Vec2::new(-0.5, 2.0)];
+        let (a, b) = Conic::pencil_through_four_points(pts[0], pts[1], pts[2], pts[3]);
+        assert_eq!(a.get_conic_type(), ConicType::IntersectingLines);
+        assert_eq!(b.get_conic_type(), ConicType::IntersectingLines);
+
+        // 端点精确为两个退化成员
+        assert_eq!(Conic::lerp(&a, &b, 1.0), a.normalized());
+        assert_eq!(Conic::lerp(&a, &b, 0.0), b.normalized());
+        // 输入比例不影响结果
+        let scaled = Conic::new(a.a * 1e6, a.b * 1e6, a.c * 1e6, a.d * 1e6, a.e * 1e6, a.f * 1e6);
+        let mid = Conic::lerp(&a, &b, 0.3);
+        assert!((Conic::lerp(&scaled, &b, 0.3).coeff_norm() - mid.coeff_norm()).abs() < 1e-12);
+
+        for lambda in [-2.0, 0.0, 0.1, 0.25, 0.5, 0.8, 1.0, 3.0] {
+            let c = Conic::lerp(&a, &b, lambda);
+            for p in pts {
+                assert!(c.eval(p).abs() < 1e-12, "lambda = {}, p = {}: {}", lambda, p, c.eval(p));
+            }
+        }
+    }
+
+    // 点到直线的距离
+    fn dist_to_line(q: Vec2, l: &Line) -> f64 {
+        (q - l.p).cross(l.v).abs() / l.v.len()
+    }
+
+    #[test]
+    fn test_pole_of_polar_roundtrip() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(1360);
+        let mut checked = 0;
+        while checked < 200 {
+            let k: [f64; 6] = std::array::from_fn(|_| rng.gen_range(-2.0..2.0));
+            let conic = Conic::new(k[0], k[1], k[2], k[3], k[4], k[5]);
+            // 退化曲线的极点-极线对应不是一一的
+            if conic.normalized().det_3x3_scaled().abs() < 1e-3 { continue; }
+            let p = Vec2::new(rng.gen_range(-3.0..3.0), rng.gen_range(-3.0..3.0));
+            let polar = conic.polar_line(p);
+            assert!(!polar.is_at_infinity());
+            let pole = conic.pole_of_line(&polar);
+            assert!((pole - p).len() < 1e-6 * (1.0 + p.len()), "{}: {} -> {}", conic, p, pole);
+            checked += 1;
+        }
+    }
+
+    #[test]
+    fn test_line_at_infinity() {
+        // 有心曲线：中心的极线是无穷远直线，反过来无穷远直线的极点是中心
+        let e = Ellipse::new(Vec2::new(1.0, -2.0), Vec2::new(3.0, 1.0), Vec2::new(-0.5, 2.0)).to_conic();
+        assert!(e.polar_line(Vec2::new(1.0, -2.0)).is_at_infinity());
+        assert!((e.pole_of_line(&Line::AT_INFINITY) - Vec2::new(1.0, -2.0)).len() < 1e-9);
+        // 经过中心的直线，极点在无穷远
+        assert_eq!(e.pole_of_line(&Line::new(Vec2::new(1.0, -2.0), Vec2::new(1.0, 1.0))), Vec2::INF);
+
+        // 抛物线 y = x²：中心在无穷远，有限点的极线都有限
+        let parabola = Conic::new(1.0, 0.0, 0.0, 0.0, -1.0, 0.0);
+        // (0, -1) 的两条切线切于 (±1, 1)，极线为 y = 1
+        let polar = parabola.polar_line(Vec2::new(0.0, -1.0));
+        assert!(dist_to_line(Vec2::new(-1.0, 1.0), &polar) < 1e-12);
+        assert!(dist_to_line(Vec2::new(1.0, 1.0), &polar) < 1e-12);
+        // 对称轴 (及其平行线) 的极点是轴方向上的无穷远点
+        assert_eq!(parabola.pole_of_line(&Line::new(Vec2::ZERO, Vec2::J)), Vec2::INF);
+        assert_eq!(parabola.pole_of_line(&Line::new(Vec2::new(2.0, 0.0), Vec2::J)), Vec2::INF);
+        // 无穷远直线与抛物线相切
+        assert!(parabola.is_tangent_line(&Line::AT_INFINITY, 1e-9));
+
+        // 交叉直线 xy = 0 的交点：极线无定义
+        assert!(Conic::new(0.0, 1.0, 0.0, 0.0, 0.0, 0.0).polar_line(Vec2::ZERO).p.x.is_nan());
+    }
+
+    #[test]
+    fn test_is_tangent_line() {
+        let e = Ellipse::new(Vec2::new(1.0, -2.0), Vec2::new(3.0, 1.0), Vec2::new(-0.5, 2.0));
+        let conic = e.to_conic();
+        // 方程缩放不影响判断
+        let scaled = Conic::new(conic.a * 1e4, conic.b * 1e4, conic.c * 1e4, conic.d * 1e4, conic.e * 1e4, conic.f * 1e4);
+        for k in 0..16 {
+            let tangent = e.tangent_line_at(k as f64 * 0.4);
+            assert!(conic.is_tangent_line(&tangent, 1e-9));
+            assert!(scaled.is_tangent_line(&tangent, 1e-9));
+            // 平移后的直线不再相切
+            let shifted = Line::new(tangent.p + tangent.v.roll90().unit() * 0.1, tangent.v);
+            assert!(!conic.is_tangent_line(&shifted, 1e-9));
+        }
+
+        // 双曲线的渐近线：射影意义下切于无穷远点
+        let h = Conic::new(1.0, 0.0, -1.0, 0.0, 0.0, -1.0);
+        assert!(h.is_tangent_line(&Line::new(Vec2::ZERO, Vec2::new(1.0, 1.0)), 1e-9));
+        assert!(!h.is_tangent_line(&Line::new(Vec2::ZERO, Vec2::new(1.0, 0.5)), 1e-9));
+    }
+
+    #[test]
+    fn test_polar_of_focus_is_directrix() {
+        // 焦点-准线构造的椭圆 / 抛物线 / 双曲线：焦点的极线就是准线
+        let focus = Vec2::new(1.5, -0.5);
+        let directrix = Line::new(Vec2::new(-1.0, 2.0), Vec2::new(2.0, 1.0));
+        for e in [0.5, 1.0, 2.0] {
+            let conic = Conic::from_focus_directrix(focus, directrix, e);
+            let polar = conic.polar_line(focus);
+            assert!(polar.is_parallel(&directrix), "e = {}", e);
+            for t in [-3.0, 0.0, 2.0] {
+                assert!(dist_to_line(directrix.index_point(t), &polar) < 1e-9, "e = {}", e);
+            }
+            assert!((conic.pole_of_line(&directrix) - focus).len() < 1e-9, "e = {}", e);
+        }
+    }
+
+    #[test]
+    fn test_gradient_and_tangent_line() {
+        let e = Ellipse::new(Vec2::new(1.0, -2.0), Vec2::new(3.0, 1.0), Vec2::new(-0.5, 2.0));
+        let conic = e.to_conic();
+        for k in 0..16 {
+            let theta = k as f64 * 0.4;
+            let p = e.index_point(theta);
+            let g = conic.gradient_at(p);
+
+            // 与中心差分一致
+            let h = 1e-6;
+            let fd = Vec2::new(
+                conic.eval(p + Vec2::new(h, 0.0)) - conic.eval(p - Vec2::new(h, 0.0)),
+                conic.eval(p + Vec2::new(0.0, h)) - conic.eval(p - Vec2::new(0.0, h)),
+            ) * (0.5 / h);
+            assert!((g - fd).len() < 1e-6 * g.len(), "theta = {}", theta);
+            // 外法向 (to_conic 内部为负)
+            assert!(g.dot(p - e.p) > 0.0);
+
+            // 与 Ellipse::tangent_line_at 是同一条直线
+            let tangent = conic.tangent_line_at(p).unwrap();
+            let expect = e.tangent_line_at(theta);
+            assert!(tangent.is_parallel(&expect), "theta = {}", theta);
+            assert!(dist_to_line(tangent.p, &expect) < 1e-9);
+            assert!(conic.is_tangent_line(&tangent, 1e-9));
+        }
+
+        // 奇点：交叉直线的交点、有心曲线的中心
+        let cross = Conic::from_line_pair(
+            &Line::new(Vec2::new(1.0, 1.0), Vec2::new(1.0, 2.0)),
+            &Line::new(Vec2::new(1.0, 1.0), Vec2::new(-3.0, 1.0)),
+        );
+        assert!(cross.tangent_line_at(Vec2::new(1.0, 1.0)).is_none());
+        assert!(cross.tangent_line_at(Vec2::new(2.0, 3.0)).is_some());
+        assert!(conic.tangent_line_at(e.p).is_none());
+    }
+}
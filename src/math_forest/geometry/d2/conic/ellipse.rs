@@ -9,6 +9,8 @@ use crate::math_forest::geometry::d2::fertile::d_point::DPoint;
 use crate::math_forest::geometry::d2::fertile::q_point::QPoint;
 use crate::math_forest::geometry::d2::linear::line::Line;
 use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+use crate::math_forest::geometry::d2::intersection::line520::x_ellipse_line;
+use super::conic::Conic;
 // use super::x_line::XLine; // 假设 XLine (叉线) 稍后提供
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -119,6 +121,12 @@ impl Ellipse {
         PI * (a + b) * (1.0 + (3.0 * h) / (10.0 + (4.0 - 3.0 * h).sqrt()))
     }
 
+    // 半通径 p = b² / a
+    pub fn semi_latus_rectum(&self) -> f64 {
+        let (a, b) = self.ab();
+        b * b / a
+    }
+
     // ====================== 特殊点获取 ======================
 
     // 计算长轴和短轴对应的参数 θ 基础值
@@ -160,6 +168,81 @@ impl Ellipse {
         DPoint::new_pv(self.p, focus_vec)
     }
 
+    // 曲线上点 q 对应的参数 θ (q - C = U cosθ + V sinθ)
+    pub fn theta_of_p(&self, q: Vec2) -> f64 {
+        let (cos, sin) = (q - self.p).rsv(self.u, self.v);
+        sin.atan2(cos)
+    }
+
+    // ====================== 轨道参数 (近点角) ======================
+    // 以焦点 F1 (f_points().p1) 为引力中心，近拱点为 F1 一侧的长轴端点 C + a·v_a
+    // 角度从 v_a 起逆时针度量 (与 U、V 的朝向无关)
+
+    // 真近点角 ν (在 F1 处量得) 对应的点，r = a(1 - e²) / (1 + e cos ν)
+    pub fn point_at_true_anomaly(&self, nu: f64) -> Vec2 {
+        let r = self.semi_latus_rectum() / (1.0 + self.e() * nu.cos());
+        let (sin, cos) = nu.sin_cos();
+        let v_a = self.v_a();
+        self.f_points().p1 + (v_a * cos + v_a.roll90() * sin) * r
+    }
+
+    // 偏近点角 E 对应的点 C + a cosE·v_a + b sinE·v_b
+    pub fn point_at_eccentric_anomaly(&self, ecc: f64) -> Vec2 {
+        let (a, b) = self.ab();
+        let (sin, cos) = ecc.sin_cos();
+        let v_a = self.v_a();
+        self.p + v_a * (a * cos) + v_a.roll90() * (b * sin)
+    }
+
+    // ν -> E：tan(E/2) = sqrt((1 - e) / (1 + e)) tan(ν/2)，ν ∈ (-2π, 2π) 时 E 与 ν 在同一圈
+    pub fn true_to_eccentric(&self, nu: f64) -> f64 {
+        let e = self.e();
+        let (sin, cos) = (nu * 0.5).sin_cos();
+        2.0 * ((1.0 - e).sqrt() * sin).atan2((1.0 + e).sqrt() * cos)
+    }
+
+    // E -> M：开普勒方程 M = E - e sinE
+    pub fn eccentric_to_mean(&self, ecc: f64) -> f64 {
+        ecc - self.e() * ecc.sin()
+    }
+
+    // M -> E：牛顿法解开普勒方程，初值取 M (e < 0.8) 或 ±π
+    // 先把 M 归到 (-π, π]，结果再加回整圈
+    pub fn mean_to_eccentric(&self, mean: f64, tol: f64) -> f64 {
+        let e = self.e();
+        let turns = (mean / (2.0 * PI)).round();
+        let m = mean - turns * 2.0 * PI;
+
+        let mut ecc = if e < 0.8 { m } else { PI.copysign(m) };
+        for _ in 0..50 {
+            let delta = (ecc - e * ecc.sin() - m) / (1.0 - e * ecc.cos());
+            ecc -= delta;
+            if delta.abs() < tol { break; }
+        }
+        ecc + turns * 2.0 * PI
+    }
+
+    // ====================== 焦点弦与反射 ======================
+
+    // 过焦点 F1 (f_points().p1) 的通径端点
+    pub fn latus_rectum(&self) -> DPoint {
+        self.focal_chord(self.v_a().roll90())
+    }
+
+    // 过焦点 F1、方向为 dir 的焦点弦的两个端点
+    pub fn focal_chord(&self, dir: Vec2) -> DPoint {
+        self.intersect_line(Line::new(self.f_points().p1, dir))
+    }
+
+    // 光线 incident (从 p 沿 +v 射出) 在曲线上反射后的光线；未击中时返回 Line::NAN
+    // 从一个焦点射出的光线反射后经过另一个焦点
+    pub fn reflect_ray(&self, incident: Line) -> Line {
+        match incident.first_hit(self.intersect_line(incident)) {
+            Some(q) => incident.reflect_at(q, self.der(self.theta_of_p(q))),
+            None => Line::NAN,
+        }
+    }
+
     // ====================== 导数与切线 ======================
 
     // 切向量 P'(t) = -U sin t + V cos t
@@ -169,11 +252,71 @@ impl Ellipse {
         self.v * cos - self.u * sin
     }
 
+    // 二阶导 P''(t) = -U cos t - V sin t (指向中心)
+    #[inline]
+    pub fn der2(&self, theta: f64) -> Vec2 {
+        let (sin, cos) = theta.sin_cos();
+        -(self.u * cos + self.v * sin)
+    }
+
+    // 曲率 κ = |P' × P''| / |P'|³
+    // 化简后分子恒为 |U × V|，长轴端点处最大 (a / b²)，短轴端点处最小 (b / a²)
+    pub fn curvature_at(&self, theta: f64) -> f64 {
+        let d = self.der(theta);
+        d.cross_len(self.der2(theta)) / d.len().powi(3)
+    }
+
     // 切线
     pub fn tangent_line_at(&self, theta: f64) -> Line {
         Line::new(self.index_point(theta), self.der(theta))
     }
 
+    // 法线 (切向量转 90°)
+    pub fn normal_line_at(&self, theta: f64) -> Line {
+        Line::new(self.index_point(theta), self.der(theta).roll90())
+    }
+
+    // 曲率中心 (渐屈线上的点)：P + J(P') |P'|² / (P' × P'')
+    // 与 U、V 的朝向无关；椭圆的渐屈线是一条星形线 (astroid)
+    pub fn evolute_point(&self, theta: f64) -> Vec2 {
+        let d = self.der(theta);
+        self.index_point(theta) + d.roll90() * (d.pow2() / d.cross(self.der2(theta)))
+    }
+
+    // ====================== 一般方程与位置关系 ======================
+
+    /// 转换为一般方程 Ax² + Bxy + Cy² + Dx + Ey + F = 0
+    /// 由 d = X - C = U cosθ + V sinθ 解出 cosθ = (d × V) / (U × V), sinθ = (U × d) / (U × V)
+    /// 代入 cos²θ + sin²θ = 1，并整体除以 (U × V)² 归一化：
+    /// 曲线内部 eval < 0，边界 eval = 0，外部 eval > 0
+    pub fn to_conic(self) -> Conic {
+        let det = self.u.cross(self.v);
+        let det2 = det * det;
+        let (u, v, p) = (self.u, self.v, self.p);
+
+        // 关于 d 的二次型系数
+        let a = (u.y * u.y + v.y * v.y) / det2;
+        let b = -2.0 * (u.x * u.y + v.x * v.y) / det2;
+        let c = (u.x * u.x + v.x * v.x) / det2;
+
+        // 平移回中心 P
+        let d = -2.0 * a * p.x - b * p.y;
+        let e = -2.0 * c * p.y - b * p.x;
+        let f = a * p.x * p.x + b * p.x * p.y + c * p.y * p.y - 1.0;
+
+        Conic::new(a, b, c, d, e, f)
+    }
+
+    /// 点是否严格位于椭圆内部 (边界上的点返回 false)
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        self.to_conic().eval(p) < -Conic::EPSILON
+    }
+
+    /// 与直线求交 (转发至 line520::x_ellipse_line)
+    pub fn intersect_line(&self, l: Line) -> DPoint {
+        x_ellipse_line(self, &l)
+    }
+
     // ====================== 距离优化求解 (关键部分) ======================
 
     // 目标函数：f(t) = |P(t) - Target|^2
@@ -262,4 +405,154 @@ impl std::fmt::Display for Ellipse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Ellipse(Center:{}, U:{}, V:{})", self.p, self.u, self.v)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_forest::statistics::random::RandomMaster;
+
+    fn sample_ellipse() -> Ellipse {
+        Ellipse::new(Vec2::new(1.0, -2.0), Vec2::new(3.0, 1.0), Vec2::new(-0.5, 2.0))
+    }
+
+    #[test]
+    fn test_boundary_points_not_contained() {
+        let e = sample_ellipse();
+        let conic = e.to_conic();
+        let theta = RandomMaster::uniform(0.0, 2.0 * PI);
+        for _ in 0..100 {
+            let p = e.index_point(theta.compute());
+            assert!(conic.eval(p).abs() < 1e-9, "eval = {}", conic.eval(p));
+            assert!(!e.contains_point(p));
+        }
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let e = sample_ellipse();
+        assert!(e.contains_point(e.p));
+        assert!(e.contains_point(e.p + e.u * 0.5 + e.v * 0.5));
+        assert!(!e.contains_point(e.p + e.u * 1.1));
+        assert!(!e.contains_point(Vec2::new(100.0, 100.0)));
+    }
+
+    #[test]
+    fn test_intersect_line() {
+        let e = sample_ellipse();
+        // 过中心的直线与椭圆交于两点，均在曲线上
+        let l = Line::new(e.p, Vec2::new(1.0, 0.3));
+        let x = e.intersect_line(l);
+        let conic = e.to_conic();
+        assert!(conic.eval(x.p1).abs() < 1e-9);
+        assert!(conic.eval(x.p2).abs() < 1e-9);
+        assert!((x.p1 - x.p2).len() > 1.0);
+    }
+
+    #[test]
+    fn test_curvature_at() {
+        // 圆：κ = 1 / r
+        let c = Ellipse::new(Vec2::ZERO, Vec2::new(2.0, 0.0), Vec2::new(0.0, 2.0));
+        assert!((c.curvature_at(0.7) - 0.5).abs() < 1e-12);
+
+        // 长短轴端点：a / b² 与 b / a²
+        let e = sample_ellipse();
+        let (a, b) = e.ab();
+        let (ta, tb) = (e.theta_a().n1, e.theta_b().n1);
+        assert!((e.curvature_at(ta) - a / (b * b)).abs() < 1e-9);
+        assert!((e.curvature_at(tb) - b / (a * a)).abs() < 1e-9);
+
+        // 二阶导与数值差分一致
+        let (t, h) = (1.3, 1e-4);
+        let num = (e.index_point(t + h) + e.index_point(t - h) - e.index_point(t) * 2.0) / (h * h);
+        assert!((num - e.der2(t)).len() < 1e-5);
+    }
+
+    #[test]
+    fn test_focal_chord_and_reflection() {
+        let e = sample_ellipse();
+        let (a, b) = e.ab();
+        let f = e.f_points();
+
+        // 焦点弦：1/r1 + 1/r2 = 2a / b²
+        for dir in [Vec2::new(1.0, 0.0), Vec2::new(0.3, 1.0), Vec2::new(-2.0, 0.7)] {
+            let ch = e.focal_chord(dir);
+            let (r1, r2) = ((ch.p1 - f.p1).len(), (ch.p2 - f.p1).len());
+            assert!((1.0 / r1 + 1.0 / r2 - 2.0 * a / (b * b)).abs() < 1e-9);
+        }
+
+        // 通径长 2b² / a
+        let lr = e.latus_rectum();
+        assert!(((lr.p1 - lr.p2).len() - 2.0 * e.semi_latus_rectum()).abs() < 1e-9);
+
+        // 从 F1 射出的光线反射后经过 F2
+        for k in 0..8 {
+            let dir = Vec2::new((k as f64).cos(), (k as f64).sin());
+            let r = e.reflect_ray(Line::new(f.p1, dir));
+            assert!(r.dis_p(f.p2) < 1e-9);
+            assert!((e.theta_of_p(r.p) - e.theta_of_p(e.index_point(e.theta_of_p(r.p)))).abs() < 1e-12);
+        }
+
+        // 外部背离椭圆的光线未击中
+        assert!(e.reflect_ray(Line::new(Vec2::new(100.0, 0.0), Vec2::new(1.0, 0.0))).p.x.is_nan());
+    }
+
+    #[test]
+    fn test_normal_and_evolute() {
+        let e = sample_ellipse();
+        for theta in [0.0, 0.9, 2.5, 4.0] {
+            let n = e.normal_line_at(theta);
+            assert!(n.v.dot(e.der(theta)).abs() < 1e-12);
+
+            // 曲率中心在法线上，到曲线点的距离为 1/κ
+            let q = e.evolute_point(theta);
+            assert!(n.dis_p(q) < 1e-9);
+            assert!(((q - n.p).len() - 1.0 / e.curvature_at(theta)).abs() < 1e-9);
+        }
+
+        // 标准位置的椭圆：渐屈线 (ax)^(2/3) + (by)^(2/3) = (a² - b²)^(2/3)
+        let (a, b) = (3.0f64, 2.0f64);
+        let std = Ellipse::new(Vec2::ZERO, Vec2::new(a, 0.0), Vec2::new(0.0, b));
+        for theta in [0.3, 1.2, 2.0, 5.5] {
+            let q = std.evolute_point(theta);
+            let lhs = (a * q.x).abs().powf(2.0 / 3.0) + (b * q.y).abs().powf(2.0 / 3.0);
+            assert!((lhs - (a * a - b * b).powf(2.0 / 3.0)).abs() < 1e-9);
+        }
+
+        // 圆的曲率中心就是圆心
+        let c = Ellipse::new(Vec2::new(1.0, 1.0), Vec2::new(0.0, 2.0), Vec2::new(2.0, 0.0));
+        assert!((c.evolute_point(0.4) - c.p).len() < 1e-12);
+    }
+
+    #[test]
+    fn test_anomalies() {
+        // 圆 (e = 0)：三种近点角相同
+        let c = Ellipse::new(Vec2::new(1.0, 1.0), Vec2::new(0.0, 2.0), Vec2::new(-2.0, 0.0));
+        for x in [-2.0, 0.3, 1.5, 3.0] {
+            assert!((c.true_to_eccentric(x) - x).abs() < 1e-12);
+            assert!((c.eccentric_to_mean(x) - x).abs() < 1e-12);
+            assert!((c.mean_to_eccentric(x, 1e-14) - x).abs() < 1e-12);
+        }
+
+        let e = sample_ellipse();
+        let (ecc, p) = (e.e(), e.semi_latus_rectum());
+        let (f1, conic) = (e.f_points().p1, e.to_conic());
+        // ν = 0 为近拱点，距焦点 a - c
+        assert!(((e.point_at_true_anomaly(0.0) - f1).len() - (e.a() - e.c())).abs() < 1e-9);
+        for nu in [-3.0, -1.0, 0.0, 0.5, 2.0, 3.1, 5.0] {
+            let q = e.point_at_true_anomaly(nu);
+            assert!(conic.eval(q).abs() < 1e-9);
+            assert!(((q - f1).len() - p / (1.0 + ecc * nu.cos())).abs() < 1e-9);
+            // 换算成偏近点角后是同一个点
+            assert!((e.point_at_eccentric_anomaly(e.true_to_eccentric(nu)) - q).len() < 1e-9);
+        }
+
+        // M -> E -> M 往返 (含多圈与高离心率)
+        let thin = Ellipse::new(Vec2::ZERO, Vec2::new(5.0, 0.0), Vec2::new(0.0, 0.3));
+        for el in [e, thin] {
+            for m in [-7.0, -PI, -0.1, 0.0, 0.01, 1.0, 3.0, PI, 10.0] {
+                let round = el.eccentric_to_mean(el.mean_to_eccentric(m, 1e-15));
+                assert!((round - m).abs() < 1e-12, "m = {}, round = {}", m, round);
+            }
+        }
+    }
+}
@@ -2,8 +2,11 @@
 #![allow(dead_code)]
 
 use std::fmt;
+use crate::math_forest::algebra::fertile::d_num::DNum;
+use crate::math_forest::geometry::d2::fertile::d_point::DPoint;
 use crate::math_forest::geometry::d2::linear::line::Line;
 use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+use super::conic::Conic;
 
 /// HLine: 平行双直线 (Parallel Double Lines)
 /// 通常用于表示圆锥曲线的准线对
@@ -34,6 +37,48 @@ impl HLine {
         Line::new(self.p2, self.v)
     }
 
+    /// 点 p0 到平行双直线的最短距离（到两条直线距离的最小值）
+    pub fn dis_p(&self, p0: Vec2) -> f64 {
+        self.l1().dis_p(p0).min(self.l2().dis_p(p0))
+    }
+
+    /// 平行双直线上离 p0 最近的点（两条直线上的投影取较近者）
+    pub fn closest_p(&self, p0: Vec2) -> Vec2 {
+        let (l1, l2) = (self.l1(), self.l2());
+        if l1.dis_p_pow2(p0) <= l2.dis_p_pow2(p0) {
+            l1.project_p(p0)
+        } else {
+            l2.project_p(p0)
+        }
+    }
+
+    /// 点是否在平行双直线上（距离不超过 tol）
+    pub fn contains(&self, p0: Vec2, tol: f64) -> bool {
+        self.dis_p(p0) <= tol
+    }
+
+    /// 多值索引：n1 取第一条线，n2 取第二条线
+    pub fn index_d_point(&self, t: DNum) -> DPoint {
+        DPoint::new(self.l1().index_point(t.n1), self.l2().index_point(t.n2))
+    }
+
+    // ====================== 几何属性 ======================
+
+    /// 两线间距（p2 到第一条线的距离）
+    pub fn separation(&self) -> f64 {
+        self.l1().dis_p(self.p2)
+    }
+
+    /// 中线：到两条线等距的平行线，过 p1、p2 的中点
+    pub fn mid_line(&self) -> Line {
+        Line::new((self.p1 + self.p2) * 0.5, self.v)
+    }
+
+    /// 转换为一般方程（两直线方程之积，见 Conic::from_line_pair）
+    pub fn to_conic(self) -> Conic {
+        Conic::from_line_pair(&self.l1(), &self.l2())
+    }
+
     pub fn get_type(&self) -> &str { "HLine" }
 }
 
@@ -43,4 +88,51 @@ impl fmt::Display for HLine {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "HLine(p1: {}, p2: {}, v: {})", self.p1, self.p2, self.v)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_separation_and_mid_line() {
+        let (p1, p2) = (Vec2::new(1.0, 1.0), Vec2::new(4.0, -2.0));
+        let v = Vec2::new(2.0, 1.0);
+        let hl = HLine::new(p1, p2, v);
+
+        // 构造点之差在法向上的投影
+        let n = v.roll90().unit();
+        assert!((hl.separation() - (p2 - p1).dot(n).abs()).abs() < 1e-12);
+
+        let m = hl.mid_line();
+        for t in [-2.0, 0.0, 3.5] {
+            let q = m.index_point(t);
+            assert!((hl.l1().dis_p(q) - hl.separation() * 0.5).abs() < 1e-12);
+            assert!((hl.l2().dis_p(q) - hl.separation() * 0.5).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_closest_and_contains() {
+        let hl = HLine::new(Vec2::new(0.0, 1.0), Vec2::new(5.0, -1.0), Vec2::new(1.0, 0.0));
+        let q = Vec2::new(3.0, 0.2);
+        assert!((hl.dis_p(q) - 0.8).abs() < 1e-12);
+        assert!(hl.closest_p(q).dis(Vec2::new(3.0, 1.0)) < 1e-12);
+        assert!(hl.contains(Vec2::new(-7.0, -1.0), 1e-9));
+        assert!(!hl.contains(q, 0.5));
+    }
+
+    #[test]
+    fn test_to_conic_vanishes_on_lines() {
+        let hl = HLine::new(Vec2::new(1.0, 2.0), Vec2::new(-1.0, 0.5), Vec2::new(1.0, -3.0));
+        let c = hl.to_conic();
+        for t in [-3.0, -0.7, 0.0, 1.3, 4.0] {
+            let dp = hl.index_d_point(DNum::new(t, t));
+            assert!(c.eval(dp.p1).abs() < 1e-12);
+            assert!(c.eval(dp.p2).abs() < 1e-12);
+        }
+        // 中线上的点位于两线之间，方程值为负：-(d/2)^2
+        let half = hl.separation() * 0.5;
+        assert!((c.eval(hl.mid_line().p) + half * half).abs() < 1e-12);
+    }
+}
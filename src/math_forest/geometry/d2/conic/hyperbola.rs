@@ -5,6 +5,7 @@ use crate::math_forest::algebra::fertile::d_num::DNum;
 use crate::math_forest::algebra::fertile::q_num::QNum;
 use crate::math_forest::geometry::d2::fertile::d_point::DPoint;
 use crate::math_forest::geometry::d2::fertile::q_point::QPoint;
+use crate::math_forest::geometry::d2::intersection::line520::x_hyperbola_line;
 use crate::math_forest::geometry::d2::linear::line::Line;
 use crate::math_forest::geometry::d2::linear::vec2::Vec2;
 
@@ -125,6 +126,12 @@ impl Hyperbola {
         DPoint::new_pv(self.p, f_vec)
     }
 
+    /// 半通径 p = b² / a
+    pub fn semi_latus_rectum(&self) -> f64 {
+        let b = self.b();
+        b * b / self.a()
+    }
+
     /// 准线 (Directrix): HLine
     pub fn l(&self) -> HLine {
         let a = self.a();
@@ -154,6 +161,84 @@ impl Hyperbola {
         Line::new(self.index_point(t), self.der(t))
     }
 
+    /// 法线
+    pub fn normal_at(&self, t: f64) -> Line {
+        Line::new(self.index_point(t), self.der(t).roll90())
+    }
+
+    /// 曲线上点 q 对应的参数 t (q - C = tU + (1/t)V 的 U 分量)
+    pub fn t_of_p(&self, q: Vec2) -> f64 {
+        (q - self.p).rsv(self.u, self.v).0
+    }
+
+    // ====================== 轨道参数 (近点角) ======================
+    // 以焦点 F1 (f_points().p1) 为引力中心，轨道为 F1 内侧的一支 (t > 0)，近拱点为顶点 C + a·v_a
+    // 近拱点在 F1 的 -v_a 方向，角度从该方向起绕 F1 逆时针度量；真近点角的取值范围为 |ν| < arccos(-1/e)
+
+    // (焦点指向近拱点的方向, 其逆时针旋转 90°)
+    fn orbit_axes(&self) -> (Vec2, Vec2) {
+        let x = -self.v_a();
+        (x, x.roll90())
+    }
+
+    /// 真近点角 ν (在 F1 处量得) 对应的点，r = a(e² - 1) / (1 + e cos ν)
+    pub fn point_at_true_anomaly(&self, nu: f64) -> Vec2 {
+        let r = self.semi_latus_rectum() / (1.0 + self.e() * nu.cos());
+        let (sin, cos) = nu.sin_cos();
+        let (x, y) = self.orbit_axes();
+        self.f_points().p1 + (x * cos + y * sin) * r
+    }
+
+    /// 双曲近点角 H 对应的点 C + a coshH·v_a + b sinhH·y (y 与真近点角的正向一致)
+    pub fn point_at_hyperbolic_anomaly(&self, h: f64) -> Vec2 {
+        let (_, y) = self.orbit_axes();
+        self.p + self.v_a() * (self.a() * h.cosh()) + y * (self.b() * h.sinh())
+    }
+
+    /// ν -> H：tanh(H/2) = sqrt((e - 1) / (e + 1)) tan(ν/2)
+    pub fn true_to_hyperbolic(&self, nu: f64) -> f64 {
+        let e = self.e();
+        2.0 * (((e - 1.0) / (e + 1.0)).sqrt() * (nu * 0.5).tan()).atanh()
+    }
+
+    /// H -> M：双曲开普勒方程 M = e sinhH - H
+    pub fn hyperbolic_to_mean(&self, h: f64) -> f64 {
+        self.e() * h.sinh() - h
+    }
+
+    /// M -> H：牛顿法解双曲开普勒方程，初值取 asinh(M / e)
+    pub fn mean_to_hyperbolic(&self, mean: f64, tol: f64) -> f64 {
+        let e = self.e();
+        let mut h = (mean / e).asinh();
+        for _ in 0..50 {
+            let delta = (e * h.sinh() - h - mean) / (e * h.cosh() - 1.0);
+            h -= delta;
+            if delta.abs() < tol { break; }
+        }
+        h
+    }
+
+    // ====================== 焦点弦与反射 ======================
+
+    /// 过焦点 F1 (f_points().p1，位于 t > 0 一支内侧) 的通径端点
+    pub fn latus_rectum(&self) -> DPoint {
+        self.focal_chord(self.v_b())
+    }
+
+    /// 过焦点 F1、方向为 dir 的焦点弦的两个端点 (可能分属两支)
+    pub fn focal_chord(&self, dir: Vec2) -> DPoint {
+        x_hyperbola_line(self, &Line::new(self.f_points().p1, dir))
+    }
+
+    /// 光线 incident (从 p 沿 +v 射出) 在曲线上反射后的光线；未击中时返回 Line::NAN
+    /// 从一个焦点射出的光线反射后，反向延长线经过另一个焦点
+    pub fn reflect_ray(&self, incident: Line) -> Line {
+        match incident.first_hit(x_hyperbola_line(self, &incident)) {
+            Some(q) => incident.reflect_at(q, self.der(self.t_of_p(q))),
+            None => Line::NAN,
+        }
+    }
+
     // ====================== 共轭性质 ======================
 
     /// 共轭双曲线
@@ -259,3 +344,67 @@ impl std::fmt::Display for Hyperbola {
         write!(f, "Hyperbola(C:{}, U:{}, V:{})", self.p, self.u, self.v)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focal_chord_and_reflection() {
+        let h = Hyperbola::new(Vec2::new(1.0, 2.0), Vec2::new(2.0, 1.0), Vec2::new(1.0, -1.5));
+        let f = h.f_points();
+
+        // 通径：两端点在同一支上，关于实轴对称，长 2b² / a
+        let lr = h.latus_rectum();
+        assert!(((lr.p1 - lr.p2).len() - 2.0 * h.semi_latus_rectum()).abs() < 1e-9);
+        assert!(((lr.p1 + lr.p2) * 0.5 - f.p1).len() < 1e-9);
+
+        // 从 F1 射出的光线反射后，反射线 (所在直线) 经过 F2
+        for dir in [h.v_b(), -h.v_b(), h.v_b() * 0.5 - h.v_a()] {
+            let r = h.reflect_ray(Line::new(f.p1, dir));
+            assert!(r.p.x.is_finite());
+            assert!(r.dis_p(f.p2) < 1e-9, "dis = {}", r.dis_p(f.p2));
+            // 看起来像从 F2 发出
+            assert!(r.v.dot(r.p - f.p2) > 0.0);
+        }
+
+        // 方向落在两渐近线夹角内的光线从焦点射出后不会击中曲线
+        assert!(h.reflect_ray(Line::new(f.p1, h.v_a())).p.x.is_nan());
+    }
+
+    #[test]
+    fn test_normal_at() {
+        // 顶点处法线即实轴，经过两焦点
+        let h = Hyperbola::new(Vec2::new(1.0, 2.0), Vec2::new(2.0, 1.0), Vec2::new(1.0, -1.5));
+        let n = h.normal_at(h.t0().n1);
+        assert!(n.dis_p(h.f_points().p1) < 1e-9 && n.dis_p(h.f_points().p2) < 1e-9);
+        for t in [-2.0, 0.7, 3.0] {
+            assert!(h.normal_at(t).v.dot(h.der(t)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_anomalies() {
+        let h = Hyperbola::new(Vec2::new(1.0, 2.0), Vec2::new(2.0, 1.0), Vec2::new(1.0, -1.5));
+        let (e, p, f1) = (h.e(), h.semi_latus_rectum(), h.f_points().p1);
+        // ν = 0 为 t > 0 一支的顶点，距焦点 c - a
+        assert!((h.point_at_true_anomaly(0.0) - h.a_vertex().p1).len() < 1e-9);
+        assert!(((h.point_at_true_anomaly(0.0) - f1).len() - (h.c() - h.a())).abs() < 1e-9);
+
+        let nu_max = (-1.0 / e).acos();
+        for nu in [-0.9 * nu_max, -0.5, 0.0, 0.3, 0.99 * nu_max] {
+            let q = h.point_at_true_anomaly(nu);
+            // 在 t > 0 一支上
+            let t = h.t_of_p(q);
+            assert!(t > 0.0 && (h.index_point(t) - q).len() < 1e-9);
+            assert!(((q - f1).len() - p / (1.0 + e * nu.cos())).abs() < 1e-9);
+            assert!((h.point_at_hyperbolic_anomaly(h.true_to_hyperbolic(nu)) - q).len() < 1e-8);
+        }
+
+        // M -> H -> M 往返
+        for m in [-50.0, -2.0, -0.01, 0.0, 0.5, 3.0, 1000.0] {
+            let round = h.hyperbolic_to_mean(h.mean_to_hyperbolic(m, 1e-15));
+            assert!((round - m).abs() < 1e-12 * m.abs().max(1.0), "m = {}, round = {}", m, round);
+        }
+    }
+}
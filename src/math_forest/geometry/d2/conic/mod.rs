@@ -11,4 +11,4 @@ pub mod x_line;
 pub mod h_line;
 
 pub mod wipkyy;
-mod conic;
+pub mod conic;
@@ -7,6 +7,7 @@ use crate::math_forest::algebra::fertile::d_num::DNum;
 use crate::math_forest::algebra::fertile::q_num::QNum;
 use crate::math_forest::geometry::d2::fertile::d_point::DPoint;
 use crate::math_forest::geometry::d2::fertile::q_point::QPoint;
+use crate::math_forest::geometry::d2::intersection::line520::x_parabola_line;
 use crate::math_forest::geometry::d2::linear::line::Line;
 use crate::math_forest::geometry::d2::linear::vec2::Vec2;
 
@@ -29,6 +30,14 @@ impl Parabola {
         Self { p: Vec2::ZERO, v: Vec2::J } // 开口向上 y = x^2/4
     }
 
+    /// 焦点-准线构造：顶点为焦点与其在准线上垂足的中点，V 由顶点指向焦点 (焦距 = |V|)
+    /// 焦点落在准线上时 V = 0，抛物线退化
+    pub fn from_focus_directrix(focus: Vec2, directrix: Line) -> Self {
+        let foot = directrix.project_p(focus);
+        let vertex = (foot + focus) * 0.5;
+        Self { p: vertex, v: focus - vertex }
+    }
+
     pub fn get_type(&self) -> &str { "Parabola" }
 
     // ====================== 几何属性 ======================
@@ -58,6 +67,11 @@ impl Parabola {
         Line::new(self.p - self.v, self.u())
     }
 
+    /// 半通径 p = 2f (焦点到曲线沿垂直于轴方向的距离)
+    pub fn semi_latus_rectum(&self) -> f64 {
+        2.0 * self.focal_length()
+    }
+
     // ====================== 核心索引 ======================
 
     /// 计算索引点 P(t) = P + t*U + (t^2 / 4)*V
@@ -81,6 +95,11 @@ impl Parabola {
         )
     }
 
+    /// 曲线上点 q 对应的参数 t (q - P = tU + (t²/4)V 的 U 分量)
+    pub fn t_of_p(&self, q: Vec2) -> f64 {
+        (q - self.p).rsv(self.u(), self.v).0
+    }
+
     // ====================== 导数与切线 ======================
 
     /// 导数 P'(t) = U + (t/2)V
@@ -88,11 +107,76 @@ impl Parabola {
         self.u() + self.v * (t * 0.5)
     }
 
+    /// 二阶导 P''(t) = V / 2 (常向量)
+    pub fn der2(&self, _t: f64) -> Vec2 {
+        self.v * 0.5
+    }
+
+    /// 曲率 κ = |P' × P''| / |P'|³
+    /// 顶点 (t = 0) 处最大，为 1 / (2f)
+    pub fn curvature_at(&self, t: f64) -> f64 {
+        let d = self.der(t);
+        d.cross_len(self.der2(t)) / d.len().powi(3)
+    }
+
     /// 切线
     pub fn tangent_line(&self, t: f64) -> Line {
         Line::new(self.index_point(t), self.der(t))
     }
 
+    /// 法线
+    pub fn normal_at(&self, t: f64) -> Line {
+        Line::new(self.index_point(t), self.der(t).roll90())
+    }
+
+    // ====================== 焦点弦与反射 ======================
+
+    /// 通径端点 F ± 2U (t = ±2)
+    pub fn latus_rectum(&self) -> DPoint {
+        self.index_d_point(DNum::new(2.0, -2.0))
+    }
+
+    /// 过焦点、方向为 dir 的焦点弦的两个端点
+    /// dir 平行于轴时只有一个交点 (两端点重合)
+    pub fn focal_chord(&self, dir: Vec2) -> DPoint {
+        x_parabola_line(self, &Line::new(self.focus(), dir))
+    }
+
+    /// 光线 incident (从 p 沿 +v 射出) 在曲线上反射后的光线；未击中时返回 Line::NAN
+    /// 平行于轴的光线反射后经过焦点
+    pub fn reflect_ray(&self, incident: Line) -> Line {
+        match incident.first_hit(x_parabola_line(self, &incident)) {
+            Some(q) => incident.reflect_at(q, self.der(self.t_of_p(q))),
+            None => Line::NAN,
+        }
+    }
+
+    // ====================== 包围盒 ======================
+
+    /// 参数区间 [t_min, t_max] 上曲线段的轴对齐包围盒 (min, max)
+    /// 每个分量是 t 的二次函数，极值只可能出现在端点或 P'(t) 分量为零处：
+    /// U.x + (t/2)V.x = 0 => t* = -2U.x / V.x (y 分量同理)
+    pub fn bounding_box(&self, t_min: f64, t_max: f64) -> (Vec2, Vec2) {
+        let (t0, t1) = if t_min <= t_max { (t_min, t_max) } else { (t_max, t_min) };
+        let u = self.u();
+
+        // 分量驻点，夹到区间内；分量退化为线性时驻点不存在，用端点代替
+        let stationary = |u_c: f64, v_c: f64| -> f64 {
+            if v_c.abs() < Vec2::EPSILON { t0 } else { (-2.0 * u_c / v_c).clamp(t0, t1) }
+        };
+        let tx = stationary(u.x, self.v.x);
+        let ty = stationary(u.y, self.v.y);
+
+        let mut min = Vec2::INF;
+        let mut max = -Vec2::INF;
+        for t in [t0, t1, tx, ty] {
+            let q = self.index_point(t);
+            min = Vec2::new(min.x.min(q.x), min.y.min(q.y));
+            max = Vec2::new(max.x.max(q.x), max.y.max(q.y));
+        }
+        (min, max)
+    }
+
     // ====================== 距离优化求解 ======================
     // 抛物线到点的距离也是一个求解三次/四次方程的问题
 
@@ -144,4 +228,122 @@ impl fmt::Display for Parabola {
         // 对应 Dart 的 toString
         write!(f, "Parabola(Vertex: {}, Axis: {})", self.p, self.v)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 密集采样得到的包围盒，用于对照
+    fn sampled_box(c: &Parabola, t0: f64, t1: f64) -> (Vec2, Vec2) {
+        let n = 20000;
+        let mut min = Vec2::INF;
+        let mut max = -Vec2::INF;
+        for i in 0..=n {
+            let q = c.index_point(t0 + (t1 - t0) * i as f64 / n as f64);
+            min = Vec2::new(min.x.min(q.x), min.y.min(q.y));
+            max = Vec2::new(max.x.max(q.x), max.y.max(q.y));
+        }
+        (min, max)
+    }
+
+    #[test]
+    fn test_bounding_box_std() {
+        // y = x^2 / 4，t ∈ [-2, 4]
+        let c = Parabola::std();
+        let (min, max) = c.bounding_box(-2.0, 4.0);
+        let (smin, smax) = sampled_box(&c, -2.0, 4.0);
+        assert!((min - smin).len() < 1e-6 && (max - smax).len() < 1e-6);
+        // 顶点在区间内，y 最小值为 0
+        assert!(min.y.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_bounding_box_rotated() {
+        let c = Parabola::new(Vec2::new(1.0, -1.0), Vec2::new(0.6, 0.8));
+        for (t0, t1) in [(-3.0, 5.0), (0.5, 2.0), (4.0, -4.0)] {
+            let (min, max) = c.bounding_box(t0, t1);
+            let (lo, hi) = if t0 <= t1 { (t0, t1) } else { (t1, t0) };
+            let (smin, smax) = sampled_box(&c, lo, hi);
+            assert!((min - smin).len() < 1e-6, "{} vs {}", min, smin);
+            assert!((max - smax).len() < 1e-6, "{} vs {}", max, smax);
+        }
+    }
+
+    #[test]
+    fn test_curvature_at() {
+        // y = x² / 4：κ(x) = (1/2) / (1 + x²/4)^{3/2}，参数 t 即 x
+        let c = Parabola::std();
+        for t in [-3.0f64, 0.0, 0.5, 2.0] {
+            let expect = 0.5 / (1.0 + t * t / 4.0).powf(1.5);
+            assert!((c.curvature_at(t) - expect).abs() < 1e-12);
+        }
+
+        // 旋转、平移不改变曲率；顶点处 1 / (2f)
+        let r = Parabola::new(Vec2::new(1.0, -1.0), Vec2::new(0.6, 0.8) * 3.0);
+        assert!((r.curvature_at(0.0) - 1.0 / 6.0).abs() < 1e-12);
+        assert!((r.curvature_at(1.5) - r.curvature_at(-1.5)).abs() < 1e-12);
+        assert!(r.curvature_at(4.0) < r.curvature_at(1.0));
+    }
+
+    #[test]
+    fn test_focal_chord_and_reflection() {
+        let c = Parabola::new(Vec2::new(1.0, -1.0), Vec2::new(0.6, 0.8) * 1.5);
+        let f = c.focus();
+
+        // 通径垂直于轴，长 4f
+        let lr = c.latus_rectum();
+        assert!(((lr.p1 - lr.p2).len() - 2.0 * c.semi_latus_rectum()).abs() < 1e-12);
+        assert!(((lr.p1 + lr.p2) * 0.5 - f).len() < 1e-12);
+        assert!((lr.p1 - lr.p2).dot(c.v).abs() < 1e-12);
+
+        // 焦点弦：1/r1 + 1/r2 = 2/p
+        let ch = c.focal_chord(Vec2::new(1.0, 0.3));
+        let (r1, r2) = ((ch.p1 - f).len(), (ch.p2 - f).len());
+        assert!((1.0 / r1 + 1.0 / r2 - 2.0 / c.semi_latus_rectum()).abs() < 1e-9);
+
+        // 平行于轴射入的光线反射后都经过焦点
+        for s in [-3.0, -1.0, 0.2, 2.5] {
+            let incident = Line::new(c.p + c.v * 20.0 + c.u() * s, -c.v);
+            let r = c.reflect_ray(incident);
+            assert!(r.dis_p(f) < 1e-9, "s = {}, dis = {}", s, r.dis_p(f));
+            assert!(r.v.dot(f - r.p) > 0.0);
+        }
+
+        // 背离曲线的光线未击中
+        let miss = c.reflect_ray(Line::new(c.p + c.v * 20.0, c.v));
+        assert!(miss.p.x.is_nan());
+    }
+
+    #[test]
+    fn test_normal_at() {
+        // 顶点处法线即对称轴，经过焦点
+        let c = Parabola::new(Vec2::new(1.0, -1.0), Vec2::new(0.6, 0.8) * 1.5);
+        assert!(c.normal_at(0.0).dis_p(c.focus()) < 1e-12);
+        for t in [-2.0, 0.7, 3.0] {
+            assert!(c.normal_at(t).v.dot(c.der(t)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_from_focus_directrix() {
+        // y = x^2：焦点 (0, 1/4)，准线 y = -1/4
+        let c = Parabola::from_focus_directrix(Vec2::new(0.0, 0.25), Line::new(Vec2::new(3.0, -0.25), Vec2::new(2.0, 0.0)));
+        assert!(c.p.len() < 1e-15);
+        assert!((c.v - Vec2::new(0.0, 0.25)).len() < 1e-15);
+        assert!((c.focal_length() - 0.25).abs() < 1e-15);
+        for t in [-3.0, -1.0, 0.5, 2.0] {
+            let q = c.index_point(t);
+            assert!((q.y - q.x * q.x).abs() < 1e-12, "{:?}", q);
+        }
+
+        // 斜放：焦点与准线往返
+        let focus = Vec2::new(1.0, 2.0);
+        let directrix = Line::new(Vec2::new(-1.0, 0.0), Vec2::new(1.0, 1.0));
+        let c = Parabola::from_focus_directrix(focus, directrix);
+        assert!((c.focus() - focus).len() < 1e-12);
+        assert!(c.directrix().is_same(directrix));
+        // 曲线上的点到焦点与到准线等距
+        let q = c.index_point(1.7);
+        assert!(((q - focus).len() - directrix.dis_p(q)).abs() < 1e-12);
+    }
+}
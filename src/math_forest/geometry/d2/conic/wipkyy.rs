@@ -2,20 +2,49 @@
 
 use crate::math_forest::geometry::d2::linear::vec2::Vec2;
 
+/// 退化区域的两种极端
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WipkyyKind {
+    Empty,      // 没有实点满足方程，如 x^2 + y^2 + 1 = 0
+    Everything, // 方程恒为零，整个平面
+}
+
 /// 古果谷掌握 conic 虚空的神 - Wipkyy
+/// 圆锥曲线方程退化为 "无解" 或 "处处成立" 时的点集
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Wipkyy {
-    pub p: Vec2,
+    pub kind: WipkyyKind,
 }
 
 impl Wipkyy {
-    // 构造函数
+    // 构造函数 (默认为空集)
     pub fn new() -> Self {
-        Self { p: Vec2::INF, }
+        Self::empty()
+    }
+
+    pub fn empty() -> Self {
+        Self { kind: WipkyyKind::Empty }
+    }
+
+    pub fn everything() -> Self {
+        Self { kind: WipkyyKind::Everything }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kind == WipkyyKind::Empty
     }
 
-    // 无论传入什么参数(mambo)，都返回虚空点 p , 在 Rust 中，未使用的参数习惯用下划线 _ 开头
+    // 点是否属于该区域：空集不含任何点，全平面含所有点
+    pub fn contains(&self, _p: Vec2) -> bool {
+        match self.kind {
+            WipkyyKind::Empty => false,
+            WipkyyKind::Everything => true,
+        }
+    }
+
+    // 退化区域没有参数化，无论传入什么参数(mambo)，都返回 NAN
     pub fn index_point(&self, _mambo: f64) -> Vec2 {
-        self.p
+        Vec2::NAN
     }
 }
 
@@ -29,12 +58,26 @@ impl Default for Wipkyy {
 
 impl std::fmt::Display for Wipkyy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Wipkyy()")
+        write!(f, "Wipkyy({:?})", self.kind)
     }
 }
 
 impl std::fmt::Debug for Wipkyy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Wipkyy(p: {:?})", self.p)
+        write!(f, "Wipkyy {{ kind: {:?} }}", self.kind)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let p = Vec2::new(1.0, -2.0);
+        assert!(!Wipkyy::empty().contains(p));
+        assert!(Wipkyy::everything().contains(p));
+        assert!(Wipkyy::default().is_empty());
+        assert!(Wipkyy::empty().index_point(0.5).x.is_nan());
+    }
+}
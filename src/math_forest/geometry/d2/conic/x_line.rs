@@ -1,10 +1,13 @@
 // src/math_forest/geometry/d2/conic/x_line.rs
 #![allow(dead_code)]
 
+use crate::math_forest::algebra::fertile::d_num::DNum;
 use crate::math_forest::geometry::d2::fertile::d_point::DPoint;
+use crate::math_forest::geometry::d2::fertile::q_point::QPoint;
 use crate::math_forest::geometry::d2::intersection::line520;
 use crate::math_forest::geometry::d2::linear::line::Line;
 use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+use super::conic::Conic;
 
 /// XLine: 叉线 - 由顶点 p 和两个方向向量 u, v 确定的两条相交直线
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -62,6 +65,49 @@ impl XLine {
         d1.min(d2)
     }
 
+    /// 叉线上离 p0 最近的点（两条直线上的投影取较近者）
+    pub fn closest_p(&self, p0: Vec2) -> Vec2 {
+        let (l1, l2) = (self.l1(), self.l2());
+        if l1.dis_p_pow2(p0) <= l2.dis_p_pow2(p0) {
+            l1.project_p(p0)
+        } else {
+            l2.project_p(p0)
+        }
+    }
+
+    /// 点是否在叉线上（距离不超过 tol）
+    pub fn contains(&self, p0: Vec2, tol: f64) -> bool {
+        self.dis_p(p0) <= tol
+    }
+
+    /// 多值索引：n1 取第一条线，n2 取第二条线
+    pub fn index_d_point(&self, t: DNum) -> DPoint {
+        DPoint::new(self.l1().index_point(t.n1), self.l2().index_point(t.n2))
+    }
+
+    // ====================== 几何属性 ======================
+
+    /// 两直线的夹角，取锐角 [0, π/2]（直线不分方向）
+    pub fn angle(&self) -> f64 {
+        self.u.cross(self.v).abs().atan2(self.u.dot(self.v).abs())
+    }
+
+    /// 角平分线对：方向为 û + v̂ 与 û - v̂，两者互相垂直
+    pub fn bisectors(&self) -> XLine {
+        let (u1, u2) = (self.u.unit(), self.v.unit());
+        XLine::new(self.p, u1 + u2, u1 - u2)
+    }
+
+    /// 转换为一般方程（两直线方程之积，见 Conic::from_line_pair）
+    pub fn to_conic(self) -> Conic {
+        Conic::from_line_pair(&self.l1(), &self.l2())
+    }
+
+    /// 与圆锥曲线的交点 (至多 4 个，见 line520::x_x_line_conic)
+    pub fn meets_conic(&self, c: &Conic) -> QPoint {
+        line520::x_x_line_conic(self, c)
+    }
+
     pub fn get_type(&self) -> &str { "XLine" }
 }
 
@@ -71,4 +117,84 @@ impl std::fmt::Display for XLine {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "XLine(p: {}, u: {}, v: {})", self.p, self.u, self.v)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    #[test]
+    fn test_bisectors_of_perpendicular_lines() {
+        let xl = XLine::new(Vec2::new(1.0, -2.0), Vec2::new(3.0, 0.0), Vec2::new(0.0, -0.5));
+        assert!((xl.angle() - FRAC_PI_2).abs() < 1e-12);
+
+        let b = xl.bisectors();
+        assert_eq!(b.p, xl.p);
+        // 平分线为 ±45° 方向
+        for d in [b.u, b.v] {
+            assert!((d.x.abs() - d.y.abs()).abs() < 1e-12);
+        }
+        assert!((b.angle() - FRAC_PI_2).abs() < 1e-12);
+        // 平分线与原直线夹 45°
+        assert!((XLine::new(xl.p, xl.u, b.u).angle() - FRAC_PI_4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_closest_and_contains() {
+        let xl = XLine::new(Vec2::ZERO, Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0));
+        let q = Vec2::new(5.0, 1.0);
+        assert!((xl.dis_p(q) - 1.0).abs() < 1e-12);
+        assert!(xl.closest_p(q).dis(Vec2::new(5.0, 0.0)) < 1e-12);
+        assert!(xl.contains(Vec2::new(-3.0, -3.0), 1e-9));
+        assert!(!xl.contains(q, 0.5));
+
+        let dp = xl.index_d_point(DNum::new(2.0, -1.0));
+        assert!(dp.p1.dis(Vec2::new(2.0, 0.0)) < 1e-12);
+        assert!(dp.p2.dis(Vec2::new(-1.0, -1.0)) < 1e-12);
+    }
+
+    #[test]
+    fn test_to_conic_vanishes_on_lines() {
+        let xl = XLine::new(Vec2::new(0.5, 2.0), Vec2::new(2.0, 1.0), Vec2::new(-1.0, 3.0));
+        let c = xl.to_conic();
+        for t in [-3.0, -0.7, 0.0, 1.3, 4.0] {
+            let dp = xl.index_d_point(DNum::new(t, t));
+            assert!(c.eval(dp.p1).abs() < 1e-12);
+            assert!(c.eval(dp.p2).abs() < 1e-12);
+        }
+        // 不在线上的点非零
+        assert!(c.eval(xl.p + Vec2::new(1.0, 0.0)).abs() > 1e-3);
+    }
+
+    #[test]
+    fn test_meets_conic() {
+        // 坐标轴与单位圆：(±1, 0), (0, ±1)
+        let axes = XLine::new(Vec2::ZERO, Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0));
+        let circle = Conic::new(1.0, 0.0, 1.0, 0.0, 0.0, -1.0);
+        let q = axes.meets_conic(&circle);
+        for p in [q.p1, q.p2, q.p3, q.p4] {
+            assert!(circle.eval(p).abs() < 1e-12);
+        }
+        assert!((q.p1.x.abs() - 1.0).abs() < 1e-12 && q.p1.y.abs() < 1e-12);
+        assert!((q.p2.y.abs() - 1.0).abs() < 1e-12 && q.p2.x.abs() < 1e-12);
+        assert!(q.dp1().p1.dis(q.dp1().p2) > 1.0);
+
+        // 双曲线 xy = 1 与叉线 y = ±x：只有 y = x 有实交点
+        let hyperbola = Conic::new(0.0, 1.0, 0.0, 0.0, 0.0, -1.0);
+        let diag = XLine::new(Vec2::ZERO, Vec2::new(1.0, 1.0), Vec2::new(1.0, -1.0));
+        let q = diag.meets_conic(&hyperbola);
+        for p in [q.p1, q.p3] {
+            assert!((p.x.abs() - 1.0).abs() < 1e-12 && (p.x - p.y).abs() < 1e-12);
+        }
+        assert!(q.p2.x.is_nan() && q.p4.x.is_nan());
+
+        // 抛物线 y = x^2 与经过 (0, 2) 的叉线
+        let parabola = Conic::new(1.0, 0.0, 0.0, 0.0, -1.0, 0.0);
+        let xl = XLine::new(Vec2::new(0.0, 2.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 3.0));
+        let q = xl.meets_conic(&parabola);
+        for p in [q.p1, q.p2, q.p3, q.p4] {
+            assert!(parabola.eval(p).abs() < 1e-9 && xl.dis_p(p) < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// src/math_forest/geometry/d2/curve.rs
+#![allow(dead_code)]
+
+use crate::math_forest::geometry::d2::conic::circle::Circle;
+use crate::math_forest::geometry::d2::conic::ellipse::Ellipse;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+use crate::math_forest::geometry::d2::special::hyperelliptic::Hyperelliptic;
+use crate::math_forest::geometry::d2::spline::b_spline::BSpline;
+use crate::math_forest::geometry::d2::spline::bezier::CubicBezier;
+
+// 中心差分步长
+const DIFF_STEP: f64 = 1e-6;
+
+/// 参数曲线 t -> P(t)，供数值求交等通用算法使用
+pub trait Curve2D {
+    fn point_at(&self, t: f64) -> Vec2;
+
+    /// 一阶导数，默认用中心差分近似
+    fn derivative_at(&self, t: f64) -> Vec2 {
+        (self.point_at(t + DIFF_STEP) - self.point_at(t - DIFF_STEP)) / (2.0 * DIFF_STEP)
+    }
+}
+
+// 闭包 t -> P(t) 直接作为曲线 (如李萨如曲线)，导数用默认的中心差分
+impl<F: Fn(f64) -> Vec2> Curve2D for F {
+    fn point_at(&self, t: f64) -> Vec2 {
+        self(t)
+    }
+}
+
+impl Curve2D for Circle {
+    fn point_at(&self, t: f64) -> Vec2 {
+        self.index_point(t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vec2 {
+        let (sin, cos) = t.sin_cos();
+        Vec2::new(-sin, cos) * self.r
+    }
+}
+
+impl Curve2D for Ellipse {
+    fn point_at(&self, t: f64) -> Vec2 {
+        self.index_point(t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vec2 {
+        let (sin, cos) = t.sin_cos();
+        self.v * cos - self.u * sin
+    }
+}
+
+impl Curve2D for Hyperelliptic {
+    fn point_at(&self, t: f64) -> Vec2 {
+        Hyperelliptic::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vec2 {
+        Hyperelliptic::derivative_at(self, t)
+    }
+}
+
+impl Curve2D for CubicBezier {
+    fn point_at(&self, t: f64) -> Vec2 {
+        CubicBezier::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vec2 {
+        CubicBezier::derivative_at(self, t)
+    }
+}
+
+impl Curve2D for BSpline {
+    fn point_at(&self, t: f64) -> Vec2 {
+        BSpline::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vec2 {
+        BSpline::derivative_at(self, t)
+    }
+}
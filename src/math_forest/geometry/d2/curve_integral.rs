@@ -0,0 +1,223 @@
+// src/math_forest/geometry/d2/curve_integral.rs
+#![allow(dead_code)]
+
+// 闭合参数曲线围成区域的积分 (格林公式，把面积分化为沿曲线的线积分)
+//   面积 A = ½∮(x dy − y dx)，一阶矩 ∬x dA = ½∮x² dy，∬y dA = −½∮y² dx
+//   二阶矩 ∬y² dA = −⅓∮y³ dx，∬x² dA = ⅓∮x³ dy，∬xy dA = ½∮x²y dy
+// 结果都带方向：逆时针为正，顺时针为负；自交曲线 (如李萨如曲线) 按环绕数加权，∬w(p)·g dA
+// 曲线首尾必须重合 (不自动用弦闭合)，否则返回 NotClosed
+
+use std::f64::consts::TAU;
+use std::fmt;
+
+use crate::math_forest::geometry::d2::curve::Curve2D;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+use crate::math_forest::numerics::quad::adaptive_simpson;
+
+// 首尾距离容差 (相对曲线尺度)
+const CLOSURE_EPS: f64 = 1e-9;
+// 估计曲线尺度的采样数
+const SCALE_SAMPLES: usize = 64;
+// 环绕数：初始分段数，单段允许的最大转角，以及二分深度上限
+const WINDING_SEGMENTS: usize = 64;
+const MAX_STEP_ANGLE: f64 = 0.5;
+const MAX_WINDING_DEPTH: u32 = 48;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurveIntegralError {
+    InvalidRange,          // t 区间非有限或为空
+    NotClosed { gap: f64 }, // 首尾距离超过容差
+    ZeroArea,              // 面积为零，质心无定义
+    PointOnCurve,          // 求环绕数的点在曲线上
+}
+
+impl fmt::Display for CurveIntegralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurveIntegralError::InvalidRange => write!(f, "invalid parameter range"),
+            CurveIntegralError::NotClosed { gap } => write!(f, "curve is not closed (endpoint gap {})", gap),
+            CurveIntegralError::ZeroArea => write!(f, "enclosed area is zero, centroid is undefined"),
+            CurveIntegralError::PointOnCurve => write!(f, "point lies on the curve"),
+        }
+    }
+}
+
+impl std::error::Error for CurveIntegralError {}
+
+/// 关于原点的二阶矩 (面积惯性矩)：ixx = ∬y² dA，iyy = ∬x² dA，ixy = ∬xy dA
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecondMoments {
+    pub ixx: f64,
+    pub iyy: f64,
+    pub ixy: f64,
+}
+
+// 检查区间与闭合性
+fn check_closed(curve: &impl Curve2D, (t0, t1): (f64, f64)) -> Result<(), CurveIntegralError> {
+    if !t0.is_finite() || !t1.is_finite() || t0 == t1 {
+        return Err(CurveIntegralError::InvalidRange);
+    }
+    let scale = (0..=SCALE_SAMPLES)
+        .map(|i| curve.point_at(t0 + (t1 - t0) * i as f64 / SCALE_SAMPLES as f64).len())
+        .fold(0.0, f64::max);
+    let gap = curve.point_at(t0).dis(curve.point_at(t1));
+    // gap 为 NaN (端点无定义) 也算不闭合
+    if gap.is_nan() || gap > CLOSURE_EPS * (1.0 + scale) {
+        return Err(CurveIntegralError::NotClosed { gap });
+    }
+    Ok(())
+}
+
+/// 有向面积 ½∮(x dy − y dx)，tol 为积分的绝对误差目标
+pub fn enclosed_area(curve: &impl Curve2D, t_range: (f64, f64), tol: f64) -> Result<f64, CurveIntegralError> {
+    check_closed(curve, t_range)?;
+    Ok(0.5 * adaptive_simpson(|t| curve.point_at(t).cross(curve.derivative_at(t)), t_range, tol))
+}
+
+/// 围成区域的质心 (∬x dA / A, ∬y dA / A)，与方向无关
+pub fn enclosed_centroid(curve: &impl Curve2D, t_range: (f64, f64), tol: f64) -> Result<Vec2, CurveIntegralError> {
+    let area = enclosed_area(curve, t_range, tol)?;
+    if area.abs() <= tol {
+        return Err(CurveIntegralError::ZeroArea);
+    }
+    let mx = adaptive_simpson(|t| { let (p, d) = (curve.point_at(t), curve.derivative_at(t)); 0.5 * p.x * p.x * d.y }, t_range, tol);
+    let my = adaptive_simpson(|t| { let (p, d) = (curve.point_at(t), curve.derivative_at(t)); -0.5 * p.y * p.y * d.x }, t_range, tol);
+    Ok(Vec2::new(mx / area, my / area))
+}
+
+/// 关于原点的二阶矩 (带方向，与 enclosed_area 同号)
+pub fn second_moments(curve: &impl Curve2D, t_range: (f64, f64), tol: f64) -> Result<SecondMoments, CurveIntegralError> {
+    check_closed(curve, t_range)?;
+    let integrate = |g: &dyn Fn(Vec2, Vec2) -> f64| adaptive_simpson(|t| g(curve.point_at(t), curve.derivative_at(t)), t_range, tol);
+    Ok(SecondMoments {
+        ixx: integrate(&|p, d| -p.y.powi(3) * d.x / 3.0),
+        iyy: integrate(&|p, d| p.x.powi(3) * d.y / 3.0),
+        ixy: integrate(&|p, d| 0.5 * p.x * p.x * p.y * d.y),
+    })
+}
+
+/// 曲线绕点 p 的圈数 (逆时针为正)：P(t) − p 的总转角 / 2π
+/// 单段转角过大或二分前后不一致 (曲线贴近 p) 时继续细分；p 在曲线上返回 PointOnCurve
+pub fn winding_number(curve: &impl Curve2D, t_range: (f64, f64), p: Vec2) -> Result<i32, CurveIntegralError> {
+    check_closed(curve, t_range)?;
+    let (t0, t1) = t_range;
+    let offset = |t: f64| curve.point_at(t) - p;
+    let h = (t1 - t0) / WINDING_SEGMENTS as f64;
+    let mut total = 0.0;
+    let mut prev = offset(t0);
+    for i in 1..=WINDING_SEGMENTS {
+        let t = if i == WINDING_SEGMENTS { t1 } else { t0 + i as f64 * h };
+        let next = offset(t);
+        total += turn_angle(&offset, t - h, t, prev, next, MAX_WINDING_DEPTH)?;
+        prev = next;
+    }
+    Ok((total / TAU).round() as i32)
+}
+
+// [ta, tb] 上 P(t) − p 转过的有向角度
+fn turn_angle(offset: &impl Fn(f64) -> Vec2, ta: f64, tb: f64, a: Vec2, b: Vec2, depth: u32) -> Result<f64, CurveIntegralError> {
+    if a == Vec2::ZERO || b == Vec2::ZERO || depth == 0 {
+        return Err(CurveIntegralError::PointOnCurve);
+    }
+    let angle = |u: Vec2, v: Vec2| u.cross(v).atan2(u.dot(v));
+    let tm = 0.5 * (ta + tb);
+    let m = offset(tm);
+    if m == Vec2::ZERO {
+        return Err(CurveIntegralError::PointOnCurve);
+    }
+    let direct = angle(a, b);
+    let halves = angle(a, m) + angle(m, b);
+    if direct.abs() <= MAX_STEP_ANGLE && (halves - direct).abs() <= 1e-9 {
+        return Ok(direct);
+    }
+    Ok(turn_angle(offset, ta, tm, a, m, depth - 1)? + turn_angle(offset, tm, tb, m, b, depth - 1)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_forest::geometry::d2::conic::circle::Circle;
+    use crate::math_forest::geometry::d2::special::hyperelliptic::Hyperelliptic;
+    use std::f64::consts::PI;
+
+    const FULL: (f64, f64) = (0.0, TAU);
+
+    #[test]
+    fn test_circle_area_centroid_moments() {
+        let unit = Circle::new(Vec2::ZERO, 1.0);
+        assert!((enclosed_area(&unit, FULL, 1e-12).unwrap() - PI).abs() < 1e-10);
+        // 顺时针参数化：面积为负，质心不变
+        let reversed = |t: f64| Vec2::new(t.cos(), -t.sin());
+        assert!((enclosed_area(&reversed, FULL, 1e-12).unwrap() + PI).abs() < 1e-8);
+
+        // 偏心圆的质心是圆心
+        let c = Circle::new(Vec2::new(3.0, -1.5), 2.0);
+        let centroid = enclosed_centroid(&c, FULL, 1e-12).unwrap();
+        assert!(centroid.dis(c.p) < 1e-10, "{}", centroid);
+
+        // 单位圆盘：ixx = iyy = π/4，ixy = 0；平移后满足平行轴定理 ixx = π/4 + A·cy²
+        let m = second_moments(&unit, FULL, 1e-12).unwrap();
+        assert!((m.ixx - PI / 4.0).abs() < 1e-10 && (m.iyy - PI / 4.0).abs() < 1e-10 && m.ixy.abs() < 1e-10);
+        let shifted = Circle::new(Vec2::new(2.0, 1.0), 1.0);
+        let m = second_moments(&shifted, FULL, 1e-12).unwrap();
+        assert!((m.ixx - (PI / 4.0 + PI)).abs() < 1e-9);
+        assert!((m.iyy - (PI / 4.0 + 4.0 * PI)).abs() < 1e-9);
+        assert!((m.ixy - 2.0 * PI).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hyperelliptic_diamond() {
+        // m = 2：|x|/a + |y|/b = 1 为菱形，面积 2ab (参数化在顶点处导数为零)
+        let h = Hyperelliptic { a: 3.0, b: 2.0, m: 2.0 };
+        assert!((enclosed_area(&h, FULL, 1e-12).unwrap() - 12.0).abs() < 1e-9);
+        assert!(enclosed_centroid(&h, FULL, 1e-12).unwrap().len() < 1e-9);
+    }
+
+    // 有向穿越计数：折线与从 p 出发向 +x 的射线的交点 (向上穿越 +1，向下 −1)
+    fn crossing_number(f: impl Fn(f64) -> Vec2, p: Vec2, n: usize) -> i32 {
+        let pts: Vec<Vec2> = (0..=n).map(|i| f(TAU * i as f64 / n as f64)).collect();
+        let mut w = 0;
+        for s in pts.windows(2) {
+            let (a, b) = (s[0] - p, s[1] - p);
+            if (a.y <= 0.0) != (b.y <= 0.0) {
+                let x = a.x + (b.x - a.x) * (-a.y) / (b.y - a.y);
+                if x > 0.0 { w += if b.y > a.y { 1 } else { -1 }; }
+            }
+        }
+        w
+    }
+
+    #[test]
+    fn test_lissajous_winding() {
+        // (sin 3t, sin 2t)：曲线经过原点 (t = 0, π)，各瓣环绕数符号交替
+        let lissajous = |t: f64| Vec2::new((3.0 * t).sin(), (2.0 * t).sin());
+        assert_eq!(winding_number(&lissajous, FULL, Vec2::ZERO), Err(CurveIntegralError::PointOnCurve));
+        assert_eq!(winding_number(&lissajous, FULL, Vec2::new(5.0, 0.0)), Ok(0));
+
+        let mut seen = Vec::new();
+        for p in [Vec2::new(0.6, 0.9), Vec2::new(-0.6, 0.9), Vec2::new(0.6, 0.3), Vec2::new(-0.6, -0.3), Vec2::new(0.1, 0.6)] {
+            let w = winding_number(&lissajous, FULL, p).unwrap();
+            assert_eq!(w, crossing_number(lissajous, p, 200_000), "p = {}", p);
+            seen.push(w);
+        }
+        assert_eq!(seen, vec![1, -1, -1, 1, 0]);
+
+        // 贴近曲线的点：细分后仍然正确
+        let unit = Circle::new(Vec2::ZERO, 1.0);
+        assert_eq!(winding_number(&unit, FULL, Vec2::new(1.0 - 1e-9, 0.0)), Ok(1));
+        assert_eq!(winding_number(&unit, FULL, Vec2::new(1.0 + 1e-9, 0.0)), Ok(0));
+        // 绕两圈
+        assert_eq!(winding_number(&unit, (0.0, 2.0 * TAU), Vec2::new(0.3, 0.2)), Ok(2));
+    }
+
+    #[test]
+    fn test_errors() {
+        let arc = |t: f64| Vec2::new(t.cos(), t.sin());
+        assert!(matches!(enclosed_area(&arc, (0.0, PI), 1e-10), Err(CurveIntegralError::NotClosed { gap }) if (gap - 2.0).abs() < 1e-12));
+        assert_eq!(enclosed_area(&arc, (1.0, 1.0), 1e-10), Err(CurveIntegralError::InvalidRange));
+        assert_eq!(winding_number(&arc, (0.0, f64::NAN), Vec2::ZERO), Err(CurveIntegralError::InvalidRange));
+        // 来回走同一条线段：面积为零
+        let segment = |t: f64| Vec2::new(t.sin(), 0.0);
+        assert_eq!(enclosed_centroid(&segment, FULL, 1e-10), Err(CurveIntegralError::ZeroArea));
+    }
+}
@@ -79,10 +79,39 @@ impl DPoint {
         self.line().index_q_point(qn).dp2()
     }
 
+    /// 交比 (A, B; C, D) = (AC / BC) / (AD / BD)
+    /// A, B = self.p1, self.p2；C, D = other.p1, other.p2，距离均为直线 AB 上的有向距离
+    /// 四点应共线 (不共线时取 C、D 在直线 AB 上的投影)
+    /// 调和点列 (如 self 与 self.harmonic(t)) 的交比为 -1；D 趋于无穷远时退化为单比 AC / BC
+    pub fn harmonic_range(self, other: DPoint) -> f64 {
+        // 以 A 为原点、AB 为单位的直线坐标
+        let u = self.p2 - self.p1;
+        let coord = |p: Vec2| (p - self.p1).dot(u) / u.pow2();
+        let (a, b) = (0.0, 1.0);
+        let (c, d) = (coord(other.p1), coord(other.p2));
+        ((c - a) / (c - b)) / ((d - a) / (d - b))
+    }
+
     // 交换两点顺序
     pub fn swap(self) -> Self {
         Self { p1: self.p2, p2: self.p1 }
     }
+
+    /// 对两点分别作用 f (如变换、投影)
+    #[inline]
+    pub fn map<F: Fn(Vec2) -> Vec2>(self, f: F) -> Self {
+        Self { p1: f(self.p1), p2: f(self.p2) }
+    }
+
+    /// 两点中离 p 较近者 (距离相等时取 p1)
+    pub fn closest_to(self, p: Vec2) -> Vec2 {
+        if self.p2.dis_pow2(p) < self.p1.dis_pow2(p) { self.p2 } else { self.p1 }
+    }
+
+    /// 两点中离 p 较远者 (距离相等时取 p2)
+    pub fn farthest_from(self, p: Vec2) -> Vec2 {
+        if self.p2.dis_pow2(p) < self.p1.dis_pow2(p) { self.p1 } else { self.p2 }
+    }
 }
 
 // ====================== 格式化显示 ======================
@@ -120,4 +149,42 @@ impl Neg for DPoint {
     fn neg(self) -> Self::Output {
         DPoint { p1: -self.p1, p2: -self.p2 }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_harmonic_range() {
+        let dp = DPoint::new(Vec2::new(1.0, 2.0), Vec2::new(4.0, -2.0));
+        for t in [0.3, 2.0, -5.0] {
+            let h = dp.harmonic(t);
+            assert!((dp.harmonic_range(h) + 1.0).abs() < 1e-12);
+            assert!((dp.harmonic_range(h.swap()) + 1.0).abs() < 1e-12);
+        }
+
+        // 直线坐标 A=0, B=1, C=2, D=3: (2/1) / (3/2) = 4/3
+        let l = dp.line();
+        let cd = DPoint::new(l.index_point(2.0), l.index_point(3.0));
+        let ab = DPoint::new(l.index_point(0.0), l.index_point(1.0));
+        assert!((ab.harmonic_range(cd) - 4.0 / 3.0).abs() < 1e-12);
+
+        // D 趋于无穷远：交比趋于单比 AC / BC = 2
+        let far = DPoint::new(l.index_point(2.0), l.index_point(1e9));
+        assert!((ab.harmonic_range(far) - 2.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_map_closest_farthest() {
+        let dp = DPoint::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0));
+        assert_eq!(dp.map(|p| p * 2.0 + Vec2::J), DPoint::new(Vec2::new(0.0, 1.0), Vec2::new(8.0, 1.0)));
+
+        let p = Vec2::new(3.0, 1.0);
+        assert_eq!(dp.closest_to(p), dp.p2);
+        assert_eq!(dp.farthest_from(p), dp.p1);
+        // 等距时两者取不同的点
+        let m = Vec2::new(2.0, 5.0);
+        assert_ne!(dp.closest_to(m), dp.farthest_from(m));
+    }
+}
@@ -135,6 +135,12 @@ impl QPoint {
         )
     }
 
+    /// 交比 (p1, p3; p2, p4)，即两组对角点 dp1、dp2 的交比
+    /// 与 from_2dp / harmonic 的点序约定一致：QPoint::harmonic(dp, t) 的交比为 -1
+    pub fn cross_ratio(self) -> f64 {
+        self.dp1().harmonic_range(self.dp2())
+    }
+
     // 调和四点组计算
     // 在直线 dp 上，根据调和比 t 生成四个点
     pub fn harmonic(dp: DPoint, t: f64) -> Self {
@@ -149,4 +155,25 @@ impl std::fmt::Display for QPoint {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "QPoint(p1: {}, p2: {}, p3: {}, p4: {})", self.p1, self.p2, self.p3, self.p4)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cross_ratio() {
+        let dp = DPoint::new(Vec2::new(-1.0, 0.5), Vec2::new(2.0, 3.0));
+        for t in [0.5, 3.0, -2.0] {
+            assert!((QPoint::harmonic(dp, t).cross_ratio() + 1.0).abs() < 1e-12);
+        }
+
+        // 射影不变性：共线四点经射影变换 x -> (2x + 1) / (x + 3) 后交比不变
+        let l = dp.line();
+        let xs = [0.0, 0.7, 2.0, -1.5];
+        let f = |x: f64| (2.0 * x + 1.0) / (x + 3.0);
+        let q = QPoint::new(l.index_point(xs[0]), l.index_point(xs[1]), l.index_point(xs[2]), l.index_point(xs[3]));
+        let q2 = QPoint::new(l.index_point(f(xs[0])), l.index_point(f(xs[1])), l.index_point(f(xs[2])), l.index_point(f(xs[3])));
+        assert!((q.cross_ratio() - q2.cross_ratio()).abs() < 1e-12);
+    }
+}
@@ -14,11 +14,13 @@ use crate::math_forest::geometry::d2::conic::parabola::Parabola;
 use crate::math_forest::geometry::d2::conic::hyperbola::Hyperbola;
 use crate::math_forest::geometry::d2::conic::x_line::XLine;
 use crate::math_forest::geometry::d2::conic::h_line::HLine;
-use crate::math_forest::geometry::d2::conic::wipkyy::Wipkyy;
+use crate::math_forest::geometry::d2::conic::wipkyy::{Wipkyy, WipkyyKind};
+use crate::math_forest::geometry::d2::conic::conic::Conic;
 
 // 结果容器
 use crate::math_forest::algebra::fertile::d_num::DNum;
 use crate::math_forest::geometry::d2::fertile::d_point::DPoint;
+use crate::math_forest::geometry::d2::fertile::q_point::QPoint;
 
 // 代数求解器 (Solver)
 // 假设这些求解器 API 是稳定的，且能处理数值误差
@@ -69,14 +71,29 @@ pub fn x_circle_line(c: &Circle, l: &Line) -> DPoint {
     c.index_d_point(theta)
 }
 
+/// 圆与圆求交 (根轴法)
+/// 根轴: |X - C1|^2 - r1^2 = |X - C2|^2 - r2^2，是垂直于连心线的直线
+/// 两圆交点 = 圆 1 与根轴的交点；同心圆无根轴，返回 NaN
+pub fn x_circle_circle(c1: &Circle, c2: &Circle) -> DPoint {
+    let d = c2.p - c1.p;
+    let d2 = d.pow2();
+    if d2 < Vec2::EPSILON {
+        return DPoint::new(Vec2::NAN, Vec2::NAN);
+    }
+    // 根轴与连心线的交点: C1 + t * d
+    let t = (d2 + c1.r * c1.r - c2.r * c2.r) / (2.0 * d2);
+    let radical = Line::new(c1.p + d * t, d.roll90());
+    x_circle_line(c1, &radical)
+}
+
 /// 直线与椭圆求交 (优化版：叉积法)
 /// 方程: (U x V_l) cos + (V x V_l) sin + (C - P_l) x V_l = 0
-/// 整理: (U x V_l) cos + (V x V_l) sin = (P_l - C) x V_l
+/// 求解器解的是 u cos + v sin + c = 0，常数项直接传 (C - P_l) x V_l (不移项)
 pub fn x_ellipse_line(c: &Ellipse, l: &Line) -> DPoint {
     // 这种写法无需判断直线是否垂直，数值极度稳定
     let a = c.u.cross(l.v);         // cos 系数
     let b = c.v.cross(l.v);         // sin 系数
-    let k = (l.p - c.p).cross(l.v); // 常数项 (移项后)
+    let k = (c.p - l.p).cross(l.v); // 常数项
 
     let thetas = trigonometric::solve_cos_sin_for_main_root(a, b, k);
     c.index_d_point(thetas) // 注意：这里检查一下是否需要 &thetas，如果 DNum 是 Copy 直接传值
@@ -86,8 +103,8 @@ pub fn x_ellipse_line(c: &Ellipse, l: &Line) -> DPoint {
 
 /// 直线与抛物线求交
 pub fn x_parabola_line(c: &Parabola, l: &Line) -> DPoint {
-    // At^2 + Bt + K = 0
-    let a = c.v.cross(l.v);         // t^2 系数
+    // P(t) = P + tU + (t^2/4)V 代入 (P(t) - P_l) x V_l = 0 得 At^2 + Bt + K = 0
+    let a = c.v.cross(l.v) * 0.25;  // t^2 系数
     let b = c.u().cross(l.v);       // t 系数 (注意 Parabola::u() 是方法)
     let k = (c.p - l.p).cross(l.v); // 常数项
 
@@ -113,6 +130,22 @@ pub fn x_hyperbola_line(c: &Hyperbola, l: &Line) -> DPoint {
 }
 
 
+/// 直线与一般圆锥曲线 (六系数形式) 求交
+/// P(t) = P_l + t V_l 代入 Ax^2 + Bxy + Cy^2 + Dx + Ey + F = 0 得关于 t 的二次方程
+/// 系数先归一化、方向取单位向量，使求解器的退化阈值与缩放无关；无实交点时为 NaN
+pub fn x_conic_line(c: &Conic, l: &Line) -> DPoint {
+    let c = c.normalized();
+    let l = Line::new(l.p, l.v.unit());
+    let (p, v) = (l.p, l.v);
+
+    let a = c.a * v.x * v.x + c.b * v.x * v.y + c.c * v.y * v.y;
+    let b = 2.0 * c.a * p.x * v.x + c.b * (p.x * v.y + p.y * v.x) + 2.0 * c.c * p.y * v.y + c.d * v.x + c.e * v.y;
+    let k = c.eval(p);
+
+    let t_dnum = polynomial::solve_real_quadratic_for_real(a, b, k);
+    l.index_d_point(t_dnum)
+}
+
 /// 直线与叉线 (XLine) 求交
 /// 结果为两个点（分别与两条渐近线/直线的交点）
 pub fn x_x_line_line(c: &XLine, l: &Line) -> DPoint {
@@ -122,6 +155,17 @@ pub fn x_x_line_line(c: &XLine, l: &Line) -> DPoint {
     )
 }
 
+/// 叉线与一般圆锥曲线求交：两条直线各与曲线交于至多两点
+/// 圆/椭圆走叉积法 (x_ellipse_line)，其余类型 (双曲线暂无标准型转换) 走六系数通用解
+/// 结果按 QPoint::from_2dp 排列：dp1() 为第一条线的交点，dp2() 为第二条线的交点
+pub fn x_x_line_conic(xl: &XLine, c: &Conic) -> QPoint {
+    let meet = |l: &Line| match c.to_ellipse() {
+        Some(e) => x_ellipse_line(&e, l),
+        None => x_conic_line(c, l),
+    };
+    QPoint::from_2dp(meet(&xl.l1()), meet(&xl.l2()))
+}
+
 /// 直线与平行线 (HLine) 求交
 pub fn x_h_line_line(c: &HLine, l: &Line) -> DPoint {
     DPoint::new(
@@ -131,7 +175,11 @@ pub fn x_h_line_line(c: &HLine, l: &Line) -> DPoint {
 }
 
 /// 直线与虚空 (Wipkyy) 求交
-/// 也许这是"WIP" (Work In Progress) 曲线？暂时保留你的逻辑
-pub fn x_wipkyy_line(_c: &Wipkyy, _l: &Line) -> DPoint {
-    DPoint::INF
+/// 空集：没有交点，返回 DPoint::NAN
+/// 全平面：整条直线都是交点 (无穷多个)，约定返回 DPoint::INF
+pub fn x_wipkyy_line(c: &Wipkyy, _l: &Line) -> DPoint {
+    match c.kind {
+        WipkyyKind::Empty => DPoint::NAN,
+        WipkyyKind::Everything => DPoint::INF,
+    }
 }
@@ -1 +1,2 @@
 pub mod line520;
+pub mod numeric;
@@ -0,0 +1,137 @@
+// src/math_forest/geometry/d2/intersection/numeric.rs
+#![allow(dead_code)]
+
+// 一般参数曲线与直线的数值求交
+// line520 只覆盖有解析解的圆锥曲线；超椭圆、样条等曲线走这里
+
+use crate::math_forest::algebra::solver::brent::find_root_bracketed;
+use crate::math_forest::geometry::d2::curve::Curve2D;
+use crate::math_forest::geometry::d2::linear::line::Line;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+
+// 粗扫描段数；疑似相切的段再细分
+const SCAN_STEPS: usize = 256;
+const REFINE_STEPS: usize = 16;
+// Brent 精度 (参数空间)
+const ROOT_TOL: f64 = 1e-12;
+// |g| 小于此值的极值点视为切点 (g 为到直线的有向距离)
+const TANGENT_EPS: f64 = 1e-9;
+// 距离小于此值的交点视为同一点
+const DEDUP_EPS: f64 = 1e-8;
+
+/// 参数曲线与直线求交，t 在 t_range 内，至多返回 max_roots 个点 (按 t 升序)
+/// g(t) = (P(t) - l.p) x l̂.v 为有向距离，扫描变号区间后用 Brent 法细化
+/// 切点处 g 不变号：在 g' 变号且 g 接近 0 的段细分，取 g' 的根作为切点
+pub fn x_curve_line_numeric(
+    curve: &impl Curve2D,
+    l: &Line,
+    t_range: (f64, f64),
+    max_roots: usize,
+) -> Vec<Vec2> {
+    let (t0, t1) = t_range;
+    let len = l.v.len();
+    if max_roots == 0 || t1 <= t0 || !t0.is_finite() || !t1.is_finite() || len < Vec2::EPSILON {
+        return Vec::new();
+    }
+    let dir = l.v / len;
+    let g = |t: f64| (curve.point_at(t) - l.p).cross(dir);
+    let dg = |t: f64| curve.derivative_at(t).cross(dir);
+
+    let mut ts = Vec::new();
+    let h = (t1 - t0) / SCAN_STEPS as f64;
+    for i in 0..SCAN_STEPS {
+        let a = t0 + i as f64 * h;
+        let b = if i + 1 == SCAN_STEPS { t1 } else { a + h };
+        let (ga, gb) = (g(a), g(b));
+        let (da, db) = (dg(a), dg(b));
+
+        // 疑似相切：g' 在段内变号 (g 有极值)，且 |g| 相对 |g'| * 步长很小
+        let near_tangent = da * db <= 0.0 && ga.abs().min(gb.abs()) <= da.abs().max(db.abs()) * (b - a);
+        if !near_tangent {
+            if ga * gb <= 0.0 {
+                ts.extend(find_root_bracketed(g, a, b, ROOT_TOL));
+            }
+            continue;
+        }
+
+        let hs = (b - a) / REFINE_STEPS as f64;
+        for k in 0..REFINE_STEPS {
+            let sa = a + k as f64 * hs;
+            let sb = if k + 1 == REFINE_STEPS { b } else { sa + hs };
+            if g(sa) * g(sb) <= 0.0 {
+                ts.extend(find_root_bracketed(g, sa, sb, ROOT_TOL));
+            } else if dg(sa) * dg(sb) <= 0.0 {
+                let touch = find_root_bracketed(dg, sa, sb, ROOT_TOL);
+                ts.extend(touch.filter(|&te| g(te).abs() <= TANGENT_EPS));
+            }
+        }
+    }
+    ts.sort_by(f64::total_cmp);
+
+    // 去重：位置重合 (含闭曲线首尾)，或相邻两根之间 g 始终在切点容差内 (数值上的切点被拆成两根)
+    let mut kept_t: Vec<f64> = Vec::new();
+    let mut points: Vec<Vec2> = Vec::new();
+    for t in ts {
+        let p = curve.point_at(t);
+        if points.iter().any(|q| q.dis(p) < DEDUP_EPS) {
+            continue;
+        }
+        if kept_t.last().is_some_and(|&prev| g(0.5 * (prev + t)).abs() <= TANGENT_EPS) {
+            continue;
+        }
+        kept_t.push(t);
+        points.push(p);
+        if points.len() == max_roots {
+            break;
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{PI, TAU};
+    use crate::math_forest::geometry::d2::conic::circle::Circle;
+    use crate::math_forest::geometry::d2::intersection::line520::x_circle_line;
+    use crate::math_forest::geometry::d2::special::hyperelliptic::Hyperelliptic;
+
+    #[test]
+    fn test_circle_matches_analytic() {
+        let c = Circle::new(Vec2::new(1.0, 2.0), 3.0);
+        let l = Line::new(Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.5));
+        let exact = x_circle_line(&c, &l);
+        let pts = x_curve_line_numeric(&c, &l, (0.0, TAU), 4);
+        assert_eq!(pts.len(), 2);
+        for e in [exact.p1, exact.p2] {
+            assert!(pts.iter().any(|p| p.dis(e) < 1e-9), "{:?} not in {:?}", e, pts);
+        }
+        // max_roots 截断
+        assert_eq!(x_curve_line_numeric(&c, &l, (0.0, TAU), 1).len(), 1);
+    }
+
+    #[test]
+    fn test_hyperelliptic_tangent_single_point() {
+        // m = 0.5：|x|^4 + |y|^4 = 1，取 t = π/4 处的切线
+        let h = Hyperelliptic { a: 1.0, b: 1.0, m: 0.5 };
+        let t = PI / 4.0;
+        let touch = h.point_at(t);
+        let l = Line::new(touch, h.derivative_at(t));
+        let pts = x_curve_line_numeric(&h, &l, (0.0, TAU), 8);
+        assert_eq!(pts.len(), 1, "{:?}", pts);
+        assert!(pts[0].dis(touch) < 1e-6);
+
+        // 沿法向稍微内移：切线变割线，得两个交点
+        let l = Line::new(touch * 0.99, h.derivative_at(t));
+        assert_eq!(x_curve_line_numeric(&h, &l, (0.0, TAU), 8).len(), 2);
+    }
+
+    #[test]
+    fn test_miss_is_empty() {
+        let h = Hyperelliptic { a: 1.0, b: 1.0, m: 0.5 };
+        let l = Line::new(Vec2::new(0.0, 2.0), Vec2::new(1.0, 0.0));
+        assert!(x_curve_line_numeric(&h, &l, (0.0, TAU), 8).is_empty());
+        let c = Circle::new(Vec2::new(0.0, 0.0), 1.0);
+        assert!(x_curve_line_numeric(&c, &l, (0.0, TAU), 8).is_empty());
+    }
+}
@@ -3,6 +3,7 @@ use crate::math_forest::algebra::fertile::d_num::DNum;
 use crate::math_forest::algebra::fertile::q_num::QNum;
 use crate::math_forest::geometry::d2::fertile::d_point::DPoint;
 use crate::math_forest::geometry::d2::fertile::q_point::QPoint;
+use crate::math_forest::geometry::d2::intersection::line520;
 use crate::math_forest::geometry::d2::linear::vec2::Vec2;
 use std::fmt;
 
@@ -13,6 +14,12 @@ pub struct Line {
 }
 
 impl Line {
+    /// 无效直线 (光线未击中曲线等情况)
+    pub const NAN: Line = Line { p: Vec2::NAN, v: Vec2::NAN };
+
+    /// 无穷远直线 (如圆锥曲线中心的极线)：基点在无穷远，方向为零
+    pub const AT_INFINITY: Line = Line { p: Vec2::INF, v: Vec2::ZERO };
+
     /// 默认构造：P + λV
     #[inline(always)]
     pub fn new(p: Vec2, v: Vec2) -> Self {
@@ -28,6 +35,12 @@ impl Line {
         }
     }
 
+    /// 是否为无穷远直线 (Line::AT_INFINITY)
+    #[inline]
+    pub fn is_at_infinity(&self) -> bool {
+        self.p.x.is_infinite() || self.p.y.is_infinite()
+    }
+
     // ================= 核心几何方法 =================
 
     /// 索引点：获取 P(t) = p + v * t
@@ -115,6 +128,50 @@ impl Line {
         self.get_t(p_target)
     }
 
+    /// 判定同一直线：方向平行 (不计正负)，且本直线基点落在 other 上
+    pub fn is_same(&self, other: Line) -> bool {
+        self.v.unit().cross(other.v.unit()).abs() < Vec2::EPSILON
+            && other.dis_p(self.p) < Vec2::EPSILON
+    }
+
+    // ================= 直线束 =================
+
+    /// 两相交直线的两条角平分线，均以交点为基点
+    /// 方向分别为 u1 + u2 与 u1 - u2 (u1、u2 为两直线的单位方向)，两者互相垂直
+    /// 两直线平行时，两条结果都退化为居中的平行线
+    pub fn angle_bisectors(&self, l2: &Line) -> (Line, Line) {
+        let u1 = self.v.unit();
+        let u2 = l2.v.unit();
+        if u1.cross(u2).abs() < Vec2::EPSILON {
+            let mid = (self.p + l2.project_p(self.p)) * 0.5;
+            let l = Line::new(mid, self.v);
+            return (l, l);
+        }
+        let p = line520::x_line_line(self, l2);
+        (Line::new(p, u1 + u2), Line::new(p, u1 - u2))
+    }
+
+    // ================= 射线 =================
+    // 把直线看成从 p 出发、沿 +v 方向的射线
+
+    /// 候选交点中沿射线方向最先到达的一个 (t > 0)，NaN 点忽略
+    pub fn first_hit(&self, hits: DPoint) -> Option<Vec2> {
+        [hits.p1, hits.p2]
+            .into_iter()
+            .filter(|q| q.x.is_finite() && q.y.is_finite())
+            .map(|q| (self.get_t(q), q))
+            .filter(|(t, _)| *t > 1e-9)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, q)| q)
+    }
+
+    /// 反射定律：在 hit 点按切向 tangent 反射，返回以 hit 为起点的反射光线 (方向为单位向量)
+    pub fn reflect_at(&self, hit: Vec2, tangent: Vec2) -> Line {
+        let d = self.v.unit();
+        let n = tangent.roll90().unit();
+        Line::new(hit, d - n * (2.0 * d.dot(n)))
+    }
+
     pub fn get_type(&self) -> &str { "Line" }
 }
 
@@ -129,4 +186,44 @@ impl fmt::Display for Line {
 // 移除了 Hash 实现。
 // 原因：f64 实现 Hash 极其危险（NaN != NaN，-0.0 != 0.0），
 // 在几何库中如果将直线作为 HashMap 的 Key，极易导致难以排查的 bug。
-// 如果确实需要，建议使用 id 或封装 ordered_float。
\ No newline at end of file
+// 如果确实需要，建议使用 id 或封装 ordered_float。
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_angle_bisectors() {
+        let l1 = Line::new(Vec2::new(1.0, 1.0), Vec2::new(2.0, 0.0));
+        let l2 = Line::new(Vec2::new(4.0, 5.0), Vec2::new(-1.0, -1.0));
+        let (b1, b2) = l1.angle_bisectors(&l2);
+
+        // 交点 (0, 1)，两平分线互相垂直
+        assert!(b1.p.dis(Vec2::new(0.0, 1.0)) < 1e-12);
+        assert!(b1.is_vertical(&b2));
+        // 平分线上任一点到两直线等距
+        for b in [b1, b2] {
+            for t in [-2.0, 0.5, 3.0] {
+                let q = b.index_point(t);
+                assert!((l1.dis_p(q) - l2.dis_p(q)).abs() < 1e-12);
+            }
+        }
+
+        // 平行：居中的平行线
+        let l3 = Line::new(Vec2::new(0.0, 3.0), Vec2::new(-5.0, 0.0));
+        let (m1, m2) = l1.angle_bisectors(&l3);
+        assert_eq!(m1, m2);
+        assert!(m1.is_same(Line::new(Vec2::new(7.0, 2.0), Vec2::I)));
+    }
+
+    #[test]
+    fn test_is_same() {
+        let l = Line::new(Vec2::new(1.0, 2.0), Vec2::new(1.0, 1.0));
+        assert!(l.is_same(Line::new(Vec2::new(-3.0, -2.0), Vec2::new(-4.0, -4.0))));
+        assert!(l.is_same(l));
+        // 平行不重合
+        assert!(!l.is_same(Line::new(Vec2::new(1.0, 3.0), Vec2::new(1.0, 1.0))));
+        // 过同一点但方向不同
+        assert!(!l.is_same(Line::new(Vec2::new(1.0, 2.0), Vec2::new(1.0, 0.0))));
+    }
+}
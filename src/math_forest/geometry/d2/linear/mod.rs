@@ -1,2 +1,3 @@
 pub mod vec2;
 pub(crate) mod line;
+pub mod triangle;
@@ -0,0 +1,260 @@
+// src/math_forest/geometry/d2/linear/triangle.rs
+// 平面三角形与经典心
+//   退化 (三点共线) 时各种心返回 Vec2::NAN、圆半径为 NaN，不 panic
+#![allow(dead_code)]
+
+use crate::math_forest::algebra::solver::linear::solve_linear_2x2;
+use crate::math_forest::algebra::solver::predicates::orient2d;
+use crate::math_forest::geometry::d2::conic::circle::Circle;
+
+use super::vec2::Vec2;
+
+// 退化判定：|叉积| 相对最长边平方的阈值
+const DEGENERATE_EPS: f64 = 1e-12;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Triangle {
+    pub a: Vec2,
+    pub b: Vec2,
+    pub c: Vec2,
+}
+
+impl Triangle {
+    pub fn new(a: Vec2, b: Vec2, c: Vec2) -> Self {
+        Triangle { a, b, c }
+    }
+
+    // ====================== 基础量 ======================
+
+    /// 有向面积 (逆时针为正)
+    pub fn signed_area(&self) -> f64 {
+        (self.b - self.a).cross(self.c - self.a) * 0.5
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// 三边长 (a 对边 BC, b 对边 CA, c 对边 AB)
+    pub fn sides(&self) -> (f64, f64, f64) {
+        (self.b.dis(self.c), self.c.dis(self.a), self.a.dis(self.b))
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        let (la, lb, lc) = self.sides();
+        la + lb + lc
+    }
+
+    /// 三个内角 (弧度)，依次为 A, B, C
+    pub fn angles(&self) -> (f64, f64, f64) {
+        let angle = |o: Vec2, p: Vec2, q: Vec2| {
+            let (u, v) = (p - o, q - o);
+            u.cross(v).abs().atan2(u.dot(v))
+        };
+        (angle(self.a, self.b, self.c), angle(self.b, self.c, self.a), angle(self.c, self.a, self.b))
+    }
+
+    /// 三点是否共线 (含重合)
+    pub fn is_degenerate(&self) -> bool {
+        let (la, lb, lc) = self.sides();
+        let scale = la.max(lb).max(lc);
+        (self.b - self.a).cross(self.c - self.a).abs() <= DEGENERATE_EPS * scale * scale
+    }
+
+    // ====================== 经典心 ======================
+
+    /// 重心 (退化时仍有定义)
+    pub fn centroid(&self) -> Vec2 {
+        (self.a + self.b + self.c) / 3.0
+    }
+
+    /// 内心：以对边长为权的加权平均
+    pub fn incenter(&self) -> Vec2 {
+        if self.is_degenerate() {
+            return Vec2::NAN;
+        }
+        let (la, lb, lc) = self.sides();
+        (self.a * la + self.b * lb + self.c * lc) / (la + lb + lc)
+    }
+
+    /// 外心：两条中垂线的交点
+    /// 以 a 为原点：2(b-a)·X = |b-a|²，2(c-a)·X = |c-a|²
+    pub fn circumcenter(&self) -> Vec2 {
+        if self.is_degenerate() {
+            return Vec2::NAN;
+        }
+        let (u, v) = (self.b - self.a, self.c - self.a);
+        let (x, y) = solve_linear_2x2(2.0 * u.x, 2.0 * u.y, u.pow2(), 2.0 * v.x, 2.0 * v.y, v.pow2());
+        self.a + Vec2::new(x, y)
+    }
+
+    /// 垂心：两条高线的交点
+    /// 以 a 为原点：(X)·(c-b) = 0，(X-(b-a))·(c-a) = 0
+    pub fn orthocenter(&self) -> Vec2 {
+        if self.is_degenerate() {
+            return Vec2::NAN;
+        }
+        let (bc, u, v) = (self.c - self.b, self.b - self.a, self.c - self.a);
+        let (x, y) = solve_linear_2x2(bc.x, bc.y, 0.0, v.x, v.y, u.dot(v));
+        self.a + Vec2::new(x, y)
+    }
+
+    // ====================== 相关圆 ======================
+
+    pub fn circumcircle(&self) -> Circle {
+        let o = self.circumcenter();
+        Circle::new(o, o.dis(self.a))
+    }
+
+    /// 内切圆：r = 面积 / 半周长
+    pub fn incircle(&self) -> Circle {
+        let i = self.incenter();
+        let r = if i.x.is_nan() { f64::NAN } else { 2.0 * self.area() / self.perimeter() };
+        Circle::new(i, r)
+    }
+
+    /// 九点圆：圆心为外心与垂心的中点，半径为外接圆半径的一半
+    pub fn nine_point_circle(&self) -> Circle {
+        let circum = self.circumcircle();
+        let h = self.orthocenter();
+        Circle::new((circum.p + h) * 0.5, circum.r * 0.5)
+    }
+
+    // ====================== 点与三角形 ======================
+
+    /// 点是否在三角形内 (含边界)；用精确的方向谓词，边上的点不受舍入影响
+    /// 退化 (三点精确共线) 时返回 false
+    pub fn contains(&self, p: Vec2) -> bool {
+        let orientation = orient2d(self.a, self.b, self.c);
+        if orientation == 0.0 {
+            return false;
+        }
+        // 三条边上 p 的方向都与三角形自身的方向一致 (或为 0)
+        [(self.a, self.b), (self.b, self.c), (self.c, self.a)]
+            .iter()
+            .all(|&(u, v)| orient2d(u, v, p) * orientation.signum() >= 0.0)
+    }
+
+    /// 三角形 (含内部) 上离 p 最近的点
+    pub fn closest_point(&self, p: Vec2) -> Vec2 {
+        if self.contains(p) {
+            return p;
+        }
+        [(self.a, self.b), (self.b, self.c), (self.c, self.a)]
+            .into_iter()
+            .map(|(s, e)| closest_on_segment(p, s, e))
+            .min_by(|q1, q2| p.dis_pow2(*q1).total_cmp(&p.dis_pow2(*q2)))
+            .unwrap()
+    }
+
+    /// 笛卡尔坐标 -> 重心坐标 (u, v, w)，退化时全为 NaN
+    pub fn barycentric_of(&self, p: Vec2) -> (f64, f64, f64) {
+        if self.is_degenerate() {
+            return (f64::NAN, f64::NAN, f64::NAN);
+        }
+        p.barycentric_coordinates(self.a, self.b, self.c).unwrap_or((f64::NAN, f64::NAN, f64::NAN))
+    }
+
+    /// 重心坐标 -> 笛卡尔坐标 (不要求 u + v + w = 1 时先归一化)
+    pub fn point_from_barycentric(&self, u: f64, v: f64, w: f64) -> Vec2 {
+        (self.a * u + self.b * v + self.c * w) / (u + v + w)
+    }
+}
+
+// 线段 se 上离 p 最近的点
+fn closest_on_segment(p: Vec2, s: Vec2, e: Vec2) -> Vec2 {
+    let d = e - s;
+    let len2 = d.pow2();
+    if len2 == 0.0 {
+        return s;
+    }
+    let t = ((p - s).dot(d) / len2).clamp(0.0, 1.0);
+    s + d * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_triangles(n: usize) -> Vec<Triangle> {
+        let mut rng = StdRng::seed_from_u64(1346);
+        let mut p = || Vec2::new(rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0));
+        (0..n)
+            .map(|_| Triangle::new(p(), p(), p()))
+            .filter(|t| t.area() > 1e-2)
+            .collect()
+    }
+
+    #[test]
+    fn test_right_triangle() {
+        let t = Triangle::new(Vec2::ZERO, Vec2::new(4.0, 0.0), Vec2::new(0.0, 3.0));
+        assert_eq!(t.signed_area(), 6.0);
+        assert_eq!(t.perimeter(), 12.0);
+        assert!((t.angles().0 - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!(t.circumcenter().dis(Vec2::new(2.0, 1.5)) < 1e-12);
+        assert!(t.orthocenter().dis(Vec2::ZERO) < 1e-12);
+        let inc = t.incircle();
+        assert!(inc.p.dis(Vec2::new(1.0, 1.0)) < 1e-12 && (inc.r - 1.0).abs() < 1e-12);
+
+        assert!(t.contains(Vec2::new(1.0, 1.0)));
+        assert!(!t.contains(Vec2::new(3.0, 3.0)));
+        assert_eq!(t.closest_point(Vec2::new(-1.0, 1.0)), Vec2::new(0.0, 1.0));
+        assert_eq!(t.closest_point(Vec2::new(5.0, -1.0)), Vec2::new(4.0, 0.0));
+    }
+
+    #[test]
+    fn test_degenerate_is_nan() {
+        let t = Triangle::new(Vec2::ZERO, Vec2::new(1.0, 1.0), Vec2::new(3.0, 3.0));
+        assert!(t.is_degenerate());
+        assert!(t.incenter().x.is_nan());
+        assert!(t.circumcenter().x.is_nan());
+        assert!(t.orthocenter().x.is_nan());
+        assert!(t.nine_point_circle().r.is_nan());
+        assert!(t.incircle().r.is_nan());
+        assert!(t.barycentric_of(Vec2::new(1.0, 0.0)).0.is_nan());
+        assert_eq!(t.centroid(), Vec2::new(4.0 / 3.0, 4.0 / 3.0));
+        assert!(!t.contains(Vec2::new(1.0, 1.0)));
+    }
+
+    #[test]
+    fn test_contains_exact_boundary() {
+        // 斜边 y = 12 - x 上的点精确判定，相差一个 ulp 即在外侧；顶点顺序不影响结果
+        let t = Triangle::new(Vec2::new(0.5, 0.5), Vec2::new(11.5, 0.5), Vec2::new(0.5, 11.5));
+        let on_edge = Vec2::new(5.25, 6.75);
+        assert!(t.contains(on_edge));
+        assert!(!t.contains(Vec2::new(5.25, 6.75f64.next_up())));
+        assert!(t.contains(Vec2::new(5.25, 6.75f64.next_down())));
+        assert!(Triangle::new(t.c, t.b, t.a).contains(on_edge));
+        assert!(t.contains(t.b));
+    }
+
+    #[test]
+    fn test_euler_line_and_nine_point_circle() {
+        for t in random_triangles(200) {
+            let (o, g, h) = (t.circumcenter(), t.centroid(), t.orthocenter());
+            let scale = t.perimeter();
+            // 欧拉线：OH = 3 OG
+            assert!((h - o - (g - o) * 3.0).len() < 1e-8 * scale, "{:?}", t);
+
+            // 九点圆过三边中点
+            let npc = t.nine_point_circle();
+            for m in [(t.a + t.b) * 0.5, (t.b + t.c) * 0.5, (t.c + t.a) * 0.5] {
+                assert!((npc.p.dis(m) - npc.r).abs() < 1e-8 * scale, "{:?}", t);
+            }
+        }
+    }
+
+    #[test]
+    fn test_barycentric_round_trip() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for t in random_triangles(100) {
+            let p = Vec2::new(rng.gen_range(-20.0..20.0), rng.gen_range(-20.0..20.0));
+            let (u, v, w) = t.barycentric_of(p);
+            assert!((u + v + w - 1.0).abs() < 1e-9);
+            assert!(t.point_from_barycentric(u, v, w).dis(p) < 1e-8, "{:?} {}", t, p);
+            assert_eq!(t.contains(p), u >= -1e-10 && v >= -1e-10 && w >= -1e-10);
+        }
+    }
+}
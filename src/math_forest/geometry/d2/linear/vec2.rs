@@ -28,6 +28,12 @@ impl Vec2 {
         Vec2 { x: theta.cos() * l, y: theta.sin() * l }
     }
 
+    // 转为 f32 数组 (上传 GPU 顶点数据)
+    #[inline]
+    pub fn to_f32_array(self) -> [f32; 2] {
+        [self.x as f32, self.y as f32]
+    }
+
     // --- 核心几何计算 (全部使用传值，性能最优) ---
 
     #[inline]
@@ -125,6 +131,30 @@ impl Vec2 {
         let den = self.len() * other.len();
         if den < Self::EPSILON { 0.0 } else { self.dot(other) / den }
     }
+
+    // 夹角 (弧度，[0, π])；先夹紧余弦，避免舍入误差略超出 [-1, 1] 时 acos 得 NaN
+    #[inline]
+    pub fn angle_between(self, other: Vec2) -> f64 {
+        self.cos(other).clamp(-1.0, 1.0).acos()
+    }
+
+    // 重心坐标 (u, v, w)：self = u*a + v*b + w*c，且 u + v + w = 1
+    // 三角形退化 (面积接近 0) 时返回 None
+    pub fn barycentric_coordinates(self, a: Vec2, b: Vec2, c: Vec2) -> Option<(f64, f64, f64)> {
+        let (v, w) = (self - a).rsv(b - a, c - a);
+        if v.is_nan() || w.is_nan() {
+            return None;
+        }
+        Some((1.0 - v - w, v, w))
+    }
+
+    // 点是否在三角形 abc 内 (含边界)
+    pub fn is_inside_triangle(self, a: Vec2, b: Vec2, c: Vec2) -> bool {
+        match self.barycentric_coordinates(a, b, c) {
+            Some((u, v, w)) => u > -Self::EPSILON && v > -Self::EPSILON && w > -Self::EPSILON,
+            None => false,
+        }
+    }
 }
 
 // ====================== 运算符重载 (宏魔法) ======================
@@ -215,4 +245,37 @@ impl fmt::Display for Vec2 {
         let y = if self.y.abs() < 1e-10 { 0.0 } else { self.y };
         write!(f, "({:.4}, {:.4})", x, y)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_barycentric_coordinates() {
+        let a = Vec2::new(0.0, 0.0);
+        let b = Vec2::new(4.0, 1.0);
+        let c = Vec2::new(1.0, 3.0);
+
+        let p = a * 0.5 + b * 0.25 + c * 0.25;
+        let (u, v, w) = p.barycentric_coordinates(a, b, c).unwrap();
+        assert!((u - 0.5).abs() < 1e-12 && (v - 0.25).abs() < 1e-12 && (w - 0.25).abs() < 1e-12);
+        assert!(p.is_inside_triangle(a, b, c));
+        assert!(b.is_inside_triangle(a, b, c));
+        assert!(!Vec2::new(3.0, 3.0).is_inside_triangle(a, b, c));
+
+        // 退化
+        assert!(p.barycentric_coordinates(a, b, b * 2.0).is_none());
+    }
+
+    #[test]
+    fn test_angle_between() {
+        let x = Vec2::new(2.0, 0.0);
+        assert!((x.angle_between(Vec2::new(0.0, 3.0)) - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!((x.angle_between(Vec2::new(-1.0, -1.0)) - 0.75 * std::f64::consts::PI).abs() < 1e-12);
+        // 同向 / 反向时余弦可能略超出 [-1, 1]
+        let v = Vec2::new(0.1, 0.7);
+        assert!(v.angle_between(v * 3.0) < 1e-7);
+        assert!((v.angle_between(-v) - std::f64::consts::PI).abs() < 1e-7);
+    }
+}
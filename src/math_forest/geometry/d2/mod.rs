@@ -9,3 +9,12 @@ pub mod conic;
 
 pub mod intersection;
 pub mod special;
+
+// 自由曲线 (贝塞尔、B 样条)
+pub mod spline;
+
+// 参数曲线统一接口 (数值求交等)
+pub mod curve;
+
+// 闭合参数曲线的面积、质心、二阶矩与环绕数
+pub mod curve_integral;
@@ -0,0 +1,453 @@
+// src/math_forest/geometry/d2/spline/b_spline.rs
+#![allow(dead_code)]
+
+use std::fmt;
+
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+use super::bezier::dist_to_segment;
+
+// 每个节点区间内的粗采样数 (最近点、包围盒)
+const SAMPLES_PER_SPAN: usize = 16;
+const REFINE_MAX_ITER: usize = 50;
+// 自适应离散化的最大递归深度 (相对单个贝塞尔段)
+const FLATTEN_MAX_DEPTH: u32 = 16;
+
+/// 一般 B 样条曲线
+/// C(t) = Σ N_{i,p}(t) P_i，节点向量长度 = 控制点数 + 次数 + 1
+/// 定义域为 [knots[p], knots[n]]，n 为控制点数
+#[derive(Clone, Debug, PartialEq)]
+pub struct BSpline {
+    pub control: Vec<Vec2>,
+    pub degree: usize,
+    pub knots: Vec<f64>,
+}
+
+impl BSpline {
+    /// 构造函数，节点向量须非递减且长度为 control.len() + degree + 1
+    pub fn new(control: Vec<Vec2>, degree: usize, knots: Vec<f64>) -> Self {
+        assert!(control.len() > degree, "BSpline: 控制点数必须大于次数");
+        assert_eq!(knots.len(), control.len() + degree + 1, "BSpline: 节点向量长度应为 n + p + 1");
+        assert!(knots.windows(2).all(|w| w[0] <= w[1]), "BSpline: 节点向量必须非递减");
+        assert!(knots[degree] < knots[control.len()], "BSpline: 定义域为空");
+        Self { control, degree, knots }
+    }
+
+    /// 均匀夹持 (clamped) B 样条：首尾节点重复 p + 1 次，曲线过首末控制点，定义域 [0, 1]
+    /// 次数超过 控制点数 - 1 时自动降为 控制点数 - 1
+    pub fn new_clamped(control: Vec<Vec2>, degree: usize) -> Self {
+        let n = control.len();
+        assert!(n >= 2, "BSpline: 至少需要两个控制点");
+        let p = degree.min(n - 1);
+        let inner = n - p; // 内部区间数
+        let mut knots = vec![0.0; p + 1];
+        for i in 1..inner {
+            knots.push(i as f64 / inner as f64);
+        }
+        knots.extend(std::iter::repeat_n(1.0, p + 1));
+        Self::new(control, p, knots)
+    }
+
+    pub fn get_type(&self) -> &str { "BSpline" }
+
+    /// 参数定义域 [t_min, t_max]
+    pub fn domain(&self) -> (f64, f64) {
+        (self.knots[self.degree], self.knots[self.control.len()])
+    }
+
+    // 非空节点区间 [knots[k], knots[k+1]]
+    fn spans(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        (self.degree..self.control.len())
+            .map(|k| (self.knots[k], self.knots[k + 1]))
+            .filter(|(a, b)| a < b)
+    }
+
+    /// 找到 t 所在节点区间下标 k，满足 knots[k] <= t < knots[k+1]
+    /// t 在定义域右端点时返回最后一个非空区间
+    fn find_span(&self, t: f64) -> usize {
+        let n = self.control.len();
+        let p = self.degree;
+        for k in p..n {
+            if t < self.knots[k + 1] {
+                return k;
+            }
+        }
+        (p..n).rev().find(|&k| self.knots[k] < self.knots[k + 1]).unwrap_or(n - 1)
+    }
+
+    // ====================== 求值 ======================
+
+    /// 曲线上的点 (de Boor)，t 会被夹到定义域内
+    pub fn point_at(&self, t: f64) -> Vec2 {
+        let (t0, t1) = self.domain();
+        let t = t.clamp(t0, t1);
+        let p = self.degree;
+        let k = self.find_span(t);
+        let u = &self.knots;
+
+        let mut d: Vec<Vec2> = (0..=p).map(|j| self.control[j + k - p]).collect();
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let lo = u[j + k - p];
+                let hi = u[j + 1 + k - r];
+                let alpha = if hi > lo { (t - lo) / (hi - lo) } else { 0.0 };
+                d[j] = d[j - 1] * (1.0 - alpha) + d[j] * alpha;
+            }
+        }
+        d[p]
+    }
+
+    /// 导曲线：次数 p - 1 的 B 样条，控制点 Q_i = p (P_{i+1} - P_i) / (u_{i+p+1} - u_{i+1})
+    /// 零次样条的导数恒为零
+    pub fn derivative(&self) -> BSpline {
+        let p = self.degree;
+        let n = self.control.len();
+        if p == 0 {
+            return BSpline {
+                control: vec![Vec2::ZERO; n],
+                degree: 0,
+                knots: self.knots.clone(),
+            };
+        }
+        let control = (0..n - 1)
+            .map(|i| {
+                let du = self.knots[i + p + 1] - self.knots[i + 1];
+                if du > 0.0 { (self.control[i + 1] - self.control[i]) * (p as f64 / du) } else { Vec2::ZERO }
+            })
+            .collect();
+        BSpline {
+            control,
+            degree: p - 1,
+            knots: self.knots[1..self.knots.len() - 1].to_vec(),
+        }
+    }
+
+    /// 一阶导数 C'(t)
+    pub fn derivative_at(&self, t: f64) -> Vec2 {
+        self.derivative().point_at(t)
+    }
+
+    // ====================== 细分 ======================
+
+    /// 插入一个节点 (Boehm 算法)，曲线形状不变
+    pub fn insert_knot(&mut self, t: f64) {
+        let p = self.degree;
+        let k = self.find_span(t);
+        let u = &self.knots;
+
+        let mut q = Vec::with_capacity(self.control.len() + 1);
+        q.extend_from_slice(&self.control[..=k - p]);
+        for i in k - p + 1..=k {
+            let a = (t - u[i]) / (u[i + p] - u[i]);
+            q.push(self.control[i - 1] * (1.0 - a) + self.control[i] * a);
+        }
+        q.extend_from_slice(&self.control[k..]);
+
+        self.knots.insert(k + 1, t);
+        self.control = q;
+    }
+
+    /// 在定义域内部的 t 处一分为二
+    /// 通过插入节点使 t 的重数达到 p，两段各自把 t 夹持为端点
+    /// 两段保留原参数：左段定义域 [t_min, t]，右段 [t, t_max]
+    pub fn split(&self, t: f64) -> (BSpline, BSpline) {
+        let (t0, t1) = self.domain();
+        assert!(t > t0 && t < t1, "BSpline::split: t 必须在定义域内部");
+        let p = self.degree;
+
+        let mut s = self.clone();
+        let mult = s.knots.iter().filter(|&&u| u == t).count();
+        for _ in mult..p {
+            s.insert_knot(t);
+        }
+
+        // t 的首次出现位置 j：knots[j..j+p] == t，曲线在 t 处经过控制点 P_{j-1}
+        let j = s.knots.iter().position(|&u| u == t).unwrap();
+
+        let mut left_knots = s.knots[..j + p].to_vec();
+        left_knots.push(t);
+        let left = BSpline { control: s.control[..j].to_vec(), degree: p, knots: left_knots };
+
+        let mut right_knots = vec![t];
+        right_knots.extend_from_slice(&s.knots[j..]);
+        let right = BSpline { control: s.control[j - 1..].to_vec(), degree: p, knots: right_knots };
+
+        (left, right)
+    }
+
+    // ====================== 包围盒 ======================
+
+    /// 轴对齐包围盒 (min, max)
+    /// 在每个节点区间内粗采样导数，分量变号处二分定位极值点
+    pub fn bounding_box(&self) -> (Vec2, Vec2) {
+        let der = self.derivative();
+        let (t0, t1) = self.domain();
+        let mut ts = vec![t0, t1];
+
+        for (a, b) in self.spans() {
+            let step = (b - a) / SAMPLES_PER_SPAN as f64;
+            for i in 0..SAMPLES_PER_SPAN {
+                let lo = a + i as f64 * step;
+                let hi = lo + step;
+                let (d_lo, d_hi) = (der.point_at(lo), der.point_at(hi));
+                for comp in [|v: Vec2| v.x, |v: Vec2| v.y] {
+                    if comp(d_lo) * comp(d_hi) < 0.0 {
+                        ts.push(bisect(|t| comp(der.point_at(t)), lo, hi));
+                    }
+                }
+            }
+        }
+
+        let mut min = Vec2::INF;
+        let mut max = -Vec2::INF;
+        for t in ts {
+            let q = self.point_at(t);
+            min = Vec2::new(min.x.min(q.x), min.y.min(q.y));
+            max = Vec2::new(max.x.max(q.x), max.y.max(q.y));
+        }
+        (min, max)
+    }
+
+    // ====================== 距离优化求解 ======================
+
+    /// 寻找曲线上距离目标点最近的点
+    /// 返回 (最短距离, 最优参数 t, 曲线上的点)
+    /// 粗采样找出距离的局部极小，再在相邻采样区间内用牛顿法细化
+    pub fn closest_point(&self, p: Vec2) -> (f64, f64, Vec2) {
+        let d1 = self.derivative();
+        let d2 = d1.derivative();
+
+        let mut samples = Vec::new();
+        for (a, b) in self.spans() {
+            let step = (b - a) / SAMPLES_PER_SPAN as f64;
+            for i in 0..SAMPLES_PER_SPAN {
+                samples.push(a + i as f64 * step);
+            }
+        }
+        samples.push(self.domain().1);
+        let dist: Vec<f64> = samples.iter().map(|&t| self.point_at(t).dis_pow2(p)).collect();
+
+        let mut best_t = samples[0];
+        let mut best_d = dist[0];
+        for i in 0..samples.len() {
+            let prev = if i > 0 { dist[i - 1] } else { f64::INFINITY };
+            let next = if i + 1 < dist.len() { dist[i + 1] } else { f64::INFINITY };
+            if dist[i] > prev || dist[i] > next { continue; }
+
+            // 局部极小：在 [t_{i-1}, t_{i+1}] 内细化
+            let lo = samples[i.saturating_sub(1)];
+            let hi = samples[(i + 1).min(samples.len() - 1)];
+            let mut t = samples[i];
+            let mut d = dist[i];
+            for _ in 0..REFINE_MAX_ITER {
+                let c = self.point_at(t) - p;
+                let c1 = d1.point_at(t);
+                let g = c.dot(c1);
+                let dg = c1.pow2() + c.dot(d2.point_at(t));
+                if dg <= 0.0 { break; }
+                let t_new = (t - g / dg).clamp(lo, hi);
+                let d_new = self.point_at(t_new).dis_pow2(p);
+                if d_new > d { break; }
+                let done = (t_new - t).abs() < 1e-15;
+                t = t_new;
+                d = d_new;
+                if done { break; }
+            }
+            if d < best_d { best_d = d; best_t = t; }
+        }
+
+        let q = self.point_at(best_t);
+        (best_d.sqrt(), best_t, q)
+    }
+
+    // ====================== 离散化 ======================
+
+    /// 转换为分段贝塞尔形式：把定义域内每个节点的重数插到 p，
+    /// 此后每个非空节点区间 [u_k, u_{k+1}] 对应控制点 P_{k-p} ..= P_k 即为该段的 p 次贝塞尔控制点
+    pub fn to_bezier_segments(&self) -> Vec<Vec<Vec2>> {
+        let p = self.degree;
+        let (t0, t1) = self.domain();
+        let mut s = self.clone();
+
+        let mut distinct: Vec<f64> = self.knots.iter().copied().filter(|&u| u >= t0 && u <= t1).collect();
+        distinct.dedup();
+        for u in distinct {
+            let mult = s.knots.iter().filter(|&&v| v == u).count();
+            for _ in mult..p {
+                s.insert_knot(u);
+            }
+        }
+
+        (p..s.control.len())
+            .filter(|&k| s.knots[k] < s.knots[k + 1])
+            .map(|k| s.control[k - p..=k].to_vec())
+            .collect()
+    }
+
+    /// 自适应离散化为折线
+    /// 先转成分段贝塞尔，再对每段递归对半细分，直到内部控制点到弦的距离 <= tolerance
+    /// (由凸包性，该段曲线偏离弦不超过此距离)
+    pub fn to_polyline(&self, tolerance: f64) -> Vec<Vec2> {
+        let tol = tolerance.max(1e-12);
+        let (t0, _) = self.domain();
+        let mut out = vec![self.point_at(t0)];
+        for seg in self.to_bezier_segments() {
+            flatten_bezier_into(&seg, tol, 0, &mut out);
+        }
+        out
+    }
+}
+
+// 任意次贝塞尔 (控制点 pts) 的自适应离散化，只追加终点
+fn flatten_bezier_into(pts: &[Vec2], tol: f64, depth: u32, out: &mut Vec<Vec2>) {
+    let first = pts[0];
+    let last = pts[pts.len() - 1];
+    let flatness = pts[1..pts.len() - 1]
+        .iter()
+        .map(|&q| dist_to_segment(q, first, last))
+        .fold(0.0, f64::max);
+    if depth >= FLATTEN_MAX_DEPTH || flatness <= tol {
+        out.push(last);
+        return;
+    }
+
+    // de Casteljau 在 1/2 处细分
+    let mut left = Vec::with_capacity(pts.len());
+    let mut right = Vec::with_capacity(pts.len());
+    let mut cur = pts.to_vec();
+    left.push(cur[0]);
+    right.push(cur[cur.len() - 1]);
+    while cur.len() > 1 {
+        cur = cur.windows(2).map(|w| (w[0] + w[1]) * 0.5).collect();
+        left.push(cur[0]);
+        right.push(cur[cur.len() - 1]);
+    }
+    right.reverse();
+
+    flatten_bezier_into(&left, tol, depth + 1, out);
+    flatten_bezier_into(&right, tol, depth + 1, out);
+}
+
+// 在 f 变号的区间 [lo, hi] 内二分求根
+fn bisect<F: Fn(f64) -> f64>(f: F, mut lo: f64, mut hi: f64) -> f64 {
+    let f_lo = f(lo);
+    for _ in 0..REFINE_MAX_ITER + 20 {
+        let m = 0.5 * (lo + hi);
+        if (f(m) < 0.0) == (f_lo < 0.0) { lo = m; } else { hi = m; }
+    }
+    0.5 * (lo + hi)
+}
+
+impl fmt::Display for BSpline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BSpline(p = {}, {} 个控制点)", self.degree, self.control.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_forest::geometry::d2::spline::bezier::CubicBezier;
+
+    fn wave() -> BSpline {
+        BSpline::new_clamped(
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(1.0, 2.0),
+                Vec2::new(2.0, -1.0),
+                Vec2::new(3.0, 3.0),
+                Vec2::new(4.0, 0.0),
+                Vec2::new(5.0, 1.0),
+            ],
+            3,
+        )
+    }
+
+    #[test]
+    fn test_matches_bezier() {
+        // 单区间夹持三次 B 样条即三次贝塞尔
+        let pts = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 2.0), Vec2::new(3.0, -1.0), Vec2::new(4.0, 1.0)];
+        let s = BSpline::new_clamped(pts.to_vec(), 3);
+        let b = CubicBezier::new(pts[0], pts[1], pts[2], pts[3]);
+        for i in 0..=20 {
+            let t = i as f64 / 20.0;
+            assert!(s.point_at(t).dis(b.point_at(t)) < 1e-12);
+            assert!(s.derivative_at(t).dis(b.derivative_at(t)) < 1e-11);
+        }
+    }
+
+    #[test]
+    fn test_bezier_segments() {
+        let c = wave();
+        let segs = c.to_bezier_segments();
+        // 3 个内部区间
+        assert_eq!(segs.len(), 3);
+        for (i, seg) in segs.iter().enumerate() {
+            let b = CubicBezier::new(seg[0], seg[1], seg[2], seg[3]);
+            for j in 0..=10 {
+                let s = j as f64 / 10.0;
+                let t = (i as f64 + s) / 3.0;
+                assert!(b.point_at(s).dis(c.point_at(t)) < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_rejoin() {
+        let c = wave();
+        for t in [0.2, 1.0 / 3.0, 0.5, 0.81] {
+            let (l, r) = c.split(t);
+            assert_eq!(l.domain(), (0.0, t));
+            assert_eq!(r.domain(), (t, 1.0));
+            assert!(l.point_at(t).dis(r.point_at(t)) < 1e-12);
+            for i in 0..=50 {
+                let s = i as f64 / 50.0;
+                let tl = s * t;
+                let tr = t + s * (1.0 - t);
+                assert!(l.point_at(tl).dis(c.point_at(tl)) < 1e-12);
+                assert!(r.point_at(tr).dis(c.point_at(tr)) < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_flatten_within_tolerance() {
+        let c = wave();
+        for tol in [1e-2, 1e-4] {
+            let poly = c.to_polyline(tol);
+            assert_eq!(poly[0], c.control[0]);
+            assert!(poly.last().unwrap().dis(*c.control.last().unwrap()) < 1e-12);
+            for i in 0..=2000 {
+                let q = c.point_at(i as f64 / 2000.0);
+                let d = poly.windows(2)
+                    .map(|w| dist_to_segment(q, w[0], w[1]))
+                    .fold(f64::INFINITY, f64::min);
+                assert!(d <= tol, "tol {}: d = {}", tol, d);
+            }
+        }
+    }
+
+    #[test]
+    fn test_closest_point_and_bbox() {
+        let c = wave();
+        let dense: Vec<Vec2> = (0..=20000).map(|i| c.point_at(i as f64 / 20000.0)).collect();
+
+        for p in [Vec2::new(2.5, 0.5), Vec2::new(1.0, -1.0), Vec2::new(4.2, 2.0)] {
+            let (d, t, q) = c.closest_point(p);
+            assert!((q.dis(p) - d).abs() < 1e-12);
+            if t > 1e-9 && t < 1.0 - 1e-9 {
+                let g = (q - p).dot(c.derivative_at(t).unit());
+                assert!(g.abs() < 1e-9, "p = {}: g = {}", p, g);
+            }
+            assert!(dense.iter().all(|s| s.dis(p) >= d - 1e-12));
+        }
+
+        let (min, max) = c.bounding_box();
+        let mut smin = Vec2::INF;
+        let mut smax = -Vec2::INF;
+        for q in &dense {
+            smin = Vec2::new(smin.x.min(q.x), smin.y.min(q.y));
+            smax = Vec2::new(smax.x.max(q.x), smax.y.max(q.y));
+        }
+        assert!((min - smin).len() < 1e-6 && (max - smax).len() < 1e-6);
+    }
+}
@@ -0,0 +1,306 @@
+// src/math_forest/geometry/d2/spline/bezier.rs
+#![allow(dead_code)]
+
+use std::fmt;
+
+use crate::math_forest::algebra::solver::polynomial::solve_real_quadratic_for_real;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+
+// 最近点：初始分段数 (每段内检查 g(t) = (B(t) - P)·B'(t) 的符号变化)
+const CLOSEST_SEGMENTS: usize = 16;
+const CLOSEST_MAX_ITER: usize = 50;
+// 自适应离散化的最大递归深度 (2^16 段已远超屏幕精度)
+const FLATTEN_MAX_DEPTH: u32 = 16;
+
+/// 三次贝塞尔曲线
+/// B(t) = (1-t)^3 P0 + 3(1-t)^2 t P1 + 3(1-t) t^2 P2 + t^3 P3, t ∈ [0, 1]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CubicBezier {
+    pub p0: Vec2,
+    pub p1: Vec2,
+    pub p2: Vec2,
+    pub p3: Vec2,
+}
+
+impl CubicBezier {
+    #[inline(always)]
+    pub fn new(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> Self {
+        Self { p0, p1, p2, p3 }
+    }
+
+    pub fn get_type(&self) -> &str { "CubicBezier" }
+
+    // ====================== 求值 ======================
+
+    /// 曲线上的点 (de Casteljau)
+    pub fn point_at(&self, t: f64) -> Vec2 {
+        let s = 1.0 - t;
+        let a = self.p0 * s + self.p1 * t;
+        let b = self.p1 * s + self.p2 * t;
+        let c = self.p2 * s + self.p3 * t;
+        let d = a * s + b * t;
+        let e = b * s + c * t;
+        d * s + e * t
+    }
+
+    /// 一阶导数 B'(t) = 3[(1-t)^2 (P1-P0) + 2(1-t)t (P2-P1) + t^2 (P3-P2)]
+    pub fn derivative_at(&self, t: f64) -> Vec2 {
+        let s = 1.0 - t;
+        let a = self.p1 - self.p0;
+        let b = self.p2 - self.p1;
+        let c = self.p3 - self.p2;
+        (a * (s * s) + b * (2.0 * s * t) + c * (t * t)) * 3.0
+    }
+
+    /// 二阶导数 B''(t) = 6[(1-t)(P2 - 2P1 + P0) + t(P3 - 2P2 + P1)]
+    pub fn second_derivative_at(&self, t: f64) -> Vec2 {
+        let a = self.p2 - self.p1 * 2.0 + self.p0;
+        let b = self.p3 - self.p2 * 2.0 + self.p1;
+        (a * (1.0 - t) + b * t) * 6.0
+    }
+
+    // ====================== 细分 ======================
+
+    /// 在 t 处一分为二 (de Casteljau)
+    /// 左半段对应原参数 [0, t]，右半段对应 [t, 1]，两段均重新参数化到 [0, 1]
+    pub fn split(&self, t: f64) -> (CubicBezier, CubicBezier) {
+        let s = 1.0 - t;
+        let a = self.p0 * s + self.p1 * t;
+        let b = self.p1 * s + self.p2 * t;
+        let c = self.p2 * s + self.p3 * t;
+        let d = a * s + b * t;
+        let e = b * s + c * t;
+        let m = d * s + e * t;
+        (
+            CubicBezier::new(self.p0, a, d, m),
+            CubicBezier::new(m, e, c, self.p3),
+        )
+    }
+
+    // ====================== 包围盒 ======================
+
+    /// 精确轴对齐包围盒 (min, max)
+    /// 每个分量的导数是关于 t 的二次式 (A - 2B + C)t^2 + 2(B - A)t + A，其 [0, 1] 内的根即极值点
+    pub fn bounding_box(&self) -> (Vec2, Vec2) {
+        let a = self.p1 - self.p0;
+        let b = self.p2 - self.p1;
+        let c = self.p3 - self.p2;
+
+        let mut ts = vec![0.0, 1.0];
+        for (qa, qb, qc) in [
+            (a.x - 2.0 * b.x + c.x, 2.0 * (b.x - a.x), a.x),
+            (a.y - 2.0 * b.y + c.y, 2.0 * (b.y - a.y), a.y),
+        ] {
+            let r = solve_real_quadratic_for_real(qa, qb, qc);
+            for t in [r.n1, r.n2] {
+                if t.is_finite() && (0.0..=1.0).contains(&t) {
+                    ts.push(t);
+                }
+            }
+        }
+
+        let mut min = Vec2::INF;
+        let mut max = -Vec2::INF;
+        for t in ts {
+            let q = self.point_at(t);
+            min = Vec2::new(min.x.min(q.x), min.y.min(q.y));
+            max = Vec2::new(max.x.max(q.x), max.y.max(q.y));
+        }
+        (min, max)
+    }
+
+    // ====================== 距离优化求解 ======================
+
+    // g(t) = (B(t) - P)·B'(t)，距离平方导数的一半
+    fn dist_sq_der(&self, p: Vec2, t: f64) -> f64 {
+        (self.point_at(t) - p).dot(self.derivative_at(t))
+    }
+
+    // g'(t) = |B'(t)|^2 + (B(t) - P)·B''(t)
+    fn dist_sq_der2(&self, p: Vec2, t: f64) -> f64 {
+        self.derivative_at(t).pow2() + (self.point_at(t) - p).dot(self.second_derivative_at(t))
+    }
+
+    // 在 g 变号的区间 [lo, hi] 内求根：牛顿步落在区间外时退回二分
+    fn refine_root(&self, p: Vec2, mut lo: f64, mut hi: f64) -> f64 {
+        let mut g_lo = self.dist_sq_der(p, lo);
+        let mut t = 0.5 * (lo + hi);
+        for _ in 0..CLOSEST_MAX_ITER {
+            let g = self.dist_sq_der(p, t);
+            if g == 0.0 { break; }
+            if (g < 0.0) == (g_lo < 0.0) { lo = t; g_lo = g; } else { hi = t; }
+
+            let dg = self.dist_sq_der2(p, t);
+            let newton = if dg != 0.0 { t - g / dg } else { f64::NAN };
+            let next = if newton > lo && newton < hi { newton } else { 0.5 * (lo + hi) };
+            if (next - t).abs() < 1e-15 { t = next; break; }
+            t = next;
+        }
+        t
+    }
+
+    /// 寻找曲线上距离目标点最近的点
+    /// 返回 (最短距离, 最优参数 t, 曲线上的点)
+    /// 先把 [0, 1] 等分，在 g(t) 变号的子区间内用带保护的牛顿法求根，再与端点比较
+    pub fn closest_point(&self, p: Vec2) -> (f64, f64, Vec2) {
+        let mut best_t = 0.0;
+        let mut best_d = self.point_at(0.0).dis_pow2(p);
+        let mut consider = |t: f64| {
+            let d = self.point_at(t).dis_pow2(p);
+            if d < best_d { best_d = d; best_t = t; }
+        };
+        consider(1.0);
+
+        let step = 1.0 / CLOSEST_SEGMENTS as f64;
+        let mut g0 = self.dist_sq_der(p, 0.0);
+        for i in 0..CLOSEST_SEGMENTS {
+            let t0 = i as f64 * step;
+            let t1 = (i + 1) as f64 * step;
+            let g1 = self.dist_sq_der(p, t1);
+            // 距离极小值处 g 由负变正
+            if g0 <= 0.0 && g1 >= 0.0 {
+                consider(self.refine_root(p, t0, t1));
+            }
+            g0 = g1;
+        }
+
+        let q = self.point_at(best_t);
+        (best_d.sqrt(), best_t, q)
+    }
+
+    // ====================== 离散化 ======================
+
+    /// 平直度：控制点 P1、P2 到弦 P0P3 (线段) 的最大距离
+    /// 由凸包性，曲线到弦的距离不超过该值
+    pub fn flatness(&self) -> f64 {
+        let d1 = dist_to_segment(self.p1, self.p0, self.p3);
+        let d2 = dist_to_segment(self.p2, self.p0, self.p3);
+        d1.max(d2)
+    }
+
+    /// 自适应离散化为折线：递归细分直到每段都满足平直度 <= tolerance
+    /// 结果包含首尾端点，相邻顶点间的曲线偏离折线不超过 tolerance
+    pub fn to_polyline(self, tolerance: f64) -> Vec<Vec2> {
+        let mut out = vec![self.p0];
+        self.flatten_into(tolerance.max(1e-12), 0, &mut out);
+        out
+    }
+
+    fn flatten_into(&self, tolerance: f64, depth: u32, out: &mut Vec<Vec2>) {
+        if depth >= FLATTEN_MAX_DEPTH || self.flatness() <= tolerance {
+            out.push(self.p3);
+            return;
+        }
+        let (l, r) = self.split(0.5);
+        l.flatten_into(tolerance, depth + 1, out);
+        r.flatten_into(tolerance, depth + 1, out);
+    }
+}
+
+/// 点 p 到线段 ab 的距离
+pub(crate) fn dist_to_segment(p: Vec2, a: Vec2, b: Vec2) -> f64 {
+    let ab = b - a;
+    let len2 = ab.pow2();
+    if len2 < 1e-300 { return p.dis(a); }
+    let t = ((p - a).dot(ab) / len2).clamp(0.0, 1.0);
+    p.dis(a + ab * t)
+}
+
+impl fmt::Display for CubicBezier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CubicBezier[{}, {}, {}, {}]", self.p0, self.p1, self.p2, self.p3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 四分之一单位圆的标准三次近似
+    fn quarter_arc() -> CubicBezier {
+        let k = 0.552_284_749_830_793_4;
+        CubicBezier::new(
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, k),
+            Vec2::new(k, 1.0),
+            Vec2::new(0.0, 1.0),
+        )
+    }
+
+    fn s_curve() -> CubicBezier {
+        CubicBezier::new(
+            Vec2::new(-2.0, 0.0),
+            Vec2::new(-0.5, 3.0),
+            Vec2::new(0.5, -3.0),
+            Vec2::new(2.0, 1.0),
+        )
+    }
+
+    #[test]
+    fn test_split_rejoin() {
+        let c = s_curve();
+        for t in [0.1, 0.37, 0.5, 0.9] {
+            let (l, r) = c.split(t);
+            assert_eq!(l.p3, r.p0);
+            for i in 0..=20 {
+                let s = i as f64 / 20.0;
+                assert!(l.point_at(s).dis(c.point_at(s * t)) < 1e-12);
+                assert!(r.point_at(s).dis(c.point_at(t + s * (1.0 - t))) < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_flatten_within_tolerance() {
+        let c = quarter_arc();
+        for tol in [1e-1, 1e-3, 1e-5] {
+            let poly = c.to_polyline(tol);
+            assert!(poly.len() >= 2);
+            assert_eq!(poly[0], c.p0);
+            assert_eq!(*poly.last().unwrap(), c.p3);
+            // 曲线上的每个点都贴近折线
+            for i in 0..=1000 {
+                let q = c.point_at(i as f64 / 1000.0);
+                let d = poly.windows(2)
+                    .map(|w| dist_to_segment(q, w[0], w[1]))
+                    .fold(f64::INFINITY, f64::min);
+                assert!(d <= tol, "tol {}: d = {}", tol, d);
+            }
+        }
+        // 自适应：容差越小段数越多，但不是均匀暴力采样
+        assert!(c.to_polyline(1e-3).len() < 100);
+        assert!(c.to_polyline(1e-1).len() < c.to_polyline(1e-5).len());
+    }
+
+    #[test]
+    fn test_closest_point_orthogonal() {
+        let c = s_curve();
+        for p in [Vec2::new(0.0, 0.0), Vec2::new(1.0, 2.0), Vec2::new(-1.5, -1.0), Vec2::new(0.3, 0.8)] {
+            let (d, t, q) = c.closest_point(p);
+            assert!((q.dis(p) - d).abs() < 1e-12);
+            if t > 1e-9 && t < 1.0 - 1e-9 {
+                let g = (q - p).dot(c.derivative_at(t).unit());
+                assert!(g.abs() < 1e-9, "p = {}: g = {}", p, g);
+            }
+            // 不比任何采样点更远
+            for i in 0..=2000 {
+                assert!(c.point_at(i as f64 / 2000.0).dis(p) >= d - 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let c = s_curve();
+        let (min, max) = c.bounding_box();
+        let mut smin = Vec2::INF;
+        let mut smax = -Vec2::INF;
+        for i in 0..=10000 {
+            let q = c.point_at(i as f64 / 10000.0);
+            smin = Vec2::new(smin.x.min(q.x), smin.y.min(q.y));
+            smax = Vec2::new(smax.x.max(q.x), smax.y.max(q.y));
+        }
+        assert!((min - smin).len() < 1e-6 && (max - smax).len() < 1e-6);
+        assert!(min.x <= smin.x && min.y <= smin.y && max.x >= smax.x && max.y >= smax.y);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod bezier;
+pub mod b_spline;
@@ -0,0 +1,140 @@
+#![allow(dead_code)]
+
+// curve_eval.rs
+// 空间曲线 r(t) 的微分几何量：Frenet 标架、曲率 κ、挠率 τ，以及弧长 / 总曲率积分
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+
+/// 曲线在某一参数处的 Frenet 数据
+#[derive(Clone, Copy, Debug)]
+pub struct FrenetData {
+    pub t: f64,
+    pub pos: Vec3,
+    pub tangent: Vec3,  // T = r' / |r'|
+    pub normal: Vec3,   // N = B × T
+    pub binormal: Vec3, // B = (r' × r'') / |r' × r''|
+    pub curvature: f64, // κ = |r' × r''| / |r'|^3
+    pub torsion: f64,   // τ = (r' × r'')·r''' / |r' × r''|^2
+    pub speed: f64,     // |r'|
+}
+
+// 差分步长：随 |t| 放大，避免 t + h 被舍入吃掉
+fn step(t: f64) -> f64 {
+    1e-3 * (1.0 + t.abs())
+}
+
+/// r', r'', r''' (四阶中心差分)
+pub fn derivatives<F: Fn(f64) -> Vec3>(f: &F, t: f64) -> (Vec3, Vec3, Vec3) {
+    let h = step(t);
+    let p = |k: f64| f(t + k * h);
+    let (m3, m2, m1, p0, p1, p2, p3) = (p(-3.0), p(-2.0), p(-1.0), p(0.0), p(1.0), p(2.0), p(3.0));
+
+    let d1 = ((p1 - m1) * 8.0 - (p2 - m2)) / (12.0 * h);
+    let d2 = ((p1 + m1) * 16.0 - (p2 + m2) - p0 * 30.0) / (12.0 * h * h);
+    let d3 = ((p2 - m2) * 8.0 - (p1 - m1) * 13.0 - (p3 - m3)) / (8.0 * h * h * h);
+    (d1, d2, d3)
+}
+
+/// 在 t 处求 Frenet 数据
+/// 曲率为零 (直线段) 时主法向不确定：取与切线不平行的辅助轴构造一个垂直标架，τ 记为 0
+pub fn evaluate<F: Fn(f64) -> Vec3>(f: &F, t: f64) -> FrenetData {
+    let (d1, d2, d3) = derivatives(f, t);
+    let speed = d1.len();
+    let tangent = d1.unit();
+    let c = d1.cross(d2);
+    let c_len = c.len();
+
+    // |r' × r''| 相对 |r'|·|r''| 很小时视为直线
+    let straight = c_len <= 1e-9 * speed * d2.len().max(speed);
+    let (normal, binormal, torsion) = if straight {
+        let helper = if tangent.dot(Vec3::J).abs() > 0.99 { Vec3::K } else { Vec3::J };
+        let normal = tangent.cross(helper).unit();
+        (normal, tangent.cross(normal), 0.0)
+    } else {
+        let binormal = c / c_len;
+        (binormal.cross(tangent), binormal, c.dot(d3) / (c_len * c_len))
+    };
+
+    FrenetData {
+        t,
+        pos: f(t),
+        tangent,
+        normal,
+        binormal,
+        curvature: if straight { 0.0 } else { c_len / (speed * speed * speed) },
+        torsion,
+        speed,
+    }
+}
+
+/// 在 [t0, t1] 上等距取 n 段 (n + 1 个点)
+pub fn sample_range<F: Fn(f64) -> Vec3>(f: &F, t_range: (f64, f64), n: u32) -> Vec<FrenetData> {
+    let n = n.max(1);
+    let (t0, t1) = t_range;
+    (0..=n).map(|i| evaluate(f, t0 + (t1 - t0) * i as f64 / n as f64)).collect()
+}
+
+// 复合 Simpson 积分 (n 向上取偶)
+fn simpson<G: Fn(f64) -> f64>(g: G, (a, b): (f64, f64), n: u32) -> f64 {
+    let n = (n.max(2) + 1) & !1;
+    let h = (b - a) / n as f64;
+    let inner: f64 = (1..n).map(|i| g(a + i as f64 * h) * if i % 2 == 1 { 4.0 } else { 2.0 }).sum();
+    (g(a) + g(b) + inner) * h / 3.0
+}
+
+/// 弧长 ∫|r'| dt
+pub fn arc_length<F: Fn(f64) -> Vec3>(f: &F, t_range: (f64, f64), n: u32) -> f64 {
+    simpson(|t| derivatives(f, t).0.len(), t_range, n)
+}
+
+/// 总曲率 ∫κ ds = ∫κ|r'| dt
+pub fn total_curvature<F: Fn(f64) -> Vec3>(f: &F, t_range: (f64, f64), n: u32) -> f64 {
+    simpson(|t| { let d = evaluate(f, t); d.curvature * d.speed }, t_range, n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{PI, TAU};
+
+    #[test]
+    fn test_circle() {
+        let r = 2.5;
+        let circle = |t: f64| Vec3::new(r * t.cos(), r * t.sin(), 1.0);
+        for d in sample_range(&circle, (0.0, TAU), 16) {
+            assert!((d.curvature - 1.0 / r).abs() < 1e-6, "κ = {}", d.curvature);
+            assert!(d.torsion.abs() < 1e-6, "τ = {}", d.torsion);
+            // 主法向指向圆心，副法向为 +z
+            assert!((d.normal - (Vec3::new(0.0, 0.0, 1.0) - d.pos).unit()).len() < 1e-6);
+            assert!((d.binormal - Vec3::K).len() < 1e-6);
+        }
+        assert!((arc_length(&circle, (0.0, TAU), 64) - TAU * r).abs() < 1e-6);
+        // 平面闭曲线的总曲率为 2π
+        assert!((total_curvature(&circle, (0.0, TAU), 64) - TAU).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_helix() {
+        // r(t) = (a cos t, a sin t, b t)：κ = a / (a² + b²)，τ = b / (a² + b²)
+        let (a, b) = (1.5, 0.4);
+        let helix = |t: f64| Vec3::new(a * t.cos(), a * t.sin(), b * t);
+        let k = a / (a * a + b * b);
+        let tau = b / (a * a + b * b);
+        for d in sample_range(&helix, (-PI, 3.0 * PI), 12) {
+            assert!((d.curvature - k).abs() < 1e-6, "κ = {}", d.curvature);
+            assert!((d.torsion - tau).abs() < 1e-6, "τ = {}", d.torsion);
+            // 右手正交标架
+            assert!(d.tangent.dot(d.normal).abs() < 1e-9);
+            assert!((d.tangent.cross(d.normal) - d.binormal).len() < 1e-9);
+        }
+        assert!((arc_length(&helix, (0.0, TAU), 32) - TAU * (a * a + b * b).sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_straight_line() {
+        let line = |t: f64| Vec3::new(t, 2.0 * t, -t);
+        let d = evaluate(&line, 0.3);
+        assert_eq!(d.curvature, 0.0);
+        assert_eq!(d.torsion, 0.0);
+        assert!(d.normal.dot(d.tangent).abs() < 1e-12 && (d.normal.len() - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,2 @@
+//
+pub mod curve_eval;
@@ -10,6 +10,9 @@ pub struct Line3 {
 }
 
 impl Line3 {
+    /// 求交判定的容差 (方向已归一化，平行判定与公垂线长度共用)
+    pub const INTERSECT_EPS: f64 = 1e-9;
+
     pub fn new(origin: Vec3, direction: Vec3) -> Self {
         Line3 {
             origin,
@@ -52,17 +55,44 @@ impl Line3 {
         (t1, t2)
     }
 
+    /// 公垂线段的中点
+    /// 相交时即交点；异面时是两条直线的“最佳逼近交点”；平行时取 self.origin 与其在 other 上投影的中点
+    pub fn closest_midpoint(&self, other: &Line3) -> Vec3 {
+        let (t1, t2) = self.closest_points_params(other);
+        let t2 = if self.is_parallel(other) { (self.origin - other.origin).dot(other.direction) } else { t2 };
+        (self.point_at(t1) + other.point_at(t2)) * 0.5
+    }
+
     /// 获取两条直线的“交点”
     /// 注意：在空间中两条线往往不严格相交（异面）。
     /// 此函数返回公垂线段的中点作为“最佳逼近交点”。
     /// 对于几何中心求解（如内心），理论上是严格相交的，此方法精确有效。
+    /// 需要区分相交 / 异面时用 intersect
     pub fn intersection(&self, other: &Line3) -> Vec3 {
-        let (t1, t2) = self.closest_points_params(other);
-        let p1 = self.point_at(t1);
-        let p2 = other.point_at(t2);
+        self.closest_midpoint(other)
+    }
 
-        // 返回中点
-        (p1 + p2) * 0.5
+    /// 严格求交：只有两直线共面且不平行 (公垂线长度 < INTERSECT_EPS) 时返回交点
+    /// 平行 (含重合) 与异面都返回 None
+    pub fn intersect(&self, other: &Line3) -> Option<Vec3> {
+        if self.is_parallel(other) || self.distance_to_line(other) > Self::INTERSECT_EPS {
+            return None;
+        }
+        Some(self.closest_midpoint(other))
+    }
+
+    /// 两直线间的距离 (公垂线段长度)；平行时为点到直线的距离
+    pub fn distance_to_line(&self, other: &Line3) -> f64 {
+        if self.is_parallel(other) {
+            return self.distance_to_point(other.origin);
+        }
+        let n = self.direction.cross(other.direction);
+        (other.origin - self.origin).dot(n).abs() / n.len()
+    }
+
+    /// 方向平行 (含反向)
+    pub fn is_parallel(&self, other: &Line3) -> bool {
+        self.direction.cross(other.direction).len() < Self::INTERSECT_EPS
     }
 
     /// 距离测试
@@ -72,3 +102,38 @@ impl Line3 {
         (v - proj).len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: Vec3, b: Vec3) -> bool {
+        (a - b).len() < 1e-9
+    }
+
+    #[test]
+    fn test_intersect_coplanar() {
+        let a = Line3::from_points(Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 2.0, 2.0));
+        let b = Line3::from_points(Vec3::new(2.0, 0.0, 1.0), Vec3::new(0.0, 2.0, 1.0));
+        let p = a.intersect(&b).expect("lines meet at (1, 1, 1)");
+        assert!(approx(p, Vec3::new(1.0, 1.0, 1.0)));
+        assert!(a.distance_to_line(&b) < 1e-12);
+    }
+
+    #[test]
+    fn test_skew_and_parallel() {
+        // x 轴与 z = 1 平面上平行于 y 轴的直线：公垂线为 (3, 0, 0) - (3, 0, 1)
+        let a = Line3::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let b = Line3::new(Vec3::new(3.0, 5.0, 1.0), Vec3::new(0.0, 1.0, 0.0));
+        assert!(a.intersect(&b).is_none());
+        assert!((a.distance_to_line(&b) - 1.0).abs() < 1e-12);
+        assert!(approx(a.closest_midpoint(&b), Vec3::new(3.0, 0.0, 0.5)));
+
+        // 平行 (含重合)：没有唯一交点
+        let c = Line3::new(Vec3::new(0.0, 2.0, 0.0), Vec3::new(-1.0, 0.0, 0.0));
+        assert!(a.intersect(&c).is_none());
+        assert!(a.intersect(&a).is_none());
+        assert!((a.distance_to_line(&c) - 2.0).abs() < 1e-12);
+        assert!(approx(a.closest_midpoint(&c), Vec3::new(0.0, 1.0, 0.0)));
+    }
+}
@@ -35,6 +35,19 @@ impl Tril {
         }
     }
 
+    /// 由单位球面上的球面三角形构造 (顶点在球心)
+    /// a, b, c 为指向三个顶点的向量，会自动归一化；立体角即该三角形的面积
+    pub fn from_spherical_triangle(a: Vec3, b: Vec3, c: Vec3) -> Self {
+        Tril::new(Vec3::ZERO, a, b, c)
+    }
+
+    /// 球面角盈 A + B + C - π (A, B, C 为三条棱处的二面角，即球面三角形的内角)
+    /// Girard 定理：等于立体角
+    pub fn spherical_excess(&self) -> f64 {
+        let (da, db, dc) = self.dihedral_angles();
+        da + db + dc - PI
+    }
+
     // ================== 核心线类 (Cuan Lines) ==================
 
     /// 衡棱线 (Balance Arris Vector)
@@ -157,6 +170,24 @@ impl Tril {
 mod tests {
     use super::*; // 导入父级模块的所有内容（包括你的结构体和方法）
 
+    #[test]
+    fn test_spherical_triangle_girard() {
+        // 第一卦限：三个直角，面积 = 4π / 8
+        let octant = Tril::from_spherical_triangle(Vec3::I, Vec3::J, Vec3::K);
+        let (da, db, dc) = octant.dihedral_angles();
+        for d in [da, db, dc] {
+            assert!((d - PI / 2.0).abs() < 1e-10);
+        }
+        assert!((octant.solid_angle() - PI / 2.0).abs() < 1e-10);
+        assert!((octant.spherical_excess() - PI / 2.0).abs() < 1e-10);
+        assert!((octant.solid_angle() - octant.spherical_excess()).abs() < 1e-10);
+
+        // 一般三角形 (未归一化、左手顺序)：L'Huilier 与 Girard 一致
+        let t = Tril::from_spherical_triangle(Vec3::new(2.0, 0.3, 0.1), Vec3::new(0.2, 0.5, 1.5), Vec3::new(-0.4, 1.0, 0.2));
+        assert!((t.a.len() - 1.0).abs() < 1e-12);
+        assert!((t.solid_angle() - t.spherical_excess()).abs() < 1e-10);
+    }
+
     #[test]
     fn test() {
         println!("=== 汆论 (Tril Theory) 验证系统 ===");
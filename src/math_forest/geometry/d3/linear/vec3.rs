@@ -57,6 +57,12 @@ impl Vec3 {
         Vec3 { x, y, z }
     }
 
+    /// 转为 f32 数组 (上传 GPU 顶点 / Uniform 数据)
+    #[inline]
+    pub fn to_f32_array(self) -> [f32; 3] {
+        [self.x as f32, self.y as f32, self.z as f32]
+    }
+
     /// 球坐标构造 (ISO约定: theta为极角(与Z轴夹角), phi为方位角(XY平面))
     /// x = r sin(theta) cos(phi)
     /// y = r sin(theta) sin(phi)
@@ -72,6 +78,27 @@ impl Vec3 {
         }
     }
 
+    /// 转为球坐标 (r, theta, phi)，约定同 from_spherical
+    /// theta ∈ [0, π]，phi ∈ (-π, π]；在 Z 轴上时 phi = 0，零向量返回 (0, 0, 0)
+    #[inline]
+    pub fn to_spherical(self) -> (f64, f64, f64) {
+        let rho = self.x.hypot(self.y);
+        (self.len(), rho.atan2(self.z), self.y.atan2(self.x))
+    }
+
+    /// 柱坐标构造: rho为到Z轴的距离, phi为方位角, z不变
+    #[inline]
+    pub fn from_cylindrical(rho: f64, phi: f64, z: f64) -> Self {
+        let (sin_p, cos_p) = phi.sin_cos();
+        Vec3 { x: rho * cos_p, y: rho * sin_p, z }
+    }
+
+    /// 转为柱坐标 (rho, phi, z)，phi ∈ (-π, π]
+    #[inline]
+    pub fn to_cylindrical(self) -> (f64, f64, f64) {
+        (self.x.hypot(self.y), self.y.atan2(self.x), self.z)
+    }
+
     #[inline]
     pub fn rand() -> Self {
         Vec3 {
@@ -141,6 +168,21 @@ impl Vec3 {
         other_u * self.dot(other_u)
     }
 
+    /// 投影到平面: self 投影到过原点、法向为 normal 的平面上 (去掉法向分量)
+    /// v - (v · n̂) n̂；normal 为零向量时原样返回
+    #[inline]
+    pub fn project_onto_plane(self, normal: Vec3) -> Vec3 {
+        let n = normal.unit();
+        self - n * self.dot(n)
+    }
+
+    /// 沿 dir 分解: 返回 (平行分量, 垂直分量)，两者之和为 self
+    #[inline]
+    pub fn decompose_along(self, dir: Vec3) -> (Vec3, Vec3) {
+        let parallel = self.project_vec(dir);
+        (parallel, self - parallel)
+    }
+
     /// 投影长度 (带符号)
     #[inline]
     pub fn project(self, other: Vec3) -> f64 {
@@ -197,6 +239,44 @@ impl Vec3 {
             self.dot(other) / den
         }
     }
+
+    /// 夹角 (弧度，[0, π])
+    /// 先夹紧余弦，避免舍入误差略超出 [-1, 1] 时 acos 得 NaN
+    #[inline]
+    pub fn angle_between(self, other: Vec3) -> f64 {
+        self.cos(other).clamp(-1.0, 1.0).acos()
+    }
+
+    /// 绕 axis 从 self 转到 other 的有向角 (弧度，[-π, π])，右手定则为正
+    /// 只计入两向量在垂直于 axis 的平面上的投影
+    pub fn signed_angle_around_axis(self, other: Vec3, axis: Vec3) -> f64 {
+        let axis = axis.unit();
+        let (a, b) = (self.project_onto_plane(axis), other.project_onto_plane(axis));
+        a.cross(b).dot(axis).atan2(a.dot(b))
+    }
+
+    /// 重心坐标 (u, v, w)：self ≈ u*a + v*b + w*c，且 u + v + w = 1
+    /// 点不在三角形平面上时，结果对应其在平面上的投影
+    /// 三角形退化 (面积接近 0) 时返回 None
+    pub fn barycentric_coordinates(self, a: Vec3, b: Vec3, c: Vec3) -> Option<Vec3> {
+        let e1 = b - a;
+        let e2 = c - a;
+        // 以 (e1, e2, n) 为基分解 self - a，法向分量即到平面的有向距离 (丢弃)
+        let n = e1.cross(e2);
+        let (v, w, _) = (self - a).rsv(e1, e2, n);
+        if v.is_nan() || w.is_nan() {
+            return None;
+        }
+        Some(Vec3::new(1.0 - v - w, v, w))
+    }
+
+    /// 点 (在平面上的投影) 是否在三角形 abc 内 (含边界)
+    pub fn is_inside_triangle(self, a: Vec3, b: Vec3, c: Vec3) -> bool {
+        match self.barycentric_coordinates(a, b, c) {
+            Some(bary) => bary.x > -Self::EPSILON && bary.y > -Self::EPSILON && bary.z > -Self::EPSILON,
+            None => false,
+        }
+    }
 }
 
 // ====================== 运算符重载 (宏魔法) ======================
@@ -348,3 +428,102 @@ impl fmt::Display for Vec3 {
         write!(f, "({:.4}, {:.4}, {:.4})", x, y, z)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_barycentric_coordinates() {
+        let a = Vec3::new(1.0, 0.0, 2.0);
+        let b = Vec3::new(3.0, 1.0, 0.0);
+        let c = Vec3::new(0.0, 4.0, 1.0);
+
+        let (u, v, w) = (0.2, 0.5, 0.3);
+        let p = a * u + b * v + c * w;
+        let bary = p.barycentric_coordinates(a, b, c).unwrap();
+        assert!(bary.dis(Vec3::new(u, v, w)) < 1e-12);
+        assert!(p.is_inside_triangle(a, b, c));
+
+        // 顶点与边界
+        assert!(a.barycentric_coordinates(a, b, c).unwrap().dis(Vec3::new(1.0, 0.0, 0.0)) < 1e-12);
+        assert!(((b + c) * 0.5).is_inside_triangle(a, b, c));
+
+        // 平面外的点按投影处理
+        let n = (b - a).cross(c - a).unit();
+        let lifted = p + n * 5.0;
+        assert!(lifted.barycentric_coordinates(a, b, c).unwrap().dis(bary) < 1e-12);
+
+        // 三角形外
+        let q = a * 1.2 + b * 0.1 + c * -0.3;
+        assert!(!q.is_inside_triangle(a, b, c));
+
+        // 退化 (共线)
+        assert!(p.barycentric_coordinates(a, b, a * 2.0 - b).is_none());
+        assert!(!p.is_inside_triangle(a, a, c));
+    }
+
+    #[test]
+    fn test_plane_projection_and_decomposition() {
+        let v = Vec3::new(1.0, 2.0, 3.0);
+        let n = Vec3::new(0.0, 0.0, 5.0);
+        assert!(v.project_onto_plane(n).dis(Vec3::new(1.0, 2.0, 0.0)) < 1e-12);
+
+        let dir = Vec3::new(1.0, -1.0, 2.0);
+        let (par, perp) = v.decompose_along(dir);
+        assert!((par + perp).dis(v) < 1e-12);
+        assert!(perp.dot(dir).abs() < 1e-12);
+        assert!(par.cross(dir).len() < 1e-12);
+        assert!(perp.dis(v.project_onto_plane(dir)) < 1e-12);
+
+        // 零法向：不做任何投影
+        assert_eq!(v.project_onto_plane(Vec3::ZERO), v);
+    }
+
+    #[test]
+    fn test_spherical_cylindrical_round_trip() {
+        use std::f64::consts::PI;
+        let points = [
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(-1.5, 0.5, -2.0), // z < 0
+            Vec3::new(0.0, -3.0, -0.1),
+            Vec3::new(0.0, 0.0, 2.0),   // 北极
+            Vec3::new(0.0, 0.0, -2.0),  // 南极
+        ];
+        for p in points {
+            let (r, theta, phi) = p.to_spherical();
+            assert!((0.0..=PI).contains(&theta));
+            assert!(Vec3::from_spherical(theta, phi, r).dis(p) < 1e-12, "{:?}", p);
+            let (rho, phi, z) = p.to_cylindrical();
+            assert!(Vec3::from_cylindrical(rho, phi, z).dis(p) < 1e-12, "{:?}", p);
+        }
+
+        // 两极的极角与方位角
+        assert_eq!(Vec3::new(0.0, 0.0, 2.0).to_spherical(), (2.0, 0.0, 0.0));
+        assert_eq!(Vec3::new(0.0, 0.0, -2.0).to_spherical(), (2.0, PI, 0.0));
+        assert_eq!(Vec3::ZERO.to_spherical(), (0.0, 0.0, 0.0));
+
+        // 反方向：球坐标 -> 点 -> 球坐标
+        let (r, theta, phi) = Vec3::from_spherical(2.5, -1.0, 3.0).to_spherical();
+        assert!((r - 3.0).abs() < 1e-12 && (theta - 2.5).abs() < 1e-12 && (phi + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_angles() {
+        use std::f64::consts::{FRAC_PI_2, PI};
+        let (x, y, z) = (Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 3.0, 0.0), Vec3::new(0.0, 0.0, 1.0));
+        assert!((x.angle_between(y) - FRAC_PI_2).abs() < 1e-12);
+        assert!((x.angle_between(Vec3::new(-1.0, 1.0, 0.0)) - 0.75 * PI).abs() < 1e-12);
+        // 平行 / 反平行不出 NaN
+        let v = Vec3::new(0.1, 0.7, 0.3);
+        assert!(v.angle_between(v * 3.0) < 1e-7);
+        assert!((v.angle_between(-v) - PI).abs() < 1e-7);
+
+        // 绕 z：x -> y 为 +π/2，反过来为 -π/2；轴长度不影响结果
+        assert!((x.signed_angle_around_axis(y, z) - FRAC_PI_2).abs() < 1e-12);
+        assert!((y.signed_angle_around_axis(x, z * 5.0) + FRAC_PI_2).abs() < 1e-12);
+        assert!((x.signed_angle_around_axis(Vec3::new(-1.0, -1.0, 0.0), z) + 0.75 * PI).abs() < 1e-12);
+        // 沿轴的分量不计入
+        assert!((x.signed_angle_around_axis(Vec3::new(0.0, 1.0, 7.0), z) - FRAC_PI_2).abs() < 1e-12);
+    }
+}
@@ -1,6 +1,7 @@
 
 //
 pub mod linear;
+pub mod curve;
 mod surface;
 mod conic;
 
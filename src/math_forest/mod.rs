@@ -6,3 +6,6 @@ pub mod geometry;
 
 //
 pub mod statistics;
+
+//
+pub mod numerics;
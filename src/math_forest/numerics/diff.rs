@@ -0,0 +1,92 @@
+// src/math_forest/numerics/diff.rs
+// 数值微分：统一使用中心差分 (f(x + h) - f(x - h)) / 2h，截断误差 O(h²)
+// eps 为步长 h；一阶导数 1e-6 左右合适，二阶导数 (Hessian) 的舍入误差按 1/h² 放大，宜取 1e-4 左右
+#![allow(dead_code)]
+
+use crate::math_forest::algebra::linear::matrix2x2::Matrix2x2;
+use crate::math_forest::algebra::linear::matrix3x3::Matrix3x3;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+
+const AXES: [Vec3; 3] = [Vec3::I, Vec3::J, Vec3::K];
+
+/// 梯度 ∇f(p) (6 次求值)
+pub fn gradient_f64(f: impl Fn(Vec3) -> f64, p: Vec3, eps: f64) -> Vec3 {
+    let d = |axis: Vec3| (f(p + axis * eps) - f(p - axis * eps)) / (2.0 * eps);
+    Vec3::new(d(Vec3::I), d(Vec3::J), d(Vec3::K))
+}
+
+/// Hessian 矩阵 H_ij = ∂²f / ∂x_i ∂x_j (对称)
+/// 对角元：(f(x + h) - 2f(x) + f(x - h)) / h²，共 6 次 + 中心 1 次
+/// 非对角元：(f(++) - f(+-) - f(-+) + f(--)) / 4h²，每个 4 次，上三角 3 个共 12 次
+pub fn hessian_f64(f: impl Fn(Vec3) -> f64, p: Vec3, eps: f64) -> Matrix3x3 {
+    let center = f(p);
+    let mut h = Matrix3x3::ZERO;
+    for (i, axis_i) in AXES.iter().enumerate() {
+        let a = *axis_i * eps;
+        h.m[i * 3 + i] = (f(p + a) - 2.0 * center + f(p - a)) / (eps * eps);
+        for (j, axis_j) in AXES.iter().enumerate().skip(i + 1) {
+            let b = *axis_j * eps;
+            let v = (f(p + a + b) - f(p + a - b) - f(p - a + b) + f(p - a - b)) / (4.0 * eps * eps);
+            h.m[i * 3 + j] = v;
+            h.m[j * 3 + i] = v;
+        }
+    }
+    h
+}
+
+/// 平面映射 f: R² -> R² 的 Jacobi 矩阵，第 j 列为 ∂f / ∂x_j (4 次求值)
+pub fn jacobian_2d(f: impl Fn(Vec2) -> Vec2, p: Vec2, eps: f64) -> Matrix2x2 {
+    let d = |axis: Vec2| (f(p + axis * eps) - f(p - axis * eps)) / (2.0 * eps);
+    let (dx, dy) = (d(Vec2::new(1.0, 0.0)), d(Vec2::new(0.0, 1.0)));
+    Matrix2x2::new(dx.x, dy.x, dx.y, dy.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // f = x²y + sin z + yz
+    fn f(p: Vec3) -> f64 {
+        p.x * p.x * p.y + p.z.sin() + p.y * p.z
+    }
+
+    #[test]
+    fn test_gradient_and_hessian() {
+        let p = Vec3::new(1.5, -0.5, 0.3);
+        let g = gradient_f64(f, p, 1e-6);
+        let expected = Vec3::new(2.0 * p.x * p.y, p.x * p.x + p.z, p.z.cos() + p.y);
+        assert!((g - expected).len() < 1e-8, "{:?}", g);
+
+        let h = hessian_f64(f, p, 1e-4);
+        let expected = Matrix3x3::new(
+            2.0 * p.y, 2.0 * p.x, 0.0,
+            2.0 * p.x, 0.0, 1.0,
+            0.0, 1.0, -p.z.sin(),
+        );
+        for (a, b) in h.m.iter().zip(expected.m) {
+            assert!((a - b).abs() < 1e-6, "{} vs {}", a, b);
+        }
+        assert_eq!(h, h.transpose());
+    }
+
+    #[test]
+    fn test_evaluation_count() {
+        use std::cell::Cell;
+        let count = Cell::new(0);
+        let counted = |p: Vec3| { count.set(count.get() + 1); f(p) };
+        hessian_f64(counted, Vec3::ZERO, 1e-4);
+        assert_eq!(count.get(), 12 + 6 + 1);
+    }
+
+    #[test]
+    fn test_jacobian_2d() {
+        // f(x, y) = (x² - y, xy)，J = [[2x, -1], [y, x]]
+        let map = |p: Vec2| Vec2::new(p.x * p.x - p.y, p.x * p.y);
+        let j = jacobian_2d(map, Vec2::new(2.0, 3.0), 1e-6);
+        let expected = Matrix2x2::new(4.0, -1.0, 3.0, 2.0);
+        for (a, b) in j.m.iter().zip(expected.m) {
+            assert!((a - b).abs() < 1e-8, "{} vs {}", a, b);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+// 数值微分
+pub mod diff;
+
+// 数值积分
+pub mod quad;
@@ -0,0 +1,62 @@
+// src/math_forest/numerics/quad.rs
+// 数值积分：自适应 Simpson
+#![allow(dead_code)]
+
+// 先均分的段数：周期或多峰的被积函数只看整个区间的 Simpson 估计容易误判收敛
+const INITIAL_PIECES: usize = 16;
+// 递归深度上限 (区间最多二分这么多次)
+const MAX_DEPTH: u32 = 16;
+
+/// ∫_a^b g(t) dt，tol 为绝对误差目标
+/// 每段比较一次 Simpson 与两次半段 Simpson，差超过 15·tol 时继续二分，收敛段加 Richardson 修正
+pub fn adaptive_simpson<G: Fn(f64) -> f64>(g: G, (a, b): (f64, f64), tol: f64) -> f64 {
+    let h = (b - a) / INITIAL_PIECES as f64;
+    let piece_tol = tol / INITIAL_PIECES as f64;
+    (0..INITIAL_PIECES)
+        .map(|i| {
+            let lo = a + i as f64 * h;
+            let hi = if i + 1 == INITIAL_PIECES { b } else { lo + h };
+            let (flo, fmid, fhi) = (g(lo), g(0.5 * (lo + hi)), g(hi));
+            let whole = simpson(lo, hi, flo, fmid, fhi);
+            refine(&g, lo, hi, flo, fmid, fhi, whole, piece_tol, MAX_DEPTH)
+        })
+        .sum()
+}
+
+fn simpson(a: f64, b: f64, fa: f64, fm: f64, fb: f64) -> f64 {
+    (b - a) / 6.0 * (fa + 4.0 * fm + fb)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn refine<G: Fn(f64) -> f64>(g: &G, a: f64, b: f64, fa: f64, fm: f64, fb: f64, whole: f64, tol: f64, depth: u32) -> f64 {
+    let m = 0.5 * (a + b);
+    let (lm, rm) = (0.5 * (a + m), 0.5 * (m + b));
+    let (flm, frm) = (g(lm), g(rm));
+    let left = simpson(a, m, fa, flm, fm);
+    let right = simpson(m, b, fm, frm, fb);
+    let delta = left + right - whole;
+    // 误差目标不低于舍入噪声，否则平滑的被积函数也会一直二分到深度上限
+    let tol = tol.max(f64::EPSILON * (left + right).abs());
+    if depth == 0 || delta.abs() <= 15.0 * tol || !delta.is_finite() {
+        return left + right + delta / 15.0;
+    }
+    refine(g, a, m, fa, flm, fm, left, 0.5 * tol, depth - 1) + refine(g, m, b, fm, frm, fb, right, 0.5 * tol, depth - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_adaptive_simpson() {
+        // 多项式 (三次以内 Simpson 精确)
+        assert!((adaptive_simpson(|x| x * x * x - 2.0 * x, (0.0, 2.0), 1e-12) - 0.0).abs() < 1e-12);
+        // 周期函数一个整周期
+        assert!((adaptive_simpson(|x| x.sin().powi(2), (0.0, 2.0 * PI), 1e-12) - PI).abs() < 1e-10);
+        // 端点附近变化剧烈：√x 在 0 处导数无界
+        assert!((adaptive_simpson(f64::sqrt, (0.0, 1.0), 1e-10) - 2.0 / 3.0).abs() < 1e-8);
+        // 反向区间取负
+        assert!((adaptive_simpson(f64::exp, (1.0, 0.0), 1e-12) + (1f64.exp() - 1.0)).abs() < 1e-11);
+    }
+}
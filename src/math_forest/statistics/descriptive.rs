@@ -0,0 +1,114 @@
+#![allow(dead_code)]
+
+use super::random::RandomMaster;
+
+// 描述统计：对采样结果分箱、求矩，用于检验分布形状
+// 空数据的统计量为 NaN；方差与高阶矩均为总体矩 (除以 n)
+impl RandomMaster {
+    /// 等宽分箱，返回 (bin_start, bin_end, count)
+    /// 范围取数据的 [min, max]，最大值计入最后一个箱；非有限值 (NaN / Inf) 忽略
+    /// 所有数据相等时以该值为中心取单位宽度
+    pub fn histogram(data: &[f64], bins: usize) -> Vec<(f64, f64, usize)> {
+        let finite: Vec<f64> = data.iter().copied().filter(|x| x.is_finite()).collect();
+        if bins == 0 || finite.is_empty() {
+            return Vec::new();
+        }
+
+        let mut lo = finite.iter().copied().fold(f64::INFINITY, f64::min);
+        let mut hi = finite.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if hi - lo <= 0.0 {
+            lo -= 0.5;
+            hi += 0.5;
+        }
+        let width = (hi - lo) / bins as f64;
+
+        let mut counts = vec![0usize; bins];
+        for x in finite {
+            let i = (((x - lo) / width) as usize).min(bins - 1);
+            counts[i] += 1;
+        }
+
+        counts.into_iter().enumerate().map(|(i, count)| {
+            let start = lo + i as f64 * width;
+            let end = if i + 1 == bins { hi } else { lo + (i + 1) as f64 * width };
+            (start, end, count)
+        }).collect()
+    }
+
+    /// 算术平均
+    pub fn mean(data: &[f64]) -> f64 {
+        if data.is_empty() {
+            return f64::NAN;
+        }
+        data.iter().sum::<f64>() / data.len() as f64
+    }
+
+    /// 总体方差 E[(X - μ)²]
+    pub fn variance(data: &[f64]) -> f64 {
+        Self::central_moment(data, 2)
+    }
+
+    /// 偏度 E[(X - μ)³] / σ³ (对称分布为 0)
+    pub fn skewness(data: &[f64]) -> f64 {
+        let var = Self::variance(data);
+        Self::central_moment(data, 3) / var.powf(1.5)
+    }
+
+    /// 超额峰度 E[(X - μ)⁴] / σ⁴ - 3 (正态分布为 0)
+    pub fn kurtosis(data: &[f64]) -> f64 {
+        let var = Self::variance(data);
+        Self::central_moment(data, 4) / (var * var) - 3.0
+    }
+
+    // k 阶中心矩
+    fn central_moment(data: &[f64], k: i32) -> f64 {
+        let mu = Self::mean(data);
+        if mu.is_nan() {
+            return f64::NAN;
+        }
+        data.iter().map(|x| (x - mu).powi(k)).sum::<f64>() / data.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, Normal};
+
+    #[test]
+    fn test_normal_mean_variance() {
+        // Normal(5, 2)：均值 5，方差 4；固定种子避免偶发越界
+        let dist = Normal::new(5.0, 2.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(1348);
+        let data: Vec<f64> = (0..100_000).map(|_| dist.sample(&mut rng)).collect();
+
+        // 误差在真值的 1% 以内
+        let mean = RandomMaster::mean(&data);
+        let var = RandomMaster::variance(&data);
+        assert!((mean - 5.0).abs() < 0.05, "mean = {}", mean);
+        assert!((var - 4.0).abs() < 0.04, "variance = {}", var);
+
+        // 正态分布：偏度、超额峰度都接近 0
+        assert!(RandomMaster::skewness(&data).abs() < 0.1);
+        assert!(RandomMaster::kurtosis(&data).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_histogram_bins() {
+        let data = [0.0, 0.5, 1.0, 1.5, 2.0, 3.9, 4.0, f64::NAN];
+        let hist = RandomMaster::histogram(&data, 4);
+        assert_eq!(hist.len(), 4);
+        assert_eq!(hist[0], (0.0, 1.0, 2));
+        assert_eq!(hist[1], (1.0, 2.0, 2));
+        assert_eq!(hist[2], (2.0, 3.0, 1));
+        // 最大值计入最后一个箱
+        assert_eq!(hist[3], (3.0, 4.0, 2));
+        assert_eq!(hist.iter().map(|b| b.2).sum::<usize>(), 7);
+
+        assert!(RandomMaster::histogram(&[], 4).is_empty());
+        assert!(RandomMaster::histogram(&data, 0).is_empty());
+        assert_eq!(RandomMaster::histogram(&[2.0, 2.0], 1), vec![(1.5, 2.5, 2)]);
+    }
+}
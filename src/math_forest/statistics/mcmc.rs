@@ -0,0 +1,143 @@
+#![allow(dead_code)]
+
+// Metropolis-Hastings 采样：从只知道比例关系的分布 p(x) ∝ f(x) 中抽样
+// 提议 x' ~ N(x, σ)，以 min(1, f(x') / f(x)) 的概率接受，否则停在原地
+// 相邻样本相关，开头一段 (burn-in) 尚未到达平稳分布，需要丢弃
+
+use rand::prelude::*;
+use rand_distr::{Distribution, Normal};
+
+pub struct MetropolisHastings<F: Fn(f64) -> f64> {
+    // ln f(x)：在对数域比较，避免密度很小时下溢；定义域外返回 -inf
+    log_density: F,
+    proposal: Normal<f64>,
+    rng: StdRng,
+    // 当前状态及其对数密度 (缓存，每步只需对提议点求值一次)
+    current: f64,
+    current_log: f64,
+    // 接受率统计
+    proposed: u64,
+    accepted: u64,
+}
+
+impl<F: Fn(f64) -> f64> MetropolisHastings<F> {
+    /// log_density: 未归一化的对数密度 ln f(x)
+    /// initial: 链的起点 (最好在高密度区域附近，burn-in 可以短一些)
+    /// proposal_std: 提议步长，接受率在 20% ~ 50% 左右时混合较快
+    pub fn new(log_density: F, initial: f64, proposal_std: f64) -> Self {
+        assert!(proposal_std > 0.0, "MCMC: 提议标准差必须大于0");
+        let proposal = Normal::new(0.0, proposal_std).unwrap();
+        let current_log = log_density(initial);
+        // NaN 或 +inf 时所有接受判断都为假，链会一直停在起点；-inf (定义域外) 可以，见 next_sample
+        assert!(current_log < f64::INFINITY, "MCMC: 起点的对数密度不能为 NaN 或 +inf");
+        Self {
+            log_density,
+            proposal,
+            rng: StdRng::from_entropy(),
+            current: initial,
+            current_log,
+            proposed: 0,
+            accepted: 0,
+        }
+    }
+
+    /// 固定随机种子 (结果可复现)
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// 走一步并返回新的状态 (被拒绝时与上一个样本相同)
+    pub fn next_sample(&mut self) -> f64 {
+        let candidate = self.current + self.proposal.sample(&mut self.rng);
+        let candidate_log = (self.log_density)(candidate);
+        self.proposed += 1;
+
+        // ln u < ln f(x') - ln f(x)  <=>  u < f(x') / f(x)
+        // 提议点密度为 NaN 时比较为假，视为拒绝；当前点在定义域外 (-inf) 时任何有限提议都接受
+        let log_u = self.rng.gen_range(0.0f64..1.0).ln();
+        if log_u < candidate_log - self.current_log || (self.current_log == f64::NEG_INFINITY && candidate_log > f64::NEG_INFINITY) {
+            self.current = candidate;
+            self.current_log = candidate_log;
+            self.accepted += 1;
+        }
+        self.current
+    }
+
+    /// 先丢弃 burn_in 个样本，再返回接下来的 n 个
+    pub fn next_samples(&mut self, n: usize, burn_in: usize) -> Vec<f64> {
+        for _ in 0..burn_in {
+            self.next_sample();
+        }
+        (0..n).map(|_| self.next_sample()).collect()
+    }
+
+    pub fn current(&self) -> f64 {
+        self.current
+    }
+
+    /// 目前为止的接受率 (尚未走过时为 0)
+    pub fn acceptance_rate(&self) -> f64 {
+        if self.proposed == 0 { 0.0 } else { self.accepted as f64 / self.proposed as f64 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean_var(xs: &[f64]) -> (f64, f64) {
+        let n = xs.len() as f64;
+        let mean = xs.iter().sum::<f64>() / n;
+        let var = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        (mean, var)
+    }
+
+    #[test]
+    fn test_normal_moments() {
+        // 未归一化的 N(2, 0.5²)：从远离峰值的地方出发，burn-in 后矩应吻合
+        let mut chain = MetropolisHastings::new(|x: f64| -(x - 2.0).powi(2) / (2.0 * 0.25), -10.0, 1.0).with_seed(7);
+        let samples = chain.next_samples(40_000, 2_000);
+        let (mean, var) = mean_var(&samples);
+        assert!((mean - 2.0).abs() < 0.05, "mean = {}", mean);
+        assert!((var - 0.25).abs() < 0.03, "var = {}", var);
+
+        let rate = chain.acceptance_rate();
+        assert!(rate > 0.2 && rate < 0.8, "acceptance rate = {}", rate);
+        assert_eq!(chain.current(), *samples.last().unwrap());
+    }
+
+    #[test]
+    fn test_support_and_reproducibility() {
+        // 指数分布 f(x) = e^{-x} (x > 0)：定义域外为 -inf，样本永远不会越界
+        let log_exp = |x: f64| if x > 0.0 { -x } else { f64::NEG_INFINITY };
+        let mut chain = MetropolisHastings::new(log_exp, 1.0, 2.0).with_seed(42);
+        let samples = chain.next_samples(20_000, 500);
+        assert!(samples.iter().all(|&x| x > 0.0));
+        let (mean, _) = mean_var(&samples);
+        assert!((mean - 1.0).abs() < 0.1, "mean = {}", mean);
+
+        // 同一种子得到同一条链
+        let again = MetropolisHastings::new(log_exp, 1.0, 2.0).with_seed(42).next_samples(20_000, 500);
+        assert_eq!(samples, again);
+
+        // 从定义域外出发：第一个落入定义域的提议一定被接受
+        let mut chain = MetropolisHastings::new(log_exp, -0.5, 1.0).with_seed(1);
+        let first_inside = (0..1000).map(|_| chain.next_sample()).find(|&x| x > 0.0);
+        assert!(first_inside.is_some());
+        assert!(chain.next_samples(100, 0).iter().all(|&x| x > 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "MCMC: 起点的对数密度不能为 NaN 或 +inf")]
+    fn test_nan_initial_log_density() {
+        // ln(x) 在 x < 0 处为 NaN
+        MetropolisHastings::new(|x: f64| x.ln(), -1.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "MCMC: 提议标准差必须大于0")]
+    fn test_invalid_proposal_std() {
+        MetropolisHastings::new(|x: f64| -x * x, 0.0, 0.0);
+    }
+}
@@ -1 +1,4 @@
 pub mod random;
+pub mod descriptive;
+pub mod sobol;
+pub mod mcmc;
@@ -1,12 +1,15 @@
 // src/parser/compiler.rs
 use super::token::{Lexer, Token};
-use crate::core::symbol_table::SymbolTable;
+use super::symbol_table::SymbolTable;
 use crate::pakoo::math_data::MathData;
 use crate::pakoo::op::Op; // 假设 Op 定义在这里
+use crate::pakoo::rpn::RPN;
 
 #[derive(Debug, PartialEq, PartialOrd)]
 enum Precedence {
     Lowest,
+    Ternary,    // ? : (右结合)
+    Comparison, // > < ==
     Sum,     // + -
     Product, // * /
     Power,   // ^
@@ -20,6 +23,13 @@ pub struct CompileResult {
     pub dependencies: Vec<usize>, // 这个公式依赖了哪些全局 ID
 }
 
+// 正在解析的函数调用 f(a, b)：每个实参在输出队列中的起点 (逗号处追加)
+struct CallFrame {
+    id: usize,
+    arity: usize,
+    arg_starts: Vec<usize>,
+}
+
 pub struct Compiler<'a> {
     lexer: Lexer<'a>,
     symbol_table: &'a mut SymbolTable,
@@ -33,17 +43,70 @@ impl<'a> Compiler<'a> {
         }
     }
 
+    // 编译表达式 (不在任何函数体内，没有形参)；语法错误时 panic，见 try_compile
+    // 只用于写死在代码里的表达式 (如测试)
+    #[allow(dead_code)]
     pub fn compile(&mut self) -> CompileResult {
+        self.try_compile().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // 同 compile，语法错误 (含词法错误) 返回错误信息而不是 panic
+    // 外部输入 (监视的文件、REPL) 用这个：release 构建 panic = "abort"，panic 无法捕获
+    pub fn try_compile(&mut self) -> Result<CompileResult, String> {
+        self.symbol_table.bind_params(&[]);
+        self.compile_body()
+    }
+
+    // 把输入编译为具名函数 name(params...) 的函数体：形参编译为 LoadPara(下标)
+    // 函数名先登记到符号表 (函数体内可以递归调用自身)，返回其全局 ID
+    // 调用方据此建立 Slice::Def { para_count: params.len(), body }，Env::sync_with 后按名字添加即落在同一 ID 的位置
+    #[allow(dead_code)]
+    pub fn compile_function_def(&mut self, name: &str, params: &[&str]) -> (usize, CompileResult) {
+        self.try_compile_function_def(name, params).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // 同 compile_function_def，形参或函数体有错时返回错误信息
+    pub fn try_compile_function_def(&mut self, name: &str, params: &[&str]) -> Result<(usize, CompileResult), String> {
+        if SymbolTable::is_reserved(name) {
+            return Err(format!("'{}' is a reserved identifier", name));
+        }
+        for (i, p) in params.iter().enumerate() {
+            if SymbolTable::is_reserved(p) {
+                return Err(format!("'{}' is a reserved identifier", p));
+            }
+            if params[..i].contains(p) {
+                return Err(format!("duplicate parameter '{}'", p));
+            }
+        }
+        let id = self.symbol_table.define_function(name, params.len());
+        self.symbol_table.bind_params(params);
+        let result = self.compile_body();
+        self.symbol_table.bind_params(&[]);
+        Ok((id, result?))
+    }
+
+    fn compile_body(&mut self) -> Result<CompileResult, String> {
         let mut output_queue: Vec<Op> = Vec::new();
         let mut op_stack: Vec<(Token, Precedence)> = Vec::new(); // 存操作符和优先级
         let mut dependencies: Vec<usize> = Vec::new();
+        // 三元表达式待回填的跳转指令位置，与 op_stack 中的 ? / : 一一对应
+        let mut pending_jumps: Vec<usize> = Vec::new();
+        // 嵌套的函数调用，与 op_stack 中优先级为 Call 的 '(' 一一对应
+        let mut calls: Vec<CallFrame> = Vec::new();
 
-        let mut token = self.lexer.next_token();
+        let mut token = self.lexer.next_token()?;
 
         // 简单的状态机，用于区分一元减号和减法
         let mut expect_operand = true;
 
         while token != Token::EOF {
+            // 隐式乘法：上一个 token 结束了一个操作数 (数字、标识符、')'、闭合的 '|')，
+            // 当前 token 又开始一个操作数 (标识符、'('、开启的 '|')，中间补一个 '*'
+            // 与显式的 '*' 同为 Product 优先级：2x^2 = 2·(x^2)，x^2y = (x^2)·y，3sin(x) = 3·sin(x)
+            // 操作数之后紧跟数字 (如 "2 3"、"x 2") 不补，仍是语法错误
+            if !expect_operand && Self::starts_operand(&token, &op_stack) {
+                self.push_operator(Token::Star, Precedence::Product, &mut op_stack, &mut output_queue);
+            }
             match token {
                 Token::Number(val) => {
                     output_queue.push(Op::Push(MathData::Num(val)));
@@ -54,10 +117,65 @@ impl<'a> Compiler<'a> {
                     // 注意：这里的 Lexer 实现比较简单，实际上可能需要 peek
                     // 假设我们在 identifier 后如果遇到 LParen 则是函数
 
-                    // 暂时简化：如果是内置函数（如 sin），生成 Op::Sin (如果不只是 CallDef)
+                    // 保留字常量 (如虚数单位 i) 直接压入常量，不进入符号表
+                    if let Some(value) = SymbolTable::reserved_constant(name) {
+                        output_queue.push(Op::Push(value));
+                        expect_operand = false;
+                        token = self.lexer.next_token()?;
+                        continue;
+                    }
+
+                    // 函数体的形参
+                    if let Some(index) = self.symbol_table.param_index(name) {
+                        output_queue.push(Op::LoadPara(index));
+                        expect_operand = false;
+                        token = self.lexer.next_token()?;
+                        continue;
+                    }
+
+                    // 内置函数 (sin、abs 等) 后跟 '('：函数名压栈，对应的 ')' 处输出指令
+                    // 同名的具名函数优先；不跟 '(' 时按普通全局量处理
+                    let user_function = self.symbol_table.get_id(name)
+                        .is_some_and(|id| self.symbol_table.function_arity(id).is_some());
+                    if !user_function && Self::builtin_op(name).is_some() {
+                        let next = self.lexer.next_token()?;
+                        if next == Token::LParen {
+                            op_stack.push((token.clone(), Precedence::Call));
+                            op_stack.push((Token::LParen, Precedence::Lowest));
+                            expect_operand = true;
+                            token = self.lexer.next_token()?;
+                        } else {
+                            let id = self.symbol_table.get_or_create_id(name);
+                            output_queue.push(Op::LoadGlobal(id));
+                            dependencies.push(id);
+                            expect_operand = false;
+                            token = next;
+                        }
+                        continue;
+                    }
+
                     // 如果是普通变量：
                     let id = self.symbol_table.get_or_create_id(name);
 
+                    // 具名函数后跟 '('：调用，实参在 ')' 处收集为 CallDef
+                    // 不跟 '(' 时按普通全局量处理 (函数本身作为值)
+                    // 歧义 x(x + 1)：x 在符号表中登记为函数时是调用，否则是隐式乘法 x·(x + 1)
+                    if let Some(arity) = self.symbol_table.function_arity(id) {
+                        let next = self.lexer.next_token()?;
+                        dependencies.push(id);
+                        if next == Token::LParen {
+                            calls.push(CallFrame { id, arity, arg_starts: vec![output_queue.len()] });
+                            op_stack.push((Token::LParen, Precedence::Call));
+                            expect_operand = true;
+                            token = self.lexer.next_token()?;
+                        } else {
+                            output_queue.push(Op::LoadGlobal(id));
+                            expect_operand = false;
+                            token = next;
+                        }
+                        continue;
+                    }
+
                     // 这里有一个歧义处理：Desmos 中 f(x) 是调用，x*y 是乘法
                     // 我们简化处理：如果是标识符，先当做 LoadGlobal
                     // 如果后面跟着 '('，Shunting Yard 的逻辑会处理成 Call
@@ -84,24 +202,38 @@ impl<'a> Compiler<'a> {
                     dependencies.push(id);
                     expect_operand = false;
                 }
-                Token::Plus | Token::Minus | Token::Star | Token::Slash | Token::Caret => {
+                Token::Plus | Token::Minus | Token::Star | Token::Slash | Token::Caret
+                | Token::Greater | Token::Less | Token::EqualEqual => {
                     let curr_prec = self.get_precedence(&token, expect_operand);
 
                     // 处理一元运算符 (-5)
                     // 如果是 Minus 且 expect_operand 为 true，这是一元负号
                     // 可以将其视为特殊操作符，或者 0 - x
 
-                    while let Some((top_op, top_prec)) = op_stack.last() {
-                        if top_op == &Token::LParen {
-                            break;
-                        }
-                        if *top_prec >= curr_prec {
-                            self.pop_op_to_queue(op_stack.pop().unwrap().0, &mut output_queue);
-                        } else {
-                            break;
-                        }
+                    self.push_operator(token.clone(), curr_prec, &mut op_stack, &mut output_queue);
+                    expect_operand = true;
+                }
+                // cond ? a : b 编译为
+                //   cond, JumpIfZero(len(a) + 1), a, Goto(len(b)), b
+                Token::Question => {
+                    self.flush_until_ternary(&mut op_stack, &mut output_queue, &mut pending_jumps, false)?;
+                    pending_jumps.push(output_queue.len());
+                    output_queue.push(Op::JumpIfZero(0)); // 遇到 ':' 时回填
+                    op_stack.push((Token::Question, Precedence::Ternary));
+                    expect_operand = true;
+                }
+                Token::Colon => {
+                    // 弹出 then 分支内的运算符，直到对应的 '?'
+                    self.flush_until_ternary(&mut op_stack, &mut output_queue, &mut pending_jumps, true)?;
+                    match op_stack.pop() {
+                        Some((Token::Question, _)) => {}
+                        _ => return Err("':' without matching '?'".to_string()),
                     }
-                    op_stack.push((token.clone(), curr_prec));
+                    let jump = pending_jumps.pop().unwrap();
+                    pending_jumps.push(output_queue.len());
+                    output_queue.push(Op::Goto(0)); // else 分支结束时回填
+                    output_queue[jump] = Op::JumpIfZero((output_queue.len() - jump - 1) as i32);
+                    op_stack.push((Token::Colon, Precedence::Ternary));
                     expect_operand = true;
                 }
                 Token::LParen => {
@@ -109,48 +241,113 @@ impl<'a> Compiler<'a> {
                     expect_operand = true;
                 }
                 Token::RParen => {
-                    let mut found_paren = false;
-                    while let Some((op, _)) = op_stack.pop() {
+                    let mut found_paren = None;
+                    while let Some((op, prec)) = op_stack.pop() {
                         if op == Token::LParen {
-                            found_paren = true;
+                            found_paren = Some(prec);
                             break;
                         }
-                        self.pop_op_to_queue(op, &mut output_queue);
+                        self.close_or_pop(op, &mut output_queue, &mut pending_jumps)?;
                     }
-                    if !found_paren {
-                        panic!("Mismatched parentheses");
+                    match found_paren {
+                        None => return Err("Mismatched parentheses".to_string()),
+                        // 函数调用的 ')'：把各实参从输出队列中切出，换成一条 CallDef
+                        Some(Precedence::Call) => {
+                            let frame = calls.pop().unwrap();
+                            let args = self.split_args(&mut output_queue, &frame)?;
+                            if args.len() != frame.arity {
+                                let name = self.symbol_table.name_of(frame.id).unwrap_or("?");
+                                return Err(format!("function '{}' expects {} argument(s), got {}", name, frame.arity, args.len()));
+                            }
+                            output_queue.push(Op::CallDef(frame.id, args));
+                        }
+                        // 内置函数的 ')'：输出栈中紧挨着 '(' 的函数名
+                        Some(_) => {
+                            if let Some((Token::Identifier(_), Precedence::Call)) = op_stack.last() {
+                                let name = op_stack.pop().unwrap().0;
+                                self.pop_op_to_queue(name, &mut output_queue);
+                            }
+                        }
                     }
-
-                    // 如果栈顶是函数，也要弹出函数并加入 Apply 指令
-                    // (当前简化版暂未实现函数名入栈，视所有 ident 为变量)
                     expect_operand = false;
                 }
+                // |expr| 即 abs(expr)：开启的 '|' 相当于 '('，闭合时输出 AbsC
+                // 期待操作数时总是开启；否则最内层未闭合的括号是 '|' 时闭合，不是时开启 (如 2|x|，已补乘号)
+                // 所以 ||x| - 1| 与 |x||y| 都按直觉解析
+                Token::Bar => {
+                    if expect_operand || !Self::innermost_is_bar(&op_stack) {
+                        op_stack.push((Token::Bar, Precedence::Lowest));
+                        expect_operand = true;
+                    } else {
+                        while let Some((op, _)) = op_stack.pop() {
+                            if op == Token::Bar {
+                                break;
+                            }
+                            self.close_or_pop(op, &mut output_queue, &mut pending_jumps)?;
+                        }
+                        output_queue.push(Op::AbsC);
+                        expect_operand = false;
+                    }
+                }
                 Token::Comma => {
                     // 函数参数分隔符
                     while let Some((top_op, _)) = op_stack.last() {
                         if top_op == &Token::LParen {
                             break;
                         }
-                        self.pop_op_to_queue(op_stack.pop().unwrap().0, &mut output_queue);
+                        let op = op_stack.pop().unwrap().0;
+                        self.close_or_pop(op, &mut output_queue, &mut pending_jumps)?;
+                    }
+                    if let Some((Token::LParen, Precedence::Call)) = op_stack.last() {
+                        calls.last_mut().unwrap().arg_starts.push(output_queue.len());
                     }
                     expect_operand = true;
                 }
                 _ => {}
             }
-            token = self.lexer.next_token();
+            token = self.lexer.next_token()?;
         }
 
         while let Some((op, _)) = op_stack.pop() {
             if op == Token::LParen {
-                panic!("Mismatched parentheses");
+                return Err("Mismatched parentheses".to_string());
             }
-            self.pop_op_to_queue(op, &mut output_queue);
+            self.close_or_pop(op, &mut output_queue, &mut pending_jumps)?;
         }
 
-        CompileResult {
-            ops: output_queue,
+        // 括号都配对了栈形状仍可能不对 (如 "1 +"、"2 3")，在这里报编译错误而不是留到求值时
+        // 下标范围由 Env 决定，这里只查栈形状
+        let rpn = RPN::new_checked(output_queue, usize::MAX, usize::MAX)
+            .map_err(|e| format!("Malformed expression: {}", e))?;
+
+        Ok(CompileResult {
+            ops: rpn.into_ops(),
             dependencies,
+        })
+    }
+
+    // 输出队列 arg_starts[0] 之后的部分按各实参起点切开 (f() 没有实参)
+    // 跳转偏移是相对的，实参内的三元式切出后依然有效
+    // 每个实参单独检查栈形状：f(,)、g(1 +) 这类空的或残缺的实参是编译错误
+    fn split_args(&self, queue: &mut Vec<Op>, frame: &CallFrame) -> Result<Vec<RPN>, String> {
+        let arg_starts = &frame.arg_starts;
+        let tail = queue.split_off(arg_starts[0]);
+        if tail.is_empty() && arg_starts.len() == 1 {
+            return Ok(Vec::new());
+        }
+        let mut pieces = Vec::with_capacity(arg_starts.len());
+        let mut rest = tail;
+        for w in arg_starts.windows(2).rev() {
+            pieces.push(rest.split_off(w[1] - arg_starts[0]));
         }
+        pieces.push(rest);
+        pieces.reverse();
+        pieces.into_iter().enumerate().map(|(i, ops)| {
+            RPN::new_checked(ops, usize::MAX, usize::MAX).map_err(|e| {
+                let name = self.symbol_table.name_of(frame.id).unwrap_or("?");
+                format!("Malformed argument {} of '{}': {}", i + 1, name, e)
+            })
+        }).collect()
     }
 
     fn get_precedence(&self, token: &Token, is_unary: bool) -> Precedence {
@@ -164,19 +361,293 @@ impl<'a> Compiler<'a> {
             }
             Token::Star | Token::Slash => Precedence::Product,
             Token::Caret => Precedence::Power,
+            Token::Greater | Token::Less | Token::EqualEqual => Precedence::Comparison,
+            Token::Question | Token::Colon => Precedence::Ternary,
             Token::LParen => Precedence::Call, // 函数调用优先级最高
             _ => Precedence::Lowest,
         }
     }
 
+    // 内置的单参数函数
+    fn builtin_op(name: &str) -> Option<Op> {
+        Some(match name {
+            "sin" => Op::Sin,
+            "cos" => Op::Cos,
+            "tan" => Op::Tan,
+            "exp" => Op::Exp,
+            "ln" => Op::Ln,
+            "sqrt" => Op::Sqrt,
+            "abs" => Op::AbsC,
+            _ => return None,
+        })
+    }
+
+    // 当前 token 是否开始一个新的操作数 (决定是否补隐式乘号)
+    fn starts_operand(token: &Token, op_stack: &[(Token, Precedence)]) -> bool {
+        match token {
+            Token::Identifier(_) | Token::LParen => true,
+            Token::Bar => !Self::innermost_is_bar(op_stack),
+            _ => false,
+        }
+    }
+
+    // 最内层未闭合的分组是否为 '|' (而不是 '(')
+    fn innermost_is_bar(op_stack: &[(Token, Precedence)]) -> bool {
+        op_stack.iter().rev()
+            .find(|(t, _)| matches!(t, Token::LParen | Token::Bar))
+            .is_some_and(|(t, _)| *t == Token::Bar)
+    }
+
+    // 运算符入栈前先弹出栈顶优先级不低于它的运算符；'^' 右结合，只弹出优先级更高的
+    fn push_operator(&self, token: Token, prec: Precedence, op_stack: &mut Vec<(Token, Precedence)>, queue: &mut Vec<Op>) {
+        while let Some((top_op, top_prec)) = op_stack.last() {
+            if matches!(top_op, Token::LParen | Token::Bar) || Self::is_ternary(top_op) {
+                break;
+            }
+            let pops = if token == Token::Caret { *top_prec > prec } else { *top_prec >= prec };
+            if !pops {
+                break;
+            }
+            self.pop_op_to_queue(op_stack.pop().unwrap().0, queue);
+        }
+        op_stack.push((token, prec));
+    }
+
+    fn is_ternary(token: &Token) -> bool {
+        matches!(token, Token::Question | Token::Colon)
+    }
+
+    // 弹出运算符直到 '(' 或三元标记
+    // stop_at_question = false (遇到新的 '?')：已完成的 ':' 分支一并收尾 (右结合)
+    // stop_at_question = true (遇到 ':')：只收尾内层完整的三元式，停在对应的 '?'
+    fn flush_until_ternary(
+        &self,
+        op_stack: &mut Vec<(Token, Precedence)>,
+        queue: &mut Vec<Op>,
+        pending_jumps: &mut Vec<usize>,
+        stop_at_question: bool,
+    ) -> Result<(), String> {
+        while let Some((top_op, _)) = op_stack.last() {
+            match top_op {
+                Token::LParen | Token::Bar => break,
+                Token::Question => break,
+                Token::Colon if !stop_at_question => break,
+                _ => {}
+            }
+            let op = op_stack.pop().unwrap().0;
+            self.close_or_pop(op, queue, pending_jumps)?;
+        }
+        Ok(())
+    }
+
+    // ':' 出栈意味着 else 分支结束：回填 Goto；其余运算符照常输出
+    fn close_or_pop(&self, token: Token, queue: &mut Vec<Op>, pending_jumps: &mut Vec<usize>) -> Result<(), String> {
+        match token {
+            Token::Colon => {
+                let goto = pending_jumps.pop().unwrap();
+                queue[goto] = Op::Goto((queue.len() - goto - 1) as i32);
+            }
+            Token::Question => return Err("'?' without matching ':'".to_string()),
+            Token::Bar => return Err("Mismatched '|'".to_string()),
+            _ => self.pop_op_to_queue(token, queue),
+        }
+        Ok(())
+    }
+
     fn pop_op_to_queue(&self, token: Token, queue: &mut Vec<Op>) {
         match token {
             Token::Plus => queue.push(Op::Add),
             Token::Minus => queue.push(Op::Sub),
             Token::Star => queue.push(Op::Mul),
             Token::Slash => queue.push(Op::Div),
-            // 注意：Power 等需要自行实现 Op::Pow
+            Token::Greater => queue.push(Op::GreaterThan),
+            Token::Less => queue.push(Op::LessThan),
+            Token::EqualEqual => queue.push(Op::Equal),
+            Token::Caret => queue.push(Op::Pow),
+            // 内置函数名 (见 builtin_op)
+            Token::Identifier(name) => queue.extend(Self::builtin_op(&name)),
             _ => {}
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pakoo::env::Env;
+    use crate::pakoo::slice::Slice;
+
+    fn num(data: Option<&MathData>) -> f64 {
+        match data {
+            Some(MathData::Num(v)) => *v,
+            other => panic!("expected a number, got {:?}", other),
+        }
+    }
+
+    // Env 先按符号表对齐槽位再添加：函数为 Def，其余为表达式
+    fn define(env: &mut Env, table: &mut SymbolTable, name: &str, params: &[&str], body: &str) {
+        let (_, result) = Compiler::new(body, table).compile_function_def(name, params);
+        env.sync_with(table);
+        env.add_named_slice(name, Slice::Def { para_count: params.len(), body: RPN::new(result.ops) });
+    }
+
+    fn assign(env: &mut Env, table: &mut SymbolTable, name: &str, expr: &str) {
+        let result = Compiler::new(expr, table).compile();
+        table.get_or_create_id(name);
+        env.sync_with(table);
+        env.add_named_slice(name, Slice::Call { body: RPN::new(result.ops) });
+    }
+
+    #[test]
+    fn test_function_def_params() {
+        let mut table = SymbolTable::new();
+        let (id, result) = Compiler::new("x * x + 1", &mut table).compile_function_def("f", &["x"]);
+        assert_eq!(id, 0);
+        assert_eq!(table.function_arity(id), Some(1));
+        // 形参编译为 LoadPara，不产生全局依赖，也不进入符号表
+        assert!(matches!(result.ops[..], [Op::LoadPara(0), Op::LoadPara(0), Op::Mul, Op::Push(_), Op::Add]));
+        assert!(result.dependencies.is_empty());
+        assert_eq!(table.get_id("x"), None);
+
+        // 形参优先于同名全局量；函数体外 x 是全局变量
+        let a = table.get_or_create_id("a");
+        let (_, result) = Compiler::new("y - a", &mut table).compile_function_def("g", &["x", "y"]);
+        assert!(matches!(result.ops[..], [Op::LoadPara(1), Op::LoadGlobal(id), Op::Sub] if id == a));
+        let result = Compiler::new("x", &mut table).compile();
+        assert!(matches!(result.ops[..], [Op::LoadGlobal(id)] if Some(id) == table.get_id("x")));
+
+        // 不跟括号的函数名是普通全局量
+        let result = Compiler::new("f", &mut table).compile();
+        assert!(matches!(result.ops[..], [Op::LoadGlobal(0)]));
+    }
+
+    #[test]
+    fn test_define_then_call() {
+        let mut table = SymbolTable::new();
+        let mut env = Env::new();
+        define(&mut env, &mut table, "f", &["x"], "x * x + 1");
+        assign(&mut env, &mut table, "a", "3");
+        define(&mut env, &mut table, "g", &["x", "y"], "x - y * a");
+        // 递归 + 三元式
+        define(&mut env, &mut table, "fact", &["n"], "n < 1 ? 1 : n * fact(n - 1)");
+        // 嵌套调用、实参中的三元式与运算
+        assign(&mut env, &mut table, "b", "f(a) + g(a > 1 ? 10 : 20, f(1) - 1)");
+        assign(&mut env, &mut table, "c", "fact(5) + f(f(2))");
+
+        let result = Compiler::new("f(a) + g(a > 1 ? 10 : 20, f(1) - 1)", &mut table).compile();
+        for name in ["f", "a", "g"] {
+            assert!(result.dependencies.contains(&table.get_id(name).unwrap()));
+        }
+
+        env.update();
+        // f(3) + g(10, 1) = 10 + (10 - 3)
+        assert_eq!(num(env.get_by_name("b")), 17.0);
+        // 120 + f(5)
+        assert_eq!(num(env.get_by_name("c")), 146.0);
+    }
+
+    #[test]
+    fn test_zero_arity() {
+        let mut table = SymbolTable::new();
+        let mut env = Env::new();
+        define(&mut env, &mut table, "two", &[], "1 + 1");
+        assign(&mut env, &mut table, "r", "two() * 3");
+        env.update();
+        assert_eq!(num(env.get_by_name("r")), 6.0);
+    }
+
+    #[test]
+    fn test_try_compile_errors() {
+        let mut table = SymbolTable::new();
+        Compiler::new("x + y", &mut table).compile_function_def("g", &["x", "y"]);
+        assert_eq!(Compiler::new("g(1)", &mut table).try_compile().err().as_deref(),
+                   Some("function 'g' expects 2 argument(s), got 1"));
+        assert_eq!(Compiler::new("x", &mut table).try_compile_function_def("e", &["x"]).err().as_deref(),
+                   Some("'e' is a reserved identifier"));
+        assert_eq!(Compiler::new("x", &mut table).try_compile_function_def("h", &["x", "pi"]).err().as_deref(),
+                   Some("'pi' is a reserved identifier"));
+        // 函数体出错后形参解除绑定：之后的 x 是全局量
+        assert!(Compiler::new("x +", &mut table).try_compile_function_def("h", &["x"]).is_err());
+        let result = Compiler::new("x", &mut table).try_compile().unwrap();
+        assert!(matches!(result.ops[..], [Op::LoadGlobal(_)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "function 'g' expects 2 argument(s), got 1")]
+    fn test_wrong_arity() {
+        let mut table = SymbolTable::new();
+        Compiler::new("x + y", &mut table).compile_function_def("g", &["x", "y"]);
+        Compiler::new("g(1)", &mut table).compile();
+    }
+
+    #[test]
+    #[should_panic(expected = "Malformed argument 1 of 'f'")]
+    fn test_empty_argument() {
+        let mut table = SymbolTable::new();
+        Compiler::new("x", &mut table).compile_function_def("f", &["x", "y"]);
+        Compiler::new("f(,)", &mut table).compile();
+    }
+
+    #[test]
+    #[should_panic(expected = "Malformed argument 2 of 'g': missing operand")]
+    fn test_incomplete_argument() {
+        let mut table = SymbolTable::new();
+        Compiler::new("x + y", &mut table).compile_function_def("g", &["x", "y"]);
+        Compiler::new("g(1, 1 +)", &mut table).compile();
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate parameter 'x'")]
+    fn test_duplicate_param() {
+        let mut table = SymbolTable::new();
+        Compiler::new("x", &mut table).compile_function_def("f", &["x", "x"]);
+    }
+
+    #[test]
+    fn test_implicit_mul_constants_abs() {
+        use std::f64::consts::{E, PI};
+        let mut table = SymbolTable::new();
+        let mut env = Env::new();
+        assign(&mut env, &mut table, "x", "2");
+        define(&mut env, &mut table, "f", &["t"], "t * 10");
+        // 表达式，期望值 (x = 2)
+        let cases: [(&str, f64); 14] = [
+            ("2x + 3sin(x)", 4.0 + 3.0 * 2f64.sin()),
+            ("2x^2", 8.0),
+            ("2^3^2", 512.0),
+            ("x(x + 1)", 6.0),
+            ("(x + 1)(x - 1)", 3.0),
+            ("f(x + 1)", 30.0),
+            ("pi", PI),
+            ("2pi x", 4.0 * PI),
+            ("e^1", E),
+            ("|x - 5|", 3.0),
+            ("2|x - 5|", 6.0),
+            ("||1 - 3| - 5|", 3.0),
+            ("|x||x - 3|", 2.0),
+            ("3sqrt(4) + abs(1 - x)", 7.0),
+        ];
+        for (i, (expr, _)) in cases.iter().enumerate() {
+            assign(&mut env, &mut table, &format!("r{}", i), expr);
+        }
+        env.update();
+        for (i, (expr, expected)) in cases.iter().enumerate() {
+            let got = num(env.get_by_name(&format!("r{}", i)));
+            assert!((got - expected).abs() < 1e-12, "{}: got {}, expected {}", expr, got, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Malformed expression: missing operand")]
+    fn test_missing_operand() {
+        let mut table = SymbolTable::new();
+        Compiler::new("1 +", &mut table).compile();
+    }
+
+    #[test]
+    #[should_panic(expected = "Mismatched '|'")]
+    fn test_unclosed_bar() {
+        let mut table = SymbolTable::new();
+        Compiler::new("|x + 1", &mut table).compile();
+    }
+}
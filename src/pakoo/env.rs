@@ -1,12 +1,49 @@
-use super::math_data::MathData;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use rayon::prelude::*;
+
+use super::math_data::{MathData, TypeError};
 use super::op::Op;
+use crate::math_forest::algebra::dual::dual_num::Dual;
+use super::persist;
 use super::rpn::RPN;
-use super::slice::Slice;
+use super::slice::{param_list, Slice};
+use super::symbol_table::SymbolTable;
+
+// serialize 文本格式的版本，首行为 "PAKOO-ENV <版本>"
+const FORMAT_HEADER: &str = "PAKOO-ENV";
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnvError {
+    UnknownOp(String), // 无法识别的指令名
+    InvalidData,       // 行格式、数据或指令参数不合法
+    VersionMismatch,   // 缺少文件头或版本不一致
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvError::UnknownOp(name) => write!(f, "unknown op '{}'", name),
+            EnvError::InvalidData => write!(f, "invalid env data"),
+            EnvError::VersionMismatch => write!(f, "env format version mismatch (expected {} {})", FORMAT_HEADER, FORMAT_VERSION),
+        }
+    }
+}
+
+impl std::error::Error for EnvError {}
 
 #[allow(dead_code)]
 pub struct Env {
     slice: Vec<Slice>,
     pub data: Vec<MathData>,
+    // 名字 -> slice 下标
+    // Op::LoadGlobal / Op::CallDef 直接按下标取值；表达式由编译器按 SymbolTable 的 id 生成，
+    // 添加前先 sync_with 同一个符号表，使下标与 id 一致
+    names: HashMap<String, usize>,
+    // slice 下标 -> 名字 (匿名 slice 为 None)，用于 fmt
+    slice_names: Vec<Option<String>>,
 }
 
 #[allow(dead_code)]
@@ -15,11 +52,75 @@ impl Env {
         Self {
             slice: Vec::new(),
             data: Vec::new(),
+            names: HashMap::new(),
+            slice_names: Vec::new(),
         }
     }
 
     pub fn add_slice(&mut self, slice: Slice) {
         self.slice.push(slice);
+        self.slice_names.push(None);
+    }
+
+    // 添加具名 slice，返回其下标 (先 sync_with 过的名字即其全局 id)
+    // 重名时原位替换，下标不变，依赖它的 slice 仍然指向同一位置
+    pub fn add_named_slice(&mut self, name: &str, slice: Slice) -> usize {
+        if let Some(&index) = self.names.get(name) {
+            self.slice[index] = slice;
+            return index;
+        }
+        let index = self.slice.len();
+        self.slice.push(slice);
+        self.slice_names.push(Some(name.to_string()));
+        self.names.insert(name.to_string(), index);
+        index
+    }
+
+    // 按符号表的全局 id 排列槽位：符号表中 id 为 k 的名字移到下标 k (Env 中还没有的以 Var(None) 占位)
+    // 其余 slice (匿名的、符号表中没有的名字) 按原顺序排在后面
+    // 之后 add_named_slice 对符号表中已有的名字返回的下标即其 id
+    pub fn sync_with(&mut self, table: &SymbolTable) {
+        let keep_data = self.data.len() == self.slice.len();
+        let mut old: Vec<Option<(Slice, Option<String>, MathData)>> = std::mem::take(&mut self.slice).into_iter()
+            .zip(std::mem::take(&mut self.slice_names))
+            .zip(std::mem::take(&mut self.data).into_iter().chain(std::iter::repeat_with(MathData::default)))
+            .map(|((slice, name), data)| Some((slice, name, data)))
+            .collect();
+
+        let mut entries = Vec::with_capacity(old.len());
+        let mut id = 0;
+        while let Some(name) = table.name_of(id) {
+            let entry = self.names.get(name).and_then(|&i| old[i].take())
+                .unwrap_or_else(|| (Slice::Var { data: MathData::None }, Some(name.to_string()), MathData::default()));
+            entries.push(entry);
+            id += 1;
+        }
+        entries.extend(old.into_iter().flatten());
+
+        self.names.clear();
+        for (index, (slice, name, data)) in entries.into_iter().enumerate() {
+            if let Some(name) = &name {
+                self.names.insert(name.clone(), index);
+            }
+            self.slice.push(slice);
+            self.slice_names.push(name);
+            if keep_data {
+                self.data.push(data);
+            }
+        }
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    pub fn name_of(&self, index: usize) -> Option<&str> {
+        self.slice_names.get(index).and_then(|n| n.as_deref())
+    }
+
+    // 按名字取计算结果 (需先 update)
+    pub fn get_by_name(&self, name: &str) -> Option<&MathData> {
+        self.index_of(name).and_then(|i| self.data.get(i))
     }
 
     pub fn get_slice(&self, index: usize) -> &Slice {
@@ -40,32 +141,229 @@ impl Env {
             self.data.resize(self.slice.len(), MathData::default());
         }
 
+        self.update_range(0, self.slice.len());
+
+        self.data.last().expect("Data should not be empty").clone()
+    }
+
+    // 同 update，求值出错时返回错误而不是 panic (出错之前的槽位已更新)
+    pub fn try_update(&mut self) -> Result<(), TypeError> {
+        if self.data.len() < self.slice.len() {
+            self.data.resize(self.slice.len(), MathData::default());
+        }
         for i in 0..self.slice.len() {
-            // 直接覆盖，不要 push
-            self.data[i] = self.slice[i].eval(&self.data);
+            self.data[i] = self.slice[i].try_eval(&self.data)?;
+        }
+        Ok(())
+    }
+
+    // 只重新计算第 index 个 slice，覆盖 data[index] 并返回新值
+    // 依赖的前序 slice 不会重算，调用方需保证它们已是最新 (至少 update 过一次)
+    pub fn update_at(&mut self, index: usize) -> MathData {
+        assert!(
+            index < self.data.len(),
+            "Env::update_at: index {} out of range, data has {} entries (call update() first)",
+            index,
+            self.data.len()
+        );
+        // 直接覆盖，不要 push
+        self.data[index] = self.slice[index].eval(&self.data);
+        self.data[index].clone()
+    }
+
+    // 按顺序重新计算 [start, end) 内的 slice
+    pub fn update_range(&mut self, start: usize, end: usize) {
+        for i in start..end {
+            self.update_at(i);
+        }
+    }
+
+    // 并行版 update，结果与 update 相同
+    // 按依赖分层 (见 levels)，同一层的 slice 互不读写对方的槽位，用 rayon 并行求值后再统一写回
+    // 大量互相独立的定义 (如 100+ 个函数) 几乎全部落在同一层
+    pub fn update_parallel(&mut self) -> MathData {
+        if self.data.len() < self.slice.len() {
+            self.data.resize(self.slice.len(), MathData::default());
+        }
+
+        for level in self.levels() {
+            let (slice, data) = (&self.slice, &self.data);
+            let values: Vec<MathData> = level.par_iter().map(|&i| slice[i].eval(data)).collect();
+            for (i, value) in level.into_iter().zip(values) {
+                self.data[i] = value;
+            }
         }
 
         self.data.last().expect("Data should not be empty").clone()
     }
 
+    // 依赖 DAG 的拓扑分层：返回每层的 slice 下标 (层内升序)
+    // 顺序 update 中 slice i 读到的 data[r]：r < i 为本轮新值，r > i 为上一轮旧值
+    // 两种情况都要求下标小的先算 (r < i：r 先写；r > i：i 先读)，所以边总是从小下标指向大下标，必然无环
+    fn levels(&self) -> Vec<Vec<usize>> {
+        let n = self.slice.len();
+        // min_level[r]：读取旧值的 slice 所在层 + 1，r 不能早于这一层
+        let mut min_level = vec![0usize; n];
+        let mut levels: Vec<Vec<usize>> = Vec::new();
+        let mut level_of = vec![0usize; n];
+        for i in 0..n {
+            let reads = self.reads_of(i);
+            let level = reads.iter().filter(|&&r| r < i).map(|&r| level_of[r] + 1).fold(min_level[i], usize::max);
+            for &r in reads.iter().filter(|&&r| r > i) {
+                min_level[r] = min_level[r].max(level + 1);
+            }
+            level_of[i] = level;
+            if levels.len() <= level {
+                levels.resize(level + 1, Vec::new());
+            }
+            levels[level].push(i);
+        }
+        levels
+    }
+
+    // slice 求值时会读取的全局下标 (去重)
+    // 只有 Call 读取 data；经 CallDef 调用的函数体在调用时读取的全局量也算在内 (递归展开)
+    fn reads_of(&self, index: usize) -> Vec<usize> {
+        let Slice::Call { body } = &self.slice[index] else { return Vec::new() };
+        let mut pending = Vec::new();
+        collect_globals(body.ops(), &mut pending);
+        let mut seen = HashSet::new();
+        while let Some(g) = pending.pop() {
+            // 越界的引用在求值时自会报错，这里忽略
+            if g >= self.slice.len() || !seen.insert(g) {
+                continue;
+            }
+            if let Slice::Def { body, .. } = &self.slice[g] {
+                collect_globals(body.ops(), &mut pending);
+            }
+        }
+        seen.into_iter().collect()
+    }
+
+    // 函数定义 (Def) 关于第 wrt_param 个参数的导函数 (前向自动微分)
+    // 返回的闭包接收全部实参，给出该点的偏导数；函数体引用的全局量取当前 data (需先 update)
+    // slice 不是 Def 或 wrt_param 越界时返回 None
+    pub fn derivative_of(&self, slice_index: usize, wrt_param: usize) -> Option<impl Fn(&[f64]) -> f64 + '_> {
+        let Some(Slice::Def { para_count, body }) = self.slice.get(slice_index) else { return None };
+        if wrt_param >= *para_count { return None; }
+        Some(move |args: &[f64]| {
+            let duals: Vec<Dual> = args.iter().enumerate()
+                .map(|(i, &x)| if i == wrt_param { Dual::variable(x) } else { Dual::constant(x) })
+                .collect();
+            body.eval_dual(&self.data, &duals).eps
+        })
+    }
+
+    // 调试输出：slice 按中缀形式写成定义式，左边的 g_i / f_i 与表达式中引用它们的记法一致
+    //   [1] f: f_1(p_0) = (p_0 + 2.0)
+    //   [2] g_2 = (f_1(2.0) + 2.0)
     pub fn fmt(&self) -> String {
         let mut s = String::new();
         s.push_str("--slice:\n");
-        for i in 0..self.slice.len() {
-            s.push_str(&format!("{:?}\n", self.slice[i]));
+        for (i, slice) in self.slice.iter().enumerate() {
+            let line = match slice {
+                Slice::Def { para_count, body } => format!("f_{}({}) = {}", i, param_list(*para_count), body.to_infix_string()),
+                _ => format!("g_{} = {}", i, slice),
+            };
+            s.push_str(&format!("[{}] {}{}\n", i, self.label(i), line));
         }
         s.push_str("--data:\n");
         for i in 0..self.data.len() {
-            s.push_str(&format!("{:?}\n", self.data[i]));
+            s.push_str(&format!("[{}] {}{:?}\n", i, self.label(i), self.data[i]));
+        }
+        s
+    }
+
+    // 保存为文本：首行版本号，之后每个 slice 一行
+    //   VAR <data>            数据为 MathData 的 Debug 文本
+    //   DEF <para_count> <ops...>
+    //   CALL <ops...>         ops 为各条 Op 的 Debug 文本，空格分隔
+    // 具名 slice 的关键字带后缀 ":name"，如 "DEF:f 1 LoadPara(0) ..."
+    // 只保存 slice，不保存计算结果；读回后需重新 update
+    pub fn serialize(&self) -> String {
+        let ops = |rpn: &RPN| rpn.ops().iter().map(|op| format!("{:?}", op)).collect::<Vec<_>>().join(" ");
+        let mut s = format!("{} {}\n", FORMAT_HEADER, FORMAT_VERSION);
+        for (i, slice) in self.slice.iter().enumerate() {
+            let (keyword, rest) = match slice {
+                Slice::Var { data } => ("VAR", format!("{:?}", data)),
+                Slice::Def { para_count, body } => ("DEF", format!("{} {}", para_count, ops(body))),
+                Slice::Call { body } => ("CALL", ops(body)),
+            };
+            match self.name_of(i) {
+                Some(name) => s.push_str(&format!("{}:{} {}\n", keyword, name, rest)),
+                None => s.push_str(&format!("{} {}\n", keyword, rest)),
+            }
         }
         s
     }
+
+    // 从 serialize 的文本恢复 (空行忽略)
+    pub fn deserialize(s: &str) -> Result<Env, EnvError> {
+        let mut lines = s.lines().map(str::trim).filter(|l| !l.is_empty());
+        let header = lines.next().ok_or(EnvError::VersionMismatch)?;
+        match header.split_once(' ') {
+            Some((FORMAT_HEADER, version)) if version.trim().parse() == Ok(FORMAT_VERSION) => {}
+            _ => return Err(EnvError::VersionMismatch),
+        }
+
+        let checked = |ops, max_para| RPN::new_checked(ops, max_para, usize::MAX).map_err(|_| EnvError::InvalidData);
+        let mut env = Env::new();
+        for line in lines {
+            let (head, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let (keyword, name) = match head.split_once(':') {
+                Some((k, n)) if !n.is_empty() => (k, Some(n)),
+                Some(_) => return Err(EnvError::InvalidData),
+                None => (head, None),
+            };
+            let slice = match keyword {
+                "VAR" => Slice::Var { data: persist::parse_data(rest)? },
+                // 函数体在读入时检查栈形状与参数下标；全局可能引用后面的 slice，不限制
+                "CALL" => Slice::Call { body: checked(persist::parse_ops(rest)?, 0)? },
+                "DEF" => {
+                    let (count, ops) = rest.trim_start().split_once(' ').unwrap_or((rest.trim(), ""));
+                    let para_count = count.parse().map_err(|_| EnvError::InvalidData)?;
+                    Slice::Def { para_count, body: checked(persist::parse_ops(ops)?, para_count)? }
+                }
+                _ => return Err(EnvError::InvalidData),
+            };
+            match name {
+                Some(name) => { env.add_named_slice(name, slice); }
+                None => env.add_slice(slice),
+            }
+        }
+        Ok(env)
+    }
+
+    // fmt 中的名字前缀："name: " 或空
+    fn label(&self, index: usize) -> String {
+        match self.name_of(index) {
+            Some(name) => format!("{}: ", name),
+            None => String::new(),
+        }
+    }
+}
+
+// ops 中直接引用的全局下标 (LoadGlobal / CallDef，含 CallDef 的参数)
+fn collect_globals(ops: &[Op], out: &mut Vec<usize>) {
+    for op in ops {
+        match op {
+            Op::LoadGlobal(index) => out.push(*index),
+            Op::CallDef(index, args) => {
+                out.push(*index);
+                for arg in args {
+                    collect_globals(arg.ops(), out);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::time::Instant;
     use super::*;
+    use crate::pakoo::compiler::Compiler;
     use crate::pakoo::op::Op;
 
     #[test]
@@ -183,6 +481,318 @@ mod tests {
         println!("env:\n {}", env.fmt());
     }
 
+    #[test]
+    fn test_derivative_through_call_def() {
+        let mut env = Env::new();
+        // f(x) = sin(x * x)
+        let f = env.add_named_slice("f", Slice::Def {
+            para_count: 1,
+            body: RPN::new(vec![Op::LoadPara(0), Op::LoadPara(0), Op::Mul, Op::Sin]),
+        });
+        // k = 3.0
+        let k = env.add_named_slice("k", Slice::Var { data: MathData::Num(3.0) });
+        // g(x, y) = f(k * x) * y
+        let g = env.add_named_slice("g", Slice::Def {
+            para_count: 2,
+            body: RPN::new(vec![
+                Op::CallDef(f, vec![RPN::new(vec![Op::LoadGlobal(k), Op::LoadPara(0), Op::Mul])]),
+                Op::LoadPara(1),
+                Op::Mul,
+            ]),
+        });
+        // h(x) = g(f(x), x)：嵌套调用
+        let h = env.add_named_slice("h", Slice::Def {
+            para_count: 1,
+            body: RPN::new(vec![Op::CallDef(g, vec![
+                RPN::new(vec![Op::CallDef(f, vec![RPN::new(vec![Op::LoadPara(0)])])]),
+                RPN::new(vec![Op::LoadPara(0)]),
+            ])]),
+        });
+        env.update();
+
+        let df = env.derivative_of(f, 0).unwrap();
+        for x in [-1.3, 0.2, 0.9, 2.4] {
+            assert!((df(&[x]) - 2.0 * x * (x * x).cos()).abs() < 1e-12);
+        }
+
+        // ∂g/∂x = 9·2x·cos(9x²)·y，∂g/∂y = sin(9x²)
+        let (x, y) = (0.4, -1.5);
+        let dgx = env.derivative_of(g, 0).unwrap();
+        let dgy = env.derivative_of(g, 1).unwrap();
+        assert!((dgx(&[x, y]) - 18.0 * x * (9.0 * x * x).cos() * y).abs() < 1e-12);
+        assert!((dgy(&[x, y]) - (9.0 * x * x).sin()).abs() < 1e-12);
+
+        // h(x) = sin(9 sin²(x²)) · x
+        let u = |x: f64| (x * x).sin();
+        let du = |x: f64| 2.0 * x * (x * x).cos();
+        let expected = |x: f64| {
+            let s = 9.0 * u(x) * u(x);
+            s.cos() * 18.0 * u(x) * du(x) * x + s.sin()
+        };
+        let dh = env.derivative_of(h, 0).unwrap();
+        for x in [-0.8, 0.3, 1.1] {
+            assert!((dh(&[x]) - expected(x)).abs() < 1e-12, "x = {}", x);
+        }
+
+        // 非函数 / 参数越界
+        assert!(env.derivative_of(k, 0).is_none());
+        assert!(env.derivative_of(f, 1).is_none());
+    }
+
+    #[test]
+    fn test_named_slice() {
+        let mut env = Env::new();
+        // a = 1.0
+        let a = env.add_named_slice("a", Slice::Var { data: MathData::Num(1.0) });
+        // b = a + 2.0
+        let b = env.add_named_slice("b", Slice::Call {
+            body: RPN::new(vec![Op::LoadGlobal(a), Op::Push(MathData::Num(2.0)), Op::Add]),
+        });
+        assert_eq!(env.index_of("a"), Some(0));
+        assert_eq!(env.index_of("b"), Some(1));
+        assert_eq!(env.index_of("c"), None);
+
+        env.update();
+        assert!(matches!(env.get_by_name("b"), Some(MathData::Num(v)) if *v == 3.0));
+
+        // 重定义 a：下标不变，b 更新后看到新值
+        let a2 = env.add_named_slice("a", Slice::Var { data: MathData::Num(10.0) });
+        assert_eq!(a2, a);
+        assert_eq!(env.index_of("b"), Some(b));
+        env.update();
+        assert!(matches!(env.get_by_name("a"), Some(MathData::Num(v)) if *v == 10.0));
+        assert!(matches!(env.get_by_name("b"), Some(MathData::Num(v)) if *v == 12.0));
+
+        // 名字与匿名 slice 混用
+        env.add_slice(Slice::Var { data: MathData::Num(5.0) });
+        let out = env.fmt();
+        assert!(out.contains("[0] a: "));
+        assert!(out.contains("[1] b: "));
+        assert!(out.contains("[2] g_2 = 5.0"));
+    }
+
+    #[test]
+    fn test_sync_with_symbol_table() {
+        // Env 中先登记 b 再登记 a，符号表中 a 的 id 在前：sync_with 后下标即 id
+        let mut env = Env::new();
+        env.add_named_slice("b", Slice::Var { data: MathData::Num(2.0) });
+        env.add_named_slice("a", Slice::Var { data: MathData::Num(1.0) });
+        env.add_slice(Slice::Var { data: MathData::Num(7.0) });
+        env.update();
+
+        let mut table = SymbolTable::new();
+        let result = Compiler::new("a * 10 + b + c", &mut table).compile();
+        let sum = table.get_or_create_id("sum");
+        env.sync_with(&table);
+        assert_eq!((env.index_of("a"), env.index_of("b"), env.index_of("c")), (Some(0), Some(1), Some(2)));
+        // 已算出的值随槽位移动，匿名 slice 排在后面
+        assert!(matches!(env.get_by_name("a"), Some(MathData::Num(v)) if *v == 1.0));
+        assert!(matches!(env.get_data(4), MathData::Num(v) if *v == 7.0));
+
+        assert_eq!(env.add_named_slice("sum", Slice::Call { body: RPN::new(result.ops) }), sum);
+        env.add_named_slice("c", Slice::Var { data: MathData::Num(0.5) });
+        env.update();
+        assert!(matches!(env.get_by_name("sum"), Some(MathData::Num(v)) if *v == 12.5));
+
+        // 再次同步不改变已对齐的槽位
+        env.sync_with(&table);
+        assert_eq!(env.index_of("sum"), Some(sum));
+        assert_eq!(env.name_of(4), None);
+    }
+
+    #[test]
+    fn test_try_update_reports_error() {
+        let mut env = Env::new();
+        let a = env.add_named_slice("a", Slice::Var { data: MathData::Num(2.0) });
+        env.add_named_slice("b", Slice::Call {
+            body: RPN::new(vec![Op::Push(MathData::Num(1.0)), Op::LoadGlobal(a), Op::Div]),
+        });
+        assert_eq!(env.try_update(), Ok(()));
+        assert!(matches!(env.get_by_name("b"), Some(MathData::Num(v)) if *v == 0.5));
+
+        env.add_named_slice("a", Slice::Var { data: MathData::Num(0.0) });
+        assert_eq!(env.try_update(), Err(TypeError::DivideByZero));
+        assert!(matches!(env.get_by_name("a"), Some(MathData::Num(v)) if *v == 0.0));
+    }
+
+    #[test]
+    fn test_fmt_infix() {
+        let mut env = Env::new();
+        // f(x) = x + 2.0
+        env.add_named_slice("f", Slice::Def {
+            para_count: 1,
+            body: RPN::new(vec![Op::LoadPara(0), Op::Push(MathData::Num(2.0)), Op::Add]),
+        });
+        // f(2.0) * k
+        env.add_slice(Slice::Call {
+            body: RPN::new(vec![Op::CallDef(0, vec![RPN::new(vec![Op::Push(MathData::Num(2.0))])]), Op::LoadGlobal(2), Op::Mul]),
+        });
+        env.add_named_slice("k", Slice::Var { data: MathData::Num(3.0) });
+
+        let out = env.fmt();
+        assert!(out.contains("[0] f: f_0(p_0) = (p_0 + 2.0)\n"), "{}", out);
+        assert!(out.contains("[1] g_1 = (f_0(2.0) * g_2)\n"), "{}", out);
+        assert!(out.contains("[2] k: g_2 = 3.0\n"), "{}", out);
+
+        // Slice 自身的 Display
+        let def = Slice::Def { para_count: 2, body: RPN::new(vec![Op::LoadPara(0), Op::LoadPara(1), Op::Div]) };
+        assert_eq!(def.to_string(), "(p_0, p_1) -> (p_0 / p_1)");
+        assert_eq!(Slice::Var { data: MathData::Bool(true) }.to_string(), "true");
+    }
+
+    #[test]
+    fn test_named_def_update_twice() {
+        let mut env = Env::new();
+        // f(x) = x + 2.0
+        let f = env.add_named_slice("f", Slice::Def {
+            para_count: 1,
+            body: RPN::new(vec![Op::LoadPara(0), Op::Push(MathData::Num(2.0)), Op::Add]),
+        });
+        // y = f(1.0)
+        env.add_named_slice("y", Slice::Call {
+            body: RPN::new(vec![Op::CallDef(f, vec![RPN::new(vec![Op::Push(MathData::Num(1.0))])])]),
+        });
+        env.update();
+        // 多次 update 函数体不能丢失
+        env.update();
+        assert!(matches!(env.get_by_name("y"), Some(MathData::Num(v)) if *v == 3.0));
+
+        // 重定义 f(x) = x * 10.0
+        env.add_named_slice("f", Slice::Def {
+            para_count: 1,
+            body: RPN::new(vec![Op::LoadPara(0), Op::Push(MathData::Num(10.0)), Op::Mul]),
+        });
+        env.update();
+        assert!(matches!(env.get_by_name("y"), Some(MathData::Num(v)) if *v == 10.0));
+    }
+
+    #[test]
+    fn test_update_at() {
+        let mut env = Env::new();
+        // a = 1.0, b = a + 2.0, c = b * 10.0
+        let a = env.add_named_slice("a", Slice::Var { data: MathData::Num(1.0) });
+        let b = env.add_named_slice("b", Slice::Call {
+            body: RPN::new(vec![Op::LoadGlobal(a), Op::Push(MathData::Num(2.0)), Op::Add]),
+        });
+        let c = env.add_named_slice("c", Slice::Call {
+            body: RPN::new(vec![Op::LoadGlobal(b), Op::Push(MathData::Num(10.0)), Op::Mul]),
+        });
+        env.update();
+
+        // 只改 a 并刷新 b：c 保持旧值
+        env.add_named_slice("a", Slice::Var { data: MathData::Num(5.0) });
+        env.update_at(a);
+        assert!(matches!(env.update_at(b), MathData::Num(v) if v == 7.0));
+        assert!(matches!(env.get_data(c), MathData::Num(v) if *v == 30.0));
+
+        // 刷新剩下的区间
+        env.update_range(c, c + 1);
+        assert!(matches!(env.get_data(c), MathData::Num(v) if *v == 70.0));
+    }
+
+    // 混合的 Env：变量、引用变量的函数、调用函数的表达式，以及一处读取后面槽位旧值的前向引用
+    fn mixed_env(count: usize) -> Env {
+        let num = |v: f64| Op::Push(MathData::Num(v));
+        let mut env = Env::new();
+        let scale = env.add_named_slice("scale", Slice::Var { data: MathData::Num(3.0) });
+        // 下标：f_i 为 2 + 2i，v_i 为 3 + 2i，last 为 2 * count + 2
+        let last = count * 2 + 2;
+        // back = last：读取最后一个槽位上一轮的值
+        env.add_named_slice("back", Slice::Call { body: RPN::new(vec![Op::LoadGlobal(last)]) });
+        for i in 0..count {
+            // f_i(x) = x * scale + i
+            let f = env.add_named_slice(&format!("f_{}", i), Slice::Def {
+                para_count: 1,
+                body: RPN::new(vec![Op::LoadPara(0), Op::LoadGlobal(scale), Op::Mul, num(i as f64), Op::Add]),
+            });
+            // v_i = f_i(i)
+            env.add_named_slice(&format!("v_{}", i), Slice::Call {
+                body: RPN::new(vec![Op::CallDef(f, vec![RPN::new(vec![num(i as f64)])])]),
+            });
+        }
+        // last = v_0 + v_{count-1}
+        env.add_named_slice("last", Slice::Call {
+            body: RPN::new(vec![Op::LoadGlobal(3), Op::LoadGlobal(last - 1), Op::Add]),
+        });
+        assert_eq!(env.index_of("last"), Some(last));
+        env
+    }
+
+    #[test]
+    fn test_update_parallel_matches_update() {
+        let (mut serial, mut parallel) = (mixed_env(150), mixed_env(150));
+        for _ in 0..2 {
+            let (a, b) = (serial.update(), parallel.update_parallel());
+            assert_eq!(format!("{:?}", a), format!("{:?}", b));
+            assert_eq!(format!("{:?}", serial.data), format!("{:?}", parallel.data));
+        }
+        // v_149 = 149 * 3 + 149，last = v_0 + v_149
+        assert!(matches!(parallel.get_by_name("last"), Some(MathData::Num(v)) if *v == 596.0));
+        // 第二轮 back 读到第一轮的 last
+        assert!(matches!(parallel.get_by_name("back"), Some(MathData::Num(v)) if *v == 596.0));
+
+        // 分层：scale、back 与全部函数定义在第 0 层，v_i 在第 1 层，last 在第 2 层
+        let levels = parallel.levels();
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels[0].len(), 2 + 150);
+        assert_eq!(levels[1].len(), 150);
+        assert_eq!(levels[2], vec![302]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_update_at_before_update() {
+        let mut env = Env::new();
+        env.add_slice(Slice::Var { data: MathData::Num(1.0) });
+        env.update_at(0);
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let mut env = Env::new();
+        // f(x) = x > 0 ? x * 2.0 : 0.0
+        let f = env.add_named_slice("f", Slice::Def {
+            para_count: 1,
+            body: RPN::new(vec![
+                Op::LoadPara(0), Op::Push(MathData::Num(0.0)), Op::GreaterThan,
+                Op::JumpIfZero(4),
+                Op::LoadPara(0), Op::Push(MathData::Num(2.0)), Op::Mul,
+                Op::Goto(1),
+                Op::Push(MathData::Num(0.0)),
+            ]),
+        });
+        env.add_named_slice("a", Slice::Var { data: MathData::Num(-0.25) });
+        env.add_slice(Slice::Var { data: MathData::Bool(true) });
+        // f(3.5) + a
+        env.add_slice(Slice::Call {
+            body: RPN::new(vec![
+                Op::CallDef(f, vec![RPN::new(vec![Op::Push(MathData::Num(3.5))])]),
+                Op::LoadGlobal(1),
+                Op::Add,
+            ]),
+        });
+
+        let text = env.serialize();
+        assert!(text.starts_with("PAKOO-ENV 1\n"));
+        assert!(text.contains("DEF:f 1 LoadPara(0) Push(Num(0.0)) GreaterThan"));
+        assert!(text.contains("\nVAR Bool(true)\n"));
+
+        let mut restored = Env::deserialize(&text).unwrap();
+        assert_eq!(restored.serialize(), text);
+        assert_eq!(restored.index_of("a"), Some(1));
+        assert!(matches!(restored.update(), MathData::Num(v) if v == 6.75));
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        assert_eq!(Env::deserialize("").err(), Some(EnvError::VersionMismatch));
+        assert_eq!(Env::deserialize("PAKOO-ENV 2\nVAR Num(1.0)").err(), Some(EnvError::VersionMismatch));
+        assert_eq!(Env::deserialize("PAKOO-ENV 1\nCALL Push(Num(1.0)) Mod").err(), Some(EnvError::UnknownOp("Mod".to_string())));
+        assert_eq!(Env::deserialize("PAKOO-ENV 1\nDEF x LoadPara(0)").err(), Some(EnvError::InvalidData));
+        assert_eq!(Env::deserialize("PAKOO-ENV 1\nLET a Num(1.0)").err(), Some(EnvError::InvalidData));
+        assert!(Env::deserialize("PAKOO-ENV 1\n\nVAR Num(1.0)\n").is_ok());
+    }
+
     #[test]
     fn test_5() {
         let start = Instant::now(); // 获取当前时间
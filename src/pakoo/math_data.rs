@@ -1,4 +1,7 @@
+use crate::math_forest::algebra::complex::complex::Complex;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
 use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+use std::fmt;
 use std::ops::{Add, Div, Mul, Sub};
 use std::sync::Arc;
 
@@ -8,7 +11,11 @@ use super::rpn::RPN;
 pub enum MathData {
     None,
     Num(f64),
+    Bool(bool),
     Vec(Vec3),
+    Vec2(Vec2),
+    // 复数：与 Num 运算时 Num 提升为实复数；与向量运算为类型错误
+    Complex(Complex),
     Fun { para_count: usize, body: Arc<RPN> },
 }
 
@@ -20,20 +27,39 @@ impl Default for MathData {
     }
 }
 
+// 运算的类型错误：RPN::try_eval 原样返回，运算符重载与 RPN::eval 中以 panic 报告
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeError {
+    Binary { op: &'static str, lhs: &'static str, rhs: &'static str },
+    Unary { op: &'static str, operand: &'static str },
+    DivideByZero,
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeError::Binary { op, lhs, rhs } => write!(f, "cannot apply {} to {} and {}", op, lhs, rhs),
+            TypeError::Unary { op, operand } => write!(f, "cannot apply {} to {}", op, operand),
+            TypeError::DivideByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+impl std::error::Error for TypeError {}
+
+// 运算符重载没有返回错误的余地：类型错误直接 panic
+#[inline(always)]
+fn or_panic(result: Result<MathData, TypeError>) -> MathData {
+    result.unwrap_or_else(|e| panic!("类型错误: {}", e))
+}
+
 // --- 运算符重载逻辑 ---
 
 impl Add for MathData {
     type Output = MathData;
     #[inline(always)]
     fn add(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (MathData::Num(a), MathData::Num(b)) => MathData::Num(a + b),
-            (MathData::Vec(a), MathData::Vec(b)) => MathData::Vec(a + b),
-            (MathData::Num(_), MathData::Vec(_)) | (MathData::Vec(_), MathData::Num(_)) => {
-                panic!("类型错误: 不能将 数字 和 向量 直接相加");
-            }
-            _ => panic!("类型错误: 运算类型不匹配"),
-        }
+        or_panic(self.checked_add(rhs))
     }
 }
 
@@ -41,11 +67,7 @@ impl Sub for MathData {
     type Output = MathData;
     #[inline(always)]
     fn sub(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (MathData::Num(a), MathData::Num(b)) => MathData::Num(a - b),
-            (MathData::Vec(a), MathData::Vec(b)) => MathData::Vec(a - b),
-            _ => panic!("类型错误: 运算类型不匹配"),
-        }
+        or_panic(self.checked_sub(rhs))
     }
 }
 
@@ -53,15 +75,7 @@ impl Mul for MathData {
     type Output = MathData;
     #[inline(always)]
     fn mul(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (MathData::Num(a), MathData::Num(b)) => MathData::Num(a * b),
-            (MathData::Vec(v), MathData::Num(s)) => MathData::Vec(v * s),
-            (MathData::Num(s), MathData::Vec(v)) => MathData::Vec(v * s),
-            (MathData::Vec(_), MathData::Vec(_)) => {
-                panic!("类型错误: 向量与向量相乘需显式使用点乘或叉乘指令");
-            }
-            _ => panic!("类型错误: 运算类型不匹配"),
-        }
+        or_panic(self.checked_mul(rhs))
     }
 }
 
@@ -69,48 +83,238 @@ impl Div for MathData {
     type Output = MathData;
     #[inline(always)]
     fn div(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (MathData::Num(a), MathData::Num(b)) => {
-                if b == 0.0 { panic!("除以零！"); }
-                MathData::Num(a / b)
-            }
-            (MathData::Vec(v), MathData::Num(s)) => {
-                if s == 0.0 { panic!("向量除以零！"); }
-                MathData::Vec(v * (1.0 / s))
-            }
-            _ => panic!("类型错误: 非法的除法运算"),
-        }
+        or_panic(self.checked_div(rhs))
     }
 }
 
 // --- 数学函数与实用方法 ---
 
 impl MathData {
+    // 类型名 (错误信息用)
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            MathData::None => "none",
+            MathData::Num(_) => "number",
+            MathData::Bool(_) => "bool",
+            MathData::Vec(_) => "vec3",
+            MathData::Vec2(_) => "vec2",
+            MathData::Complex(_) => "complex",
+            MathData::Fun { .. } => "function",
+        }
+    }
+
+    fn binary_error(op: &'static str, lhs: &MathData, rhs: &MathData) -> TypeError {
+        TypeError::Binary { op, lhs: lhs.type_name(), rhs: rhs.type_name() }
+    }
+
+    fn unary_error(&self, op: &'static str) -> TypeError {
+        TypeError::Unary { op, operand: self.type_name() }
+    }
+
+    // 复数运算的两个操作数：至少一个是复数，另一个是复数或数字 (提升为实复数)
+    fn complex_pair(lhs: &MathData, rhs: &MathData) -> Option<(Complex, Complex)> {
+        match (lhs, rhs) {
+            (MathData::Complex(a), MathData::Complex(b)) => Some((*a, *b)),
+            (MathData::Complex(a), MathData::Num(b)) => Some((*a, Complex::from_real(*b))),
+            (MathData::Num(a), MathData::Complex(b)) => Some((Complex::from_real(*a), *b)),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub fn checked_add(self, rhs: Self) -> Result<MathData, TypeError> {
+        match (self, rhs) {
+            (MathData::Num(a), MathData::Num(b)) => Ok(MathData::Num(a + b)),
+            (MathData::Vec(a), MathData::Vec(b)) => Ok(MathData::Vec(a + b)),
+            (MathData::Vec2(a), MathData::Vec2(b)) => Ok(MathData::Vec2(a + b)),
+            (lhs, rhs) => match Self::complex_pair(&lhs, &rhs) {
+                Some((a, b)) => Ok(MathData::Complex(a + b)),
+                None => Err(Self::binary_error("+", &lhs, &rhs)),
+            },
+        }
+    }
+
+    #[inline(always)]
+    pub fn checked_sub(self, rhs: Self) -> Result<MathData, TypeError> {
+        match (self, rhs) {
+            (MathData::Num(a), MathData::Num(b)) => Ok(MathData::Num(a - b)),
+            (MathData::Vec(a), MathData::Vec(b)) => Ok(MathData::Vec(a - b)),
+            (MathData::Vec2(a), MathData::Vec2(b)) => Ok(MathData::Vec2(a - b)),
+            (lhs, rhs) => match Self::complex_pair(&lhs, &rhs) {
+                Some((a, b)) => Ok(MathData::Complex(a - b)),
+                None => Err(Self::binary_error("-", &lhs, &rhs)),
+            },
+        }
+    }
+
+    // 向量与向量相乘需显式使用点乘或叉乘指令
+    #[inline(always)]
+    pub fn checked_mul(self, rhs: Self) -> Result<MathData, TypeError> {
+        match (self, rhs) {
+            (MathData::Num(a), MathData::Num(b)) => Ok(MathData::Num(a * b)),
+            (MathData::Vec(v), MathData::Num(s)) | (MathData::Num(s), MathData::Vec(v)) => Ok(MathData::Vec(v * s)),
+            (MathData::Vec2(v), MathData::Num(s)) | (MathData::Num(s), MathData::Vec2(v)) => Ok(MathData::Vec2(v * s)),
+            (lhs, rhs) => match Self::complex_pair(&lhs, &rhs) {
+                Some((a, b)) => Ok(MathData::Complex(a * b)),
+                None => Err(Self::binary_error("*", &lhs, &rhs)),
+            },
+        }
+    }
+
+    // 实数 (及向量) 除以 0 为错误；复数除以 0 按 Complex 的规则得到非有限值
+    #[inline(always)]
+    pub fn checked_div(self, rhs: Self) -> Result<MathData, TypeError> {
+        match (self, rhs) {
+            (MathData::Num(_) | MathData::Vec(_) | MathData::Vec2(_), MathData::Num(0.0)) => Err(TypeError::DivideByZero),
+            (MathData::Num(a), MathData::Num(b)) => Ok(MathData::Num(a / b)),
+            (MathData::Vec(v), MathData::Num(s)) => Ok(MathData::Vec(v * (1.0 / s))),
+            (MathData::Vec2(v), MathData::Num(s)) => Ok(MathData::Vec2(v / s)),
+            (lhs, rhs) => match Self::complex_pair(&lhs, &rhs) {
+                Some((a, b)) => Ok(MathData::Complex(a / b)),
+                None => Err(Self::binary_error("/", &lhs, &rhs)),
+            },
+        }
+    }
+
+    // 实数底数为负、指数非整数时为 NaN (与 f64::powf 相同)；含复数时取主值
+    #[inline(always)]
+    pub fn checked_pow(self, rhs: Self) -> Result<MathData, TypeError> {
+        match (self, rhs) {
+            (MathData::Num(a), MathData::Num(b)) => Ok(MathData::Num(a.powf(b))),
+            (lhs, rhs) => match Self::complex_pair(&lhs, &rhs) {
+                Some((a, b)) => Ok(MathData::Complex(a.pow(b))),
+                None => Err(Self::binary_error("^", &lhs, &rhs)),
+            },
+        }
+    }
+
+    // 超越函数：数字用 f64 的实现，复数用 Complex 的实现 (主值)
+    #[inline(always)]
+    fn transcendental(&self, op: &'static str, real: fn(f64) -> f64, complex: fn(Complex) -> Complex) -> Result<MathData, TypeError> {
+        match self {
+            MathData::Num(v) => Ok(MathData::Num(real(*v))),
+            MathData::Complex(z) => Ok(MathData::Complex(complex(*z))),
+            _ => Err(self.unary_error(op)),
+        }
+    }
+    #[inline(always)]
+    pub fn sin(&self) -> Result<MathData, TypeError> {
+        self.transcendental("sin", f64::sin, Complex::sin)
+    }
+    #[inline(always)]
+    pub fn cos(&self) -> Result<MathData, TypeError> {
+        self.transcendental("cos", f64::cos, Complex::cos)
+    }
+    #[inline(always)]
+    pub fn tan(&self) -> Result<MathData, TypeError> {
+        self.transcendental("tan", f64::tan, Complex::tan)
+    }
+    // 实数的 ln / sqrt 在定义域外为 NaN，不会自动提升为复数
+    #[inline(always)]
+    pub fn exp(&self) -> Result<MathData, TypeError> {
+        self.transcendental("exp", f64::exp, Complex::exp)
+    }
+    #[inline(always)]
+    pub fn ln(&self) -> Result<MathData, TypeError> {
+        self.transcendental("ln", f64::ln, Complex::ln)
+    }
+    #[inline(always)]
+    pub fn sqrt(&self) -> Result<MathData, TypeError> {
+        self.transcendental("sqrt", f64::sqrt, Complex::sqrt)
+    }
+
+    // 复数：由实部、虚部 (两个数) 构造
+    #[inline(always)]
+    pub fn make_complex(re: MathData, im: MathData) -> Result<MathData, TypeError> {
+        match (re, im) {
+            (MathData::Num(re), MathData::Num(im)) => Ok(MathData::Complex(Complex::new(re, im))),
+            (re, im) => Err(Self::binary_error("complex", &re, &im)),
+        }
+    }
+    // 实部 / 虚部 / 共轭 / 辐角 / 模：数字视为实复数
+    #[inline(always)]
+    fn complex_part(&self, op: &'static str, part: fn(Complex) -> MathData) -> Result<MathData, TypeError> {
+        match self {
+            MathData::Num(v) => Ok(part(Complex::from_real(*v))),
+            MathData::Complex(z) => Ok(part(*z)),
+            _ => Err(self.unary_error(op)),
+        }
+    }
+    #[inline(always)]
+    pub fn re(&self) -> Result<MathData, TypeError> {
+        self.complex_part("re", |z| MathData::Num(z.re))
+    }
+    #[inline(always)]
+    pub fn im(&self) -> Result<MathData, TypeError> {
+        self.complex_part("im", |z| MathData::Num(z.im))
+    }
     #[inline(always)]
-    pub fn sin(&self) -> MathData {
-        if let MathData::Num(val) = self {
-            MathData::Num(val.sin())
-        } else {
-            panic!("类型错误: sin 仅支持数字");
+    pub fn conj(&self) -> Result<MathData, TypeError> {
+        match self {
+            MathData::Num(v) => Ok(MathData::Num(*v)),
+            _ => self.complex_part("conj", |z| MathData::Complex(z.conj())),
         }
     }
     #[inline(always)]
-    pub fn cos(&self) -> MathData {
-        if let MathData::Num(val) = self {
-            MathData::Num(val.cos())
-        } else {
-            panic!("类型错误: cos 仅支持数字");
+    pub fn arg(&self) -> Result<MathData, TypeError> {
+        self.complex_part("arg", |z| MathData::Num(z.arg()))
+    }
+    #[inline(always)]
+    pub fn abs_c(&self) -> Result<MathData, TypeError> {
+        self.complex_part("abs", |z| MathData::Num(z.len()))
+    }
+
+    // 平面向量：由两个数构造，点乘 / 叉乘 (z 分量) 结果为数字
+    #[inline(always)]
+    pub fn make_vec2(x: MathData, y: MathData) -> Result<MathData, TypeError> {
+        match (x, y) {
+            (MathData::Num(x), MathData::Num(y)) => Ok(MathData::Vec2(Vec2::new(x, y))),
+            (x, y) => Err(Self::binary_error("vec2", &x, &y)),
+        }
+    }
+    #[inline(always)]
+    pub fn dot2(&self, rhs: &MathData) -> Result<MathData, TypeError> {
+        match (self, rhs) {
+            (MathData::Vec2(a), MathData::Vec2(b)) => Ok(MathData::Num(a.dot(*b))),
+            _ => Err(Self::binary_error("dot", self, rhs)),
         }
     }
     #[inline(always)]
-    pub fn tan(&self) -> MathData {
-        if let MathData::Num(val) = self {
-            MathData::Num(val.tan())
-        } else {
-            panic!("类型错误: tan 仅支持数字");
+    pub fn cross2(&self, rhs: &MathData) -> Result<MathData, TypeError> {
+        match (self, rhs) {
+            (MathData::Vec2(a), MathData::Vec2(b)) => Ok(MathData::Num(a.cross(*b))),
+            _ => Err(Self::binary_error("cross", self, rhs)),
+        }
+    }
+
+    // 分量：平面向量与空间向量都支持
+    #[inline(always)]
+    pub fn get_x(&self) -> Result<MathData, TypeError> {
+        match self {
+            MathData::Vec2(v) => Ok(MathData::Num(v.x)),
+            MathData::Vec(v) => Ok(MathData::Num(v.x)),
+            _ => Err(self.unary_error(".x")),
+        }
+    }
+    #[inline(always)]
+    pub fn get_y(&self) -> Result<MathData, TypeError> {
+        match self {
+            MathData::Vec2(v) => Ok(MathData::Num(v.y)),
+            MathData::Vec(v) => Ok(MathData::Num(v.y)),
+            _ => Err(self.unary_error(".y")),
+        }
+    }
+
+    // 条件取值：Bool 直接使用，Num 以非零为真 (兼容 0.0 / 1.0 编码)
+    #[inline(always)]
+    pub fn truthy(&self) -> Result<bool, TypeError> {
+        match self {
+            MathData::Bool(b) => Ok(*b),
+            MathData::Num(v) => Ok(*v != 0.0),
+            _ => Err(self.unary_error("condition")),
         }
     }
 
     // 注意：Rust 自动通过 #[derive(Clone)] 生成了 clone 方法。
     // 如果没有特殊逻辑，不需要手动实现 pub fn clone(&self)。
-}
\ No newline at end of file
+}
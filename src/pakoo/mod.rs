@@ -4,4 +4,7 @@ pub mod rpn;
 pub mod slice;
 pub mod op;
 pub mod env;
+pub mod compiler;
+pub mod symbol_table;
+pub(crate) mod persist;
 mod token;
\ No newline at end of file
@@ -6,12 +6,43 @@ pub(crate) enum Op {
     Sub,
     Mul,
     Div,
-    // 指数运算
-    // Pow,
+    // 指数运算：弹出指数 b、底数 a，压入 a^b
+    Pow,
     // 三角函数
     Sin,
     Cos,
     Tan,
+    // 指数 / 对数 / 平方根 (数字或复数，复数取主值)
+    Exp,
+    Ln,
+    Sqrt,
+    // 比较：弹出两个数，压入 Bool
+    GreaterThan,
+    LessThan,
+    Equal,
+    // 逻辑运算：操作数为 Bool (Num 以非零为真)，结果为 Bool
+    And,
+    Or,
+    Not,
+    // 平面向量：MakeVec2 弹出 x, y 压入 Vec2；Dot2 / Cross2 弹出两个 Vec2 压入 Num
+    MakeVec2,
+    Dot2,
+    Cross2,
+    // 取分量：Vec2 与 Vec3 均可
+    GetX,
+    GetY,
+    // 复数：MakeComplex 弹出 im, re 压入 Complex；其余弹出一个值 (数字视为实复数)
+    // Re / Im / Arg / AbsC 结果为 Num，Conj 结果类型不变
+    MakeComplex,
+    Re,
+    Im,
+    Conj,
+    Arg,
+    AbsC,
+    // 跳转：offset 为相对下一条指令跳过的条数 (可为负)
+    // JumpIfZero 弹出条件，为 Bool(false) 或 Num(0.0) 时跳转；Goto 无条件跳转
+    JumpIfZero(i32),
+    Goto(i32),
     //
     LoadPara(usize),
     LoadGlobal(usize),
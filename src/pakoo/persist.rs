@@ -0,0 +1,274 @@
+// Env 持久化：解析 Op / MathData 的 Debug 文本
+// 先把文本解析成通用的语法树 (名字、元组、结构体、列表)，再按名字还原成具体类型
+#![allow(dead_code)]
+
+use std::iter::Peekable;
+use std::str::Chars;
+use std::sync::Arc;
+
+use crate::math_forest::algebra::complex::complex::Complex;
+use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
+
+use super::env::EnvError;
+use super::math_data::MathData;
+use super::op::Op;
+use super::rpn::RPN;
+
+// Debug 文本的语法树
+#[derive(Debug, PartialEq)]
+enum Node {
+    Atom(String),                       // Add / 1.0 / true / NaN
+    Tuple(String, Vec<Node>),           // Push(...)
+    Struct(String, Vec<(String, Node)>), // RPN { op: ... }
+    List(Vec<Node>),                    // [...]
+}
+
+// ====================== 词法 + 语法 ======================
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Self { chars: s.chars().peekable() }
+    }
+
+    fn skip_ws(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_ws();
+        self.chars.peek().is_none()
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), EnvError> {
+        self.skip_ws();
+        self.chars.next_if_eq(&c).map(|_| ()).ok_or(EnvError::InvalidData)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        self.chars.next_if_eq(&c).is_some()
+    }
+
+    // 名字与数字共用一种记号 (数字可能是 -1.5 / 1e-7 / -inf)
+    fn atom(&mut self) -> Result<String, EnvError> {
+        self.skip_ws();
+        let mut s = String::new();
+        while let Some(c) = self.chars.next_if(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '+')) {
+            s.push(c);
+        }
+        if s.is_empty() { Err(EnvError::InvalidData) } else { Ok(s) }
+    }
+
+    // 逗号分隔直到 close
+    fn separated<T>(&mut self, close: char, mut item: impl FnMut(&mut Self) -> Result<T, EnvError>) -> Result<Vec<T>, EnvError> {
+        let mut items = Vec::new();
+        if self.eat(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if self.eat(close) {
+                return Ok(items);
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn node(&mut self) -> Result<Node, EnvError> {
+        if self.eat('[') {
+            return Ok(Node::List(self.separated(']', Self::node)?));
+        }
+        let name = self.atom()?;
+        // Debug 的元组紧跟名字，结构体前有一个空格
+        if self.chars.next_if_eq(&'(').is_some() {
+            return Ok(Node::Tuple(name, self.separated(')', Self::node)?));
+        }
+        if self.eat('{') {
+            let fields = self.separated('}', |p| {
+                let key = p.atom()?;
+                p.expect(':')?;
+                Ok((key, p.node()?))
+            })?;
+            return Ok(Node::Struct(name, fields));
+        }
+        Ok(Node::Atom(name))
+    }
+}
+
+// ====================== 还原 ======================
+
+fn number<T: std::str::FromStr>(node: &Node) -> Result<T, EnvError> {
+    match node {
+        Node::Atom(s) => s.parse().map_err(|_| EnvError::InvalidData),
+        _ => Err(EnvError::InvalidData),
+    }
+}
+
+fn single(args: &[Node]) -> Result<&Node, EnvError> {
+    match args {
+        [a] => Ok(a),
+        _ => Err(EnvError::InvalidData),
+    }
+}
+
+fn field<'n>(fields: &'n [(String, Node)], key: &str) -> Result<&'n Node, EnvError> {
+    fields.iter().find(|(k, _)| k == key).map(|(_, v)| v).ok_or(EnvError::InvalidData)
+}
+
+fn to_rpn(node: &Node) -> Result<RPN, EnvError> {
+    match node {
+        Node::Struct(name, fields) if name == "RPN" => match field(fields, "op")? {
+            Node::List(ops) => {
+                let ops = ops.iter().map(to_op).collect::<Result<_, _>>()?;
+                // 参数个数与全局个数此处未知，只检查栈形状
+                RPN::new_checked(ops, usize::MAX, usize::MAX).map_err(|_| EnvError::InvalidData)
+            }
+            _ => Err(EnvError::InvalidData),
+        },
+        _ => Err(EnvError::InvalidData),
+    }
+}
+
+fn to_data(node: &Node) -> Result<MathData, EnvError> {
+    match node {
+        Node::Atom(name) if name == "None" => Ok(MathData::None),
+        Node::Tuple(name, args) => match name.as_str() {
+            "Num" => Ok(MathData::Num(number(single(args)?)?)),
+            "Bool" => Ok(MathData::Bool(number(single(args)?)?)),
+            "Vec" => match single(args)? {
+                Node::Struct(v, fields) if v == "Vec3" => Ok(MathData::Vec(Vec3::new(
+                    number(field(fields, "x")?)?,
+                    number(field(fields, "y")?)?,
+                    number(field(fields, "z")?)?,
+                ))),
+                _ => Err(EnvError::InvalidData),
+            },
+            "Vec2" => match single(args)? {
+                Node::Struct(v, fields) if v == "Vec2" => Ok(MathData::Vec2(Vec2::new(
+                    number(field(fields, "x")?)?,
+                    number(field(fields, "y")?)?,
+                ))),
+                _ => Err(EnvError::InvalidData),
+            },
+            "Complex" => match single(args)? {
+                Node::Struct(c, fields) if c == "Complex" => Ok(MathData::Complex(Complex::new(
+                    number(field(fields, "re")?)?,
+                    number(field(fields, "im")?)?,
+                ))),
+                _ => Err(EnvError::InvalidData),
+            },
+            _ => Err(EnvError::InvalidData),
+        },
+        Node::Struct(name, fields) if name == "Fun" => Ok(MathData::Fun {
+            para_count: number(field(fields, "para_count")?)?,
+            body: Arc::new(to_rpn(field(fields, "body")?)?),
+        }),
+        _ => Err(EnvError::InvalidData),
+    }
+}
+
+fn to_op(node: &Node) -> Result<Op, EnvError> {
+    match node {
+        Node::Atom(name) => Ok(match name.as_str() {
+            "Add" => Op::Add,
+            "Sub" => Op::Sub,
+            "Mul" => Op::Mul,
+            "Div" => Op::Div,
+            "Pow" => Op::Pow,
+            "Sin" => Op::Sin,
+            "Cos" => Op::Cos,
+            "Tan" => Op::Tan,
+            "Exp" => Op::Exp,
+            "Ln" => Op::Ln,
+            "Sqrt" => Op::Sqrt,
+            "GreaterThan" => Op::GreaterThan,
+            "LessThan" => Op::LessThan,
+            "Equal" => Op::Equal,
+            "And" => Op::And,
+            "Or" => Op::Or,
+            "Not" => Op::Not,
+            "MakeVec2" => Op::MakeVec2,
+            "Dot2" => Op::Dot2,
+            "Cross2" => Op::Cross2,
+            "GetX" => Op::GetX,
+            "GetY" => Op::GetY,
+            "MakeComplex" => Op::MakeComplex,
+            "Re" => Op::Re,
+            "Im" => Op::Im,
+            "Conj" => Op::Conj,
+            "Arg" => Op::Arg,
+            "AbsC" => Op::AbsC,
+            _ => return Err(EnvError::UnknownOp(name.clone())),
+        }),
+        Node::Tuple(name, args) => Ok(match name.as_str() {
+            "JumpIfZero" => Op::JumpIfZero(number(single(args)?)?),
+            "Goto" => Op::Goto(number(single(args)?)?),
+            "LoadPara" => Op::LoadPara(number(single(args)?)?),
+            "LoadGlobal" => Op::LoadGlobal(number(single(args)?)?),
+            "Push" => Op::Push(to_data(single(args)?)?),
+            "CallDef" => match args.as_slice() {
+                [index, Node::List(paras)] => Op::CallDef(
+                    number(index)?,
+                    paras.iter().map(to_rpn).collect::<Result<_, _>>()?,
+                ),
+                _ => return Err(EnvError::InvalidData),
+            },
+            _ => return Err(EnvError::UnknownOp(name.clone())),
+        }),
+        _ => Err(EnvError::InvalidData),
+    }
+}
+
+// ====================== 入口 ======================
+
+// 空白分隔的一串 Op (每个都是 Debug 文本)
+pub(crate) fn parse_ops(s: &str) -> Result<Vec<Op>, EnvError> {
+    let mut p = Parser::new(s);
+    let mut ops = Vec::new();
+    while !p.at_end() {
+        ops.push(to_op(&p.node()?)?);
+    }
+    Ok(ops)
+}
+
+// 单个 MathData 的 Debug 文本
+pub(crate) fn parse_data(s: &str) -> Result<MathData, EnvError> {
+    let mut p = Parser::new(s);
+    let data = to_data(&p.node()?)?;
+    if p.at_end() { Ok(data) } else { Err(EnvError::InvalidData) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_debug_text() {
+        let ops = vec![
+            Op::Push(MathData::Num(-1.5e-7)),
+            Op::LoadPara(0),
+            Op::JumpIfZero(-3),
+            Op::CallDef(2, vec![RPN::new(vec![Op::Push(MathData::Bool(true)), Op::Not])]),
+            Op::Push(MathData::Vec(Vec3::new(1.0, -2.0, 0.5))),
+            Op::Add, Op::Pow,
+            Op::Push(MathData::Vec2(Vec2::new(0.25, -3.0))),
+            Op::MakeVec2, Op::Dot2, Op::Cross2, Op::GetX, Op::GetY,
+            Op::Push(MathData::Complex(Complex::new(1.5, -2.0))),
+            Op::Exp, Op::Ln, Op::Sqrt, Op::MakeComplex, Op::Re, Op::Im, Op::Conj, Op::Arg, Op::AbsC,
+        ];
+        let text: Vec<String> = ops.iter().map(|op| format!("{:?}", op)).collect();
+        let parsed = parse_ops(&text.join(" ")).unwrap();
+        assert_eq!(format!("{:?}", parsed), format!("{:?}", ops));
+
+        assert!(matches!(parse_data("Num(inf)"), Ok(MathData::Num(v)) if v == f64::INFINITY));
+        assert!(matches!(parse_data("None"), Ok(MathData::None)));
+        assert_eq!(parse_ops("Add Mod").unwrap_err(), EnvError::UnknownOp("Mod".to_string()));
+        assert_eq!(parse_ops("Push(Num(1.0)").unwrap_err(), EnvError::InvalidData);
+        assert_eq!(parse_data("Num(1.0) Num(2.0)").unwrap_err(), EnvError::InvalidData);
+    }
+}
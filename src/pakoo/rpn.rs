@@ -1,7 +1,10 @@
 use std::clone;
+use std::fmt;
 use rand_distr::num_traits::real::Real;
-use super::math_data::MathData;
+use super::math_data::{MathData, TypeError};
 use super::op::Op;
+use crate::math_forest::algebra::dual::dual_num::Dual;
+use crate::math_forest::geometry::d3::linear::vec3::Vec3;
 
 #[derive(Clone, Debug)]
 #[derive(Default)]
@@ -9,25 +12,147 @@ pub(crate) struct RPN {
     op: Vec<Op>,
 }
 
+// validate 的结果：沿所有跳转路径模拟出的最大栈深度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StackInfo {
+    pub max_depth: usize,
+}
+
+// 静态检查失败的原因，at 为出错指令的下标
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidateError {
+    Underflow { at: usize },                  // 操作数不足
+    Overflow { at: usize, depth: usize },     // 超过 MAX_STACK_SIZE
+    ParaOutOfRange { at: usize, index: usize },
+    GlobalOutOfRange { at: usize, index: usize }, // LoadGlobal / CallDef 越界
+    JumpOutOfRange { at: usize },
+    InconsistentBranches { at: usize },       // 不同路径到达同一指令时深度不同
+    WrongResultCount { count: usize },        // 结束时栈上不是恰好一个值
+    InArgument { at: usize, arg: usize, error: Box<ValidateError> }, // CallDef 的第 arg 个参数
+}
+
+impl fmt::Display for ValidateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidateError::Underflow { at } => write!(f, "missing operand at op {}", at),
+            ValidateError::Overflow { at, depth } => write!(f, "expression too deep at op {} (depth {} > {})", at, depth, RPN::MAX_STACK_SIZE),
+            ValidateError::ParaOutOfRange { at, index } => write!(f, "parameter {} out of range at op {}", index, at),
+            ValidateError::GlobalOutOfRange { at, index } => write!(f, "global {} out of range at op {}", index, at),
+            ValidateError::JumpOutOfRange { at } => write!(f, "jump out of range at op {}", at),
+            ValidateError::InconsistentBranches { at } => write!(f, "inconsistent branch results at op {}", at),
+            ValidateError::WrongResultCount { count } => write!(f, "expression leaves {} values", count),
+            ValidateError::InArgument { at, arg, error } => write!(f, "argument {} of call at op {}: {}", arg, at, error),
+        }
+    }
+}
+
+impl std::error::Error for ValidateError {}
+
 impl RPN {
     pub fn none() -> Self {
         RPN { op: Vec::new() }
     }
 
+    // eval 不检查栈，调试构建下至少保证栈形状合法 (下标范围由调用方负责)
     pub fn new(op: Vec<Op>) -> Self {
-        RPN { op }
+        let rpn = RPN { op };
+        debug_assert!(rpn.validate(usize::MAX, usize::MAX).is_ok(), "类型错误: 非法的 RPN 程序");
+        rpn
+    }
+
+    // 来源不可信 (编译器输出、读入的文件) 时使用：检查失败返回错误而不是 panic
+    pub fn new_checked(op: Vec<Op>, max_para: usize, env_len: usize) -> Result<Self, ValidateError> {
+        let rpn = RPN { op };
+        rpn.validate(max_para, env_len)?;
+        Ok(rpn)
     }
 
-    const MAX_STACK_SIZE: usize = 32;
+    pub fn ops(&self) -> &[Op] {
+        &self.op
+    }
+
+    pub fn into_ops(self) -> Vec<Op> {
+        self.op
+    }
+
+    // 静态检查：沿所有跳转路径模拟栈深度，每条指令处的深度必须唯一，结束时恰好剩一个值
+    // max_para: 可用参数个数 (LoadPara 上界)；env_len: 全局数据个数 (LoadGlobal / CallDef 上界)
+    // CallDef 的参数与调用方共享参数与全局，按相同上界递归检查
+    pub fn validate(&self, max_para: usize, env_len: usize) -> Result<StackInfo, ValidateError> {
+        let ops = &self.op;
+        let mut seen: Vec<Option<usize>> = vec![None; ops.len() + 1];
+        let mut work = vec![(0usize, 0usize)];
+        let mut max_depth = 0;
+        while let Some((ip, depth)) = work.pop() {
+            match seen[ip] {
+                Some(d) if d == depth => continue,
+                Some(_) => return Err(ValidateError::InconsistentBranches { at: ip }),
+                None => seen[ip] = Some(depth),
+            }
+            let Some(op) = ops.get(ip) else {
+                if depth != 1 { return Err(ValidateError::WrongResultCount { count: depth }); }
+                continue;
+            };
+
+            match op {
+                Op::LoadPara(index) if *index >= max_para => return Err(ValidateError::ParaOutOfRange { at: ip, index: *index }),
+                Op::LoadGlobal(index) | Op::CallDef(index, _) if *index >= env_len => {
+                    return Err(ValidateError::GlobalOutOfRange { at: ip, index: *index });
+                }
+                Op::CallDef(_, args) => {
+                    for (arg, rpn) in args.iter().enumerate() {
+                        rpn.validate(max_para, env_len)
+                            .map_err(|e| ValidateError::InArgument { at: ip, arg, error: Box::new(e) })?;
+                    }
+                }
+                _ => {}
+            }
+
+            let (pops, pushes) = match op {
+                Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Pow
+                | Op::GreaterThan | Op::LessThan | Op::Equal | Op::And | Op::Or
+                | Op::MakeVec2 | Op::Dot2 | Op::Cross2 | Op::MakeComplex => (2, 1),
+                Op::Sin | Op::Cos | Op::Tan | Op::Exp | Op::Ln | Op::Sqrt | Op::Not | Op::GetX | Op::GetY
+                | Op::Re | Op::Im | Op::Conj | Op::Arg | Op::AbsC => (1, 1),
+                Op::Push(_) | Op::LoadPara(_) | Op::LoadGlobal(_) | Op::CallDef(..) => (0, 1),
+                Op::JumpIfZero(_) => (1, 0),
+                Op::Goto(_) => (0, 0),
+            };
+            if depth < pops { return Err(ValidateError::Underflow { at: ip }); }
+            let depth = depth - pops + pushes;
+            if depth > Self::MAX_STACK_SIZE { return Err(ValidateError::Overflow { at: ip, depth }); }
+            max_depth = max_depth.max(depth);
+
+            let jump = |offset: i32| {
+                let target = ip as i64 + 1 + offset as i64;
+                if (0..=ops.len() as i64).contains(&target) { Ok(target as usize) } else { Err(ValidateError::JumpOutOfRange { at: ip }) }
+            };
+            match op {
+                Op::JumpIfZero(offset) => { work.push((ip + 1, depth)); work.push((jump(*offset)?, depth)); }
+                Op::Goto(offset) => work.push((jump(*offset)?, depth)),
+                _ => work.push((ip + 1, depth)),
+            }
+        }
+        Ok(StackInfo { max_depth })
+    }
+
+    pub(crate) const MAX_STACK_SIZE: usize = 32;
+    // 类型错误 (如向量加复数) 时 panic；需要把错误交给调用方时用 try_eval
     pub fn eval(&self, env_data: &[MathData], args: &[MathData]) -> MathData {
+        self.try_eval(env_data, args).unwrap_or_else(|e| panic!("类型错误: {}", e))
+    }
+
+    pub fn try_eval(&self, env_data: &[MathData], args: &[MathData]) -> Result<MathData, TypeError> {
         // println!("--- 开始运行 ---");
         // 1. 使用定长数组替代 Vec。
         // 要求 MathData 实现了 Default（例如默认是 Number(0.0)）
         let mut stack: [MathData; Self::MAX_STACK_SIZE] = Default::default();
         let mut top: usize = 0; // 栈顶指针
 
-        // 2. 直接遍历指令引用
-        for instruction in &self.op {
+        // 2. 按指令指针执行 (跳转指令会修改 ip)
+        let mut ip: usize = 0;
+        while let Some(instruction) = self.op.get(ip) {
+            ip += 1;
             //  使用 unsafe 块处理指令
             unsafe {
                 match instruction {
@@ -37,55 +162,89 @@ impl RPN {
                     }
 
                     // 运算指令：改为直接操作数组和索引
-                    Op::Add => {
+                    Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Pow
+                    | Op::MakeVec2 | Op::Dot2 | Op::Cross2 | Op::MakeComplex => {
                         top -= 1;
                         let rhs = std::mem::take(stack.get_unchecked_mut(top));
                         top -= 1;
                         let lhs = std::mem::take(stack.get_unchecked_mut(top));
-                        *stack.get_unchecked_mut(top) = lhs + rhs;
+                        *stack.get_unchecked_mut(top) = match instruction {
+                            Op::Add => lhs.checked_add(rhs),
+                            Op::Sub => lhs.checked_sub(rhs),
+                            Op::Mul => lhs.checked_mul(rhs),
+                            Op::Div => lhs.checked_div(rhs),
+                            Op::Pow => lhs.checked_pow(rhs),
+                            Op::MakeVec2 => MathData::make_vec2(lhs, rhs),
+                            Op::Dot2 => lhs.dot2(&rhs),
+                            Op::Cross2 => lhs.cross2(&rhs),
+                            _ => MathData::make_complex(lhs, rhs),
+                        }?;
                         top += 1;
                     }
-                    Op::Sub => {
-                        top -= 1;
-                        let rhs = std::mem::take(stack.get_unchecked_mut(top));
+                    Op::Sin | Op::Cos | Op::Tan | Op::Exp | Op::Ln | Op::Sqrt
+                    | Op::GetX | Op::GetY | Op::Re | Op::Im | Op::Conj | Op::Arg | Op::AbsC => {
                         top -= 1;
-                        let lhs = std::mem::take(stack.get_unchecked_mut(top));
-                        *stack.get_unchecked_mut(top) = lhs - rhs;
+                        let val = std::mem::take(stack.get_unchecked_mut(top));
+                        *stack.get_unchecked_mut(top) = match instruction {
+                            Op::Sin => val.sin(),
+                            Op::Cos => val.cos(),
+                            Op::Tan => val.tan(),
+                            Op::Exp => val.exp(),
+                            Op::Ln => val.ln(),
+                            Op::Sqrt => val.sqrt(),
+                            Op::GetX => val.get_x(),
+                            Op::GetY => val.get_y(),
+                            Op::Re => val.re(),
+                            Op::Im => val.im(),
+                            Op::Conj => val.conj(),
+                            Op::Arg => val.arg(),
+                            _ => val.abs_c(),
+                        }?;
                         top += 1;
                     }
-                    Op::Mul => {
+
+                    Op::GreaterThan | Op::LessThan | Op::Equal => {
                         top -= 1;
                         let rhs = std::mem::take(stack.get_unchecked_mut(top));
                         top -= 1;
                         let lhs = std::mem::take(stack.get_unchecked_mut(top));
-                        *stack.get_unchecked_mut(top) = lhs * rhs;
+                        let (MathData::Num(a), MathData::Num(b)) = (&lhs, &rhs) else {
+                            // 比较运算仅支持数字
+                            return Err(TypeError::Binary { op: "comparison", lhs: lhs.type_name(), rhs: rhs.type_name() });
+                        };
+                        let holds = match instruction {
+                            Op::GreaterThan => a > b,
+                            Op::LessThan => a < b,
+                            _ => a == b,
+                        };
+                        *stack.get_unchecked_mut(top) = MathData::Bool(holds);
                         top += 1;
                     }
-                    Op::Div => {
+                    Op::And | Op::Or => {
                         top -= 1;
-                        let rhs = std::mem::take(stack.get_unchecked_mut(top));
+                        let rhs = std::mem::take(stack.get_unchecked_mut(top)).truthy()?;
                         top -= 1;
-                        let lhs = std::mem::take(stack.get_unchecked_mut(top));
-                        *stack.get_unchecked_mut(top) = lhs / rhs;
+                        let lhs = std::mem::take(stack.get_unchecked_mut(top)).truthy()?;
+                        let value = if matches!(instruction, Op::And) { lhs && rhs } else { lhs || rhs };
+                        *stack.get_unchecked_mut(top) = MathData::Bool(value);
                         top += 1;
                     }
-                    Op::Sin => {
+                    Op::Not => {
                         top -= 1;
-                        let val = std::mem::take(stack.get_unchecked_mut(top));
-                        *stack.get_unchecked_mut(top) = val.sin();
+                        let val = std::mem::take(stack.get_unchecked_mut(top)).truthy()?;
+                        *stack.get_unchecked_mut(top) = MathData::Bool(!val);
                         top += 1;
                     }
-                    Op::Cos => {
+
+                    Op::JumpIfZero(offset) => {
                         top -= 1;
-                        let val = std::mem::take(stack.get_unchecked_mut(top));
-                        *stack.get_unchecked_mut(top) = val.cos();
-                        top += 1;
+                        let cond = std::mem::take(stack.get_unchecked_mut(top));
+                        if !cond.truthy()? {
+                            ip = ip.wrapping_add_signed(*offset as isize);
+                        }
                     }
-                    Op::Tan => {
-                        top -= 1;
-                        let val = std::mem::take(stack.get_unchecked_mut(top));
-                        *stack.get_unchecked_mut(top) = val.tan();
-                        top += 1;
+                    Op::Goto(offset) => {
+                        ip = ip.wrapping_add_signed(*offset as isize);
                     }
 
                     Op::LoadGlobal(gi) => {
@@ -104,13 +263,13 @@ impl RPN {
 
                         for (i, p_rpn) in para_rpns.iter().enumerate() {
                             if i < 8 {
-                                call_args[i] = p_rpn.eval(env_data, args);
+                                call_args[i] = p_rpn.try_eval(env_data, args)?;
                             }
                         }
 
                         if let MathData::Fun { para_count, body } = &env_data[*index] {
                             // 传递切片 &[MathData] 而不是 Vec
-                            stack[top] = body.eval(env_data, &call_args[..*para_count]);
+                            stack[top] = body.try_eval(env_data, &call_args[..*para_count])?;
                             top += 1;
                         }
                     }
@@ -120,7 +279,223 @@ impl RPN {
         }
 
         // 3. 转移所有权返回结果（使用 std::mem::take 避免 clone 数组中的最后一个元素）
-        std::mem::take(&mut stack[0])
+        Ok(std::mem::take(&mut stack[0]))
+    }
+
+    // 前向自动微分：与 eval 相同的指令语义，栈上是对偶数
+    // 参数 args 中 eps = 1 的那一个即求导变量，结果的 eps 为导数 (精确到舍入误差)
+    // 全局量与常量视为常数；Bool 编码为 1.0 / 0.0 (与 truthy 一致，非零为真)，比较与跳转只看实部
+    pub fn eval_dual(&self, env_data: &[MathData], args: &[Dual]) -> Dual {
+        let mut stack = [Dual::ZERO; Self::MAX_STACK_SIZE];
+        let mut top: usize = 0;
+        let flag = |b: bool| Dual::constant(if b { 1.0 } else { 0.0 });
+
+        let mut ip: usize = 0;
+        while let Some(instruction) = self.op.get(ip) {
+            ip += 1;
+            match instruction {
+                Op::Push(val) => {
+                    stack[top] = Self::dual_of(val);
+                    top += 1;
+                }
+
+                Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Pow
+                | Op::GreaterThan | Op::LessThan | Op::Equal | Op::And | Op::Or => {
+                    top -= 1;
+                    let rhs = stack[top];
+                    let lhs = stack[top - 1];
+                    stack[top - 1] = match instruction {
+                        Op::Add => lhs + rhs,
+                        Op::Sub => lhs - rhs,
+                        Op::Mul => lhs * rhs,
+                        Op::Div => lhs / rhs,
+                        Op::Pow => lhs.pow(rhs),
+                        Op::GreaterThan => flag(lhs.re > rhs.re),
+                        Op::LessThan => flag(lhs.re < rhs.re),
+                        Op::Equal => flag(lhs.re == rhs.re),
+                        Op::And => flag(lhs.re != 0.0 && rhs.re != 0.0),
+                        _ => flag(lhs.re != 0.0 || rhs.re != 0.0),
+                    };
+                }
+                Op::Sin => stack[top - 1] = stack[top - 1].sin(),
+                Op::Cos => stack[top - 1] = stack[top - 1].cos(),
+                Op::Tan => stack[top - 1] = stack[top - 1].tan(),
+                Op::Exp => stack[top - 1] = stack[top - 1].exp(),
+                Op::Ln => stack[top - 1] = stack[top - 1].ln(),
+                Op::Sqrt => stack[top - 1] = stack[top - 1].sqrt(),
+                Op::Not => stack[top - 1] = flag(stack[top - 1].re == 0.0),
+                Op::MakeVec2 | Op::Dot2 | Op::Cross2 | Op::GetX | Op::GetY
+                | Op::MakeComplex | Op::Re | Op::Im | Op::Conj | Op::Arg | Op::AbsC => {
+                    panic!("类型错误: 自动微分仅支持数字");
+                }
+
+                Op::JumpIfZero(offset) => {
+                    top -= 1;
+                    if stack[top].re == 0.0 {
+                        ip = ip.wrapping_add_signed(*offset as isize);
+                    }
+                }
+                Op::Goto(offset) => {
+                    ip = ip.wrapping_add_signed(*offset as isize);
+                }
+
+                Op::LoadGlobal(gi) => {
+                    stack[top] = Self::dual_of(&env_data[*gi]);
+                    top += 1;
+                }
+                Op::LoadPara(pi) => {
+                    stack[top] = args[*pi];
+                    top += 1;
+                }
+
+                // 实参表达式的导数随实参一起传入函数体，嵌套调用即链式法则
+                Op::CallDef(index, para_rpns) => {
+                    let mut call_args = [Dual::ZERO; 8];
+                    for (i, p_rpn) in para_rpns.iter().enumerate().take(8) {
+                        call_args[i] = p_rpn.eval_dual(env_data, args);
+                    }
+                    if let MathData::Fun { para_count, body } = &env_data[*index] {
+                        stack[top] = body.eval_dual(env_data, &call_args[..*para_count]);
+                        top += 1;
+                    }
+                }
+            }
+        }
+        stack[0]
+    }
+
+    // 三元标量场 f(x, y, z) 的梯度：三次前向求值，每次一个分量带 eps = 1
+    pub fn gradient3(&self, env_data: &[MathData], p: Vec3) -> Vec3 {
+        let partial = |k: usize| {
+            let mut args = [Dual::constant(p.x), Dual::constant(p.y), Dual::constant(p.z)];
+            args[k].eps = 1.0;
+            self.eval_dual(env_data, &args).eps
+        };
+        Vec3::new(partial(0), partial(1), partial(2))
+    }
+
+    // 常量 / 全局量转为对偶数 (导数为 0)
+    fn dual_of(data: &MathData) -> Dual {
+        match data {
+            MathData::Num(v) => Dual::constant(*v),
+            MathData::Bool(b) => Dual::constant(if *b { 1.0 } else { 0.0 }),
+            _ => panic!("类型错误: 自动微分仅支持数字"),
+        }
+    }
+
+    // 反编译为中缀表达式，便于调试：[Push(1), Push(2), Add] -> "(1.0 + 2.0)"
+    // 形参记为 p_{i}，全局量记为 g_{i}，函数调用记为 f_{id}(arg0, arg1)
+    // 跳转只识别编译器生成的三元结构 (cond JumpIfZero then Goto else)，
+    // 其余无法还原的程序原样输出指令列表
+    pub fn to_infix_string(&self) -> String {
+        Self::decompile_one(&self.op).unwrap_or_else(|| format!("{:?}", self.op))
+    }
+
+    // 把 ops 模拟执行在字符串栈上；结构不合法 (栈下溢、跳转越界等) 返回 None
+    fn decompile(ops: &[Op], stack: &mut Vec<String>) -> Option<()> {
+        let infix = |stack: &mut Vec<String>, sym: &str| -> Option<()> {
+            let rhs = stack.pop()?;
+            let lhs = stack.pop()?;
+            stack.push(format!("({} {} {})", lhs, sym, rhs));
+            Some(())
+        };
+        let call = |stack: &mut Vec<String>, name: &str| -> Option<()> {
+            let arg = stack.pop()?;
+            stack.push(format!("{}({})", name, arg));
+            Some(())
+        };
+
+        let mut ip = 0;
+        while let Some(instruction) = ops.get(ip) {
+            ip += 1;
+            match instruction {
+                Op::Add => infix(stack, "+")?,
+                Op::Sub => infix(stack, "-")?,
+                Op::Mul => infix(stack, "*")?,
+                Op::Div => infix(stack, "/")?,
+                Op::Pow => infix(stack, "^")?,
+                Op::GreaterThan => infix(stack, ">")?,
+                Op::LessThan => infix(stack, "<")?,
+                Op::Equal => infix(stack, "==")?,
+                Op::And => infix(stack, "&&")?,
+                Op::Or => infix(stack, "||")?,
+                Op::Sin => call(stack, "sin")?,
+                Op::Cos => call(stack, "cos")?,
+                Op::Tan => call(stack, "tan")?,
+                Op::Exp => call(stack, "exp")?,
+                Op::Ln => call(stack, "ln")?,
+                Op::Sqrt => call(stack, "sqrt")?,
+                Op::Re => call(stack, "re")?,
+                Op::Im => call(stack, "im")?,
+                Op::Conj => call(stack, "conj")?,
+                Op::Arg => call(stack, "arg")?,
+                Op::AbsC => call(stack, "abs")?,
+                Op::Not => {
+                    let val = stack.pop()?;
+                    stack.push(format!("!{}", val));
+                }
+                Op::MakeVec2 | Op::Dot2 | Op::Cross2 | Op::MakeComplex => {
+                    let rhs = stack.pop()?;
+                    let lhs = stack.pop()?;
+                    let name = match instruction { Op::MakeVec2 => "vec2", Op::Dot2 => "dot", Op::Cross2 => "cross", _ => "complex" };
+                    stack.push(format!("{}({}, {})", name, lhs, rhs));
+                }
+                Op::GetX | Op::GetY => {
+                    let val = stack.pop()?;
+                    stack.push(format!("{}.{}", val, if matches!(instruction, Op::GetX) { "x" } else { "y" }));
+                }
+
+                Op::JumpIfZero(offset) => {
+                    // cond JumpIfZero(n) [then ... Goto(m)] [else ...]
+                    let cond = stack.pop()?;
+                    let else_start = ip.checked_add_signed(*offset as isize)?;
+                    let Some(Op::Goto(m)) = else_start.checked_sub(1).and_then(|i| ops.get(i)) else {
+                        return None;
+                    };
+                    let end = else_start.checked_add_signed(*m as isize)?;
+                    if else_start <= ip || end < else_start || end > ops.len() {
+                        return None;
+                    }
+                    let then_str = Self::decompile_one(&ops[ip..else_start - 1])?;
+                    let else_str = Self::decompile_one(&ops[else_start..end])?;
+                    stack.push(format!("({} ? {} : {})", cond, then_str, else_str));
+                    ip = end;
+                }
+                Op::Goto(_) => return None,
+
+                Op::LoadPara(pi) => stack.push(format!("p_{}", pi)),
+                Op::LoadGlobal(gi) => stack.push(format!("g_{}", gi)),
+                Op::Push(val) => stack.push(Self::literal_string(val)),
+
+                Op::CallDef(index, para_rpns) => {
+                    let args = para_rpns.iter()
+                        .map(|p| Self::decompile_one(&p.op))
+                        .collect::<Option<Vec<_>>>()?;
+                    stack.push(format!("f_{}({})", index, args.join(", ")));
+                }
+            }
+        }
+        Some(())
+    }
+
+    // 常量的中缀写法 (数字保留小数点，如 2.0)
+    pub(crate) fn literal_string(val: &MathData) -> String {
+        match val {
+            MathData::None => "none".to_string(),
+            MathData::Num(v) => format!("{:?}", v),
+            MathData::Bool(b) => b.to_string(),
+            MathData::Vec(v) => format!("{}", v),
+            MathData::Vec2(v) => format!("{}", v),
+            MathData::Complex(z) => format!("complex({:?}, {:?})", z.re, z.im),
+            MathData::Fun { para_count, .. } => format!("fun/{}", para_count),
+        }
+    }
+
+    // 反编译一段恰好产生一个值的指令
+    fn decompile_one(ops: &[Op]) -> Option<String> {
+        let mut stack = Vec::new();
+        Self::decompile(ops, &mut stack)?;
+        if stack.len() == 1 { stack.pop() } else { None }
     }
 
 
@@ -150,3 +525,350 @@ impl RPN {
         stack.push(result);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_forest::algebra::complex::complex::Complex;
+
+    fn num(x: f64) -> Op {
+        Op::Push(MathData::Num(x))
+    }
+
+    fn eval_num(rpn: &RPN, args: &[MathData]) -> f64 {
+        match rpn.eval(&[], args) {
+            MathData::Num(v) => v,
+            other => panic!("expected Num, got {:?}", other),
+        }
+    }
+
+    fn eval_bool(rpn: &RPN) -> bool {
+        match rpn.eval(&[], &[]) {
+            MathData::Bool(b) => b,
+            other => panic!("expected Bool, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_comparisons() {
+        for (op, expected) in [(Op::GreaterThan, true), (Op::LessThan, false), (Op::Equal, false)] {
+            assert_eq!(eval_bool(&RPN::new(vec![num(3.0), num(2.0), op])), expected);
+        }
+        assert!(eval_bool(&RPN::new(vec![num(2.0), num(2.0), Op::Equal])));
+    }
+
+    #[test]
+    fn test_boolean_ops() {
+        let t = || Op::Push(MathData::Bool(true));
+        let f = || Op::Push(MathData::Bool(false));
+        assert!(!eval_bool(&RPN::new(vec![t(), f(), Op::And])));
+        assert!(eval_bool(&RPN::new(vec![t(), f(), Op::Or])));
+        assert!(eval_bool(&RPN::new(vec![f(), Op::Not])));
+        // 0 < x && x < 1
+        let in_unit = |x: f64| eval_bool(&RPN::new(vec![
+            num(0.0), num(x), Op::LessThan,
+            num(x), num(1.0), Op::LessThan,
+            Op::And,
+        ]));
+        assert!(in_unit(0.5));
+        assert!(!in_unit(1.5));
+        // Num 以非零为真
+        assert!(eval_bool(&RPN::new(vec![num(2.0), Op::Not, Op::Not])));
+    }
+
+    #[test]
+    fn test_jump_accepts_bool_and_num() {
+        for cond in [MathData::Bool(false), MathData::Num(0.0)] {
+            let rpn = RPN::new(vec![Op::Push(cond), Op::JumpIfZero(2), num(1.0), Op::Goto(1), num(2.0)]);
+            assert_eq!(eval_num(&rpn, &[]), 2.0);
+        }
+        for cond in [MathData::Bool(true), MathData::Num(3.0)] {
+            let rpn = RPN::new(vec![Op::Push(cond), Op::JumpIfZero(2), num(1.0), Op::Goto(1), num(2.0)]);
+            assert_eq!(eval_num(&rpn, &[]), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_piecewise_jumps() {
+        // x > 0 ? x * x : 0 - x
+        let rpn = RPN::new(vec![
+            Op::LoadPara(0), num(0.0), Op::GreaterThan,
+            Op::JumpIfZero(4),
+            Op::LoadPara(0), Op::LoadPara(0), Op::Mul,
+            Op::Goto(3),
+            num(0.0), Op::LoadPara(0), Op::Sub,
+        ]);
+        assert_eq!(eval_num(&rpn, &[MathData::Num(3.0)]), 9.0);
+        assert_eq!(eval_num(&rpn, &[MathData::Num(-2.0)]), 2.0);
+        assert_eq!(eval_num(&rpn, &[MathData::Num(0.0)]), 0.0);
+    }
+
+    #[test]
+    fn test_backward_goto() {
+        // 0 -> 3 -> 4 -(-4)-> 1 -> 2 -> 5：先压 5 再压 1，最后相加
+        let rpn = RPN::new(vec![
+            Op::Goto(2),
+            num(1.0),
+            Op::Goto(2),
+            num(5.0),
+            Op::Goto(-4),
+            Op::Add,
+        ]);
+        assert_eq!(eval_num(&rpn, &[]), 6.0);
+    }
+
+    #[test]
+    fn test_eval_dual_sin_square() {
+        // sin(x * x)，导数 2x·cos(x²)
+        let rpn = RPN::new(vec![Op::LoadPara(0), Op::LoadPara(0), Op::Mul, Op::Sin]);
+        for x in [-2.3, -0.5, 0.0, 0.8, 1.7, 3.1] {
+            let d = rpn.eval_dual(&[], &[Dual::variable(x)]);
+            assert!((d.re - (x * x).sin()).abs() < 1e-12);
+            assert!((d.eps - 2.0 * x * (x * x).cos()).abs() < 1e-12, "x = {}", x);
+            // 实部与 eval 一致
+            assert_eq!(d.re, eval_num(&rpn, &[MathData::Num(x)]));
+        }
+
+        // 分段函数：只对走到的分支求导
+        let rpn = RPN::new(vec![
+            Op::LoadPara(0), num(0.0), Op::GreaterThan,
+            Op::JumpIfZero(4),
+            Op::LoadPara(0), Op::LoadPara(0), Op::Mul,
+            Op::Goto(3),
+            num(0.0), Op::LoadPara(0), Op::Sub,
+        ]);
+        assert_eq!(rpn.eval_dual(&[], &[Dual::variable(3.0)]), Dual::new(9.0, 6.0));
+        assert_eq!(rpn.eval_dual(&[], &[Dual::variable(-2.0)]), Dual::new(2.0, -1.0));
+    }
+
+    #[test]
+    fn test_gradient3() {
+        // f = x * y + sin(z)
+        let rpn = RPN::new(vec![Op::LoadPara(0), Op::LoadPara(1), Op::Mul, Op::LoadPara(2), Op::Sin, Op::Add]);
+        let g = rpn.gradient3(&[], Vec3::new(2.0, -3.0, 0.5));
+        assert!(g.dis(Vec3::new(-3.0, 2.0, 0.5f64.cos())) < 1e-15);
+    }
+
+    #[test]
+    fn test_to_infix_string() {
+        let rpn = RPN::new(vec![num(1.0), num(2.0), Op::Add]);
+        assert_eq!(rpn.to_infix_string(), "(1.0 + 2.0)");
+
+        // sin(p_0) * g_1 - 3.5
+        let rpn = RPN::new(vec![Op::LoadPara(0), Op::Sin, Op::LoadGlobal(1), Op::Mul, num(3.5), Op::Sub]);
+        assert_eq!(rpn.to_infix_string(), "((sin(p_0) * g_1) - 3.5)");
+
+        let rpn = RPN::new(vec![
+            Op::CallDef(2, vec![RPN::new(vec![Op::LoadPara(0)]), RPN::new(vec![num(1.0), Op::LoadPara(1), Op::Div])]),
+            Op::Push(MathData::Bool(true)), Op::Not, Op::And,
+        ]);
+        assert_eq!(rpn.to_infix_string(), "(f_2(p_0, (1.0 / p_1)) && !true)");
+    }
+
+    #[test]
+    fn test_to_infix_string_ternary() {
+        // 与 test_piecewise_jumps 相同的程序
+        let rpn = RPN::new(vec![
+            Op::LoadPara(0), num(0.0), Op::GreaterThan,
+            Op::JumpIfZero(4),
+            Op::LoadPara(0), Op::LoadPara(0), Op::Mul,
+            Op::Goto(3),
+            num(0.0), Op::LoadPara(0), Op::Sub,
+        ]);
+        assert_eq!(rpn.to_infix_string(), "((p_0 > 0.0) ? (p_0 * p_0) : (0.0 - p_0))");
+
+        // 非结构化跳转与栈下溢：退回原始指令列表
+        let rpn = RPN::new(vec![Op::Goto(2), num(1.0), Op::Goto(2), num(5.0), Op::Goto(-4), Op::Add]);
+        assert!(rpn.to_infix_string().starts_with('['));
+        assert!(RPN { op: vec![Op::Add] }.to_infix_string().starts_with('['));
+    }
+
+    #[test]
+    fn test_vec2_point_line_distance() {
+        use crate::math_forest::geometry::d2::linear::line::Line;
+        use crate::math_forest::geometry::d2::linear::vec2::Vec2;
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        // ((p - a) × v)² / (v · v)，与 Line::dis_p 的平方比较 (没有 sqrt / abs 指令)
+        // 参数：p_0 = a, p_1 = v, p_2 = p
+        let cross = || [Op::LoadPara(2), Op::LoadPara(0), Op::Sub, Op::LoadPara(1), Op::Cross2];
+        let mut ops: Vec<Op> = cross().into_iter().chain(cross()).collect();
+        ops.extend([Op::Mul, Op::LoadPara(1), Op::LoadPara(1), Op::Dot2, Op::Div]);
+        let rpn = RPN::new(ops);
+
+        let mut rng = StdRng::seed_from_u64(1349);
+        let mut rand_vec = || Vec2::new(rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0));
+        for _ in 0..100 {
+            let (a, v, p) = (rand_vec(), rand_vec(), rand_vec());
+            let d2 = eval_num(&rpn, &[MathData::Vec2(a), MathData::Vec2(v), MathData::Vec2(p)]);
+            let expected = Line::new(a, v).dis_p(p).powi(2);
+            assert!((d2 - expected).abs() < 1e-9 * expected.max(1.0), "{} vs {}", d2, expected);
+        }
+    }
+
+    #[test]
+    fn test_vec2_construction_and_components() {
+        // vec2(1, 2) * 3 + vec2(0.5, 0) -> (3.5, 6)
+        let rpn = RPN::new(vec![
+            num(1.0), num(2.0), Op::MakeVec2, num(3.0), Op::Mul,
+            num(0.5), num(0.0), Op::MakeVec2, Op::Add,
+        ]);
+        let v = rpn.eval(&[], &[]);
+        let x = RPN::new(vec![Op::LoadPara(0), Op::GetX]);
+        let y = RPN::new(vec![Op::LoadPara(0), Op::GetY]);
+        assert_eq!(eval_num(&x, std::slice::from_ref(&v)), 3.5);
+        assert_eq!(eval_num(&y, std::slice::from_ref(&v)), 6.0);
+
+        // 分量指令同样适用于 Vec3
+        let v3 = MathData::Vec(Vec3::new(-1.0, 4.0, 9.0));
+        assert_eq!(eval_num(&x, std::slice::from_ref(&v3)), -1.0);
+        assert_eq!(eval_num(&y, std::slice::from_ref(&v3)), 4.0);
+
+        assert_eq!(rpn.to_infix_string(), "((vec2(1.0, 2.0) * 3.0) + vec2(0.5, 0.0))");
+        assert_eq!(RPN::new(vec![Op::LoadPara(0), Op::LoadPara(1), Op::Cross2, Op::LoadPara(0), Op::GetY, Op::Add]).to_infix_string(),
+            "(cross(p_0, p_1) + p_0.y)");
+    }
+
+    #[test]
+    #[should_panic(expected = "类型错误")]
+    fn test_vec2_vec3_mismatch() {
+        let rpn = RPN::new(vec![
+            num(1.0), num(2.0), Op::MakeVec2,
+            Op::Push(MathData::Vec(Vec3::new(1.0, 2.0, 3.0))), Op::Add,
+        ]);
+        rpn.eval(&[], &[]);
+    }
+
+    #[test]
+    fn test_validate_underflow() {
+        let rpn = RPN { op: vec![num(1.0), Op::Add] };
+        assert_eq!(rpn.validate(0, 0), Err(ValidateError::Underflow { at: 1 }));
+        assert!(RPN::new_checked(vec![num(1.0), Op::Add], 0, 0).is_err());
+        // 只有一个分支缺操作数也算下溢
+        let ops = vec![num(1.0), Op::JumpIfZero(2), num(1.0), Op::Goto(1), Op::Sin];
+        assert_eq!(RPN::new_checked(ops, 0, 0).err(), Some(ValidateError::Underflow { at: 4 }));
+        // CallDef 参数中的错误带上位置
+        let ops = vec![Op::CallDef(0, vec![RPN { op: vec![Op::Mul] }])];
+        assert_eq!(RPN::new_checked(ops, 0, 1).err(), Some(ValidateError::InArgument {
+            at: 0, arg: 0, error: Box::new(ValidateError::Underflow { at: 0 }),
+        }));
+    }
+
+    #[test]
+    fn test_validate_overflow_and_ranges() {
+        let mut ops: Vec<Op> = (0..=RPN::MAX_STACK_SIZE).map(|_| num(1.0)).collect();
+        ops.extend((0..RPN::MAX_STACK_SIZE).map(|_| Op::Add));
+        assert_eq!(RPN::new_checked(ops, 0, 0).err(), Some(ValidateError::Overflow { at: RPN::MAX_STACK_SIZE, depth: 33 }));
+
+        let mut ops: Vec<Op> = (0..RPN::MAX_STACK_SIZE).map(|_| num(1.0)).collect();
+        ops.extend((1..RPN::MAX_STACK_SIZE).map(|_| Op::Add));
+        assert_eq!(RPN::new_checked(ops, 0, 0).unwrap().validate(0, 0), Ok(StackInfo { max_depth: 32 }));
+
+        let rpn = RPN::new(vec![Op::LoadPara(1), Op::LoadGlobal(2), Op::Add]);
+        assert_eq!(rpn.validate(1, 3), Err(ValidateError::ParaOutOfRange { at: 0, index: 1 }));
+        assert_eq!(rpn.validate(2, 2), Err(ValidateError::GlobalOutOfRange { at: 1, index: 2 }));
+        assert_eq!(rpn.validate(2, 3), Ok(StackInfo { max_depth: 2 }));
+
+        assert_eq!(RPN { op: vec![] }.validate(0, 0), Err(ValidateError::WrongResultCount { count: 0 }));
+        assert_eq!(RPN { op: vec![num(1.0), num(2.0)] }.validate(0, 0), Err(ValidateError::WrongResultCount { count: 2 }));
+        assert_eq!(RPN { op: vec![Op::Goto(5)] }.validate(0, 0), Err(ValidateError::JumpOutOfRange { at: 0 }));
+        let ops = vec![num(1.0), Op::JumpIfZero(2), num(1.0), Op::Goto(0), num(2.0)];
+        assert!(matches!(RPN::new_checked(ops, 0, 0), Err(ValidateError::InconsistentBranches { .. })));
+    }
+
+    #[test]
+    fn test_existing_programs_validate() {
+        // 上面各测试中的程序 (含回跳与嵌套调用) 都应通过检查
+        let programs = [
+            vec![Op::LoadPara(0), num(0.0), Op::GreaterThan, Op::JumpIfZero(4),
+                 Op::LoadPara(0), Op::LoadPara(0), Op::Mul, Op::Goto(3), num(0.0), Op::LoadPara(0), Op::Sub],
+            vec![Op::Goto(2), num(1.0), Op::Goto(2), num(5.0), Op::Goto(-4), Op::Add],
+            vec![Op::LoadPara(0), Op::Sin, Op::LoadGlobal(1), Op::Mul, num(3.5), Op::Sub],
+            vec![Op::CallDef(2, vec![RPN::new(vec![Op::LoadPara(0)]), RPN::new(vec![num(1.0), Op::LoadPara(1), Op::Div])])],
+            vec![Op::LoadPara(0), Op::LoadPara(1), Op::Cross2, Op::LoadPara(0), Op::GetY, Op::Add],
+        ];
+        for ops in programs {
+            assert!(RPN::new_checked(ops, 3, 3).is_ok());
+        }
+    }
+
+    fn eval_complex(rpn: &RPN) -> Complex {
+        match rpn.eval(&[], &[]) {
+            MathData::Complex(z) => z,
+            other => panic!("expected Complex, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_complex_from_compiler() {
+        use crate::pakoo::compiler::Compiler;
+        use crate::pakoo::symbol_table::SymbolTable;
+
+        // i 是保留字：编译为常量，不产生全局依赖
+        let mut table = SymbolTable::new();
+        let result = Compiler::new("(1 + i) * (1 - i)", &mut table).compile();
+        assert!(result.dependencies.is_empty());
+        assert!(table.get_id("i").is_none());
+        let z = eval_complex(&RPN::new_checked(result.ops, 0, 0).unwrap());
+        assert!((z - Complex::new(2.0, 0.0)).len() < 1e-15);
+
+        // 数字与复数混合运算：数字提升为实复数
+        let result = Compiler::new("2 * i / (1 + i)", &mut table).compile();
+        let z = eval_complex(&RPN::new(result.ops));
+        assert!((z - Complex::new(1.0, 1.0)).len() < 1e-15);
+    }
+
+    #[test]
+    fn test_complex_ops() {
+        use std::f64::consts::PI;
+
+        // exp(iπ) ≈ -1
+        let rpn = RPN::new(vec![num(0.0), num(PI), Op::MakeComplex, Op::Exp]);
+        assert!((eval_complex(&rpn) - Complex::new(-1.0, 0.0)).len() < 1e-15);
+
+        // 3 + 4i 的各部分
+        let z = || [num(3.0), num(4.0), Op::MakeComplex];
+        let part = |op: Op| eval_num(&RPN::new(z().into_iter().chain([op]).collect()), &[]);
+        assert_eq!(part(Op::Re), 3.0);
+        assert_eq!(part(Op::Im), 4.0);
+        assert_eq!(part(Op::AbsC), 5.0);
+        assert!((part(Op::Arg) - 4.0f64.atan2(3.0)).abs() < 1e-15);
+        let conj = eval_complex(&RPN::new(z().into_iter().chain([Op::Conj]).collect()));
+        assert_eq!(conj, Complex::new(3.0, -4.0));
+
+        // 超越函数按操作数类型分派：数字仍为数字
+        assert_eq!(eval_num(&RPN::new(vec![num(4.0), Op::Sqrt]), &[]), 2.0);
+        assert!(eval_num(&RPN::new(vec![num(-1.0), Op::Sqrt]), &[]).is_nan());
+        let root = eval_complex(&RPN::new(vec![num(-4.0), num(0.0), Op::MakeComplex, Op::Sqrt]));
+        assert!((root - Complex::new(0.0, 2.0)).len() < 1e-15);
+        let w = eval_complex(&RPN::new(vec![num(0.0), num(1.0), Op::MakeComplex, Op::Sin]));
+        assert!((w - Complex::I.sin()).len() < 1e-15);
+
+        assert_eq!(RPN::new(vec![num(1.0), num(2.0), Op::MakeComplex, Op::Ln, Op::Re]).to_infix_string(), "re(ln(complex(1.0, 2.0)))");
+        assert_eq!(RPN::new(vec![Op::Push(MathData::Complex(Complex::I))]).to_infix_string(), "complex(0.0, 1.0)");
+    }
+
+    #[test]
+    fn test_complex_type_errors() {
+        // 向量与复数混合：try_eval 返回类型错误，而不是 panic
+        let rpn = RPN::new(vec![
+            num(1.0), num(2.0), Op::MakeVec2,
+            Op::Push(MathData::Complex(Complex::I)), Op::Add,
+        ]);
+        assert_eq!(rpn.try_eval(&[], &[]).unwrap_err(), TypeError::Binary { op: "+", lhs: "vec2", rhs: "complex" });
+
+        let rpn = RPN::new(vec![Op::Push(MathData::Complex(Complex::I)), num(0.0), Op::GreaterThan]);
+        assert!(matches!(rpn.try_eval(&[], &[]), Err(TypeError::Binary { op: "comparison", .. })));
+        let rpn = RPN::new(vec![Op::Push(MathData::Bool(true)), Op::Re]);
+        assert_eq!(rpn.try_eval(&[], &[]).unwrap_err(), TypeError::Unary { op: "re", operand: "bool" });
+        // 函数调用的实参中的错误同样传出
+        let env = [MathData::Fun { para_count: 1, body: std::sync::Arc::new(RPN::new(vec![Op::LoadPara(0)])) }];
+        let rpn = RPN::new(vec![Op::CallDef(0, vec![RPN::new(vec![num(1.0), num(0.0), Op::Div])])]);
+        assert_eq!(rpn.try_eval(&env, &[]).unwrap_err(), TypeError::DivideByZero);
+        assert_eq!(TypeError::DivideByZero.to_string(), "division by zero");
+        // -0.0 同样是 0
+        let rpn = RPN::new(vec![num(1.0), num(-0.0), Op::Div]);
+        assert_eq!(rpn.try_eval(&[], &[]).unwrap_err(), TypeError::DivideByZero);
+    }
+}
@@ -1,7 +1,8 @@
+use std::fmt;
 use std::sync::Arc; // <--- 1. 记得引入这个
 
 use super::env::Env;
-use super::math_data::MathData;
+use super::math_data::{MathData, TypeError};
 use super::rpn::RPN;
 
 // 行
@@ -12,6 +13,23 @@ pub(crate) enum Slice {
     Def { para_count: usize, body: RPN },
 }
 
+// 中缀形式：Var 为常量，Call 为表达式，Def 为 "(p_0, p_1) -> 函数体"
+// 形参 / 全局量 / 函数的记法与 RPN::to_infix_string 一致
+impl fmt::Display for Slice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Slice::Var { data } => write!(f, "{}", RPN::literal_string(data)),
+            Slice::Call { body } => write!(f, "{}", body.to_infix_string()),
+            Slice::Def { para_count, body } => write!(f, "({}) -> {}", param_list(*para_count), body.to_infix_string()),
+        }
+    }
+}
+
+// 形参列表 "p_0, p_1, ..."
+pub(crate) fn param_list(para_count: usize) -> String {
+    (0..para_count).map(|i| format!("p_{}", i)).collect::<Vec<_>>().join(", ")
+}
+
 impl Slice {
     pub fn clone(&self) -> Slice {
         match self {
@@ -24,21 +42,27 @@ impl Slice {
         }
     }
 
-    pub fn eval(&mut self, env_data: &Vec<MathData>) -> MathData {
-        match self {
+    pub fn eval(&self, env_data: &Vec<MathData>) -> MathData {
+        self.try_eval(env_data).unwrap_or_else(|e| panic!("类型错误: {}", e))
+    }
+
+    // 同 eval，表达式求值出错时返回错误
+    pub fn try_eval(&self, env_data: &[MathData]) -> Result<MathData, TypeError> {
+        Ok(match self {
             Slice::Var { data } => data.clone(),
             // 注意：因为 RPN::eval 签名变了，这里需要传空参数 &[]
-            Slice::Call { body } => body.eval(env_data, &[]),
+            Slice::Call { body } => body.try_eval(env_data, &[])?,
             Slice::Def { para_count, body } => {
                 // 返回函数对象
                 MathData::Fun {
                     para_count: *para_count,
                     // 修复错误：将 RPN 包装进 Arc
-                    // 这里虽然还有一次 clone，但这是在"定义函数"时发生的（只发生一次）
+                    // 这里虽然还有一次 clone，但这是在"定义函数"时发生的（每次 update 一次）
                     // 在"调用函数"时已经没有 clone 了，所以是可以接受的
-                    body: Arc::new(std::mem::take(body)),
+                    // 注意不能 take：Env 会反复 update，函数体必须保留在 slice 中
+                    body: Arc::new(body.clone()),
                 }
             }
-        }
+        })
     }
 }
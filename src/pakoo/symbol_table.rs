@@ -1,10 +1,17 @@
 // symbol_table.rs
 use std::collections::HashMap;
 
+use crate::math_forest::algebra::complex::complex::Complex;
+use super::math_data::MathData;
+
 #[derive(Default)]
 pub struct SymbolTable {
     name_to_id: HashMap<String, usize>,
     id_to_name: Vec<String>,
+    // 具名函数：全局 ID -> 参数个数 (编译器据此把 f(...) 编译为 CallDef)
+    arity: HashMap<usize, usize>,
+    // 正在编译的函数体的形参 (下标即 LoadPara 的参数位置)，优先于同名全局变量
+    params: Vec<String>,
 }
 
 impl SymbolTable {
@@ -12,8 +19,25 @@ impl SymbolTable {
         Self::default()
     }
 
+    // 保留字 (编译为常量，不能用作变量名)
+    pub fn is_reserved(name: &str) -> bool {
+        Self::reserved_constant(name).is_some()
+    }
+
+    // 保留字对应的常量
+    pub fn reserved_constant(name: &str) -> Option<MathData> {
+        match name {
+            "i" => Some(MathData::Complex(Complex::I)),
+            "pi" => Some(MathData::Num(std::f64::consts::PI)),
+            "e" => Some(MathData::Num(std::f64::consts::E)),
+            _ => None,
+        }
+    }
+
     // 获取 ID，如果不存在则创建（用于解析新变量定义或前向引用）
+    // 保留字不是变量：调用方应先用 reserved_constant 处理
     pub fn get_or_create_id(&mut self, name: &str) -> usize {
+        debug_assert!(!Self::is_reserved(name), "'{}' is a reserved identifier", name);
         if let Some(&id) = self.name_to_id.get(name) {
             id
         } else {
@@ -29,11 +53,105 @@ impl SymbolTable {
         self.name_to_id.get(name).cloned()
     }
 
-    pub fn get_name(&self, id: usize) -> Option<&str> {
-        if id < self.id_to_name.len() {
-            Some(&self.id_to_name[id])
-        } else {
-            None
+    // 登记具名函数，返回其全局 ID (已存在的名字沿用原 ID，参数个数以最新定义为准)
+    pub fn define_function(&mut self, name: &str, para_count: usize) -> usize {
+        let id = self.get_or_create_id(name);
+        self.arity.insert(id, para_count);
+        id
+    }
+
+    // 参数个数；不是函数时返回 None
+    pub fn function_arity(&self, id: usize) -> Option<usize> {
+        self.arity.get(&id).copied()
+    }
+
+    // 绑定函数体的形参 (覆盖上一次的绑定；传空切片即解除)
+    pub fn bind_params(&mut self, params: &[&str]) {
+        self.params = params.iter().map(|p| p.to_string()).collect();
+    }
+
+    pub fn param_index(&self, name: &str) -> Option<usize> {
+        self.params.iter().position(|p| p == name)
+    }
+
+    // ID -> 名字 (与 Env::name_of 同名)
+    pub fn name_of(&self, id: usize) -> Option<&str> {
+        self.id_to_name.get(id).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pakoo::compiler::Compiler;
+    use crate::pakoo::op::Op;
+
+    #[test]
+    fn test_ids_round_trip() {
+        let mut table = SymbolTable::new();
+        assert_eq!(table.name_of(0), None);
+        let names = ["x", "y", "radius", "f", "x_1"];
+        let ids: Vec<usize> = names.iter().map(|n| table.get_or_create_id(n)).collect();
+        // 按首次出现顺序连续编号
+        assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+
+        // 再次获取不新建
+        assert_eq!(table.get_or_create_id("radius"), 2);
+        assert_eq!(table.get_or_create_id("t"), names.len());
+
+        for (name, id) in names.iter().zip(&ids) {
+            assert_eq!(table.get_id(name), Some(*id));
+            assert_eq!(table.name_of(*id), Some(*name));
         }
+        assert_eq!(table.get_id("z"), None);
+        assert_eq!(table.name_of(names.len() + 1), None);
+        // 大小写敏感
+        assert_eq!(table.get_id("X"), None);
+    }
+
+    #[test]
+    fn test_reserved() {
+        assert!(SymbolTable::is_reserved("i"));
+        assert!(!SymbolTable::is_reserved("x"));
+        assert!(matches!(SymbolTable::reserved_constant("i"), Some(MathData::Complex(c)) if c == Complex::I));
+        assert!(SymbolTable::reserved_constant("pi_").is_none());
+        assert!(matches!(SymbolTable::reserved_constant("pi"), Some(MathData::Num(v)) if v == std::f64::consts::PI));
+        assert!(SymbolTable::is_reserved("e") && !SymbolTable::is_reserved("E"));
+    }
+
+    #[test]
+    fn test_functions_and_params() {
+        let mut table = SymbolTable::new();
+        let x = table.get_or_create_id("x");
+        let f = table.define_function("f", 2);
+        assert_eq!(table.function_arity(f), Some(2));
+        assert_eq!(table.function_arity(x), None);
+        // 重新定义沿用原 ID
+        assert_eq!(table.define_function("f", 1), f);
+        assert_eq!(table.function_arity(f), Some(1));
+
+        table.bind_params(&["x", "y"]);
+        assert_eq!((table.param_index("x"), table.param_index("y")), (Some(0), Some(1)));
+        // 形参不占用全局 ID
+        assert_eq!(table.get_id("y"), None);
+        table.bind_params(&[]);
+        assert_eq!(table.param_index("x"), None);
+    }
+
+    #[test]
+    fn test_shared_with_compiler() {
+        // 多次编译共用一张表：同名变量得到同一个全局 id，依赖按 id 记录
+        let mut table = SymbolTable::new();
+        let first = Compiler::new("a * b + a", &mut table).compile();
+        let (a, b) = (table.get_id("a").unwrap(), table.get_id("b").unwrap());
+        assert_eq!((a, b), (0, 1));
+        assert!(first.dependencies.contains(&a) && first.dependencies.contains(&b));
+        assert!(first.ops.iter().any(|op| matches!(op, Op::LoadGlobal(id) if *id == b)));
+
+        let second = Compiler::new("c - b", &mut table).compile();
+        assert_eq!(table.get_id("c"), Some(2));
+        assert!(second.dependencies.contains(&b) && second.dependencies.contains(&2));
+        assert_eq!(table.name_of(2), Some("c"));
+        assert_eq!(table.name_of(3), None);
     }
 }
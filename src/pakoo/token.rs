@@ -12,6 +12,12 @@ pub enum Token {
     LParen,             // (
     RParen,             // )
     Comma,              // ,
+    Greater,            // >
+    Less,               // <
+    EqualEqual,         // ==
+    Question,           // ?
+    Colon,              // :
+    Bar,                // | (绝对值 |x|，开闭由编译器按上下文区分)
     EOF,
 }
 
@@ -26,10 +32,11 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    pub fn next_token(&mut self) -> Token {
+    // 非法字符返回错误 (编译器据此报编译错误，不 panic)
+    pub fn next_token(&mut self) -> Result<Token, String> {
         self.skip_whitespace();
 
-        match self.input.peek() {
+        Ok(match self.input.peek() {
             None => Token::EOF,
             Some(&c) => match c {
                 '+' => {
@@ -64,11 +71,38 @@ impl<'a> Lexer<'a> {
                     self.input.next();
                     Token::Comma
                 }
-                '0'..='9' | '.' => self.read_number(),
+                '>' => {
+                    self.input.next();
+                    Token::Greater
+                }
+                '<' => {
+                    self.input.next();
+                    Token::Less
+                }
+                '=' => {
+                    self.input.next();
+                    if self.input.next_if_eq(&'=').is_none() {
+                        return Err("非法字符: =，相等比较请用 ==".to_string());
+                    }
+                    Token::EqualEqual
+                }
+                '?' => {
+                    self.input.next();
+                    Token::Question
+                }
+                ':' => {
+                    self.input.next();
+                    Token::Colon
+                }
+                '|' => {
+                    self.input.next();
+                    Token::Bar
+                }
+                '0'..='9' | '.' => self.read_number()?,
                 'a'..='z' | 'A'..='Z' | '_' => self.read_identifier(),
-                _ => panic!("非法字符: {}", c), // 实际项目中应返回 Result
+                _ => return Err(format!("非法字符: {}", c)),
             },
-        }
+        })
     }
 
     fn skip_whitespace(&mut self) {
@@ -81,7 +115,7 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    fn read_number(&mut self) -> Token {
+    fn read_number(&mut self) -> Result<Token, String> {
         let mut s = String::new();
         while let Some(&c) = self.input.peek() {
             if c.is_digit(10) || c == '.' {
@@ -91,7 +125,7 @@ impl<'a> Lexer<'a> {
                 break;
             }
         }
-        Token::Number(s.parse().unwrap_or(0.0))
+        s.parse().map(Token::Number).map_err(|_| format!("非法数字: {}", s))
     }
 
     fn read_identifier(&mut self) -> Token {
@@ -1,23 +1,49 @@
 #![allow(dead_code)]
 // 窗口管理
 use winit::event_loop::EventLoop;
+use std::io::{self, Write};
+use std::path::Path;
 
 // 数学库
 
 use super::super::math_forest::algebra::complex::complex::Complex;
+use super::super::math_forest::algebra::linear::matrix3x3::Matrix3x3;
 use super::super::math_forest::geometry::d3::linear::vec3::Vec3;
 
 // 平面
 use super::super::graph::d2::colors;
 use super::super::graph::d2::common::GeoObj;
+use super::super::graph::d2::conformal;
+use super::super::graph::d2::explicit::ExplicitSolver;
+use super::super::graph::d2::gradient::{Coloring, Gradient};
+use super::super::graph::d2::heatmap::ColorMap;
 use super::super::graph::d2::main::D2Plotter;
+use super::super::graph::d2::scatter::MarkerShape;
 // 三维
 use super::super::graph::d3::implicit_surface::ImplicitSurfaceSolver;
-use super::super::graph::d3::{D3Plotter, GeoObjD3, MeshData, ParametricCurveSolver};
+use super::super::graph::d3::{D3Plotter, GeoObjD3, MeshData, ParametricCurveSolver, SurfaceTopology, DEFAULT_JUMP_THRESHOLD};
+use super::super::graph::d3::parametric_curve::TubeFrame;
+use super::super::graph::d3::mesh_job::MeshJob;
+use super::super::graph::implicit_ops;
+use super::super::graph::session::Session;
+use crate::math_forest::geometry::d3::curve::curve_eval;
+use crate::math_forest::algebra::linear::matrix4x4::Matrix4x4;
+use crate::math_forest::statistics::random::RandomMaster;
+use crate::pakoo::compiler::Compiler;
+use crate::pakoo::env::Env;
+use crate::pakoo::math_data::MathData;
+use crate::pakoo::rpn::RPN;
+use crate::pakoo::slice::Slice;
+use crate::pakoo::symbol_table::SymbolTable;
 
 //
+use crate::math_forest::geometry::d2::conic::conic::Conic;
+use crate::math_forest::geometry::d2::conic::ellipse::Ellipse;
+use crate::math_forest::geometry::d2::linear::line::Line;
 use crate::math_forest::geometry::d2::linear::vec2::Vec2;
 use crate::math_forest::geometry::d2::special::hyperelliptic::Hyperelliptic;
+use crate::math_forest::geometry::d2::spline::b_spline::BSpline;
+use crate::math_forest::geometry::d2::spline::bezier::CubicBezier;
 
 
 
@@ -60,6 +86,139 @@ pub fn main_d2() {
         4.0,
     ));
 
+    // 双曲线 y = 1/x：t 范围跟随视口，缩放后始终铺满可见区域
+    let reciprocal = d2_plotter.add_object(GeoObj::new_parametric_dynamic(
+        |t| (t, 1.0 / t),
+        |view| view.x_range,
+        colors::CYAN,
+        6.0,
+    ));
+
+    // 三次贝塞尔 S 形曲线
+    let bezier = d2_plotter.add_object(GeoObj::new_bezier(
+        CubicBezier::new(
+            Vec2::new(-2.0, -1.0),
+            Vec2::new(-1.0, 1.5),
+            Vec2::new(0.0, -2.5),
+            Vec2::new(1.5, -0.5),
+        ),
+        colors::GREEN,
+        5.0,
+    ));
+
+    // clamped 三次 B 样条：前四个控制点同上面的贝塞尔曲线，曲线只经过首末控制点
+    d2_plotter.add_object(GeoObj::new_b_spline(
+        BSpline::new_clamped(vec![
+            Vec2::new(-2.0, -1.0),
+            Vec2::new(-1.0, 1.5),
+            Vec2::new(0.0, -2.5),
+            Vec2::new(1.5, -0.5),
+            Vec2::new(2.5, 1.5),
+            Vec2::new(3.5, 0.0),
+        ], 3),
+        colors::MAGENTA,
+        3.0,
+    ));
+
+    // 同一个椭圆的两个姿态：不重复写方程，只换仿射变换
+    let ellipse = |x: f64, y: f64| x * x + 4.0 * y * y - 1.0;
+    d2_plotter.add_object(
+        GeoObj::new_implicit(ellipse, colors::ORANGE, 4.0)
+            .with_transform(Matrix3x3::from_translation(2.5, 1.0)),
+    );
+    let rotated = d2_plotter.add_object(GeoObj::new_implicit(ellipse, colors::ORANGE, 4.0));
+    d2_plotter.set_transform(
+        rotated,
+        Matrix3x3::from_transform(Vec2::new(2.5, 1.0), std::f64::consts::FRAC_PI_2, Vec2::new(0.5, 0.5)),
+    );
+
+    // 一般二次方程：x^2 + xy - y^2 + 2x - 1 = 0 (双曲线)；虚圆锥曲线成为 Wipkyy，不参与求解
+    let hyperbola = d2_plotter.add_object(GeoObj::new_conic(Conic::new(1.0, 1.0, -1.0, 2.0, 0.0, -1.0), colors::CYAN, 3.0));
+    d2_plotter.add_object(GeoObj::new_conic(Conic::new(1.0, 0.0, 1.0, 0.0, 0.0, 1.0), colors::RED, 3.0));
+
+    // 利萨茹曲线按参数 t 渐变着色：蓝色为起点，红色为终点，能看出走向
+    d2_plotter.add_object(
+        GeoObj::new_parametric(
+            |t| (2.0 * (3.0 * t).sin() - 3.0, 1.5 * (2.0 * t).sin() - 2.0),
+            (0.0, 2.0 * std::f64::consts::PI),
+            colors::BLUE,
+            5.0,
+        )
+        .with_coloring(Coloring::ByParam(Gradient::rainbow())),
+    );
+
+    // 椭圆按曲率着色：长轴端点最红 (κ = a / b²)，短轴端点最蓝 (κ = b / a²)
+    let ellipse = Ellipse::new(Vec2::new(3.0, -2.0), Vec2::new(2.0, 0.5), Vec2::new(-0.3, 1.0));
+    let (a, b) = ellipse.ab();
+    println!("ellipse curvature: {:.4} ~ {:.4}", b / (a * a), a / (b * b));
+    d2_plotter.add_object(
+        GeoObj::new_parametric(
+            move |t| { let q = ellipse.index_point(t); (q.x, q.y) },
+            (0.0, 2.0 * std::f64::consts::PI),
+            colors::BLUE,
+            5.0,
+        )
+        .with_coloring(Coloring::ByCurvature(Gradient::rainbow().with_range(b / (a * a), a / (b * b)))),
+    );
+    // 它的渐屈线 (曲率中心的轨迹) 是一条星形线
+    d2_plotter.add_object(GeoObj::new_parametric(
+        move |t| { let q = ellipse.evolute_point(t); (q.x, q.y) },
+        (0.0, 2.0 * std::f64::consts::PI),
+        colors::WHITE,
+        2.0,
+    ));
+
+    // 交点标注：圆锥曲线与直线走解析解，贝塞尔曲线与 y = 1/x 走数值求根
+    let line = d2_plotter.add_object(GeoObj::new_line(Line::new(Vec2::new(0.0, -1.5), Vec2::new(1.0, 0.5)), colors::ICE_BLUE, 2.0));
+    d2_plotter.show_intersections(hyperbola, line);
+    d2_plotter.show_intersections(bezier, reciprocal);
+
+    // 逐点着色的隐函数：Cassini 卵形线 |z - 1||z + 1| = 1.1，按极角取彩虹色
+    let rainbow = Gradient::rainbow();
+    d2_plotter.add_object(GeoObj::new_implicit_colored(
+        |x, y| ((x - 1.0).powi(2) + y * y) * ((x + 1.0).powi(2) + y * y) - 1.21,
+        move |x, y| rainbow.sample(((y.atan2(x) / std::f64::consts::TAU) + 0.5) as f32),
+        4.0,
+    ));
+
+    // 等值线组：x² - y² + xy 的五条等值线作为一个对象
+    d2_plotter.add_object(GeoObj::new_level_curves(
+        |x, y| x * x - y * y + x * y,
+        &[-4.0, -2.0, 0.0, 2.0, 4.0],
+        colors::MINT,
+        2.0,
+    ).with_transform(Matrix3x3::from_translation(-6.0, 6.0)));
+
+    // 方波：竖直边的跳跃只有 0.6，调小断裂阈值，缩小视图后也不连线
+    d2_plotter.add_object(GeoObj::new_parametric(
+        |t| (t, 0.3 * t.sin().signum()),
+        (-6.0, 6.0),
+        colors::ICE_BLUE,
+        3.0,
+    ).with_jump_threshold(0.02).with_transform(Matrix3x3::from_translation(0.0, 6.0)));
+
+    // Weierstrass 函数 y = Σ 0.5^n cos(3^n πx)：只累加波长大于一个像素的项，缩放时细节随之增减
+    d2_plotter.add_object(GeoObj::new_implicit_detailed(
+        |x, y, hint| {
+            let (mut sum, mut amp, mut freq) = (0.0, 1.0, 1.0);
+            while 2.0 / freq > hint && amp > 1e-4 {
+                sum += amp * (freq * std::f64::consts::PI * x).cos();
+                amp *= 0.5;
+                freq *= 3.0;
+            }
+            y - sum
+        },
+        colors::YELLOW,
+        2.0,
+    ).with_transform(Matrix3x3::from_translation(6.0, -6.0)));
+
+    d2_plotter.add_annotation(target_point, "P(1.1, 0.8)".to_string(), colors::WHITE);
+    d2_plotter.add_annotation(
+        nearest_point,
+        format!("Q  d={:.4}", min_dist),
+        colors::YELLOW,
+    );
+
 
 
     /*
@@ -123,64 +282,207 @@ pub fn main_d2() {
     event_loop.run_app(&mut d2_plotter).unwrap();
 }
 
+// 显函数采样：均匀采样与自适应采样 (陡峭段加密) 的点数对比，平坦函数两者相同
+pub fn explicit_sampling() {
+    let solver = ExplicitSolver::new();
+    let functions = [("sin(x)", f64::sin as fn(f64) -> f64), ("tan(x)", f64::tan), ("x^(1/3)", f64::cbrt)];
+    let full = (f64::NEG_INFINITY, f64::INFINITY);
+    for (name, f) in functions {
+        let count = |lines: Vec<Vec<(f64, f64)>>| lines.iter().map(|l| l.len()).sum::<usize>();
+        let uniform = count(solver.polylines(&f, (-4.0, 4.0), full, 1.0, 1600));
+        let adaptive = count(solver.polylines_adaptive(&f, (-4.0, 4.0), full, 1.0, 1600));
+        let vertices = solver.solve(&f, (-4.0, 4.0), full, 2.0, 1.0, 1600, 800.0).len();
+        let adaptive_vertices = solver.solve_adaptive(&f, (-4.0, 4.0), full, 2.0, 1.0, 1600, 800.0).len();
+        println!("{:>8}: points {} -> {}, vertices {} -> {}", name, uniform, adaptive, vertices, adaptive_vertices);
+    }
+}
+
+// 过四点的圆锥曲线束 λA + (1 - λ)B：λ 随时间往复，曲线在两组对边直线之间扫过
+pub fn main_d2_pencil() {
+    let event_loop = EventLoop::new().unwrap();
+    let mut d2_plotter = D2Plotter::new();
+
+    let pts = [Vec2::new(-1.5, -1.0), Vec2::new(2.0, -1.5), Vec2::new(1.5, 1.5), Vec2::new(-1.0, 1.0)];
+    let (a, b) = Conic::pencil_through_four_points(pts[0], pts[1], pts[2], pts[3]);
+
+    // 两个退化成员细线常驻，当前成员粗线
+    let scene = move |lambda: f64| vec![
+        GeoObj::new_conic(a, colors::SOFT_PINK, 1.5),
+        GeoObj::new_conic(b, colors::SOFT_PINK, 1.5),
+        GeoObj::new_conic(Conic::lerp(&a, &b, lambda), colors::CYAN, 4.0),
+    ];
+    for obj in scene(0.0) {
+        d2_plotter.add_object(obj);
+    }
+    for (i, p) in pts.iter().enumerate() {
+        d2_plotter.add_annotation(*p, format!("P{}", i + 1), colors::WHITE);
+    }
+
+    // 简单的计时循环：后台线程按时间算 λ 并发送新的对象列表，窗口关闭后线程退出
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let start = std::time::Instant::now();
+        loop {
+            let lambda = 0.5 - 0.5 * (start.elapsed().as_secs_f64() * 0.8).cos();
+            if tx.send(scene(lambda)).is_err() { break; }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    });
+    d2_plotter.set_object_source(rx);
+
+    event_loop.run_app(&mut d2_plotter).unwrap();
+}
+
+// 示波器：模拟线程以 200 Hz 推送两路信号，视口自动跟随最新采样 (拖动后暂停，双击复位后恢复)
+pub fn main_d2_scope() {
+    let event_loop = EventLoop::new().unwrap();
+    let mut d2_plotter = D2Plotter::new();
+
+    let sine = d2_plotter.create_channel(2000);
+    let noisy = d2_plotter.create_channel(2000);
+    d2_plotter.add_object(GeoObj::new_series(sine, colors::CYAN, 2.0));
+    d2_plotter.add_object(GeoObj::new_series(noisy, colors::ORANGE, 1.5));
+    d2_plotter.push_sample(sine, 0.0, 0.0);
+
+    let (sine, noisy) = (d2_plotter.channel_handle(sine).unwrap(), d2_plotter.channel_handle(noisy).unwrap());
+    std::thread::spawn(move || {
+        let start = std::time::Instant::now();
+        loop {
+            let t = start.elapsed().as_secs_f64();
+            sine.push(t, (3.0 * t).sin());
+            noisy.push(t, 0.5 * (7.0 * t).sin() * (0.5 * t).cos() - 1.0);
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    });
+
+    event_loop.run_app(&mut d2_plotter).unwrap();
+}
+
+// 二维正态分布的 500 个采样点，叠加 1σ / 2σ 等概率椭圆
+// (x, y) = L·(z1, z2)，L 为协方差矩阵的 Cholesky 因子；L 的两列是 1σ 椭圆的一对共轭半径
+pub fn main_d2_scatter() {
+    let event_loop = EventLoop::new().unwrap();
+    let mut d2_plotter = D2Plotter::new();
+
+    let (sx, sy, rho) = (1.2, 0.7, 0.6);
+    let center = Vec2::new(0.5, -0.3);
+    let u = Vec2::new(sx, rho * sy);
+    let v = Vec2::new(0.0, (1.0 - rho * rho).sqrt() * sy);
+
+    let normal = RandomMaster::normal_unit();
+    let points: Vec<Vec2> = (0..500).map(|_| center + u * normal.compute() + v * normal.compute()).collect();
+    // 落在 1σ 椭圆内的点另用颜色标出 (马氏距离 = |(z1, z2)|)
+    let inv = Matrix3x3::new(u.x, v.x, 0.0, u.y, v.y, 0.0, 0.0, 0.0, 1.0).inverse().unwrap();
+    let colors = points.iter()
+        .map(|p| if inv.transform_vector2(*p - center).len() <= 1.0 { colors::ORANGE } else { colors::CYAN })
+        .collect();
+    d2_plotter.add_object(GeoObj::new_points(points, colors::CYAN, 5.0).with_point_colors(colors));
+
+    for (k, color) in [(1.0, colors::ORANGE), (2.0, colors::WHITE)] {
+        let ellipse = Ellipse::new(center, u * k, v * k);
+        d2_plotter.add_object(GeoObj::new_parametric(
+            move |t| { let q = ellipse.index_point(t); (q.x, q.y) },
+            (0.0, 2.0 * std::f64::consts::PI),
+            color,
+            2.0,
+        ));
+    }
+    // 均值处画一个叉号
+    d2_plotter.add_object(GeoObj::new_points(vec![center], colors::RED, 14.0).with_marker_shape(MarkerShape::Cross));
+
+    event_loop.run_app(&mut d2_plotter).unwrap();
+}
+
+// 热力图：x² + y² - 1 的整个标量场，零等值线 (单位圆) 叠在上面
+// 左边 viridis 按可见范围自动归一化；右边发散色表以 0 为白色，固定区间，圆内为蓝、圆外为红
+pub fn main_d2_heatmap() {
+    let event_loop = EventLoop::new().unwrap();
+    let mut d2_plotter = D2Plotter::new();
+
+    let f = |x: f64, y: f64| x * x + y * y - 1.0;
+    let left = Matrix3x3::from_translation(-1.5, 0.0);
+    let right = Matrix3x3::from_translation(1.5, 0.0);
+    // 两张热力图各占半边：局部坐标下把场限制在世界坐标 x < 0 / x > 0 的一侧 (另一侧为 NaN，不画)
+    d2_plotter.add_object(GeoObj::new_heatmap(move |x, y| if x < 1.5 { f(x, y) } else { f64::NAN }, ColorMap::Viridis).with_transform(left));
+    d2_plotter.add_object(
+        GeoObj::new_heatmap(move |x, y| if x > -1.5 { f(x, y) } else { f64::NAN }, ColorMap::Diverging { center: 0.0 })
+            .with_heatmap_range(-1.0, 3.0)
+            .with_heatmap_resolution(0.25)
+            .with_transform(right),
+    );
+    d2_plotter.add_object(GeoObj::new_implicit(f, colors::WHITE, 2.0).with_transform(left));
+    d2_plotter.add_object(GeoObj::new_implicit(f, colors::BLACK, 2.0).with_transform(right));
+
+    event_loop.run_app(&mut d2_plotter).unwrap();
+}
+
+// 共形网格：源矩形 [-2, 2]² 的直角网格经 w = f(z) 映射，竖线 / 横线两族分色
+// preset 为 conformal::PRESETS 中的名字 (z^2, 1/z, exp(z), ln(z))，未知时取第一个
+pub fn main_d2_conformal(preset: &str) {
+    let event_loop = EventLoop::new().unwrap();
+    let mut d2_plotter = D2Plotter::new();
+
+    let (name, f) = conformal::PRESETS.iter().find(|(n, _)| *n == preset).copied().unwrap_or(conformal::PRESETS[0]);
+    let source = (Vec2::new(-2.0, -2.0), Vec2::new(2.0, 2.0));
+    for obj in GeoObj::new_conformal_grid(f, source, 0.25, [colors::ORANGE, colors::CYAN], 1.5) {
+        d2_plotter.add_object(obj);
+    }
+    d2_plotter.add_annotation(Vec2::new(-3.5, 3.5), format!("w = {}", name), colors::WHITE);
+
+    event_loop.run_app(&mut d2_plotter).unwrap();
+}
+
+// 动画：4 秒一个周期，椭圆与圆之间来回变形，半径端点沿曲线转一圈
+pub fn main_d2_animated() {
+    let event_loop = EventLoop::new().unwrap();
+    let mut d2_plotter = D2Plotter::new();
+
+    d2_plotter.animate(4.0, |t| {
+        let tau = 2.0 * std::f64::consts::PI;
+        let b = 1.0 - 0.5 * (tau * t).cos().abs();
+        let ellipse = Ellipse::new(Vec2::new(0.0, 0.0), Vec2::new(1.5, 0.0), Vec2::new(0.0, 1.5 * b));
+        let tip = ellipse.index_point(tau * t);
+        vec![
+            GeoObj::new_parametric(move |s| { let q = ellipse.index_point(s); (q.x, q.y) }, (0.0, tau), colors::CYAN, 3.0),
+            GeoObj::new_parametric(move |s| (tip.x * s, tip.y * s), (0.0, 1.0), colors::WHITE, 1.0),
+            GeoObj::new_points(vec![tip], colors::ORANGE, 10.0),
+        ]
+    });
+
+    event_loop.run_app(&mut d2_plotter).unwrap();
+}
+
 //
-pub fn main_d3() {
+// session: 恢复上次的相机视角，关闭窗口时保存 (--session <path> --d3)
+pub fn main_d3(session: Option<&Path>) {
     let event_loop = EventLoop::new().unwrap();
     let mut d3_plotter = D3Plotter::new();
+    if let Some(path) = session {
+        if let Some(camera) = Session::load_or_default(path).camera {
+            d3_plotter.restore_view(camera);
+        }
+        d3_plotter.set_session_path(path.to_path_buf());
+    }
 
     /*
     // 蓝色游泳圈
-    let torus_mesh = MeshData::new_parametric_surface(
-        |u, v| {
-            let r_major = 3.0;
-            let r_minor = 1.2;
-            Vec3::new(
-                (r_major + r_minor * v.cos()) * u.cos(),
-                (r_major + r_minor * v.cos()) * u.sin(),
-                r_minor * v.sin(),
-            ) + Vec3::new(0.0, -8.0, 0.0)
-        },
-        (0.0, std::f64::consts::TAU),
-        (0.0, std::f64::consts::TAU),
-        60,
-        30,
-    );
+    let torus_mesh = MeshData::new_torus(Vec3::new(0.0, -8.0, 0.0), 3.0, 1.2, 60, 30).unwrap();
     d3_plotter.add_object(GeoObjD3::new_surface(torus_mesh, colors::BLUE)); // 蓝色
 
     // 红色球面
-    let sphere_mesh = MeshData::new_parametric_surface(
-        |u, v| {
-            let r = 2.0;
-            Vec3::new(r * v.sin() * u.cos(), r * v.sin() * u.sin(), r * v.cos())
-                + Vec3::new(5.0, 0.0, 0.0)
-        },
-        (0.0, std::f64::consts::TAU),
-        (0.0, std::f64::consts::PI),
-        40,
-        40,
-    );
+    let sphere_mesh = MeshData::new_sphere(Vec3::new(5.0, 0.0, 0.0), 2.0, 40).unwrap();
     d3_plotter.add_object(GeoObjD3::new_surface(sphere_mesh, colors::RED));
 
-
     // 线框球体
-    let spiral_mesh = MeshData::new_parametric_surface(
-        |u, v| {
-            let r = 2.0;
-            // 偏移球心到 (5,0,0)
-            Vec3::new(r * v.sin() * u.cos(), r * v.sin() * u.sin(), r * v.cos())
-                + Vec3::new(0.0, 0.0, 5.0)
-        },
-        (0.0, std::f64::consts::TAU),
-        (0.0, std::f64::consts::PI),
-        40,
-        40,
-    );
+    let spiral_mesh = MeshData::new_sphere(Vec3::new(0.0, 0.0, 5.0), 2.0, 40).unwrap();
     d3_plotter.add_object(GeoObjD3 {
         mesh: spiral_mesh,
         color: colors::GREEN,
         topology: wgpu::PrimitiveTopology::LineList, // 线框模式
         use_lighting: false,
         is_transparent: false,
+        instances: None,
+        instance_colors: None,
     });
 
     // 绿色螺旋
@@ -193,24 +495,13 @@ pub fn main_d3() {
     );
     d3_plotter.add_object(GeoObjD3::new_surface(helix_curve, colors::GREEN));
 
-    // 扭结曲线 (Knot) 黄色管子
-    let knot_curve = ParametricCurveSolver::solve(
-        |t| {
-            let r = 1.0;
-            let x = r * (t.cos() + 2.0 * (2.0 * t).cos());
-            let y = r * (t.sin() - 2.0 * (2.0 * t).sin());
-            let z = r * 2.0 * (3.0 * t).sin();
-            Vec3::new(x, y, z) + Vec3::new(-8.0, 0.0, 0.0)
-        },
-        (0.0, 2.0 * std::f64::consts::PI),
-        0.3,
-        16,
-        300,
-    );
+    // (2, 3) 环面结 (三叶结) 黄色管子
+    let knot_curve = ParametricCurveSolver::solve_knot(2, 3, 2.0, 1.0, 0.3, 16, 300)
+        .translated(Vec3::new(-8.0, 0.0, 0.0));
     d3_plotter.add_object(GeoObjD3::new_surface(knot_curve, colors::YELLOW));
 
     // x^{2}+y^{2}+z^{2}+\sin4x+\sin4y+\sin4z=a
-    let gyroid_mesh = ImplicitSurfaceSolver::solve(
+    let gyroid_mesh = ImplicitSurfaceSolver::solve_adaptive(
         &|x, y, z| {
             x * x + y * y + z * z + (4.0 * x).sin() + (4.0 * y).sin() + (4.0 * z).sin() - 1.7
         },
@@ -221,7 +512,7 @@ pub fn main_d3() {
     );
     d3_plotter.add_object(GeoObjD3::new_surface(gyroid_mesh, colors::PURPLE));
 
-    let imp_2 = ImplicitSurfaceSolver::solve(
+    let imp_2 = ImplicitSurfaceSolver::solve_adaptive(
         &|x_, y_, z_| {
             let x = x_ - 5.0;
             let y = y_ - 5.0;
@@ -251,6 +542,34 @@ pub fn main_d3() {
     d3_plotter.add_object(GeoObjD3::new_surface(gyroid_mesh, colors::RED));
     */
 
+    // 三叶结：按曲率分段着色 (红色弯得最厉害)
+    let trefoil = |t: f64| {
+        Vec3::new(t.cos() + 2.0 * (2.0 * t).cos(), t.sin() - 2.0 * (2.0 * t).sin(), 2.0 * (3.0 * t).sin())
+            + Vec3::new(-8.0, 0.0, 0.0)
+    };
+    let frames = curve_eval::sample_range(&trefoil, (0.0, std::f64::consts::TAU), 360);
+    let (k_min, k_max) = frames.iter().fold((f64::INFINITY, 0.0f64), |(lo, hi), d| (lo.min(d.curvature), hi.max(d.curvature)));
+    println!("trefoil curvature: {:.4} ~ {:.4}, length = {:.4}", k_min, k_max,
+             curve_eval::arc_length(&trefoil, (0.0, std::f64::consts::TAU), 360));
+    let rainbow = Gradient::rainbow();
+    // 分段用 Frenet 标架 (三叶结处处 κ > 0)，相邻两段共享端点，截面在接缝处一致
+    for start in (0..frames.len() - 1).step_by(15) {
+        let piece = &frames[start..(start + 16).min(frames.len())];
+        let k_mean = piece.iter().map(|d| d.curvature).sum::<f64>() / piece.len() as f64;
+        let color = rainbow.sample(((k_mean - k_min) / (k_max - k_min).max(1e-9)) as f32);
+        let tube = ParametricCurveSolver::solve_with_frames(piece, 0.3, 16, TubeFrame::Frenet);
+        d3_plotter.add_object(GeoObjD3::new_surface(tube, color));
+    }
+
+    // 球与平移后的环面光滑融合 (高分辨率，后台生成)
+    let ball = |x: f64, y: f64, z: f64| (x * x + y * y + z * z).sqrt() - 1.5;
+    let ring = |x: f64, y: f64, z: f64| ((x * x + y * y).sqrt() - 2.0).hypot(z) - 0.5;
+    let blob = implicit_ops::translate(
+        implicit_ops::smooth_union(ball, implicit_ops::translate(ring, Vec3::new(1.5, 0.0, 0.0)), 0.6),
+        Vec3::new(0.0, 8.0, 0.0),
+    );
+    d3_plotter.add_object_async(MeshJob::implicit(blob, (-3.0, 5.0), (4.0, 12.0), (-2.0, 2.0), 128, colors::ORANGE));
+
     let riemann_surface_mesh = MeshData::new_parametric_surface(
         |u, v| {
             let r = u;
@@ -272,8 +591,127 @@ pub fn main_d3() {
     event_loop.run_app(&mut d3_plotter).unwrap();
 }
 
+// 三维正态分布的 1000 个采样点：同一个小球网格实例化绘制，按到原点的距离着色
+pub fn main_d3_instanced() {
+    let event_loop = EventLoop::new().unwrap();
+    let mut d3_plotter = D3Plotter::new();
+
+    let normal = RandomMaster::normal(0.0, 3.0);
+    let points: Vec<Vec3> = (0..1000).map(|_| Vec3::new(normal.compute(), normal.compute(), normal.compute())).collect();
+    let rainbow = Gradient::rainbow();
+    let colors = points.iter().map(|p| rainbow.sample((p.len() / 9.0).min(1.0) as f32)).collect();
+
+    let ball = MeshData::new_sphere(Vec3::ZERO, 0.15, 12).unwrap();
+    let id = d3_plotter.add_object(GeoObjD3::new_instanced(ball, colors::BLUE, Vec::new()).with_instance_colors(colors));
+    // 实例变换可在添加后整体替换 (窗口运行时同样适用)
+    let transforms: Vec<Matrix4x4> = points.iter().map(|p| Matrix4x4::from_translation(*p)).collect();
+    d3_plotter.set_instance_transforms(id, &transforms);
+
+    event_loop.run_app(&mut d3_plotter).unwrap();
+}
+
+// 模型矩阵动画：圆环从原点转半圈并平移到 (4, 0, 0)，中间保持刚体 (不会被压扁)
+pub fn main_d3_transform_animation() {
+    let event_loop = EventLoop::new().unwrap();
+    let mut d3_plotter = D3Plotter::new();
+
+    let torus = MeshData::new_torus(Vec3::ZERO, 1.5, 0.4, 64, 24).unwrap();
+    let id = d3_plotter.add_object(GeoObjD3::new_surface(torus, colors::ORANGE));
+    // 相机对准运动路径的中点
+    d3_plotter.look_at(Vec3::new(2.0, 0.0, 0.0));
+    d3_plotter.set_camera_radius(12.0);
+    let target = Matrix4x4::from_scale_rotation_translation(Vec3::ONE, Vec3::new(1.0, 1.0, 0.0), std::f64::consts::PI, Vec3::new(4.0, 0.0, 0.0));
+    d3_plotter.animate_model_matrix(id, target, 3.0);
+
+    event_loop.run_app(&mut d3_plotter).unwrap();
+}
+
+// 曲面叠加线：环面上 12 条 iso-u、8 条 iso-v 等参线 + 5 条 z 等高线
+pub fn main_d3_isolines() {
+    let event_loop = EventLoop::new().unwrap();
+    let mut d3_plotter = D3Plotter::new();
+
+    let torus = |u: f64, v: f64| {
+        let rho = 3.0 + 1.2 * v.cos();
+        Vec3::new(rho * u.cos(), rho * u.sin(), 1.2 * v.sin())
+    };
+    let range = (0.0, std::f64::consts::TAU);
+    let topology = SurfaceTopology { wrap_u: true, wrap_v: true, ..Default::default() };
+    let mesh = MeshData::new_parametric_surface_with(torus, range, range, 96, 48, topology);
+    let contours = mesh.extract_z_contours(&[-0.9, -0.45, 0.0, 0.45, 0.9]);
+    let iso = MeshData::new_iso_curves(torus, range, range, 12, 8, 256, topology, DEFAULT_JUMP_THRESHOLD);
+    d3_plotter.frame_aabb(mesh.compute_aabb());
+
+    d3_plotter.add_object(GeoObjD3::new_surface(mesh, colors::ICE_BLUE));
+    d3_plotter.add_object(GeoObjD3::new_overlay(iso, colors::DARK_GRAY, 0.01));
+    d3_plotter.add_object(GeoObjD3::new_overlay(contours, colors::RED, 0.01));
+
+    event_loop.run_app(&mut d3_plotter).unwrap();
+}
+
+// 文本 REPL：逐行输入
+//   f(x, y) = body   定义具名函数
+//   a = expr         定义全局量
+//   expr             求值并打印
+// 每次添加前 Env::sync_with 符号表，ID 即 Env 的下标 (未定义的名字先占一个空位)；空行退出
+pub fn pakoo_repl() {
+    let mut table = SymbolTable::new();
+    let mut env = Env::new();
+    loop {
+        print!("pakoo> ");
+        io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+            break;
+        }
+        match repl_line(&mut table, &mut env, line.trim()) {
+            Ok(Some(value)) => println!("{:?}", value),
+            Ok(None) => {}
+            Err(e) => println!("error: {}", e),
+        }
+    }
+}
+
+// 处理一行；定义返回 None，表达式返回求值结果
+fn repl_line(table: &mut SymbolTable, env: &mut Env, line: &str) -> Result<Option<MathData>, String> {
+    // 赋值号：第一个不属于 "==" 的 '='
+    let bytes = line.as_bytes();
+    let eq = (0..bytes.len()).find(|&i| {
+        bytes[i] == b'=' && bytes.get(i + 1) != Some(&b'=') && (i == 0 || bytes[i - 1] != b'=')
+    });
+    let Some(eq) = eq else {
+        let ops = Compiler::new(line, table).try_compile()?.ops;
+        env.sync_with(table);
+        env.try_update().map_err(|e| e.to_string())?;
+        let rpn = RPN::new_checked(ops, 0, env.data.len()).map_err(|e| e.to_string())?;
+        return rpn.try_eval(&env.data, &[]).map(Some).map_err(|e| e.to_string());
+    };
+
+    let (lhs, body) = (line[..eq].trim(), line[eq + 1..].trim());
+    match lhs.split_once('(') {
+        Some((name, params)) => {
+            let params: Vec<&str> = params.trim_end_matches(')').split(',').map(str::trim).filter(|p| !p.is_empty()).collect();
+            let (id, result) = Compiler::new(body, table).try_compile_function_def(name.trim(), &params)?;
+            env.sync_with(table);
+            env.add_named_slice(name.trim(), Slice::Def { para_count: params.len(), body: RPN::new(result.ops) });
+            println!("{} = g_{}", name.trim(), id);
+        }
+        None => {
+            if lhs.is_empty() || SymbolTable::is_reserved(lhs) {
+                return Err(format!("cannot assign to '{}'", lhs));
+            }
+            let result = Compiler::new(body, table).try_compile()?;
+            table.get_or_create_id(lhs);
+            env.sync_with(table);
+            env.add_named_slice(lhs, Slice::Call { body: RPN::new(result.ops) });
+        }
+    }
+    env.try_update().map_err(|e| e.to_string())?;
+    Ok(None)
+}
+
 //
 fn run_test() {
     // main_d2();
-    main_d3();
+    main_d3(None);
 }